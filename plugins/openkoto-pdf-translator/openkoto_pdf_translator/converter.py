@@ -154,6 +154,8 @@ class TranslateConverter(PDFConverterEx):
         self.noto_name = noto_name
         self.noto = noto
         self.translator: BaseTranslator = None
+        # 原文/译文段落对照（为 None 时不记录），供 OpenKoto 导入为分段学习文章
+        self.pairs: list[dict] = None
         # e.g. "ollama:gemma2:9b" -> ["ollama", "gemma2:9b"]
         param = service.split(":", 1)
         service_name = param[0]
@@ -363,6 +365,16 @@ class TranslateConverter(PDFConverterEx):
         ) as executor:
             news = list(executor.map(worker, sstk))
 
+        if self.pairs is not None:
+            for s, new in zip(sstk, news):
+                source = re.sub(r"\{v\d+\}", "", s).strip()  # 去掉公式占位符
+                if source:
+                    self.pairs.append({
+                        "page": ltpage.pageid,
+                        "source": source,
+                        "translation": re.sub(r"\{v\d+\}", "", new).strip(),
+                    })
+
         ############################################################
         # C. 新文档排版
         def raw_string(fcur: str, cstk: str):  # 编码字符串
@@ -2,6 +2,7 @@
 
 import asyncio
 import io
+import json
 import os
 import re
 import sys
@@ -85,6 +86,7 @@ def translate_patch(
     envs: Dict = None,
     prompt: Template = None,
     ignore_cache: bool = False,
+    pairs: Optional[list] = None,
     **kwarg: Any,
 ) -> None:
     rsrcmgr = PDFResourceManager()
@@ -106,6 +108,7 @@ def translate_patch(
     )
 
     assert device is not None
+    device.pairs = pairs
     obj_patch = {}
     interpreter = PDFPageInterpreterEx(rsrcmgr, device, obj_patch)
     if pages:
@@ -182,6 +185,7 @@ def translate_stream(
     prompt: Template = None,
     skip_subset_fonts: bool = False,
     ignore_cache: bool = False,
+    pairs: Optional[list] = None,
     **kwarg: Any,
 ):
     font_list = [("tiro", None)]
@@ -380,12 +384,21 @@ def translate(
         except Exception as e:
             logger.warning(f"Failed to clean temp file {file_path}", exc_info=True)
 
+        pairs = []
         s_mono, s_dual = translate_stream(
             s_raw,
             **locals(),
         )
         file_mono = Path(output) / f"{filename}-mono.pdf"
         file_dual = Path(output) / f"{filename}-dual.pdf"
+        # 段落对照数据，OpenKoto 据此把译文回导为可分段学习的文章
+        file_pairs = Path(output) / f"{filename}-pairs.json"
+        with open(file_pairs, "w", encoding="utf-8") as f:
+            json.dump(
+                {"lang_in": lang_in, "lang_out": lang_out, "pairs": pairs},
+                f,
+                ensure_ascii=False,
+            )
         doc_mono = open(file_mono, "wb")
         doc_dual = open(file_dual, "wb")
         doc_mono.write(s_mono)
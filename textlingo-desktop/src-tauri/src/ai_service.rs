@@ -1,6 +1,12 @@
+use crate::error::AppError;
+use crate::glossary::glossary_prompt;
+use crate::i18n::tr;
+use crate::jobs::{parse_retry_after, rate_limit_error};
+use crate::language_detect::language_name;
+use crate::prompt_templates::{phonetic_style, reading_style, render, template, PromptKind};
 use crate::types::{
-    AnalysisRequest, AnalysisResponse, AnalysisType, ChatRequest, ChatResponse, TranslationRequest,
-    TranslationResponse,
+    AiTask, AnalysisRequest, AnalysisResponse, AnalysisType, AppConfig, ChatRequest, ChatResponse,
+    GlossaryEntry, TranslationQuality, TranslationRequest, TranslationResponse,
 };
 use futures::StreamExt;
 use regex::Regex;
@@ -12,6 +18,12 @@ const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions"
 const DEEPSEEK_API_URL: &str = "https://api.deepseek.com/v1/chat/completions";
 const SILICONFLOW_API_URL: &str = "https://api.siliconflow.cn/v1/chat/completions";
 const API_302AI_URL: &str = "https://api.302.ai/v1/chat/completions";
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Anthropic messages API requires max_tokens on every request
+const ANTHROPIC_MAX_TOKENS: u32 = 8192;
+/// 生成标题时只发送正文开头这么多字符
+const TITLE_EXCERPT_CHARS: usize = 2000;
 
 pub struct AIService {
     client: Client,
@@ -56,6 +68,10 @@ impl AIService {
     }
 
     fn get_api_url(&self) -> String {
+        if self.is_anthropic_provider() {
+            return self.get_anthropic_api_url();
+        }
+
         // If custom base_url is provided, use it (append /chat/completions if needed)
         if let Some(ref url) = self.base_url {
             let trimmed = url.trim_end_matches('/');
@@ -87,11 +103,63 @@ impl AIService {
         }
     }
 
+    /// 当前使用的模型名称
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// 服务商标识，如 "openrouter"
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// 检查 API 端点是否可达（任何 HTTP 响应都视为可达），返回状态码
+    pub async fn check_reachable(&self) -> Result<u16, String> {
+        let response = self
+            .client
+            .get(self.get_api_url())
+            .timeout(std::time::Duration::from_secs(8))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        Ok(response.status().as_u16())
+    }
+
+    /// Anthropic messages endpoint; custom base_url may be given with or without /v1
+    fn get_anthropic_api_url(&self) -> String {
+        match self
+            .base_url
+            .as_deref()
+            .map(|url| url.trim_end_matches('/'))
+        {
+            Some(url) if url.ends_with("/messages") => url.to_string(),
+            Some(url) if url.ends_with("/v1") => format!("{}/messages", url),
+            Some(url) if !url.is_empty() => format!("{}/v1/messages", url),
+            _ => ANTHROPIC_API_URL.to_string(),
+        }
+    }
+
     /// 检查是否为 Google 类型的 provider（需要使用 X-goog-api-key 认证）
     fn is_google_provider(&self) -> bool {
         self.provider == "google" || self.provider == "google-ai-studio"
     }
 
+    /// 检查是否为 Anthropic (Claude) provider（使用 messages API 格式）
+    fn is_anthropic_provider(&self) -> bool {
+        self.provider == "anthropic"
+    }
+
+    /// 复制一份独立的服务实例，供命令在释放缓存锁后使用
+    fn detached(&self) -> AIService {
+        AIService {
+            client: Client::new(),
+            api_key: self.api_key.clone(),
+            provider: self.provider.clone(),
+            model: self.model.clone(),
+            base_url: self.base_url.clone(),
+        }
+    }
+
     async fn make_request(
         &self,
         messages: Vec<Value>,
@@ -135,11 +203,7 @@ impl AIService {
             .map_err(|e| format!("Failed to send request: {}", e))?;
 
         if !response.status().is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("API error: {}", error_text));
+            return Err(response_error("API", response).await);
         }
 
         let response_json: Value = response
@@ -176,11 +240,7 @@ impl AIService {
             .map_err(|e| format!("Failed to send request: {}", e))?;
 
         if !response.status().is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Google API error: {}", error_text));
+            return Err(response_error("Google API", response).await);
         }
 
         let response_json: Value = response
@@ -195,15 +255,78 @@ impl AIService {
             .ok_or_else(|| "No content in response".to_string())
     }
 
+    async fn make_anthropic_request(
+        &self,
+        system: Option<String>,
+        messages: Vec<Value>,
+        temperature: Option<f32>,
+    ) -> Result<String, String> {
+        let mut request_body = json!({
+            "model": self.model,
+            "max_tokens": ANTHROPIC_MAX_TOKENS,
+            "messages": messages,
+            "temperature": temperature.unwrap_or(0.7)
+        });
+
+        // Anthropic takes the system prompt as a top-level field, not a message
+        if let Some(system) = system.filter(|s| !s.is_empty()) {
+            if let Some(obj) = request_body.as_object_mut() {
+                obj.insert("system".to_string(), json!(system));
+            }
+        }
+
+        let response = self
+            .client
+            .post(self.get_api_url())
+            .header("Content-Type", "application/json")
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(response_error("Anthropic API", response).await);
+        }
+
+        let response_json: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        // Anthropic response structure: { content: [ { type: "text", text: "..." }, ... ] }
+        let text: String = response_json["content"]
+            .as_array()
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter(|block| block["type"] == "text")
+                    .filter_map(|block| block["text"].as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if text.is_empty() {
+            return Err("No content in response".to_string());
+        }
+        Ok(text)
+    }
+
     pub async fn translate(
         &self,
         request: TranslationRequest,
+        glossary: &[&GlossaryEntry],
     ) -> Result<TranslationResponse, String> {
-        let system_prompt = format!(
-            "You are a professional translator. Translate the following text to {}. \
-            Preserve the original meaning and tone. Only return the translated text without any explanations.",
-            request.target_language
+        let mut system_prompt = render(
+            &template(PromptKind::Translate),
+            &[("target_language", &request.target_language)],
         );
+        let glossary_section = glossary_prompt(glossary);
+        if !glossary_section.is_empty() {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&glossary_section);
+        }
 
         let translated_text = if self.is_google_provider() {
             // 使用 Google API 格式
@@ -212,6 +335,10 @@ impl AIService {
                 "parts": [{"text": format!("{}\n\n{}", system_prompt, request.text)}]
             })];
             self.make_google_request(contents, Some(0.3)).await?
+        } else if self.is_anthropic_provider() {
+            let messages = vec![json!({"role": "user", "content": request.text.clone()})];
+            self.make_anthropic_request(Some(system_prompt), messages, Some(0.3))
+                .await?
         } else {
             let messages = vec![
                 json!({"role": "system", "content": system_prompt}),
@@ -232,23 +359,99 @@ impl AIService {
     pub async fn batch_translate(
         &self,
         items: Vec<(String, String)>, // Vec<(id, text)>
+        source_language: Option<&str>,
         target_language: &str,
+        glossary: &[&GlossaryEntry],
+        quality: TranslationQuality,
     ) -> Result<Vec<(String, String)>, String> {
         if items.is_empty() {
             return Ok(vec![]);
         }
 
-        // 构建批量翻译提示词
-        let mut prompt = format!(
-            "将以下编号的文本翻译成{}。严格按照JSON数组格式返回，每项包含id和translation字段。\n\n",
-            target_language
-        );
+        // 构建批量翻译提示词（快速模式使用更短的指令）
+        let kind = match quality {
+            TranslationQuality::Fast => PromptKind::BatchTranslateFast,
+            TranslationQuality::Standard | TranslationQuality::Thorough => {
+                PromptKind::BatchTranslate
+            }
+        };
+        let mut prompt = render(&template(kind), &[("target_language", target_language)]);
+        // 已知原文语言时告诉模型，避免把夹杂的外语词或汉字误判语言
+        if let Some(source_language) = source_language {
+            prompt.push_str(&format!("\n原文语言：{}", language_name(source_language)));
+        }
+        prompt.push_str("\n\n");
+        let glossary_section = glossary_prompt(glossary);
+        if !glossary_section.is_empty() {
+            prompt.push_str(&glossary_section);
+            prompt.push('\n');
+        }
         prompt.push_str("待翻译文本：\n");
         for (id, text) in &items {
             prompt.push_str(&format!("[{}] {}\n", id, text));
         }
+
+        self.request_json_array(prompt, "translation").await
+    }
+
+    /// 精翻模式的第二轮：对照原文审校初译，改写生硬或错误的译文
+    /// items: Vec<(id, 原文, 初译)>，返回 Vec<(id, 审校后译文)>
+    pub async fn review_translations(
+        &self,
+        items: Vec<(String, String, String)>,
+        target_language: &str,
+        glossary: &[&GlossaryEntry],
+    ) -> Result<Vec<(String, String)>, String> {
+        if items.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut prompt = render(
+            &template(PromptKind::TranslationReview),
+            &[("target_language", target_language)],
+        );
+        prompt.push_str("\n\n");
+        let glossary_section = glossary_prompt(glossary);
+        if !glossary_section.is_empty() {
+            prompt.push_str(&glossary_section);
+            prompt.push('\n');
+        }
+        prompt.push_str("待审校文本：\n");
+        for (id, source, draft) in &items {
+            prompt.push_str(&format!("[{}] 原文：{}\n初译：{}\n", id, source, draft));
+        }
+
+        self.request_json_array(prompt, "translation").await
+    }
+
+    /// 发送批量翻译类请求，解析 [{"id", "translation"}] 格式的返回
+    async fn request_json_array(
+        &self,
+        prompt: String,
+        field: &str,
+    ) -> Result<Vec<(String, String)>, String> {
+        let example = format!(r#"[{{"id": "xxx", "{}": "结果"}}, ...]"#, field);
+        let mut results = Vec::new();
+        for item in self.request_json_items(prompt, &example).await? {
+            if let (Some(id), Some(translation)) = (
+                item.get("id").and_then(|v| v.as_str()),
+                item.get(field).and_then(|v| v.as_str()),
+            ) {
+                results.push((id.to_string(), translation.to_string()));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// 发送要求返回 JSON 数组的请求，example 为附在提示词末尾的返回格式示例
+    async fn request_json_items(
+        &self,
+        mut prompt: String,
+        example: &str,
+    ) -> Result<Vec<Value>, String> {
         prompt.push_str("\n返回格式示例：\n");
-        prompt.push_str(r#"[{"id": "xxx", "translation": "翻译结果"}, ...]"#);
+        prompt.push_str(example);
 
         let response_text = if self.is_google_provider() {
             let contents = vec![json!({
@@ -256,6 +459,14 @@ impl AIService {
                 "parts": [{"text": prompt}]
             })];
             self.make_google_request(contents, Some(0.3)).await?
+        } else if self.is_anthropic_provider() {
+            let messages = vec![json!({"role": "user", "content": prompt})];
+            self.make_anthropic_request(
+                Some("你是专业翻译助手，将文本翻译并返回JSON格式结果。".to_string()),
+                messages,
+                Some(0.3),
+            )
+            .await?
         } else {
             let messages = vec![
                 json!({"role": "system", "content": "你是专业翻译助手，将文本翻译并返回JSON格式结果。"}),
@@ -266,24 +477,180 @@ impl AIService {
 
         // 解析返回的 JSON 数组
         let json_str = Self::extract_json_array(&response_text);
-        let parsed: Vec<Value> = serde_json::from_str(&json_str).map_err(|e| {
+        serde_json::from_str(&json_str).map_err(|e| {
             format!(
                 "Failed to parse batch translation response: {} - raw: {}",
                 e, json_str
             )
-        })?;
+        })
+    }
 
-        let mut results = Vec::new();
-        for item in parsed {
-            if let (Some(id), Some(translation)) = (
-                item.get("id").and_then(|v| v.as_str()),
-                item.get("translation").and_then(|v| v.as_str()),
-            ) {
-                results.push((id.to_string(), translation.to_string()));
+    /// 译文质检：对每条原文和译文打 1-10 分并简述问题，返回 (id, 分数, 问题)
+    pub async fn grade_translations(
+        &self,
+        items: Vec<(String, String, String)>,
+        target_language: &str,
+    ) -> Result<Vec<(String, u8, String)>, String> {
+        if items.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut prompt = render(
+            &template(PromptKind::TranslationQa),
+            &[("target_language", target_language)],
+        );
+        prompt.push_str("\n\n待评估文本：\n");
+        for (id, source, translation) in &items {
+            prompt.push_str(&format!(
+                "[{}] 原文：{}\n译文：{}\n",
+                id, source, translation
+            ));
+        }
+
+        let example = r#"[{"id": "xxx", "score": 8, "issue": ""}, ...]"#;
+        let mut grades = Vec::new();
+        for item in self.request_json_items(prompt, example).await? {
+            let id = item.get("id").and_then(|v| v.as_str());
+            // 有的模型把分数写成字符串
+            let score = item.get("score").and_then(|v| {
+                v.as_f64()
+                    .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+            });
+            if let (Some(id), Some(score)) = (id, score) {
+                let issue = item
+                    .get("issue")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                grades.push((id.to_string(), score.round().clamp(1.0, 10.0) as u8, issue));
             }
         }
+        Ok(grades)
+    }
 
-        Ok(results)
+    /// 语义分段：units 为 (全文中的编号, 片段)，返回模型给出的学习单元起始编号
+    pub async fn semantic_breaks(&self, units: &[(usize, &str)]) -> Result<Vec<usize>, String> {
+        if units.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut prompt = template(PromptKind::SemanticSegmentation);
+        prompt.push_str("\n\n片段：\n");
+        for (index, text) in units {
+            prompt.push_str(&format!("[{}] {}\n", index, text));
+        }
+        prompt.push_str("\n返回格式示例：\n[0, 3, 5]");
+
+        let system_prompt = "你是语言学习助手，按要求切分文本并返回JSON格式结果。";
+        let response_text = if self.is_google_provider() {
+            let contents = vec![json!({
+                "role": "user",
+                "parts": [{"text": format!("{}\n\n{}", system_prompt, prompt)}]
+            })];
+            self.make_google_request(contents, Some(0.2)).await?
+        } else if self.is_anthropic_provider() {
+            let messages = vec![json!({"role": "user", "content": prompt})];
+            self.make_anthropic_request(Some(system_prompt.to_string()), messages, Some(0.2))
+                .await?
+        } else {
+            let messages = vec![
+                json!({"role": "system", "content": system_prompt}),
+                json!({"role": "user", "content": prompt}),
+            ];
+            self.make_request(messages, Some(0.2), false).await?
+        };
+
+        let json_str = Self::extract_json_array(&response_text);
+        let parsed: Vec<Value> = serde_json::from_str(&json_str).map_err(|e| {
+            format!(
+                "Failed to parse segmentation response: {} - raw: {}",
+                e, json_str
+            )
+        })?;
+        Ok(parsed
+            .iter()
+            .filter_map(|v| v.as_u64().map(|n| n as usize))
+            .collect())
+    }
+
+    /// 为文章生成简短标题，只发送正文开头部分
+    pub async fn generate_title(&self, content: &str) -> Result<String, String> {
+        let excerpt: String = content.chars().take(TITLE_EXCERPT_CHARS).collect();
+        let prompt = format!("{}\n\n{}", template(PromptKind::ArticleTitle), excerpt);
+
+        let response_text = if self.is_google_provider() {
+            let contents = vec![json!({
+                "role": "user",
+                "parts": [{"text": prompt}]
+            })];
+            self.make_google_request(contents, Some(0.3)).await?
+        } else if self.is_anthropic_provider() {
+            let messages = vec![json!({"role": "user", "content": prompt})];
+            self.make_anthropic_request(None, messages, Some(0.3))
+                .await?
+        } else {
+            let messages = vec![json!({"role": "user", "content": prompt})];
+            self.make_request(messages, Some(0.3), false).await?
+        };
+
+        let title = response_text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .trim_matches(|c: char| matches!(c, '"' | '\'' | '「' | '」' | '《' | '》' | '*' | '#'))
+            .trim()
+            .to_string();
+        if title.is_empty() {
+            return Err("Model returned an empty title".to_string());
+        }
+        Ok(title)
+    }
+
+    /// 批量生成注音，items: Vec<(id, 原文)>，返回 Vec<(id, 注音文本)>
+    /// language 为学习语言代码，决定注音方式（日语振假名、中文拼音等）
+    pub async fn batch_readings(
+        &self,
+        items: Vec<(String, String)>,
+        language: &str,
+    ) -> Result<Vec<(String, String)>, String> {
+        if items.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut prompt = render(
+            &template(PromptKind::BatchReading),
+            &[("reading_style", reading_style(language))],
+        );
+        prompt.push_str("\n\n待注音文本：\n");
+        for (id, text) in &items {
+            prompt.push_str(&format!("[{}] {}\n", id, text));
+        }
+
+        self.request_json_array(prompt, "reading").await
+    }
+
+    /// 批量生成生词的拉丁字母读音（IPA、罗马字等），返回 (id, 读音)
+    pub async fn batch_phonetics(
+        &self,
+        items: Vec<(String, String)>,
+        language: &str,
+    ) -> Result<Vec<(String, String)>, String> {
+        if items.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut prompt = render(
+            &template(PromptKind::BatchReading),
+            &[("reading_style", phonetic_style(language))],
+        );
+        prompt.push_str("\n\n待注音单词：\n");
+        for (id, word) in &items {
+            prompt.push_str(&format!("[{}] {}\n", id, word));
+        }
+
+        self.request_json_array(prompt, "reading").await
     }
 
     /// 从响应中提取 JSON 数组
@@ -334,32 +701,41 @@ impl AIService {
     }
 
     pub async fn analyze(&self, request: AnalysisRequest) -> Result<AnalysisResponse, String> {
-        let system_prompt = match request.analysis_type {
-            AnalysisType::Summary => {
-                "Provide a concise summary of the following text in 3-5 sentences."
-                    .to_string()
-            }
-            AnalysisType::KeyPoints => {
-                "Extract and list the key points from the following text. Use bullet points."
-                    .to_string()
-            }
-            AnalysisType::Vocabulary => {
-                "Identify and explain important vocabulary words, phrases, and idioms from the following text. \
-                Include definitions and example sentences."
-                    .to_string()
-            }
-            AnalysisType::Grammar => {
-                "Analyze the grammatical structures and patterns used in the following text. \
-                Highlight any interesting or complex constructions."
-                    .to_string()
-            }
-            AnalysisType::FullAnalysis => {
-                "Provide a comprehensive analysis of the following text including: \
-                1) Summary, 2) Key points, 3) Vocabulary highlights, 4) Grammar notes."
-                    .to_string()
-            }
-        };
+        let system_prompt = template(analysis_prompt_kind(&request.analysis_type));
+        self.run_analysis(system_prompt, request).await
+    }
+
+    /// 长文分块分析的汇总步骤：把按顺序排列的各块结果合成全文结果
+    pub async fn synthesize_analysis(
+        &self,
+        analysis_type: AnalysisType,
+        partials: &[String],
+    ) -> Result<AnalysisResponse, String> {
+        let system_prompt = render(
+            &template(PromptKind::AnalysisSynthesis),
+            &[("task", &template(analysis_prompt_kind(&analysis_type)))],
+        );
+        let text = partials
+            .iter()
+            .enumerate()
+            .map(|(i, partial)| format!("[Part {}]\n{}", i + 1, partial))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.run_analysis(
+            system_prompt,
+            AnalysisRequest {
+                text,
+                analysis_type,
+            },
+        )
+        .await
+    }
 
+    async fn run_analysis(
+        &self,
+        system_prompt: String,
+        request: AnalysisRequest,
+    ) -> Result<AnalysisResponse, String> {
         let result = if self.is_google_provider() {
             // 使用 Google API 格式
             let contents = vec![json!({
@@ -367,6 +743,10 @@ impl AIService {
                 "parts": [{"text": format!("{}\n\n{}", system_prompt, request.text)}]
             })];
             self.make_google_request(contents, Some(0.5)).await?
+        } else if self.is_anthropic_provider() {
+            let messages = vec![json!({"role": "user", "content": request.text})];
+            self.make_anthropic_request(Some(system_prompt), messages, Some(0.5))
+                .await?
         } else {
             let messages = vec![
                 json!({"role": "system", "content": system_prompt}),
@@ -386,6 +766,9 @@ impl AIService {
         if self.provider == "google" || self.provider == "google-ai-studio" {
             return self.chat_google(request).await;
         }
+        if self.is_anthropic_provider() {
+            return self.chat_anthropic(request).await;
+        }
         if self.provider == "moonshot" {
             // Moonshot requires specific message formatting for multimedia
             let messages = self.format_messages_for_provider(&request.messages);
@@ -426,9 +809,12 @@ impl AIService {
     where
         F: Fn(String) + Send + Sync + 'static,
     {
-        // For now, only support standard OpenAI SSE streaming
-        // Google streaming requires different handling, fallback to normal chat
         if self.is_google_provider() {
+            return self.stream_chat_google(request, callback).await;
+        }
+
+        // Anthropic streaming requires different handling, fallback to normal chat
+        if self.is_anthropic_provider() {
             let response = self.chat(request).await?;
             callback(response.content.clone());
             return Ok(response.content);
@@ -483,11 +869,7 @@ impl AIService {
             .map_err(|e| format!("Failed to send request: {}", e))?;
 
         if !response.status().is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("API error: {}", error_text));
+            return Err(response_error("API", response).await);
         }
 
         let mut stream = response.bytes_stream();
@@ -522,9 +904,71 @@ impl AIService {
         Ok(full_content)
     }
 
-    async fn chat_google(&self, request: ChatRequest) -> Result<ChatResponse, String> {
-        let contents: Vec<Value> = request
-            .messages
+    /// Gemini streamGenerateContent (alt=sse)，每个事件携带一段增量文本
+    async fn stream_chat_google<F>(
+        &self,
+        request: ChatRequest,
+        callback: F,
+    ) -> Result<String, String>
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        let request_body = json!({
+            "contents": Self::google_contents(request.messages),
+            "generationConfig": {
+                "temperature": request.temperature.unwrap_or(0.7)
+            }
+        });
+
+        let url = format!(
+            "{}?alt=sse",
+            self.get_api_url()
+                .replace(":generateContent", ":streamGenerateContent")
+        );
+
+        let response = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("X-goog-api-key", &self.api_key)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(response_error("Google API", response).await);
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut full_content = String::new();
+        // 网络分块不一定按行对齐（多字节字符也可能被截断），按字节缓存，凑满一行再解码
+        let mut buffer: Vec<u8> = Vec::new();
+
+        while let Some(item) = stream.next().await {
+            let chunk = item.map_err(|e| format!("Error reading stream: {}", e))?;
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(newline) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline).collect();
+                if let Some(text) = parse_gemini_sse_line(&String::from_utf8_lossy(&line)) {
+                    full_content.push_str(&text);
+                    callback(text);
+                }
+            }
+        }
+
+        if let Some(text) = parse_gemini_sse_line(&String::from_utf8_lossy(&buffer)) {
+            full_content.push_str(&text);
+            callback(text);
+        }
+
+        Ok(full_content)
+    }
+
+    /// 将 ChatMessage 转为 Gemini contents（assistant 映射为 model，其余为 user）
+    fn google_contents(messages: Vec<crate::types::ChatMessage>) -> Vec<Value> {
+        messages
             .into_iter()
             .map(|msg| {
                 let role = if msg.role == "assistant" {
@@ -559,7 +1003,11 @@ impl AIService {
                     "parts": parts
                 })
             })
-            .collect();
+            .collect()
+    }
+
+    async fn chat_google(&self, request: ChatRequest) -> Result<ChatResponse, String> {
+        let contents = Self::google_contents(request.messages);
 
         let content = self
             .make_google_request(contents, request.temperature)
@@ -572,6 +1020,101 @@ impl AIService {
         })
     }
 
+    async fn chat_anthropic(&self, request: ChatRequest) -> Result<ChatResponse, String> {
+        let mut system_parts = Vec::new();
+        let mut messages = Vec::new();
+
+        for msg in request.messages {
+            if msg.role == "system" {
+                if let crate::types::ChatContent::Text(text) = msg.content {
+                    system_parts.push(text);
+                }
+                continue;
+            }
+
+            let role = if msg.role == "assistant" {
+                "assistant"
+            } else {
+                "user"
+            };
+
+            let content = match msg.content {
+                crate::types::ChatContent::Text(text) => json!(text),
+                crate::types::ChatContent::Parts(parts) => {
+                    let blocks: Vec<Value> = parts
+                        .into_iter()
+                        .filter_map(|part| {
+                            if let Some(text) = part.text {
+                                Some(json!({"type": "text", "text": text}))
+                            } else if let Some(image) = part.image_url {
+                                Some(Self::anthropic_image_block(&image.url))
+                            } else if let Some(file) = part.file_data {
+                                let block_type = if file.mime_type == "application/pdf" {
+                                    "document"
+                                } else {
+                                    "image"
+                                };
+                                Some(json!({
+                                    "type": block_type,
+                                    "source": {
+                                        "type": "base64",
+                                        "media_type": file.mime_type,
+                                        "data": file.data
+                                    }
+                                }))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
+                    json!(blocks)
+                }
+            };
+
+            messages.push(json!({
+                "role": role,
+                "content": content
+            }));
+        }
+
+        let system = if system_parts.is_empty() {
+            None
+        } else {
+            Some(system_parts.join("\n\n"))
+        };
+
+        let content = self
+            .make_anthropic_request(system, messages, request.temperature)
+            .await?;
+
+        Ok(ChatResponse {
+            content,
+            model: self.model.clone(),
+            tokens_used: None,
+        })
+    }
+
+    /// Anthropic image block: data URLs are sent inline, other URLs by reference
+    fn anthropic_image_block(url: &str) -> Value {
+        if let Some(rest) = url.strip_prefix("data:") {
+            if let Some((media_type, data)) = rest.split_once(";base64,") {
+                return json!({
+                    "type": "image",
+                    "source": {
+                        "type": "base64",
+                        "media_type": media_type,
+                        "data": data
+                    }
+                });
+            }
+        }
+
+        json!({
+            "type": "image",
+            "source": { "type": "url", "url": url }
+        })
+    }
+
     // Helper to format messages for different providers
     fn format_messages_for_provider(&self, messages: &[crate::types::ChatMessage]) -> Vec<Value> {
         messages
@@ -636,43 +1179,9 @@ impl AIService {
             _ => "中文",
         };
 
-        let system_prompt = format!(
-            r#"You are a professional language learning assistant. The user's native language is {0}. Please analyze the following text segment comprehensively and return the result strictly in the following JSON format. Do NOT add any extra explanations or markdown formatting outside the JSON block.
-
-User's Native Language: {0}
-
-Text to Analyze:
----
-{1}
----
-
-Please strictly adhere to this JSON structure (all keys must be in English):
-{{
-  "translation": "Translate the text into natural, fluent {0}",
-  "explanation": "Explain the text in {0}, covering context, tone, and cultural background. Use Markdown formatting.",
-  "vocabulary": [
-    {{
-      "word": "The word or phrase from the text",
-      "reading": "Pronunciation/Reading (e.g., Hiragana for Japanese, IPA for English)",
-      "meaning": "Core meaning in the context, explained in {0}",
-      "usage": "Usage notes and collocations in {0}",
-      "example": "Example sentence containing the word, with {0} translation"
-    }}
-  ],
-  "grammar_points": [
-    {{
-      "point": "Name of the grammar point",
-      "explanation": "Detailed explanation in {0}",
-      "example": "Example sentence using the grammar point, with {0} translation"
-    }}
-  ],
-  "cultural_context": "Cultural background info in {0} (if applicable, else null)",
-  "difficulty_level": "beginner | intermediate | advanced",
-  "learning_tips": "Learning advice for this segment in {0}"
-}}
-
-Ensure all explanations, meanings, and descriptive text are written in {0}."#,
-            native_language_name, text
+        let system_prompt = render(
+            &template(PromptKind::SegmentExplanation),
+            &[("native_language", native_language_name), ("text", &text)],
         );
 
         let messages = vec![
@@ -688,6 +1197,11 @@ Ensure all explanations, meanings, and descriptive text are written in {0}."#,
                 "parts": [{"text": format!("{}\n\nAnalyze this: {}", system_prompt, text)}]
             })];
             self.make_google_request(contents, Some(0.3)).await?
+        } else if self.is_anthropic_provider() {
+            let messages =
+                vec![json!({"role": "user", "content": format!("Analyze this: {}", text)})];
+            self.make_anthropic_request(Some(system_prompt), messages, Some(0.3))
+                .await?
         } else {
             self.make_request(messages, Some(0.3), false).await?
         };
@@ -885,27 +1399,79 @@ Ensure all explanations, meanings, and descriptive text are written in {0}."#,
     }
 }
 
+fn analysis_prompt_kind(analysis_type: &AnalysisType) -> PromptKind {
+    match analysis_type {
+        AnalysisType::Summary => PromptKind::AnalysisSummary,
+        AnalysisType::KeyPoints => PromptKind::AnalysisKeyPoints,
+        AnalysisType::Vocabulary => PromptKind::AnalysisVocabulary,
+        AnalysisType::Grammar => PromptKind::AnalysisGrammar,
+        AnalysisType::FullAnalysis => PromptKind::AnalysisFull,
+    }
+}
+
+/// 解析 Gemini SSE 中的一行 `data: {...}`，返回该事件所有 parts 拼接后的文本
+fn parse_gemini_sse_line(line: &str) -> Option<String> {
+    let data = line.trim().strip_prefix("data:")?.trim();
+    let json: Value = serde_json::from_str(data).ok()?;
+    let text: String = json["candidates"][0]["content"]["parts"]
+        .as_array()?
+        .iter()
+        .filter_map(|part| part["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
 // Simple in-memory cache for AI service instances
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-// Newtype wrapper to allow Default implementation
+/// 当前活动模型的服务实例，以及按任务单独指定的模型（未指定的任务使用活动模型）
 #[derive(Clone)]
-pub struct AIServiceCache(Arc<RwLock<Option<AIService>>>);
+pub struct AIServiceCache {
+    active: Arc<RwLock<Option<AIService>>>,
+    task_services: Arc<RwLock<HashMap<AiTask, AIService>>>,
+}
 
 impl Default for AIServiceCache {
     fn default() -> Self {
-        Self(Arc::new(RwLock::new(None)))
+        Self {
+            active: Arc::new(RwLock::new(None)),
+            task_services: Arc::new(RwLock::new(HashMap::new())),
+        }
     }
 }
 
 impl AIServiceCache {
     pub async fn read(&self) -> tokio::sync::RwLockReadGuard<'_, Option<AIService>> {
-        self.0.read().await
+        self.active.read().await
     }
 
     pub async fn write(&self) -> tokio::sync::RwLockWriteGuard<'_, Option<AIService>> {
-        self.0.write().await
+        self.active.write().await
+    }
+}
+
+/// 读取失败响应的错误信息；429 时附带 Retry-After，排队任务据此暂停后自动续跑
+pub(crate) async fn response_error(label: &str, response: reqwest::Response) -> String {
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        rate_limit_error(label, retry_after, &error_text)
+    } else {
+        format!("{} error: {}", label, error_text)
     }
 }
 
@@ -921,16 +1487,51 @@ pub async fn get_or_create_ai_service(
     Ok(())
 }
 
-pub async fn get_ai_service(cache: &AIServiceCache) -> Result<AIService, String> {
+pub async fn get_ai_service(cache: &AIServiceCache) -> Result<AIService, AppError> {
+    crate::offline::ensure_online()?;
+    active_ai_service(cache).await
+}
+
+/// 当前活动模型的服务实例，不检查离线模式（只用于读取模型信息，不发请求）
+pub async fn active_ai_service(cache: &AIServiceCache) -> Result<AIService, AppError> {
     let cache_guard = cache.read().await;
     cache_guard
         .as_ref()
-        .map(|service| AIService {
-            client: Client::new(),
-            api_key: service.api_key.clone(),
-            provider: service.provider.clone(),
-            model: service.model.clone(),
-            base_url: service.base_url.clone(),
-        })
-        .ok_or_else(|| "AI service not initialized".to_string())
+        .map(AIService::detached)
+        .ok_or_else(|| AppError::missing_api_key(tr("error.ai_not_configured")))
+}
+
+/// 根据配置中的任务路由重建各任务的服务实例
+pub async fn refresh_task_services(cache: &AIServiceCache, config: &AppConfig) {
+    let mut services = HashMap::new();
+    for task in AiTask::ALL {
+        let routed = config
+            .task_models
+            .model_id(task)
+            .and_then(|id| config.get_config(id));
+        if let Some(model_config) = routed {
+            services.insert(
+                task,
+                AIService::with_base_url(
+                    model_config.api_key.clone(),
+                    model_config.api_provider.clone(),
+                    model_config.model.clone(),
+                    model_config.base_url.clone(),
+                ),
+            );
+        }
+    }
+    *cache.task_services.write().await = services;
+}
+
+/// 获取指定任务使用的 AI 服务，未单独配置时使用活动模型
+pub async fn get_ai_service_for_task(
+    cache: &AIServiceCache,
+    task: AiTask,
+) -> Result<AIService, AppError> {
+    crate::offline::ensure_online()?;
+    if let Some(service) = cache.task_services.read().await.get(&task) {
+        return Ok(service.detached());
+    }
+    get_ai_service(cache).await
 }
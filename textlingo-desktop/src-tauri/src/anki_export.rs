@@ -0,0 +1,167 @@
+// 文章讲解导出为 Anki 卡片
+// 每个分段讲解中的词汇生成词汇卡、语法点生成语法卡，背面附例句（所在分段原文及译文）和出处，
+// 输出 Anki 可直接导入的制表符分隔文本（文件头指定分隔符、HTML、牌组和标签列）
+
+use html_escape::encode_text;
+
+use crate::types::{Article, ArticleSegment, GrammarPoint, VocabularyItem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnkiCardKind {
+    Vocabulary,
+    Grammar,
+}
+
+impl AnkiCardKind {
+    fn tag(self) -> &'static str {
+        match self {
+            AnkiCardKind::Vocabulary => "vocabulary",
+            AnkiCardKind::Grammar => "grammar",
+        }
+    }
+}
+
+/// 一张 Anki 卡片，正反面为 HTML
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnkiCard {
+    pub kind: AnkiCardKind,
+    pub front: String,
+    pub back: String,
+}
+
+/// 默认牌组名：OpenKoto 下以文章标题命名的子牌组
+pub fn default_deck_name(article: &Article) -> String {
+    format!("OpenKoto::{}", article.title.trim())
+}
+
+/// 按分段顺序为文章所有讲解生成卡片；同一单词或语法点在多个分段出现时只保留第一次
+pub fn article_cards(article: &Article) -> Vec<AnkiCard> {
+    let mut segments: Vec<&ArticleSegment> = article.segments.iter().collect();
+    segments.sort_by_key(|seg| seg.order);
+
+    let mut seen_words = Vec::new();
+    let mut seen_points = Vec::new();
+    let mut cards = Vec::new();
+    for seg in segments {
+        let Some(explanation) = &seg.explanation else {
+            continue;
+        };
+        for item in &explanation.vocabulary {
+            let key = item.word.trim().to_lowercase();
+            if key.is_empty() || seen_words.contains(&key) {
+                continue;
+            }
+            seen_words.push(key);
+            cards.push(vocabulary_card(item, seg, &article.title));
+        }
+        for point in &explanation.grammar_points {
+            let key = point.point.trim().to_lowercase();
+            if key.is_empty() || seen_points.contains(&key) {
+                continue;
+            }
+            seen_points.push(key);
+            cards.push(grammar_card(point, seg, &article.title));
+        }
+    }
+    cards
+}
+
+/// 例句：分段原文（词汇加粗）和分段译文
+fn example_html(seg: &ArticleSegment, highlight: Option<&str>) -> String {
+    let mut sentence = html(&seg.text);
+    if let Some(word) = highlight.map(str::trim).filter(|w| !w.is_empty()) {
+        let word = html(word);
+        sentence = sentence.replace(&word, &format!("<b>{}</b>", word));
+    }
+    let mut out = format!("<div class=\"example\">{}</div>", sentence);
+    let translation = seg
+        .translation
+        .as_deref()
+        .or_else(|| seg.explanation.as_ref().map(|e| e.translation.as_str()))
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    if let Some(translation) = translation {
+        out.push_str(&format!(
+            "<div class=\"example-translation\">{}</div>",
+            html(translation)
+        ));
+    }
+    out
+}
+
+fn source_html(title: &str) -> String {
+    format!("<div class=\"source\">{}</div>", html(title.trim()))
+}
+
+fn vocabulary_card(item: &VocabularyItem, seg: &ArticleSegment, title: &str) -> AnkiCard {
+    let mut back = String::new();
+    if let Some(reading) = item.reading.as_deref().filter(|r| !r.trim().is_empty()) {
+        back.push_str(&format!("<div class=\"reading\">{}</div>", html(reading)));
+    }
+    back.push_str(&format!(
+        "<div class=\"meaning\">{}</div>",
+        html(&item.meaning)
+    ));
+    if !item.usage.trim().is_empty() {
+        back.push_str(&format!("<div class=\"usage\">{}</div>", html(&item.usage)));
+    }
+    back.push_str(&example_html(seg, Some(&item.word)));
+    back.push_str(&source_html(title));
+    AnkiCard {
+        kind: AnkiCardKind::Vocabulary,
+        front: html(item.word.trim()),
+        back,
+    }
+}
+
+fn grammar_card(point: &GrammarPoint, seg: &ArticleSegment, title: &str) -> AnkiCard {
+    let mut back = format!(
+        "<div class=\"explanation\">{}</div>",
+        html(&point.explanation)
+    );
+    // 讲解自带的例句与分段原文不同时一并列出
+    if let Some(example) = point
+        .example
+        .as_deref()
+        .map(str::trim)
+        .filter(|e| !e.is_empty() && *e != seg.text.trim())
+    {
+        back.push_str(&format!(
+            "<div class=\"grammar-example\">{}</div>",
+            html(example)
+        ));
+    }
+    back.push_str(&example_html(seg, None));
+    back.push_str(&source_html(title));
+    AnkiCard {
+        kind: AnkiCardKind::Grammar,
+        front: html(point.point.trim()),
+        back,
+    }
+}
+
+/// 字段内容转义为 HTML，换行转为 <br>，制表符会破坏列分隔，替换为空格
+fn html(text: &str) -> String {
+    encode_text(text)
+        .replace("\r\n", "\n")
+        .replace('\n', "<br>")
+        .replace('\t', " ")
+}
+
+/// 生成 Anki 导入文件（文件 → 导入，选择 Basic 笔记类型）：正面、背面、标签三列
+pub fn render_anki_tsv(cards: &[AnkiCard], deck: &str) -> String {
+    let deck = deck.replace(['\t', '\r', '\n'], " ");
+    let mut out = format!(
+        "#separator:tab\n#html:true\n#deck:{}\n#tags column:3\n",
+        deck.trim()
+    );
+    for card in cards {
+        out.push_str(&format!(
+            "{}\t{}\topenkoto {}\n",
+            card.front,
+            card.back,
+            card.kind.tag()
+        ));
+    }
+    out
+}
@@ -0,0 +1,76 @@
+// 文章合并：分多次导入的连载博文、分块转录的字幕等按给定顺序拼成一篇文章
+// 段落保留原有的翻译、讲解和注音，换用新的段落 ID 并改写所属文章、重新编号
+
+use crate::types::{Article, ArticleSegment};
+use uuid::Uuid;
+
+/// 各篇都相同时取该值，否则为 None（如来源地址、媒体文件只在同一来源的分块间保留）
+fn shared<T: Clone + PartialEq>(
+    parts: &[Article],
+    field: impl Fn(&Article) -> &Option<T>,
+) -> Option<T> {
+    let first = field(parts.first()?);
+    parts
+        .iter()
+        .all(|part| field(part) == first)
+        .then(|| first.clone())
+        .flatten()
+}
+
+/// 按 parts 的顺序合并为一篇新文章：正文以空行连接，段落按各篇内原有顺序依次排列。
+/// 标签取并集，整篇分析结果随内容变化失效，不保留
+pub fn merge_articles(parts: &[Article], id: &str, title: &str, created_at: &str) -> Article {
+    let mut segments: Vec<ArticleSegment> = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    for (index, part) in parts.iter().enumerate() {
+        let mut part_segments = part.segments.clone();
+        part_segments.sort_by_key(|seg| seg.order);
+        for (position, mut seg) in part_segments.into_iter().enumerate() {
+            // 原文章可能保留，段落 ID 不能与原文章的段落重复
+            seg.id = Uuid::new_v4().to_string();
+            seg.article_id = id.to_string();
+            seg.order = segments.len() as i32;
+            // 每篇的第一段另起一行，避免接在上一篇的最后一句后面
+            if index > 0 && position == 0 {
+                seg.is_new_paragraph = true;
+            }
+            segments.push(seg);
+        }
+        for tag in &part.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+
+    let title = title.trim();
+    Article {
+        id: id.to_string(),
+        title: if title.is_empty() {
+            parts.first().map(|p| p.title.clone()).unwrap_or_default()
+        } else {
+            title.to_string()
+        },
+        content: parts
+            .iter()
+            .map(|part| part.content.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        source_type: shared(parts, |p| &p.source_type).or_else(|| Some("article".to_string())),
+        source_url: shared(parts, |p| &p.source_url),
+        media_path: shared(parts, |p| &p.media_path),
+        book_path: shared(parts, |p| &p.book_path),
+        book_type: shared(parts, |p| &p.book_type),
+        language: shared(parts, |p| &p.language)
+            .or_else(|| parts.iter().find_map(|p| p.language.clone())),
+        created_at: created_at.to_string(),
+        translated: !parts.is_empty() && parts.iter().all(|p| p.translated),
+        translation_language: shared(parts, |p| &p.translation_language),
+        segmentation: shared(parts, |p| &p.segmentation),
+        analyses: Default::default(),
+        channel: shared(parts, |p| &p.channel),
+        tags,
+        media_info: shared(parts, |p| &p.media_info),
+        segments,
+    }
+}
@@ -0,0 +1,59 @@
+// 文章数据升级：文章结构新增字段时旧数据靠 serde 默认值读取，存储中的 JSON 一直是旧形状，
+// 长期使用的安装会积累形状不一的数据。升级把每篇文章按最新结构重写（默认字段显式写出，
+// 段落按顺序重新编号并归属本文章），原文先备份到 backups/article-upgrades 下
+
+use serde_json::Value;
+
+use crate::segmentation::renumber_segments;
+use crate::types::Article;
+
+/// 文章数据版本，Article 结构变化后加一，启动时发现存储的版本较旧会自动升级
+pub const ARTICLE_SCHEMA_VERSION: u32 = 2;
+
+/// 备份目录: app_data_dir/backups/article-upgrades/{时间戳}
+pub const UPGRADE_BACKUP_DIR: &str = "backups/article-upgrades";
+
+/// 单篇文章的升级结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArticleUpgrade {
+    /// 已是最新结构
+    Unchanged,
+    /// 重写后的 JSON
+    Upgraded(String),
+}
+
+/// 把文章 JSON 重写为最新结构；无法解析为文章时返回错误
+pub fn upgrade_article_json(json: &str) -> Result<ArticleUpgrade, String> {
+    let original: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut article: Article =
+        serde_json::from_value(original.clone()).map_err(|e| e.to_string())?;
+
+    article.segments.sort_by_key(|seg| seg.order);
+    for seg in &mut article.segments {
+        seg.article_id = article.id.clone();
+    }
+    renumber_segments(&mut article.segments);
+
+    let upgraded = serde_json::to_value(&article).map_err(|e| e.to_string())?;
+    if upgraded == original {
+        return Ok(ArticleUpgrade::Unchanged);
+    }
+    serde_json::to_string(&upgraded)
+        .map(ArticleUpgrade::Upgraded)
+        .map_err(|e| e.to_string())
+}
+
+/// 备份文件名，ID 中不适合做文件名的字符替换为 _
+pub fn backup_file_name(article_id: &str) -> String {
+    let name: String = article_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.json", name)
+}
@@ -0,0 +1,205 @@
+// 文本与有声书对齐：把音频转录稿与文章段落逐词对齐，为段落填上开始/结束时间，实现跟读播放
+// 转录稿的时间只精确到句，句内每个词的时间按字符数线性插值；对齐按窗口求最长公共子序列，长篇章节也不会占用过多内存
+
+use crate::subtitle_files::SubtitleCue;
+
+/// 每次参与比较的正文词数
+const WINDOW: usize = 400;
+
+/// 带时间的转录词
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedToken {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// 中日文、泰文不以空格分词，按单字比较
+fn is_unspaced(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{0e00}'..='\u{0e7f}')
+}
+
+/// 拆成用于比较的词：忽略大小写和标点，中日文每个字单独成词
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if is_unspaced(c) {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            tokens.push(c.to_string());
+        } else if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        } else if !(c == '\'' || c == '’') || word.is_empty() {
+            // 词内的撇号（don't）忽略，其余符号作为分隔
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+/// 转录稿拆成带时间的词，句内按字符数分配时间
+pub fn transcript_tokens(cues: &[SubtitleCue]) -> Vec<TimedToken> {
+    let mut tokens = Vec::new();
+    for cue in cues {
+        let words = tokenize(&cue.text);
+        let total: usize = words.iter().map(|w| w.chars().count()).sum();
+        if total == 0 {
+            continue;
+        }
+        let per_char = (cue.end - cue.start).max(0.0) / total as f64;
+        let mut offset = 0;
+        for word in words {
+            let len = word.chars().count();
+            tokens.push(TimedToken {
+                text: word,
+                start: cue.start + offset as f64 * per_char,
+                end: cue.start + (offset + len) as f64 * per_char,
+            });
+            offset += len;
+        }
+    }
+    tokens
+}
+
+/// 最长公共子序列，返回匹配的 (a 下标, b 下标)
+fn lcs_pairs(a: &[String], b: &[TimedToken]) -> Vec<(usize, usize)> {
+    let width = b.len() + 1;
+    let mut table = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i * width + j] = if a[i] == b[j].text {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut pairs = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j].text {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// 为正文的每个词找到对应的转录词下标（未匹配为 None）
+///
+/// 按窗口推进：每次取 WINDOW 个正文词，与按语速估算的转录区间求公共子序列，
+/// 只采纳窗口前三分之二的匹配，避免窗口边缘的误配
+pub fn align_tokens(text: &[String], transcript: &[TimedToken]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; text.len()];
+    if text.is_empty() || transcript.is_empty() {
+        return matches;
+    }
+    let ratio = transcript.len() as f64 / text.len() as f64;
+    let (mut i, mut j) = (0, 0);
+    while i < text.len() && j < transcript.len() {
+        let a_end = (i + WINDOW).min(text.len());
+        let expected = ((a_end - i) as f64 * ratio).ceil() as usize;
+        let b_end = (j + expected * 3 / 2 + 100).min(transcript.len());
+        let limit = if a_end == text.len() {
+            a_end
+        } else {
+            i + WINDOW * 2 / 3
+        };
+
+        let mut last = None;
+        for (a, b) in lcs_pairs(&text[i..a_end], &transcript[j..b_end]) {
+            if i + a >= limit {
+                break;
+            }
+            matches[i + a] = Some(j + b);
+            last = Some((a, b));
+        }
+        match last {
+            Some((a, b)) => {
+                i += a + 1;
+                j += b + 1;
+            }
+            // 这段正文没有朗读（如章节标题、注释），跳过
+            None => i = limit,
+        }
+    }
+    matches
+}
+
+/// 计算每个段落的 (开始, 结束) 时间；没有匹配词的段落夹在前后已对齐段落之间，无法确定时为 None
+pub fn align_segments(segment_texts: &[&str], cues: &[SubtitleCue]) -> Vec<Option<(f64, f64)>> {
+    let transcript = transcript_tokens(cues);
+    let mut text_tokens = Vec::new();
+    let mut owners = Vec::new();
+    for (index, text) in segment_texts.iter().enumerate() {
+        for token in tokenize(text) {
+            text_tokens.push(token);
+            owners.push(index);
+        }
+    }
+
+    let mut spans: Vec<Option<(f64, f64)>> = vec![None; segment_texts.len()];
+    for (token_index, matched) in align_tokens(&text_tokens, &transcript)
+        .into_iter()
+        .enumerate()
+    {
+        let Some(matched) = matched else {
+            continue;
+        };
+        let token = &transcript[matched];
+        let span = &mut spans[owners[token_index]];
+        *span = Some(match *span {
+            Some((start, end)) => (start.min(token.start), end.max(token.end)),
+            None => (token.start, token.end),
+        });
+    }
+
+    // 连续未对齐的段落平分前一段结束到后一段开始之间的时间
+    let mut index = 0;
+    while index < spans.len() {
+        if spans[index].is_some() {
+            index += 1;
+            continue;
+        }
+        let run_end = spans[index..]
+            .iter()
+            .position(Option::is_some)
+            .map_or(spans.len(), |offset| index + offset);
+        let prev_end = index
+            .checked_sub(1)
+            .and_then(|prev| spans[prev])
+            .map(|(_, end)| end);
+        let next_start = spans
+            .get(run_end)
+            .copied()
+            .flatten()
+            .map(|(start, _)| start);
+        if let (Some(start), Some(end)) = (prev_end, next_start) {
+            if end > start {
+                let step = (end - start) / (run_end - index) as f64;
+                for (k, span) in spans[index..run_end].iter_mut().enumerate() {
+                    *span = Some((start + k as f64 * step, start + (k + 1) as f64 * step));
+                }
+            }
+        }
+        index = run_end;
+    }
+    spans
+}
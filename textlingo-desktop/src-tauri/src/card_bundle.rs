@@ -0,0 +1,205 @@
+// 离线卡片包：把单词包导出为自带音频和配图的目录，供手机端配套应用或静态网页复习
+// 目录结构: cards.json（卡片数据）、index.html（直接用浏览器打开的翻卡页面）、audio/、images/
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::types::{FavoriteVocabulary, WordPack};
+
+/// 导出目录: app_data_dir/exports
+pub const EXPORTS_DIR: &str = "exports";
+
+pub const CARD_BUNDLE_SCHEMA: &str = "openkoto-card-bundle-v1";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundlePack {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub language_from: Option<String>,
+    #[serde(default)]
+    pub language_to: Option<String>,
+}
+
+/// 一张卡片；audio / example_audio / image 为相对于包目录的路径
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleCard {
+    pub id: String,
+    pub word: String,
+    #[serde(default)]
+    pub reading: Option<String>,
+    pub meaning: String,
+    #[serde(default)]
+    pub usage: Option<String>,
+    #[serde(default)]
+    pub example: Option<String>,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub audio: Option<String>,
+    #[serde(default)]
+    pub example_audio: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardBundle {
+    pub schema_version: String,
+    pub pack: BundlePack,
+    pub exported_at: String,
+    pub cards: Vec<BundleCard>,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+impl BundlePack {
+    pub fn from_pack(pack: &WordPack) -> Self {
+        Self {
+            name: pack.name.clone(),
+            description: non_empty(pack.description.as_deref()),
+            language_from: pack.language_from.clone(),
+            language_to: pack.language_to.clone(),
+        }
+    }
+}
+
+impl BundleCard {
+    /// 收藏的单词转换为卡片（音频和配图由导出时填入）
+    pub fn from_vocabulary(vocabulary: &FavoriteVocabulary) -> Self {
+        Self {
+            id: vocabulary.id.clone(),
+            word: vocabulary.word.clone(),
+            reading: non_empty(vocabulary.reading.as_deref()),
+            meaning: vocabulary.meaning.clone(),
+            usage: non_empty(Some(&vocabulary.usage)),
+            example: non_empty(vocabulary.example.as_deref()),
+            explanation: non_empty(vocabulary.explanation.as_deref()),
+            tags: vocabulary.tags.clone(),
+            audio: None,
+            example_audio: None,
+            image: None,
+        }
+    }
+}
+
+/// 包目录名：{包名}-{导出时间}，去掉文件名中不允许的字符
+pub fn bundle_dir_name(pack_name: &str, exported_at: &chrono::DateTime<chrono::Local>) -> String {
+    let name: String = pack_name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | '?' | '%' | '*' | ':' | '|' | '"' | '<' | '>' | '.' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let name = name.trim();
+    format!(
+        "{}-{}",
+        if name.trim_matches('-').is_empty() {
+            "word-pack"
+        } else {
+            name
+        },
+        exported_at.format("%Y%m%d-%H%M%S")
+    )
+}
+
+const INDEX_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{title}}</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 0; background: #f5f5f4; color: #1c1917; }
+main { max-width: 560px; margin: 0 auto; padding: 24px 16px; }
+.card { background: #fff; border-radius: 12px; padding: 24px; box-shadow: 0 1px 4px rgba(0,0,0,.1); min-height: 240px; cursor: pointer; }
+.word { font-size: 2em; font-weight: 600; }
+.reading, .meta { color: #78716c; }
+.back { display: none; margin-top: 16px; border-top: 1px solid #e7e5e4; padding-top: 16px; }
+.card.flipped .back { display: block; }
+img { max-width: 100%; border-radius: 8px; margin-top: 12px; }
+nav { display: flex; justify-content: space-between; align-items: center; margin-top: 16px; }
+button { font-size: 1em; padding: 8px 16px; }
+</style>
+</head>
+<body>
+<main>
+<h1>{{title}}</h1>
+<div id="card" class="card"></div>
+<nav><button id="prev">&larr;</button><span id="pos" class="meta"></span><button id="next">&rarr;</button></nav>
+</main>
+<script type="application/json" id="bundle">{{bundle}}</script>
+<script>
+const bundle = JSON.parse(document.getElementById("bundle").textContent);
+const cards = bundle.cards;
+let index = 0;
+const el = document.getElementById("card");
+function text(tag, cls, value) {
+  const node = document.createElement(tag);
+  node.className = cls;
+  node.textContent = value;
+  return node;
+}
+function play(src) { if (src) new Audio(src).play(); }
+function show() {
+  el.replaceChildren();
+  el.classList.remove("flipped");
+  document.getElementById("pos").textContent = cards.length ? (index + 1) + " / " + cards.length : "";
+  const card = cards[index];
+  if (!card) return;
+  el.append(text("div", "word", card.word));
+  if (card.reading) el.append(text("div", "reading", card.reading));
+  const back = document.createElement("div");
+  back.className = "back";
+  back.append(text("div", "meaning", card.meaning));
+  if (card.usage) back.append(text("p", "meta", card.usage));
+  if (card.example) back.append(text("p", "example", card.example));
+  if (card.image) { const img = document.createElement("img"); img.src = card.image; back.append(img); }
+  el.append(back);
+  play(card.audio);
+}
+el.onclick = () => {
+  el.classList.toggle("flipped");
+  if (el.classList.contains("flipped")) play(cards[index].example_audio);
+};
+document.getElementById("prev").onclick = () => { index = (index + cards.length - 1) % cards.length; show(); };
+document.getElementById("next").onclick = () => { index = (index + 1) % cards.length; show(); };
+show();
+</script>
+</body>
+</html>
+"#;
+
+/// 生成静态翻卡页面；卡片数据直接嵌入页面，以 file:// 打开时无需读取 cards.json
+pub fn render_index_html(bundle: &CardBundle) -> Result<String, String> {
+    let json = serde_json::to_string(bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {}", e))?
+        // 防止卡片内容中的 </script> 提前结束脚本块
+        .replace("</", "<\\/");
+    let title = html_escape::encode_text(&bundle.pack.name);
+    Ok(INDEX_TEMPLATE
+        .replace("{{title}}", &title)
+        .replace("{{bundle}}", &json))
+}
+
+/// 写入 cards.json 和 index.html（音频和配图由调用方复制到 audio/、images/）
+pub fn write_bundle(dir: &Path, bundle: &CardBundle) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create bundle dir: {}", e))?;
+    let json = serde_json::to_string_pretty(bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+    std::fs::write(dir.join("cards.json"), json)
+        .map_err(|e| format!("Failed to write cards.json: {}", e))?;
+    std::fs::write(dir.join("index.html"), render_index_html(bundle)?)
+        .map_err(|e| format!("Failed to write index.html: {}", e))?;
+    Ok(())
+}
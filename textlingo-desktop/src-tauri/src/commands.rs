@@ -1,186 +1,288 @@
-use crate::ai_service::{get_ai_service, get_or_create_ai_service, AIServiceCache};
+use crate::ai_service::{
+    active_ai_service, get_ai_service, get_ai_service_for_task, get_or_create_ai_service,
+    refresh_task_services, AIServiceCache,
+};
+use crate::anki_export;
+use crate::article_upgrade::{self, ArticleUpgrade};
+use crate::card_bundle;
+use crate::corpus_counts;
+use crate::error::{AppError, ErrorKind};
+use crate::export_templates;
+use crate::extraction_rules::{
+    load_rules, load_rules_or_default, save_rules, select_content_html, select_title,
+    ExtractionRules,
+};
+use crate::folder_watcher::FolderWatcher;
+use crate::glossary;
+use crate::i18n::{tr, tr_with};
+use crate::jobs::{
+    cancelled_message, is_rate_limit_error, job_error, run_cancellable, wait_for_rate_limit,
+    JobRegistry, MAX_RATE_LIMIT_WAITS,
+};
+use crate::language_detect::detect_language;
+use crate::model_metadata;
+use crate::obsidian_export;
+use crate::offline;
+use crate::prompt_templates::{self, detect_reading_language, PromptKind, PromptTemplateInfo};
+use crate::readings;
+use crate::result_validation;
+use crate::segmentation;
 use crate::storage::{
+    clear_translation_memory,
     delete_article,
+    delete_article_recap,
+    delete_article_revision,
     delete_bookmark,
+    delete_channel_subscription,
+    delete_export_template,
+    delete_failed_capture,
     delete_favorite_grammar,
     delete_favorite_vocabulary,
+    delete_glossary_entry,
+    delete_known_word,
+    delete_quarantined_result,
+    delete_review_log_entry,
+    delete_tag_rule,
+    delete_translation_progress,
+    delete_translation_qa,
     delete_word_pack,
     ensure_app_dirs,
     ensure_favorites_dirs,
-    list_articles,
-    list_bookmarks,
+    get_app_data_dir,
     list_bookmarks_for_book,
-    list_favorite_grammars,
-    list_favorite_vocabularies,
     list_word_packs,
+    load_all_article_entries,
+    load_all_article_recaps,
+    load_all_article_revisions,
+    load_all_articles,
+    load_all_bookmarks,
+    load_all_channel_subscriptions,
+    load_all_export_templates,
+    load_all_failed_captures,
+    load_all_favorite_grammars,
+    load_all_favorite_vocabularies,
+    load_all_glossary_entries,
+    load_all_known_words,
+    load_all_quarantined_results,
+    load_all_review_log_entries,
+    load_all_tag_rules,
+    load_all_vocabulary_encounters,
+    load_all_word_packs_json,
     load_article,
+    load_article_recap,
+    load_article_revision,
     load_bookmark,
+    load_channel_subscription,
     load_config,
-    load_favorite_grammar,
+    load_export_template,
     load_favorite_vocabulary,
+    load_glossary_entry,
+    load_meta,
+    load_quarantined_result,
+    load_tag_rule,
+    load_translation_progress,
+    load_translation_qa,
     load_word_pack,
     save_article,
+    save_article_recap,
+    save_article_revision,
     // 书签存储函数
     save_bookmark,
+    save_channel_subscription,
     save_config,
+    save_export_template,
+    save_failed_capture,
     save_favorite_grammar,
     // 收藏夹存储函数
     save_favorite_vocabulary,
+    save_glossary_entry,
+    save_known_word,
+    save_meta,
+    save_quarantined_result,
+    save_review_log_entry,
+    save_tag_rule,
+    save_translation_progress,
+    save_translation_qa,
+    save_vocabulary_encounter,
     save_word_pack,
 };
+use crate::subtitle_extraction::TimeRange;
+use crate::tag_rules;
+use crate::transcription::{
+    create_engine, resolve_engine_id, TranscriptionEngine, TRANSCRIPTION_ENGINES,
+};
+use crate::translation_memory;
+use crate::translation_qa;
+use crate::tts;
 use crate::types::{
-    AnalysisRequest, AnalysisResponse, AnalysisType, Article, ArticleSegment, Bookmark,
-    ChatRequest, ChatResponse, FavoriteGrammar, FavoriteVocabulary, ModelConfig,
-    TranslationRequest, TranslationResponse, WordPack,
+    AiTask, AnalysisRequest, AnalysisResponse, AnalysisType, Article, ArticleAnalysis,
+    ArticleDifficulty, ArticleRecap, ArticleRevision, ArticleRevisionInfo, ArticleSegment,
+    ArticleUpgradeFailure, ArticleUpgradeReport, Bookmark, ChannelImportResult,
+    ChannelSubscription, ChatRequest, ChatResponse, ChunkStatus, DailyReviewSummary, DiffLine,
+    EmbeddedSubtitleTrack, ExportTemplate, ExportTemplateKind, FailedCapture, FailedChannelVideo,
+    FailedVideoImport, FavoriteGrammar, FavoriteVocabulary, GlossaryEntry, GrammarPoint,
+    GrammarUsage, ImportVideoFolderResult, KnownWord, ModelConfig, ModelMetadata, MoveDirection,
+    PackSplitKey, PreReadingList, ProcessingStatus, ProcessingTask, QuarantineKind,
+    QuarantinedResult, ReadingEngine, RequestEstimate, RetentionBucket, RetentionStats,
+    RetryCapturesResult, ReviewLogEntry, SegmentDifficulty, SegmentSelection, SegmentationMode,
+    SrsMaintenanceSummary, StudyPlan, TagRule, TagRuleField, TemplateField, TitleGeneration,
+    TranslationChunkStatus, TranslationProgress, TranslationQaReport, TranslationQuality,
+    TranslationRequest, TranslationResponse, TtsPlaylist, TtsPlaylistItem, UndoActionInfo,
+    VocabularyEncounter, VocabularyItem, WaveformPeaks, WindowKind, WindowRoute, WordPack,
+    YoutubeQuality, YoutubeSubtitleOptions, YoutubeSubtitleTrack,
 };
+use crate::undo::{UndoAction, UndoJournal};
+use crate::vocabulary_images;
+use crate::web_images::{copy_article_images, delete_article_images, replace_img_tags};
+use crate::windows::{emit_article_event, WindowRegistry};
+use crate::word_density::{count_words, KnownWordSet};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager, State};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 pub type AppState<'a> = State<'a, AIServiceCache>;
 
 // Helper function to create segments from content
-// 按句子分隔内容（使用.或。作为分隔符），并标记是否需要换行
-fn create_segments_from_content(article_id: &str, content: &str) -> Vec<ArticleSegment> {
-    let mut segments = Vec::new();
-    let mut order = 0;
-
-    // 首先按段落分割（双换行或单换行）
-    let paragraphs: Vec<&str> = content
-        .split('\n')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    for paragraph in paragraphs {
-        // 将段落按句子分割（使用 . 或 。 作为分隔符）
-        // 使用正则表达式保留分隔符
-        let sentences = split_into_sentences(paragraph);
-
-        for (sentence_index, sentence) in sentences.iter().enumerate() {
-            let text = sentence.trim();
-            if text.is_empty() {
-                continue;
-            }
-
-            segments.push(ArticleSegment {
-                id: Uuid::new_v4().to_string(),
-                article_id: article_id.to_string(),
-                order,
-                text: text.to_string(),
-                reading_text: None,
-                translation: None,
-                explanation: None,
-                start_time: None,
-                end_time: None,
-                created_at: chrono::Utc::now().to_rfc3339(),
-                // 段落的第一个句子需要换行显示，后续句子紧跟前一个显示
-                is_new_paragraph: sentence_index == 0,
-            });
-            order += 1;
-        }
-    }
-
-    segments
-}
-
-/// 将段落拆分成句子，保留句末标点
-/// 支持英文句号(.)、中文句号(。)、问号(?/？)、感叹号(!/！)
-fn split_into_sentences(text: &str) -> Vec<String> {
-    let mut sentences = Vec::new();
-    let mut current = String::new();
-    let chars: Vec<char> = text.chars().collect();
-
-    let mut i = 0;
-    while i < chars.len() {
-        let c = chars[i];
-        current.push(c);
-
-        // 检查是否是句子结束符
-        let is_sentence_end = c == '。'
-            || c == '？'
-            || c == '！'
-            || (c == '.' && !is_abbreviation(&chars, i))
-            || c == '?'
-            || c == '!';
-
-        if is_sentence_end {
-            // 处理引号闭合情况：如 ... said." 这种情况
-            // 向后看，如果下一个字符是引号，把它也加进来
-            if i + 1 < chars.len() {
-                let next = chars[i + 1];
-                if next == '"'
-                    || next == '"'
-                    || next == '\''
-                    || next == '\u{2019}'
-                    || next == ')'
-                    || next == '）'
-                {
-                    i += 1;
-                    current.push(next);
-                }
-            }
-
-            let trimmed = current.trim().to_string();
-            if !trimmed.is_empty() {
-                sentences.push(trimmed);
-            }
-            current = String::new();
+// 按分段方式切分内容（分句规则随原文语言而定），并标记是否需要换行
+fn create_segments_from_content(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+    language: Option<&str>,
+    mode: Option<&SegmentationMode>,
+) -> Vec<ArticleSegment> {
+    let mode = mode.cloned().unwrap_or_default();
+    let mut pieces = segmentation::split_content(content, language, &mode);
+    // 按句分段时，超过设置长度的长句在逗号、连词处继续拆开
+    if mode == SegmentationMode::Sentence {
+        let max_chars = load_config(app_handle)
+            .ok()
+            .flatten()
+            .and_then(|config| config.max_segment_chars);
+        if let Some(max_chars) = max_chars {
+            pieces = segmentation::limit_piece_length(pieces, max_chars);
         }
-
-        i += 1;
-    }
-
-    // 处理剩余内容（没有句号结尾的情况）
-    let trimmed = current.trim().to_string();
-    if !trimmed.is_empty() {
-        sentences.push(trimmed);
-    }
-
-    // 如果整个段落没有分割成功（没有找到分隔符），返回整段
-    if sentences.is_empty() && !text.trim().is_empty() {
-        sentences.push(text.trim().to_string());
     }
+    segments_from_pieces(article_id, pieces)
+}
 
-    sentences
+fn segments_from_pieces(
+    article_id: &str,
+    pieces: Vec<segmentation::SegmentPiece>,
+) -> Vec<ArticleSegment> {
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(order, piece)| ArticleSegment {
+            id: Uuid::new_v4().to_string(),
+            article_id: article_id.to_string(),
+            order: order as i32,
+            text: piece.text,
+            reading_text: None,
+            translation: None,
+            explanation: None,
+            start_time: None,
+            end_time: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            is_new_paragraph: piece.is_new_paragraph,
+            image_url: piece.image_url,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: None,
+        })
+        .collect()
 }
 
-/// 检查句点是否是缩写的一部分（如 Mr. Mrs. Dr. U.S. 等）
-/// 简单的启发式规则
-fn is_abbreviation(chars: &[char], pos: usize) -> bool {
-    // 如果句点后面紧跟字母，可能是缩写 (如 U.S.A)
-    if pos + 1 < chars.len() && chars[pos + 1].is_alphabetic() {
-        return true;
+/// 新建或重新分段时切分文章：语义分段交给模型；按句分段且设置了该语言的分段插件时
+/// 交给插件（含插图的文章和插件失败时退回内置规则）；其余按规则切分
+async fn create_article_segments(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+    language: Option<&str>,
+    mode: Option<&SegmentationMode>,
+) -> Result<Vec<ArticleSegment>, AppError> {
+    if mode == Some(&SegmentationMode::Semantic) {
+        return create_semantic_segments(app_handle, article_id, content, language).await;
     }
 
-    // 检查句点前是否是常见缩写
-    // 向前查找单词
-    let mut word = String::new();
-    let mut j = pos as i32 - 1;
-    while j >= 0 && chars[j as usize].is_alphabetic() {
-        word.insert(0, chars[j as usize]);
-        j -= 1;
+    let plugin = load_config(app_handle)?.and_then(|config| {
+        config
+            .segmentation_plugin(language)
+            .map(|plugin| plugin.to_string())
+    });
+    if let (Some(plugin), Some(language)) = (plugin, language) {
+        let sentence_mode = matches!(mode, None | Some(SegmentationMode::Sentence));
+        let has_images =
+            segmentation::split_content(content, Some(language), &SegmentationMode::Paragraph)
+                .iter()
+                .any(|piece| piece.image_url.is_some());
+        if sentence_mode && !has_images {
+            match crate::segmentation_plugin::split_with_plugin(
+                app_handle, &plugin, language, content,
+            )
+            .await
+            {
+                Ok(pieces) => return Ok(segments_from_pieces(article_id, pieces)),
+                Err(e) => eprintln!(
+                    "[Article] Segmentation plugin {} failed, using built-in rules: {}",
+                    plugin, e
+                ),
+            }
+        }
     }
+    Ok(create_segments_from_content(
+        app_handle, article_id, content, language, mode,
+    ))
+}
 
-    let word_lower = word.to_lowercase();
-    let abbreviations = [
-        "mr", "mrs", "ms", "dr", "jr", "sr", "vs", "etc", "inc", "ltd", "no", "st", "ave", "rd",
-    ];
+/// 语义分段单次请求的片段数
+const SEMANTIC_BATCH_UNITS: usize = 200;
 
-    if abbreviations.contains(&word_lower.as_str()) {
-        return true;
-    }
+/// 语义分段：先按规则切成小单元，再由模型按意思边界组合成学习单元
+/// 使用整篇翻译的模型；单元较多时分批请求，每批的第一个单元总是开始新段落
+async fn create_semantic_segments(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+    language: Option<&str>,
+) -> Result<Vec<ArticleSegment>, AppError> {
+    let units = segmentation::semantic_units(content, language);
+    let text_units: Vec<(usize, &str)> = units
+        .iter()
+        .enumerate()
+        .filter(|(_, unit)| unit.image_url.is_none())
+        .map(|(index, unit)| (index, unit.text.as_str()))
+        .collect();
 
-    // 单字母后跟句点通常是缩写（如 A. B. C.）
-    if word.len() == 1 && word.chars().next().unwrap().is_uppercase() {
-        return true;
+    let cache = app_handle.state::<AIServiceCache>();
+    let ai_service = get_ai_service_for_task(&cache, AiTask::BatchTranslation).await?;
+    let mut starts = Vec::new();
+    for batch in text_units.chunks(SEMANTIC_BATCH_UNITS) {
+        starts.push(batch[0].0);
+        starts.extend(
+            ai_service
+                .semantic_breaks(batch)
+                .await
+                .map_err(AppError::ai_provider)?,
+        );
     }
+    println!(
+        "[Article] Semantic segmentation: {} units, {} breaks",
+        units.len(),
+        starts.len()
+    );
 
-    false
+    Ok(segments_from_pieces(
+        article_id,
+        segmentation::group_semantic_units(units, &starts),
+    ))
 }
 
 const DEFAULT_UNGROUPED_PACK_ID: &str = "system-ungrouped";
@@ -216,6 +318,8 @@ struct WordPackExportEntry {
     #[serde(default)]
     reading: Option<String>,
     #[serde(default)]
+    phonetic: Option<String>,
+    #[serde(default)]
     explanation: Option<String>,
     #[serde(default)]
     tags: Vec<String>,
@@ -234,6 +338,17 @@ pub struct ExportWordPackResult {
     pub json_content: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportCardBundleResult {
+    /// 导出的包目录
+    pub path: String,
+    pub card_count: usize,
+    pub audio_count: usize,
+    pub image_count: usize,
+    /// 未能合成（离线、未配置模型或合成失败）的音频数
+    pub missing_audio: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportWordPackResult {
     pub created_pack_id: String,
@@ -281,6 +396,7 @@ fn ensure_default_word_pack(app_handle: &AppHandle) -> Result<WordPack, String>
         created_at: now.clone(),
         updated_at: now,
         is_system: true,
+        daily_new_limit: None,
     };
 
     let existing = load_word_pack(app_handle, DEFAULT_UNGROUPED_PACK_ID)
@@ -298,16 +414,10 @@ fn ensure_default_word_pack(app_handle: &AppHandle) -> Result<WordPack, String>
 }
 
 fn load_all_word_packs(app_handle: &AppHandle) -> Result<Vec<WordPack>, String> {
-    let ids = list_word_packs(app_handle)?;
-    let mut packs = Vec::new();
-
-    for id in ids {
-        if let Ok(json) = load_word_pack(app_handle, &id) {
-            if let Ok(pack) = serde_json::from_str::<WordPack>(&json) {
-                packs.push(pack);
-            }
-        }
-    }
+    let mut packs: Vec<WordPack> = load_all_word_packs_json(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<WordPack>(json).ok())
+        .collect();
 
     packs.sort_by(|a, b| a.created_at.cmp(&b.created_at));
     Ok(packs)
@@ -316,16 +426,10 @@ fn load_all_word_packs(app_handle: &AppHandle) -> Result<Vec<WordPack>, String>
 fn load_all_favorite_vocabularies_internal(
     app_handle: &AppHandle,
 ) -> Result<Vec<FavoriteVocabulary>, String> {
-    let ids = list_favorite_vocabularies(app_handle)?;
-    let mut favorites = Vec::new();
-
-    for id in ids {
-        if let Ok(json) = load_favorite_vocabulary(app_handle, &id) {
-            if let Ok(favorite) = serde_json::from_str::<FavoriteVocabulary>(&json) {
-                favorites.push(favorite);
-            }
-        }
-    }
+    let favorites = load_all_favorite_vocabularies(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteVocabulary>(json).ok())
+        .collect();
 
     Ok(favorites)
 }
@@ -367,6 +471,33 @@ fn filter_existing_pack_ids(
     result
 }
 
+/// 按拆分依据把包内单词分组（分组名 -> 单词 ID）；按标签拆分时没有标签的单词留在原包
+pub fn group_pack_vocabularies(
+    favorites: &[FavoriteVocabulary],
+    pack_id: &str,
+    key: PackSplitKey,
+) -> BTreeMap<String, Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for fav in favorites
+        .iter()
+        .filter(|fav| fav.pack_ids.iter().any(|id| id == pack_id))
+    {
+        let group = match key {
+            PackSplitKey::Tag => fav
+                .tags
+                .iter()
+                .map(|tag| tag.trim())
+                .find(|tag| !tag.is_empty())
+                .map(str::to_string),
+            PackSplitKey::Level => Some(fav.srs_state.clone()),
+        };
+        if let Some(group) = group {
+            groups.entry(group).or_default().push(fav.id.clone());
+        }
+    }
+    groups
+}
+
 fn sort_by_due_then_last_review(
     a: &FavoriteVocabulary,
     b: &FavoriteVocabulary,
@@ -479,22 +610,110 @@ pub fn build_due_vocabulary_queue(
     Ok(queue)
 }
 
+/// 学习计划最多预测的天数
+pub const STUDY_PLAN_FORECAST_DAYS: usize = 365;
+
+/// 模拟从 start 起 days 天每天的复习量：假设每次复习都评为 known，每天引入 daily_new 个新词
+/// 已过期的单词计入第一天；新词当天学习，之后按 SM-2 间隔进入复习
+pub fn forecast_daily_reviews(
+    favorites: &[FavoriteVocabulary],
+    start: chrono::NaiveDate,
+    days: usize,
+    daily_new: usize,
+) -> Vec<usize> {
+    struct Card {
+        repetitions: i32,
+        interval_days: i32,
+        ease_factor: f64,
+        due: chrono::NaiveDate,
+    }
+
+    let mut new_remaining = favorites.iter().filter(|f| f.srs_state == "new").count();
+    let mut cards: Vec<Card> = favorites
+        .iter()
+        .filter(|f| f.srs_state != "new")
+        .map(|f| Card {
+            repetitions: f.repetitions,
+            interval_days: f.interval_days,
+            ease_factor: f.ease_factor,
+            due: parse_local_date(&f.due_date).unwrap_or(start).max(start),
+        })
+        .collect();
+
+    let mut forecast = Vec::with_capacity(days);
+    for day in 0..days {
+        let date = start + chrono::Duration::days(day as i64);
+        let mut reviews = 0;
+        for card in cards.iter_mut().filter(|card| card.due <= date) {
+            reviews += 1;
+            if let Ok(next) = calculate_sm2_update(
+                card.repetitions,
+                card.interval_days,
+                card.ease_factor,
+                "known",
+                date,
+            ) {
+                card.repetitions = next.repetitions;
+                card.interval_days = next.interval_days;
+                card.ease_factor = next.ease_factor;
+                card.due = date + chrono::Duration::days(next.interval_days.max(1) as i64);
+            }
+        }
+        forecast.push(reviews);
+
+        let introduced = daily_new.min(new_remaining);
+        new_remaining -= introduced;
+        cards.extend((0..introduced).map(|_| Card {
+            repetitions: 1,
+            interval_days: 1,
+            ease_factor: 2.6,
+            due: date + chrono::Duration::days(1),
+        }));
+    }
+    forecast
+}
+
+/// 计算在 target_date 前学完包内剩余新词的每日节奏，并模拟该节奏下的复习量
+pub fn plan_study_pace(
+    pack_id: &str,
+    favorites: &[FavoriteVocabulary],
+    today: chrono::NaiveDate,
+    target_date: chrono::NaiveDate,
+    review_limit: i32,
+) -> Option<StudyPlan> {
+    let days_remaining = (target_date - today).num_days();
+    if days_remaining < 1 {
+        return None;
+    }
+    let new_count = favorites.iter().filter(|f| f.srs_state == "new").count();
+    let daily_new = new_count.div_ceil(days_remaining as usize);
+    let review_forecast = forecast_daily_reviews(
+        favorites,
+        today,
+        (days_remaining as usize).min(STUDY_PLAN_FORECAST_DAYS),
+        daily_new,
+    );
+    let peak_reviews = review_forecast.iter().copied().max().unwrap_or(0);
+
+    Some(StudyPlan {
+        pack_id: pack_id.to_string(),
+        target_date: target_date.format("%Y-%m-%d").to_string(),
+        days_remaining,
+        new_count,
+        daily_new,
+        review_forecast,
+        peak_reviews,
+        exceeds_review_limit: peak_reviews > review_limit.max(0) as usize,
+        applied: false,
+    })
+}
+
 fn migrate_favorite_vocabularies(app_handle: &AppHandle) -> Result<(), String> {
     let default_pack = ensure_default_word_pack(app_handle)?;
-    let ids = list_favorite_vocabularies(app_handle)?;
+    let favorites = load_all_favorite_vocabularies_internal(app_handle)?;
     let today = today_local_date().format("%Y-%m-%d").to_string();
 
-    for id in ids {
-        let json = match load_favorite_vocabulary(app_handle, &id) {
-            Ok(content) => content,
-            Err(_) => continue,
-        };
-
-        let mut favorite = match serde_json::from_str::<FavoriteVocabulary>(&json) {
-            Ok(item) => item,
-            Err(_) => continue,
-        };
-
+    for mut favorite in favorites {
         let mut changed = false;
 
         if favorite.pack_ids.is_empty() {
@@ -552,23 +771,293 @@ fn migrate_favorite_vocabularies(app_handle: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// ============================================================================
+// SRS Maintenance - 每日维护（数据迁移、过期统计、顽固词识别、复习记录清理）
+// ============================================================================
+
+/// 遗忘次数达到该值视为顽固词（leech）
+pub const LEECH_LAPSE_THRESHOLD: i32 = 8;
+/// 复习记录保留天数
+const REVIEW_LOG_RETENTION_DAYS: i64 = 365;
+/// 检查是否跨天需要再次维护的间隔
+const SRS_MAINTENANCE_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+const SRS_MAINTENANCE_DATE_KEY: &str = "srs_maintenance_last_date";
+const SRS_MAINTENANCE_SUMMARY_KEY: &str = "srs_maintenance_summary";
+
+/// 统计过期积压和顽固词（复习记录清理数由调用方填写）
+pub fn summarize_srs_backlog(
+    favorites: &[FavoriteVocabulary],
+    today: chrono::NaiveDate,
+) -> SrsMaintenanceSummary {
+    let mut summary = SrsMaintenanceSummary::default();
+
+    for favorite in favorites {
+        if favorite.lapses >= LEECH_LAPSE_THRESHOLD {
+            summary.leech_ids.push(favorite.id.clone());
+        }
+
+        if favorite.srs_state == "new" {
+            continue;
+        }
+        let overdue = parse_local_date(&favorite.due_date)
+            .map(|due| due < today)
+            .unwrap_or(false);
+        if !overdue {
+            continue;
+        }
+
+        summary.overdue_count += 1;
+        for pack_id in &favorite.pack_ids {
+            *summary.overdue_by_pack.entry(pack_id.clone()).or_insert(0) += 1;
+        }
+        if summary
+            .oldest_overdue_date
+            .as_ref()
+            .is_none_or(|oldest| &favorite.due_date < oldest)
+        {
+            summary.oldest_overdue_date = Some(favorite.due_date.clone());
+        }
+    }
+
+    summary
+}
+
+/// 删除超过保留期或对应单词已被删除的复习记录，返回删除条数
+fn compact_review_log(
+    app_handle: &AppHandle,
+    favorites: &[FavoriteVocabulary],
+    today: chrono::NaiveDate,
+) -> Result<usize, String> {
+    let existing_ids: HashSet<&str> = favorites.iter().map(|f| f.id.as_str()).collect();
+    let cutoff = today - chrono::Duration::days(REVIEW_LOG_RETENTION_DAYS);
+    let mut removed = 0;
+
+    for json in load_all_review_log_entries(app_handle)? {
+        let Ok(entry) = serde_json::from_str::<ReviewLogEntry>(&json) else {
+            continue;
+        };
+        let expired = parse_local_date(&entry.review_date)
+            .map(|date| date < cutoff)
+            .unwrap_or(true);
+        if expired || !existing_ids.contains(entry.vocabulary_id.as_str()) {
+            delete_review_log_entry(app_handle, &entry.id)?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// 执行一次 SRS 维护，保存结果并发送 srs-maintenance://completed 事件
+fn run_srs_maintenance(app_handle: &AppHandle) -> Result<SrsMaintenanceSummary, String> {
+    let today = today_local_date();
+    let favorites = load_all_favorite_vocabularies_internal(app_handle)?;
+    let mut summary = summarize_srs_backlog(&favorites, today);
+    summary.review_log_removed = compact_review_log(app_handle, &favorites, today)?;
+    summary.ran_at = chrono::Utc::now().to_rfc3339();
+
+    let summary_json = serde_json::to_string(&summary)
+        .map_err(|e| format!("Failed to serialize maintenance summary: {}", e))?;
+    save_meta(app_handle, SRS_MAINTENANCE_SUMMARY_KEY, &summary_json)?;
+    save_meta(
+        app_handle,
+        SRS_MAINTENANCE_DATE_KEY,
+        &today.format("%Y-%m-%d").to_string(),
+    )?;
+
+    println!(
+        "[SRS] Maintenance done: {} overdue, {} leeches, {} review logs removed",
+        summary.overdue_count,
+        summary.leech_ids.len(),
+        summary.review_log_removed
+    );
+    let _ = app_handle.emit("srs-maintenance://completed", &summary);
+    Ok(summary)
+}
+
+/// 启动时执行一次维护，之后每小时检查一次，跨天后再次执行
+pub fn start_srs_maintenance_scheduler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = run_srs_maintenance(&app_handle) {
+            eprintln!("[SRS] Maintenance failed: {}", e);
+        }
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(SRS_MAINTENANCE_CHECK_INTERVAL_SECS)).await;
+
+            let today = today_local_date().format("%Y-%m-%d").to_string();
+            let last_run = load_meta(&app_handle, SRS_MAINTENANCE_DATE_KEY).unwrap_or(None);
+            if last_run.as_deref() == Some(today.as_str()) {
+                continue;
+            }
+            if let Err(e) = run_srs_maintenance(&app_handle) {
+                eprintln!("[SRS] Maintenance failed: {}", e);
+            }
+        }
+    });
+}
+
+/// 手动执行 SRS 维护
+#[tauri::command]
+pub async fn run_srs_maintenance_cmd(
+    app_handle: AppHandle,
+) -> Result<SrsMaintenanceSummary, AppError> {
+    Ok(run_srs_maintenance(&app_handle)?)
+}
+
+/// 获取最近一次 SRS 维护结果，从未执行过时返回 None
+#[tauri::command]
+pub async fn get_srs_maintenance_summary_cmd(
+    app_handle: AppHandle,
+) -> Result<Option<SrsMaintenanceSummary>, AppError> {
+    Ok(load_meta(&app_handle, SRS_MAINTENANCE_SUMMARY_KEY)?
+        .and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+// ============================================================================
+// Article Upgrade - 文章数据升级到最新结构
+// ============================================================================
+
+const ARTICLE_SCHEMA_VERSION_KEY: &str = "article_schema_version";
+const ARTICLE_UPGRADE_REPORT_KEY: &str = "article_upgrade_report";
+
+/// 把全部文章重写为最新结构；被改写和无法解析的文章先备份原 JSON，
+/// 无法解析的文章保持原样，只在结果中报告
+fn run_article_upgrade(app_handle: &AppHandle) -> Result<ArticleUpgradeReport, String> {
+    let ran_at = chrono::Utc::now();
+    let backup_dir = get_app_data_dir(app_handle)?
+        .join(article_upgrade::UPGRADE_BACKUP_DIR)
+        .join(ran_at.format("%Y%m%d-%H%M%S").to_string());
+    let backup = |id: &str, json: &str| -> Result<(), String> {
+        std::fs::create_dir_all(&backup_dir)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+        std::fs::write(backup_dir.join(article_upgrade::backup_file_name(id)), json)
+            .map_err(|e| format!("Failed to back up article {}: {}", id, e))
+    };
+
+    let entries = load_all_article_entries(app_handle)?;
+    let mut report = ArticleUpgradeReport {
+        ran_at: ran_at.to_rfc3339(),
+        schema_version: article_upgrade::ARTICLE_SCHEMA_VERSION,
+        total: entries.len(),
+        ..Default::default()
+    };
+    for (id, json) in entries {
+        match article_upgrade::upgrade_article_json(&json) {
+            Ok(ArticleUpgrade::Unchanged) => report.unchanged += 1,
+            Ok(ArticleUpgrade::Upgraded(upgraded)) => {
+                backup(&id, &json)?;
+                save_article(app_handle, &id, &upgraded)?;
+                report.upgraded += 1;
+            }
+            Err(error) => {
+                backup(&id, &json)?;
+                report.failed.push(ArticleUpgradeFailure { id, error });
+            }
+        }
+    }
+    if backup_dir.exists() {
+        report.backup_dir = Some(backup_dir.to_string_lossy().into_owned());
+    }
+
+    let report_json = serde_json::to_string(&report)
+        .map_err(|e| format!("Failed to serialize upgrade report: {}", e))?;
+    save_meta(app_handle, ARTICLE_UPGRADE_REPORT_KEY, &report_json)?;
+    save_meta(
+        app_handle,
+        ARTICLE_SCHEMA_VERSION_KEY,
+        &article_upgrade::ARTICLE_SCHEMA_VERSION.to_string(),
+    )?;
+
+    println!(
+        "[Storage] Article upgrade done: {} upgraded, {} unchanged, {} failed",
+        report.upgraded,
+        report.unchanged,
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// 启动时检查文章数据版本，存储的版本较旧时自动升级
+pub fn upgrade_articles_if_needed(app_handle: &AppHandle) {
+    let stored = load_meta(app_handle, ARTICLE_SCHEMA_VERSION_KEY)
+        .unwrap_or(None)
+        .and_then(|version| version.parse::<u32>().ok())
+        .unwrap_or(0);
+    if stored >= article_upgrade::ARTICLE_SCHEMA_VERSION {
+        return;
+    }
+    if let Err(e) = run_article_upgrade(app_handle) {
+        eprintln!("[Storage] Article upgrade failed: {}", e);
+    }
+}
+
+/// 手动把全部文章重写为最新结构，返回升级、未改动和失败的数量
+#[tauri::command]
+pub async fn upgrade_articles_cmd(app_handle: AppHandle) -> Result<ArticleUpgradeReport, AppError> {
+    Ok(run_article_upgrade(&app_handle)?)
+}
+
+/// 获取最近一次文章升级结果，从未执行过时返回 None
+#[tauri::command]
+pub async fn get_article_upgrade_report_cmd(
+    app_handle: AppHandle,
+) -> Result<Option<ArticleUpgradeReport>, AppError> {
+    Ok(load_meta(&app_handle, ARTICLE_UPGRADE_REPORT_KEY)?
+        .and_then(|json| serde_json::from_str(&json).ok()))
+}
+
 // Initialize the app (ensure directories exist)
 #[tauri::command]
-pub async fn init_app(app_handle: AppHandle) -> Result<String, String> {
+pub async fn init_app(app_handle: AppHandle) -> Result<String, AppError> {
     ensure_app_dirs(&app_handle)?;
     ensure_favorites_dirs(&app_handle)?;
     let _ = ensure_default_word_pack(&app_handle)?;
     migrate_favorite_vocabularies(&app_handle)?;
+    if let Err(e) = prompt_templates::load_templates(&app_handle) {
+        eprintln!("[PromptTemplates] Failed to load custom templates: {}", e);
+    }
     Ok("App initialized successfully".to_string())
 }
 
+/// 获取全部提示词模板（含内置默认值和可用占位符）
+#[tauri::command]
+pub async fn list_prompt_templates_cmd() -> Result<Vec<PromptTemplateInfo>, AppError> {
+    Ok(PromptKind::ALL
+        .iter()
+        .map(|kind| prompt_templates::template_info(*kind))
+        .collect())
+}
+
+/// 保存自定义提示词模板
+#[tauri::command]
+pub async fn save_prompt_template_cmd(
+    app_handle: AppHandle,
+    kind: PromptKind,
+    template: String,
+) -> Result<PromptTemplateInfo, AppError> {
+    prompt_templates::validate(kind, &template).map_err(AppError::invalid_input)?;
+    prompt_templates::set_template(&app_handle, kind, Some(template))?;
+    Ok(prompt_templates::template_info(kind))
+}
+
+/// 将提示词模板重置为内置默认值
+#[tauri::command]
+pub async fn reset_prompt_template_cmd(
+    app_handle: AppHandle,
+    kind: PromptKind,
+) -> Result<PromptTemplateInfo, AppError> {
+    prompt_templates::set_template(&app_handle, kind, None)?;
+    Ok(prompt_templates::template_info(kind))
+}
+
 // Configuration commands
 #[tauri::command]
 pub async fn get_config(
     app_handle: AppHandle,
     state: AppState<'_>,
-) -> Result<Option<crate::types::AppConfig>, String> {
-    let config = load_config(&app_handle)?;
+) -> Result<Option<crate::types::AppConfig>, AppError> {
+    let config = load_config(&app_handle).map_err(|e| AppError::new(ErrorKind::Config, e))?;
 
     // If we have a config and an active model, ensure AI service is initialized
     if let Some(ref app_config) = config {
@@ -586,6 +1075,7 @@ pub async fn get_config(
                 .await;
             }
         }
+        refresh_task_services(&state, app_config).await;
     }
 
     Ok(config)
@@ -594,19 +1084,119 @@ pub async fn get_config(
 #[tauri::command]
 pub async fn save_config_cmd(
     app_handle: AppHandle,
+    state: AppState<'_>,
     config: crate::types::AppConfig,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     save_config(&app_handle, &config)?;
+    refresh_task_services(&state, &config).await;
     Ok("Configuration saved".to_string())
 }
 
+/// 切换离线模式（只在本次运行内有效），并发送 offline-mode://changed 事件
+#[tauri::command]
+pub async fn set_offline_mode_cmd(app_handle: AppHandle, enabled: bool) -> Result<bool, AppError> {
+    offline::set_offline_mode(enabled);
+    let _ = app_handle.emit("offline-mode://changed", enabled);
+    Ok(enabled)
+}
+
+#[tauri::command]
+pub async fn get_offline_mode_cmd() -> Result<bool, AppError> {
+    Ok(offline::is_offline())
+}
+
+// ============================================================================
+// Windows - 阅读器、复习、统计的独立窗口
+// ============================================================================
+
+/// 在独立窗口打开阅读器（需传 article_id）、复习或统计界面；窗口已打开时聚焦到该窗口
+#[tauri::command]
+pub async fn open_window_cmd(
+    app_handle: AppHandle,
+    windows: State<'_, WindowRegistry>,
+    kind: WindowKind,
+    article_id: Option<String>,
+) -> Result<WindowRoute, AppError> {
+    let article_id = match kind {
+        WindowKind::Reader => Some(
+            article_id
+                .filter(|id| !id.trim().is_empty())
+                .ok_or_else(|| AppError::invalid_input(tr("error.window_article_required")))?,
+        ),
+        WindowKind::Review | WindowKind::Stats => None,
+    };
+    let route = WindowRoute {
+        label: crate::windows::window_label(kind, article_id.as_deref()),
+        kind,
+        article_id,
+    };
+
+    if let Some(window) = app_handle.get_webview_window(&route.label) {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        return Ok(windows.route(&route.label).unwrap_or(route));
+    }
+
+    let title = match &route.article_id {
+        Some(id) => get_article(app_handle.clone(), id.clone()).await?.title,
+        None => tr(&format!("window.{}", kind.as_str())),
+    };
+    let window = tauri::WebviewWindowBuilder::new(
+        &app_handle,
+        route.label.clone(),
+        tauri::WebviewUrl::App(crate::windows::window_url(&route).into()),
+    )
+    .title(title)
+    .inner_size(1000.0, 720.0)
+    .min_inner_size(600.0, 400.0)
+    .build()
+    .map_err(|e| format!("Failed to open window: {}", e))?;
+
+    windows.insert(route.clone());
+    let registry = windows.inner().clone();
+    let label = route.label.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            registry.remove(&label);
+        }
+    });
+    Ok(route)
+}
+
+/// 当前窗口的路由，主窗口返回 None
+#[tauri::command]
+pub async fn get_window_route_cmd(
+    window: tauri::WebviewWindow,
+    windows: State<'_, WindowRegistry>,
+) -> Result<Option<WindowRoute>, AppError> {
+    Ok(windows.route(window.label()))
+}
+
+/// 阅读器窗口切换到另一篇文章，之后只接收新文章的进度事件
+#[tauri::command]
+pub async fn set_window_article_cmd(
+    window: tauri::WebviewWindow,
+    windows: State<'_, WindowRegistry>,
+    article_id: Option<String>,
+) -> Result<bool, AppError> {
+    Ok(windows.set_article(window.label(), article_id))
+}
+
+/// 列出已打开的独立窗口
+#[tauri::command]
+pub async fn list_windows_cmd(
+    windows: State<'_, WindowRegistry>,
+) -> Result<Vec<WindowRoute>, AppError> {
+    Ok(windows.list())
+}
+
 /// Add or update a model configuration
 #[tauri::command]
 pub async fn save_model_config(
     app_handle: AppHandle,
     state: AppState<'_>,
     config: ModelConfig,
-) -> Result<ModelConfig, String> {
+) -> Result<ModelConfig, AppError> {
     let mut app_config = load_config(&app_handle)?.unwrap_or_default();
 
     // Check if this is an update or new config
@@ -647,13 +1237,18 @@ pub async fn save_model_config(
         )
         .await?;
     }
+    refresh_task_services(&state, &app_config).await;
 
     Ok(config)
 }
 
 /// Delete a model configuration
 #[tauri::command]
-pub async fn delete_model_config(app_handle: AppHandle, config_id: String) -> Result<(), String> {
+pub async fn delete_model_config(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    config_id: String,
+) -> Result<(), AppError> {
     let mut app_config = load_config(&app_handle)?.unwrap_or_default();
 
     // Remove the config
@@ -661,7 +1256,7 @@ pub async fn delete_model_config(app_handle: AppHandle, config_id: String) -> Re
     app_config.model_configs.retain(|c| c.id != config_id);
 
     if app_config.model_configs.len() == original_len {
-        return Err("Configuration not found".to_string());
+        return Err(AppError::not_found(tr("error.config_not_found")));
     }
 
     // If we deleted the active config, set a new active one
@@ -669,7 +1264,22 @@ pub async fn delete_model_config(app_handle: AppHandle, config_id: String) -> Re
         app_config.active_model_id = app_config.model_configs.first().map(|c| c.id.clone());
     }
 
+    // 指向已删除配置的任务改回使用活动模型
+    let routing = &mut app_config.task_models;
+    for model_id in [
+        &mut routing.batch_translation,
+        &mut routing.segment_explanation,
+        &mut routing.subtitle_transcription,
+        &mut routing.chat,
+        &mut routing.translation_review,
+    ] {
+        if model_id.as_deref() == Some(config_id.as_str()) {
+            *model_id = None;
+        }
+    }
+
     save_config(&app_handle, &app_config)?;
+    refresh_task_services(&state, &app_config).await;
     Ok(())
 }
 
@@ -679,7 +1289,7 @@ pub async fn set_active_model_config(
     app_handle: AppHandle,
     state: AppState<'_>,
     config_id: String,
-) -> Result<ModelConfig, String> {
+) -> Result<ModelConfig, AppError> {
     let mut app_config = load_config(&app_handle)?.unwrap_or_default();
 
     let config = app_config
@@ -700,13 +1310,16 @@ pub async fn set_active_model_config(
         config.base_url.clone(),
     )
     .await?;
+    refresh_task_services(&state, &app_config).await;
 
     Ok(config)
 }
 
 /// Get the active model configuration
 #[tauri::command]
-pub async fn get_active_model_config(app_handle: AppHandle) -> Result<Option<ModelConfig>, String> {
+pub async fn get_active_model_config(
+    app_handle: AppHandle,
+) -> Result<Option<ModelConfig>, AppError> {
     let app_config = load_config(&app_handle)?.unwrap_or_default();
     Ok(app_config.get_active_config().cloned())
 }
@@ -719,7 +1332,7 @@ pub async fn set_api_key(
     api_key: String,
     provider: String,
     model: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let mut app_config = load_config(&app_handle)?.unwrap_or_default();
 
     // Create a default config name
@@ -769,54 +1382,228 @@ pub async fn set_api_key(
         config.base_url.clone(),
     )
     .await?;
+    refresh_task_services(&state, &app_config).await;
 
     Ok("API key saved successfully".to_string())
 }
 
-// Article commands
+/// 设置监视文件夹：之后放入其中的文本、书籍和音视频文件自动导入；folder_path 为空时停止监视
 #[tauri::command]
-pub async fn create_article(
+pub async fn set_watch_folder_cmd(
     app_handle: AppHandle,
-    title: String,
-    content: String,
-    source_url: Option<String>,
-) -> Result<Article, String> {
-    let id = Uuid::new_v4().to_string();
-    let created_at = chrono::Utc::now().to_rfc3339();
-
-    let segments = create_segments_from_content(&id, &content);
-
-    let article = Article {
-        id: id.clone(),
-        title: title.clone(),
-        content: content.clone(),
-        source_type: Some("article".to_string()),
-        source_url: source_url.clone(),
-        media_path: None,
-        book_path: None,
-        book_type: None,
-        created_at: created_at.clone(),
-        translated: false,
-        segments,
-    };
+    watcher: State<'_, FolderWatcher>,
+    folder_path: Option<String>,
+) -> Result<(), AppError> {
+    let folder_path = folder_path.filter(|path| !path.trim().is_empty());
+    if let Some(path) = &folder_path {
+        if !std::path::Path::new(path).is_dir() {
+            return Err(AppError::not_found(tr_with(
+                "error.folder_missing",
+                &[("path", path.clone())],
+            )));
+        }
+    }
 
-    // Save article metadata and content
-    let article_json = serde_json::to_string(&article).unwrap();
-    save_article(&app_handle, &id, &article_json)?;
+    watcher.watch(
+        &app_handle,
+        folder_path.as_deref().map(std::path::Path::new),
+    )?;
 
-    Ok(article)
+    let mut config = load_config(&app_handle)?.unwrap_or_default();
+    config.watch_folder = folder_path;
+    save_config(&app_handle, &config)?;
+    Ok(())
+}
+
+/// 设置中选择的当前学习语言，新建的文章、单词和单词包默认归入该语言
+fn active_learning_language(app_handle: &AppHandle) -> Option<String> {
+    load_config(app_handle)
+        .ok()
+        .flatten()
+        .and_then(|config| config.active_learning_language)
+        .filter(|language| !language.trim().is_empty())
+}
+
+/// 未填写标题时生成标题：按设置由模型概括或取第一句，模型不可用时退回第一句
+async fn generate_article_title(
+    app_handle: &AppHandle,
+    content: &str,
+    language: Option<&str>,
+) -> String {
+    let mode = load_config(app_handle)
+        .ok()
+        .flatten()
+        .map(|config| config.title_generation)
+        .unwrap_or_default();
+    if mode == TitleGeneration::Ai {
+        let cache = app_handle.state::<AIServiceCache>();
+        if let Ok(ai_service) = get_ai_service_for_task(&cache, AiTask::BatchTranslation).await {
+            match ai_service.generate_title(content).await {
+                Ok(title) => return title,
+                Err(e) => eprintln!("[create_article] title generation failed: {}", e),
+            }
+        }
+    }
+    segmentation::title_from_content(content, language).unwrap_or_else(|| "Untitled".to_string())
+}
+
+/// 检测文章原文语言：有分段时用分段文本（字幕等），否则用正文
+fn detect_article_language(article: &Article) -> Option<String> {
+    let text = if article.segments.is_empty() {
+        article.content.clone()
+    } else {
+        article
+            .segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    detect_language(&text).map(str::to_string)
+}
+
+/// 重新检测文章语言并保存（用于旧文章或检测有误时）
+#[tauri::command]
+pub async fn detect_article_language_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+    article.language = Some(
+        detect_article_language(&article)
+            .ok_or_else(|| AppError::invalid_input(tr("error.language_undetected")))?,
+    );
+
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article.id, &updated_json)?;
+
+    Ok(article)
+}
+
+/// 按学习语言过滤：未指定过滤语言，或条目未标记语言（旧数据）时都视为匹配
+pub fn matches_learning_language(item_language: Option<&str>, filter: Option<&str>) -> bool {
+    match (item_language, filter) {
+        (Some(item), Some(filter)) => item.eq_ignore_ascii_case(filter),
+        _ => true,
+    }
+}
+
+/// 固定长度分段的长度必须大于 0
+fn validate_segmentation(segmentation: Option<&SegmentationMode>) -> Result<(), AppError> {
+    match segmentation {
+        Some(SegmentationMode::FixedLength { max_chars: 0 }) => {
+            Err(AppError::invalid_input(tr("error.segment_length_invalid")))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Article commands
+#[tauri::command]
+pub async fn create_article(
+    app_handle: AppHandle,
+    title: String,
+    content: String,
+    source_url: Option<String>,
+    language: Option<String>,
+    segmentation: Option<SegmentationMode>,
+) -> Result<Article, AppError> {
+    validate_segmentation(segmentation.as_ref())?;
+    let id = Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+
+    // 未指定语言时按内容检测，检测不出再归入当前学习语言
+    let language = language
+        .or_else(|| detect_language(&content).map(str::to_string))
+        .or_else(|| active_learning_language(&app_handle));
+    let title = if title.trim().is_empty() {
+        generate_article_title(&app_handle, &content, language.as_deref()).await
+    } else {
+        title
+    };
+    let segments = create_article_segments(
+        &app_handle,
+        &id,
+        &content,
+        language.as_deref(),
+        segmentation.as_ref(),
+    )
+    .await?;
+
+    let mut article = Article {
+        id: id.clone(),
+        title: title.clone(),
+        content: content.clone(),
+        source_type: Some("article".to_string()),
+        source_url: source_url.clone(),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language,
+        created_at: created_at.clone(),
+        translated: false,
+        translation_language: None,
+        segmentation,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments,
+    };
+    finish_article_import(&app_handle, &mut article);
+
+    // Save article metadata and content
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &id, &article_json)?;
+
+    Ok(article)
+}
+
+/// 除图片外的段落都已有译文时，文章视为已翻译
+fn segments_translated(segments: &[ArticleSegment]) -> bool {
+    !segments.is_empty()
+        && segments
+            .iter()
+            .all(|segment| segment.image_url.is_some() || segment.translation.is_some())
 }
 
 #[tauri::command]
 pub async fn resegment_article(
     app_handle: AppHandle,
     article_id: String,
-) -> Result<Article, String> {
+    segmentation: Option<SegmentationMode>,
+) -> Result<Article, AppError> {
+    validate_segmentation(segmentation.as_ref())?;
     let article_json = load_article(&app_handle, &article_id)?;
     let mut article: Article = serde_json::from_str(&article_json)
         .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    article.segments = create_segments_from_content(&article.id, &article.content);
+    snapshot_article_revision(&app_handle, &article)?;
+    // 指定的分段方式记录在文章上，之后重新分段时沿用
+    if segmentation.is_some() {
+        article.segmentation = segmentation;
+    }
+    let mut segments = create_article_segments(
+        &app_handle,
+        &article.id,
+        &article.content,
+        article.language.as_deref(),
+        article.segmentation.as_ref(),
+    )
+    .await?;
+    // 文本未变的段落保留已有的译文和讲解
+    let carried = segmentation::carry_over_segments(&article.segments, &mut segments);
+    println!(
+        "[Article] Resegmented {}: {} segments, {} carried over",
+        article.id,
+        segments.len(),
+        carried
+    );
+    article.segments = segments;
+    article.translated = segments_translated(&article.segments);
 
     let updated_json = serde_json::to_string(&article).unwrap();
     save_article(&app_handle, &article.id, &updated_json)?;
@@ -825,25 +1612,26 @@ pub async fn resegment_article(
 }
 
 #[tauri::command]
-pub async fn get_article(app_handle: AppHandle, id: String) -> Result<Article, String> {
+pub async fn get_article(app_handle: AppHandle, id: String) -> Result<Article, AppError> {
     let article_json = load_article(&app_handle, &id)?;
     let article: Article = serde_json::from_str(&article_json)
         .map_err(|e| format!("Failed to parse article: {}", e))?;
     Ok(article)
 }
 
+/// 列出文章；指定 language 时只返回该学习语言（及未标记语言）的文章
 #[tauri::command]
-pub async fn list_articles_cmd(app_handle: AppHandle) -> Result<Vec<Article>, String> {
-    let article_ids = list_articles(&app_handle)?;
-
-    let mut articles = Vec::new();
-    for id in article_ids {
-        if let Ok(article_json) = load_article(&app_handle, &id) {
-            if let Ok(article) = serde_json::from_str::<Article>(&article_json) {
-                articles.push(article);
-            }
-        }
-    }
+pub async fn list_articles_cmd(
+    app_handle: AppHandle,
+    language: Option<String>,
+) -> Result<Vec<Article>, AppError> {
+    let mut articles: Vec<Article> = load_all_articles(&app_handle)?
+        .iter()
+        .filter_map(|article_json| serde_json::from_str::<Article>(article_json).ok())
+        .filter(|article| {
+            matches_learning_language(article.language.as_deref(), language.as_deref())
+        })
+        .collect();
 
     // Sort by created_at (newest first)
     articles.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -859,7 +1647,8 @@ pub async fn update_article(
     content: Option<String>,
     source_url: Option<String>,
     translated: Option<bool>,
-) -> Result<Article, String> {
+    language: Option<String>,
+) -> Result<Article, AppError> {
     let article_json = load_article(&app_handle, &id)?;
     let mut article: Article = serde_json::from_str(&article_json)
         .map_err(|e| format!("Failed to parse article: {}", e))?;
@@ -868,6 +1657,9 @@ pub async fn update_article(
         article.title = t;
     }
     if let Some(c) = content {
+        if c != article.content {
+            snapshot_article_revision(&app_handle, &article)?;
+        }
         article.content = c;
     }
     if let Some(s) = source_url {
@@ -876,6 +1668,9 @@ pub async fn update_article(
     if let Some(t) = translated {
         article.translated = t;
     }
+    if let Some(l) = language {
+        article.language = Some(l);
+    }
 
     let updated_json = serde_json::to_string(&article).unwrap();
     save_article(&app_handle, &id, &updated_json)?;
@@ -884,1701 +1679,7956 @@ pub async fn update_article(
 }
 
 #[tauri::command]
-pub async fn delete_article_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
+pub async fn delete_article_cmd(app_handle: AppHandle, id: String) -> Result<(), AppError> {
+    if let Ok(article) = serde_json::from_str::<Article>(&load_article(&app_handle, &id)?) {
+        update_corpus_counts(&app_handle, &article, true);
+    }
     delete_article(&app_handle, &id)?;
+    delete_article_recap(&app_handle, &id)?;
+    delete_translation_progress(&app_handle, &id)?;
+    delete_translation_qa(&app_handle, &id)?;
+    for revision in load_article_revisions(&app_handle, &id)? {
+        delete_article_revision(&app_handle, &revision.id)?;
+    }
+    for entry in list_quarantined_results_cmd(app_handle.clone(), Some(id.clone())).await? {
+        delete_quarantined_result(&app_handle, &entry.id)?;
+    }
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    delete_article_images(&app_data_dir, &id);
+    crate::subtitle_checkpoint::remove_checkpoint(&app_data_dir, &id);
+    crate::youtube::remove_thumbnail(&app_data_dir, &id);
     Ok(())
 }
 
+/// 按 ids 的顺序把多篇文章合并为一篇新文章（分多次导入的连载、分块的转录稿），
+/// title 为空时沿用第一篇的标题；delete_originals 为 true 时合并后删除原文章
 #[tauri::command]
-pub async fn update_article_segment(
+pub async fn merge_articles_cmd(
     app_handle: AppHandle,
-    article_id: String,
-    segment_id: String,
-    explanation: Option<crate::types::SegmentExplanation>,
-    reading: Option<String>,
-    translation: Option<String>,
-) -> Result<Article, String> {
-    let article_json = load_article(&app_handle, &article_id)?;
-    let mut article: Article = serde_json::from_str(&article_json)
-        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    ids: Vec<String>,
+    title: String,
+    delete_originals: Option<bool>,
+) -> Result<Article, AppError> {
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+    if ids.len() < 2 {
+        return Err(AppError::invalid_input(tr(
+            "error.merge_needs_two_articles",
+        )));
+    }
 
-    if let Some(segment) = article.segments.iter_mut().find(|s| s.id == segment_id) {
-        if let Some(exp) = explanation {
-            segment.explanation = Some(exp);
+    let mut parts = Vec::new();
+    for id in &ids {
+        let mut article = get_article(app_handle.clone(), id.clone()).await?;
+        if article.segments.is_empty() {
+            article.segments = create_segments_from_content(
+                &app_handle,
+                &article.id,
+                &article.content,
+                article.language.as_deref(),
+                article.segmentation.as_ref(),
+            );
         }
-        if let Some(read) = reading {
-            segment.reading_text = Some(read);
+        parts.push(article);
+    }
+
+    let merged_id = Uuid::new_v4().to_string();
+    let mut merged = crate::article_merge::merge_articles(
+        &parts,
+        &merged_id,
+        &title,
+        &chrono::Utc::now().to_rfc3339(),
+    );
+
+    // 本地图片按原文章 ID 命名，复制一份给新文章，删除原文章时不受影响
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    for (index, part) in parts.iter().enumerate() {
+        for (old_url, new_url) in
+            copy_article_images(&app_data_dir, &part.id, &merged_id, index + 1)
+        {
+            merged.content = merged.content.replace(&old_url, &new_url);
+            for seg in merged.segments.iter_mut() {
+                if seg.image_url.as_deref() == Some(old_url.as_str()) {
+                    seg.image_url = Some(new_url.clone());
+                }
+            }
         }
-        if let Some(trans) = translation {
-            segment.translation = Some(trans);
+    }
+
+    finish_article_import(&app_handle, &mut merged);
+    let article_json = serde_json::to_string(&merged).unwrap();
+    save_article(&app_handle, &merged.id, &article_json)?;
+
+    if delete_originals.unwrap_or(false) {
+        for id in ids {
+            delete_article_cmd(app_handle.clone(), id).await?;
         }
-    } else {
-        return Err("Segment not found".to_string());
     }
+    Ok(merged)
+}
 
-    let updated_json = serde_json::to_string(&article).unwrap();
-    save_article(&app_handle, &article_id, &updated_json)?;
+/// 每篇文章保留的修订数量上限，超出时删除最旧的
+const MAX_ARTICLE_REVISIONS: usize = 20;
 
-    Ok(article)
+/// 读取文章的全部修订，按时间从新到旧排列
+fn load_article_revisions(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Vec<ArticleRevision>, String> {
+    let mut revisions: Vec<ArticleRevision> = load_all_article_revisions(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ArticleRevision>(json).ok())
+        .filter(|revision| revision.article_id == article_id)
+        .collect();
+    revisions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(revisions)
 }
 
-// AI commands
-#[tauri::command]
-pub async fn translate_text(
-    state: AppState<'_>,
-    request: TranslationRequest,
-) -> Result<TranslationResponse, String> {
-    let ai_service = get_ai_service(&state).await?;
-    ai_service.translate(request).await
+fn load_revision_of(
+    app_handle: &AppHandle,
+    article_id: &str,
+    revision_id: &str,
+) -> Result<ArticleRevision, AppError> {
+    load_article_revision(app_handle, revision_id)?
+        .and_then(|json| serde_json::from_str::<ArticleRevision>(&json).ok())
+        .filter(|revision| revision.article_id == article_id)
+        .ok_or_else(|| AppError::not_found(tr("error.revision_not_found")))
+}
+
+/// 在修改内容或分段前保存当前状态，并清理超出上限的旧修订
+fn snapshot_article_revision(app_handle: &AppHandle, article: &Article) -> Result<(), String> {
+    let revision = ArticleRevision {
+        id: Uuid::new_v4().to_string(),
+        article_id: article.id.clone(),
+        title: article.title.clone(),
+        content: article.content.clone(),
+        segments: article.segments.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let revision_json = serde_json::to_string(&revision).unwrap();
+    save_article_revision(app_handle, &revision.id, &revision_json)?;
+
+    for stale in load_article_revisions(app_handle, &article.id)?
+        .iter()
+        .skip(MAX_ARTICLE_REVISIONS)
+    {
+        delete_article_revision(app_handle, &stale.id)?;
+    }
+    Ok(())
 }
 
+/// 列出文章的修订历史（从新到旧）
 #[tauri::command]
-pub async fn analyze_text(
-    state: AppState<'_>,
-    request: AnalysisRequest,
-) -> Result<AnalysisResponse, String> {
-    let ai_service = get_ai_service(&state).await?;
-    ai_service.analyze(request).await
+pub async fn list_article_revisions_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Vec<ArticleRevisionInfo>, AppError> {
+    Ok(load_article_revisions(&app_handle, &article_id)?
+        .into_iter()
+        .map(|revision| ArticleRevisionInfo {
+            id: revision.id,
+            title: revision.title,
+            created_at: revision.created_at,
+            content_length: revision.content.chars().count(),
+            segment_count: revision.segments.len(),
+        })
+        .collect())
 }
 
+/// 按行比较某个修订与当前内容
 #[tauri::command]
-pub async fn chat_completion(
-    state: AppState<'_>,
-    request: ChatRequest,
-) -> Result<ChatResponse, String> {
-    let ai_service = get_ai_service(&state).await?;
-    ai_service.chat(request).await
+pub async fn diff_article_revision_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    revision_id: String,
+) -> Result<Vec<DiffLine>, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    let revision = load_revision_of(&app_handle, &article_id, &revision_id)?;
+    Ok(crate::text_diff::diff_lines(
+        &revision.content,
+        &article.content,
+    ))
 }
 
+/// 恢复到某个修订；恢复前先把当前状态存为新修订，因此恢复本身也可撤销
 #[tauri::command]
-pub async fn stream_chat_completion(
+pub async fn revert_article_revision_cmd(
     app_handle: AppHandle,
-    state: AppState<'_>,
-    request: ChatRequest,
-    event_id: String,
-) -> Result<String, String> {
-    let ai_service = get_ai_service(&state).await?;
+    article_id: String,
+    revision_id: String,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    let revision = load_revision_of(&app_handle, &article_id, &revision_id)?;
+
+    snapshot_article_revision(&app_handle, &article)?;
+    article.title = revision.title;
+    article.content = revision.content;
+    article.segments = revision.segments;
+    article.translated = !article.segments.is_empty()
+        && article
+            .segments
+            .iter()
+            .all(|segment| segment.translation.is_some());
 
-    // Create a callback that emits events to the frontend
-    let app_handle_clone = app_handle.clone();
-    let event_name = format!("chat-stream://{}", event_id);
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article.id, &updated_json)?;
 
-    ai_service
-        .stream_chat(request, move |chunk| {
-            // Emit the chunk to the frontend
-            // We ignore errors here as we can't do much if emission fails
-            let _ = app_handle_clone.emit(&event_name, chunk);
-        })
-        .await
+    Ok(article)
 }
 
+/// 手动把一个段落在 char_offset（按字符计）处拆成两段，并重新编号 order
 #[tauri::command]
-pub async fn segment_translate_explain_cmd(
-    state: AppState<'_>,
-    text: String,
-    target_language: String,
-) -> Result<crate::types::SegmentExplanation, String> {
-    let ai_service = get_ai_service(&state).await?;
-    ai_service
-        .segment_translate_explain(text, target_language)
-        .await
+pub async fn split_segment_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    segment_id: String,
+    char_offset: usize,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+    let index = article
+        .segments
+        .iter()
+        .position(|s| s.id == segment_id)
+        .ok_or_else(|| AppError::not_found(tr("error.segment_not_found")))?;
+    let (first, second) = segmentation::split_segment_at(&article.segments[index], char_offset)
+        .ok_or_else(|| AppError::invalid_input(tr("error.segment_split_invalid")))?;
+
+    let had_translation = article.segments[index].translation.is_some();
+    article.segments[index] = first;
+    article.segments.insert(index + 1, second);
+    segmentation::renumber_segments(&mut article.segments);
+    if had_translation {
+        article.translated = false;
+    }
+
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
+
+    Ok(article)
 }
 
+/// 手动合并相邻段落（如语音识别切碎的 "Hello." / "everyone."），合并后需重新翻译
 #[tauri::command]
-pub async fn translate_article(
+pub async fn merge_segments_cmd(
     app_handle: AppHandle,
-    state: AppState<'_>,
     article_id: String,
-    target_language: String,
-) -> Result<Article, String> {
-    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    segment_ids: Vec<String>,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    // Ensure segments exist
-    if article.segments.is_empty() {
-        article.segments = create_segments_from_content(&article.id, &article.content);
+    if segment_ids
+        .iter()
+        .any(|id| !article.segments.iter().any(|s| &s.id == id))
+    {
+        return Err(AppError::not_found(tr("error.segment_not_found")));
     }
+    if !segmentation::merge_adjacent_segments(&mut article.segments, &segment_ids) {
+        return Err(AppError::invalid_input(tr("error.segment_merge_invalid")));
+    }
+    article.translated = false;
 
-    // 收集需要翻译的段落（没有翻译的）
-    let untranslated: Vec<(String, String)> = article
-        .segments
-        .iter()
-        .filter(|s| s.translation.is_none())
-        .map(|s| (s.id.clone(), s.text.clone()))
-        .collect();
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-    if !untranslated.is_empty() {
-        let ai_service = get_ai_service(&state).await?;
+    Ok(article)
+}
 
-        // 批量翻译（每批最多30条）
-        const BATCH_SIZE: usize = 30;
-        let total_count = untranslated.len();
-        let total_chunks = (total_count + BATCH_SIZE - 1) / BATCH_SIZE;
+/// 在 position 处插入一个新段落（默认空白），position 等于段落数时追加到末尾
+#[tauri::command]
+pub async fn insert_segment_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    position: usize,
+    text: Option<String>,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-        println!(
-            "[Article] Starting quick translation for article: {}, items: {}",
-            article_id, total_count
-        );
+    if position > article.segments.len() {
+        return Err(AppError::invalid_input(tr(
+            "error.segment_position_invalid",
+        )));
+    }
+    let text = text.map(|t| t.trim().to_string()).unwrap_or_default();
+    if !text.is_empty() {
+        article.translated = false;
+    }
+    article.segments.insert(
+        position,
+        ArticleSegment {
+            id: uuid::Uuid::new_v4().to_string(),
+            article_id: article_id.clone(),
+            order: position as i32,
+            text,
+            reading_text: None,
+            translation: None,
+            explanation: None,
+            start_time: None,
+            end_time: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            is_new_paragraph: false,
+            image_url: None,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: None,
+        },
+    );
+    segmentation::renumber_segments(&mut article.segments);
 
-        for (i, chunk) in untranslated.chunks(BATCH_SIZE).enumerate() {
-            println!(
-                "[Article] Translating chunk {}/{} ({} items)...",
-                i + 1,
-                total_chunks,
-                chunk.len()
-            );
-            let batch_items: Vec<(String, String)> = chunk.to_vec();
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-            match ai_service
-                .batch_translate(batch_items, &target_language)
-                .await
-            {
-                Ok(translations) => {
-                    // 将翻译结果写回对应的 segment
-                    for (id, translation) in translations {
-                        if let Some(seg) = article.segments.iter_mut().find(|s| s.id == id) {
-                            seg.translation = Some(translation);
-                        }
-                    }
-                    println!(
-                        "[Article] Chunk {}/{} completed successfully",
-                        i + 1,
-                        total_chunks
-                    );
+    Ok(article)
+}
 
-                    // Emit progress event
-                    let progress = serde_json::json!({
-                        "current": (i + 1) * BATCH_SIZE,
-                        "total": total_count,
-                        "message": format!("Translating chunk {}/{}", i + 1, total_chunks)
-                    });
-                    let _ = app_handle
-                        .emit(&format!("translation-progress://{}", article_id), progress);
-                }
-                Err(e) => {
-                    // 批量翻译失败，记录错误但继续
-                    eprintln!(
-                        "[Article] Batch translation error in chunk {}/{}: {}",
-                        i + 1,
-                        total_chunks,
-                        e
-                    );
-                }
-            }
-        }
-    }
+/// 删除单个段落并重新编号 order，其余段落的译文和讲解保持不变
+#[tauri::command]
+pub async fn delete_segment_cmd(
+    app_handle: AppHandle,
+    undo: State<'_, UndoJournal>,
+    article_id: String,
+    segment_id: String,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    // Emit complete event
-    let _ = app_handle.emit(
-        &format!("translation-progress://{}", article_id),
-        serde_json::json!({
-            "current": untranslated.len(),
-            "total": untranslated.len(),
-            "message": "Translation completed"
-        }),
-    );
+    let index = article
+        .segments
+        .iter()
+        .position(|s| s.id == segment_id)
+        .ok_or_else(|| AppError::not_found(tr("error.segment_not_found")))?;
+    let next_is_new_paragraph = article.segments.get(index + 1).map(|s| s.is_new_paragraph);
+    let removed = segmentation::remove_segment(&mut article.segments, index);
+    article.translated = !article.segments.is_empty()
+        && article
+            .segments
+            .iter()
+            .all(|segment| segment.translation.is_some());
 
-    println!(
-        "[Article] Quick translation completed for article: {}",
-        article_id
-    );
-    article.translated = true;
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-    let article_json = serde_json::to_string(&article).unwrap();
-    save_article(&app_handle, &article_id, &article_json)?;
+    if let Some(segment) = removed {
+        undo.record(
+            &segment.text.clone(),
+            UndoAction::DeleteSegment {
+                article_id: article_id.clone(),
+                index,
+                segment: Box::new(segment),
+                next_is_new_paragraph,
+            },
+            chrono::Utc::now(),
+        );
+    }
 
     Ok(article)
 }
 
+/// 把段落上移或下移一位并重新编号 order
 #[tauri::command]
-pub async fn analyze_article(
+pub async fn move_segment_cmd(
     app_handle: AppHandle,
-    state: AppState<'_>,
     article_id: String,
-    analysis_type: String,
-) -> Result<String, String> {
-    let article = get_article(app_handle.clone(), article_id.clone()).await?;
-
-    let analysis_type = match analysis_type.as_str() {
-        "summary" => AnalysisType::Summary,
-        "key_points" => AnalysisType::KeyPoints,
-        "vocabulary" => AnalysisType::Vocabulary,
-        "grammar" => AnalysisType::Grammar,
-        "full" => AnalysisType::FullAnalysis,
-        _ => return Err("Invalid analysis type".to_string()),
-    };
+    segment_id: String,
+    direction: MoveDirection,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    let request = AnalysisRequest {
-        text: article.content,
-        analysis_type,
-    };
+    let index = article
+        .segments
+        .iter()
+        .position(|s| s.id == segment_id)
+        .ok_or_else(|| AppError::not_found(tr("error.segment_not_found")))?;
+    if !segmentation::move_segment(&mut article.segments, index, direction) {
+        return Err(AppError::invalid_input(tr(
+            "error.segment_position_invalid",
+        )));
+    }
 
-    let response = analyze_text(state, request).await?;
-    Ok(response.result)
-}
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-// Return type for fetch_url_content
-#[derive(serde::Serialize)]
-pub struct FetchedContent {
-    pub title: String,
-    pub content: String,
+    Ok(article)
 }
 
-// Fetch content from a URL
 #[tauri::command]
-pub async fn fetch_url_content(url: String) -> Result<FetchedContent, String> {
-    // Validate URL
-    let parsed_url = url::Url::parse(&url).map_err(|_| "Invalid URL format".to_string())?;
+pub async fn update_article_segment(
+    app_handle: AppHandle,
+    article_id: String,
+    segment_id: String,
+    explanation: Option<crate::types::SegmentExplanation>,
+    reading: Option<String>,
+    translation: Option<String>,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    // Only allow http/https
-    if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
-        return Err("Only HTTP and HTTPS URLs are supported".to_string());
+    if let Some(segment) = article.segments.iter_mut().find(|s| s.id == segment_id) {
+        if let Some(exp) = explanation {
+            // 前端保存的讲解来自 segment_translate_explain_cmd，写入前同样校验；
+            // 讲解使用的母语按设置中的目标语言记录，供重试时使用
+            let language = load_config(&app_handle)?
+                .map(|config| config.target_language)
+                .unwrap_or_default();
+            segment.explanation = Some(checked_explanation(
+                &app_handle,
+                &article_id,
+                &segment_id,
+                &language,
+                exp,
+            )?);
+        }
+        if let Some(read) = reading {
+            segment.reading_text = Some(read);
+        }
+        if let Some(trans) = translation {
+            segment.translation = Some(trans);
+        }
+    } else {
+        return Err(AppError::not_found(tr("error.segment_not_found")));
     }
 
-    // Create HTTP client with timeout
-    let client = Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-    // Fetch the page with better headers to avoid blocking
-    let response = client
-        .get(&url)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8")
-        .header("Accept-Language", "en-US,en;q=0.9,zh-CN;q=0.8,zh;q=0.7")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch URL: {}", e))?;
+    Ok(article)
+}
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+/// 重新翻译单个段落，覆盖已保存的该语言译文
+/// 不查翻译记忆，新译文同时写回翻译记忆，之后相同句子复用修正后的结果
+#[tauri::command]
+pub async fn retranslate_segment_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    article_id: String,
+    segment_id: String,
+    target_language: String,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+    let index = article
+        .segments
+        .iter()
+        .position(|s| s.id == segment_id)
+        .ok_or_else(|| AppError::not_found(tr("error.segment_not_found")))?;
+    if article.segments[index].image_url.is_some() {
+        return Err(AppError::invalid_input(tr("error.segment_not_text")));
     }
 
-    // Get HTML content
-    // Note: readability prefers a "Cursor" or string. We'll get text first.
-    let html = response
-        .text()
+    // 前后各一段作为上下文，帮助模型理解省略和指代
+    let context = [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| article.segments.get(i))
+        .filter(|s| s.image_url.is_none())
+        .map(|s| s.with_speaker_prefix(&s.text))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = article.segments[index].text.clone();
+
+    let ai_service = get_ai_service_for_task(&state, AiTask::BatchTranslation).await?;
+    let entries = glossary::load_entries(&app_handle);
+    let glossary = glossary::matching_entries(&entries, &[&text], &target_language);
+    let response = ai_service
+        .translate(
+            TranslationRequest {
+                text: text.clone(),
+                target_language: target_language.clone(),
+                context: (!context.is_empty()).then_some(context),
+            },
+            &glossary,
+        )
         .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+        .map_err(AppError::ai_provider)?;
+    // 上下文带说话人时模型可能在译文前照抄前缀
+    let translation = article.segments[index]
+        .strip_speaker_prefix(response.translated_text.trim())
+        .to_string();
+    let translation = checked_translation(
+        &app_handle,
+        &article_id,
+        &article.segments[index],
+        &target_language,
+        translation,
+    )?;
 
-    // Pre-process HTML to handle common issues (optional)
-    // For now, feed directly to readability.
+    translation_memory::remember(
+        &app_handle,
+        &text,
+        &target_language,
+        &memory_model_for(ai_service.model(), &entries, &text, &target_language),
+        &translation,
+    );
 
-    // Extract content using readability
-    // This removes ads, sidebars, navigation, and JS.
-    let mut cursor = std::io::Cursor::new(html.as_bytes());
-    let mut title = String::new();
-    let mut content = String::new();
+    let primary_language = article
+        .translation_language
+        .get_or_insert_with(|| target_language.clone())
+        .clone();
+    article.segments[index].set_translation(&target_language, Some(&primary_language), translation);
 
-    // Try readability first
-    if let Ok(extracted) =
-        readability::extractor::extract(&mut cursor, &url::Url::parse(&url).unwrap())
-    {
-        title = extracted.title;
-        content = html_to_text_preserving_layout(&extracted.content);
-    }
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &updated_json)?;
 
-    // Check if we got meaningful content. If not, try fallback selectors.
-    // Uta-net returns very short content (e.g. "Voting thanks") via readability.
-    if content.trim().len() < 200 {
-        if let Some(fallback_content) = try_fallback_extraction(&html) {
-            // If fallback found something substantial, use it
-            if fallback_content.len() > content.len() {
-                content = html_to_text_preserving_layout(&fallback_content);
-                // If title was missing, try to get it again or keep old one
-                if title.is_empty() {
-                    title = extract_title_from_html(&html, &url);
-                }
-            }
-        }
-    }
+    Ok(article)
+}
 
-    // Final check
-    if content.trim().len() < 10 {
-        if content.trim().is_empty() {
-            return Err("Could not extract meaningful content. The page might be empty or require JavaScript interaction that is not supported.".to_string());
+/// 精翻模式：审校一批初译，审校失败时保留初译
+async fn review_chunk(
+    reviewer: &crate::ai_service::AIService,
+    cancel: &CancellationToken,
+    chunk: &[(String, String)],
+    drafts: Vec<(String, String)>,
+    target_language: &str,
+    glossary: &[&GlossaryEntry],
+) -> Vec<(String, String)> {
+    let items: Vec<(String, String, String)> = drafts
+        .iter()
+        .filter_map(|(id, draft)| {
+            chunk
+                .iter()
+                .find(|(source_id, _)| source_id == id)
+                .map(|(_, text)| (id.clone(), text.clone(), draft.clone()))
+        })
+        .collect();
+
+    match run_cancellable(
+        cancel,
+        reviewer.review_translations(items, target_language, glossary),
+    )
+    .await
+    {
+        Ok(refined) => {
+            let mut refined: HashMap<String, String> = refined.into_iter().collect();
+            drafts
+                .into_iter()
+                .map(|(id, draft)| {
+                    let translation = refined
+                        .remove(&id)
+                        .filter(|t| !t.trim().is_empty())
+                        .unwrap_or(draft);
+                    (id, translation)
+                })
+                .collect()
+        }
+        Err(e) => {
+            if !cancel.is_cancelled() {
+                eprintln!(
+                    "[Article] Review pass failed, keeping draft translations: {}",
+                    e
+                );
+            }
+            drafts
         }
     }
+}
 
-    // If title is still empty
-    if title.is_empty() {
-        title = extract_title_from_html(&html, &url);
+/// 翻译记忆键中的模型部分：句子命中术语表时附加术语签名
+fn memory_model_for(
+    model: &str,
+    glossary_entries: &[GlossaryEntry],
+    text: &str,
+    target_language: &str,
+) -> String {
+    let matched = glossary::matching_entries(glossary_entries, &[text], target_language);
+    if matched.is_empty() {
+        model.to_string()
+    } else {
+        format!("{}#{}", model, glossary::glossary_signature(&matched))
     }
-
-    Ok(FetchedContent { title, content })
 }
 
-/// Fallback extraction using CSS selectors for known difficult sites
-fn try_fallback_extraction(html: &str) -> Option<String> {
-    use scraper::{Html, Selector};
+// AI commands
+#[tauri::command]
+pub async fn translate_text(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    request: TranslationRequest,
+) -> Result<TranslationResponse, AppError> {
+    let ai_service = get_ai_service(&state).await?;
+    let entries = glossary::load_entries(&app_handle);
+    let glossary = glossary::matching_entries(&entries, &[&request.text], &request.target_language);
+    ai_service
+        .translate(request, &glossary)
+        .await
+        .map_err(AppError::ai_provider)
+}
 
-    let document = Html::parse_document(html);
+#[tauri::command]
+pub async fn analyze_text(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    request: AnalysisRequest,
+) -> Result<AnalysisResponse, AppError> {
+    let ai_service = get_ai_service(&state).await?;
+    warn_if_exceeds_context(&app_handle, &ai_service, &request.text);
+    ai_service
+        .analyze(request)
+        .await
+        .map_err(AppError::ai_provider)
+}
 
-    // List of selectors to try, in order of preference
-    // #kashi_area: Uta-net
-    // .lyrics_box: common lyrics class
-    // #lyrics: common lyrics id
-    let selectors = vec![
-        "#kashi_area",
-        "div[itemprop='text']", // Generic schema.org text
-        ".lyrics",
-        "#lyrics",
-        ".post-content",
-        "article",
-        "main",
-    ];
+/// 获取 OpenRouter 模型的上下文长度和价格（缓存一天，refresh 为 true 时强制刷新）
+#[tauri::command]
+pub async fn get_openrouter_models_cmd(
+    app_handle: AppHandle,
+    refresh: Option<bool>,
+) -> Result<Vec<ModelMetadata>, AppError> {
+    // 离线且没有缓存时返回离线错误，其余失败都是请求 OpenRouter 失败
+    model_metadata::openrouter_models(&app_handle, refresh.unwrap_or(false))
+        .await
+        .map_err(|e| {
+            if offline::is_offline() {
+                AppError::offline(e)
+            } else {
+                AppError::network(e)
+            }
+        })
+}
 
-    for selector_str in selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            if let Some(element) = document.select(&selector).next() {
-                let html_content = element.html();
-                // Simple heuristic: must be at least somewhat long
-                if html_content.len() > 100 {
-                    return Some(html_content);
-                }
+/// 估算用当前模型处理 text 的 token 数和费用，并检查是否超出上下文长度
+/// 只有 OpenRouter 模型有元数据，其他服务商只返回 token 估算
+#[tauri::command]
+pub async fn estimate_request_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    text: String,
+    completion_tokens: Option<usize>,
+) -> Result<RequestEstimate, AppError> {
+    let ai_service = active_ai_service(&state).await?;
+    // 离线时不显示费用（只做 token 估算），也不去拉取模型元数据
+    let metadata = if offline::is_offline() {
+        None
+    } else if ai_service.provider() == "openrouter" {
+        match model_metadata::openrouter_models(&app_handle, false).await {
+            Ok(models) => models.into_iter().find(|m| m.id == ai_service.model()),
+            Err(e) => {
+                eprintln!("[ModelMetadata] {}", e);
+                None
             }
         }
-    }
+    } else {
+        None
+    };
+    Ok(model_metadata::estimate_request(
+        ai_service.model(),
+        metadata.as_ref(),
+        &text,
+        completion_tokens.unwrap_or(0),
+    ))
+}
 
-    None
+/// 按段落长度和当前模型的上下文长度把待处理段落分批（短句一批更多，长句一批更少）
+fn plan_segment_batches(
+    app_handle: &AppHandle,
+    ai_service: &crate::ai_service::AIService,
+    pending: &[(String, String)],
+) -> Vec<std::ops::Range<usize>> {
+    let metadata = cached_model_metadata(app_handle, ai_service);
+    model_metadata::plan_batches(
+        pending.iter().map(|(_, text)| text.as_str()),
+        model_metadata::batch_token_budget(metadata.as_ref()),
+        model_metadata::MAX_BATCH_ITEMS,
+    )
 }
 
-/// Convert HTML to text, preserving significant layout (newlines)
-/// Ideal for lyrics, poems, and clean articles.
-fn html_to_text_preserving_layout(html: &str) -> String {
-    use regex::Regex;
+/// 当前模型的缓存元数据（仅 OpenRouter，不发网络请求）
+fn cached_model_metadata(
+    app_handle: &AppHandle,
+    ai_service: &crate::ai_service::AIService,
+) -> Option<ModelMetadata> {
+    (ai_service.provider() == "openrouter")
+        .then(|| model_metadata::cached_model(app_handle, ai_service.model()))
+        .flatten()
+}
 
-    // 1. Normalize newlines in source to spaces (browser behavior), we will re-add them based on tags.
-    let normalized = html.replace("\r", " ").replace("\n", " ");
+/// 估算的请求长度超出模型上下文时发出 model-context://warning 事件（不阻止请求）
+fn warn_if_exceeds_context(
+    app_handle: &AppHandle,
+    ai_service: &crate::ai_service::AIService,
+    text: &str,
+) {
+    let Some(metadata) = cached_model_metadata(app_handle, ai_service) else {
+        return;
+    };
+    let estimate = model_metadata::estimate_request(ai_service.model(), Some(&metadata), text, 0);
+    if let Some(message) = model_metadata::context_warning(&estimate) {
+        eprintln!("[ModelMetadata] {}", message);
+        let _ = app_handle.emit(
+            "model-context://warning",
+            serde_json::json!({ "estimate": estimate, "message": message }),
+        );
+    }
+}
 
-    // 2. Replace block tags with sentinel newlines
-    // <br>, <br/> -> \n
-    // <p>, <div>, <li>, <h1>-<h6>, <blockquote>, <pre> -> \n\n (surround with breaks)
-    // </tr> -> \n (table rows)
-    let re_br = Regex::new(r"(?i)<br\s*/?>").unwrap();
-    let with_br = re_br.replace_all(&normalized, "\n");
+#[tauri::command]
+pub async fn chat_completion(
+    state: AppState<'_>,
+    request: ChatRequest,
+) -> Result<ChatResponse, AppError> {
+    let ai_service = get_ai_service_for_task(&state, AiTask::Chat).await?;
+    ai_service
+        .chat(request)
+        .await
+        .map_err(AppError::ai_provider)
+}
 
-    let re_block_start = Regex::new(r"(?i)<(p|div|h[1-6]|li|blockquote|pre|tr)[^>]*>").unwrap();
-    let with_block_start = re_block_start.replace_all(&with_br, "\n"); // Add newline before block
+#[tauri::command]
+pub async fn stream_chat_completion(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    request: ChatRequest,
+    event_id: String,
+) -> Result<String, AppError> {
+    let ai_service = get_ai_service_for_task(&state, AiTask::Chat).await?;
 
-    let re_block_end = Regex::new(r"(?i)</(p|div|h[1-6]|li|blockquote|pre|tr)>").unwrap();
-    let with_block_end = re_block_end.replace_all(&with_block_start, "\n\n"); // Add double newline after block
+    // Create a callback that emits events to the frontend
+    let app_handle_clone = app_handle.clone();
+    let event_name = format!("chat-stream://{}", event_id);
 
-    // 3. Strip all other tags
-    let re_tags = Regex::new(r"<[^>]*>").unwrap();
-    let stripped = re_tags.replace_all(&with_block_end, "");
+    ai_service
+        .stream_chat(request, move |chunk| {
+            // Emit the chunk to the frontend
+            // We ignore errors here as we can't do much if emission fails
+            let _ = app_handle_clone.emit(&event_name, chunk);
+        })
+        .await
+        .map_err(AppError::ai_provider)
+}
+
+#[tauri::command]
+pub async fn segment_translate_explain_cmd(
+    state: AppState<'_>,
+    text: String,
+    target_language: String,
+) -> Result<crate::types::SegmentExplanation, AppError> {
+    let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+    ai_service
+        .segment_translate_explain(text, target_language)
+        .await
+        .map_err(AppError::ai_provider)
+}
+
+/// 批量更新段落的处理状态（items 为 (段落 id, 文本)）
+fn mark_segments(
+    article: &mut Article,
+    items: &[(String, String)],
+    task: ProcessingTask,
+    status: ProcessingStatus,
+    error: Option<String>,
+) {
+    for segment in article
+        .segments
+        .iter_mut()
+        .filter(|s| items.iter().any(|(id, _)| id == &s.id))
+    {
+        segment.set_processing(task, status, error.clone());
+    }
+}
+
+/// 批量讲解时每批并发的段落数
+const EXPLAIN_BATCH_SIZE: usize = 3;
+/// 批与批之间的间隔，避免触发服务商的速率限制
+const EXPLAIN_BATCH_DELAY: Duration = Duration::from_millis(1500);
+
+/// 整篇讲解：逐批为段落生成讲解并写入 segment.explanation，每批完成后保存文章并发送进度事件
+/// 默认跳过已有讲解的段落，overwrite 为 true 时全部重新生成；单个段落失败不影响其他段落
+#[tauri::command]
+pub async fn explain_article_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    target_language: String,
+    overwrite: Option<bool>,
+    job_id: Option<String>,
+) -> Result<Article, AppError> {
+    let job = jobs.register(job_id);
+    let cancel = job.token();
+    let overwrite = overwrite.unwrap_or(false);
+
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    if article.segments.is_empty() {
+        article.segments = create_segments_from_content(
+            &app_handle,
+            &article.id,
+            &article.content,
+            article.language.as_deref(),
+            article.segmentation.as_ref(),
+        );
+    }
+
+    let pending: Vec<(String, String)> = article
+        .segments
+        .iter()
+        .filter(|s| {
+            s.image_url.is_none()
+                && !s.text.trim().is_empty()
+                && (overwrite || s.explanation.is_none())
+        })
+        .map(|s| (s.id.clone(), s.text.clone()))
+        .collect();
+    let total = pending.len();
+    let event = format!("explanation-progress://{}", article_id);
+
+    let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+    let mut done = 0usize;
+    let mut failed = 0usize;
+
+    mark_segments(
+        &mut article,
+        &pending,
+        ProcessingTask::Explanation,
+        ProcessingStatus::Pending,
+        None,
+    );
+
+    for (i, batch) in pending.chunks(EXPLAIN_BATCH_SIZE).enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        if i > 0 {
+            let _ = run_cancellable(cancel, async {
+                tokio::time::sleep(EXPLAIN_BATCH_DELAY).await;
+                Ok(())
+            })
+            .await;
+            if cancel.is_cancelled() {
+                break;
+            }
+        }
+
+        mark_segments(
+            &mut article,
+            batch,
+            ProcessingTask::Explanation,
+            ProcessingStatus::Processing,
+            None,
+        );
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+
+        let results = run_cancellable(cancel, async {
+            Ok(futures::future::join_all(batch.iter().map(|(_, text)| {
+                ai_service.segment_translate_explain(text.clone(), target_language.clone())
+            }))
+            .await)
+        })
+        .await;
+        let Ok(results) = results else {
+            mark_segments(
+                &mut article,
+                batch,
+                ProcessingTask::Explanation,
+                ProcessingStatus::Pending,
+                None,
+            );
+            break;
+        };
+
+        for ((id, _), result) in batch.iter().zip(results) {
+            let Some(seg) = article.segments.iter_mut().find(|s| &s.id == id) else {
+                continue;
+            };
+            let result = result.and_then(|explanation| {
+                checked_explanation(&app_handle, &article_id, id, &target_language, explanation)
+            });
+            match result {
+                Ok(explanation) => {
+                    seg.explanation = Some(explanation);
+                    seg.set_processing(ProcessingTask::Explanation, ProcessingStatus::Done, None);
+                }
+                Err(e) => {
+                    eprintln!("[Article] Failed to explain segment {}: {}", id, e);
+                    seg.set_processing(
+                        ProcessingTask::Explanation,
+                        ProcessingStatus::Failed,
+                        Some(e),
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        done += batch.len();
+
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+        emit_article_event(
+            &app_handle,
+            &article_id,
+            &event,
+            serde_json::json!({
+                "current": done,
+                "total": total,
+                "failed": failed,
+                "message": tr_with(
+                    "explain.batch",
+                    &[("current", done.to_string()), ("total", total.to_string())],
+                )
+            }),
+        );
+    }
+
+    // 取消时保留已生成的讲解
+    if cancel.is_cancelled() {
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+        return Err(AppError::cancelled(cancelled_message()));
+    }
+
+    let message = if failed == 0 {
+        tr("explain.completed")
+    } else {
+        tr_with(
+            "explain.completed_with_failures",
+            &[("failed", failed.to_string())],
+        )
+    };
+    emit_article_event(
+        &app_handle,
+        &article_id,
+        &event,
+        serde_json::json!({
+            "current": total,
+            "total": total,
+            "failed": failed,
+            "message": message
+        }),
+    );
+
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &article_json)?;
+    Ok(article)
+}
+
+/// 阅读时预取讲解的段落数
+const PREFETCH_AHEAD: usize = 3;
+
+/// 预读讲解：阅读到 segment_id 时在后台为后面几段请求讲解并写回文章，点击“讲解”时直接显示
+/// 设置中未开启或离线时不做任何事；同一篇文章的新请求会取消上一次还没完成的预取
+/// 每完成一段发送 explanation-prefetch://{article_id} 事件（含 segment_id），返回排队的段落数
+#[tauri::command]
+pub async fn prefetch_explanations_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    segment_id: String,
+    target_language: String,
+) -> Result<usize, AppError> {
+    let enabled = load_config(&app_handle)?.is_some_and(|c| c.explanation_prefetch);
+    if !enabled || offline::is_offline() {
+        return Ok(0);
+    }
+
+    let article = get_article(app_handle.clone(), article_id.clone()).await?;
+    let pending: Vec<(String, String)> =
+        segmentation::segments_to_prefetch(&article.segments, &segment_id, PREFETCH_AHEAD)
+            .into_iter()
+            .map(|s| (s.id.clone(), s.text.clone()))
+            .collect();
+    let job_id = format!("prefetch:{}", article_id);
+    let job = jobs.register(Some(job_id.clone()));
+    if pending.is_empty() {
+        return Ok(0);
+    }
+    let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+
+    let count = pending.len();
+    let app = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let cancel = job.token();
+        let event = format!("explanation-prefetch://{}", article_id);
+        for (i, (id, text)) in pending.into_iter().enumerate() {
+            if i > 0 {
+                let _ = run_cancellable(cancel, async {
+                    tokio::time::sleep(EXPLAIN_BATCH_DELAY).await;
+                    Ok(())
+                })
+                .await;
+            }
+            if cancel.is_cancelled() {
+                return;
+            }
+
+            // 限流时按 Retry-After 等待；无法等待时停止预取，把额度留给用户主动的请求
+            let mut rate_limit_waits = 0;
+            let result = loop {
+                let result = run_cancellable(cancel, async {
+                    Ok(ai_service
+                        .segment_translate_explain(text.clone(), target_language.clone())
+                        .await)
+                })
+                .await;
+                match result {
+                    Err(_) => return,
+                    Ok(Err(e)) if is_rate_limit_error(&e) => {
+                        if rate_limit_waits < MAX_RATE_LIMIT_WAITS
+                            && wait_for_rate_limit(&app, cancel, &job_id, &e).await
+                        {
+                            rate_limit_waits += 1;
+                            continue;
+                        }
+                        eprintln!("[Prefetch] Rate limited, stopping: {}", e);
+                        return;
+                    }
+                    Ok(result) => break result,
+                }
+            };
+            let result = result.and_then(|explanation| {
+                checked_explanation(&app, &article_id, &id, &target_language, explanation)
+            });
+            let explanation = match result {
+                Ok(explanation) => explanation,
+                Err(e) => {
+                    eprintln!("[Prefetch] Failed to explain segment {}: {}", id, e);
+                    continue;
+                }
+            };
+
+            // 重新读取文章再写入，不覆盖请求期间用户手动生成或编辑的内容
+            let Ok(json) = load_article(&app, &article_id) else {
+                return;
+            };
+            let Ok(mut article) = serde_json::from_str::<Article>(&json) else {
+                return;
+            };
+            let Some(seg) = article.segments.iter_mut().find(|s| s.id == id) else {
+                continue;
+            };
+            if seg.explanation.is_some() {
+                continue;
+            }
+            seg.explanation = Some(explanation);
+            seg.set_processing(ProcessingTask::Explanation, ProcessingStatus::Done, None);
+            let article_json = serde_json::to_string(&article).unwrap();
+            if let Err(e) = save_article(&app, &article_id, &article_json) {
+                eprintln!("[Prefetch] Failed to save article {}: {}", article_id, e);
+                return;
+            }
+            emit_article_event(
+                &app,
+                &article_id,
+                &event,
+                serde_json::json!({ "segment_id": id }),
+            );
+        }
+    });
+
+    Ok(count)
+}
+
+/// 整篇生成注音：按文章的学习语言（未设置时从正文推断）批量生成 reading_text，
+/// 默认跳过已有注音的段落，overwrite 为 true 时全部重新生成
+/// engine 未指定时中文、韩语、西里尔和阿拉伯字母的语言使用本地注音，其他语言调用 AI
+#[tauri::command]
+pub async fn generate_readings_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    overwrite: Option<bool>,
+    job_id: Option<String>,
+    engine: Option<ReadingEngine>,
+) -> Result<Article, AppError> {
+    let job = jobs.register(job_id);
+    let cancel = job.token();
+    let overwrite = overwrite.unwrap_or(false);
+
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    if article.segments.is_empty() {
+        article.segments = create_segments_from_content(
+            &app_handle,
+            &article.id,
+            &article.content,
+            article.language.as_deref(),
+            article.segmentation.as_ref(),
+        );
+    }
+
+    let language = match article.language.clone() {
+        Some(language) => language,
+        // 纯汉字的段落在日文中也很常见，取前若干段一起判断
+        None => detect_reading_language(
+            &article
+                .segments
+                .iter()
+                .take(20)
+                .map(|s| s.text.as_str())
+                .collect::<String>(),
+        )
+        .map(str::to_string)
+        .ok_or_else(|| AppError::invalid_input(tr("error.reading_language_unknown")))?,
+    };
+
+    let pending: Vec<(String, String)> = article
+        .segments
+        .iter()
+        .filter(|s| {
+            s.image_url.is_none()
+                && !s.text.trim().is_empty()
+                && (overwrite || s.reading_text.is_none())
+        })
+        .map(|s| (s.id.clone(), s.text.clone()))
+        .collect();
+    let total = pending.len();
+    let event = format!("reading-progress://{}", article_id);
+
+    if engine.unwrap_or_else(|| ReadingEngine::default_for(&language)) == ReadingEngine::Local {
+        if !readings::supports_local(&language) {
+            return Err(AppError::invalid_input(tr(
+                "error.local_reading_unsupported",
+            )));
+        }
+        for (id, text) in &pending {
+            if let Some(seg) = article.segments.iter_mut().find(|s| &s.id == id) {
+                seg.reading_text = readings::local_reading(&language, text);
+            }
+        }
+        emit_article_event(
+            &app_handle,
+            &article_id,
+            &event,
+            serde_json::json!({
+                "current": total,
+                "total": total,
+                "message": tr("reading.completed")
+            }),
+        );
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+        return Ok(article);
+    }
+
+    let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+    let batches = plan_segment_batches(&app_handle, &ai_service, &pending);
+    let total_chunks = batches.len();
+    for (i, range) in batches.into_iter().enumerate() {
+        let chunk = &pending[range.clone()];
+        if cancel.is_cancelled() {
+            break;
+        }
+        let readings =
+            match run_cancellable(cancel, ai_service.batch_readings(chunk.to_vec(), &language))
+                .await
+            {
+                Ok(readings) => readings,
+                Err(_) if cancel.is_cancelled() => break,
+                Err(e) => {
+                    eprintln!(
+                        "[Article] Reading generation error in chunk {}/{}: {}",
+                        i + 1,
+                        total_chunks,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+        for (id, reading) in readings {
+            if !chunk.iter().any(|(cid, _)| cid == &id) {
+                continue;
+            }
+            if let Some(seg) = article.segments.iter_mut().find(|s| s.id == id) {
+                seg.reading_text = Some(reading);
+            }
+        }
+
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+        emit_article_event(
+            &app_handle,
+            &article_id,
+            &event,
+            serde_json::json!({
+                "current": range.end,
+                "total": total,
+                "message": tr_with(
+                    "reading.batch",
+                    &[
+                        ("current", (i + 1).to_string()),
+                        ("total", total_chunks.to_string()),
+                    ],
+                )
+            }),
+        );
+    }
+
+    if cancel.is_cancelled() {
+        return Err(AppError::cancelled(cancelled_message()));
+    }
+
+    emit_article_event(
+        &app_handle,
+        &article_id,
+        &event,
+        serde_json::json!({
+            "current": total,
+            "total": total,
+            "message": tr("reading.completed")
+        }),
+    );
+
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &article_json)?;
+    Ok(article)
+}
+
+#[tauri::command]
+pub async fn translate_article(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    target_language: String,
+    job_id: Option<String>,
+    quality: Option<TranslationQuality>,
+    selection: Option<SegmentSelection>,
+) -> Result<Article, AppError> {
+    let job = jobs.register(job_id);
+    run_article_translation(
+        &app_handle,
+        &state,
+        job.token(),
+        &article_id,
+        &target_language,
+        quality.unwrap_or_default(),
+        selection,
+    )
+    .await
+}
+
+/// 继续上次未完成的整篇翻译：沿用记录的目标语言、质量模式和段落范围，只发送仍未翻译的段落
+#[tauri::command]
+pub async fn resume_translation_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    job_id: Option<String>,
+) -> Result<Article, AppError> {
+    let job = jobs.register(job_id);
+    let progress = load_translation_progress_internal(&app_handle, &article_id)?
+        .ok_or_else(|| AppError::not_found(tr("error.translation_nothing_to_resume")))?;
+    run_article_translation(
+        &app_handle,
+        &state,
+        job.token(),
+        &article_id,
+        &progress.target_language,
+        progress.quality,
+        progress.selection,
+    )
+    .await
+}
+
+/// 获取文章未完成的整篇翻译进度，没有待续译的任务时返回 None
+#[tauri::command]
+pub async fn get_translation_progress_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Option<TranslationProgress>, AppError> {
+    Ok(load_translation_progress_internal(
+        &app_handle,
+        &article_id,
+    )?)
+}
+
+/// 获取文章最近一次整篇翻译后的译文抽查结果
+#[tauri::command]
+pub async fn get_translation_qa_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Option<TranslationQaReport>, AppError> {
+    Ok(load_translation_qa(&app_handle, &article_id)?
+        .and_then(|json| serde_json::from_str::<TranslationQaReport>(&json).ok()))
+}
+
+/// 整篇翻译完成后抽查译文质量：从本次由模型翻译的 (段落 ID, 原文, 译文) 中随机抽取几句，
+/// 由审校任务的模型打分，结果保存并随完成事件发送。抽查只是提示，关闭、离线或请求失败时返回 None
+async fn check_translation_quality(
+    app_handle: &AppHandle,
+    state: &AIServiceCache,
+    cancel: &CancellationToken,
+    article_id: &str,
+    candidates: Vec<(String, String, String)>,
+    target_language: &str,
+    model: &str,
+) -> Option<TranslationQaReport> {
+    let sample_size = load_config(app_handle)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .translation_qa_sample;
+    if sample_size == 0 || candidates.is_empty() {
+        return None;
+    }
+    let seed = Uuid::new_v4().as_u128() as u64;
+    let sample: Vec<(String, String, String)> =
+        translation_qa::sample_indices(candidates.len(), sample_size, seed)
+            .into_iter()
+            .map(|index| candidates[index].clone())
+            .collect();
+
+    let reviewer = get_ai_service_for_task(state, AiTask::TranslationReview)
+        .await
+        .ok()?;
+    let grades = match run_cancellable(
+        cancel,
+        reviewer.grade_translations(sample.clone(), target_language),
+    )
+    .await
+    {
+        Ok(grades) => grades,
+        Err(e) => {
+            if !cancel.is_cancelled() {
+                eprintln!("[Article] Translation quality check failed: {}", e);
+            }
+            return None;
+        }
+    };
+    let grades: Vec<(String, u8, String)> = grades
+        .into_iter()
+        .filter(|(id, _, _)| sample.iter().any(|(sid, _, _)| sid == id))
+        .collect();
+
+    let report = translation_qa::build_report(
+        article_id,
+        target_language,
+        model,
+        &grades,
+        &chrono::Utc::now().to_rfc3339(),
+    )?;
+    match serde_json::to_string(&report) {
+        Ok(json) => {
+            if let Err(e) = save_translation_qa(app_handle, article_id, &json) {
+                eprintln!("[Article] Failed to save translation quality check: {}", e);
+            }
+        }
+        Err(e) => eprintln!(
+            "[Article] Failed to serialize translation quality check: {}",
+            e
+        ),
+    }
+    Some(report)
+}
+
+fn load_translation_progress_internal(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Option<TranslationProgress>, String> {
+    Ok(load_translation_progress(app_handle, article_id)?
+        .and_then(|json| serde_json::from_str::<TranslationProgress>(&json).ok()))
+}
+
+fn persist_translation_progress(
+    app_handle: &AppHandle,
+    progress: &mut TranslationProgress,
+) -> Result<(), String> {
+    progress.updated_at = chrono::Utc::now().to_rfc3339();
+    let json = serde_json::to_string(progress)
+        .map_err(|e| format!("Failed to serialize translation progress: {}", e))?;
+    save_translation_progress(app_handle, &progress.article_id, &json)
+}
+
+/// 每批翻译的最大尝试次数（含首次），之间按 2/4 秒退避
+const CHUNK_MAX_ATTEMPTS: u32 = 3;
+
+/// 整篇翻译：逐批翻译缺少该语言译文的段落，每批完成后立即保存文章和批次状态，
+/// 失败的批次自动重试，仍失败的保留进度，之后可通过 resume_translation_cmd 续译
+/// selection 不为 None 时只翻译选中的段落（按章节阅读时无需一次翻译整本书）
+async fn run_article_translation(
+    app_handle: &AppHandle,
+    state: &AIServiceCache,
+    cancel: &CancellationToken,
+    article_id: &str,
+    target_language: &str,
+    quality: TranslationQuality,
+    selection: Option<SegmentSelection>,
+) -> Result<Article, AppError> {
+    let mut article = get_article(app_handle.clone(), article_id.to_string()).await?;
+
+    // Ensure segments exist
+    if article.segments.is_empty() {
+        article.segments = create_segments_from_content(
+            app_handle,
+            &article.id,
+            &article.content,
+            article.language.as_deref(),
+            article.segmentation.as_ref(),
+        );
+    }
+
+    // 主译文语言：首次整篇翻译时记录（已有译文的旧文章视为当前目标语言），
+    // 其他目标语言的译文写入 segment.translations
+    let primary_language = article
+        .translation_language
+        .get_or_insert_with(|| target_language.to_string())
+        .clone();
+
+    // 收集需要翻译的段落（没有该语言译文的）
+    let untranslated: Vec<(String, String)> = article
+        .segments
+        .iter()
+        .filter(|s| {
+            s.image_url.is_none()
+                && selection.as_ref().is_none_or(|sel| sel.includes(s))
+                && s.translation_for(target_language, Some(&primary_language))
+                    .is_none()
+        })
+        .map(|s| (s.id.clone(), s.text.clone()))
+        .collect();
+
+    let mut progress = TranslationProgress {
+        article_id: article_id.to_string(),
+        target_language: target_language.to_string(),
+        quality,
+        selection,
+        chunks: Vec::new(),
+        updated_at: String::new(),
+    };
+    // 本次交给模型翻译的段落和所用模型，完成后从中抽查译文质量
+    let mut translated_by: Option<(String, Vec<String>)> = None;
+
+    if !untranslated.is_empty() {
+        let ai_service = get_ai_service_for_task(state, AiTask::BatchTranslation).await?;
+        // 精翻模式的审校轮可单独路由到更强的模型
+        let review_service = match quality {
+            TranslationQuality::Thorough => {
+                Some(get_ai_service_for_task(state, AiTask::TranslationReview).await?)
+            }
+            TranslationQuality::Fast | TranslationQuality::Standard => None,
+        };
+        let model = match (&review_service, quality) {
+            (_, TranslationQuality::Standard) => ai_service.model().to_string(),
+            (Some(reviewer), _) => format!(
+                "{}@{}+{}",
+                ai_service.model(),
+                quality.as_str(),
+                reviewer.model()
+            ),
+            (None, _) => format!("{}@{}", ai_service.model(), quality.as_str()),
+        };
+        let glossary_entries = glossary::load_entries(app_handle);
+
+        // 翻译记忆：相同原文、目标语言、模型和质量模式的句子直接复用
+        // 句子命中术语表时，把术语译法计入键，术语表修改后不再复用旧译文
+        let memory_model =
+            |text: &str| memory_model_for(&model, &glossary_entries, text, target_language);
+        let mut pending: Vec<(String, String)> = Vec::new();
+        for (id, text) in &untranslated {
+            match translation_memory::lookup(app_handle, text, target_language, &memory_model(text))
+            {
+                Some(translation) => {
+                    if let Some(seg) = article.segments.iter_mut().find(|s| &s.id == id) {
+                        seg.set_translation(target_language, Some(&primary_language), translation);
+                        seg.set_processing(
+                            ProcessingTask::Translation,
+                            ProcessingStatus::Done,
+                            None,
+                        );
+                    }
+                }
+                None => pending.push((id.clone(), text.clone())),
+            }
+        }
+        if pending.len() < untranslated.len() {
+            println!(
+                "[Article] Reused {} translations from translation memory",
+                untranslated.len() - pending.len()
+            );
+        }
+
+        translated_by = Some((
+            model.clone(),
+            pending.iter().map(|(id, _)| id.clone()).collect(),
+        ));
+
+        // 批量翻译：按段落长度和模型上下文分批
+        let batches = plan_segment_batches(app_handle, &ai_service, &pending);
+        let total_count = pending.len();
+        let total_chunks = batches.len();
+
+        println!(
+            "[Article] Starting quick translation for article: {}, items: {}",
+            article_id, total_count
+        );
+
+        progress.chunks = batches
+            .iter()
+            .map(|range| TranslationChunkStatus {
+                segment_ids: pending[range.clone()]
+                    .iter()
+                    .map(|(id, _)| id.clone())
+                    .collect(),
+                status: ChunkStatus::Pending,
+                attempts: 0,
+                error: None,
+            })
+            .collect();
+        if !progress.chunks.is_empty() {
+            persist_translation_progress(app_handle, &mut progress)?;
+        }
+        mark_segments(
+            &mut article,
+            &pending,
+            ProcessingTask::Translation,
+            ProcessingStatus::Pending,
+            None,
+        );
+
+        for (i, range) in batches.iter().enumerate() {
+            let chunk = &pending[range.clone()];
+            if cancel.is_cancelled() {
+                break;
+            }
+            println!(
+                "[Article] Translating chunk {}/{} ({} items)...",
+                i + 1,
+                total_chunks,
+                chunk.len()
+            );
+
+            mark_segments(
+                &mut article,
+                chunk,
+                ProcessingTask::Translation,
+                ProcessingStatus::Processing,
+                None,
+            );
+            let article_json = serde_json::to_string(&article).unwrap();
+            save_article(app_handle, article_id, &article_json)?;
+
+            // 每次尝试只发送本批中仍未拿到译文的段落（模型可能漏译部分条目）
+            let mut remaining: Vec<(String, String)> = chunk.to_vec();
+            let mut rate_limit_waits = 0;
+            while !remaining.is_empty() && !cancel.is_cancelled() {
+                let attempt = progress.chunks[i].attempts;
+                if attempt > 0 {
+                    // 重试前退避；等待期间也可取消
+                    let delay = Duration::from_secs(2u64.pow(attempt));
+                    let _ = run_cancellable(cancel, async {
+                        tokio::time::sleep(delay).await;
+                        Ok(())
+                    })
+                    .await;
+                    if cancel.is_cancelled() {
+                        break;
+                    }
+                }
+                progress.chunks[i].attempts += 1;
+
+                let texts: Vec<&str> = remaining.iter().map(|(_, text)| text.as_str()).collect();
+                let glossary =
+                    glossary::matching_entries(&glossary_entries, &texts, target_language);
+
+                let result = match run_cancellable(
+                    cancel,
+                    ai_service.batch_translate(
+                        remaining.clone(),
+                        article.language.as_deref(),
+                        target_language,
+                        &glossary,
+                        quality,
+                    ),
+                )
+                .await
+                {
+                    Ok(translations) => match &review_service {
+                        Some(reviewer) => Ok(review_chunk(
+                            reviewer,
+                            cancel,
+                            &remaining,
+                            translations,
+                            target_language,
+                            &glossary,
+                        )
+                        .await),
+                        None => Ok(translations),
+                    },
+                    Err(e) => Err(e),
+                };
+
+                let error = match result {
+                    Ok(translations) => {
+                        // 将翻译结果写回对应的 segment
+                        for (id, translation) in translations {
+                            if !remaining.iter().any(|(rid, _)| rid == &id) {
+                                continue;
+                            }
+                            if let Some(seg) = article.segments.iter_mut().find(|s| s.id == id) {
+                                let translation =
+                                    seg.strip_speaker_prefix(&translation).to_string();
+                                // 不合格的译文隔离后留在 remaining 中，随本批一起重试
+                                let Ok(translation) = checked_translation(
+                                    app_handle,
+                                    article_id,
+                                    seg,
+                                    target_language,
+                                    translation,
+                                ) else {
+                                    continue;
+                                };
+                                translation_memory::remember(
+                                    app_handle,
+                                    &seg.text,
+                                    target_language,
+                                    &memory_model(&seg.text),
+                                    &translation,
+                                );
+                                seg.set_translation(
+                                    target_language,
+                                    Some(&primary_language),
+                                    translation,
+                                );
+                            }
+                        }
+                        remaining.retain(|(id, _)| {
+                            article.segments.iter().any(|s| {
+                                &s.id == id
+                                    && s.translation_for(target_language, Some(&primary_language))
+                                        .is_none()
+                            })
+                        });
+                        (!remaining.is_empty()).then(|| {
+                            tr_with(
+                                "translate.chunk_incomplete",
+                                &[("missing", remaining.len().to_string())],
+                            )
+                        })
+                    }
+                    Err(_) if cancel.is_cancelled() => break,
+                    Err(e) => Some(e),
+                };
+
+                match error {
+                    None => {
+                        progress.chunks[i].status = ChunkStatus::Done;
+                        progress.chunks[i].error = None;
+                    }
+                    // 服务商限流时按 Retry-After 暂停后重试本批，不计入失败次数
+                    Some(e)
+                        if rate_limit_waits < MAX_RATE_LIMIT_WAITS
+                            && wait_for_rate_limit(app_handle, cancel, article_id, &e).await =>
+                    {
+                        rate_limit_waits += 1;
+                        progress.chunks[i].attempts -= 1;
+                    }
+                    Some(e) => {
+                        eprintln!(
+                            "[Article] Batch translation error in chunk {}/{} (attempt {}): {}",
+                            i + 1,
+                            total_chunks,
+                            progress.chunks[i].attempts,
+                            e
+                        );
+                        progress.chunks[i].error = Some(e);
+                        if progress.chunks[i].attempts >= CHUNK_MAX_ATTEMPTS {
+                            progress.chunks[i].status = ChunkStatus::Failed;
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // 拿到译文的段落标为完成；本批最终失败的标为失败，被取消的回到等待
+            let chunk_failed = progress.chunks[i].status == ChunkStatus::Failed;
+            for (id, _) in chunk {
+                let Some(seg) = article.segments.iter_mut().find(|s| &s.id == id) else {
+                    continue;
+                };
+                if seg
+                    .translation_for(target_language, Some(&primary_language))
+                    .is_some()
+                {
+                    seg.set_processing(ProcessingTask::Translation, ProcessingStatus::Done, None);
+                } else if chunk_failed {
+                    seg.set_processing(
+                        ProcessingTask::Translation,
+                        ProcessingStatus::Failed,
+                        progress.chunks[i].error.clone(),
+                    );
+                } else {
+                    seg.set_processing(
+                        ProcessingTask::Translation,
+                        ProcessingStatus::Pending,
+                        None,
+                    );
+                }
+            }
+
+            // 每批结束后保存，应用退出或崩溃后可从断点续译
+            let article_json = serde_json::to_string(&article).unwrap();
+            save_article(app_handle, article_id, &article_json)?;
+            persist_translation_progress(app_handle, &mut progress)?;
+
+            if progress.chunks[i].status == ChunkStatus::Done {
+                println!(
+                    "[Article] Chunk {}/{} completed successfully",
+                    i + 1,
+                    total_chunks
+                );
+
+                // Emit progress event
+                let progress_event = serde_json::json!({
+                    "current": range.end,
+                    "total": total_count,
+                    "message": tr_with(
+                        "translate.chunk",
+                        &[
+                            ("current", (i + 1).to_string()),
+                            ("total", total_chunks.to_string()),
+                        ],
+                    )
+                });
+                emit_article_event(
+                    app_handle,
+                    article_id,
+                    &format!("translation-progress://{}", article_id),
+                    progress_event,
+                );
+            }
+        }
+    }
+
+    // 取消时保留已完成的批次，文章不标记为已翻译
+    if cancel.is_cancelled() {
+        println!(
+            "[Article] Translation cancelled for article: {}",
+            article_id
+        );
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(app_handle, article_id, &article_json)?;
+        return Err(AppError::cancelled(cancelled_message()));
+    }
+
+    let quality_check = match translated_by {
+        Some((model, ids)) => {
+            let candidates: Vec<(String, String, String)> = article
+                .segments
+                .iter()
+                .filter(|s| ids.contains(&s.id))
+                .filter_map(|s| {
+                    s.translation_for(target_language, Some(&primary_language))
+                        .map(|t| (s.id.clone(), s.text.clone(), t.to_string()))
+                })
+                .collect();
+            check_translation_quality(
+                app_handle,
+                state,
+                cancel,
+                article_id,
+                candidates,
+                target_language,
+                &model,
+            )
+            .await
+        }
+        None => None,
+    };
+    let quality_warning = quality_check
+        .as_ref()
+        .filter(|report| report.warning)
+        .map(|report| {
+            tr_with(
+                "translate.quality_warning",
+                &[
+                    ("score", format!("{:.1}", report.average_score)),
+                    ("low", report.issues.len().to_string()),
+                    ("sampled", report.sampled.to_string()),
+                ],
+            )
+        });
+
+    let failed_chunks = progress.failed_count();
+    let message = if failed_chunks == 0 {
+        delete_translation_progress(app_handle, article_id)?;
+        tr("translate.completed")
+    } else {
+        tr_with(
+            "translate.completed_with_failures",
+            &[("failed", failed_chunks.to_string())],
+        )
+    };
+
+    // Emit complete event
+    emit_article_event(
+        app_handle,
+        article_id,
+        &format!("translation-progress://{}", article_id),
+        serde_json::json!({
+            "current": untranslated.len(),
+            "total": untranslated.len(),
+            "failed_chunks": failed_chunks,
+            "message": message,
+            "quality_check": quality_check,
+            "quality_warning": quality_warning
+        }),
+    );
+
+    println!(
+        "[Article] Quick translation completed for article: {} (failed chunks: {})",
+        article_id, failed_chunks
+    );
+    // 只翻译了部分段落时，全部段落都有译文才算整篇翻译完成
+    let fully_translated = article.segments.iter().all(|s| {
+        s.image_url.is_some()
+            || s.translation_for(target_language, Some(&primary_language))
+                .is_some()
+    });
+    if failed_chunks == 0
+        && fully_translated
+        && primary_language.eq_ignore_ascii_case(target_language)
+    {
+        article.translated = true;
+    }
+
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(app_handle, article_id, &article_json)?;
+
+    Ok(article)
+}
+
+/// 清空翻译记忆，返回删除条数
+#[tauri::command]
+pub async fn clear_translation_memory_cmd(app_handle: AppHandle) -> Result<usize, AppError> {
+    Ok(clear_translation_memory(&app_handle)?)
+}
+
+fn parse_analysis_type(analysis_type: &str) -> Result<AnalysisType, AppError> {
+    match analysis_type {
+        "summary" => Ok(AnalysisType::Summary),
+        "key_points" => Ok(AnalysisType::KeyPoints),
+        "vocabulary" => Ok(AnalysisType::Vocabulary),
+        "grammar" => Ok(AnalysisType::Grammar),
+        "full" => Ok(AnalysisType::FullAnalysis),
+        _ => Err(AppError::invalid_input(tr("error.invalid_analysis_type"))),
+    }
+}
+
+/// 列出文章已保存的分析结果（新的在前）
+#[tauri::command]
+pub async fn list_article_analyses_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Vec<ArticleAnalysis>, AppError> {
+    let article = get_article(app_handle, article_id).await?;
+    let mut analyses: Vec<ArticleAnalysis> = article.analyses.into_values().collect();
+    analyses.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(analyses)
+}
+
+/// 保存分析结果（如前端流式生成的分析），同类型的旧结果被替换
+#[tauri::command]
+pub async fn save_article_analysis_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    analysis_type: String,
+    result: String,
+    model: Option<String>,
+) -> Result<ArticleAnalysis, AppError> {
+    parse_analysis_type(&analysis_type)?;
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+
+    let analysis = ArticleAnalysis {
+        analysis_type: analysis_type.clone(),
+        result,
+        chunk_count: 0,
+        model: model.unwrap_or_default(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    article.analyses.insert(analysis_type, analysis.clone());
+
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &article_json)?;
+    Ok(analysis)
+}
+
+/// 删除已保存的某类分析结果（不影响段落的译文和讲解）
+#[tauri::command]
+pub async fn delete_article_analysis_result_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    analysis_type: String,
+) -> Result<(), AppError> {
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    if article.analyses.remove(&analysis_type).is_some() {
+        let article_json = serde_json::to_string(&article).unwrap();
+        save_article(&app_handle, &article_id, &article_json)?;
+    }
+    Ok(())
+}
+
+/// 整篇分析时每块的最大字符数，超出时分块分析再汇总
+const ANALYSIS_CHUNK_CHARS: usize = 12_000;
+
+/// 分析整篇文章；长文分块分析后汇总，结果保存在 article.analyses
+/// 进度通过 analysis-progress://{article_id} 事件推送
+#[tauri::command]
+pub async fn analyze_article(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    analysis_type: String,
+    job_id: Option<String>,
+) -> Result<String, AppError> {
+    let job = jobs.register(job_id);
+    let article = get_article(app_handle.clone(), article_id.clone()).await?;
+
+    let analysis_key = analysis_type;
+    let analysis_type = parse_analysis_type(&analysis_key)?;
+
+    let ai_service = get_ai_service(&state).await?;
+    let cancel = job.token();
+    let event = format!("analysis-progress://{}", article_id);
+    let emit_progress = |current: usize, total: usize, message: String| {
+        emit_article_event(
+            &app_handle,
+            &article_id,
+            &event,
+            serde_json::json!({ "current": current, "total": total, "message": message }),
+        );
+    };
+
+    // 长文先逐块分析，再把各块结果汇总成全文结果，避免单次请求超出上下文长度
+    // 已知模型上下文长度时按其一半缩小每块大小（按每字最多 1 个 token 保守估计）
+    let chunk_chars = cached_model_metadata(&app_handle, &ai_service)
+        .and_then(|m| m.context_length)
+        .map_or(ANALYSIS_CHUNK_CHARS, |limit| {
+            (limit as usize / 2).clamp(1_000, ANALYSIS_CHUNK_CHARS)
+        });
+    let chunks = segmentation::chunk_text(&article.content, chunk_chars);
+    let result = if chunks.len() <= 1 {
+        warn_if_exceeds_context(&app_handle, &ai_service, &article.content);
+        let request = AnalysisRequest {
+            text: article.content.clone(),
+            analysis_type,
+        };
+        run_cancellable(cancel, ai_service.analyze(request))
+            .await
+            .map_err(|e| job_error(cancel, e, AppError::ai_provider))?
+            .result
+    } else {
+        // 汇总也算一步
+        let total = chunks.len() + 1;
+        let mut partials = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            emit_progress(
+                i,
+                total,
+                tr_with(
+                    "analysis.chunk",
+                    &[
+                        ("current", (i + 1).to_string()),
+                        ("total", chunks.len().to_string()),
+                    ],
+                ),
+            );
+            let request = AnalysisRequest {
+                text: chunk.clone(),
+                analysis_type: analysis_type.clone(),
+            };
+            partials.push(
+                run_cancellable(cancel, ai_service.analyze(request))
+                    .await
+                    .map_err(|e| job_error(cancel, e, AppError::ai_provider))?
+                    .result,
+            );
+        }
+        emit_progress(
+            chunks.len(),
+            total,
+            tr_with(
+                "analysis.synthesizing",
+                &[("total", chunks.len().to_string())],
+            ),
+        );
+        run_cancellable(
+            cancel,
+            ai_service.synthesize_analysis(analysis_type, &partials),
+        )
+        .await
+        .map_err(|e| job_error(cancel, e, AppError::ai_provider))?
+        .result
+    };
+
+    // 重新读取文章再写入，避免覆盖分析期间的其他修改
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    article.analyses.insert(
+        analysis_key.clone(),
+        ArticleAnalysis {
+            analysis_type: analysis_key,
+            result: result.clone(),
+            chunk_count: chunks.len().max(1),
+            model: ai_service.model().to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    let article_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &article_id, &article_json)?;
+    emit_progress(1, 1, tr("analysis.completed"));
+
+    Ok(result)
+}
+
+/// 取消正在运行的任务（translate_article / analyze_article / extract_subtitles_cmd 传入的 job_id）
+/// 返回任务是否存在；已结束的任务返回 false
+#[tauri::command]
+pub async fn cancel_job_cmd(
+    jobs: State<'_, JobRegistry>,
+    job_id: String,
+) -> Result<bool, AppError> {
+    let found = jobs.cancel(&job_id);
+    if found {
+        println!("[Jobs] Cancelled job: {}", job_id);
+    }
+    Ok(found)
+}
+
+/// 最近一次仍可撤销的删除操作，没有时返回 None
+#[tauri::command]
+pub async fn get_last_undo_action_cmd(
+    undo: State<'_, UndoJournal>,
+) -> Result<Option<UndoActionInfo>, AppError> {
+    Ok(undo.latest(chrono::Utc::now()))
+}
+
+/// 撤销最近一次删除（单词、单词包、书签、段落），返回被撤销的操作
+#[tauri::command]
+pub async fn undo_last_action_cmd(
+    app_handle: AppHandle,
+    undo: State<'_, UndoJournal>,
+) -> Result<UndoActionInfo, AppError> {
+    // 恢复成功后才移出日志，失败时仍可再次撤销
+    let entry = undo
+        .peek(chrono::Utc::now())
+        .ok_or_else(|| AppError::not_found(tr("error.nothing_to_undo")))?;
+    let info = entry.info;
+
+    match entry.action {
+        UndoAction::DeleteVocabulary { vocabulary, images } => {
+            let mut favorite: FavoriteVocabulary = serde_json::from_str(&vocabulary)
+                .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+            // 期间被删除的单词包不再恢复关联
+            let packs: HashSet<String> = load_all_word_packs(&app_handle)?
+                .into_iter()
+                .map(|pack| pack.id)
+                .collect();
+            favorite.pack_ids.retain(|pack_id| packs.contains(pack_id));
+            if favorite.pack_ids.is_empty() {
+                favorite
+                    .pack_ids
+                    .push(ensure_default_word_pack(&app_handle)?.id);
+            }
+            persist_favorite_vocabulary(&app_handle, &favorite)?;
+            vocabulary_images::restore_images(&get_app_data_dir(&app_handle)?, &images)?;
+        }
+        UndoAction::DeleteWordPack { pack, members } => {
+            let pack_id = serde_json::from_str::<WordPack>(&pack)
+                .map_err(|e| format!("Failed to parse word pack: {}", e))?
+                .id;
+            save_word_pack(&app_handle, &pack_id, &pack)?;
+            for (favorite_id, pack_ids) in members {
+                // 期间被删除的单词跳过
+                let Ok(json) = load_favorite_vocabulary(&app_handle, &favorite_id) else {
+                    continue;
+                };
+                let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
+                    .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+                // 删除包时补上的默认包去掉，期间新加入的其他包保留
+                favorite
+                    .pack_ids
+                    .retain(|id| id != DEFAULT_UNGROUPED_PACK_ID || pack_ids.contains(id));
+                for id in pack_ids {
+                    if !favorite.pack_ids.contains(&id) {
+                        favorite.pack_ids.push(id);
+                    }
+                }
+                persist_favorite_vocabulary(&app_handle, &favorite)?;
+            }
+        }
+        UndoAction::DeleteBookmark { bookmark } => {
+            let id = serde_json::from_str::<Bookmark>(&bookmark)
+                .map_err(|e| format!("Failed to parse bookmark: {}", e))?
+                .id;
+            save_bookmark(&app_handle, &id, &bookmark)?;
+        }
+        UndoAction::DeleteSegment {
+            article_id,
+            index,
+            segment,
+            next_is_new_paragraph,
+        } => {
+            let mut article = get_article(app_handle.clone(), article_id).await?;
+            segmentation::restore_segment(
+                &mut article.segments,
+                index,
+                *segment,
+                next_is_new_paragraph,
+            );
+            article.translated = article
+                .segments
+                .iter()
+                .all(|segment| segment.translation.is_some());
+            let updated_json = serde_json::to_string(&article)
+                .map_err(|e| format!("Failed to serialize article: {}", e))?;
+            save_article(&app_handle, &article.id, &updated_json)?;
+        }
+    }
+
+    undo.remove(&info.id);
+    Ok(info)
+}
+
+// Return type for fetch_url_content
+#[derive(serde::Serialize)]
+pub struct FetchedContent {
+    pub title: String,
+    pub content: String,
+}
+
+/// test_extraction_rules_cmd 的返回值
+#[derive(serde::Serialize)]
+pub struct ExtractionTestResult {
+    /// 命中的站点规则名称，未命中时为空
+    pub matched_rule: Option<String>,
+    pub title: String,
+    pub content: String,
+}
+
+// Fetch content from a URL
+// queue_on_failure: 抓取失败时加入重试队列，稍后通过 retry_failed_captures_cmd 自动导入
+// keep_images: 在正文中以 ![alt](url) 占位行保留插图
+#[tauri::command]
+pub async fn fetch_url_content(
+    app_handle: AppHandle,
+    url: String,
+    queue_on_failure: Option<bool>,
+    keep_images: Option<bool>,
+) -> Result<FetchedContent, AppError> {
+    offline::ensure_online()?;
+    let browser_path = load_config(&app_handle)?.and_then(|c| c.headless_browser_path);
+    let rules = load_rules_or_default(&app_handle);
+    let keep_images = keep_images.unwrap_or(false);
+    match fetch_and_extract_url(url.clone(), browser_path, &rules, keep_images).await {
+        Ok(fetched) => Ok(fetched),
+        Err(e) => {
+            // URL 本身不合法时重试也没有意义
+            if queue_on_failure.unwrap_or(false) && e.kind != ErrorKind::InvalidInput {
+                if let Err(queue_err) = queue_failed_capture(&app_handle, &url, &e.message) {
+                    eprintln!("[Capture] Failed to queue {}: {}", url, queue_err);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// 抓取并提取网页内容；静态 HTML 提取不到正文时，若配置了无头浏览器则渲染后再提取
+async fn fetch_and_extract_url(
+    url: String,
+    browser_path: Option<String>,
+    rules: &ExtractionRules,
+    keep_images: bool,
+) -> Result<FetchedContent, AppError> {
+    let static_result = fetch_static_page(url.clone(), rules, keep_images).await;
+
+    let browser_path = match browser_path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => path,
+        None => return static_result,
+    };
+
+    match static_result {
+        Ok(fetched) if fetched.content.trim().len() >= MIN_STATIC_CONTENT_LEN => Ok(fetched),
+        Err(e) if e.kind == ErrorKind::InvalidInput => Err(e),
+        previous => {
+            println!("[Fetch] Rendering with headless browser: {}", url);
+            match render_with_headless_browser(&browser_path, &url).await {
+                Ok(html) => {
+                    let rendered = extract_content_from_html(&html, &url, rules, keep_images);
+                    let previous_len = previous
+                        .as_ref()
+                        .map(|fetched| fetched.content.trim().len())
+                        .unwrap_or(0);
+                    if rendered.content.trim().len() > previous_len {
+                        Ok(rendered)
+                    } else {
+                        previous
+                    }
+                }
+                Err(e) => {
+                    eprintln!("[Fetch] Headless browser fallback failed: {}", e);
+                    previous
+                }
+            }
+        }
+    }
+}
+
+/// 直接请求页面 HTML 并提取内容（不执行 JavaScript）
+async fn fetch_static_page(
+    url: String,
+    rules: &ExtractionRules,
+    keep_images: bool,
+) -> Result<FetchedContent, AppError> {
+    // Validate URL
+    let parsed_url = url::Url::parse(&url).map_err(|_| "Invalid URL format".to_string())?;
+
+    // Only allow http/https
+    if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
+        return Err(AppError::invalid_input(tr("error.http_only")));
+    }
+
+    // Create HTTP client with timeout
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    // Fetch the page with better headers to avoid blocking
+    let response = client
+        .get(&url)
+        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8")
+        .header("Accept-Language", "en-US,en;q=0.9,zh-CN;q=0.8,zh;q=0.7")
+        .send()
+        .await
+        .map_err(|e| AppError::network(format!("Failed to fetch URL: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::network(tr_with(
+            "error.http_status",
+            &[("status", response.status().to_string())],
+        )));
+    }
+
+    // Get HTML content
+    // Note: readability prefers a "Cursor" or string. We'll get text first.
+    let html = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    let fetched = extract_content_from_html(&html, &url, rules, keep_images);
+
+    // Final check
+    if fetched.content.trim().is_empty() {
+        return Err(AppError::not_found(tr("error.no_meaningful_content")));
+    }
+
+    Ok(fetched)
+}
+
+/// 从 HTML 中提取标题和正文
+/// 命中站点规则时优先使用规则中的选择器，否则 readability 优先、通用选择器兜底
+fn extract_content_from_html(
+    html: &str,
+    url: &str,
+    rules: &ExtractionRules,
+    keep_images: bool,
+) -> FetchedContent {
+    extract_page_content(html, url, url, rules, keep_images)
+}
+
+/// page_url 用于匹配站点规则和兜底标题；base_url 用于解析正文中的相对地址
+/// （本地保存的网页为 HTML 文件地址，插图指向同目录下的资源文件）
+fn extract_page_content(
+    html: &str,
+    page_url: &str,
+    base_url: &str,
+    rules: &ExtractionRules,
+    keep_images: bool,
+) -> FetchedContent {
+    let document = scraper::Html::parse_document(html);
+    let image_base = if keep_images {
+        url::Url::parse(base_url).ok()
+    } else {
+        None
+    };
+    let site_rule = rules.find_site_rule(page_url);
+    let mut title = String::new();
+    let mut content = String::new();
+
+    if let Some(rule) = site_rule {
+        if let Some(rule_content) = select_content_html(&document, &rule.content_selectors) {
+            content = html_to_text_preserving_layout(&rule_content, image_base.as_ref());
+        }
+        if let Some(rule_title) = rule
+            .title_selector
+            .as_deref()
+            .and_then(|selector| select_title(&document, selector))
+        {
+            title = rule_title;
+        }
+    }
+
+    // Extract content using readability
+    // This removes ads, sidebars, navigation, and JS.
+    if content.trim().is_empty() {
+        let mut cursor = std::io::Cursor::new(html.as_bytes());
+        if let Ok(parsed_url) = url::Url::parse(base_url) {
+            if let Ok(extracted) = readability::extractor::extract(&mut cursor, &parsed_url) {
+                if title.is_empty() {
+                    title = extracted.title;
+                }
+                content = html_to_text_preserving_layout(&extracted.content, image_base.as_ref());
+            }
+        }
+    }
+
+    // Check if we got meaningful content. If not, try fallback selectors.
+    // Uta-net returns very short content (e.g. "Voting thanks") via readability.
+    if content.trim().len() < MIN_STATIC_CONTENT_LEN {
+        if let Some(fallback_content) = select_content_html(&document, &rules.fallback_selectors) {
+            // If fallback found something substantial, use it
+            if fallback_content.len() > content.len() {
+                content = html_to_text_preserving_layout(&fallback_content, image_base.as_ref());
+            }
+        }
+    }
+
+    // If title is still empty
+    if title.is_empty() {
+        title = extract_title_from_html(html, page_url);
+    }
+
+    if let Some(rule) = site_rule {
+        content = rule.apply_cleanup(&content);
+    }
+
+    FetchedContent { title, content }
+}
+
+/// 低于该长度的静态提取结果视为可能需要 JavaScript 渲染
+const MIN_STATIC_CONTENT_LEN: usize = 200;
+/// 无头浏览器渲染超时
+const HEADLESS_BROWSER_TIMEOUT_SECS: u64 = 60;
+
+/// 使用配置的无头浏览器渲染页面并返回执行 JavaScript 后的 DOM HTML
+async fn render_with_headless_browser(browser_path: &str, url: &str) -> Result<String, String> {
+    let mut command = tokio::process::Command::new(browser_path);
+    command
+        .args([
+            "--headless=new",
+            "--disable-gpu",
+            "--hide-scrollbars",
+            "--mute-audio",
+            // 给页面脚本留出执行时间后再导出 DOM
+            "--virtual-time-budget=10000",
+            "--dump-dom",
+            url,
+        ])
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(HEADLESS_BROWSER_TIMEOUT_SECS),
+        command.output(),
+    )
+    .await
+    .map_err(|_| "Headless browser timed out".to_string())?
+    .map_err(|e| format!("Failed to launch headless browser: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Headless browser exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Convert HTML to text, preserving significant layout (newlines)
+/// Ideal for lyrics, poems, and clean articles.
+/// image_base 不为空时，<img> 会转换为图片占位行而不是被丢弃
+fn html_to_text_preserving_layout(html: &str, image_base: Option<&url::Url>) -> String {
+    use regex::Regex;
+
+    // 1. Normalize newlines in source to spaces (browser behavior), we will re-add them based on tags.
+    let mut normalized = html.replace("\r", " ").replace("\n", " ");
+    if let Some(base_url) = image_base {
+        normalized = replace_img_tags(&normalized, base_url);
+    }
+
+    // 2. Replace block tags with sentinel newlines
+    // <br>, <br/> -> \n
+    // <p>, <div>, <li>, <h1>-<h6>, <blockquote>, <pre> -> \n\n (surround with breaks)
+    // </tr> -> \n (table rows)
+    let re_br = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    let with_br = re_br.replace_all(&normalized, "\n");
+
+    let re_block_start = Regex::new(r"(?i)<(p|div|h[1-6]|li|blockquote|pre|tr)[^>]*>").unwrap();
+    let with_block_start = re_block_start.replace_all(&with_br, "\n"); // Add newline before block
+
+    let re_block_end = Regex::new(r"(?i)</(p|div|h[1-6]|li|blockquote|pre|tr)>").unwrap();
+    let with_block_end = re_block_end.replace_all(&with_block_start, "\n\n"); // Add double newline after block
+
+    // 3. Strip all other tags
+    let re_tags = Regex::new(r"<[^>]*>").unwrap();
+    let stripped = re_tags.replace_all(&with_block_end, "");
+
+    // 4. Decode HTML entities
+    let decoded = html_escape::decode_html_entities(&stripped);
+
+    // 5. Clean up whitespace
+    // Split by newline, trim each line, filter empty lines if they are excessive (more than 2)
+    // But for lyrics, we want to keep single empty lines (stanza breaks).
+    let lines: Vec<&str> = decoded.lines().collect();
+    let mut clean_lines = Vec::new();
+    let mut empty_count = 0;
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            empty_count += 1;
+            // Allow up to 2 consecutive empty lines (paragraph break)
+            if empty_count <= 2 {
+                clean_lines.push("");
+            }
+        } else {
+            empty_count = 0;
+            clean_lines.push(trimmed);
+        }
+    }
+
+    let result = clean_lines.join("\n");
+
+    // Final trim of the whole text
+    result.trim().to_string()
+}
+
+/// 本地保存的网页记录的原始地址：浏览器另存时的 saved from url 注释、SingleFile 注释、
+/// canonical 链接或 og:url，只接受 http/https
+pub fn saved_page_url(html: &str) -> Option<String> {
+    use regex::Regex;
+
+    let from_comment = Regex::new(r"(?i)<!--\s*saved from url=\(\d+\)\s*(\S+?)\s*-->")
+        .unwrap()
+        .captures(html)
+        .map(|caps| caps[1].to_string());
+    let from_single_file = || {
+        let start = html.find("Page saved with SingleFile")?;
+        let end = html[start..]
+            .find("-->")
+            .map_or(html.len(), |end| start + end);
+        Regex::new(r"(?m)^\s*url:\s*(\S+)")
+            .unwrap()
+            .captures(&html[start..end])
+            .map(|caps| caps[1].to_string())
+    };
+    let from_meta = || {
+        let document = scraper::Html::parse_document(html);
+        [
+            ("link[rel=canonical]", "href"),
+            ("meta[property=\"og:url\"]", "content"),
+        ]
+        .into_iter()
+        .find_map(|(selector, attr)| {
+            let selector = scraper::Selector::parse(selector).ok()?;
+            document
+                .select(&selector)
+                .find_map(|el| el.value().attr(attr).map(str::to_string))
+        })
+    };
+
+    from_comment
+        .or_else(from_single_file)
+        .or_else(from_meta)
+        .map(|url| url.trim().to_string())
+        .filter(|url| {
+            url::Url::parse(url).is_ok_and(|u| u.scheme() == "http" || u.scheme() == "https")
+        })
+}
+
+// Extract title from HTML
+fn extract_title_from_html(html: &str, url: &str) -> String {
+    let html_lower = html.to_lowercase();
+
+    // Find <title> tag
+    if let Some(start) = html_lower.find("<title>") {
+        let start = start + 7; // len("<title>")
+        if let Some(end) = html_lower[start..].find("</title>") {
+            let title_html = &html[start..start + end];
+            // Decode basic HTML entities
+            let decoded = html_escape::decode_html_entities(title_html).to_string();
+            let trimmed = decoded.trim();
+            if !trimmed.is_empty() {
+                return trimmed.to_string();
+            }
+        }
+    }
+
+    // Fallback: extract from URL
+    if let Ok(parsed) = url::Url::parse(url) {
+        if let Some(segments) = parsed.path_segments() {
+            let last = segments.last().unwrap_or("");
+            if !last.is_empty() {
+                return last
+                    .replace('-', " ")
+                    .replace('_', " ")
+                    .split(' ')
+                    .map(|s| {
+                        let mut chars = s.chars();
+                        match chars.next() {
+                            None => String::new(),
+                            Some(first) => {
+                                if !first.is_alphabetic() {
+                                    String::new()
+                                } else {
+                                    first.to_uppercase().collect::<String>() + chars.as_str()
+                                }
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+            }
+            return parsed.host_str().unwrap_or("Untitled").to_string();
+        }
+    }
+
+    "Untitled".to_string()
+}
+
+// ============================================================================
+// Favorites Commands - 收藏夹命令
+// ============================================================================
+
+/// 创建单词包
+#[tauri::command]
+pub async fn create_word_pack_cmd(
+    app_handle: AppHandle,
+    name: String,
+    description: Option<String>,
+    cover_url: Option<String>,
+    author: Option<String>,
+    language_from: Option<String>,
+    language_to: Option<String>,
+    tags: Option<Vec<String>>,
+    version: Option<String>,
+) -> Result<WordPack, AppError> {
+    ensure_default_word_pack(&app_handle)?;
+
+    let language_from = language_from.or_else(|| active_learning_language(&app_handle));
+    let now = chrono::Utc::now().to_rfc3339();
+    let pack = WordPack {
+        id: Uuid::new_v4().to_string(),
+        name: name.trim().to_string(),
+        description,
+        cover_url,
+        author,
+        language_from,
+        language_to,
+        tags: tags.unwrap_or_default(),
+        version,
+        created_at: now.clone(),
+        updated_at: now,
+        is_system: false,
+        daily_new_limit: None,
+    };
+
+    if pack.name.is_empty() {
+        return Err(AppError::invalid_input(tr("error.pack_name_required")));
+    }
+
+    let json = serde_json::to_string(&pack)
+        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+    save_word_pack(&app_handle, &pack.id, &json)?;
+    Ok(pack)
+}
+
+/// 更新单词包
+#[tauri::command]
+pub async fn update_word_pack_cmd(
+    app_handle: AppHandle,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    cover_url: Option<String>,
+    author: Option<String>,
+    language_from: Option<String>,
+    language_to: Option<String>,
+    tags: Option<Vec<String>>,
+    version: Option<String>,
+) -> Result<WordPack, AppError> {
+    let json = load_word_pack(&app_handle, &id)?;
+    let mut pack: WordPack =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse word pack: {}", e))?;
+
+    if let Some(name) = name {
+        let trimmed = name.trim();
+        if trimmed.is_empty() {
+            return Err(AppError::invalid_input(tr("error.pack_name_required")));
+        }
+        pack.name = trimmed.to_string();
+    }
+    if description.is_some() {
+        pack.description = description;
+    }
+    if cover_url.is_some() {
+        pack.cover_url = cover_url;
+    }
+    if author.is_some() {
+        pack.author = author;
+    }
+    if language_from.is_some() {
+        pack.language_from = language_from;
+    }
+    if language_to.is_some() {
+        pack.language_to = language_to;
+    }
+    if tags.is_some() {
+        pack.tags = tags.unwrap_or_default();
+    }
+    if version.is_some() {
+        pack.version = version;
+    }
+
+    pack.updated_at = chrono::Utc::now().to_rfc3339();
+
+    let updated_json = serde_json::to_string(&pack)
+        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+    save_word_pack(&app_handle, &pack.id, &updated_json)?;
+    Ok(pack)
+}
+
+/// 列出单词包；指定 language 时按 language_from 过滤（系统包和未标记语言的包始终保留）
+#[tauri::command]
+pub async fn list_word_packs_cmd(
+    app_handle: AppHandle,
+    language: Option<String>,
+) -> Result<Vec<WordPack>, AppError> {
+    ensure_default_word_pack(&app_handle)?;
+    let mut packs = load_all_word_packs(&app_handle)?;
+    packs.retain(|pack| {
+        matches_learning_language(pack.language_from.as_deref(), language.as_deref())
+    });
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    packs.sort_by(|a, b| b.is_system.cmp(&a.is_system));
+    Ok(packs)
+}
+
+/// 删除单词包（系统包不可删除）
+#[tauri::command]
+pub async fn delete_word_pack_cmd(
+    app_handle: AppHandle,
+    undo: State<'_, UndoJournal>,
+    id: String,
+) -> Result<(), AppError> {
+    if id == DEFAULT_UNGROUPED_PACK_ID {
+        return Err(AppError::invalid_input(tr("error.system_pack_delete")));
+    }
+
+    let default_pack = ensure_default_word_pack(&app_handle)?;
+    let pack_json = load_word_pack(&app_handle, &id)?;
+
+    delete_word_pack(&app_handle, &id)?;
+
+    let mut members = Vec::new();
+    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    for favorite in &mut favorites {
+        if favorite.pack_ids.iter().any(|pack_id| pack_id == &id) {
+            members.push((favorite.id.clone(), favorite.pack_ids.clone()));
+            favorite.pack_ids.retain(|pack_id| pack_id != &id);
+            if favorite.pack_ids.is_empty() {
+                favorite.pack_ids.push(default_pack.id.clone());
+            }
+            persist_favorite_vocabulary(&app_handle, favorite)?;
+        }
+    }
+
+    let name = serde_json::from_str::<WordPack>(&pack_json)
+        .map(|pack| pack.name)
+        .unwrap_or_default();
+    undo.record(
+        &name,
+        UndoAction::DeleteWordPack {
+            pack: pack_json,
+            members,
+        },
+        chrono::Utc::now(),
+    );
+
+    Ok(())
+}
+
+/// 合并单词包：把 source 包的全部单词并入 target 包，然后删除 source 包
+#[tauri::command]
+pub async fn merge_packs_cmd(
+    app_handle: AppHandle,
+    target_pack_id: String,
+    source_pack_id: String,
+) -> Result<WordPack, AppError> {
+    if target_pack_id == source_pack_id {
+        return Err(AppError::invalid_input(tr("error.pack_merge_same")));
+    }
+    if source_pack_id == DEFAULT_UNGROUPED_PACK_ID {
+        return Err(AppError::invalid_input(tr("error.system_pack_delete")));
+    }
+
+    ensure_default_word_pack(&app_handle)?;
+    let mut target: WordPack = serde_json::from_str(&load_word_pack(&app_handle, &target_pack_id)?)
+        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
+    let source: WordPack = serde_json::from_str(&load_word_pack(&app_handle, &source_pack_id)?)
+        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
+
+    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    for favorite in &mut favorites {
+        if favorite.pack_ids.iter().any(|id| id == &source_pack_id) {
+            let merged = favorite
+                .pack_ids
+                .iter()
+                .map(|id| {
+                    if id == &source_pack_id {
+                        target_pack_id.clone()
+                    } else {
+                        id.clone()
+                    }
+                })
+                .collect();
+            favorite.pack_ids = sanitize_pack_ids(Some(merged));
+            persist_favorite_vocabulary(&app_handle, favorite)?;
+        }
+    }
+
+    for tag in source.tags {
+        if !target.tags.contains(&tag) {
+            target.tags.push(tag);
+        }
+    }
+    target.updated_at = chrono::Utc::now().to_rfc3339();
+    let json = serde_json::to_string(&target)
+        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+    save_word_pack(&app_handle, &target.id, &json)?;
+    delete_word_pack(&app_handle, &source_pack_id)?;
+
+    Ok(target)
+}
+
+/// 拆分单词包：按标签或掌握程度把单词移到新建的子包（"原包名 - 分组名"），
+/// 不属于任何分组的单词留在原包，返回新建的包
+#[tauri::command]
+pub async fn split_word_pack_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    by: PackSplitKey,
+) -> Result<Vec<WordPack>, AppError> {
+    ensure_default_word_pack(&app_handle)?;
+    let source: WordPack = serde_json::from_str(&load_word_pack(&app_handle, &pack_id)?)
+        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
+
+    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    let groups = group_pack_vocabularies(&favorites, &pack_id, by);
+
+    let mut created = Vec::new();
+    for (group, vocabulary_ids) in groups {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut tags = source.tags.clone();
+        tags.retain(|tag| tag != "system");
+        if !tags.contains(&group) {
+            tags.push(group.clone());
+        }
+        let pack = WordPack {
+            id: Uuid::new_v4().to_string(),
+            name: format!("{} - {}", source.name, group),
+            description: source.description.clone(),
+            cover_url: source.cover_url.clone(),
+            author: source.author.clone(),
+            language_from: source.language_from.clone(),
+            language_to: source.language_to.clone(),
+            tags,
+            version: source.version.clone(),
+            created_at: now.clone(),
+            updated_at: now,
+            is_system: false,
+            daily_new_limit: None,
+        };
+        let json = serde_json::to_string(&pack)
+            .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+        save_word_pack(&app_handle, &pack.id, &json)?;
+
+        for favorite in favorites
+            .iter_mut()
+            .filter(|fav| vocabulary_ids.contains(&fav.id))
+        {
+            for id in &mut favorite.pack_ids {
+                if id == &pack_id {
+                    *id = pack.id.clone();
+                }
+            }
+            persist_favorite_vocabulary(&app_handle, favorite)?;
+        }
+        created.push(pack);
+    }
+
+    Ok(created)
+}
+
+/// 生词读音使用的语言：优先取词条的学习语言，未标记时从单词文字推断
+fn phonetic_language(favorite: &FavoriteVocabulary) -> String {
+    favorite
+        .language
+        .clone()
+        .or_else(|| detect_reading_language(&favorite.word).map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// 生成生词的拉丁字母读音：假名、谚文本地转写，其他情况请求 AI。
+/// 只是辅助信息，离线或请求失败时返回 None，不影响收藏
+async fn generate_phonetic(
+    state: &AIServiceCache,
+    favorite: &FavoriteVocabulary,
+) -> Option<String> {
+    let language = phonetic_language(favorite);
+    if let Some(phonetic) =
+        readings::local_phonetic(&language, &favorite.word, favorite.reading.as_deref())
+    {
+        return Some(phonetic);
+    }
+    let ai_service = get_ai_service_for_task(state, AiTask::SegmentExplanation)
+        .await
+        .ok()?;
+    match ai_service
+        .batch_phonetics(
+            vec![(favorite.id.clone(), favorite.word.clone())],
+            &language,
+        )
+        .await
+    {
+        Ok(phonetics) => phonetics
+            .into_iter()
+            .find(|(id, _)| id == &favorite.id)
+            .map(|(_, phonetic)| phonetic.trim().to_string())
+            .filter(|phonetic| !phonetic.is_empty()),
+        Err(e) => {
+            eprintln!("[Vocabulary] Phonetic generation failed: {}", e);
+            None
+        }
+    }
+}
+
+/// 添加单词收藏
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn add_favorite_vocabulary_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    word: String,
+    meaning: String,
+    usage: String,
+    explanation: Option<String>,
+    example: Option<String>,
+    reading: Option<String>,
+    source_article_id: Option<String>,
+    source_article_title: Option<String>,
+    pack_ids: Option<Vec<String>>,
+    language: Option<String>,
+) -> Result<FavoriteVocabulary, AppError> {
+    let default_pack = ensure_default_word_pack(&app_handle)?;
+    let packs = load_all_word_packs(&app_handle)?;
+    let existing_pack_ids: HashSet<String> = packs.into_iter().map(|p| p.id).collect();
+
+    let normalized_input = normalize_word(&word);
+    if normalized_input.is_empty() || meaning.trim().is_empty() {
+        return Err(AppError::invalid_input(tr("error.word_meaning_required")));
+    }
+
+    let mut pack_ids = filter_existing_pack_ids(
+        sanitize_pack_ids(pack_ids),
+        &existing_pack_ids,
+        &default_pack.id,
+    );
+
+    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    if let Some(existing) = favorites
+        .iter_mut()
+        .find(|fav| normalize_word(&fav.word) == normalized_input)
+    {
+        let mut merged = existing.pack_ids.clone();
+        merged.append(&mut pack_ids);
+        existing.pack_ids = sanitize_pack_ids(Some(merged));
+        if existing.pack_ids.is_empty() {
+            existing.pack_ids.push(default_pack.id.clone());
+        }
+
+        if existing.meaning.trim().is_empty() {
+            existing.meaning = meaning.clone();
+        }
+        if existing.usage.trim().is_empty() {
+            existing.usage = usage.clone();
+        }
+        if existing.example.is_none() {
+            existing.example = example.clone();
+        }
+        if existing.reading.is_none() {
+            existing.reading = reading.clone();
+        }
+        if existing.explanation.is_none() {
+            existing.explanation = explanation.clone();
+        }
+        if existing.source_article_id.is_none() {
+            existing.source_article_id = source_article_id.clone();
+        }
+        if existing.source_article_title.is_none() {
+            existing.source_article_title = source_article_title.clone();
+        }
+        if existing.language.is_none() {
+            existing.language = language.or_else(|| active_learning_language(&app_handle));
+        }
+        if existing.phonetic.is_none() {
+            existing.phonetic = generate_phonetic(&state, existing).await;
+        }
+
+        persist_favorite_vocabulary(&app_handle, existing)?;
+        return Ok(existing.clone());
+    }
+
+    let mut favorite = FavoriteVocabulary {
+        id: Uuid::new_v4().to_string(),
+        word: word.trim().to_string(),
+        meaning: meaning.trim().to_string(),
+        usage: usage.trim().to_string(),
+        explanation,
+        example,
+        reading,
+        phonetic: None,
+        source_article_id,
+        source_article_title,
+        pack_ids,
+        tags: Vec::new(),
+        image_path: None,
+        language: language.or_else(|| active_learning_language(&app_handle)),
+        srs_state: "new".to_string(),
+        ease_factor: 2.5,
+        repetitions: 0,
+        interval_days: 0,
+        due_date: today_local_date().format("%Y-%m-%d").to_string(),
+        last_reviewed_at: None,
+        review_count: 0,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    match load_corpus(&app_handle) {
+        Ok(corpus) => corpus.count_into(&mut favorite),
+        Err(e) => eprintln!("[CorpusCounts] Failed to load articles: {}", e),
+    }
+    favorite.phonetic = generate_phonetic(&state, &favorite).await;
+
+    persist_favorite_vocabulary(&app_handle, &favorite)?;
+    Ok(favorite)
+}
+
+/// 单次 AI 请求生成读音的单词数
+const PHONETIC_BATCH_SIZE: usize = 40;
+
+/// 批量补全生词的拉丁字母读音：pack_id 为空时处理全部单词，默认跳过已有读音的单词。
+/// 能本地转写的先直接填写，其余按语言分组批量请求 AI，每批完成后保存，返回补全的单词数
+#[tauri::command]
+pub async fn backfill_vocabulary_phonetics_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    jobs: State<'_, JobRegistry>,
+    pack_id: Option<String>,
+    overwrite: Option<bool>,
+    job_id: Option<String>,
+) -> Result<usize, AppError> {
+    let job = jobs.register(job_id);
+    let cancel = job.token();
+    let overwrite = overwrite.unwrap_or(false);
+
+    let pending: Vec<FavoriteVocabulary> = load_all_favorite_vocabularies_internal(&app_handle)?
+        .into_iter()
+        .filter(|fav| {
+            pack_id
+                .as_ref()
+                .is_none_or(|pack_id| fav.pack_ids.contains(pack_id))
+                && (overwrite || fav.phonetic.is_none())
+        })
+        .collect();
+    let total = pending.len();
+    let event = "phonetic-progress";
+    let mut updated = 0usize;
+
+    // 本地转写
+    let mut by_language: BTreeMap<String, Vec<FavoriteVocabulary>> = BTreeMap::new();
+    for mut fav in pending {
+        let language = phonetic_language(&fav);
+        match readings::local_phonetic(&language, &fav.word, fav.reading.as_deref()) {
+            Some(phonetic) => {
+                fav.phonetic = Some(phonetic);
+                persist_favorite_vocabulary(&app_handle, &fav)?;
+                updated += 1;
+            }
+            None => by_language.entry(language).or_default().push(fav),
+        }
+    }
+
+    if by_language.is_empty() {
+        let _ = app_handle.emit(
+            event,
+            serde_json::json!({
+                "current": total,
+                "total": total,
+                "message": tr("phonetic.completed")
+            }),
+        );
+        return Ok(updated);
+    }
+
+    let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+    let mut done = updated;
+    'languages: for (language, favorites) in by_language {
+        for chunk in favorites.chunks(PHONETIC_BATCH_SIZE) {
+            if cancel.is_cancelled() {
+                break 'languages;
+            }
+            let items: Vec<(String, String)> = chunk
+                .iter()
+                .map(|fav| (fav.id.clone(), fav.word.clone()))
+                .collect();
+            match run_cancellable(cancel, ai_service.batch_phonetics(items, &language)).await {
+                Ok(phonetics) => {
+                    for (id, phonetic) in phonetics {
+                        let phonetic = phonetic.trim();
+                        if phonetic.is_empty() {
+                            continue;
+                        }
+                        if let Some(fav) = chunk.iter().find(|fav| fav.id == id) {
+                            let mut fav = fav.clone();
+                            fav.phonetic = Some(phonetic.to_string());
+                            persist_favorite_vocabulary(&app_handle, &fav)?;
+                            updated += 1;
+                        }
+                    }
+                }
+                Err(_) if cancel.is_cancelled() => break 'languages,
+                Err(e) => eprintln!("[Vocabulary] Phonetic generation error: {}", e),
+            }
+            done += chunk.len();
+            let _ = app_handle.emit(
+                event,
+                serde_json::json!({
+                    "current": done,
+                    "total": total,
+                    "message": tr_with(
+                        "phonetic.batch",
+                        &[("current", done.to_string()), ("total", total.to_string())],
+                    )
+                }),
+            );
+        }
+    }
+
+    Ok(updated)
+}
+
+/// 列出单词收藏；指定 language 时只返回该学习语言（及未标记语言）的单词
+#[tauri::command]
+pub async fn list_favorite_vocabularies_cmd(
+    app_handle: AppHandle,
+    language: Option<String>,
+) -> Result<Vec<FavoriteVocabulary>, AppError> {
+    ensure_default_word_pack(&app_handle)?;
+    migrate_favorite_vocabularies(&app_handle)?;
+    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    favorites.retain(|fav| matches_learning_language(fav.language.as_deref(), language.as_deref()));
+
+    // 按创建时间降序排列
+    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(favorites)
+}
+
+/// 删除单词收藏
+#[tauri::command]
+pub async fn delete_favorite_vocabulary_cmd(
+    app_handle: AppHandle,
+    undo: State<'_, UndoJournal>,
+    id: String,
+) -> Result<(), AppError> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let previous = load_favorite_vocabulary(&app_handle, &id).ok();
+    let images = vocabulary_images::read_images(&app_data_dir, &id);
+
+    delete_favorite_vocabulary(&app_handle, &id)?;
+    vocabulary_images::delete_images(&app_data_dir, &id);
+
+    if let Some(vocabulary) = previous {
+        let word = serde_json::from_str::<FavoriteVocabulary>(&vocabulary)
+            .map(|favorite| favorite.word)
+            .unwrap_or_default();
+        undo.record(
+            &word,
+            UndoAction::DeleteVocabulary { vocabulary, images },
+            chrono::Utc::now(),
+        );
+    }
+    Ok(())
+}
+
+/// 设置生词配图：data_url 为粘贴的图片（data:image/png;base64,...），file_path 为截图文件，
+/// 两者都为空时移除图片
+#[tauri::command]
+pub async fn set_vocabulary_image_cmd(
+    app_handle: AppHandle,
+    vocabulary_id: String,
+    data_url: Option<String>,
+    file_path: Option<String>,
+) -> Result<FavoriteVocabulary, AppError> {
+    let json = load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
+    let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    let image = match (data_url, file_path) {
+        (Some(data_url), _) => Some(vocabulary_images::decode_data_url(&data_url)),
+        (None, Some(file_path)) => Some(vocabulary_images::read_image_file(&PathBuf::from(
+            file_path,
+        ))),
+        (None, None) => None,
+    };
+
+    favorite.image_path = match image {
+        Some(image) => {
+            let (extension, bytes) = image.map_err(AppError::invalid_input)?;
+            Some(vocabulary_images::save_image(
+                &app_data_dir,
+                &favorite.id,
+                extension,
+                &bytes,
+            )?)
+        }
+        None => {
+            vocabulary_images::delete_images(&app_data_dir, &favorite.id);
+            None
+        }
+    };
+
+    persist_favorite_vocabulary(&app_handle, &favorite)?;
+    Ok(favorite)
+}
+
+/// 设置单词收藏所属合集
+#[tauri::command]
+pub async fn set_vocabulary_pack_ids_cmd(
+    app_handle: AppHandle,
+    vocabulary_id: String,
+    pack_ids: Vec<String>,
+) -> Result<FavoriteVocabulary, AppError> {
+    let default_pack = ensure_default_word_pack(&app_handle)?;
+    let existing_pack_ids: HashSet<String> = list_word_packs(&app_handle)?.into_iter().collect();
+
+    let json = load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
+    let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+
+    favorite.pack_ids = filter_existing_pack_ids(
+        sanitize_pack_ids(Some(pack_ids)),
+        &existing_pack_ids,
+        &default_pack.id,
+    );
+    persist_favorite_vocabulary(&app_handle, &favorite)?;
+    Ok(favorite)
+}
+
+/// 按合集列出单词收藏
+#[tauri::command]
+pub async fn list_favorite_vocabularies_by_pack_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    language: Option<String>,
+) -> Result<Vec<FavoriteVocabulary>, AppError> {
+    let mut favorites = list_favorite_vocabularies_cmd(app_handle, language).await?;
+    if pack_id != "all" {
+        favorites.retain(|fav| fav.pack_ids.iter().any(|id| id == &pack_id));
+    }
+    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(favorites)
+}
+
+/// 获取指定日期到期的背诵队列
+#[tauri::command]
+pub async fn get_due_vocabulary_queue_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    date_local: String,
+    language: Option<String>,
+) -> Result<Vec<FavoriteVocabulary>, AppError> {
+    let config = load_config(&app_handle)?.unwrap_or_default();
+    // 单独背某个包时优先使用该包的每日新词上限（学习计划设置）
+    let new_limit = if pack_id == "all" {
+        None
+    } else {
+        load_word_pack(&app_handle, &pack_id)
+            .ok()
+            .and_then(|json| serde_json::from_str::<WordPack>(&json).ok())
+            .and_then(|pack| pack.daily_new_limit)
+    };
+    let all = list_favorite_vocabularies_cmd(app_handle, language).await?;
+    build_due_vocabulary_queue(
+        all,
+        &pack_id,
+        &date_local,
+        new_limit.unwrap_or(config.srs_daily_new_limit),
+        config.srs_daily_review_limit,
+    )
+    .map_err(AppError::from)
+}
+
+/// 生成单词包学习计划（如"60 天内背完 800 词"）；apply 为 true 时把建议节奏设为该包的每日新词上限
+#[tauri::command]
+pub async fn generate_study_plan_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    target_date: String,
+    date_local: String,
+    apply: Option<bool>,
+) -> Result<StudyPlan, AppError> {
+    let today = parse_local_date(&date_local)?;
+    let target = parse_local_date(&target_date)?;
+    let json = load_word_pack(&app_handle, &pack_id)?;
+    let mut pack: WordPack =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse word pack: {}", e))?;
+
+    let config = load_config(&app_handle)?.unwrap_or_default();
+    let mut favorites = list_favorite_vocabularies_cmd(app_handle.clone(), None).await?;
+    favorites.retain(|fav| fav.pack_ids.iter().any(|id| id == &pack_id));
+
+    let mut plan = plan_study_pace(
+        &pack_id,
+        &favorites,
+        today,
+        target,
+        config.srs_daily_review_limit,
+    )
+    .ok_or_else(|| AppError::invalid_input(tr("error.study_plan_date_invalid")))?;
+
+    if apply.unwrap_or(false) {
+        pack.daily_new_limit = Some(plan.daily_new as i32);
+        pack.updated_at = chrono::Utc::now().to_rfc3339();
+        let updated_json = serde_json::to_string(&pack)
+            .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+        save_word_pack(&app_handle, &pack.id, &updated_json)?;
+        plan.applied = true;
+    }
+
+    Ok(plan)
+}
+
+/// 复习单词并更新 SM-2 状态
+/// article_id: 在文章上下文中复习时传入，同时记录一次该文章中的作答
+#[tauri::command]
+pub async fn review_vocabulary_cmd(
+    app_handle: AppHandle,
+    vocabulary_id: String,
+    grade: String,
+    date_local: String,
+    article_id: Option<String>,
+) -> Result<FavoriteVocabulary, AppError> {
+    let review_date = parse_local_date(&date_local)?;
+
+    let json = load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
+    let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+
+    let next = calculate_sm2_update(
+        favorite.repetitions,
+        favorite.interval_days,
+        favorite.ease_factor,
+        &grade,
+        review_date,
+    )?;
+
+    favorite.srs_state = next.srs_state;
+    favorite.repetitions = next.repetitions;
+    favorite.interval_days = next.interval_days;
+    favorite.ease_factor = next.ease_factor;
+    favorite.due_date = next.due_date;
+    favorite.last_reviewed_at = Some(chrono::Utc::now().to_rfc3339());
+    favorite.review_count += 1;
+    if grade == "unknown" {
+        favorite.lapses += 1;
+    }
+
+    persist_favorite_vocabulary(&app_handle, &favorite)?;
+
+    let entry = ReviewLogEntry {
+        id: Uuid::new_v4().to_string(),
+        vocabulary_id: favorite.id.clone(),
+        grade,
+        review_date: date_local,
+        interval_days: favorite.interval_days,
+        reviewed_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let entry_json = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize review log: {}", e))?;
+    save_review_log_entry(&app_handle, &entry.id, &entry_json)?;
+
+    if let Some(article_id) = article_id.filter(|id| !id.is_empty()) {
+        let correct = entry.grade != "unknown";
+        record_encounter(&app_handle, &favorite.id, &article_id, correct)?;
+    }
+    Ok(favorite)
+}
+
+fn record_encounter(
+    app_handle: &AppHandle,
+    vocabulary_id: &str,
+    article_id: &str,
+    correct: bool,
+) -> Result<VocabularyEncounter, String> {
+    let encounter = VocabularyEncounter {
+        id: Uuid::new_v4().to_string(),
+        vocabulary_id: vocabulary_id.to_string(),
+        article_id: article_id.to_string(),
+        correct,
+        encountered_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let json = serde_json::to_string(&encounter)
+        .map_err(|e| format!("Failed to serialize encounter: {}", e))?;
+    save_vocabulary_encounter(app_handle, &encounter.id, &json)?;
+    Ok(encounter)
+}
+
+/// 记录生词在某篇文章中的一次作答（阅读时的小测等），不改变复习进度
+#[tauri::command]
+pub async fn record_vocabulary_encounter_cmd(
+    app_handle: AppHandle,
+    vocabulary_id: String,
+    article_id: String,
+    correct: bool,
+) -> Result<VocabularyEncounter, AppError> {
+    // 确认单词存在，避免为已删除的收藏留下记录
+    load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
+    Ok(record_encounter(
+        &app_handle,
+        &vocabulary_id,
+        &article_id,
+        correct,
+    )?)
+}
+
+/// 根据复习记录生成某一天的复习小结
+pub fn build_daily_review_summary(
+    review_log: &[ReviewLogEntry],
+    favorites: &[FavoriteVocabulary],
+    date: chrono::NaiveDate,
+) -> DailyReviewSummary {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    let todays: Vec<&ReviewLogEntry> = review_log
+        .iter()
+        .filter(|entry| entry.review_date == date_str)
+        .collect();
+
+    let count_grade = |grade: &str| todays.iter().filter(|e| e.grade == grade).count();
+    let known_count = count_grade("known");
+    let uncertain_count = count_grade("uncertain");
+    let unknown_count = count_grade("unknown");
+    let review_count = todays.len();
+    let accuracy = if review_count == 0 {
+        0.0
+    } else {
+        (known_count + uncertain_count) as f64 / review_count as f64
+    };
+
+    // 当日之前没有复习记录的单词视为新学
+    let reviewed_before: HashSet<&str> = review_log
+        .iter()
+        .filter(|entry| entry.review_date.as_str() < date_str.as_str())
+        .map(|entry| entry.vocabulary_id.as_str())
+        .collect();
+    let reviewed_today: HashSet<&str> = todays.iter().map(|e| e.vocabulary_id.as_str()).collect();
+    let mut new_words: Vec<FavoriteVocabulary> = favorites
+        .iter()
+        .filter(|fav| {
+            reviewed_today.contains(fav.id.as_str()) && !reviewed_before.contains(fav.id.as_str())
+        })
+        .cloned()
+        .collect();
+    new_words.sort_by(|a, b| a.word.cmp(&b.word));
+
+    let tomorrow = date + chrono::Duration::days(1);
+    let due_tomorrow_count = favorites
+        .iter()
+        .filter(|fav| fav.srs_state != "new" && is_due_on_or_before(&fav.due_date, tomorrow))
+        .count();
+
+    DailyReviewSummary {
+        date: date_str,
+        review_count,
+        known_count,
+        uncertain_count,
+        unknown_count,
+        accuracy,
+        new_words,
+        due_tomorrow_count,
+    }
+}
+
+/// 获取每日复习小结
+#[tauri::command]
+pub async fn get_daily_review_summary_cmd(
+    app_handle: AppHandle,
+    date_local: String,
+) -> Result<DailyReviewSummary, AppError> {
+    let date = parse_local_date(&date_local)?;
+    let review_log: Vec<ReviewLogEntry> = load_all_review_log_entries(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ReviewLogEntry>(json).ok())
+        .collect();
+    let favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+
+    Ok(build_daily_review_summary(&review_log, &favorites, date))
+}
+
+/// 保持率统计的间隔档位（复习前的间隔天数，含两端）
+const RETENTION_BUCKETS: &[(i32, Option<i32>)] = &[
+    (1, Some(1)),
+    (2, Some(3)),
+    (4, Some(7)),
+    (8, Some(14)),
+    (15, Some(30)),
+    (31, Some(90)),
+    (91, None),
+];
+
+fn retention_ratio(passed: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        passed as f64 / total as f64
+    }
+}
+
+/// 根据复习记录计算各间隔档位的实际保持率
+/// 每次复习的间隔取同一单词上一次复习后的 interval_days，首次复习（新词）不计入；
+/// month (YYYY-MM) 和 pack_id 用于筛选统计范围
+pub fn build_retention_stats(
+    review_log: &[ReviewLogEntry],
+    favorites: &[FavoriteVocabulary],
+    month: Option<&str>,
+    pack_id: Option<&str>,
+) -> RetentionStats {
+    let in_pack: Option<HashSet<&str>> = pack_id.map(|pack_id| {
+        favorites
+            .iter()
+            .filter(|fav| fav.pack_ids.iter().any(|id| id == pack_id))
+            .map(|fav| fav.id.as_str())
+            .collect()
+    });
+
+    let mut entries: Vec<&ReviewLogEntry> = review_log.iter().collect();
+    entries.sort_by(|a, b| a.reviewed_at.cmp(&b.reviewed_at));
+
+    let mut last_interval: HashMap<&str, i32> = HashMap::new();
+    let mut counts = vec![(0usize, 0usize); RETENTION_BUCKETS.len()];
+    for entry in entries {
+        let previous = last_interval.insert(entry.vocabulary_id.as_str(), entry.interval_days);
+        let Some(interval) = previous else {
+            continue;
+        };
+        if month.is_some_and(|month| !entry.review_date.starts_with(month)) {
+            continue;
+        }
+        if in_pack
+            .as_ref()
+            .is_some_and(|ids| !ids.contains(entry.vocabulary_id.as_str()))
+        {
+            continue;
+        }
+
+        let interval = interval.max(1);
+        let Some(index) = RETENTION_BUCKETS
+            .iter()
+            .position(|(min, max)| interval >= *min && max.is_none_or(|max| interval <= max))
+        else {
+            continue;
+        };
+        counts[index].0 += 1;
+        if entry.grade != "unknown" {
+            counts[index].1 += 1;
+        }
+    }
+
+    let buckets: Vec<RetentionBucket> = RETENTION_BUCKETS
+        .iter()
+        .zip(counts)
+        .map(
+            |((min, max), (review_count, passed_count))| RetentionBucket {
+                label: match max {
+                    Some(max) if max == min => format!("{}d", min),
+                    Some(max) => format!("{}-{}d", min, max),
+                    None => format!("{}d+", min),
+                },
+                min_days: *min,
+                max_days: *max,
+                review_count,
+                passed_count,
+                retention: retention_ratio(passed_count, review_count),
+            },
+        )
+        .collect();
+
+    let review_count = buckets.iter().map(|b| b.review_count).sum();
+    let passed_count = buckets.iter().map(|b| b.passed_count).sum();
+    RetentionStats {
+        month: month.map(str::to_string),
+        pack_id: pack_id.map(str::to_string),
+        review_count,
+        passed_count,
+        retention: retention_ratio(passed_count, review_count),
+        buckets,
+    }
+}
+
+/// 获取记忆保持率统计，可按月份 (YYYY-MM) 和单词包筛选
+#[tauri::command]
+pub async fn get_retention_stats_cmd(
+    app_handle: AppHandle,
+    month: Option<String>,
+    pack_id: Option<String>,
+) -> Result<RetentionStats, AppError> {
+    if let Some(month) = &month {
+        parse_local_date(&format!("{}-01", month))?;
+    }
+    let review_log: Vec<ReviewLogEntry> = load_all_review_log_entries(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ReviewLogEntry>(json).ok())
+        .collect();
+    let favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+
+    Ok(build_retention_stats(
+        &review_log,
+        &favorites,
+        month.as_deref(),
+        pack_id.as_deref(),
+    ))
+}
+
+/// 导出单词包为 OpenKoto JSON 包
+#[tauri::command]
+pub async fn export_word_pack_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+) -> Result<ExportWordPackResult, AppError> {
+    let pack_json = load_word_pack(&app_handle, &pack_id)?;
+    let pack: WordPack = serde_json::from_str(&pack_json)
+        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
+
+    let mut entries: Vec<WordPackExportEntry> =
+        list_favorite_vocabularies_by_pack_cmd(app_handle.clone(), pack_id, None)
+            .await?
+            .into_iter()
+            .map(|fav| WordPackExportEntry {
+                word: fav.word,
+                meaning: fav.meaning,
+                usage: if fav.usage.trim().is_empty() {
+                    None
+                } else {
+                    Some(fav.usage)
+                },
+                example: fav.example,
+                reading: fav.reading,
+                phonetic: fav.phonetic,
+                explanation: fav.explanation,
+                tags: fav.tags,
+            })
+            .collect();
+
+    entries.sort_by(|a, b| a.word.cmp(&b.word));
+
+    let export_file = WordPackExportFile {
+        schema_version: "openkoto-word-pack-v1".to_string(),
+        pack: WordPackExportMeta {
+            name: pack.name.clone(),
+            description: pack.description.clone(),
+            cover_url: pack.cover_url.clone(),
+            author: pack.author.clone(),
+            language_from: pack.language_from.clone(),
+            language_to: pack.language_to.clone(),
+            tags: pack.tags.clone(),
+            version: pack.version.clone(),
+        },
+        entries,
+    };
+
+    let json_content = serde_json::to_string_pretty(&export_file)
+        .map_err(|e| format!("Failed to serialize export file: {}", e))?;
+    let file_name = format!("{}.okpack.json", sanitize_file_name(&pack.name));
+
+    Ok(ExportWordPackResult {
+        file_name,
+        json_content,
+    })
+}
+
+/// 把单词包导出为离线卡片包（cards.json + index.html + 单词与例句音频 + 配图），放在 exports 目录
+/// 音频复用朗读缓存，离线或未配置模型时跳过未缓存的音频；进度通过 card-bundle-progress://{pack_id} 事件发送
+#[tauri::command]
+pub async fn export_card_bundle_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    include_audio: Option<bool>,
+) -> Result<ExportCardBundleResult, AppError> {
+    let pack_json = load_word_pack(&app_handle, &pack_id)?;
+    let pack: WordPack = serde_json::from_str(&pack_json)
+        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
+    let mut vocabularies =
+        list_favorite_vocabularies_by_pack_cmd(app_handle.clone(), pack_id.clone(), None).await?;
+    vocabularies.sort_by(|a, b| a.word.cmp(&b.word));
+
+    let config = load_config(&app_handle)?.unwrap_or_default();
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let tts_dir = app_data_dir.join(tts::TTS_AUDIO_DIR);
+    let images_dir = app_data_dir.join(vocabulary_images::VOCABULARY_IMAGES_DIR);
+    let now = chrono::Local::now();
+    let bundle_dir = app_data_dir
+        .join(card_bundle::EXPORTS_DIR)
+        .join(card_bundle::bundle_dir_name(&pack.name, &now));
+    let (audio_out, images_out) = (bundle_dir.join("audio"), bundle_dir.join("images"));
+    for dir in [&tts_dir, &audio_out, &images_out] {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create dir: {}", e))?;
+    }
+
+    let include_audio = include_audio.unwrap_or(true);
+    let (mut audio_count, mut image_count, mut missing_audio) = (0, 0, 0);
+    let total = vocabularies.len();
+    let mut cards = Vec::with_capacity(total);
+
+    for (index, vocabulary) in vocabularies.iter().enumerate() {
+        let mut card = card_bundle::BundleCard::from_vocabulary(vocabulary);
+
+        if let Some(image) = vocabulary.image_path.as_deref() {
+            let source = images_dir.join(image);
+            if source.exists() {
+                std::fs::copy(&source, images_out.join(image))
+                    .map_err(|e| format!("Failed to copy image: {}", e))?;
+                card.image = Some(format!("images/{}", image));
+                image_count += 1;
+            }
+        }
+
+        let texts = [Some(card.word.clone()), card.example.clone()];
+        for (slot, text) in texts.into_iter().enumerate() {
+            let Some(text) = text.filter(|_| include_audio) else {
+                continue;
+            };
+            let filename = match cached_speech(
+                &config,
+                &tts_dir,
+                &config.tts.voice,
+                config.tts.speed,
+                &text,
+            )
+            .await
+            {
+                Ok(Ok(filename)) => filename,
+                Ok(Err(e)) => {
+                    eprintln!("[CardBundle] Audio for {} failed: {}", vocabulary.word, e);
+                    missing_audio += 1;
+                    continue;
+                }
+                // 离线或未配置模型：之后只使用已缓存的音频
+                Err(_) => {
+                    let filename = tts::audio_file_name(
+                        &text,
+                        &config.tts.model,
+                        &config.tts.voice,
+                        config.tts.speed,
+                    );
+                    if !tts_dir.join(&filename).exists() {
+                        missing_audio += 1;
+                        continue;
+                    }
+                    filename
+                }
+            };
+            std::fs::copy(tts_dir.join(&filename), audio_out.join(&filename))
+                .map_err(|e| format!("Failed to copy audio: {}", e))?;
+            let path = Some(format!("audio/{}", filename));
+            if slot == 0 {
+                card.audio = path;
+            } else {
+                card.example_audio = path;
+            }
+            audio_count += 1;
+        }
+
+        cards.push(card);
+        let _ = app_handle.emit(
+            &format!("card-bundle-progress://{}", pack_id),
+            serde_json::json!({ "current": index + 1, "total": total }),
+        );
+    }
+
+    let bundle = card_bundle::CardBundle {
+        schema_version: card_bundle::CARD_BUNDLE_SCHEMA.to_string(),
+        pack: card_bundle::BundlePack::from_pack(&pack),
+        exported_at: now.to_rfc3339(),
+        cards,
+    };
+    card_bundle::write_bundle(&bundle_dir, &bundle)?;
+
+    Ok(ExportCardBundleResult {
+        path: bundle_dir.to_string_lossy().into_owned(),
+        card_count: total,
+        audio_count,
+        image_count,
+        missing_audio,
+    })
+}
+
+/// 导入 OpenKoto JSON 单词包
+#[tauri::command]
+pub async fn import_word_pack_cmd(
+    app_handle: AppHandle,
+    json_content: String,
+) -> Result<ImportWordPackResult, AppError> {
+    ensure_default_word_pack(&app_handle)?;
+    let parsed: WordPackExportFile = serde_json::from_str(&json_content)
+        .map_err(|e| format!("Invalid word pack JSON: {}", e))?;
+
+    if parsed.entries.len() > 20000 {
+        return Err(AppError::invalid_input(tr("error.pack_too_large")));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let pack = WordPack {
+        id: Uuid::new_v4().to_string(),
+        name: if parsed.pack.name.trim().is_empty() {
+            "Imported Pack".to_string()
+        } else {
+            parsed.pack.name.trim().to_string()
+        },
+        description: parsed.pack.description.clone(),
+        cover_url: parsed.pack.cover_url.clone(),
+        author: parsed.pack.author.clone(),
+        language_from: parsed
+            .pack
+            .language_from
+            .clone()
+            .or_else(|| active_learning_language(&app_handle)),
+        language_to: parsed.pack.language_to.clone(),
+        tags: parsed.pack.tags.clone(),
+        version: parsed.pack.version.clone(),
+        created_at: now.clone(),
+        updated_at: now,
+        is_system: false,
+        daily_new_limit: None,
+    };
+
+    let pack_json = serde_json::to_string(&pack)
+        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
+    save_word_pack(&app_handle, &pack.id, &pack_json)?;
+
+    let mut existing_words: HashSet<String> = load_all_favorite_vocabularies_internal(&app_handle)?
+        .into_iter()
+        .map(|fav| normalize_word(&fav.word))
+        .collect();
+    let mut file_seen_words = HashSet::new();
+    let corpus = load_corpus(&app_handle)?;
+
+    let total = parsed.entries.len();
+    let mut imported = 0usize;
+    let mut skipped = 0usize;
+    let mut errors = Vec::new();
+
+    for (index, entry) in parsed.entries.into_iter().enumerate() {
+        let word = entry.word.trim().to_string();
+        let meaning = entry.meaning.trim().to_string();
+        if word.is_empty() || meaning.is_empty() {
+            skipped += 1;
+            errors.push(format!("Entry {} missing required word/meaning", index + 1));
+            continue;
+        }
+
+        let normalized = normalize_word(&word);
+        if file_seen_words.contains(&normalized) || existing_words.contains(&normalized) {
+            skipped += 1;
+            continue;
+        }
+
+        file_seen_words.insert(normalized.clone());
+        existing_words.insert(normalized);
+
+        let mut favorite = FavoriteVocabulary {
+            id: Uuid::new_v4().to_string(),
+            word,
+            meaning,
+            usage: entry.usage.unwrap_or_default(),
+            explanation: entry.explanation,
+            example: entry.example,
+            reading: entry.reading,
+            phonetic: entry.phonetic,
+            source_article_id: None,
+            source_article_title: None,
+            pack_ids: vec![pack.id.clone()],
+            tags: entry.tags,
+            image_path: None,
+            language: pack.language_from.clone(),
+            srs_state: "new".to_string(),
+            ease_factor: 2.5,
+            repetitions: 0,
+            interval_days: 0,
+            due_date: today_local_date().format("%Y-%m-%d").to_string(),
+            last_reviewed_at: None,
+            review_count: 0,
+            lapses: 0,
+            corpus_count: 0,
+            corpus_articles: 0,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+        corpus.count_into(&mut favorite);
+        // 导入时只做本地转写，需要 AI 的读音由 backfill_vocabulary_phonetics_cmd 批量补全
+        if favorite.phonetic.is_none() {
+            favorite.phonetic = readings::local_phonetic(
+                &phonetic_language(&favorite),
+                &favorite.word,
+                favorite.reading.as_deref(),
+            );
+        }
+
+        if let Err(e) = persist_favorite_vocabulary(&app_handle, &favorite) {
+            skipped += 1;
+            errors.push(format!("Entry {} failed to import: {}", index + 1, e));
+            continue;
+        }
+
+        imported += 1;
+    }
+
+    Ok(ImportWordPackResult {
+        created_pack_id: pack.id,
+        total,
+        imported,
+        skipped,
+        errors,
+    })
+}
+
+/// 添加语法收藏
+#[tauri::command]
+pub async fn add_favorite_grammar_cmd(
+    app_handle: AppHandle,
+    point: String,
+    explanation: String,
+    example: Option<String>,
+    source_article_id: Option<String>,
+    source_article_title: Option<String>,
+    source_segment_id: Option<String>,
+) -> Result<FavoriteGrammar, AppError> {
+    let favorite = FavoriteGrammar {
+        id: Uuid::new_v4().to_string(),
+        point,
+        explanation,
+        example,
+        source_article_id,
+        source_article_title,
+        source_segment_id,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let json = serde_json::to_string(&favorite)
+        .map_err(|e| format!("Failed to serialize favorite: {}", e))?;
+    save_favorite_grammar(&app_handle, &favorite.id, &json)?;
+
+    Ok(favorite)
+}
+
+/// 列出所有语法收藏
+#[tauri::command]
+pub async fn list_favorite_grammars_cmd(
+    app_handle: AppHandle,
+) -> Result<Vec<FavoriteGrammar>, AppError> {
+    let mut favorites: Vec<FavoriteGrammar> = load_all_favorite_grammars(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteGrammar>(json).ok())
+        .collect();
+
+    // 按创建时间降序排列
+    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(favorites)
+}
+
+/// 删除语法收藏
+#[tauri::command]
+pub async fn delete_favorite_grammar_cmd(
+    app_handle: AppHandle,
+    id: String,
+) -> Result<(), AppError> {
+    delete_favorite_grammar(&app_handle, &id)?;
+    Ok(())
+}
+
+/// 比较语法点时忽略大小写、空白和 〜 等占位符号
+fn normalize_grammar_point(point: &str) -> String {
+    point
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '〜' | '～' | '~'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 段落是否用到了某个语法收藏：收藏时所在的段落，或讲解中列出了同一语法点的段落
+pub fn segment_uses_grammar(segment: &ArticleSegment, grammar: &FavoriteGrammar) -> bool {
+    if grammar.source_segment_id.as_deref() == Some(segment.id.as_str()) {
+        return true;
+    }
+    let point = normalize_grammar_point(&grammar.point);
+    !point.is_empty()
+        && segment.explanation.as_ref().is_some_and(|explanation| {
+            explanation
+                .grammar_points
+                .iter()
+                .any(|p| normalize_grammar_point(&p.point) == point)
+        })
+}
+
+/// 列出语法收藏在各篇文章中的用例，收藏时所在的段落排在最前
+pub fn find_grammar_usages(grammar: &FavoriteGrammar, articles: &[Article]) -> Vec<GrammarUsage> {
+    let mut usages: Vec<GrammarUsage> = articles
+        .iter()
+        .flat_map(|article| {
+            article
+                .segments
+                .iter()
+                .filter(|segment| segment_uses_grammar(segment, grammar))
+                .map(|segment| GrammarUsage {
+                    article_id: article.id.clone(),
+                    article_title: article.title.clone(),
+                    segment_id: segment.id.clone(),
+                    order: segment.order,
+                    text: segment.text.clone(),
+                    translation: segment.translation.clone(),
+                    is_source: grammar.source_segment_id.as_deref() == Some(segment.id.as_str()),
+                })
+        })
+        .collect();
+    usages.sort_by_key(|usage| !usage.is_source);
+    usages
+}
+
+/// 文章中每个段落包含的语法收藏（段落 id -> 语法收藏 id），没有匹配的段落不列出
+pub fn article_grammar_links(
+    article: &Article,
+    grammars: &[FavoriteGrammar],
+) -> BTreeMap<String, Vec<String>> {
+    article
+        .segments
+        .iter()
+        .filter_map(|segment| {
+            let ids: Vec<String> = grammars
+                .iter()
+                .filter(|grammar| segment_uses_grammar(segment, grammar))
+                .map(|grammar| grammar.id.clone())
+                .collect();
+            (!ids.is_empty()).then(|| (segment.id.clone(), ids))
+        })
+        .collect()
+}
+
+/// 列出语法收藏在所有文章中的例句段落
+#[tauri::command]
+pub async fn list_grammar_usages_cmd(
+    app_handle: AppHandle,
+    grammar_id: String,
+) -> Result<Vec<GrammarUsage>, AppError> {
+    let grammar = load_all_favorite_grammars(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteGrammar>(json).ok())
+        .find(|grammar| grammar.id == grammar_id)
+        .ok_or_else(|| AppError::not_found(tr("error.grammar_not_found")))?;
+    let mut articles: Vec<Article> = load_all_articles(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<Article>(json).ok())
+        .collect();
+    articles.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(find_grammar_usages(&grammar, &articles))
+}
+
+/// 文章中各段落包含的语法收藏，供阅读界面标注
+#[tauri::command]
+pub async fn get_article_grammar_links_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<BTreeMap<String, Vec<String>>, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    let grammars: Vec<FavoriteGrammar> = load_all_favorite_grammars(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteGrammar>(json).ok())
+        .collect();
+    Ok(article_grammar_links(&article, &grammars))
+}
+
+// YouTube Import
+/// 列出 YouTube 视频可下载的字幕轨（上传字幕和自动字幕），供导入前选择语言
+#[tauri::command]
+pub async fn list_youtube_subtitles_cmd(
+    app_handle: AppHandle,
+    url: String,
+) -> Result<Vec<YoutubeSubtitleTrack>, AppError> {
+    offline::ensure_online()?;
+    Ok(crate::youtube::list_subtitle_tracks(&app_handle, &url).await?)
+}
+
+/// 下载过程中发送 youtube-download-progress://{event_id} 事件（进度百分比、速度、剩余时间）
+/// quality 未指定时下载 720p
+#[tauri::command]
+pub async fn import_youtube_video_cmd(
+    app_handle: AppHandle,
+    url: String,
+    subtitles: Option<YoutubeSubtitleOptions>,
+    quality: Option<YoutubeQuality>,
+    event_id: Option<String>,
+) -> Result<Article, AppError> {
+    offline::ensure_online()?;
+    let mut article = crate::youtube::import_youtube_video(
+        app_handle.clone(),
+        url,
+        subtitles.unwrap_or_default(),
+        quality.unwrap_or_default(),
+        event_id.as_deref().unwrap_or("default"),
+    )
+    .await?;
+    save_downloaded_article(&app_handle, &mut article)?;
+    Ok(article)
+}
+
+/// 导入任意 yt-dlp 支持的网站（Vimeo、SoundCloud、新闻网站等）的视频或音频，
+/// 网站没有字幕时照常导入，之后可用转录生成字幕。进度事件与 import_youtube_video_cmd 相同
+#[tauri::command]
+pub async fn import_media_url_cmd(
+    app_handle: AppHandle,
+    url: String,
+    subtitles: Option<YoutubeSubtitleOptions>,
+    quality: Option<YoutubeQuality>,
+    event_id: Option<String>,
+) -> Result<Article, AppError> {
+    let url = url.trim().to_string();
+    if url.is_empty() {
+        return Err(AppError::invalid_input(tr("error.media_url_empty")));
+    }
+    offline::ensure_online()?;
+    let mut article = crate::youtube::import_media_url(
+        app_handle.clone(),
+        url,
+        subtitles.unwrap_or_default(),
+        quality.unwrap_or_default(),
+        event_id.as_deref().unwrap_or("default"),
+    )
+    .await?;
+    save_downloaded_article(&app_handle, &mut article)?;
+    Ok(article)
+}
+
+/// 下载导入的文章检测语言并保存；没有字幕时内容只是占位文本，优先用网站提供的原始语言
+fn save_downloaded_article(app_handle: &AppHandle, article: &mut Article) -> Result<(), String> {
+    let original_language = article
+        .media_info
+        .as_ref()
+        .and_then(|info| info.original_language.clone());
+    article.language = if article.segments.is_empty() {
+        original_language.or_else(|| detect_article_language(article))
+    } else {
+        detect_article_language(article).or(original_language)
+    }
+    .or_else(|| active_learning_language(app_handle));
+    finish_article_import(app_handle, article);
+
+    let article_json = serde_json::to_string(article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &article.id, &article_json)
+}
+
+/// 频道每次检查的默认和最多视频数
+const DEFAULT_CHANNEL_VIDEO_LIMIT: usize = 5;
+const MAX_CHANNEL_VIDEO_LIMIT: usize = 50;
+
+fn load_channel_subscription_internal(
+    app_handle: &AppHandle,
+    id: &str,
+) -> Result<ChannelSubscription, AppError> {
+    let json = load_channel_subscription(app_handle, id)?
+        .ok_or_else(|| AppError::not_found(tr("error.channel_subscription_not_found")))?;
+    Ok(serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse channel subscription: {}", e))?)
+}
+
+fn persist_channel_subscription(
+    app_handle: &AppHandle,
+    subscription: &ChannelSubscription,
+) -> Result<(), String> {
+    let json = serde_json::to_string(subscription)
+        .map_err(|e| format!("Failed to serialize channel subscription: {}", e))?;
+    save_channel_subscription(app_handle, &subscription.id, &json)
+}
+
+/// 导入频道最新视频中尚未导入的（按视频 ID 去重），逐个发送
+/// youtube-channel-import-progress://{event_id} 进度事件
+async fn import_new_channel_videos(
+    app_handle: &AppHandle,
+    mut subscription: ChannelSubscription,
+    event_id: Option<String>,
+) -> Result<ChannelImportResult, AppError> {
+    offline::ensure_online()?;
+    let (title, videos) =
+        crate::youtube::list_channel_videos(app_handle, &subscription.url, subscription.limit)
+            .await?;
+    if title.is_some() {
+        subscription.title = title;
+    }
+
+    let event_id = event_id.unwrap_or_else(|| subscription.id.clone());
+    let event_name = format!("youtube-channel-import-progress://{}", event_id);
+    let total = videos.len();
+    let mut imported = Vec::new();
+    let mut skipped = 0;
+    let mut failed = Vec::new();
+    for (i, video) in videos.into_iter().enumerate() {
+        // 文章 ID 即视频 ID：已导入过或文章仍存在的跳过
+        if subscription.seen_video_ids.contains(&video.id)
+            || load_article(app_handle, &video.id).is_ok()
+        {
+            if !subscription.seen_video_ids.contains(&video.id) {
+                subscription.seen_video_ids.push(video.id.clone());
+            }
+            skipped += 1;
+            continue;
+        }
+
+        let message = tr_with(
+            "import.channel_video",
+            &[
+                ("current", (i + 1).to_string()),
+                ("total", total.to_string()),
+                ("title", video.title.clone()),
+            ],
+        );
+        let _ = app_handle.emit(
+            &event_name,
+            serde_json::json!({
+                "current": i + 1,
+                "total": total,
+                "video_id": video.id,
+                "status": "importing",
+                "message": message,
+            }),
+        );
+        match import_youtube_video_cmd(
+            app_handle.clone(),
+            video.url.clone(),
+            Some(subscription.subtitles.clone()),
+            Some(subscription.quality),
+            Some(event_id.clone()),
+        )
+        .await
+        {
+            Ok(article) => {
+                subscription.seen_video_ids.push(video.id);
+                imported.push(article);
+            }
+            Err(e) => {
+                // 失败的视频不记为已导入，下次检查时重试
+                eprintln!("[Channel] 导入失败 {}: {}", video.url, e);
+                failed.push(FailedChannelVideo {
+                    video_id: video.id,
+                    title: video.title,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    subscription.last_checked_at = Some(chrono::Utc::now().to_rfc3339());
+    persist_channel_subscription(app_handle, &subscription)?;
+    let _ = app_handle.emit(
+        &event_name,
+        serde_json::json!({
+            "current": total,
+            "total": total,
+            "status": "completed",
+            "message": tr_with(
+                "import.channel_completed",
+                &[
+                    ("imported", imported.len().to_string()),
+                    ("skipped", skipped.to_string()),
+                    ("failed", failed.len().to_string()),
+                ],
+            ),
+        }),
+    );
+
+    Ok(ChannelImportResult {
+        subscription,
+        imported,
+        skipped,
+        failed,
+    })
+}
+
+/// 订阅 YouTube 频道并导入最新 limit 个视频；已订阅同一地址时更新设置后重新检查
+#[tauri::command]
+pub async fn subscribe_youtube_channel_cmd(
+    app_handle: AppHandle,
+    url: String,
+    limit: Option<usize>,
+    subtitles: Option<YoutubeSubtitleOptions>,
+    quality: Option<YoutubeQuality>,
+    event_id: Option<String>,
+) -> Result<ChannelImportResult, AppError> {
+    let url = url.trim().trim_end_matches('/').to_string();
+    if url.is_empty() {
+        return Err(AppError::invalid_input(tr("error.channel_url_empty")));
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_CHANNEL_VIDEO_LIMIT)
+        .clamp(1, MAX_CHANNEL_VIDEO_LIMIT);
+
+    let existing = load_all_channel_subscriptions(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ChannelSubscription>(json).ok())
+        .find(|subscription| subscription.url.eq_ignore_ascii_case(&url));
+    let subscription = match existing {
+        Some(mut subscription) => {
+            subscription.limit = limit;
+            if let Some(subtitles) = subtitles {
+                subscription.subtitles = subtitles;
+            }
+            if let Some(quality) = quality {
+                subscription.quality = quality;
+            }
+            subscription
+        }
+        None => ChannelSubscription {
+            id: Uuid::new_v4().to_string(),
+            url,
+            title: None,
+            limit,
+            subtitles: subtitles.unwrap_or_default(),
+            quality: quality.unwrap_or_default(),
+            seen_video_ids: Vec::new(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            last_checked_at: None,
+        },
+    };
+    persist_channel_subscription(&app_handle, &subscription)?;
+
+    import_new_channel_videos(&app_handle, subscription, event_id).await
+}
+
+/// 检查订阅频道的新视频，只导入尚未导入的
+#[tauri::command]
+pub async fn check_youtube_channel_cmd(
+    app_handle: AppHandle,
+    subscription_id: String,
+    event_id: Option<String>,
+) -> Result<ChannelImportResult, AppError> {
+    let subscription = load_channel_subscription_internal(&app_handle, &subscription_id)?;
+    import_new_channel_videos(&app_handle, subscription, event_id).await
+}
+
+/// 列出订阅的频道，按订阅时间排序
+#[tauri::command]
+pub async fn list_channel_subscriptions_cmd(
+    app_handle: AppHandle,
+) -> Result<Vec<ChannelSubscription>, AppError> {
+    let mut subscriptions: Vec<ChannelSubscription> = load_all_channel_subscriptions(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .collect();
+    subscriptions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(subscriptions)
+}
+
+/// 取消订阅频道（已导入的文章保留）
+#[tauri::command]
+pub async fn delete_channel_subscription_cmd(
+    app_handle: AppHandle,
+    id: String,
+) -> Result<(), AppError> {
+    delete_channel_subscription(&app_handle, &id)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn import_local_video_cmd(
+    app_handle: AppHandle,
+    file_path: String,
+) -> Result<Article, AppError> {
+    import_local_video(&app_handle, &file_path)
+}
+
+pub(crate) fn import_local_video(
+    app_handle: &AppHandle,
+    file_path: &str,
+) -> Result<Article, AppError> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+
+    let videos_dir = app_data_dir.join("videos");
+    if !videos_dir.exists() {
+        std::fs::create_dir_all(&videos_dir)
+            .map_err(|e| format!("Failed to create videos dir: {}", e))?;
+    }
+
+    let src_path = std::path::Path::new(file_path);
+    if !src_path.exists() {
+        return Err(AppError::not_found(tr("error.source_file_missing")));
+    }
+
+    let file_name = src_path
+        .file_name()
+        .ok_or("Invalid file name")?
+        .to_string_lossy();
+
+    let ext = src_path
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mp4".to_string());
+
+    let id = Uuid::new_v4().to_string();
+    let dest_name = format!("{}.{}", id, ext);
+    let dest_path = videos_dir.join(&dest_name);
+
+    std::fs::copy(src_path, &dest_path).map_err(|e| format!("Failed to copy file: {}", e))?;
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let is_audio = AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str());
+
+    // Initial content placeholder
+    let content = if is_audio {
+        format!("[Audio Import] {}", file_name)
+    } else {
+        format!("[Local Import] {}", file_name)
+    };
+
+    let mut article = Article {
+        id: id.clone(),
+        title: file_name.into_owned(),
+        content,
+        source_type: Some(if is_audio {
+            "audio".to_string()
+        } else {
+            "local_video".to_string()
+        }),
+        source_url: Some(format!("file://{}", file_path)),
+        media_path: Some(dest_path.to_string_lossy().into_owned()),
+        book_path: None,
+        book_type: None,
+        language: active_learning_language(app_handle),
+        created_at,
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments: Vec::new(),
+    };
+    finish_article_import(app_handle, &mut article);
+
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &id, &article_json)?;
+
+    Ok(article)
+}
+
+/// 本地导入识别为音频的扩展名（小写）
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "m4a", "aac", "flac", "ogg", "wma"];
+
+/// 文件夹批量导入时识别为视频的扩展名（小写）
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "mov", "webm", "avi", "m4v", "flv"];
+
+/// 是否为可导入的本地音视频文件（按扩展名判断）
+pub fn is_local_media_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| {
+            AUDIO_EXTENSIONS.contains(&ext.as_str()) || VIDEO_EXTENSIONS.contains(&ext.as_str())
+        })
+}
+
+/// 文件名自然排序：数字部分按数值比较，"第2课" 排在 "第10课" 之前
+pub fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    // 大小写、前导零只在其余部分完全相同时才决定顺序
+    let mut tiebreak = Ordering::Equal;
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return tiebreak,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let mut x_digits = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    x_digits.push(c);
+                }
+                let mut y_digits = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    y_digits.push(c);
+                }
+                let x_trimmed = x_digits.trim_start_matches('0');
+                let y_trimmed = y_digits.trim_start_matches('0');
+                let ordering = x_trimmed
+                    .len()
+                    .cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                tiebreak = tiebreak.then(x_digits.len().cmp(&y_digits.len()));
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_lowercase().cmp(y.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                tiebreak = tiebreak.then(x.cmp(&y));
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// 批量导入文件夹中的本地视频/音频
+/// 按文件名自然排序逐个导入，通过 video-folder-import-progress://{event_id} 上报每个文件的进度；
+/// extract_subtitles 为 true 时导入完成后在后台依次提取字幕（job_id 为 subtitles:{article_id}，可单独取消）
+#[tauri::command]
+pub async fn import_local_video_folder_cmd(
+    app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    folder_path: String,
+    extract_subtitles: Option<bool>,
+    event_id: Option<String>,
+) -> Result<ImportVideoFolderResult, AppError> {
+    let folder = std::path::Path::new(&folder_path);
+    if !folder.is_dir() {
+        return Err(AppError::not_found(tr_with(
+            "error.folder_missing",
+            &[("path", folder_path.clone())],
+        )));
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read folder: {}", e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_local_media_file(path))
+        .collect();
+    if files.is_empty() {
+        return Err(AppError::not_found(tr("error.folder_no_media")));
+    }
+    files.sort_by(|a, b| {
+        natural_cmp(
+            &a.file_name().unwrap_or_default().to_string_lossy(),
+            &b.file_name().unwrap_or_default().to_string_lossy(),
+        )
+    });
+
+    let event_name = format!(
+        "video-folder-import-progress://{}",
+        event_id.unwrap_or_else(|| "default".to_string())
+    );
+    let total = files.len();
+    let mut result = ImportVideoFolderResult {
+        imported: Vec::new(),
+        failed: Vec::new(),
+        subtitle_job_ids: Vec::new(),
+    };
+
+    for (i, path) in files.iter().enumerate() {
+        let file_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let file_path = path.to_string_lossy().into_owned();
+        let message = tr_with(
+            "import.folder_file",
+            &[
+                ("current", (i + 1).to_string()),
+                ("total", total.to_string()),
+                ("file", file_name.clone()),
+            ],
+        );
+
+        match import_local_video(&app_handle, &file_path) {
+            Ok(article) => {
+                let _ = app_handle.emit(
+                    &event_name,
+                    serde_json::json!({
+                        "current": i + 1,
+                        "total": total,
+                        "file": file_name,
+                        "status": "imported",
+                        "article_id": article.id,
+                        "message": message,
+                    }),
+                );
+                result.imported.push(article);
+            }
+            Err(e) => {
+                eprintln!("[ImportFolder] 导入失败 {}: {}", file_path, e);
+                let _ = app_handle.emit(
+                    &event_name,
+                    serde_json::json!({
+                        "current": i + 1,
+                        "total": total,
+                        "file": file_name,
+                        "status": "failed",
+                        "error": e.to_string(),
+                        "message": message,
+                    }),
+                );
+                result.failed.push(FailedVideoImport {
+                    file_path,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    let _ = app_handle.emit(
+        &event_name,
+        serde_json::json!({
+            "current": total,
+            "total": total,
+            "status": "completed",
+            "message": tr_with(
+                "import.folder_completed",
+                &[
+                    ("imported", result.imported.len().to_string()),
+                    ("failed", result.failed.len().to_string()),
+                ],
+            ),
+        }),
+    );
+
+    if extract_subtitles.unwrap_or(false) && !result.imported.is_empty() {
+        // 先注册全部任务，排队中的字幕提取也可以被取消
+        let queued: Vec<_> = result
+            .imported
+            .iter()
+            .filter_map(|article| {
+                let job_id = subtitle_job_id(&article.id);
+                let job = jobs.try_register(job_id.clone())?;
+                result.subtitle_job_ids.push(job_id);
+                Some((article.id.clone(), job))
+            })
+            .collect();
+
+        let app = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            for (article_id, job) in queued {
+                if job.token().is_cancelled() {
+                    continue;
+                }
+                // 排队中的任务遇到限流时按 Retry-After 暂停后重新提取
+                let mut rate_limit_waits = 0;
+                let result = loop {
+                    let result =
+                        extract_subtitles_for_article(&app, &article_id, job.token(), None).await;
+                    match &result {
+                        Err(e)
+                            if rate_limit_waits < MAX_RATE_LIMIT_WAITS
+                                && wait_for_rate_limit(
+                                    &app,
+                                    job.token(),
+                                    &article_id,
+                                    &e.message,
+                                )
+                                .await =>
+                        {
+                            rate_limit_waits += 1;
+                        }
+                        _ => break result,
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("[ImportFolder] 字幕提取失败 {}: {}", article_id, e);
+                    emit_article_event(
+                        &app,
+                        &article_id,
+                        &format!("subtitle-extraction-progress://{}", article_id),
+                        serde_json::json!({ "phase": "error", "message": e.to_string() }),
+                    );
+                }
+            }
+        });
+    }
 
-    // 4. Decode HTML entities
-    let decoded = html_escape::decode_html_entities(&stripped);
+    Ok(result)
+}
 
-    // 5. Clean up whitespace
-    // Split by newline, trim each line, filter empty lines if they are excessive (more than 2)
-    // But for lyrics, we want to keep single empty lines (stanza breaks).
-    let lines: Vec<&str> = decoded.lines().collect();
-    let mut clean_lines = Vec::new();
-    let mut empty_count = 0;
+// 字幕提取
+/// 提取视频字幕
+/// 使用 Gemini 多模态 API 从视频中提取音频并转录为字幕
+/// 传入 start_time/end_time（秒）时只提取该区间，区间外已有的字幕保留
+/// 任务按文章注册，可用 cancel_subtitle_extraction_cmd 取消；同一文章正在提取时拒绝重复发起
+#[tauri::command]
+pub async fn extract_subtitles_cmd(
+    app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+) -> Result<Article, AppError> {
+    let range = match (start_time, end_time) {
+        (None, None) => None,
+        (start, end) => Some(TimeRange::new(start, end).map_err(AppError::invalid_input)?),
+    };
+    let job = jobs
+        .try_register(subtitle_job_id(&article_id))
+        .ok_or_else(|| AppError::invalid_input(tr("error.subtitle_extraction_running")))?;
+    extract_subtitles_for_article(&app_handle, &article_id, job.token(), range).await
+}
 
-    for line in lines {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            empty_count += 1;
-            // Allow up to 2 consecutive empty lines (paragraph break)
-            if empty_count <= 2 {
-                clean_lines.push("");
+/// 字幕提取任务的 job_id（单个提取和文件夹导入排队提取共用，取消时也按它查找）
+fn subtitle_job_id(article_id: &str) -> String {
+    format!("subtitles:{}", article_id)
+}
+
+/// 取消字幕提取：event_id 即进度事件 subtitle-extraction-progress://{event_id} 中的文章 ID
+/// 分片提取在当前片段完成后停止，已有字幕不会被覆盖；返回是否有正在运行的提取
+#[tauri::command]
+pub async fn cancel_subtitle_extraction_cmd(
+    jobs: State<'_, JobRegistry>,
+    event_id: String,
+) -> Result<bool, AppError> {
+    let found = jobs.cancel(&subtitle_job_id(&event_id));
+    if found {
+        println!("[ExtractSubtitles] Cancelled extraction: {}", event_id);
+    }
+    Ok(found)
+}
+
+/// 根据设置选择转录引擎（"auto" 时按字幕转录模型选择 Gemini / Kimi / OpenAI 音频）
+fn configured_transcription_engine(
+    app_handle: &AppHandle,
+) -> Result<Box<dyn TranscriptionEngine>, AppError> {
+    let config = load_config(app_handle)?
+        .ok_or_else(|| AppError::missing_api_key(tr("error.ai_not_configured")))?;
+    let model_config = config.get_config_for_task(AiTask::SubtitleTranscription);
+    let engine_id = resolve_engine_id(&config.transcription, model_config)
+        .map_err(|e| AppError::new(ErrorKind::Config, e))?;
+    // 除本地 whisper 外的引擎都需要联网
+    if engine_id != "whisper_local" {
+        offline::ensure_online()?;
+    }
+    create_engine(app_handle, engine_id, &config.transcription, model_config)
+        .map_err(|e| AppError::new(ErrorKind::Config, e))
+}
+
+/// 为视频/音频文章提取字幕并保存（单个提取与文件夹批量导入共用）
+async fn extract_subtitles_for_article(
+    app_handle: &AppHandle,
+    article_id: &str,
+    cancel: &CancellationToken,
+    range: Option<TimeRange>,
+) -> Result<Article, AppError> {
+    println!("[ExtractSubtitles] 开始提取字幕: {}", article_id);
+
+    // 1. 加载文章
+    let article_json = load_article(app_handle, article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+    // 2. 验证是视频并获取视频路径
+    let video_path = article
+        .media_path
+        .as_ref()
+        .ok_or_else(|| AppError::invalid_input(tr("error.subtitle_requires_media")))?;
+    let video_path = std::path::Path::new(video_path);
+
+    if !video_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.video_missing",
+            &[("path", video_path.display().to_string())],
+        )));
+    }
+
+    // 3. 按设置选择转录引擎
+    let engine = configured_transcription_engine(app_handle)?;
+
+    // 4. 调用字幕提取模块 (使用 article_id 作为 event_id)
+    let mut segments = run_cancellable(
+        cancel,
+        crate::subtitle_extraction::extract_subtitles(
+            app_handle.clone(),
+            video_path,
+            article_id,
+            engine.as_ref(),
+            article_id, // event_id 用于进度事件
+            cancel,
+            range,
+        ),
+    )
+    .await
+    .map_err(|e| job_error(cancel, e, AppError::ai_provider))?;
+
+    if segments.is_empty() {
+        return Err(AppError::not_found(tr("error.no_subtitles")));
+    }
+
+    println!("[ExtractSubtitles] 提取到 {} 个字幕片段", segments.len());
+
+    // 可选的强制对齐：把模型给出的时间吸附到词边界，失败时保留原时间
+    let settings = load_config(app_handle)?
+        .map(|config| config.transcription)
+        .unwrap_or_default();
+    if settings.forced_alignment {
+        match snap_to_word_boundaries(
+            app_handle,
+            cancel,
+            article_id,
+            video_path,
+            &mut segments,
+            &settings,
+        )
+        .await
+        {
+            Ok(snapped) => println!("[ExtractSubtitles] 强制对齐修正了 {} 个片段", snapped),
+            Err(_) if cancel.is_cancelled() => {
+                return Err(AppError::cancelled(cancelled_message()))
             }
-        } else {
-            empty_count = 0;
-            clean_lines.push(trimmed);
+            Err(e) => eprintln!("[ExtractSubtitles] 强制对齐失败，保留原时间: {}", e),
+        }
+    }
+
+    // 5. 更新文章内容（区间提取时只替换区间内的字幕）
+    article.segments = match range {
+        Some(range) => crate::subtitle_extraction::merge_range_segments(
+            std::mem::take(&mut article.segments),
+            segments,
+            range,
+        ),
+        None => segments,
+    };
+    article.content = article
+        .segments
+        .iter()
+        .map(|s| s.text.clone())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // 6. 保存文章
+    let updated_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, article_id, &updated_json)?;
+
+    println!("[ExtractSubtitles] 字幕提取完成并保存");
+
+    Ok(article)
+}
+
+/// 平移或拉伸文章全部段落的时间轴，修正提取字幕的整体偏移：新时间 = 原时间 × scale_factor + offset_seconds
+/// scale_factor 默认为 1；调整前保存修订，可撤销
+#[tauri::command]
+pub async fn adjust_subtitle_timing_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    offset_seconds: f64,
+    scale_factor: Option<f64>,
+) -> Result<Article, AppError> {
+    let scale = scale_factor.unwrap_or(1.0);
+    if !offset_seconds.is_finite() || !scale.is_finite() || scale <= 0.0 {
+        return Err(AppError::invalid_input(tr("error.timing_invalid")));
+    }
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    if !article
+        .segments
+        .iter()
+        .any(|s| s.start_time.is_some() || s.end_time.is_some())
+    {
+        return Err(AppError::invalid_input(tr("error.article_not_timed")));
+    }
+
+    snapshot_article_revision(&app_handle, &article)?;
+    crate::subtitle_files::adjust_timing(&mut article.segments, offset_seconds, scale);
+
+    let updated_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &article_id, &updated_json)?;
+    Ok(article)
+}
+
+/// 强制对齐：用本地 whisper 的单词时间戳把段落时间吸附到词边界，返回修正的段落数
+/// 进度沿用 subtitle-extraction-progress://{article_id} 事件
+async fn snap_to_word_boundaries(
+    app_handle: &AppHandle,
+    cancel: &CancellationToken,
+    article_id: &str,
+    media_path: &std::path::Path,
+    segments: &mut [ArticleSegment],
+    settings: &crate::types::TranscriptionSettings,
+) -> Result<usize, String> {
+    crate::subtitle_extraction::emit_progress(
+        app_handle,
+        article_id,
+        serde_json::json!({ "phase": "aligning", "message": tr("subtitle.aligning") }),
+    );
+    let words = run_cancellable(
+        cancel,
+        crate::forced_alignment::word_timestamps(settings, media_path),
+    )
+    .await?;
+    if words.is_empty() {
+        return Err(tr("error.alignment_no_words"));
+    }
+    Ok(crate::forced_alignment::snap_segments(segments, &words))
+}
+
+/// 对已有字幕的视频/音频文章做强制对齐，修正大模型时间戳的漂移；调整前保存修订
+#[tauri::command]
+pub async fn snap_subtitle_timing_cmd(
+    app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    job_id: Option<String>,
+) -> Result<Article, AppError> {
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    let media_path = article
+        .media_path
+        .clone()
+        .ok_or_else(|| AppError::invalid_input(tr("error.alignment_requires_media")))?;
+    let media_path = std::path::Path::new(&media_path);
+    if !media_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.video_missing",
+            &[("path", media_path.display().to_string())],
+        )));
+    }
+    if !article.segments.iter().any(|s| s.start_time.is_some()) {
+        return Err(AppError::invalid_input(tr("error.article_not_timed")));
+    }
+
+    let settings = load_config(&app_handle)?
+        .map(|config| config.transcription)
+        .unwrap_or_default();
+    let job = jobs.register(job_id);
+    let original = article.clone();
+    article.segments.sort_by_key(|s| s.order);
+    let snapped = match snap_to_word_boundaries(
+        &app_handle,
+        job.token(),
+        &article_id,
+        media_path,
+        &mut article.segments,
+        &settings,
+    )
+    .await
+    {
+        Ok(snapped) => snapped,
+        Err(_) if job.token().is_cancelled() => {
+            return Err(AppError::cancelled(cancelled_message()))
         }
+        Err(e) => return Err(e.into()),
+    };
+    if snapped == 0 {
+        return Err(AppError::invalid_input(tr("error.alignment_failed")));
+    }
+
+    snapshot_article_revision(&app_handle, &original)?;
+    let updated_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &article_id, &updated_json)?;
+    Ok(article)
+}
+
+/// 把文章（电子书章节等）与对应的有声书音频对齐：转录音频后逐词匹配正文，
+/// 为段落填上开始/结束时间并把音频设为文章的媒体文件，实现跟读播放
+/// 进度沿用 subtitle-extraction-progress://{article_id} 事件；对齐前保存修订
+#[tauri::command]
+pub async fn align_article_audio_cmd(
+    app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    audio_path: String,
+    job_id: Option<String>,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    if article.segments.is_empty() {
+        return Err(AppError::invalid_input(tr("error.article_no_segments")));
+    }
+    let path = std::path::Path::new(&audio_path);
+    if !path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", audio_path.clone())],
+        )));
+    }
+
+    let engine = configured_transcription_engine(&app_handle)?;
+    let job = jobs.register(job_id);
+    let transcript = run_cancellable(
+        job.token(),
+        crate::subtitle_extraction::extract_subtitles(
+            app_handle.clone(),
+            path,
+            &article_id,
+            engine.as_ref(),
+            &article_id,
+            job.token(),
+            None,
+        ),
+    )
+    .await
+    .map_err(|e| job_error(job.token(), e, AppError::ai_provider))?;
+    if transcript.is_empty() {
+        return Err(AppError::not_found(tr("error.no_subtitles")));
+    }
+
+    let cues: Vec<crate::subtitle_files::SubtitleCue> = transcript
+        .iter()
+        .filter_map(|seg| {
+            Some(crate::subtitle_files::SubtitleCue {
+                start: seg.start_time?,
+                end: seg.end_time?,
+                text: seg.text.clone(),
+            })
+        })
+        .collect();
+    article.segments.sort_by_key(|s| s.order);
+    let texts: Vec<&str> = article.segments.iter().map(|s| s.text.as_str()).collect();
+    let spans = crate::audio_alignment::align_segments(&texts, &cues);
+    if spans.iter().all(Option::is_none) {
+        return Err(AppError::invalid_input(tr("error.alignment_failed")));
+    }
+
+    snapshot_article_revision(&app_handle, &article)?;
+    for (segment, span) in article.segments.iter_mut().zip(spans) {
+        segment.start_time = span.map(|(start, _)| start);
+        segment.end_time = span.map(|(_, end)| end);
+    }
+    article.media_path = Some(audio_path);
+
+    let updated_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &article_id, &updated_json)?;
+    Ok(article)
+}
+
+/// 获取可选的字幕转录引擎 ID
+#[tauri::command]
+pub async fn list_transcription_engines_cmd() -> Result<Vec<String>, AppError> {
+    Ok(TRANSCRIPTION_ENGINES
+        .iter()
+        .map(|id| id.to_string())
+        .collect())
+}
+
+// ============================================================================
+// 书籍导入功能 - 支持 EPUB、TXT 和 PDF 格式
+// ============================================================================
+
+const BOOKS_DIR: &str = "books";
+
+/// 确保书籍存储目录存在
+fn ensure_books_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| tr_with("error.app_data_dir_failed", &[("error", e.to_string())]))?;
+
+    let books_dir = app_data_dir.join(BOOKS_DIR);
+    if !books_dir.exists() {
+        std::fs::create_dir_all(&books_dir)
+            .map_err(|e| tr_with("error.create_dir_failed", &[("error", e.to_string())]))?;
+    }
+
+    Ok(books_dir)
+}
+
+/// 导入书籍文件 (EPUB/TXT/PDF)
+/// 将文件复制到应用数据目录并创建 Article 记录
+#[tauri::command]
+pub async fn import_book_cmd(
+    app_handle: AppHandle,
+    file_path: String,
+    title: Option<String>,
+) -> Result<Article, AppError> {
+    use std::path::Path;
+
+    let src_path = Path::new(&file_path);
+
+    // 验证文件存在
+    if !src_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", file_path.clone())],
+        )));
     }
 
-    let result = clean_lines.join("\n");
+    // 获取文件扩展名并验证格式
+    let ext = src_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .ok_or_else(|| AppError::invalid_input(tr("error.file_format_unknown")))?;
+
+    let book_type = match ext.as_str() {
+        "epub" => "epub",
+        "txt" => "txt",
+        "pdf" => "pdf",
+        _ => {
+            return Err(AppError::invalid_input(tr_with(
+                "error.unsupported_format",
+                &[("ext", ext.to_string())],
+            )))
+        }
+    };
+
+    // 获取文件名作为默认标题
+    let file_name = src_path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| tr("book.untitled"));
+
+    let book_title = title.unwrap_or(file_name);
+
+    // 确保书籍目录存在
+    let books_dir = ensure_books_dir(&app_handle)?;
+
+    // 生成唯一 ID 和目标路径
+    let id = Uuid::new_v4().to_string();
+    let dest_name = format!("{}.{}", id, ext);
+    let dest_path = books_dir.join(&dest_name);
+
+    // 复制文件到应用数据目录
+    std::fs::copy(src_path, &dest_path)
+        .map_err(|e| tr_with("error.copy_file_failed", &[("error", e.to_string())]))?;
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+
+    // 读取 TXT 文件内容作为 content，EPUB/PDF 使用占位符
+    let content = match book_type {
+        "txt" => {
+            // 尝试读取 TXT 文件内容
+            std::fs::read_to_string(&dest_path)
+                .unwrap_or_else(|_| tr_with("book.imported", &[("title", book_title.clone())]))
+        }
+        "epub" => tr_with("book.epub", &[("title", book_title.clone())]),
+        "pdf" => tr_with("book.pdf", &[("title", book_title.clone())]),
+        _ => tr_with("book.imported", &[("title", book_title.clone())]),
+    };
+
+    // EPUB/PDF 的 content 只是占位符，只对 TXT 检测语言
+    let language = (book_type == "txt")
+        .then(|| detect_language(&content))
+        .flatten()
+        .map(str::to_string)
+        .or_else(|| active_learning_language(&app_handle));
+
+    // 创建 Article 记录
+    let mut article = Article {
+        id: id.clone(),
+        title: book_title,
+        content,
+        source_type: Some("book".to_string()),
+        source_url: Some(format!("file://{}", file_path)),
+        media_path: None,
+        book_path: Some(dest_path.to_string_lossy().into_owned()),
+        book_type: Some(book_type.to_string()),
+        language,
+        created_at,
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments: Vec::new(), // 书籍不预分段，由阅读器处理
+    };
+    finish_article_import(&app_handle, &mut article);
+
+    // 保存文章记录
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| tr_with("error.serialize_failed", &[("error", e.to_string())]))?;
+    save_article(&app_handle, &id, &article_json)?;
+
+    println!(
+        "[ImportBook] 书籍导入成功: {} ({})",
+        article.title, book_type
+    );
 
-    // Final trim of the whole text
-    result.trim().to_string()
+    Ok(article)
 }
 
-// Extract title from HTML
-fn extract_title_from_html(html: &str, url: &str) -> String {
-    let html_lower = html.to_lowercase();
-
-    // Find <title> tag
-    if let Some(start) = html_lower.find("<title>") {
-        let start = start + 7; // len("<title>")
-        if let Some(end) = html_lower[start..].find("</title>") {
-            let title_html = &html[start..start + end];
-            // Decode basic HTML entities
-            let decoded = html_escape::decode_html_entities(title_html).to_string();
-            let trimmed = decoded.trim();
-            if !trimmed.is_empty() {
-                return trimmed.to_string();
-            }
-        }
+/// 把网页正文保存为文章；download_images 为 true 时先把正文中的图片下载到本地，
+/// 改由资源服务器提供，避免外链失效
+#[tauri::command]
+pub async fn import_web_material_cmd(
+    app_handle: AppHandle,
+    url: String,
+    title: Option<String>,
+    content: String,
+    download_images: Option<bool>,
+) -> Result<Article, AppError> {
+    let parsed_url = url::Url::parse(&url).map_err(|_| "Invalid URL format".to_string())?;
+    if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
+        return Err(AppError::invalid_input(tr("error.http_only")));
     }
 
-    // Fallback: extract from URL
-    if let Ok(parsed) = url::Url::parse(url) {
-        if let Some(segments) = parsed.path_segments() {
-            let last = segments.last().unwrap_or("");
-            if !last.is_empty() {
-                return last
-                    .replace('-', " ")
-                    .replace('_', " ")
-                    .split(' ')
-                    .map(|s| {
-                        let mut chars = s.chars();
-                        match chars.next() {
-                            None => String::new(),
-                            Some(first) => {
-                                if !first.is_alphabetic() {
-                                    String::new()
-                                } else {
-                                    first.to_uppercase().collect::<String>() + chars.as_str()
-                                }
-                            }
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" ");
-            }
-            return parsed.host_str().unwrap_or("Untitled").to_string();
-        }
+    if content.trim().len() < 10 {
+        return Err(AppError::invalid_input(tr("error.content_too_short")));
     }
 
-    "Untitled".to_string()
-}
+    let id = Uuid::new_v4().to_string();
+    let content = if download_images.unwrap_or(false) {
+        let app_data_dir = get_app_data_dir(&app_handle)?;
+        crate::web_images::download_images(&app_data_dir, &id, &content).await
+    } else {
+        content
+    };
 
-// ============================================================================
-// Favorites Commands - 收藏夹命令
-// ============================================================================
+    Ok(save_web_article(&app_handle, id, url, title, content)?)
+}
 
-/// 创建单词包
+/// 导入本地保存的网页（.html/.htm），与 fetch_url_content 使用相同的站点规则、readability
+/// 和保留排版的提取流程，无需联网；另存时记录的原始地址用于匹配站点规则并作为来源，
+/// keep_images 时插图按 HTML 文件所在目录解析为本地文件地址
 #[tauri::command]
-pub async fn create_word_pack_cmd(
+pub async fn import_html_file_cmd(
     app_handle: AppHandle,
-    name: String,
-    description: Option<String>,
-    cover_url: Option<String>,
-    author: Option<String>,
-    language_from: Option<String>,
-    language_to: Option<String>,
-    tags: Option<Vec<String>>,
-    version: Option<String>,
-) -> Result<WordPack, String> {
-    ensure_default_word_pack(&app_handle)?;
-
-    let now = chrono::Utc::now().to_rfc3339();
-    let pack = WordPack {
-        id: Uuid::new_v4().to_string(),
-        name: name.trim().to_string(),
-        description,
-        cover_url,
-        author,
-        language_from,
-        language_to,
-        tags: tags.unwrap_or_default(),
-        version,
-        created_at: now.clone(),
-        updated_at: now,
-        is_system: false,
-    };
+    file_path: String,
+    title: Option<String>,
+    keep_images: Option<bool>,
+) -> Result<Article, AppError> {
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", file_path.clone())],
+        )));
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !matches!(ext.as_str(), "html" | "htm" | "xhtml") {
+        return Err(AppError::invalid_input(tr_with(
+            "error.unsupported_format",
+            &[("ext", ext)],
+        )));
+    }
 
-    if pack.name.is_empty() {
-        return Err("Pack name is required".to_string());
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let html = String::from_utf8_lossy(&bytes).into_owned();
+    let absolute = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let file_url = url::Url::from_file_path(&absolute)
+        .map_err(|_| format!("Invalid file path: {}", file_path))?
+        .to_string();
+    let page_url = saved_page_url(&html).unwrap_or_else(|| file_url.clone());
+
+    let rules = load_rules_or_default(&app_handle);
+    let fetched = extract_page_content(
+        &html,
+        &page_url,
+        &file_url,
+        &rules,
+        keep_images.unwrap_or(false),
+    );
+    if fetched.content.trim().is_empty() {
+        return Err(AppError::not_found(tr("error.no_meaningful_content")));
     }
 
-    let json = serde_json::to_string(&pack)
-        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
-    save_word_pack(&app_handle, &pack.id, &json)?;
-    Ok(pack)
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .or(Some(fetched.title).filter(|t| !t.trim().is_empty()));
+    Ok(save_web_article(
+        &app_handle,
+        Uuid::new_v4().to_string(),
+        page_url,
+        title,
+        fetched.content,
+    )?)
 }
 
-/// 更新单词包
+/// 导入对照文本：传入两个文件时逐行对齐，只传 source_path 时按每行的制表符或 || 拆分
+/// 生成的段落已带人工译文，无需再调用 AI 翻译
 #[tauri::command]
-pub async fn update_word_pack_cmd(
+pub async fn import_parallel_text_cmd(
     app_handle: AppHandle,
-    id: String,
-    name: Option<String>,
-    description: Option<String>,
-    cover_url: Option<String>,
-    author: Option<String>,
-    language_from: Option<String>,
-    language_to: Option<String>,
-    tags: Option<Vec<String>>,
-    version: Option<String>,
-) -> Result<WordPack, String> {
-    let json = load_word_pack(&app_handle, &id)?;
-    let mut pack: WordPack =
-        serde_json::from_str(&json).map_err(|e| format!("Failed to parse word pack: {}", e))?;
-
-    if let Some(name) = name {
-        let trimmed = name.trim();
-        if trimmed.is_empty() {
-            return Err("Pack name is required".to_string());
+    source_path: String,
+    translation_path: Option<String>,
+    title: Option<String>,
+) -> Result<Article, AppError> {
+    let read_text = |path: &str| -> Result<String, AppError> {
+        if !std::path::Path::new(path).exists() {
+            return Err(AppError::not_found(tr_with(
+                "error.file_missing",
+                &[("path", path.to_string())],
+            )));
         }
-        pack.name = trimmed.to_string();
-    }
-    if description.is_some() {
-        pack.description = description;
-    }
-    if cover_url.is_some() {
-        pack.cover_url = cover_url;
-    }
-    if author.is_some() {
-        pack.author = author;
-    }
-    if language_from.is_some() {
-        pack.language_from = language_from;
-    }
-    if language_to.is_some() {
-        pack.language_to = language_to;
-    }
-    if tags.is_some() {
-        pack.tags = tags.unwrap_or_default();
-    }
-    if version.is_some() {
-        pack.version = version;
+        std::fs::read_to_string(path)
+            .map_err(|e| AppError::from(format!("Failed to read file: {}", e)))
+    };
+
+    let source = read_text(&source_path)?;
+    let pairs = match translation_path.as_deref() {
+        Some(path) => crate::parallel_text::align_texts(&source, &read_text(path)?),
+        None => crate::parallel_text::parse_aligned_lines(&source),
     }
+    .map_err(AppError::invalid_input)?;
 
-    pack.updated_at = chrono::Utc::now().to_rfc3339();
+    if pairs.is_empty() {
+        return Err(AppError::invalid_input(tr("error.parallel_empty")));
+    }
 
-    let updated_json = serde_json::to_string(&pack)
-        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
-    save_word_pack(&app_handle, &pack.id, &updated_json)?;
-    Ok(pack)
-}
+    let default_title = std::path::Path::new(&source_path)
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
 
-/// 列出所有单词包
-#[tauri::command]
-pub async fn list_word_packs_cmd(app_handle: AppHandle) -> Result<Vec<WordPack>, String> {
-    ensure_default_word_pack(&app_handle)?;
-    let mut packs = load_all_word_packs(&app_handle)?;
-    packs.sort_by(|a, b| a.name.cmp(&b.name));
-    packs.sort_by(|a, b| b.is_system.cmp(&a.is_system));
-    Ok(packs)
+    save_parallel_article(
+        &app_handle,
+        &pairs,
+        title
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or(default_title),
+        format!("file://{}", source_path),
+        active_learning_language(&app_handle),
+        None,
+    )
 }
 
-/// 删除单词包（系统包不可删除）
+/// 导入 Markdown 文件：标题、列表、引用和代码块记录在段落的 block 字段上，
+/// 段落文本去掉行内格式；未传标题时使用文档开头的一级标题或文件名
 #[tauri::command]
-pub async fn delete_word_pack_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
-    if id == DEFAULT_UNGROUPED_PACK_ID {
-        return Err("System pack cannot be deleted".to_string());
+pub async fn import_markdown_cmd(
+    app_handle: AppHandle,
+    file_path: String,
+    title: Option<String>,
+) -> Result<Article, AppError> {
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", file_path.clone())],
+        )));
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !matches!(ext.as_str(), "md" | "markdown") {
+        return Err(AppError::invalid_input(tr_with(
+            "error.unsupported_format",
+            &[("ext", ext)],
+        )));
     }
 
-    let default_pack = ensure_default_word_pack(&app_handle)?;
-    let _ = load_word_pack(&app_handle, &id)?;
-
-    delete_word_pack(&app_handle, &id)?;
-
-    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
-    for favorite in &mut favorites {
-        if favorite.pack_ids.iter().any(|pack_id| pack_id == &id) {
-            favorite.pack_ids.retain(|pack_id| pack_id != &id);
-            if favorite.pack_ids.is_empty() {
-                favorite.pack_ids.push(default_pack.id.clone());
-            }
-            persist_favorite_vocabulary(&app_handle, favorite)?;
-        }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut document = crate::markdown_import::parse_markdown(&text);
+    if let Some(dir) = path.parent() {
+        crate::markdown_import::resolve_image_urls(&mut document, dir);
     }
 
-    Ok(())
+    let default_title = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
+    save_structured_article(
+        &app_handle,
+        document,
+        title.filter(|t| !t.trim().is_empty()),
+        default_title,
+        "markdown",
+        format!("file://{}", file_path),
+    )
 }
 
-/// 添加单词收藏
+/// 导入 Word 文档（.docx）：按段落提取文字，标题、列表和引用样式记录为段落结构，
+/// 未传标题时使用文档属性中的标题、标题样式段落或文件名
 #[tauri::command]
-pub async fn add_favorite_vocabulary_cmd(
+pub async fn import_docx_cmd(
     app_handle: AppHandle,
-    word: String,
-    meaning: String,
-    usage: String,
-    explanation: Option<String>,
-    example: Option<String>,
-    reading: Option<String>,
-    source_article_id: Option<String>,
-    source_article_title: Option<String>,
-    pack_ids: Option<Vec<String>>,
-) -> Result<FavoriteVocabulary, String> {
-    let default_pack = ensure_default_word_pack(&app_handle)?;
-    let packs = load_all_word_packs(&app_handle)?;
-    let existing_pack_ids: HashSet<String> = packs.into_iter().map(|p| p.id).collect();
-
-    let normalized_input = normalize_word(&word);
-    if normalized_input.is_empty() || meaning.trim().is_empty() {
-        return Err("Word and meaning are required".to_string());
+    file_path: String,
+    title: Option<String>,
+) -> Result<Article, AppError> {
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", file_path.clone())],
+        )));
     }
-
-    let mut pack_ids = filter_existing_pack_ids(
-        sanitize_pack_ids(pack_ids),
-        &existing_pack_ids,
-        &default_pack.id,
-    );
-
-    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
-    if let Some(existing) = favorites
-        .iter_mut()
-        .find(|fav| normalize_word(&fav.word) == normalized_input)
-    {
-        let mut merged = existing.pack_ids.clone();
-        merged.append(&mut pack_ids);
-        existing.pack_ids = sanitize_pack_ids(Some(merged));
-        if existing.pack_ids.is_empty() {
-            existing.pack_ids.push(default_pack.id.clone());
-        }
-
-        if existing.meaning.trim().is_empty() {
-            existing.meaning = meaning.clone();
-        }
-        if existing.usage.trim().is_empty() {
-            existing.usage = usage.clone();
-        }
-        if existing.example.is_none() {
-            existing.example = example.clone();
-        }
-        if existing.reading.is_none() {
-            existing.reading = reading.clone();
-        }
-        if existing.explanation.is_none() {
-            existing.explanation = explanation.clone();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if ext != "docx" {
+        return Err(AppError::invalid_input(tr_with(
+            "error.unsupported_format",
+            &[("ext", ext)],
+        )));
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let document = crate::docx_import::parse_docx(&bytes).map_err(AppError::invalid_input)?;
+
+    let default_title = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
+    save_structured_article(
+        &app_handle,
+        document,
+        title.filter(|t| !t.trim().is_empty()),
+        default_title,
+        "docx",
+        format!("file://{}", file_path),
+    )
+}
+
+/// 导入独立的字幕文件（SRT / VTT / ASS），每条字幕成为带时间轴的段落
+/// 传入 video_article_id 时把字幕挂到已有的本地视频文章上（替换原有段落，替换前保存修订），
+/// 否则新建一篇字幕文章
+#[tauri::command]
+pub async fn import_subtitle_file_cmd(
+    app_handle: AppHandle,
+    file_path: String,
+    title: Option<String>,
+    video_article_id: Option<String>,
+) -> Result<Article, AppError> {
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.file_missing",
+            &[("path", file_path.clone())],
+        )));
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let text = std::fs::read(path)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let cues = crate::subtitle_files::parse_subtitles(&text, &ext).ok_or_else(|| {
+        AppError::invalid_input(tr_with("error.unsupported_format", &[("ext", ext.clone())]))
+    })?;
+    if cues.is_empty() {
+        return Err(AppError::invalid_input(tr("error.subtitle_file_empty")));
+    }
+    let content = crate::subtitle_files::cues_content(&cues);
+
+    if let Some(video_article_id) = video_article_id.filter(|id| !id.is_empty()) {
+        let article_json = load_article(&app_handle, &video_article_id)?;
+        let mut article: Article = serde_json::from_str(&article_json)
+            .map_err(|e| format!("Failed to parse article: {}", e))?;
+        if article.media_path.is_none() {
+            return Err(AppError::invalid_input(tr(
+                "error.subtitle_target_no_media",
+            )));
         }
-        if existing.source_article_id.is_none() {
-            existing.source_article_id = source_article_id.clone();
+        if !article.segments.is_empty() {
+            snapshot_article_revision(&app_handle, &article)?;
         }
-        if existing.source_article_title.is_none() {
-            existing.source_article_title = source_article_title.clone();
+        article.segments = crate::subtitle_files::cues_to_segments(&article.id, &cues);
+        article.content = content;
+        article.translated = false;
+        if let Some(title) = title.filter(|t| !t.trim().is_empty()) {
+            article.title = title;
         }
-
-        persist_favorite_vocabulary(&app_handle, existing)?;
-        return Ok(existing.clone());
+        let updated_json = serde_json::to_string(&article)
+            .map_err(|e| format!("Failed to serialize article: {}", e))?;
+        save_article(&app_handle, &article.id, &updated_json)?;
+        return Ok(article);
     }
 
-    let favorite = FavoriteVocabulary {
-        id: Uuid::new_v4().to_string(),
-        word: word.trim().to_string(),
-        meaning: meaning.trim().to_string(),
-        usage: usage.trim().to_string(),
-        explanation,
-        example,
-        reading,
-        source_article_id,
-        source_article_title,
-        pack_ids,
-        srs_state: "new".to_string(),
-        ease_factor: 2.5,
-        repetitions: 0,
-        interval_days: 0,
-        due_date: today_local_date().format("%Y-%m-%d").to_string(),
-        last_reviewed_at: None,
-        review_count: 0,
+    let id = Uuid::new_v4().to_string();
+    let language = detect_language(&content)
+        .map(str::to_string)
+        .or_else(|| active_learning_language(&app_handle));
+    let default_title = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
+    let mut article = Article {
+        id: id.clone(),
+        title: title
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or(default_title),
+        content,
+        source_type: Some("subtitle".to_string()),
+        source_url: Some(format!("file://{}", file_path)),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language,
         created_at: chrono::Utc::now().to_rfc3339(),
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments: crate::subtitle_files::cues_to_segments(&id, &cues),
     };
+    finish_article_import(&app_handle, &mut article);
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &id, &article_json)?;
 
-    persist_favorite_vocabulary(&app_handle, &favorite)?;
-    Ok(favorite)
+    Ok(article)
+}
+
+/// 视频文章的本地媒体文件，用于探测和提取内嵌字幕
+fn embedded_subtitle_source(article: &Article) -> Result<std::path::PathBuf, AppError> {
+    let media_path = article
+        .media_path
+        .as_deref()
+        .map(std::path::PathBuf::from)
+        .ok_or_else(|| AppError::invalid_input(tr("error.subtitle_target_no_media")))?;
+    if !media_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.video_missing",
+            &[("path", media_path.display().to_string())],
+        )));
+    }
+    Ok(media_path)
 }
 
-/// 列出所有单词收藏
+/// 列出视频文章内嵌的字幕轨，前端在调用 AI 转录前提示可直接提取
 #[tauri::command]
-pub async fn list_favorite_vocabularies_cmd(
+pub async fn list_embedded_subtitles_cmd(
     app_handle: AppHandle,
-) -> Result<Vec<FavoriteVocabulary>, String> {
-    ensure_default_word_pack(&app_handle)?;
-    migrate_favorite_vocabularies(&app_handle)?;
-    let mut favorites = load_all_favorite_vocabularies_internal(&app_handle)?;
+    article_id: String,
+) -> Result<Vec<EmbeddedSubtitleTrack>, AppError> {
+    let article = get_article(app_handle.clone(), article_id).await?;
+    let media_path = embedded_subtitle_source(&article)?;
+    Ok(crate::embedded_subtitles::probe(&app_handle, &media_path).await?)
+}
 
-    // 按创建时间降序排列
-    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+/// 把视频内嵌的文本字幕轨提取为文章段落，替换已有段落（旧版本存入修订历史）
+#[tauri::command]
+pub async fn extract_embedded_subtitles_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    stream_index: u32,
+) -> Result<Article, AppError> {
+    let mut article = get_article(app_handle.clone(), article_id).await?;
+    let media_path = embedded_subtitle_source(&article)?;
+    let tracks = crate::embedded_subtitles::probe(&app_handle, &media_path).await?;
+    let track = tracks
+        .iter()
+        .find(|track| track.stream_index == stream_index)
+        .ok_or_else(|| {
+            AppError::not_found(tr_with(
+                "error.embedded_subtitle_missing",
+                &[("index", stream_index.to_string())],
+            ))
+        })?;
+    if !track.text_based {
+        return Err(AppError::invalid_input(tr_with(
+            "error.embedded_subtitle_bitmap",
+            &[("codec", track.codec.clone())],
+        )));
+    }
 
-    Ok(favorites)
+    let srt =
+        crate::embedded_subtitles::extract_srt(&app_handle, &media_path, stream_index).await?;
+    let cues = crate::subtitle_files::parse_srt(&srt);
+    if cues.is_empty() {
+        return Err(AppError::invalid_input(tr("error.subtitle_file_empty")));
+    }
+
+    if !article.segments.is_empty() {
+        snapshot_article_revision(&app_handle, &article)?;
+    }
+    article.segments = crate::subtitle_files::cues_to_segments(&article.id, &cues);
+    article.content = crate::subtitle_files::cues_content(&cues);
+    article.translated = false;
+    let updated_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &article.id, &updated_json)?;
+    Ok(article)
 }
 
-/// 删除单词收藏
+/// 把带时间轴的段落导出为 SRT 或 VTT 字幕文件，供外部播放器使用；
+/// include_translation 为 true 时译文作为每条字幕的第二行。返回写入的字幕条数
 #[tauri::command]
-pub async fn delete_favorite_vocabulary_cmd(
+pub async fn export_subtitles_cmd(
     app_handle: AppHandle,
-    id: String,
-) -> Result<(), String> {
-    delete_favorite_vocabulary(&app_handle, &id)?;
-    Ok(())
+    article_id: String,
+    format: String,
+    dest_path: String,
+    include_translation: Option<bool>,
+) -> Result<usize, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+    let cues = crate::subtitle_files::segments_to_cues(
+        &article.segments,
+        include_translation.unwrap_or(false),
+    );
+    if cues.is_empty() {
+        return Err(AppError::invalid_input(tr("error.no_timed_segments")));
+    }
+    let rendered = crate::subtitle_files::render_subtitles(&cues, &format).ok_or_else(|| {
+        AppError::invalid_input(tr_with(
+            "error.unsupported_format",
+            &[("ext", format.clone())],
+        ))
+    })?;
+
+    std::fs::write(&dest_path, rendered).map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(cues.len())
 }
 
-/// 设置单词收藏所属合集
+/// 把段落（可附译文）作为硬字幕烧录进视频副本，返回导出视频的路径
+/// 未指定 dest_path 时输出到原视频旁；进度通过 subtitle-burn-progress://{article_id} 事件发送
 #[tauri::command]
-pub async fn set_vocabulary_pack_ids_cmd(
+pub async fn burn_subtitles_cmd(
     app_handle: AppHandle,
-    vocabulary_id: String,
-    pack_ids: Vec<String>,
-) -> Result<FavoriteVocabulary, String> {
-    let default_pack = ensure_default_word_pack(&app_handle)?;
-    let existing_pack_ids: HashSet<String> = list_word_packs(&app_handle)?.into_iter().collect();
+    jobs: State<'_, JobRegistry>,
+    article_id: String,
+    dest_path: Option<String>,
+    include_translation: Option<bool>,
+    job_id: Option<String>,
+) -> Result<String, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    let json = load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
-    let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
-        .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+    let video_path = article
+        .media_path
+        .as_deref()
+        .map(std::path::Path::new)
+        .ok_or_else(|| AppError::invalid_input(tr("error.burn_requires_video")))?;
+    if !video_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.video_missing",
+            &[("path", video_path.display().to_string())],
+        )));
+    }
 
-    favorite.pack_ids = filter_existing_pack_ids(
-        sanitize_pack_ids(Some(pack_ids)),
-        &existing_pack_ids,
-        &default_pack.id,
+    let cues = crate::subtitle_files::segments_to_cues(
+        &article.segments,
+        include_translation.unwrap_or(true),
     );
-    persist_favorite_vocabulary(&app_handle, &favorite)?;
-    Ok(favorite)
+    if cues.is_empty() {
+        return Err(AppError::invalid_input(tr("error.no_timed_segments")));
+    }
+    let ass = crate::subtitle_files::render_ass(&cues);
+
+    let output_path = dest_path
+        .filter(|p| !p.trim().is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| crate::subtitle_burn::burned_video_path(video_path));
+
+    let job = jobs.register(job_id);
+    crate::subtitle_burn::burn_subtitles(
+        &app_handle,
+        video_path,
+        &ass,
+        &output_path,
+        &article_id,
+        job.token(),
+    )
+    .await
+    .map_err(|e| job_error(job.token(), e, AppError::external_tool))?;
+
+    Ok(output_path.to_string_lossy().into_owned())
 }
 
-/// 按合集列出单词收藏
+/// 获取视频或音频文章的波形峰值（播放器进度条显示波形），首次计算后按媒体文件缓存
+/// peaks_per_second 为每秒的峰值个数，默认 50
 #[tauri::command]
-pub async fn list_favorite_vocabularies_by_pack_cmd(
+pub async fn get_waveform_cmd(
     app_handle: AppHandle,
-    pack_id: String,
-) -> Result<Vec<FavoriteVocabulary>, String> {
-    let mut favorites = list_favorite_vocabularies_cmd(app_handle).await?;
-    if pack_id != "all" {
-        favorites.retain(|fav| fav.pack_ids.iter().any(|id| id == &pack_id));
+    article_id: String,
+    peaks_per_second: Option<u32>,
+) -> Result<WaveformPeaks, AppError> {
+    let article = get_article(app_handle.clone(), article_id).await?;
+    let media_path = article
+        .media_path
+        .as_deref()
+        .map(std::path::Path::new)
+        .ok_or_else(|| AppError::invalid_input(tr("error.waveform_requires_media")))?;
+    if !media_path.exists() {
+        return Err(AppError::not_found(tr_with(
+            "error.video_missing",
+            &[("path", media_path.display().to_string())],
+        )));
     }
-    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-    Ok(favorites)
+
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    Ok(crate::waveform::load_or_compute(
+        &app_handle,
+        &app_data_dir,
+        media_path,
+        crate::waveform::peaks_per_second(peaks_per_second),
+    )
+    .await?)
 }
 
-/// 获取指定日期到期的背诵队列
+/// 由带块结构的文档（Markdown、Word）创建并保存文章：标题和代码块整块成段，其余按句切分，
+/// 段落记录所属的块类型；标题优先使用传入值，其次是文档自带的标题
+fn save_structured_article(
+    app_handle: &AppHandle,
+    document: crate::markdown_import::MarkdownDocument,
+    title: Option<String>,
+    default_title: String,
+    source_type: &str,
+    source_url: String,
+) -> Result<Article, AppError> {
+    let content = crate::markdown_import::plain_content(&document);
+    if content.trim().len() < 10 {
+        return Err(AppError::invalid_input(tr("error.content_too_short")));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let language = detect_language(&content)
+        .map(str::to_string)
+        .or_else(|| active_learning_language(app_handle));
+    let segments = structured_segments(&document, &id, language.as_deref(), &created_at);
+
+    let mut article = Article {
+        id: id.clone(),
+        title: title.or(document.title).unwrap_or(default_title),
+        content,
+        source_type: Some(source_type.to_string()),
+        source_url: Some(source_url),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language,
+        created_at,
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments,
+    };
+    finish_article_import(app_handle, &mut article);
+
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &id, &article_json)?;
+
+    Ok(article)
+}
+
+/// Markdown / Word 文档按块拆分的段落，块的类型记录在段落的 block 字段上
+fn structured_segments(
+    document: &crate::markdown_import::MarkdownDocument,
+    article_id: &str,
+    language: Option<&str>,
+    created_at: &str,
+) -> Vec<ArticleSegment> {
+    crate::markdown_import::markdown_pieces(document, language)
+        .into_iter()
+        .enumerate()
+        .map(|(order, (piece, block))| ArticleSegment {
+            id: Uuid::new_v4().to_string(),
+            article_id: article_id.to_string(),
+            order: order as i32,
+            text: piece.text,
+            reading_text: None,
+            translation: None,
+            explanation: None,
+            start_time: None,
+            end_time: None,
+            created_at: created_at.to_string(),
+            is_new_paragraph: piece.is_new_paragraph,
+            image_url: piece.image_url,
+            translations: Default::default(),
+            processing_status: None,
+            block,
+            speaker: None,
+        })
+        .collect()
+}
+
+/// 查找从该本地文件导入的文章（来源地址为 file://{file_path}）
+pub fn find_article_by_file(
+    app_handle: &AppHandle,
+    file_path: &str,
+) -> Result<Option<Article>, String> {
+    let source_url = format!("file://{}", file_path);
+    Ok(load_all_articles(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<Article>(json).ok())
+        .find(|article| article.source_url.as_deref() == Some(source_url.as_str())))
+}
+
+/// 源文件变化后重新读取从 .txt / .md 导入的文章：保存修订后替换正文并重新分段，
+/// 文本未变的段落保留已有的译文和讲解；内容没有变化时返回 None
+pub async fn refresh_file_article(
+    app_handle: &AppHandle,
+    mut article: Article,
+    path: &std::path::Path,
+    markdown: bool,
+) -> Result<Option<Article>, AppError> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let document = markdown.then(|| {
+        let mut document = crate::markdown_import::parse_markdown(&text);
+        if let Some(dir) = path.parent() {
+            crate::markdown_import::resolve_image_urls(&mut document, dir);
+        }
+        document
+    });
+    let content = match &document {
+        Some(document) => crate::markdown_import::plain_content(document),
+        None => text,
+    };
+    if content == article.content {
+        return Ok(None);
+    }
+
+    let mut segments = match &document {
+        Some(document) => structured_segments(
+            document,
+            &article.id,
+            article.language.as_deref(),
+            &chrono::Utc::now().to_rfc3339(),
+        ),
+        None => {
+            create_article_segments(
+                app_handle,
+                &article.id,
+                &content,
+                article.language.as_deref(),
+                article.segmentation.as_ref(),
+            )
+            .await?
+        }
+    };
+
+    snapshot_article_revision(app_handle, &article)?;
+    segmentation::carry_over_segments(&article.segments, &mut segments);
+    article.content = content;
+    article.segments = segments;
+    article.translated = segments_translated(&article.segments);
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &article.id, &article_json)?;
+    Ok(Some(article))
+}
+
+/// 把 PDF 翻译插件输出的原文/译文段落对照回导为文章，每段已带译文，可直接逐句讲解
 #[tauri::command]
-pub async fn get_due_vocabulary_queue_cmd(
+pub async fn import_pdf_translation_cmd(
     app_handle: AppHandle,
-    pack_id: String,
-    date_local: String,
-) -> Result<Vec<FavoriteVocabulary>, String> {
-    let config = load_config(&app_handle)?.unwrap_or_default();
-    let all = list_favorite_vocabularies_cmd(app_handle).await?;
-    build_due_vocabulary_queue(
-        all,
-        &pack_id,
-        &date_local,
-        config.srs_daily_new_limit,
-        config.srs_daily_review_limit,
+    pdf_path: String,
+    title: Option<String>,
+) -> Result<Article, AppError> {
+    let path = std::path::Path::new(&pdf_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    let pairs_path = path
+        .parent()
+        .unwrap_or(std::path::Path::new("."))
+        .join(format!("{}-pairs.json", stem));
+    if !pairs_path.exists() {
+        return Err(AppError::not_found(tr("error.pdf_pairs_missing")));
+    }
+
+    let json =
+        std::fs::read_to_string(&pairs_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let (parsed, pairs) =
+        crate::parallel_text::parse_pdf_pairs(&json).map_err(AppError::invalid_input)?;
+    if pairs.is_empty() {
+        return Err(AppError::invalid_input(tr("error.parallel_empty")));
+    }
+
+    let language = Some(parsed.lang_in)
+        .filter(|l| !l.trim().is_empty())
+        .or_else(|| active_learning_language(&app_handle));
+    let translation_language = Some(parsed.lang_out).filter(|l| !l.trim().is_empty());
+
+    save_parallel_article(
+        &app_handle,
+        &pairs,
+        title.filter(|t| !t.trim().is_empty()).unwrap_or(stem),
+        format!("file://{}", pdf_path),
+        language,
+        translation_language,
     )
 }
 
-/// 复习单词并更新 SM-2 状态
-#[tauri::command]
-pub async fn review_vocabulary_cmd(
-    app_handle: AppHandle,
-    vocabulary_id: String,
-    grade: String,
-    date_local: String,
-) -> Result<FavoriteVocabulary, String> {
-    let review_date = parse_local_date(&date_local)?;
+/// 由对齐的原文/译文创建并保存文章
+fn save_parallel_article(
+    app_handle: &AppHandle,
+    pairs: &[crate::parallel_text::AlignedPair],
+    title: String,
+    source_url: String,
+    language: Option<String>,
+    translation_language: Option<String>,
+) -> Result<Article, AppError> {
+    let id = Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let segments = pairs
+        .iter()
+        .enumerate()
+        .map(|(order, pair)| ArticleSegment {
+            id: Uuid::new_v4().to_string(),
+            article_id: id.clone(),
+            order: order as i32,
+            text: pair.source.clone(),
+            reading_text: None,
+            translation: Some(pair.translation.clone()).filter(|t| !t.is_empty()),
+            explanation: None,
+            start_time: None,
+            end_time: None,
+            created_at: created_at.clone(),
+            is_new_paragraph: pair.is_new_paragraph,
+            image_url: None,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: None,
+        })
+        .collect();
+
+    let mut article = Article {
+        id: id.clone(),
+        title,
+        content: crate::parallel_text::source_content(pairs),
+        source_type: Some("article".to_string()),
+        source_url: Some(source_url),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language,
+        created_at,
+        translated: true,
+        translation_language,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments,
+    };
+    finish_article_import(app_handle, &mut article);
+
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &id, &article_json)?;
+
+    Ok(article)
+}
+
+/// 创建并保存网页来源的文章
+fn save_web_article(
+    app_handle: &AppHandle,
+    id: String,
+    url: String,
+    title: Option<String>,
+    content: String,
+) -> Result<Article, String> {
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let final_title = title.unwrap_or_else(|| "Untitled Web Material".to_string());
+    let language = detect_language(&content)
+        .map(str::to_string)
+        .or_else(|| active_learning_language(app_handle));
+    let segments =
+        create_segments_from_content(app_handle, &id, &content, language.as_deref(), None);
+
+    let mut article = Article {
+        id: id.clone(),
+        title: final_title,
+        content,
+        source_type: Some("web".to_string()),
+        source_url: Some(url),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language,
+        created_at,
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: None,
+        tags: Vec::new(),
+        media_info: None,
+        segments,
+    };
+    finish_article_import(app_handle, &mut article);
+
+    let article_json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(app_handle, &id, &article_json)?;
 
-    let json = load_favorite_vocabulary(&app_handle, &vocabulary_id)?;
-    let mut favorite: FavoriteVocabulary = serde_json::from_str(&json)
-        .map_err(|e| format!("Failed to parse favorite vocabulary: {}", e))?;
+    Ok(article)
+}
 
-    let next = calculate_sm2_update(
-        favorite.repetitions,
-        favorite.interval_days,
-        favorite.ease_factor,
-        &grade,
-        review_date,
-    )?;
+// ============================================================================
+// Failed Capture Queue - 网页抓取失败重试队列
+// ============================================================================
 
-    favorite.srs_state = next.srs_state;
-    favorite.repetitions = next.repetitions;
-    favorite.interval_days = next.interval_days;
-    favorite.ease_factor = next.ease_factor;
-    favorite.due_date = next.due_date;
-    favorite.last_reviewed_at = Some(chrono::Utc::now().to_rfc3339());
-    favorite.review_count += 1;
+fn load_failed_captures_internal(app_handle: &AppHandle) -> Result<Vec<FailedCapture>, String> {
+    let mut captures: Vec<FailedCapture> = load_all_failed_captures(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FailedCapture>(json).ok())
+        .collect();
 
-    persist_favorite_vocabulary(&app_handle, &favorite)?;
-    Ok(favorite)
+    captures.sort_by(|a, b| a.failed_at.cmp(&b.failed_at));
+    Ok(captures)
 }
 
-/// 导出单词包为 OpenKoto JSON 包
-#[tauri::command]
-pub async fn export_word_pack_cmd(
-    app_handle: AppHandle,
-    pack_id: String,
-) -> Result<ExportWordPackResult, String> {
-    let pack_json = load_word_pack(&app_handle, &pack_id)?;
-    let pack: WordPack = serde_json::from_str(&pack_json)
-        .map_err(|e| format!("Failed to parse word pack: {}", e))?;
-
-    let mut entries: Vec<WordPackExportEntry> =
-        list_favorite_vocabularies_by_pack_cmd(app_handle.clone(), pack_id)
-            .await?
-            .into_iter()
-            .map(|fav| WordPackExportEntry {
-                word: fav.word,
-                meaning: fav.meaning,
-                usage: if fav.usage.trim().is_empty() {
-                    None
-                } else {
-                    Some(fav.usage)
-                },
-                example: fav.example,
-                reading: fav.reading,
-                explanation: fav.explanation,
-                tags: Vec::new(),
-            })
-            .collect();
+fn persist_failed_capture(app_handle: &AppHandle, capture: &FailedCapture) -> Result<(), String> {
+    let json = serde_json::to_string(capture)
+        .map_err(|e| format!("Failed to serialize failed capture: {}", e))?;
+    save_failed_capture(app_handle, &capture.id, &json)
+}
 
-    entries.sort_by(|a, b| a.word.cmp(&b.word));
+/// 记录抓取失败的 URL；同一 URL 已在队列中时只更新错误信息
+fn queue_failed_capture(app_handle: &AppHandle, url: &str, error: &str) -> Result<(), String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let existing = load_failed_captures_internal(app_handle)?
+        .into_iter()
+        .find(|capture| capture.url == url);
 
-    let export_file = WordPackExportFile {
-        schema_version: "openkoto-word-pack-v1".to_string(),
-        pack: WordPackExportMeta {
-            name: pack.name.clone(),
-            description: pack.description.clone(),
-            cover_url: pack.cover_url.clone(),
-            author: pack.author.clone(),
-            language_from: pack.language_from.clone(),
-            language_to: pack.language_to.clone(),
-            tags: pack.tags.clone(),
-            version: pack.version.clone(),
+    let capture = match existing {
+        Some(mut capture) => {
+            capture.error = error.to_string();
+            capture.last_attempt_at = Some(now);
+            capture
+        }
+        None => FailedCapture {
+            id: Uuid::new_v4().to_string(),
+            url: url.to_string(),
+            error: error.to_string(),
+            failed_at: now,
+            last_attempt_at: None,
+            retry_count: 0,
         },
-        entries,
     };
 
-    let json_content = serde_json::to_string_pretty(&export_file)
-        .map_err(|e| format!("Failed to serialize export file: {}", e))?;
-    let file_name = format!("{}.okpack.json", sanitize_file_name(&pack.name));
-
-    Ok(ExportWordPackResult {
-        file_name,
-        json_content,
-    })
+    persist_failed_capture(app_handle, &capture)
 }
 
-/// 导入 OpenKoto JSON 单词包
+/// 列出重试队列中的网页
 #[tauri::command]
-pub async fn import_word_pack_cmd(
+pub async fn list_failed_captures_cmd(
     app_handle: AppHandle,
-    json_content: String,
-) -> Result<ImportWordPackResult, String> {
-    ensure_default_word_pack(&app_handle)?;
-    let parsed: WordPackExportFile = serde_json::from_str(&json_content)
-        .map_err(|e| format!("Invalid word pack JSON: {}", e))?;
-
-    if parsed.entries.len() > 20000 {
-        return Err("Word pack is too large (max 20000 entries)".to_string());
-    }
-
-    let now = chrono::Utc::now().to_rfc3339();
-    let pack = WordPack {
-        id: Uuid::new_v4().to_string(),
-        name: if parsed.pack.name.trim().is_empty() {
-            "Imported Pack".to_string()
-        } else {
-            parsed.pack.name.trim().to_string()
-        },
-        description: parsed.pack.description.clone(),
-        cover_url: parsed.pack.cover_url.clone(),
-        author: parsed.pack.author.clone(),
-        language_from: parsed.pack.language_from.clone(),
-        language_to: parsed.pack.language_to.clone(),
-        tags: parsed.pack.tags.clone(),
-        version: parsed.pack.version.clone(),
-        created_at: now.clone(),
-        updated_at: now,
-        is_system: false,
-    };
-
-    let pack_json = serde_json::to_string(&pack)
-        .map_err(|e| format!("Failed to serialize word pack: {}", e))?;
-    save_word_pack(&app_handle, &pack.id, &pack_json)?;
+) -> Result<Vec<FailedCapture>, AppError> {
+    Ok(load_failed_captures_internal(&app_handle)?)
+}
 
-    let mut existing_words: HashSet<String> = load_all_favorite_vocabularies_internal(&app_handle)?
-        .into_iter()
-        .map(|fav| normalize_word(&fav.word))
-        .collect();
-    let mut file_seen_words = HashSet::new();
+/// 从重试队列中移除
+#[tauri::command]
+pub async fn delete_failed_capture_cmd(app_handle: AppHandle, id: String) -> Result<(), AppError> {
+    delete_failed_capture(&app_handle, &id)?;
+    Ok(())
+}
 
-    let total = parsed.entries.len();
-    let mut imported = 0usize;
-    let mut skipped = 0usize;
-    let mut errors = Vec::new();
+/// 单条重试的结果
+#[derive(Debug)]
+pub enum CaptureRetry<T> {
+    Imported(T),
+    StillFailing(FailedCapture),
+}
 
-    for (index, entry) in parsed.entries.into_iter().enumerate() {
-        let word = entry.word.trim().to_string();
-        let meaning = entry.meaning.trim().to_string();
-        if word.is_empty() || meaning.is_empty() {
-            skipped += 1;
-            errors.push(format!("Entry {} missing required word/meaning", index + 1));
-            continue;
+/// 结算一条重试：导入成功则移出队列，否则记下错误并留在队列中。
+/// 移出或保存队列失败只打日志，不影响同批其余条目
+pub fn settle_capture_retry<T>(
+    mut capture: FailedCapture,
+    outcome: Result<T, String>,
+    attempted_at: &str,
+    remove: impl FnOnce(&str) -> Result<(), String>,
+    persist: impl FnOnce(&FailedCapture) -> Result<(), String>,
+) -> CaptureRetry<T> {
+    match outcome {
+        Ok(imported) => {
+            if let Err(e) = remove(&capture.id) {
+                println!("[Capture] Failed to dequeue {}: {}", capture.url, e);
+            }
+            println!("[Capture] Retry succeeded: {}", capture.url);
+            CaptureRetry::Imported(imported)
+        }
+        Err(error) => {
+            capture.error = error;
+            capture.retry_count += 1;
+            capture.last_attempt_at = Some(attempted_at.to_string());
+            if let Err(e) = persist(&capture) {
+                println!("[Capture] Failed to update {}: {}", capture.url, e);
+            }
+            CaptureRetry::StillFailing(capture)
         }
+    }
+}
 
-        let normalized = normalize_word(&word);
-        if file_seen_words.contains(&normalized) || existing_words.contains(&normalized) {
-            skipped += 1;
-            continue;
+/// 重新抓取队列中的所有网页，成功的直接导入为文章并移出队列
+#[tauri::command]
+pub async fn retry_failed_captures_cmd(
+    app_handle: AppHandle,
+) -> Result<RetryCapturesResult, AppError> {
+    offline::ensure_online()?;
+    let captures = load_failed_captures_internal(&app_handle)?;
+    let browser_path = load_config(&app_handle)?.and_then(|c| c.headless_browser_path);
+    let rules = load_rules_or_default(&app_handle);
+    let mut imported = Vec::new();
+    let mut still_failing = Vec::new();
+
+    for capture in captures {
+        let outcome =
+            match fetch_and_extract_url(capture.url.clone(), browser_path.clone(), &rules, false)
+                .await
+            {
+                Ok(fetched) => {
+                    let title = Some(fetched.title).filter(|t| !t.trim().is_empty());
+                    save_web_article(
+                        &app_handle,
+                        Uuid::new_v4().to_string(),
+                        capture.url.clone(),
+                        title,
+                        fetched.content,
+                    )
+                }
+                Err(e) => Err(e.message),
+            };
+        let attempted_at = chrono::Utc::now().to_rfc3339();
+        match settle_capture_retry(
+            capture,
+            outcome,
+            &attempted_at,
+            |id| delete_failed_capture(&app_handle, id),
+            |capture| persist_failed_capture(&app_handle, capture),
+        ) {
+            CaptureRetry::Imported(article) => imported.push(article),
+            CaptureRetry::StillFailing(capture) => still_failing.push(capture),
         }
+    }
 
-        file_seen_words.insert(normalized.clone());
-        existing_words.insert(normalized);
+    Ok(RetryCapturesResult {
+        imported,
+        still_failing,
+    })
+}
 
-        let favorite = FavoriteVocabulary {
-            id: Uuid::new_v4().to_string(),
-            word,
-            meaning,
-            usage: entry.usage.unwrap_or_default(),
-            explanation: entry.explanation,
-            example: entry.example,
-            reading: entry.reading,
-            source_article_id: None,
-            source_article_title: None,
-            pack_ids: vec![pack.id.clone()],
-            srs_state: "new".to_string(),
-            ease_factor: 2.5,
-            repetitions: 0,
-            interval_days: 0,
-            due_date: today_local_date().format("%Y-%m-%d").to_string(),
-            last_reviewed_at: None,
-            review_count: 0,
-            created_at: chrono::Utc::now().to_rfc3339(),
-        };
+// ============================================================================
+// Quarantine - 未通过校验的 AI 结果
+// ============================================================================
 
-        if let Err(e) = persist_favorite_vocabulary(&app_handle, &favorite) {
-            skipped += 1;
-            errors.push(format!("Entry {} failed to import: {}", index + 1, e));
-            continue;
+/// 记录未通过校验的结果；隔离失败只打日志，不影响调用方报告原来的错误
+fn quarantine_result(
+    app_handle: &AppHandle,
+    article_id: &str,
+    segment_id: &str,
+    kind: QuarantineKind,
+    target_language: &str,
+    payload: String,
+    reason: &str,
+) {
+    println!(
+        "[Quarantine] Invalid {:?} for segment {}: {}",
+        kind, segment_id, reason
+    );
+    let entry = QuarantinedResult {
+        id: Uuid::new_v4().to_string(),
+        article_id: article_id.to_string(),
+        segment_id: segment_id.to_string(),
+        kind,
+        target_language: target_language.to_string(),
+        payload,
+        reason: reason.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let saved = serde_json::to_string(&entry)
+        .map_err(|e| e.to_string())
+        .and_then(|json| save_quarantined_result(app_handle, &entry.id, &json));
+    if let Err(e) = saved {
+        eprintln!("[Quarantine] Failed to save quarantined result: {}", e);
+    }
+}
+
+/// 校验讲解，不合格时隔离并返回错误信息
+fn checked_explanation(
+    app_handle: &AppHandle,
+    article_id: &str,
+    segment_id: &str,
+    target_language: &str,
+    explanation: crate::types::SegmentExplanation,
+) -> Result<crate::types::SegmentExplanation, String> {
+    match result_validation::validate_explanation(&explanation) {
+        Ok(()) => Ok(explanation),
+        Err(reason) => {
+            quarantine_result(
+                app_handle,
+                article_id,
+                segment_id,
+                QuarantineKind::Explanation,
+                target_language,
+                serde_json::to_string(&explanation).unwrap_or_default(),
+                &reason,
+            );
+            Err(tr_with("error.result_invalid", &[("reason", reason)]))
         }
+    }
+}
 
-        imported += 1;
+/// 校验译文，不合格时隔离并返回错误信息
+fn checked_translation(
+    app_handle: &AppHandle,
+    article_id: &str,
+    segment: &ArticleSegment,
+    target_language: &str,
+    translation: String,
+) -> Result<String, String> {
+    match result_validation::validate_translation(&segment.text, &translation) {
+        Ok(()) => Ok(translation),
+        Err(reason) => {
+            quarantine_result(
+                app_handle,
+                article_id,
+                &segment.id,
+                QuarantineKind::Translation,
+                target_language,
+                translation,
+                &reason,
+            );
+            Err(tr_with("error.result_invalid", &[("reason", reason)]))
+        }
     }
+}
 
-    Ok(ImportWordPackResult {
-        created_pack_id: pack.id,
-        total,
-        imported,
-        skipped,
-        errors,
-    })
+fn load_quarantine_entry(app_handle: &AppHandle, id: &str) -> Result<QuarantinedResult, AppError> {
+    let json = load_quarantined_result(app_handle, id)?
+        .ok_or_else(|| AppError::not_found(tr("error.quarantine_not_found")))?;
+    Ok(serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse quarantined result: {}", e))?)
 }
 
-/// 添加语法收藏
+/// 列出隔离的结果（可按文章筛选），最新的在前
 #[tauri::command]
-pub async fn add_favorite_grammar_cmd(
+pub async fn list_quarantined_results_cmd(
     app_handle: AppHandle,
-    point: String,
-    explanation: String,
-    example: Option<String>,
-    source_article_id: Option<String>,
-    source_article_title: Option<String>,
-) -> Result<FavoriteGrammar, String> {
-    let favorite = FavoriteGrammar {
-        id: Uuid::new_v4().to_string(),
-        point,
-        explanation,
-        example,
-        source_article_id,
-        source_article_title,
-        created_at: chrono::Utc::now().to_rfc3339(),
-    };
+    article_id: Option<String>,
+) -> Result<Vec<QuarantinedResult>, AppError> {
+    let mut entries: Vec<QuarantinedResult> = load_all_quarantined_results(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<QuarantinedResult>(json).ok())
+        .filter(|entry| article_id.as_ref().is_none_or(|id| &entry.article_id == id))
+        .collect();
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(entries)
+}
 
-    let json = serde_json::to_string(&favorite)
-        .map_err(|e| format!("Failed to serialize favorite: {}", e))?;
-    save_favorite_grammar(&app_handle, &favorite.id, &json)?;
+/// 删除隔离的结果
+#[tauri::command]
+pub async fn delete_quarantined_result_cmd(
+    app_handle: AppHandle,
+    id: String,
+) -> Result<(), AppError> {
+    delete_quarantined_result(&app_handle, &id)?;
+    Ok(())
+}
 
-    Ok(favorite)
+/// 为隔离结果对应的段落重新生成讲解或译文；新结果通过校验并写入文章后移出隔离区，
+/// 仍不合格时作为新的隔离记录保存并返回错误
+#[tauri::command]
+pub async fn retry_quarantined_result_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    id: String,
+) -> Result<Article, AppError> {
+    let entry = load_quarantine_entry(&app_handle, &id)?;
+    let article = match entry.kind {
+        QuarantineKind::Translation => {
+            retranslate_segment_cmd(
+                app_handle.clone(),
+                state,
+                entry.article_id.clone(),
+                entry.segment_id.clone(),
+                entry.target_language.clone(),
+            )
+            .await?
+        }
+        QuarantineKind::Explanation => {
+            let mut article = get_article(app_handle.clone(), entry.article_id.clone()).await?;
+            let text = article
+                .segments
+                .iter()
+                .find(|s| s.id == entry.segment_id)
+                .map(|s| s.text.clone())
+                .ok_or_else(|| AppError::not_found(tr("error.segment_not_found")))?;
+            let ai_service = get_ai_service_for_task(&state, AiTask::SegmentExplanation).await?;
+            let explanation = ai_service
+                .segment_translate_explain(text, entry.target_language.clone())
+                .await
+                .map_err(AppError::ai_provider)?;
+            let explanation = checked_explanation(
+                &app_handle,
+                &entry.article_id,
+                &entry.segment_id,
+                &entry.target_language,
+                explanation,
+            )?;
+            if let Some(seg) = article
+                .segments
+                .iter_mut()
+                .find(|s| s.id == entry.segment_id)
+            {
+                seg.explanation = Some(explanation);
+                seg.set_processing(ProcessingTask::Explanation, ProcessingStatus::Done, None);
+            }
+            let article_json = serde_json::to_string(&article).unwrap();
+            save_article(&app_handle, &entry.article_id, &article_json)?;
+            article
+        }
+    };
+    delete_quarantined_result(&app_handle, &entry.id)?;
+    Ok(article)
+}
+
+/// 获取网页提取规则（extraction_rules.json）
+#[tauri::command]
+pub async fn get_extraction_rules_cmd(app_handle: AppHandle) -> Result<ExtractionRules, AppError> {
+    Ok(load_rules(&app_handle)?)
 }
 
-/// 列出所有语法收藏
+/// 保存网页提取规则，选择器或正则无效时拒绝保存
 #[tauri::command]
-pub async fn list_favorite_grammars_cmd(
+pub async fn save_extraction_rules_cmd(
     app_handle: AppHandle,
-) -> Result<Vec<FavoriteGrammar>, String> {
-    let ids = list_favorite_grammars(&app_handle)?;
-    let mut favorites = Vec::new();
+    rules: ExtractionRules,
+) -> Result<(), AppError> {
+    rules.validate().map_err(AppError::invalid_input)?;
+    save_rules(&app_handle, &rules)?;
+    Ok(())
+}
 
-    for id in ids {
-        if let Ok(json) = load_favorite_grammar(&app_handle, &id) {
-            if let Ok(favorite) = serde_json::from_str::<FavoriteGrammar>(&json) {
-                favorites.push(favorite);
-            }
+/// 用给定规则（未传入时使用当前规则文件）试抓取网页，供用户调试规则，不保存文章
+#[tauri::command]
+pub async fn test_extraction_rules_cmd(
+    app_handle: AppHandle,
+    url: String,
+    rules: Option<ExtractionRules>,
+) -> Result<ExtractionTestResult, AppError> {
+    let rules = match rules {
+        Some(rules) => {
+            rules.validate().map_err(AppError::invalid_input)?;
+            rules
         }
-    }
+        None => load_rules(&app_handle)?,
+    };
+    offline::ensure_online()?;
+    let browser_path = load_config(&app_handle)?.and_then(|c| c.headless_browser_path);
+    let matched_rule = rules.find_site_rule(&url).map(|rule| {
+        if rule.name.is_empty() {
+            rule.domains.join(", ")
+        } else {
+            rule.name.clone()
+        }
+    });
+    let fetched = fetch_and_extract_url(url, browser_path, &rules, false).await?;
 
-    // 按创建时间降序排列
-    favorites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(ExtractionTestResult {
+        matched_rule,
+        title: fetched.title,
+        content: fetched.content,
+    })
+}
 
-    Ok(favorites)
+// File System Commands
+#[tauri::command]
+pub async fn write_text_file(path: String, content: String) -> Result<(), AppError> {
+    use std::fs;
+    fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e).into())
 }
 
-/// 删除语法收藏
 #[tauri::command]
-pub async fn delete_favorite_grammar_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
-    delete_favorite_grammar(&app_handle, &id)?;
-    Ok(())
+pub async fn write_binary_file(path: String, content: Vec<u8>) -> Result<(), AppError> {
+    use std::fs;
+    fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e).into())
 }
 
-// YouTube Import
 #[tauri::command]
-pub async fn import_youtube_video_cmd(
+pub async fn delete_article_subtitles_cmd(
     app_handle: AppHandle,
-    url: String,
-) -> Result<Article, String> {
-    let article = crate::youtube::import_youtube_video(app_handle.clone(), url).await?;
+    id: String,
+) -> Result<(), AppError> {
+    let article_json = load_article(&app_handle, &id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    let article_json = serde_json::to_string(&article)
-        .map_err(|e| format!("Failed to serialize article: {}", e))?;
-    save_article(&app_handle, &article.id, &article_json)?;
+    article.segments = Vec::new();
+    article.translated = false;
 
-    Ok(article)
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &id, &updated_json)?;
+
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn import_local_video_cmd(
+pub async fn delete_article_analysis_cmd(
     app_handle: AppHandle,
-    file_path: String,
-) -> Result<Article, String> {
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    id: String,
+) -> Result<(), AppError> {
+    let article_json = load_article(&app_handle, &id)?;
+    let mut article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    let videos_dir = app_data_dir.join("videos");
-    if !videos_dir.exists() {
-        std::fs::create_dir_all(&videos_dir)
-            .map_err(|e| format!("Failed to create videos dir: {}", e))?;
+    for segment in &mut article.segments {
+        segment.translation = None;
+        segment.explanation = None;
     }
+    article.translated = false;
 
-    let src_path = std::path::Path::new(&file_path);
-    if !src_path.exists() {
-        return Err("Source file does not exist".to_string());
+    let updated_json = serde_json::to_string(&article).unwrap();
+    save_article(&app_handle, &id, &updated_json)?;
+
+    Ok(())
+}
+
+/// PDF全文翻译命令
+/// 调用 Python PDF翻译插件进行翻译，生成纯译文和双语对照PDF
+#[tauri::command]
+pub async fn translate_pdf_document(
+    app_handle: AppHandle,
+    pdf_path: String,
+    lang_in: String,
+    lang_out: String,
+    provider: String,
+    api_key: String,
+    model: String,
+    base_url: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    use crate::plugin_manager;
+    use std::process::Command;
+
+    println!(
+        "[PDF Translate] Starting translation: {} -> {}",
+        lang_in, lang_out
+    );
+    println!("[PDF Translate] Provider: {}, Model: {}", provider, model);
+
+    // 获取输出目录（与原PDF相同目录）
+    let pdf_path_buf = PathBuf::from(&pdf_path);
+    let output_dir = pdf_path_buf
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string());
+
+    let filename_stem = pdf_path_buf
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+
+    // 构建环境变量
+    let mut envs: Vec<(&str, String)> = vec![
+        ("OPENKOTO_PROVIDER", provider.clone()),
+        ("OPENKOTO_API_KEY", api_key.clone()),
+        ("OPENKOTO_MODEL", model.clone()),
+    ];
+
+    if let Some(ref url) = base_url {
+        envs.push(("OPENKOTO_BASE_URL", url.clone()));
     }
 
-    let file_name = src_path
-        .file_name()
-        .ok_or("Invalid file name")?
-        .to_string_lossy();
+    // 使用 PluginManager 获取执行命令
+    // 假设插件名称为 "openkoto-pdf-translator"
+    let plugin_name = "openkoto-pdf-translator";
 
-    let ext = src_path
-        .extension()
-        .map(|e| e.to_string_lossy().to_string())
-        .unwrap_or_else(|| "mp4".to_string());
+    let (cmd, mut args, plugin_dir) =
+        match plugin_manager::get_plugin_execution_command(&app_handle, plugin_name) {
+            Ok(res) => res,
+            Err(e) => return Err(AppError::external_tool(format!("Plugin error: {}", e))),
+        };
 
-    let id = Uuid::new_v4().to_string();
-    let dest_name = format!("{}.{}", id, ext);
-    let dest_path = videos_dir.join(&dest_name);
+    // 动态添加参数
+    // 我们约定 entry_point.args 包含固定前缀，如 ["-m", "openkoto_pdf_translator.pdf2zh"]
+    // 我们需要追加 PDF 相关的参数
+    // 原 args: -m openkoto_pdf_translator.pdf2zh [input] -li ...
 
-    std::fs::copy(src_path, &dest_path).map_err(|e| format!("Failed to copy file: {}", e))?;
+    args.push(pdf_path.clone());
+    args.push("-li".to_string());
+    args.push(lang_in);
+    args.push("-lo".to_string());
+    args.push(lang_out);
+    args.push("-s".to_string());
+    args.push("openkoto".to_string());
+    args.push("-o".to_string());
+    args.push(output_dir.clone());
 
-    let created_at = chrono::Utc::now().to_rfc3339();
-    let is_audio = matches!(
-        ext.to_lowercase().as_str(),
-        "mp3" | "wav" | "m4a" | "aac" | "flac" | "ogg" | "wma"
-    );
+    println!("[Plugin] Executing: {} {:?}", cmd, args);
+    println!("[Plugin] CWD: {:?}", plugin_dir);
 
-    // Initial content placeholder
-    let content = if is_audio {
-        format!("[Audio Import] {}", file_name)
-    } else {
-        format!("[Local Import] {}", file_name)
-    };
+    // 在插件目录下执行，以确保 Python 模块导入正确 (如果是 Dev 模式)
+    // 或者对于 Prod 模式，通常也不影响
+    let result = Command::new(&cmd)
+        .args(&args)
+        .envs(envs.iter().map(|(k, v)| (*k, v.as_str())))
+        .current_dir(&plugin_dir) // 关键：设置工作目录为插件目录
+        .output();
 
-    let article = Article {
-        id: id.clone(),
-        title: file_name.into_owned(),
-        content,
-        source_type: Some(if is_audio {
-            "audio".to_string()
-        } else {
-            "local_video".to_string()
-        }),
-        source_url: Some(format!("file://{}", file_path)),
-        media_path: Some(dest_path.to_string_lossy().into_owned()),
-        book_path: None,
-        book_type: None,
-        created_at,
-        translated: false,
-        segments: Vec::new(),
+    match result {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+
+            println!("[PDF Translate] stdout: {}", stdout);
+            if !stderr.is_empty() {
+                println!("[PDF Translate] stderr: {}", stderr);
+            }
+
+            if output.status.success() {
+                // 构建输出文件路径
+                let mono_path = format!("{}/{}-mono.pdf", output_dir, filename_stem);
+                let dual_path = format!("{}/{}-dual.pdf", output_dir, filename_stem);
+                // 旧版插件不输出段落对照
+                let pairs_path = format!("{}/{}-pairs.json", output_dir, filename_stem);
+                let pairs_json = std::path::Path::new(&pairs_path)
+                    .exists()
+                    .then_some(pairs_path);
+
+                Ok(serde_json::json!({
+                    "success": true,
+                    "mono_pdf": mono_path,
+                    "dual_pdf": dual_path,
+                    "pairs_json": pairs_json,
+                    "original_pdf": pdf_path,
+                }))
+            } else {
+                Err(AppError::external_tool(format!(
+                    "PDF translation failed: {}",
+                    stderr
+                )))
+            }
+        }
+        Err(e) => Err(AppError::external_tool(format!(
+            "Failed to execute plugin command '{}': {}",
+            cmd, e
+        ))),
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct TranslationFiles {
+    pub mono_path: Option<String>,
+    pub dual_path: Option<String>,
+    pub pairs_path: Option<String>,
+}
+
+#[tauri::command]
+pub async fn check_pdf_translation_files(pdf_path: String) -> Result<TranslationFiles, AppError> {
+    use std::path::Path;
+    let path = Path::new(&pdf_path);
+    if !path.exists() {
+        return Ok(TranslationFiles {
+            mono_path: None,
+            dual_path: None,
+            pairs_path: None,
+        });
+    }
+
+    let parent = path.parent().unwrap_or(Path::new("."));
+
+    // Safety check: ensure file stem exists
+    let stem = match path.file_stem() {
+        Some(s) => s.to_string_lossy(),
+        None => {
+            return Ok(TranslationFiles {
+                mono_path: None,
+                dual_path: None,
+                pairs_path: None,
+            })
+        }
     };
 
-    let article_json = serde_json::to_string(&article)
-        .map_err(|e| format!("Failed to serialize article: {}", e))?;
-    save_article(&app_handle, &id, &article_json)?;
+    let mono_name = format!("{}-mono.pdf", stem);
+    let dual_name = format!("{}-dual.pdf", stem);
+    let pairs_name = format!("{}-pairs.json", stem);
 
-    Ok(article)
+    let mono_path = parent.join(&mono_name);
+    let dual_path = parent.join(&dual_name);
+    let pairs_path = parent.join(&pairs_name);
+
+    Ok(TranslationFiles {
+        mono_path: if mono_path.exists() {
+            Some(mono_path.to_string_lossy().into_owned())
+        } else {
+            None
+        },
+        dual_path: if dual_path.exists() {
+            Some(dual_path.to_string_lossy().into_owned())
+        } else {
+            None
+        },
+        // 段落对照数据存在时可通过 import_pdf_translation_cmd 回导为文章
+        pairs_path: if pairs_path.exists() {
+            Some(pairs_path.to_string_lossy().into_owned())
+        } else {
+            None
+        },
+    })
 }
 
-// 字幕提取
-/// 提取视频字幕
-/// 使用 Gemini 多模态 API 从视频中提取音频并转录为字幕
+/// 导出文章为 Obsidian 笔记到用户选择的库文件夹，每篇一个文件，返回写入的文件路径
+/// 包含书籍摘录、来自该文章的生词和语法收藏；再次导出会更新同一文件
+/// 指定 template_id 时按用户的 Markdown 模板生成笔记（模板中保留 openkoto_id 才能原地更新）
 #[tauri::command]
-pub async fn extract_subtitles_cmd(
+pub async fn export_articles_to_obsidian_cmd(
     app_handle: AppHandle,
-    article_id: String,
-) -> Result<Article, String> {
-    println!("[ExtractSubtitles] 开始提取字幕: {}", article_id);
+    article_ids: Vec<String>,
+    vault_dir: String,
+    template_id: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let dir = std::path::Path::new(&vault_dir);
+    if !dir.is_dir() {
+        return Err(AppError::not_found(tr_with(
+            "error.folder_missing",
+            &[("path", vault_dir.clone())],
+        )));
+    }
 
-    // 1. 加载文章
-    let article_json = load_article(&app_handle, &article_id)?;
-    let mut article: Article = serde_json::from_str(&article_json)
-        .map_err(|e| format!("Failed to parse article: {}", e))?;
+    let template = match template_id.as_deref() {
+        Some(id) => Some(
+            export_templates::Template::parse(
+                &load_export_template_of_kind(
+                    &app_handle,
+                    id,
+                    ExportTemplateKind::ArticleMarkdown,
+                )?
+                .body,
+            )
+            .map_err(template_syntax_error)?,
+        ),
+        None => None,
+    };
 
-    // 2. 验证是视频并获取视频路径
-    let video_path = article
-        .media_path
-        .as_ref()
-        .ok_or("该文章不是视频，无法提取字幕")?;
-    let video_path = std::path::Path::new(video_path);
+    let mut vocabulary = load_all_favorite_vocabularies_internal(&app_handle)?;
+    vocabulary.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    let mut grammar: Vec<FavoriteGrammar> = load_all_favorite_grammars(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteGrammar>(json).ok())
+        .collect();
+    grammar.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let mut written = Vec::new();
+    for article_id in &article_ids {
+        let article_json = load_article(&app_handle, article_id)?;
+        let article: Article = serde_json::from_str(&article_json)
+            .map_err(|e| format!("Failed to parse article: {}", e))?;
+
+        let mut highlights = Vec::new();
+        if let Some(book_path) = article.book_path.as_deref() {
+            for id in list_bookmarks_for_book(&app_handle, book_path)? {
+                if let Ok(bookmark) = load_bookmark(&app_handle, &id).and_then(|json| {
+                    serde_json::from_str::<Bookmark>(&json).map_err(|e| e.to_string())
+                }) {
+                    if bookmark.book_path == book_path {
+                        highlights.push(bookmark);
+                    }
+                }
+            }
+            highlights.sort_by(|a, b| {
+                (a.page_number, &a.created_at).cmp(&(b.page_number, &b.created_at))
+            });
+        }
+        let from_article = |source: &Option<String>| source.as_deref() == Some(article_id);
+        let article_vocabulary: Vec<FavoriteVocabulary> = vocabulary
+            .iter()
+            .filter(|fav| from_article(&fav.source_article_id))
+            .cloned()
+            .collect();
+        let article_grammar: Vec<FavoriteGrammar> = grammar
+            .iter()
+            .filter(|fav| from_article(&fav.source_article_id))
+            .cloned()
+            .collect();
 
-    if !video_path.exists() {
-        return Err(format!("视频文件不存在: {:?}", video_path));
+        let note = match &template {
+            Some(template) => template.render(&export_templates::article_context(
+                &article,
+                &highlights,
+                &article_vocabulary,
+                &article_grammar,
+            )),
+            None => obsidian_export::render_article_note(
+                &article,
+                &highlights,
+                &article_vocabulary,
+                &article_grammar,
+            ),
+        };
+        let path = obsidian_export::note_path(dir, &article);
+        std::fs::write(&path, note).map_err(|e| format!("Failed to write note: {}", e))?;
+        written.push(path.to_string_lossy().to_string());
     }
 
-    // 3. 获取 API 配置
-    let config = load_config(&app_handle)?.ok_or("未配置 API，请先在设置中配置 AI 模型")?;
-
-    let active_config = config
-        .get_active_config()
-        .ok_or("未设置活动模型配置，请先在设置中配置 AI 模型")?;
-
-    // 检查是否是 Gemini 模型
-    let model = &active_config.model;
-    let provider = &active_config.api_provider;
-    let api_key = &active_config.api_key;
-    let base_url = active_config.base_url.as_deref();
+    Ok(written)
+}
 
-    // 本地 provider 当前不支持字幕提取（该流程依赖云端多模态转录能力）
-    if provider == "ollama" || provider == "lmstudio" {
-        return Err(
-            "字幕提取暂不支持 Ollama / LM Studio 本地模型。请切换到 Gemini 或 Kimi K2.5。"
-                .to_string(),
-        );
-    }
+#[tauri::command]
+pub async fn export_file_cmd(src_path: String, dest_path: String) -> Result<(), AppError> {
+    std::fs::copy(&src_path, &dest_path).map_err(|e| format!("Failed to export file: {}", e))?;
+    Ok(())
+}
 
-    // 允许的 Gemini 或 Kimi K2.5 模型
-    let is_supported = model.contains("gemini")
-        || model.starts_with("google/gemini")
-        || provider == "google"
-        || provider == "google-ai-studio"
-        || (provider == "moonshot" && model.contains("kimi"))
-        || model.contains("kimi");
+// ============================================================================
+// Reading Aloud - 朗读模式（逐句语音播放清单）
+// ============================================================================
 
-    if !is_supported {
-        return Err(
-            "字幕提取需要使用 Gemini 或 Kimi K2.5 云端模型。请在设置中切换模型。".to_string(),
-        );
+/// 取一句话的朗读音频：已缓存时直接返回文件名，否则合成后写入缓存
+/// 外层错误（离线、未配置模型）表示无法继续合成，内层错误为这一句合成失败
+async fn cached_speech(
+    config: &crate::types::AppConfig,
+    audio_dir: &std::path::Path,
+    voice: &str,
+    speed: f32,
+    text: &str,
+) -> Result<Result<String, String>, AppError> {
+    let settings = &config.tts;
+    let filename = tts::audio_file_name(text, &settings.model, voice, speed);
+    let path = audio_dir.join(&filename);
+    if path.exists() {
+        return Ok(Ok(filename));
     }
-
-    // 4. 调用字幕提取模块 (使用 article_id 作为 event_id)
-    let segments = crate::subtitle_extraction::extract_subtitles(
-        app_handle.clone(),
-        video_path,
-        &article_id,
-        provider,
-        api_key,
-        model,
-        base_url,
-        &article_id, // event_id 用于进度事件
+    offline::ensure_online()?;
+    let model_config = settings
+        .model_config_id
+        .as_deref()
+        .and_then(|id| config.get_config(id))
+        .or_else(|| config.get_active_config())
+        .ok_or_else(|| AppError::missing_api_key(tr("error.ai_not_configured")))?;
+    let bytes = match tts::synthesize(
+        &model_config.api_key,
+        model_config.base_url.as_deref(),
+        &settings.model,
+        voice,
+        speed,
+        text,
     )
-    .await?;
+    .await
+    {
+        Ok(bytes) => bytes,
+        Err(e) => return Ok(Err(e)),
+    };
+    // 先写临时文件再改名，中断时不会留下被当作缓存的半截音频
+    let partial = path.with_extension("mp3.part");
+    std::fs::write(&partial, bytes)
+        .and_then(|_| std::fs::rename(&partial, &path))
+        .map_err(|e| format!("Failed to write audio: {}", e))?;
+    Ok(Ok(filename))
+}
 
-    if segments.is_empty() {
-        return Err("未能从视频中提取到字幕内容".to_string());
-    }
+/// 为文章的每个段落合成语音（已缓存的句子直接复用），返回按段落顺序排列的播放清单
+/// 清单同时写入 tts_audio/{article_id}.json 供资源服务器读取；进度通过 tts-progress://{article_id} 事件发送
+/// 全部句子都已缓存时离线也能生成清单
+#[tauri::command]
+pub async fn generate_tts_playlist_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    voice: Option<String>,
+    speed: Option<f32>,
+) -> Result<TtsPlaylist, AppError> {
+    let config = load_config(&app_handle)?.unwrap_or_default();
+    let settings = &config.tts;
+    let voice = voice
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| settings.voice.clone());
+    let speed = speed.unwrap_or(settings.speed).clamp(0.25, 4.0);
 
-    println!("[ExtractSubtitles] 提取到 {} 个字幕片段", segments.len());
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    // 5. 更新文章内容
-    article.segments = segments;
-    article.content = article
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let audio_dir = app_data_dir.join(tts::TTS_AUDIO_DIR);
+    std::fs::create_dir_all(&audio_dir).map_err(|e| format!("Failed to create TTS dir: {}", e))?;
+
+    let segments: Vec<&ArticleSegment> = article
         .segments
         .iter()
-        .map(|s| s.text.clone())
-        .collect::<Vec<_>>()
-        .join(" ");
+        .filter(|s| tts::is_speakable(s))
+        .collect();
+    let total = segments.len();
+    let mut items = Vec::new();
+    let mut failed = 0;
+    let mut first_error = None;
+
+    for (index, segment) in segments.into_iter().enumerate() {
+        let filename =
+            match cached_speech(&config, &audio_dir, &voice, speed, &segment.text).await? {
+                Ok(filename) => filename,
+                Err(e) => {
+                    eprintln!("[TTS] Segment {} failed: {}", segment.id, e);
+                    failed += 1;
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+
+        items.push(TtsPlaylistItem {
+            segment_id: segment.id.clone(),
+            order: segment.order,
+            text: segment.text.clone(),
+            audio_url: tts::audio_url(&filename),
+            is_new_paragraph: segment.is_new_paragraph,
+        });
+        emit_article_event(
+            &app_handle,
+            &article_id,
+            &format!("tts-progress://{}", article_id),
+            serde_json::json!({
+                "current": index + 1,
+                "total": total,
+                "failed": failed,
+            }),
+        );
+    }
 
-    // 6. 保存文章
-    let updated_json = serde_json::to_string(&article)
-        .map_err(|e| format!("Failed to serialize article: {}", e))?;
-    save_article(&app_handle, &article_id, &updated_json)?;
+    // 一句都没有合成成功时直接返回错误，便于界面提示配置或网络问题
+    if items.is_empty() {
+        if let Some(e) = first_error {
+            return Err(AppError::from(e));
+        }
+    }
 
-    println!("[ExtractSubtitles] 字幕提取完成并保存");
+    let playlist = TtsPlaylist {
+        article_id: article_id.clone(),
+        model: settings.model.clone(),
+        voice,
+        speed,
+        items,
+        failed,
+        manifest_url: Some(tts::audio_url(&format!("{}.json", article_id))),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    tts::save_playlist(&app_data_dir, &playlist)?;
 
-    Ok(article)
+    Ok(playlist)
 }
 
 // ============================================================================
-// 书籍导入功能 - 支持 EPUB、TXT 和 PDF 格式
+// Bookmarks Commands - 书签命令
 // ============================================================================
 
-const BOOKS_DIR: &str = "books";
+/// 添加书签
+#[tauri::command]
+pub async fn add_bookmark_cmd(
+    app_handle: AppHandle,
+    book_path: String,
+    book_type: String,
+    title: String,
+    note: Option<String>,
+    selected_text: Option<String>,
+    page_number: Option<i32>,
+    epub_cfi: Option<String>,
+    color: Option<String>,
+) -> Result<Bookmark, AppError> {
+    let bookmark = Bookmark {
+        id: Uuid::new_v4().to_string(),
+        book_path,
+        book_type,
+        title,
+        note,
+        selected_text,
+        page_number,
+        epub_cfi,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        color,
+    };
 
-/// 确保书籍存储目录存在
-fn ensure_books_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let json = serde_json::to_string(&bookmark)
+        .map_err(|e| format!("Failed to serialize bookmark: {}", e))?;
+    save_bookmark(&app_handle, &bookmark.id, &json)?;
 
-    let books_dir = app_data_dir.join(BOOKS_DIR);
-    if !books_dir.exists() {
-        std::fs::create_dir_all(&books_dir).map_err(|e| format!("创建书籍目录失败: {}", e))?;
-    }
+    Ok(bookmark)
+}
 
-    Ok(books_dir)
+/// 列出所有书签
+#[tauri::command]
+pub async fn list_bookmarks_cmd(app_handle: AppHandle) -> Result<Vec<Bookmark>, AppError> {
+    let mut bookmarks: Vec<Bookmark> = load_all_bookmarks(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<Bookmark>(json).ok())
+        .collect();
+
+    // 按创建时间降序排列
+    bookmarks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(bookmarks)
 }
 
-/// 导入书籍文件 (EPUB/TXT/PDF)
-/// 将文件复制到应用数据目录并创建 Article 记录
+/// 列出指定书籍的书签
 #[tauri::command]
-pub async fn import_book_cmd(
+pub async fn list_bookmarks_for_book_cmd(
     app_handle: AppHandle,
-    file_path: String,
-    title: Option<String>,
-) -> Result<Article, String> {
-    use std::path::Path;
-
-    let src_path = Path::new(&file_path);
+    book_path: String,
+) -> Result<Vec<Bookmark>, AppError> {
+    let ids = list_bookmarks_for_book(&app_handle, &book_path)?;
+    let mut bookmarks = Vec::new();
 
-    // 验证文件存在
-    if !src_path.exists() {
-        return Err(format!("文件不存在: {}", file_path));
+    for id in ids {
+        if let Ok(json) = load_bookmark(&app_handle, &id) {
+            if let Ok(bookmark) = serde_json::from_str::<Bookmark>(&json) {
+                bookmarks.push(bookmark);
+            }
+        }
     }
 
-    // 获取文件扩展名并验证格式
-    let ext = src_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .ok_or("无法识别文件格式")?;
+    // 按创建时间降序排列
+    bookmarks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
-    let book_type = match ext.as_str() {
-        "epub" => "epub",
-        "txt" => "txt",
-        "pdf" => "pdf",
-        _ => return Err(format!("不支持的文件格式: {}", ext)),
-    };
+    Ok(bookmarks)
+}
 
-    // 获取文件名作为默认标题
-    let file_name = src_path
-        .file_stem()
-        .and_then(|n| n.to_str())
-        .unwrap_or("未命名书籍");
+/// 更新书签
+#[tauri::command]
+pub async fn update_bookmark_cmd(
+    app_handle: AppHandle,
+    id: String,
+    title: Option<String>,
+    note: Option<String>,
+    color: Option<String>,
+) -> Result<Bookmark, AppError> {
+    let json = load_bookmark(&app_handle, &id)?;
+    let mut bookmark: Bookmark =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse bookmark: {}", e))?;
 
-    let book_title = title.unwrap_or_else(|| file_name.to_string());
+    if let Some(t) = title {
+        bookmark.title = t;
+    }
+    if let Some(n) = note {
+        bookmark.note = Some(n);
+    }
+    if let Some(c) = color {
+        bookmark.color = Some(c);
+    }
 
-    // 确保书籍目录存在
-    let books_dir = ensure_books_dir(&app_handle)?;
+    let updated_json = serde_json::to_string(&bookmark)
+        .map_err(|e| format!("Failed to serialize bookmark: {}", e))?;
+    save_bookmark(&app_handle, &id, &updated_json)?;
 
-    // 生成唯一 ID 和目标路径
-    let id = Uuid::new_v4().to_string();
-    let dest_name = format!("{}.{}", id, ext);
-    let dest_path = books_dir.join(&dest_name);
+    Ok(bookmark)
+}
 
-    // 复制文件到应用数据目录
-    std::fs::copy(src_path, &dest_path).map_err(|e| format!("复制文件失败: {}", e))?;
+/// 删除书签
+#[tauri::command]
+pub async fn delete_bookmark_cmd(
+    app_handle: AppHandle,
+    undo: State<'_, UndoJournal>,
+    id: String,
+) -> Result<(), AppError> {
+    let previous = load_bookmark(&app_handle, &id).ok();
+    delete_bookmark(&app_handle, &id)?;
 
-    let created_at = chrono::Utc::now().to_rfc3339();
+    if let Some(bookmark) = previous {
+        let title = serde_json::from_str::<Bookmark>(&bookmark)
+            .map(|bookmark| bookmark.title)
+            .unwrap_or_default();
+        undo.record(
+            &title,
+            UndoAction::DeleteBookmark { bookmark },
+            chrono::Utc::now(),
+        );
+    }
+    Ok(())
+}
 
-    // 读取 TXT 文件内容作为 content，EPUB/PDF 使用占位符
-    let content = match book_type {
-        "txt" => {
-            // 尝试读取 TXT 文件内容
-            std::fs::read_to_string(&dest_path)
-                .unwrap_or_else(|_| format!("[书籍已导入] {}", book_title))
-        }
-        "epub" => format!("[EPUB 书籍] {}", book_title),
-        "pdf" => format!("[PDF 书籍] {}", book_title),
-        _ => format!("[书籍已导入] {}", book_title),
-    };
+// ============================================================================
+// Glossary Commands - 术语表命令
+// ============================================================================
 
-    // 创建 Article 记录
-    let article = Article {
-        id: id.clone(),
-        title: book_title,
-        content,
-        source_type: Some("book".to_string()),
-        source_url: Some(format!("file://{}", file_path)),
-        media_path: None,
-        book_path: Some(dest_path.to_string_lossy().into_owned()),
-        book_type: Some(book_type.to_string()),
-        created_at,
-        translated: false,
-        segments: Vec::new(), // 书籍不预分段，由阅读器处理
+/// 添加术语
+#[tauri::command]
+pub async fn add_glossary_entry_cmd(
+    app_handle: AppHandle,
+    term: String,
+    translation: String,
+    target_language: String,
+    source_language: Option<String>,
+    note: Option<String>,
+) -> Result<GlossaryEntry, AppError> {
+    if term.trim().is_empty() || translation.trim().is_empty() {
+        return Err(AppError::invalid_input(tr("error.glossary_empty")));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let entry = GlossaryEntry {
+        id: Uuid::new_v4().to_string(),
+        term: term.trim().to_string(),
+        translation: translation.trim().to_string(),
+        source_language,
+        target_language,
+        note,
+        created_at: now.clone(),
+        updated_at: now,
     };
 
-    // 保存文章记录
-    let article_json =
-        serde_json::to_string(&article).map_err(|e| format!("序列化文章失败: {}", e))?;
-    save_article(&app_handle, &id, &article_json)?;
+    let json = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize glossary entry: {}", e))?;
+    save_glossary_entry(&app_handle, &entry.id, &json)?;
 
-    println!(
-        "[ImportBook] 书籍导入成功: {} ({})",
-        article.title, book_type
-    );
+    Ok(entry)
+}
+
+/// 列出术语表，可按目标语言筛选
+#[tauri::command]
+pub async fn list_glossary_entries_cmd(
+    app_handle: AppHandle,
+    target_language: Option<String>,
+) -> Result<Vec<GlossaryEntry>, AppError> {
+    let mut entries: Vec<GlossaryEntry> = load_all_glossary_entries(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<GlossaryEntry>(json).ok())
+        .filter(|entry| {
+            target_language
+                .as_deref()
+                .is_none_or(|lang| entry.target_language.eq_ignore_ascii_case(lang))
+        })
+        .collect();
 
-    Ok(article)
+    entries.sort_by_key(|entry| entry.term.to_lowercase());
+
+    Ok(entries)
 }
 
+/// 更新术语
 #[tauri::command]
-pub async fn import_web_material_cmd(
+pub async fn update_glossary_entry_cmd(
     app_handle: AppHandle,
-    url: String,
-    title: Option<String>,
-    content: String,
-) -> Result<Article, String> {
-    let parsed_url = url::Url::parse(&url).map_err(|_| "Invalid URL format".to_string())?;
-    if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
-        return Err("Only HTTP and HTTPS URLs are supported".to_string());
+    id: String,
+    term: Option<String>,
+    translation: Option<String>,
+    target_language: Option<String>,
+    source_language: Option<String>,
+    note: Option<String>,
+) -> Result<GlossaryEntry, AppError> {
+    let json = load_glossary_entry(&app_handle, &id)?
+        .ok_or_else(|| AppError::not_found(tr("error.glossary_not_found")))?;
+    let mut entry: GlossaryEntry = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse glossary entry: {}", e))?;
+
+    if let Some(t) = term {
+        entry.term = t.trim().to_string();
+    }
+    if let Some(t) = translation {
+        entry.translation = t.trim().to_string();
+    }
+    if entry.term.is_empty() || entry.translation.is_empty() {
+        return Err(AppError::invalid_input(tr("error.glossary_empty")));
+    }
+    if let Some(lang) = target_language {
+        entry.target_language = lang;
+    }
+    if let Some(lang) = source_language {
+        entry.source_language = Some(lang);
     }
+    if let Some(n) = note {
+        entry.note = Some(n);
+    }
+    entry.updated_at = chrono::Utc::now().to_rfc3339();
 
-    if content.trim().len() < 10 {
-        return Err(
-            "Extracted content is too short. Please check the URL and try again.".to_string(),
-        );
+    let updated_json = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize glossary entry: {}", e))?;
+    save_glossary_entry(&app_handle, &id, &updated_json)?;
+
+    Ok(entry)
+}
+
+/// 删除术语
+#[tauri::command]
+pub async fn delete_glossary_entry_cmd(app_handle: AppHandle, id: String) -> Result<(), AppError> {
+    delete_glossary_entry(&app_handle, &id)?;
+    Ok(())
+}
+
+// ============================================================================
+// Tag Rules - 导入时自动加标签
+// ============================================================================
+
+fn load_tag_rules_internal(app_handle: &AppHandle) -> Result<Vec<TagRule>, String> {
+    let mut rules: Vec<TagRule> = load_all_tag_rules(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<TagRule>(json).ok())
+        .collect();
+    rules.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(rules)
+}
+
+/// 新导入的文章按自动标签规则加标签；规则读取失败不影响导入
+fn apply_import_tag_rules(app_handle: &AppHandle, article: &mut Article) {
+    match load_tag_rules_internal(app_handle) {
+        Ok(rules) => {
+            tag_rules::apply_rules(&rules, article);
+        }
+        Err(e) => eprintln!("[TagRules] Failed to load rules: {}", e),
     }
+}
 
-    let id = Uuid::new_v4().to_string();
-    let created_at = chrono::Utc::now().to_rfc3339();
-    let final_title = title.unwrap_or_else(|| "Untitled Web Material".to_string());
-    let segments = create_segments_from_content(&id, &content);
+fn validate_tag_rule(pattern: &str, tag: &str) -> Result<(), AppError> {
+    if pattern.trim().is_empty() || tag.trim().is_empty() {
+        return Err(AppError::invalid_input(tr("error.tag_rule_empty")));
+    }
+    Ok(())
+}
 
-    let article = Article {
-        id: id.clone(),
-        title: final_title,
-        content,
-        source_type: Some("web".to_string()),
-        source_url: Some(url),
-        media_path: None,
-        book_path: None,
-        book_type: None,
-        created_at,
-        translated: false,
-        segments,
+/// 添加自动标签规则
+#[tauri::command]
+pub async fn add_tag_rule_cmd(
+    app_handle: AppHandle,
+    field: TagRuleField,
+    pattern: String,
+    tag: String,
+) -> Result<TagRule, AppError> {
+    validate_tag_rule(&pattern, &tag)?;
+    let rule = TagRule {
+        id: Uuid::new_v4().to_string(),
+        field,
+        pattern: pattern.trim().to_string(),
+        tag: tag.trim().to_string(),
+        enabled: true,
+        created_at: chrono::Utc::now().to_rfc3339(),
     };
+    let json =
+        serde_json::to_string(&rule).map_err(|e| format!("Failed to serialize tag rule: {}", e))?;
+    save_tag_rule(&app_handle, &rule.id, &json)?;
+    Ok(rule)
+}
 
-    let article_json = serde_json::to_string(&article)
-        .map_err(|e| format!("Failed to serialize article: {}", e))?;
-    save_article(&app_handle, &id, &article_json)?;
+/// 列出自动标签规则（按创建顺序）
+#[tauri::command]
+pub async fn list_tag_rules_cmd(app_handle: AppHandle) -> Result<Vec<TagRule>, AppError> {
+    Ok(load_tag_rules_internal(&app_handle)?)
+}
 
-    Ok(article)
+/// 更新自动标签规则
+#[tauri::command]
+pub async fn update_tag_rule_cmd(
+    app_handle: AppHandle,
+    id: String,
+    field: Option<TagRuleField>,
+    pattern: Option<String>,
+    tag: Option<String>,
+    enabled: Option<bool>,
+) -> Result<TagRule, AppError> {
+    let json = load_tag_rule(&app_handle, &id)?
+        .ok_or_else(|| AppError::not_found(tr("error.tag_rule_not_found")))?;
+    let mut rule: TagRule =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse tag rule: {}", e))?;
+
+    if let Some(field) = field {
+        rule.field = field;
+    }
+    if let Some(pattern) = pattern {
+        rule.pattern = pattern.trim().to_string();
+    }
+    if let Some(tag) = tag {
+        rule.tag = tag.trim().to_string();
+    }
+    if let Some(enabled) = enabled {
+        rule.enabled = enabled;
+    }
+    validate_tag_rule(&rule.pattern, &rule.tag)?;
+
+    let json =
+        serde_json::to_string(&rule).map_err(|e| format!("Failed to serialize tag rule: {}", e))?;
+    save_tag_rule(&app_handle, &rule.id, &json)?;
+    Ok(rule)
 }
 
-// File System Commands
+/// 删除自动标签规则（已加上的标签保留）
 #[tauri::command]
-pub async fn write_text_file(path: String, content: String) -> Result<(), String> {
-    use std::fs;
-    fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e))
+pub async fn delete_tag_rule_cmd(app_handle: AppHandle, id: String) -> Result<(), AppError> {
+    delete_tag_rule(&app_handle, &id)?;
+    Ok(())
 }
 
+/// 对已有文章重新执行全部规则，只添加缺少的标签，返回有变化的文章数
 #[tauri::command]
-pub async fn write_binary_file(path: String, content: Vec<u8>) -> Result<(), String> {
-    use std::fs;
-    fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e))
+pub async fn apply_tag_rules_cmd(app_handle: AppHandle) -> Result<usize, AppError> {
+    let rules = load_tag_rules_internal(&app_handle)?;
+    let mut updated = 0;
+    for json in load_all_articles(&app_handle)? {
+        let Ok(mut article) = serde_json::from_str::<Article>(&json) else {
+            continue;
+        };
+        if tag_rules::apply_rules(&rules, &mut article) {
+            let json = serde_json::to_string(&article)
+                .map_err(|e| format!("Failed to serialize article: {}", e))?;
+            save_article(&app_handle, &article.id, &json)?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
 }
 
+/// 手动设置文章标签（去重、去掉空标签）
 #[tauri::command]
-pub async fn delete_article_subtitles_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
-    let article_json = load_article(&app_handle, &id)?;
+pub async fn set_article_tags_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    tags: Vec<String>,
+) -> Result<Article, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
     let mut article: Article = serde_json::from_str(&article_json)
         .map_err(|e| format!("Failed to parse article: {}", e))?;
+    article.tags.clear();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !article.tags.iter().any(|t| t == tag) {
+            article.tags.push(tag.to_string());
+        }
+    }
+    let json = serde_json::to_string(&article)
+        .map_err(|e| format!("Failed to serialize article: {}", e))?;
+    save_article(&app_handle, &article_id, &json)?;
+    Ok(article)
+}
 
-    article.segments = Vec::new();
-    article.translated = false;
+// ============================================================================
+// Export Templates - 用户自定义的导出模板
+// ============================================================================
 
-    let updated_json = serde_json::to_string(&article).unwrap();
-    save_article(&app_handle, &id, &updated_json)?;
+fn template_syntax_error(error: String) -> AppError {
+    AppError::invalid_input(tr_with(
+        "error.export_template_invalid",
+        &[("error", error)],
+    ))
+}
 
-    Ok(())
+fn load_export_template_internal(
+    app_handle: &AppHandle,
+    id: &str,
+) -> Result<ExportTemplate, AppError> {
+    let json = load_export_template(app_handle, id)?
+        .ok_or_else(|| AppError::not_found(tr("error.export_template_not_found")))?;
+    Ok(serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse export template: {}", e))?)
 }
 
-#[tauri::command]
-pub async fn delete_article_analysis_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
-    let article_json = load_article(&app_handle, &id)?;
-    let mut article: Article = serde_json::from_str(&article_json)
-        .map_err(|e| format!("Failed to parse article: {}", e))?;
+/// 读取模板并检查用途是否与本次导出一致
+fn load_export_template_of_kind(
+    app_handle: &AppHandle,
+    id: &str,
+    kind: ExportTemplateKind,
+) -> Result<ExportTemplate, AppError> {
+    let template = load_export_template_internal(app_handle, id)?;
+    if template.kind != kind {
+        return Err(AppError::invalid_input(tr("error.export_template_kind")));
+    }
+    Ok(template)
+}
 
-    for segment in &mut article.segments {
-        segment.translation = None;
-        segment.explanation = None;
+/// 模板名称不能为空，Markdown 模板需要正文、CSV 和 Anki 模板至少一个字段，且全部能解析
+fn validate_export_template(template: &ExportTemplate) -> Result<(), AppError> {
+    let empty = match template.kind {
+        ExportTemplateKind::ArticleMarkdown => template.body.trim().is_empty(),
+        ExportTemplateKind::VocabularyCsv | ExportTemplateKind::AnkiNote => {
+            template.fields.is_empty() || template.fields.iter().any(|f| f.name.trim().is_empty())
+        }
+    };
+    if template.name.trim().is_empty() || empty {
+        return Err(AppError::invalid_input(tr("error.export_template_empty")));
     }
-    article.translated = false;
+    export_templates::Template::parse(&template.body).map_err(template_syntax_error)?;
+    for field in &template.fields {
+        export_templates::Template::parse(&field.template)
+            .map_err(|e| template_syntax_error(format!("{}: {}", field.name, e)))?;
+    }
+    Ok(())
+}
 
-    let updated_json = serde_json::to_string(&article).unwrap();
-    save_article(&app_handle, &id, &updated_json)?;
+fn persist_export_template(
+    app_handle: &AppHandle,
+    template: &ExportTemplate,
+) -> Result<(), String> {
+    let json = serde_json::to_string(template)
+        .map_err(|e| format!("Failed to serialize export template: {}", e))?;
+    save_export_template(app_handle, &template.id, &json)
+}
 
-    Ok(())
+/// 某类导出的内置模板，作为新建模板的初始内容（未保存，id 为空）
+#[tauri::command]
+pub async fn default_export_template_cmd(
+    kind: ExportTemplateKind,
+) -> Result<ExportTemplate, AppError> {
+    let (body, fields) = export_templates::default_template(kind);
+    Ok(ExportTemplate {
+        id: String::new(),
+        name: String::new(),
+        kind,
+        body,
+        fields,
+        created_at: String::new(),
+        updated_at: String::new(),
+    })
 }
 
-/// PDF全文翻译命令
-/// 调用 Python PDF翻译插件进行翻译，生成纯译文和双语对照PDF
+/// 新建导出模板；未提供正文或字段时使用内置模板的内容
 #[tauri::command]
-pub async fn translate_pdf_document(
+pub async fn add_export_template_cmd(
     app_handle: AppHandle,
-    pdf_path: String,
-    lang_in: String,
-    lang_out: String,
-    provider: String,
-    api_key: String,
-    model: String,
-    base_url: Option<String>,
-) -> Result<serde_json::Value, String> {
-    use crate::plugin_manager;
-    use std::process::Command;
+    name: String,
+    kind: ExportTemplateKind,
+    body: Option<String>,
+    fields: Option<Vec<TemplateField>>,
+) -> Result<ExportTemplate, AppError> {
+    let (default_body, default_fields) = export_templates::default_template(kind);
+    let now = chrono::Utc::now().to_rfc3339();
+    let template = ExportTemplate {
+        id: Uuid::new_v4().to_string(),
+        name: name.trim().to_string(),
+        kind,
+        body: body.unwrap_or(default_body),
+        fields: fields.unwrap_or(default_fields),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    validate_export_template(&template)?;
+    persist_export_template(&app_handle, &template)?;
+    Ok(template)
+}
 
-    println!(
-        "[PDF Translate] Starting translation: {} -> {}",
-        lang_in, lang_out
-    );
-    println!("[PDF Translate] Provider: {}, Model: {}", provider, model);
+/// 列出导出模板（按创建顺序），指定 kind 时只返回该用途的模板
+#[tauri::command]
+pub async fn list_export_templates_cmd(
+    app_handle: AppHandle,
+    kind: Option<ExportTemplateKind>,
+) -> Result<Vec<ExportTemplate>, AppError> {
+    let mut templates: Vec<ExportTemplate> = load_all_export_templates(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ExportTemplate>(json).ok())
+        .filter(|template| kind.is_none_or(|kind| template.kind == kind))
+        .collect();
+    templates.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(templates)
+}
 
-    // 获取输出目录（与原PDF相同目录）
-    let pdf_path_buf = PathBuf::from(&pdf_path);
-    let output_dir = pdf_path_buf
-        .parent()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| ".".to_string());
+/// 更新导出模板的名称、正文或字段
+#[tauri::command]
+pub async fn update_export_template_cmd(
+    app_handle: AppHandle,
+    id: String,
+    name: Option<String>,
+    body: Option<String>,
+    fields: Option<Vec<TemplateField>>,
+) -> Result<ExportTemplate, AppError> {
+    let mut template = load_export_template_internal(&app_handle, &id)?;
+    if let Some(name) = name {
+        template.name = name.trim().to_string();
+    }
+    if let Some(body) = body {
+        template.body = body;
+    }
+    if let Some(fields) = fields {
+        template.fields = fields;
+    }
+    validate_export_template(&template)?;
+    template.updated_at = chrono::Utc::now().to_rfc3339();
+    persist_export_template(&app_handle, &template)?;
+    Ok(template)
+}
 
-    let filename_stem = pdf_path_buf
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "output".to_string());
+/// 删除导出模板
+#[tauri::command]
+pub async fn delete_export_template_cmd(app_handle: AppHandle, id: String) -> Result<(), AppError> {
+    delete_export_template(&app_handle, &id)?;
+    Ok(())
+}
 
-    // 构建环境变量
-    let mut envs: Vec<(&str, String)> = vec![
-        ("OPENKOTO_PROVIDER", provider.clone()),
-        ("OPENKOTO_API_KEY", api_key.clone()),
-        ("OPENKOTO_MODEL", model.clone()),
-    ];
+/// 单词包（pack_id 为 "all" 时全部单词）按收藏顺序生成模板数据，以及所用模板的字段
+async fn vocabulary_export_items(
+    app_handle: &AppHandle,
+    pack_id: String,
+    template_id: Option<&str>,
+    kind: ExportTemplateKind,
+) -> Result<(Vec<serde_json::Value>, Vec<TemplateField>), AppError> {
+    let fields = match template_id {
+        Some(id) => load_export_template_of_kind(app_handle, id, kind)?.fields,
+        None => export_templates::default_template(kind).1,
+    };
+    let mut favorites =
+        list_favorite_vocabularies_by_pack_cmd(app_handle.clone(), pack_id, None).await?;
+    favorites.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    let items = favorites
+        .iter()
+        .map(export_templates::vocabulary_context)
+        .collect();
+    Ok((items, fields))
+}
 
-    if let Some(ref url) = base_url {
-        envs.push(("OPENKOTO_BASE_URL", url.clone()));
-    }
+/// 单词导出为 CSV，每列按模板生成（未指定模板时使用内置列），返回导出的单词数
+#[tauri::command]
+pub async fn export_vocabulary_csv_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    dest_path: String,
+    template_id: Option<String>,
+) -> Result<usize, AppError> {
+    let (items, fields) = vocabulary_export_items(
+        &app_handle,
+        pack_id,
+        template_id.as_deref(),
+        ExportTemplateKind::VocabularyCsv,
+    )
+    .await?;
+    let csv = export_templates::render_csv(&fields, &items).map_err(template_syntax_error)?;
+    // 带 BOM，Excel 打开时才能正确识别 UTF-8 中的中日文
+    std::fs::write(&dest_path, format!("\u{feff}{}", csv))
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(items.len())
+}
 
-    // 使用 PluginManager 获取执行命令
-    // 假设插件名称为 "openkoto-pdf-translator"
-    let plugin_name = "openkoto-pdf-translator";
+/// 单词导出为 Anki 文本导入文件，每个笔记字段按模板生成（未指定模板时为正面/背面/标签），返回导出的单词数
+#[tauri::command]
+pub async fn export_anki_notes_cmd(
+    app_handle: AppHandle,
+    pack_id: String,
+    dest_path: String,
+    template_id: Option<String>,
+) -> Result<usize, AppError> {
+    let (items, fields) = vocabulary_export_items(
+        &app_handle,
+        pack_id,
+        template_id.as_deref(),
+        ExportTemplateKind::AnkiNote,
+    )
+    .await?;
+    let notes =
+        export_templates::render_anki_notes(&fields, &items).map_err(template_syntax_error)?;
+    std::fs::write(&dest_path, notes).map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(items.len())
+}
 
-    let (cmd, mut args, plugin_dir) =
-        match plugin_manager::get_plugin_execution_command(&app_handle, plugin_name) {
-            Ok(res) => res,
-            Err(e) => return Err(format!("Plugin error: {}", e)),
-        };
+// ============================================================================
+// Corpus Counts - 收藏单词在已导入文章中的出现次数
+// ============================================================================
 
-    // 动态添加参数
-    // 我们约定 entry_point.args 包含固定前缀，如 ["-m", "openkoto_pdf_translator.pdf2zh"]
-    // 我们需要追加 PDF 相关的参数
-    // 原 args: -m openkoto_pdf_translator.pdf2zh [input] -li ...
+fn load_corpus(app_handle: &AppHandle) -> Result<corpus_counts::Corpus, String> {
+    let articles: Vec<Article> = load_all_articles(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<Article>(json).ok())
+        .collect();
+    Ok(corpus_counts::Corpus::new(&articles))
+}
 
-    args.push(pdf_path.clone());
-    args.push("-li".to_string());
-    args.push(lang_in);
-    args.push("-lo".to_string());
-    args.push(lang_out);
-    args.push("-s".to_string());
-    args.push("openkoto".to_string());
-    args.push("-o".to_string());
-    args.push(output_dir.clone());
+/// 文章导入或删除后增量更新收藏单词的出现次数；失败只记录日志，不影响导入和删除
+fn update_corpus_counts(app_handle: &AppHandle, article: &Article, removed: bool) {
+    let result = load_all_favorite_vocabularies_internal(app_handle).and_then(|mut favorites| {
+        for index in corpus_counts::apply_article(&mut favorites, article, removed) {
+            persist_favorite_vocabulary(app_handle, &favorites[index])?;
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("[CorpusCounts] Failed to update counts: {}", e);
+    }
+}
 
-    println!("[Plugin] Executing: {} {:?}", cmd, args);
-    println!("[Plugin] CWD: {:?}", plugin_dir);
+/// 新导入的文章：按自动标签规则加标签，并把文中的收藏单词计入出现次数
+fn finish_article_import(app_handle: &AppHandle, article: &mut Article) {
+    apply_import_tag_rules(app_handle, article);
+    update_corpus_counts(app_handle, article, false);
+}
 
-    // 在插件目录下执行，以确保 Python 模块导入正确 (如果是 Dev 模式)
-    // 或者对于 Prod 模式，通常也不影响
-    let result = Command::new(&cmd)
-        .args(&args)
-        .envs(envs.iter().map(|(k, v)| (*k, v.as_str())))
-        .current_dir(&plugin_dir) // 关键：设置工作目录为插件目录
-        .output();
+/// 按全部文章重新统计所有收藏单词的出现次数（文章内容编辑后或旧数据升级时使用），返回次数有变化的单词数
+#[tauri::command]
+pub async fn recount_vocabulary_corpus_cmd(app_handle: AppHandle) -> Result<usize, AppError> {
+    let corpus = load_corpus(&app_handle)?;
+    let mut updated = 0;
+    for mut favorite in load_all_favorite_vocabularies_internal(&app_handle)? {
+        let previous = (favorite.corpus_count, favorite.corpus_articles);
+        corpus.count_into(&mut favorite);
+        if (favorite.corpus_count, favorite.corpus_articles) != previous {
+            persist_favorite_vocabulary(&app_handle, &favorite)?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
 
-    match result {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
+// ============================================================================
+// Known Words - 已知词库与文章生词密度
+// ============================================================================
 
-            println!("[PDF Translate] stdout: {}", stdout);
-            if !stderr.is_empty() {
-                println!("[PDF Translate] stderr: {}", stderr);
-            }
+/// 复习间隔达到该天数的收藏词视为已掌握，统计生词密度时计为已知词
+pub const MASTERED_INTERVAL_DAYS: i32 = 21;
+
+/// 每个单词答对过的不同文章数
+pub fn correct_source_counts(encounters: &[VocabularyEncounter]) -> HashMap<String, usize> {
+    let mut sources: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for encounter in encounters.iter().filter(|e| e.correct) {
+        sources
+            .entry(&encounter.vocabulary_id)
+            .or_default()
+            .insert(&encounter.article_id);
+    }
+    sources
+        .into_iter()
+        .map(|(id, articles)| (id.to_string(), articles.len()))
+        .collect()
+}
 
-            if output.status.success() {
-                // 构建输出文件路径
-                let mono_path = format!("{}/{}-mono.pdf", output_dir, filename_stem);
-                let dual_path = format!("{}/{}-dual.pdf", output_dir, filename_stem);
+/// 收藏词是否视为已掌握：复习间隔达标，且设置了来源数要求时在足够多篇不同文章中答对过，
+/// 避免只记住了某张卡片的样子就被当作已知词
+pub fn is_vocabulary_mastered(
+    favorite: &FavoriteVocabulary,
+    correct_sources: &HashMap<String, usize>,
+    min_sources: Option<usize>,
+) -> bool {
+    favorite.srs_state == "review"
+        && favorite.interval_days >= MASTERED_INTERVAL_DAYS
+        && min_sources.is_none_or(|n| correct_sources.get(&favorite.id).copied().unwrap_or(0) >= n)
+}
 
-                Ok(serde_json::json!({
-                    "success": true,
-                    "mono_pdf": mono_path,
-                    "dual_pdf": dual_path,
-                    "original_pdf": pdf_path,
-                }))
-            } else {
-                Err(format!("PDF translation failed: {}", stderr))
-            }
+/// 批量标记已知词（已存在的词会被覆盖），返回保存的数量
+#[tauri::command]
+pub async fn add_known_words_cmd(
+    app_handle: AppHandle,
+    words: Vec<String>,
+    language: Option<String>,
+) -> Result<usize, AppError> {
+    let language = language.or_else(|| active_learning_language(&app_handle));
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut seen = HashSet::new();
+    for word in words {
+        let normalized = normalize_word(&word);
+        if normalized.is_empty() || !seen.insert(normalized.clone()) {
+            continue;
         }
-        Err(e) => Err(format!("Failed to execute plugin command '{}': {}", cmd, e)),
+        let known = KnownWord {
+            word: normalized.clone(),
+            language: language.clone(),
+            created_at: now.clone(),
+        };
+        let json = serde_json::to_string(&known)
+            .map_err(|e| format!("Failed to serialize known word: {}", e))?;
+        save_known_word(&app_handle, &normalized, &json)?;
     }
+    Ok(seen.len())
 }
 
-#[derive(serde::Serialize)]
-pub struct TranslationFiles {
-    pub mono_path: Option<String>,
-    pub dual_path: Option<String>,
+/// 列出已知词；指定 language 时只返回该学习语言（及未标记语言）的词
+#[tauri::command]
+pub async fn list_known_words_cmd(
+    app_handle: AppHandle,
+    language: Option<String>,
+) -> Result<Vec<KnownWord>, AppError> {
+    let mut words = load_known_words_internal(&app_handle)?;
+    words.retain(|w| matches_learning_language(w.language.as_deref(), language.as_deref()));
+    words.sort_by(|a, b| a.word.cmp(&b.word));
+    Ok(words)
 }
 
+/// 删除已知词
 #[tauri::command]
-pub async fn check_pdf_translation_files(pdf_path: String) -> Result<TranslationFiles, String> {
-    use std::path::Path;
-    let path = Path::new(&pdf_path);
-    if !path.exists() {
-        return Ok(TranslationFiles {
-            mono_path: None,
-            dual_path: None,
-        });
-    }
+pub async fn delete_known_word_cmd(app_handle: AppHandle, word: String) -> Result<(), AppError> {
+    delete_known_word(&app_handle, &normalize_word(&word))?;
+    Ok(())
+}
 
-    let parent = path.parent().unwrap_or(Path::new("."));
+fn load_known_words_internal(app_handle: &AppHandle) -> Result<Vec<KnownWord>, String> {
+    Ok(load_all_known_words(app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<KnownWord>(json).ok())
+        .collect())
+}
 
-    // Safety check: ensure file stem exists
-    let stem = match path.file_stem() {
-        Some(s) => s.to_string_lossy(),
-        None => {
-            return Ok(TranslationFiles {
-                mono_path: None,
-                dual_path: None,
-            })
-        }
-    };
+/// 逐段统计文章的生词数量和密度（已知词库 + 已掌握的收藏词），用于难度热力图
+#[tauri::command]
+pub async fn get_article_difficulty_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<ArticleDifficulty, AppError> {
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    if article.segments.is_empty() {
+        article.segments = create_segments_from_content(
+            &app_handle,
+            &article.id,
+            &article.content,
+            article.language.as_deref(),
+            article.segmentation.as_ref(),
+        );
+    }
+    let language = article.language.as_deref();
 
-    let mono_name = format!("{}-mono.pdf", stem);
-    let dual_name = format!("{}-dual.pdf", stem);
+    let known_words = load_known_words_internal(&app_handle)?
+        .into_iter()
+        .filter(|w| matches_learning_language(w.language.as_deref(), language))
+        .map(|w| w.word);
+    let min_sources = load_config(&app_handle)?.and_then(|c| c.known_min_sources);
+    let correct_sources = match min_sources {
+        Some(_) => correct_source_counts(
+            &load_all_vocabulary_encounters(&app_handle)?
+                .iter()
+                .filter_map(|json| serde_json::from_str::<VocabularyEncounter>(json).ok())
+                .collect::<Vec<_>>(),
+        ),
+        None => HashMap::new(),
+    };
+    let mastered = load_all_favorite_vocabularies_internal(&app_handle)?
+        .into_iter()
+        .filter(|fav| {
+            is_vocabulary_mastered(fav, &correct_sources, min_sources)
+                && matches_learning_language(fav.language.as_deref(), language)
+        })
+        .map(|fav| fav.word);
+    let known = KnownWordSet::new(known_words.chain(mastered));
 
-    let mono_path = parent.join(&mono_name);
-    let dual_path = parent.join(&dual_name);
+    let mut total_tokens = 0;
+    let mut total_unknown = 0;
+    let segments: Vec<SegmentDifficulty> = article
+        .segments
+        .iter()
+        .filter(|s| s.image_url.is_none())
+        .map(|s| {
+            let counts = count_words(&s.text, &known);
+            total_tokens += counts.tokens;
+            total_unknown += counts.unknown;
+            SegmentDifficulty {
+                segment_id: s.id.clone(),
+                order: s.order,
+                token_count: counts.tokens,
+                unknown_count: counts.unknown,
+                density: counts.density(),
+                score: (counts.density() * 100.0).round() as u32,
+            }
+        })
+        .collect();
 
-    Ok(TranslationFiles {
-        mono_path: if mono_path.exists() {
-            Some(mono_path.to_string_lossy().into_owned())
-        } else {
-            None
-        },
-        dual_path: if dual_path.exists() {
-            Some(dual_path.to_string_lossy().into_owned())
+    Ok(ArticleDifficulty {
+        article_id,
+        known_word_count: known.len(),
+        overall_density: if total_tokens == 0 {
+            0.0
         } else {
-            None
+            total_unknown as f64 / total_tokens as f64
         },
+        segments,
     })
 }
 
+/// 读前生词表默认和最多列出的词数
+const PRE_READING_DEFAULT_LIMIT: usize = 50;
+const PRE_READING_MAX_LIMIT: usize = 200;
+
+/// 每次请求释义的词数
+const PRE_READING_GLOSS_BATCH: usize = 30;
+
+/// 读前生词表：切分文章，去掉已知词和已收藏的词，按出现次数列出生词，
+/// 可选由 AI 给出简短释义，方便阅读难文前先预习词汇
 #[tauri::command]
-pub async fn export_file_cmd(src_path: String, dest_path: String) -> Result<(), String> {
-    std::fs::copy(&src_path, &dest_path).map_err(|e| format!("Failed to export file: {}", e))?;
-    Ok(())
+pub async fn get_pre_reading_words_cmd(
+    app_handle: AppHandle,
+    state: AppState<'_>,
+    article_id: String,
+    limit: Option<usize>,
+    with_glosses: Option<bool>,
+    target_language: Option<String>,
+) -> Result<PreReadingList, AppError> {
+    let mut article = get_article(app_handle.clone(), article_id.clone()).await?;
+    if article.segments.is_empty() {
+        article.segments = create_segments_from_content(
+            &app_handle,
+            &article.id,
+            &article.content,
+            article.language.as_deref(),
+            article.segmentation.as_ref(),
+        );
+    }
+    let language = article.language.as_deref();
+
+    let known_words = load_known_words_internal(&app_handle)?
+        .into_iter()
+        .filter(|w| matches_learning_language(w.language.as_deref(), language))
+        .map(|w| w.word);
+    // 收藏过的词不论是否掌握都已在学习中，不再列出
+    let favorites = load_all_favorite_vocabularies_internal(&app_handle)?
+        .into_iter()
+        .filter(|fav| matches_learning_language(fav.language.as_deref(), language))
+        .map(|fav| fav.word);
+    let known = KnownWordSet::new(known_words.chain(favorites));
+
+    let mut words = crate::word_density::pre_reading_words(&article.segments, &known);
+    let unknown_word_count = words.len();
+    words.truncate(
+        limit
+            .unwrap_or(PRE_READING_DEFAULT_LIMIT)
+            .clamp(1, PRE_READING_MAX_LIMIT),
+    );
+
+    if with_glosses.unwrap_or(false) && !words.is_empty() {
+        let target_language = match target_language.filter(|l| !l.trim().is_empty()) {
+            Some(language) => language,
+            None => load_config(&app_handle)?
+                .map(|config| config.target_language)
+                .unwrap_or_else(|| "zh-CN".to_string()),
+        };
+        let ai_service = get_ai_service_for_task(&state, AiTask::BatchTranslation).await?;
+        for (batch_index, batch) in words.chunks_mut(PRE_READING_GLOSS_BATCH).enumerate() {
+            let items: Vec<(String, String)> = batch
+                .iter()
+                .enumerate()
+                .map(|(index, word)| (index.to_string(), word.word.clone()))
+                .collect();
+            // 释义只是预习辅助，某一批失败时跳过，其余照常返回
+            match ai_service
+                .batch_translate(
+                    items,
+                    language,
+                    &target_language,
+                    &[],
+                    TranslationQuality::Fast,
+                )
+                .await
+            {
+                Ok(glosses) => {
+                    for (id, gloss) in glosses {
+                        if let Some(word) = id.parse::<usize>().ok().and_then(|i| batch.get_mut(i))
+                        {
+                            word.gloss = Some(gloss.trim().to_string()).filter(|g| !g.is_empty());
+                        }
+                    }
+                }
+                Err(e) => eprintln!("[PreReading] Gloss batch {} failed: {}", batch_index + 1, e),
+            }
+        }
+    }
+
+    Ok(PreReadingList {
+        article_id,
+        known_word_count: known.len(),
+        unknown_word_count,
+        words,
+    })
 }
 
 // ============================================================================
-// Bookmarks Commands - 书签命令
+// Article Recap Commands - 文章学习总结卡片
 // ============================================================================
 
-/// 添加书签
+/// 重读间隔（天），依次使用，之后保持最后一个间隔
+const RECAP_REREAD_INTERVALS: [i64; 5] = [1, 3, 7, 14, 30];
+
+fn next_reread_date(from: chrono::NaiveDate, reread_count: i32) -> String {
+    let index = (reread_count.max(0) as usize).min(RECAP_REREAD_INTERVALS.len() - 1);
+    (from + chrono::Duration::days(RECAP_REREAD_INTERVALS[index]))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn load_article_recap_internal(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Option<ArticleRecap>, String> {
+    load_article_recap(app_handle, article_id)?
+        .map(|json| {
+            serde_json::from_str::<ArticleRecap>(&json)
+                .map_err(|e| format!("Failed to parse article recap: {}", e))
+        })
+        .transpose()
+}
+
+fn persist_article_recap(app_handle: &AppHandle, recap: &ArticleRecap) -> Result<(), String> {
+    let json = serde_json::to_string(recap)
+        .map_err(|e| format!("Failed to serialize article recap: {}", e))?;
+    save_article_recap(app_handle, &recap.article_id, &json)
+}
+
+/// 汇总文章的收藏单词、语法点和 AI 摘要，生成总结卡片并随文章保存
+/// 重新生成时保留已有的重读进度
 #[tauri::command]
-pub async fn add_bookmark_cmd(
+pub async fn generate_article_recap_cmd(
     app_handle: AppHandle,
-    book_path: String,
-    book_type: String,
-    title: String,
-    note: Option<String>,
-    selected_text: Option<String>,
-    page_number: Option<i32>,
-    epub_cfi: Option<String>,
-    color: Option<String>,
-) -> Result<Bookmark, String> {
-    let bookmark = Bookmark {
-        id: Uuid::new_v4().to_string(),
-        book_path,
-        book_type,
-        title,
-        note,
-        selected_text,
-        page_number,
-        epub_cfi,
+    state: AppState<'_>,
+    article_id: String,
+    date_local: String,
+) -> Result<ArticleRecap, AppError> {
+    let today = parse_local_date(&date_local).map_err(AppError::invalid_input)?;
+    let article = get_article(app_handle.clone(), article_id.clone()).await?;
+
+    let vocabulary = load_all_favorite_vocabularies(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteVocabulary>(json).ok())
+        .filter(|fav| fav.source_article_id.as_deref() == Some(article_id.as_str()))
+        .map(|fav| VocabularyItem {
+            word: fav.word,
+            meaning: fav.meaning,
+            usage: fav.usage,
+            example: fav.example,
+            reading: fav.reading,
+        })
+        .collect();
+
+    let grammar_points = load_all_favorite_grammars(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<FavoriteGrammar>(json).ok())
+        .filter(|fav| fav.source_article_id.as_deref() == Some(article_id.as_str()))
+        .map(|fav| GrammarPoint {
+            point: fav.point,
+            explanation: fav.explanation,
+            example: fav.example,
+        })
+        .collect();
+
+    let ai_service = get_ai_service(&state).await?;
+    let summary = ai_service
+        .analyze(AnalysisRequest {
+            text: article.content,
+            analysis_type: AnalysisType::Summary,
+        })
+        .await
+        .map_err(AppError::ai_provider)?
+        .result;
+
+    let existing = load_article_recap_internal(&app_handle, &article_id)?;
+    let recap = ArticleRecap {
+        article_id,
+        article_title: article.title,
+        summary,
+        vocabulary,
+        grammar_points,
         created_at: chrono::Utc::now().to_rfc3339(),
-        color,
+        reread_count: existing.as_ref().map(|r| r.reread_count).unwrap_or(0),
+        next_reread_date: existing
+            .map(|r| r.next_reread_date)
+            .unwrap_or_else(|| next_reread_date(today, 0)),
     };
 
-    let json = serde_json::to_string(&bookmark)
-        .map_err(|e| format!("Failed to serialize bookmark: {}", e))?;
-    save_bookmark(&app_handle, &bookmark.id, &json)?;
-
-    Ok(bookmark)
+    persist_article_recap(&app_handle, &recap)?;
+    Ok(recap)
 }
 
-/// 列出所有书签
+/// 获取文章的总结卡片，未生成时返回 None
 #[tauri::command]
-pub async fn list_bookmarks_cmd(app_handle: AppHandle) -> Result<Vec<Bookmark>, String> {
-    let ids = list_bookmarks(&app_handle)?;
-    let mut bookmarks = Vec::new();
-
-    for id in ids {
-        if let Ok(json) = load_bookmark(&app_handle, &id) {
-            if let Ok(bookmark) = serde_json::from_str::<Bookmark>(&json) {
-                bookmarks.push(bookmark);
-            }
-        }
-    }
+pub async fn get_article_recap_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+) -> Result<Option<ArticleRecap>, AppError> {
+    Ok(load_article_recap_internal(&app_handle, &article_id)?)
+}
 
-    // 按创建时间降序排列
-    bookmarks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+/// 列出到期需要重读的总结卡片（按到期日期排序）
+#[tauri::command]
+pub async fn list_due_article_recaps_cmd(
+    app_handle: AppHandle,
+    date_local: String,
+) -> Result<Vec<ArticleRecap>, AppError> {
+    let today = parse_local_date(&date_local).map_err(AppError::invalid_input)?;
+    let mut recaps: Vec<ArticleRecap> = load_all_article_recaps(&app_handle)?
+        .iter()
+        .filter_map(|json| serde_json::from_str::<ArticleRecap>(json).ok())
+        .filter(|recap| {
+            parse_local_date(&recap.next_reread_date)
+                .map(|due| due <= today)
+                .unwrap_or(true)
+        })
+        .collect();
 
-    Ok(bookmarks)
+    recaps.sort_by(|a, b| a.next_reread_date.cmp(&b.next_reread_date));
+    Ok(recaps)
 }
 
-/// 列出指定书籍的书签
+/// 完成一次重读，安排下次重读日期
 #[tauri::command]
-pub async fn list_bookmarks_for_book_cmd(
+pub async fn mark_article_recap_reread_cmd(
     app_handle: AppHandle,
-    book_path: String,
-) -> Result<Vec<Bookmark>, String> {
-    let ids = list_bookmarks_for_book(&app_handle, &book_path)?;
-    let mut bookmarks = Vec::new();
+    article_id: String,
+    date_local: String,
+) -> Result<ArticleRecap, AppError> {
+    let today = parse_local_date(&date_local).map_err(AppError::invalid_input)?;
+    let mut recap = load_article_recap_internal(&app_handle, &article_id)?
+        .ok_or_else(|| AppError::not_found(tr("error.recap_not_found")))?;
+
+    recap.reread_count += 1;
+    recap.next_reread_date = next_reread_date(today, recap.reread_count);
+    persist_article_recap(&app_handle, &recap)?;
+    Ok(recap)
+}
 
-    for id in ids {
-        if let Ok(json) = load_bookmark(&app_handle, &id) {
-            if let Ok(bookmark) = serde_json::from_str::<Bookmark>(&json) {
-                bookmarks.push(bookmark);
-            }
-        }
-    }
+/// 导出总结卡片：.md 导出 Markdown，其他扩展名导出可直接打印为 PDF 的 HTML
+#[tauri::command]
+pub async fn export_article_recap_cmd(
+    app_handle: AppHandle,
+    article_id: String,
+    dest_path: String,
+) -> Result<(), AppError> {
+    let recap = load_article_recap_internal(&app_handle, &article_id)?
+        .ok_or_else(|| AppError::not_found(tr("error.recap_not_found")))?;
 
-    // 按创建时间降序排列
-    bookmarks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let rendered = if dest_path.to_lowercase().ends_with(".md") {
+        render_article_recap_markdown(&recap)
+    } else {
+        render_article_recap_html(&recap)
+    };
 
-    Ok(bookmarks)
+    std::fs::write(&dest_path, rendered).map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(())
 }
 
-/// 更新书签
+/// 把文章所有分段讲解中的词汇和语法点导出为 Anki 可导入的卡片文件，返回卡片数
+/// deck_name 未指定时使用 "OpenKoto::文章标题"
 #[tauri::command]
-pub async fn update_bookmark_cmd(
+pub async fn export_article_anki_cmd(
     app_handle: AppHandle,
-    id: String,
-    title: Option<String>,
-    note: Option<String>,
-    color: Option<String>,
-) -> Result<Bookmark, String> {
-    let json = load_bookmark(&app_handle, &id)?;
-    let mut bookmark: Bookmark =
-        serde_json::from_str(&json).map_err(|e| format!("Failed to parse bookmark: {}", e))?;
+    article_id: String,
+    dest_path: String,
+    deck_name: Option<String>,
+) -> Result<usize, AppError> {
+    let article_json = load_article(&app_handle, &article_id)?;
+    let article: Article = serde_json::from_str(&article_json)
+        .map_err(|e| format!("Failed to parse article: {}", e))?;
 
-    if let Some(t) = title {
-        bookmark.title = t;
+    let cards = anki_export::article_cards(&article);
+    if cards.is_empty() {
+        return Err(AppError::invalid_input(tr(
+            "error.no_explanations_to_export",
+        )));
     }
-    if let Some(n) = note {
-        bookmark.note = Some(n);
-    }
-    if let Some(c) = color {
-        bookmark.color = Some(c);
+    let deck = deck_name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| anki_export::default_deck_name(&article));
+
+    std::fs::write(&dest_path, anki_export::render_anki_tsv(&cards, &deck))
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(cards.len())
+}
+
+fn render_article_recap_markdown(recap: &ArticleRecap) -> String {
+    let mut out = format!("# {}\n\n{}\n", recap.article_title, recap.summary.trim());
+
+    if !recap.vocabulary.is_empty() {
+        out.push_str("\n## Vocabulary\n\n");
+        for item in &recap.vocabulary {
+            match item.reading.as_deref().filter(|r| !r.is_empty()) {
+                Some(reading) => out.push_str(&format!(
+                    "- **{}** ({}) — {}\n",
+                    item.word, reading, item.meaning
+                )),
+                None => out.push_str(&format!("- **{}** — {}\n", item.word, item.meaning)),
+            }
+        }
     }
 
-    let updated_json = serde_json::to_string(&bookmark)
-        .map_err(|e| format!("Failed to serialize bookmark: {}", e))?;
-    save_bookmark(&app_handle, &id, &updated_json)?;
+    if !recap.grammar_points.is_empty() {
+        out.push_str("\n## Grammar\n\n");
+        for point in &recap.grammar_points {
+            out.push_str(&format!("- **{}** — {}\n", point.point, point.explanation));
+            if let Some(example) = point.example.as_deref().filter(|e| !e.is_empty()) {
+                out.push_str(&format!("  - {}\n", example));
+            }
+        }
+    }
 
-    Ok(bookmark)
+    out
 }
 
-/// 删除书签
-#[tauri::command]
-pub async fn delete_bookmark_cmd(app_handle: AppHandle, id: String) -> Result<(), String> {
-    delete_bookmark(&app_handle, &id)?;
-    Ok(())
+fn render_article_recap_html(recap: &ArticleRecap) -> String {
+    use html_escape::encode_text;
+
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"summary\">{}</p>\n",
+        encode_text(&recap.article_title),
+        encode_text(recap.summary.trim()).replace('\n', "<br>")
+    );
+
+    if !recap.vocabulary.is_empty() {
+        body.push_str("<h2>Vocabulary</h2>\n<table>\n");
+        for item in &recap.vocabulary {
+            body.push_str(&format!(
+                "<tr><td class=\"word\">{}</td><td class=\"reading\">{}</td><td>{}</td></tr>\n",
+                encode_text(&item.word),
+                encode_text(item.reading.as_deref().unwrap_or("")),
+                encode_text(&item.meaning)
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    if !recap.grammar_points.is_empty() {
+        body.push_str("<h2>Grammar</h2>\n<ul>\n");
+        for point in &recap.grammar_points {
+            body.push_str(&format!(
+                "<li><strong>{}</strong> — {}",
+                encode_text(&point.point),
+                encode_text(&point.explanation)
+            ));
+            if let Some(example) = point.example.as_deref().filter(|e| !e.is_empty()) {
+                body.push_str(&format!("<br><em>{}</em>", encode_text(example)));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+body {{ font-family: -apple-system, "Hiragino Sans", "PingFang SC", sans-serif; max-width: 720px; margin: 40px auto; padding: 0 24px; color: #222; }}
+.summary {{ line-height: 1.7; }}
+table {{ border-collapse: collapse; width: 100%; }}
+td {{ border-bottom: 1px solid #eee; padding: 6px 8px; vertical-align: top; }}
+.word {{ font-weight: 600; white-space: nowrap; }}
+.reading {{ color: #888; white-space: nowrap; }}
+li {{ margin-bottom: 8px; line-height: 1.6; }}
+</style>
+</head>
+<body>
+{}</body>
+</html>
+"#,
+        encode_text(&recap.article_title),
+        body
+    )
 }
@@ -0,0 +1,128 @@
+// 语料出现次数：统计每个收藏单词在已导入文章中出现的次数和篇数，复习时优先经常遇到的词
+// 导入文章时把该文的次数累加到收藏上、删除文章时减去；新收藏的单词扫描全部文章。
+// 按字面形式匹配（忽略大小写），变位、变格后的形式不计入
+
+use std::collections::HashMap;
+
+use crate::types::{Article, FavoriteVocabulary};
+
+/// 中日韩文字不以空格分词，匹配时不检查词边界
+fn is_cjk(c: char) -> bool {
+    c >= '\u{2e80}'
+}
+
+/// 可按空格分词的单词字符
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !is_cjk(c)
+}
+
+/// 一篇文章的索引：单个单词直接查词频表，词组和中日文在小写正文中查找
+pub struct CorpusText {
+    lower: String,
+    tokens: HashMap<String, u32>,
+}
+
+impl CorpusText {
+    pub fn new(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        let mut tokens: HashMap<String, u32> = HashMap::new();
+        for token in lower.split(|c: char| !is_word_char(c)) {
+            if !token.is_empty() {
+                *tokens.entry(token.to_string()).or_default() += 1;
+            }
+        }
+        Self { lower, tokens }
+    }
+
+    /// 单词在文中出现的次数；有空格分词的语言要求前后是词边界（"cat" 不计入 "category"）
+    pub fn count(&self, word: &str) -> u32 {
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            return 0;
+        }
+        if word.chars().all(is_word_char) {
+            return self.tokens.get(&word).copied().unwrap_or(0);
+        }
+
+        let check_before = word.chars().next().is_some_and(|c| !is_cjk(c));
+        let check_after = word.chars().next_back().is_some_and(|c| !is_cjk(c));
+        self.lower
+            .match_indices(&word)
+            .filter(|(start, _)| {
+                let before = self.lower[..*start].chars().next_back();
+                let after = self.lower[start + word.len()..].chars().next();
+                let joined_before = check_before && before.is_some_and(is_word_char);
+                let joined_after = check_after && after.is_some_and(is_word_char);
+                !joined_before && !joined_after
+            })
+            .count() as u32
+    }
+}
+
+/// 单词的学习语言与文章语言一致（任一方未标记时视为一致）才统计
+fn same_language(word_language: Option<&str>, article_language: Option<&str>) -> bool {
+    match (word_language, article_language) {
+        (Some(word), Some(article)) => word.eq_ignore_ascii_case(article),
+        _ => true,
+    }
+}
+
+/// 把一篇文章中的出现次数加到收藏上（removed 为 true 时减去），返回次数有变化的收藏下标
+pub fn apply_article(
+    favorites: &mut [FavoriteVocabulary],
+    article: &Article,
+    removed: bool,
+) -> Vec<usize> {
+    let text = CorpusText::new(&article.content);
+    let mut changed = Vec::new();
+    for (index, favorite) in favorites.iter_mut().enumerate() {
+        if !same_language(favorite.language.as_deref(), article.language.as_deref()) {
+            continue;
+        }
+        let count = text.count(&favorite.word);
+        if count == 0 {
+            continue;
+        }
+        if removed {
+            favorite.corpus_count = favorite.corpus_count.saturating_sub(count);
+            favorite.corpus_articles = favorite.corpus_articles.saturating_sub(1);
+        } else {
+            favorite.corpus_count += count;
+            favorite.corpus_articles += 1;
+        }
+        changed.push(index);
+    }
+    changed
+}
+
+/// 全部文章的索引，用于给新收藏的单词或全部收藏重新统计
+pub struct Corpus {
+    articles: Vec<(Option<String>, CorpusText)>,
+}
+
+impl Corpus {
+    pub fn new(articles: &[Article]) -> Self {
+        Self {
+            articles: articles
+                .iter()
+                .map(|article| (article.language.clone(), CorpusText::new(&article.content)))
+                .collect(),
+        }
+    }
+
+    /// 重新统计单词的出现次数和篇数，覆盖已有的值
+    pub fn count_into(&self, favorite: &mut FavoriteVocabulary) {
+        favorite.corpus_count = 0;
+        favorite.corpus_articles = 0;
+        for (language, text) in &self.articles {
+            if !same_language(favorite.language.as_deref(), language.as_deref()) {
+                continue;
+            }
+            let count = text.count(&favorite.word);
+            if count > 0 {
+                favorite.corpus_count += count;
+                favorite.corpus_articles += 1;
+            }
+        }
+    }
+}
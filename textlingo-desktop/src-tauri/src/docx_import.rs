@@ -0,0 +1,177 @@
+// Word 文档（.docx）导入：docx 是 zip 包，正文在 word/document.xml
+// 逐段落提取文字；标题、列表和引用样式转换为与 Markdown 导入相同的块结构，阅读器按同样方式渲染
+
+use crate::markdown_import::{MarkdownBlock, MarkdownDocument};
+use crate::types::SegmentBlock;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+fn read_entry(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut text = String::new();
+    entry.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = attrs[start..].find('"')?;
+    Some(&attrs[start..start + end])
+}
+
+/// 解析 styles.xml：样式 id -> 样式名（中文版 Word 的样式 id 可能是数字，名称仍是 heading 1 等）
+pub fn parse_style_names(styles_xml: &str) -> HashMap<String, String> {
+    let re_style = Regex::new(r#"(?s)<w:style\b([^>]*)>(.*?)</w:style>"#).unwrap();
+    let re_name = Regex::new(r#"<w:name\b([^>]*)/?>"#).unwrap();
+    re_style
+        .captures_iter(styles_xml)
+        .filter_map(|caps| {
+            let id = attr(&caps[1], "w:styleId")?.to_string();
+            let name_caps = re_name.captures(&caps[2])?;
+            let name = attr(&name_caps[1], "w:val")?.to_string();
+            Some((id, name))
+        })
+        .collect()
+}
+
+/// 根据段落样式和列表信息确定块类型；返回 (块类型, 是否为文档标题样式)
+fn block_kind(style: Option<&str>, list_depth: Option<u8>) -> (Option<SegmentBlock>, bool) {
+    let style = style
+        .map(|s| s.to_lowercase().replace(' ', ""))
+        .unwrap_or_default();
+    if style == "title" {
+        return (Some(SegmentBlock::Heading { level: 1 }), true);
+    }
+    for prefix in ["heading", "标题", "見出し"] {
+        if let Some(level) = style
+            .strip_prefix(prefix)
+            .and_then(|l| l.parse::<u8>().ok())
+        {
+            return (
+                Some(SegmentBlock::Heading {
+                    level: level.clamp(1, 6),
+                }),
+                false,
+            );
+        }
+    }
+    if style.contains("quote") {
+        return (Some(SegmentBlock::Quote), false);
+    }
+    if let Some(depth) = list_depth {
+        return (
+            Some(SegmentBlock::ListItem {
+                ordered: false,
+                depth,
+            }),
+            false,
+        );
+    }
+    if style.contains("list") {
+        return (
+            Some(SegmentBlock::ListItem {
+                ordered: false,
+                depth: 0,
+            }),
+            false,
+        );
+    }
+    (None, false)
+}
+
+/// 解析 document.xml，返回 (标题样式段落, 段落块)
+pub fn parse_document_xml(
+    xml: &str,
+    style_names: &HashMap<String, String>,
+) -> (Option<String>, Vec<MarkdownBlock>) {
+    let re_token =
+        Regex::new(r#"<(/?)([A-Za-z0-9_:]+)((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>|([^<]+)"#).unwrap();
+
+    let mut title = None;
+    let mut blocks = Vec::new();
+    let mut text = String::new();
+    let mut style: Option<String> = None;
+    let mut list_depth: Option<u8> = None;
+    let mut in_text = false;
+
+    for caps in re_token.captures_iter(xml) {
+        if let Some(chars) = caps.get(5) {
+            if in_text {
+                text.push_str(&html_escape::decode_html_entities(chars.as_str()));
+            }
+            continue;
+        }
+        let closing = !caps[1].is_empty();
+        let self_closing = !caps[4].is_empty();
+        let attrs = &caps[3];
+        match (&caps[2], closing) {
+            ("w:p", false) if !self_closing => {
+                text.clear();
+                style = None;
+                list_depth = None;
+            }
+            ("w:pStyle", false) => {
+                style = attr(attrs, "w:val")
+                    .map(|id| style_names.get(id).map_or(id, String::as_str).to_string());
+            }
+            ("w:numPr", false) => list_depth = Some(list_depth.unwrap_or(0)),
+            ("w:ilvl", false) => {
+                list_depth = attr(attrs, "w:val")
+                    .and_then(|v| v.parse().ok())
+                    .or(Some(0));
+            }
+            ("w:t", false) => in_text = !self_closing,
+            ("w:t", true) => in_text = false,
+            ("w:tab", false) | ("w:br", false) | ("w:cr", false) => text.push(' '),
+            ("w:p", true) => {
+                let paragraph = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                text.clear();
+                if paragraph.is_empty() {
+                    continue;
+                }
+                let (kind, is_title) = block_kind(style.as_deref(), list_depth);
+                if is_title && title.is_none() && blocks.is_empty() {
+                    title = Some(paragraph);
+                    continue;
+                }
+                blocks.push(MarkdownBlock {
+                    kind,
+                    text: paragraph,
+                    image_url: None,
+                });
+            }
+            _ => {}
+        }
+    }
+    (title, blocks)
+}
+
+/// 读取 docx 文件内容；标题依次取文档属性中的标题、标题样式段落、开头的一级标题
+pub fn parse_docx(bytes: &[u8]) -> Result<MarkdownDocument, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid DOCX file: {}", e))?;
+    let document_xml = read_entry(&mut archive, "word/document.xml")
+        .ok_or_else(|| "Invalid DOCX file: word/document.xml is missing".to_string())?;
+    let style_names = read_entry(&mut archive, "word/styles.xml")
+        .map(|xml| parse_style_names(&xml))
+        .unwrap_or_default();
+    let core_title = read_entry(&mut archive, "docProps/core.xml").and_then(|xml| {
+        let re_title = Regex::new(r"(?s)<dc:title>(.*?)</dc:title>").unwrap();
+        let title = html_escape::decode_html_entities(re_title.captures(&xml)?.get(1)?.as_str())
+            .trim()
+            .to_string();
+        Some(title).filter(|t| !t.is_empty())
+    });
+
+    let (styled_title, mut blocks) = parse_document_xml(&document_xml, &style_names);
+    let mut title = core_title.or(styled_title);
+    if let Some(first) = blocks.first() {
+        if first.kind == Some(SegmentBlock::Heading { level: 1 })
+            && title.as_deref().is_none_or(|t| t == first.text)
+        {
+            title = Some(blocks.remove(0).text);
+        }
+    }
+    Ok(MarkdownDocument { title, blocks })
+}
@@ -0,0 +1,130 @@
+// 内嵌字幕：本地视频（MKV、MP4 等）常自带字幕轨，用 FFmpeg 探测后可直接提取为文章段落，
+// 比调用 AI 转录免费且即时完成。图形字幕（PGS、VobSub）需要 OCR，只列出不提取
+
+use std::path::Path;
+
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::i18n::{tr, tr_with};
+use crate::types::EmbeddedSubtitleTrack;
+
+/// 可以转换为 SRT 的文本字幕编码
+const TEXT_CODECS: &[&str] = &["mov_text", "subrip", "srt", "ass", "ssa", "webvtt", "text"];
+
+/// 解析 `ffmpeg -i` 输出中的字幕流，如
+/// `Stream #0:2(eng): Subtitle: mov_text (tx3g / 0x67337874), 0 kb/s (default)`，
+/// 之后缩进的 Metadata 中的 title 作为轨道名称
+pub fn parse_subtitle_streams(stderr: &str) -> Vec<EmbeddedSubtitleTrack> {
+    let mut tracks: Vec<EmbeddedSubtitleTrack> = Vec::new();
+    // 当前所在的流是否为字幕流，只有字幕流的 Metadata 归入上一条轨道
+    let mut in_subtitle = false;
+    for line in stderr.lines() {
+        let trimmed = line.trim();
+        if let Some(stream) = trimmed.strip_prefix("Stream #") {
+            in_subtitle = false;
+            let Some((id, description)) = stream.split_once(": Subtitle: ") else {
+                continue;
+            };
+            // id 形如 0:2、0:2(eng)、0:3[0x1100](jpn)
+            let index = id
+                .split_once(':')
+                .map(|(_, rest)| rest)
+                .unwrap_or_default()
+                .split(['(', '['])
+                .next()
+                .and_then(|index| index.parse::<u32>().ok());
+            let Some(stream_index) = index else {
+                continue;
+            };
+            let language = id
+                .split_once('(')
+                .and_then(|(_, rest)| rest.split_once(')'))
+                .map(|(language, _)| language.to_string())
+                .filter(|language| !language.is_empty() && language != "und");
+            let codec = description
+                .split([' ', ','])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            tracks.push(EmbeddedSubtitleTrack {
+                stream_index,
+                language,
+                text_based: TEXT_CODECS.contains(&codec.as_str()),
+                codec,
+                title: None,
+                default: description.contains("(default)"),
+                forced: description.contains("(forced)"),
+            });
+            in_subtitle = true;
+        } else if in_subtitle {
+            if let Some((key, value)) = trimmed.split_once(':') {
+                if key.trim() == "title" && !value.trim().is_empty() {
+                    if let Some(track) = tracks.last_mut() {
+                        track.title = Some(value.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+    tracks
+}
+
+/// 用 FFmpeg 探测视频中的字幕轨
+pub async fn probe(
+    app: &AppHandle,
+    video_path: &Path,
+) -> Result<Vec<EmbeddedSubtitleTrack>, String> {
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    // 只给输入文件时 FFmpeg 打印流信息后以非 0 状态退出，直接解析 stderr
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
+        .args(["-hide_banner", "-i", video_path_str])
+        .output()
+        .await
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
+    Ok(parse_subtitle_streams(&String::from_utf8_lossy(
+        &output.stderr,
+    )))
+}
+
+/// 把指定的字幕轨转换为 SRT 文本
+pub async fn extract_srt(
+    app: &AppHandle,
+    video_path: &Path,
+    stream_index: u32,
+) -> Result<String, String> {
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
+        .args([
+            "-hide_banner",
+            "-i",
+            video_path_str,
+            "-map",
+            &format!("0:{}", stream_index),
+            "-f",
+            "srt",
+            "pipe:1",
+        ])
+        .output()
+        .await
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(tr_with(
+            "error.embedded_subtitle_extract_failed",
+            &[("error", stderr.to_string())],
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
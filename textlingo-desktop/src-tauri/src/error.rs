@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 错误类别，前端据此决定提示文案和是否提供重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// 未配置模型或 API Key
+    MissingApiKey,
+    /// 网络不可用、请求超时等
+    Network,
+    /// 服务商限流 (HTTP 429)
+    RateLimited,
+    /// AI 服务商返回错误或无法解析的响应
+    AiProvider,
+    /// 文章、收藏、文件等不存在
+    NotFound,
+    /// 参数不合法
+    InvalidInput,
+    /// 配置文件损坏或配置缺失
+    Config,
+    /// 数据库读写失败
+    Storage,
+    /// 本地文件读写失败
+    Io,
+    /// FFmpeg / yt-dlp / 插件等外部程序失败
+    ExternalTool,
+    /// 用户取消了任务
+    Cancelled,
+    /// 离线模式下调用了需要 AI 或网络的功能
+    Offline,
+    /// 其他未分类错误
+    Internal,
+}
+
+impl ErrorKind {
+    /// 该类错误通常是暂时性的，重试可能成功
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Network | ErrorKind::RateLimited | ErrorKind::AiProvider
+        )
+    }
+}
+
+/// 所有 Tauri 命令统一返回的错误类型
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub retryable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retryable: kind.is_retryable(),
+            details: None,
+        }
+    }
+
+    pub fn missing_api_key(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::MissingApiKey, message)
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    /// AI 服务返回的错误；jobs::rate_limit_error 组装的限流错误归为 RateLimited
+    pub fn ai_provider(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let kind = if crate::jobs::is_rate_limit_error(&message) {
+            ErrorKind::RateLimited
+        } else {
+            ErrorKind::AiProvider
+        };
+        Self::new(kind, message)
+    }
+
+    pub fn external_tool(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::ExternalTool, message)
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Cancelled, message)
+    }
+
+    pub fn offline(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Offline, message)
+    }
+
+    /// 附加结构化的错误上下文（如文件路径、HTTP 状态码）
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// 内部模块返回的字符串错误：类别未知，归为 Internal。
+/// 已知类别的错误应在出错处用 `AppError::not_found`、`AppError::network` 等构造
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+            _ => ErrorKind::Io,
+        };
+        Self::new(kind, error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::new(ErrorKind::InvalidInput, error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        let kind = match error.status() {
+            Some(status) if status.as_u16() == 429 => ErrorKind::RateLimited,
+            Some(_) => ErrorKind::AiProvider,
+            None => ErrorKind::Network,
+        };
+        Self::new(kind, error.to_string())
+    }
+}
@@ -0,0 +1,537 @@
+// 导出模板：handlebars 风格的小型模板引擎，用于文章 Markdown 导出、单词 CSV 列和 Anki 笔记字段
+// 语法: {{变量}}、{{a.b}}、{{json 变量}}、{{#if 变量}}…{{else}}…{{/if}}、{{#unless}}、{{#each 列表}}…{{/each}}、{{! 注释}}
+// 循环内用 {{this}} 和 {{@index}}，当前项没有的字段向外层查找；单独占一行的块标签连同换行一起去掉
+
+use serde_json::{json, Value};
+
+use crate::types::{
+    Article, Bookmark, ExportTemplateKind, FavoriteGrammar, FavoriteVocabulary, TemplateField,
+};
+
+#[derive(Debug, Clone)]
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Value {
+        helper: Option<Helper>,
+        path: String,
+    },
+    If {
+        path: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        path: String,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Helper {
+    /// 输出 JSON 字符串（带引号和转义），用于 YAML frontmatter
+    Json,
+    Upper,
+    Lower,
+}
+
+/// 解析后的模板
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "Unclosed {{ tag".to_string())?;
+        tokens.push(Token::Tag(after[..end].trim().to_string()));
+        rest = &after[end + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with(['#', '/', '!']) || tag == "else"
+}
+
+/// 单独占一行的块标签：去掉所在行前面的缩进和后面的换行
+/// 先按原文判断每个标签，再统一裁剪（相邻两行的块标签会裁剪同一段文本）
+fn strip_standalone(tokens: &mut [Token]) {
+    // 每段文本保留的范围
+    let mut keep: Vec<(usize, usize)> = tokens
+        .iter()
+        .map(|t| match t {
+            Token::Text(text) => (0, text.len()),
+            Token::Tag(_) => (0, 0),
+        })
+        .collect();
+    for i in 0..tokens.len() {
+        if !matches!(&tokens[i], Token::Tag(tag) if is_block_tag(tag)) {
+            continue;
+        }
+        let line_start = match i.checked_sub(1).map(|p| &tokens[p]) {
+            None => Some(0),
+            Some(Token::Text(text)) => {
+                let from = text.rfind('\n').map_or(0, |p| p + 1);
+                let blank = text[from..].chars().all(|c| c == ' ' || c == '\t');
+                (blank && (from > 0 || i == 1)).then_some(from)
+            }
+            Some(Token::Tag(_)) => None,
+        };
+        let line_end = match tokens.get(i + 1) {
+            None => Some(0),
+            Some(Token::Text(text)) => {
+                let to = text.find('\n').map_or(text.len(), |p| p + 1);
+                let blank = text[..to].trim().is_empty();
+                (blank && (text.contains('\n') || i + 2 == tokens.len())).then_some(to)
+            }
+            Some(Token::Tag(_)) => None,
+        };
+        if let (Some(from), Some(to)) = (line_start, line_end) {
+            if let Some(prev) = i.checked_sub(1) {
+                keep[prev].1 = from;
+            }
+            if let Some(next) = keep.get_mut(i + 1) {
+                next.0 = to;
+            }
+        }
+    }
+    for (token, (start, end)) in tokens.iter_mut().zip(keep) {
+        if let Token::Text(text) = token {
+            *text = text
+                .get(start..end.max(start))
+                .unwrap_or_default()
+                .to_string();
+        }
+    }
+}
+
+fn parse_nodes(
+    tokens: &mut std::vec::IntoIter<Token>,
+    open: Option<&str>,
+) -> Result<(Vec<Node>, Vec<Node>), String> {
+    let mut main = Vec::new();
+    let mut otherwise = Vec::new();
+    let mut in_else = false;
+    while let Some(token) = tokens.next() {
+        let target = if in_else { &mut otherwise } else { &mut main };
+        let tag = match token {
+            Token::Text(text) => {
+                target.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+        if tag.starts_with('!') {
+            continue;
+        }
+        if tag == "else" {
+            if open.is_none() || in_else {
+                return Err("Unexpected {{else}}".to_string());
+            }
+            in_else = true;
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            return if Some(name) == open {
+                Ok((main, otherwise))
+            } else {
+                Err(format!("Unexpected {{{{/{}}}}}", name))
+            };
+        }
+        if let Some(block) = tag.strip_prefix('#') {
+            let (kind, path) = block
+                .split_once(char::is_whitespace)
+                .map(|(kind, path)| (kind, path.trim().to_string()))
+                .ok_or_else(|| format!("Missing variable in {{{{#{}}}}}", block))?;
+            let (body, alt) = parse_nodes(tokens, Some(kind))?;
+            target.push(match kind {
+                "if" | "unless" => Node::If {
+                    path,
+                    negate: kind == "unless",
+                    then: body,
+                    otherwise: alt,
+                },
+                "each" => Node::Each {
+                    path,
+                    body,
+                    otherwise: alt,
+                },
+                _ => return Err(format!("Unknown block {{{{#{}}}}}", kind)),
+            });
+            continue;
+        }
+        let parts: Vec<&str> = tag.split_whitespace().collect();
+        target.push(match parts.as_slice() {
+            [path] => Node::Value {
+                helper: None,
+                path: path.to_string(),
+            },
+            [helper, path] => Node::Value {
+                helper: Some(match *helper {
+                    "json" => Helper::Json,
+                    "upper" => Helper::Upper,
+                    "lower" => Helper::Lower,
+                    _ => return Err(format!("Unknown helper \"{}\"", helper)),
+                }),
+                path: path.to_string(),
+            },
+            _ => return Err(format!("Invalid tag {{{{{}}}}}", tag)),
+        });
+    }
+    match open {
+        Some(name) => Err(format!("Missing {{{{/{}}}}}", name)),
+        None => Ok((main, otherwise)),
+    }
+}
+
+/// 模板中可见的一层数据；循环中的每一项是一层，带有下标
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    value: &'a Value,
+    index: Option<usize>,
+}
+
+fn resolve(scopes: &[Scope], path: &str) -> Value {
+    let innermost = &scopes[scopes.len() - 1];
+    match path {
+        "this" | "." => return innermost.value.clone(),
+        "@index" => {
+            return scopes
+                .iter()
+                .rev()
+                .find_map(|s| s.index)
+                .map_or(Value::Null, Value::from)
+        }
+        _ => {}
+    }
+    let (path, scopes) = match path.strip_prefix("this.") {
+        Some(path) => (path, std::slice::from_ref(innermost)),
+        None => (path, scopes),
+    };
+    let mut keys = path.split('.');
+    let first = keys.next().unwrap_or_default();
+    for scope in scopes.iter().rev() {
+        if let Some(value) = scope.value.get(first) {
+            return keys
+                .try_fold(value, |value, key| value.get(key))
+                .cloned()
+                .unwrap_or(Value::Null);
+        }
+    }
+    Value::Null
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.trim().is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+/// 值转为文本；列表用 ", " 连接，对象不输出
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Object(_) => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(to_text)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn render_nodes(nodes: &[Node], scopes: &[Scope], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Value { helper, path } => {
+                let value = resolve(scopes, path);
+                match helper {
+                    None => out.push_str(&to_text(&value)),
+                    Some(Helper::Json) => {
+                        out.push_str(&serde_json::to_string(&to_text(&value)).unwrap_or_default())
+                    }
+                    Some(Helper::Upper) => out.push_str(&to_text(&value).to_uppercase()),
+                    Some(Helper::Lower) => out.push_str(&to_text(&value).to_lowercase()),
+                }
+            }
+            Node::If {
+                path,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let branch = if truthy(&resolve(scopes, path)) != *negate {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, scopes, out);
+            }
+            Node::Each {
+                path,
+                body,
+                otherwise,
+            } => {
+                let items = match resolve(scopes, path) {
+                    Value::Array(items) => items,
+                    Value::Null => Vec::new(),
+                    value => vec![value],
+                };
+                if items.is_empty() {
+                    render_nodes(otherwise, scopes, out);
+                    continue;
+                }
+                for (index, item) in items.iter().enumerate() {
+                    let mut inner = scopes.to_vec();
+                    inner.push(Scope {
+                        value: item,
+                        index: Some(index),
+                    });
+                    render_nodes(body, &inner, out);
+                }
+            }
+        }
+    }
+}
+
+impl Template {
+    /// 解析模板，语法错误（标签未闭合、未知的块或函数）返回错误说明
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut tokens = tokenize(source)?;
+        strip_standalone(&mut tokens);
+        let (nodes, _) = parse_nodes(&mut tokens.into_iter(), None)?;
+        Ok(Self { nodes })
+    }
+
+    pub fn render(&self, context: &Value) -> String {
+        let mut out = String::new();
+        let scope = Scope {
+            value: context,
+            index: None,
+        };
+        render_nodes(&self.nodes, &[scope], &mut out);
+        out
+    }
+}
+
+/// 解析并渲染模板
+pub fn render(source: &str, context: &Value) -> Result<String, String> {
+    Ok(Template::parse(source)?.render(context))
+}
+
+/// 文章导出的模板数据：文章字段、段落、书籍摘录、来自该文章的生词和语法
+pub fn article_context(
+    article: &Article,
+    highlights: &[Bookmark],
+    vocabulary: &[FavoriteVocabulary],
+    grammar: &[FavoriteGrammar],
+) -> Value {
+    json!({
+        "id": article.id,
+        "title": article.title,
+        "source_url": article.source_url,
+        "language": article.language,
+        "translation_language": article.translation_language,
+        "channel": article.channel,
+        "tags": article.tags,
+        "created_at": article.created_at,
+        "content": article.content,
+        "segments": article.segments.iter().filter(|s| s.image_url.is_none()).map(|s| json!({
+            "text": s.text,
+            "translation": s.translation,
+            "reading": s.reading_text,
+            "speaker": s.speaker,
+            "start_time": s.start_time,
+            "end_time": s.end_time,
+        })).collect::<Vec<_>>(),
+        "highlights": highlights.iter().map(|b| json!({
+            "text": b.selected_text.as_deref().map(str::trim),
+            "note": b.note.as_deref().map(str::trim),
+            "page": b.page_number,
+        })).collect::<Vec<_>>(),
+        "vocabulary": vocabulary.iter().map(vocabulary_context).collect::<Vec<_>>(),
+        "grammar": grammar,
+    })
+}
+
+/// 单词的模板数据：收藏的全部字段
+pub fn vocabulary_context(vocabulary: &FavoriteVocabulary) -> Value {
+    serde_json::to_value(vocabulary).unwrap_or(Value::Null)
+}
+
+/// CSV 字段：含逗号、引号或换行时加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_fields(fields: &[TemplateField]) -> Result<Vec<Template>, String> {
+    fields
+        .iter()
+        .map(|field| Template::parse(&field.template).map_err(|e| format!("{}: {}", field.name, e)))
+        .collect()
+}
+
+/// 每列一个模板生成 CSV，第一行为列名
+pub fn render_csv(fields: &[TemplateField], items: &[Value]) -> Result<String, String> {
+    let templates = parse_fields(fields)?;
+    let mut lines = vec![fields
+        .iter()
+        .map(|f| csv_field(&f.name))
+        .collect::<Vec<_>>()
+        .join(",")];
+    for item in items {
+        lines.push(
+            templates
+                .iter()
+                .map(|t| csv_field(&t.render(item)))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    Ok(lines.join("\r\n") + "\r\n")
+}
+
+/// 生成 Anki 可直接导入的文本文件：制表符分隔、字段按 HTML 解释，换行转为 <br>；
+/// 名为 Tags 的字段作为 Anki 标签列（标签以空格分隔）
+pub fn render_anki_notes(fields: &[TemplateField], items: &[Value]) -> Result<String, String> {
+    let templates = parse_fields(fields)?;
+    let mut out = String::from("#separator:tab\n#html:true\n");
+    out.push_str(&format!(
+        "#columns:{}\n",
+        fields
+            .iter()
+            .map(|f| f.name.replace(['\t', '\n'], " "))
+            .collect::<Vec<_>>()
+            .join("\t")
+    ));
+    if let Some(column) = fields
+        .iter()
+        .position(|f| f.name.trim().eq_ignore_ascii_case("tags"))
+    {
+        out.push_str(&format!("#tags column:{}\n", column + 1));
+    }
+    for item in items {
+        let row: Vec<String> = templates
+            .iter()
+            .map(|t| {
+                t.render(item)
+                    .trim()
+                    .replace("\r\n", "\n")
+                    .replace('\n', "<br>")
+                    .replace('\t', " ")
+            })
+            .collect();
+        out.push_str(&row.join("\t"));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+const DEFAULT_ARTICLE_MARKDOWN: &str = r#"---
+openkoto_id: {{json id}}
+title: {{json title}}
+{{#if source_url}}
+source: {{json source_url}}
+{{/if}}
+created: {{json created_at}}
+---
+
+# {{title}}
+{{#if highlights}}
+
+## Highlights
+{{#each highlights}}
+
+> {{text}}
+{{#if note}}
+
+{{note}}
+{{/if}}
+{{/each}}
+{{/if}}
+{{#if vocabulary}}
+
+## Vocabulary
+
+{{#each vocabulary}}
+- [[{{word}}]]{{#if reading}} ({{reading}}){{/if}} — {{meaning}}
+{{/each}}
+{{/if}}
+{{#if grammar}}
+
+## Grammar
+
+{{#each grammar}}
+- **{{point}}** — {{explanation}}
+{{/each}}
+{{/if}}
+"#;
+
+fn field(name: &str, template: &str) -> TemplateField {
+    TemplateField {
+        name: name.to_string(),
+        template: template.to_string(),
+    }
+}
+
+/// 新建模板时的初始内容：Markdown 模板返回正文，CSV 和 Anki 模板返回字段
+pub fn default_template(kind: ExportTemplateKind) -> (String, Vec<TemplateField>) {
+    match kind {
+        ExportTemplateKind::ArticleMarkdown => (DEFAULT_ARTICLE_MARKDOWN.to_string(), Vec::new()),
+        ExportTemplateKind::VocabularyCsv => (
+            String::new(),
+            vec![
+                field("word", "{{word}}"),
+                field("reading", "{{reading}}"),
+                field("meaning", "{{meaning}}"),
+                field("usage", "{{usage}}"),
+                field("example", "{{example}}"),
+                field("tags", "{{tags}}"),
+            ],
+        ),
+        ExportTemplateKind::AnkiNote => (
+            String::new(),
+            vec![
+                field("Front", "{{word}}{{#if reading}}<br>{{reading}}{{/if}}"),
+                field(
+                    "Back",
+                    "{{meaning}}{{#if example}}<br><br><i>{{example}}</i>{{/if}}",
+                ),
+                field("Tags", "{{#each tags}}{{this}} {{/each}}"),
+            ],
+        ),
+    }
+}
@@ -0,0 +1,249 @@
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::AppHandle;
+
+use crate::storage::get_app_data_dir;
+
+/// 站点规则文件名（位于 app data 目录，用户可直接编辑）
+const RULES_FILE_NAME: &str = "extraction_rules.json";
+
+/// 选择器命中的元素 HTML 至少要这么长才视为正文
+const MIN_SELECTED_HTML_LEN: usize = 100;
+
+/// 单个站点的正文提取规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteRule {
+    /// 规则名称，仅用于界面显示
+    #[serde(default)]
+    pub name: String,
+    /// 匹配的域名，同时匹配其子域名（"uta-net.com" 也匹配 "www.uta-net.com"）
+    pub domains: Vec<String>,
+    /// 正文 CSS 选择器，按顺序尝试，优先于 readability
+    #[serde(default)]
+    pub content_selectors: Vec<String>,
+    /// 标题 CSS 选择器，取元素文本
+    #[serde(default)]
+    pub title_selector: Option<String>,
+    /// 从提取出的正文中删除的正则表达式（如版权声明、"作詞：..."）
+    #[serde(default)]
+    pub cleanup_patterns: Vec<String>,
+}
+
+impl SiteRule {
+    /// 判断域名是否命中该规则
+    pub fn matches_host(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_lowercase();
+        self.domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches("*.").to_lowercase();
+            !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+        })
+    }
+
+    /// 依次应用 cleanup_patterns，并去掉首尾空白
+    /// 无效的正则会被跳过（保存时已校验，这里只防御手动编辑出错的文件）
+    pub fn apply_cleanup(&self, text: &str) -> String {
+        let mut cleaned = text.to_string();
+        for pattern in &self.cleanup_patterns {
+            match Regex::new(pattern) {
+                Ok(re) => cleaned = re.replace_all(&cleaned, "").to_string(),
+                Err(e) => eprintln!(
+                    "[ExtractionRules] Invalid cleanup pattern {}: {}",
+                    pattern, e
+                ),
+            }
+        }
+        cleaned.trim().to_string()
+    }
+}
+
+/// 用户可编辑的网页提取规则集
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionRules {
+    #[serde(default)]
+    pub sites: Vec<SiteRule>,
+    /// readability 提取结果过短时，对所有站点依次尝试的通用选择器
+    #[serde(default = "default_fallback_selectors")]
+    pub fallback_selectors: Vec<String>,
+}
+
+fn default_fallback_selectors() -> Vec<String> {
+    [
+        "div[itemprop='text']", // Generic schema.org text
+        ".lyrics",
+        "#lyrics",
+        ".post-content",
+        "article",
+        "main",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+impl Default for ExtractionRules {
+    fn default() -> Self {
+        Self {
+            sites: vec![SiteRule {
+                name: "Uta-Net".to_string(),
+                domains: vec!["uta-net.com".to_string()],
+                // readability 在歌词页只能提取到 "投票ありがとう" 之类的短文本
+                content_selectors: vec!["#kashi_area".to_string()],
+                title_selector: None,
+                cleanup_patterns: Vec::new(),
+            }],
+            fallback_selectors: default_fallback_selectors(),
+        }
+    }
+}
+
+impl ExtractionRules {
+    /// 查找与 URL 域名匹配的第一条站点规则
+    pub fn find_site_rule(&self, url: &str) -> Option<&SiteRule> {
+        let parsed = url::Url::parse(url).ok()?;
+        let host = parsed.host_str()?;
+        self.sites.iter().find(|rule| rule.matches_host(host))
+    }
+
+    /// 校验所有选择器和正则，返回第一个错误
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.sites {
+            let label = if rule.name.is_empty() {
+                rule.domains.join(", ")
+            } else {
+                rule.name.clone()
+            };
+            if rule.domains.iter().all(|d| d.trim().is_empty()) {
+                return Err(format!(
+                    "Invalid rule {}: at least one domain is required",
+                    label
+                ));
+            }
+            for selector in rule.content_selectors.iter().chain(&rule.title_selector) {
+                Selector::parse(selector).map_err(|e| {
+                    format!("Invalid selector '{}' in {}: {:?}", selector, label, e)
+                })?;
+            }
+            for pattern in &rule.cleanup_patterns {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid pattern '{}' in {}: {}", pattern, label, e))?;
+            }
+        }
+        for selector in &self.fallback_selectors {
+            Selector::parse(selector)
+                .map_err(|e| format!("Invalid fallback selector '{}': {:?}", selector, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// 按顺序尝试选择器，返回第一个足够长的元素 HTML
+pub fn select_content_html(document: &Html, selectors: &[String]) -> Option<String> {
+    for selector_str in selectors {
+        if let Ok(selector) = Selector::parse(selector_str) {
+            if let Some(element) = document.select(&selector).next() {
+                let html_content = element.html();
+                if html_content.len() > MIN_SELECTED_HTML_LEN {
+                    return Some(html_content);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// 用选择器提取标题文本
+pub fn select_title(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    let element = document.select(&selector).next()?;
+    let title = element
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+// ==================== 规则文件加载（按修改时间热重载） ====================
+
+struct CachedRules {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    rules: ExtractionRules,
+}
+
+static RULES_CACHE: Mutex<Option<CachedRules>> = Mutex::new(None);
+
+pub fn rules_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_data_dir(app_handle)?.join(RULES_FILE_NAME))
+}
+
+/// 加载规则文件；文件不存在时写入默认规则
+/// 文件修改时间未变时直接使用缓存，用户手动编辑后下次抓取自动生效
+pub fn load_rules(app_handle: &AppHandle) -> Result<ExtractionRules, String> {
+    let path = rules_file_path(app_handle)?;
+    if !path.exists() {
+        save_rules(app_handle, &ExtractionRules::default())?;
+    }
+
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let mut cache = RULES_CACHE.lock().map_err(|e| e.to_string())?;
+    if let Some(cached) = cache.as_ref() {
+        if cached.path == path && modified.is_some() && cached.modified == modified {
+            return Ok(cached.rules.clone());
+        }
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read extraction rules: {}", e))?;
+    let rules: ExtractionRules = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid extraction rules file: {}", e))?;
+    println!("[ExtractionRules] Loaded {} site rules", rules.sites.len());
+
+    *cache = Some(CachedRules {
+        path,
+        modified,
+        rules: rules.clone(),
+    });
+    Ok(rules)
+}
+
+/// 加载规则，失败时（如用户把 JSON 改坏）回退到内置默认规则，保证抓取可用
+pub fn load_rules_or_default(app_handle: &AppHandle) -> ExtractionRules {
+    load_rules(app_handle).unwrap_or_else(|e| {
+        eprintln!("[ExtractionRules] {}, using built-in rules", e);
+        ExtractionRules::default()
+    })
+}
+
+/// 校验并保存规则文件
+pub fn save_rules(app_handle: &AppHandle, rules: &ExtractionRules) -> Result<(), String> {
+    rules.validate()?;
+    let path = rules_file_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(rules)
+        .map_err(|e| format!("Failed to serialize extraction rules: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write extraction rules: {}", e))?;
+
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    if let Ok(mut cache) = RULES_CACHE.lock() {
+        *cache = Some(CachedRules {
+            path,
+            modified,
+            rules: rules.clone(),
+        });
+    }
+    Ok(())
+}
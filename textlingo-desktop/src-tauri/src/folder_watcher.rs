@@ -0,0 +1,240 @@
+// 监视文件夹自动导入：设置一个文件夹（例如同步盘目录），放入其中的文件按类型自动导入，
+// 已导入过的文本 / Markdown 文件再次保存时更新原文章，不重复导入。
+// 结果通过 watch-folder://imported、watch-folder://updated 和 watch-folder://failed 事件通知前端
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+
+use crate::commands;
+use crate::error::AppError;
+use crate::storage::load_config;
+use crate::types::Article;
+
+/// 文件大小稳定检查的间隔和最长等待时间（同步盘可能分多次写入文件）
+const STABLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const STABLE_MAX_WAIT: Duration = Duration::from_secs(120);
+/// 去重记录超过该数量时清理已删除文件的记录
+const MAX_HANDLED: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchImportKind {
+    /// .txt：按普通文章导入并分段
+    Text,
+    /// .md/.markdown：按 Markdown 导入，保留标题、列表等段落结构
+    Markdown,
+    /// .epub/.pdf：按书籍导入
+    Book,
+    /// 音视频：按本地视频导入
+    Media,
+}
+
+/// 根据文件名判断导入方式，不支持的文件返回 None
+pub fn watch_import_kind(path: &Path) -> Option<WatchImportKind> {
+    let name = path.file_name()?.to_str()?;
+    // 忽略隐藏文件以及同步工具、编辑器的临时文件
+    if name.starts_with('.') || name.starts_with('~') {
+        return None;
+    }
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    match ext.as_str() {
+        "txt" => Some(WatchImportKind::Text),
+        "md" | "markdown" => Some(WatchImportKind::Markdown),
+        "epub" | "pdf" => Some(WatchImportKind::Book),
+        _ if commands::is_local_media_file(path) => Some(WatchImportKind::Media),
+        _ => None,
+    }
+}
+
+/// 当前的文件夹监视器，替换或清空时旧的监视随之停止
+#[derive(Default)]
+pub struct FolderWatcher {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl FolderWatcher {
+    /// 开始监视 folder（只监视新放入的文件，不导入已有文件），None 时停止监视
+    /// 新的监视建立失败时保留原来的监视
+    pub fn watch(&self, app_handle: &AppHandle, folder: Option<&Path>) -> Result<(), String> {
+        let Some(folder) = folder else {
+            *self.watcher.lock().map_err(|e| e.to_string())? = None;
+            return Ok(());
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel::<PathBuf>();
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[WatchFolder] Watch error: {}", e),
+            })
+            .map_err(|e| format!("Failed to create folder watcher: {}", e))?;
+        watcher
+            .watch(folder, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch folder: {}", e))?;
+        *self.watcher.lock().map_err(|e| e.to_string())? = Some(watcher);
+
+        // 监视器被替换后发送端随之释放，处理任务自然结束
+        tauri::async_runtime::spawn(process_events(app_handle.clone(), rx));
+        println!("[WatchFolder] Watching {:?}", folder);
+        Ok(())
+    }
+}
+
+/// 启动时按配置恢复文件夹监视
+pub fn start_from_config(app_handle: &AppHandle) {
+    let Some(folder) = load_config(app_handle)
+        .ok()
+        .flatten()
+        .and_then(|config| config.watch_folder)
+    else {
+        return;
+    };
+
+    let watcher = app_handle.state::<FolderWatcher>();
+    if let Err(e) = watcher.watch(app_handle, Some(Path::new(&folder))) {
+        eprintln!("[WatchFolder] Failed to watch {}: {}", folder, e);
+    }
+}
+
+/// 依次处理文件事件；记录每个文件已处理的修改时间，同一次写入的多次事件只处理一次
+async fn process_events(app_handle: AppHandle, mut rx: mpsc::UnboundedReceiver<PathBuf>) {
+    let mut handled: HashMap<PathBuf, SystemTime> = HashMap::new();
+
+    while let Some(path) = rx.recv().await {
+        let Some(kind) = watch_import_kind(&path) else {
+            continue;
+        };
+        if modified_time(&path).is_none_or(|modified| handled.get(&path) == Some(&modified)) {
+            continue;
+        }
+        // 文件在写入完成前被移走（例如临时文件被重命名）时跳过，之后的事件可再次处理
+        if !wait_until_stable(&path).await {
+            continue;
+        }
+        let Some(modified) = modified_time(&path) else {
+            continue;
+        };
+        if handled.insert(path.clone(), modified) == Some(modified) {
+            continue;
+        }
+        if handled.len() > MAX_HANDLED {
+            handled.retain(|path, _| path.exists());
+        }
+
+        let file_path = path.to_string_lossy().into_owned();
+        match import_file(&app_handle, &path, kind).await {
+            Ok(WatchImport::Imported(article)) => {
+                println!("[WatchFolder] Imported {}", file_path);
+                let _ = app_handle.emit(
+                    "watch-folder://imported",
+                    serde_json::json!({ "file_path": file_path, "article": article }),
+                );
+            }
+            Ok(WatchImport::Updated(article)) => {
+                println!("[WatchFolder] Updated {}", file_path);
+                let _ = app_handle.emit(
+                    "watch-folder://updated",
+                    serde_json::json!({ "file_path": file_path, "article": article }),
+                );
+            }
+            Ok(WatchImport::Unchanged) => {}
+            Err(e) => {
+                eprintln!("[WatchFolder] Failed to import {}: {}", file_path, e);
+                let _ = app_handle.emit(
+                    "watch-folder://failed",
+                    serde_json::json!({ "file_path": file_path, "error": e.to_string() }),
+                );
+            }
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 等待文件大小不再变化，文件消失时返回 false
+async fn wait_until_stable(path: &Path) -> bool {
+    let mut last_len = None;
+    let mut waited = Duration::ZERO;
+
+    while waited < STABLE_MAX_WAIT {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        let len = metadata.len();
+        if len > 0 && last_len == Some(len) {
+            return true;
+        }
+        last_len = Some(len);
+        tokio::time::sleep(STABLE_CHECK_INTERVAL).await;
+        waited += STABLE_CHECK_INTERVAL;
+    }
+    path.exists()
+}
+
+enum WatchImport {
+    Imported(Article),
+    Updated(Article),
+    /// 已导入过且内容没有变化，或书籍、音视频已导入过
+    Unchanged,
+}
+
+/// 导入文件；从该文件导入过的文章存在时，文本 / Markdown 文章按新内容更新，书籍和音视频不再导入
+async fn import_file(
+    app_handle: &AppHandle,
+    path: &Path,
+    kind: WatchImportKind,
+) -> Result<WatchImport, AppError> {
+    let file_path = path.to_string_lossy().into_owned();
+    if let Some(article) = commands::find_article_by_file(app_handle, &file_path)? {
+        let markdown = match kind {
+            WatchImportKind::Text => false,
+            WatchImportKind::Markdown => true,
+            WatchImportKind::Book | WatchImportKind::Media => return Ok(WatchImport::Unchanged),
+        };
+        return Ok(
+            match commands::refresh_file_article(app_handle, article, path, markdown).await? {
+                Some(article) => WatchImport::Updated(article),
+                None => WatchImport::Unchanged,
+            },
+        );
+    }
+
+    let article = match kind {
+        WatchImportKind::Text => {
+            let content =
+                std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+            let title = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            commands::create_article(
+                app_handle.clone(),
+                title,
+                content,
+                Some(format!("file://{}", file_path)),
+                None,
+                None,
+            )
+            .await
+        }
+        WatchImportKind::Markdown => {
+            commands::import_markdown_cmd(app_handle.clone(), file_path, None).await
+        }
+        WatchImportKind::Book => {
+            commands::import_book_cmd(app_handle.clone(), file_path, None).await
+        }
+        WatchImportKind::Media => commands::import_local_video(app_handle, &file_path),
+    }?;
+    Ok(WatchImport::Imported(article))
+}
@@ -0,0 +1,142 @@
+// 强制对齐：大模型返回的 MM:SS 时间只精确到秒且常有漂移，
+// 用本地 whisper 命令行（--word_timestamps）取得音频中每个词的实际时间，
+// 把段落文本逐词匹配到这些词上，再把段落的开始/结束时间吸附到首尾词的边界
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use tokio::process::Command;
+
+use crate::audio_alignment::{align_tokens, tokenize, transcript_tokens, TimedToken};
+use crate::i18n::{tr, tr_with};
+use crate::subtitle_files::SubtitleCue;
+use crate::transcription::{parse_words, whisper_command, whisper_model};
+use crate::types::{ArticleSegment, TranscriptionSettings};
+
+/// 吸附后开始时间与原时间相差超过该值（秒）时认为匹配错误，保留原时间
+pub const MAX_SHIFT_SECONDS: f64 = 15.0;
+
+/// 解析带单词时间戳的转录结果（whisper CLI 在各分段的 words，OpenAI 格式在顶层 words），
+/// 拆成用于匹配的词；中日文一个单词含多个字时按字数分配时间
+pub fn parse_word_timestamps(value: &Value) -> Vec<TimedToken> {
+    let mut words = parse_words(&value["words"]);
+    if let Some(segments) = value["segments"].as_array() {
+        words.extend(segments.iter().flat_map(|seg| parse_words(&seg["words"])));
+    }
+    let cues: Vec<SubtitleCue> = words
+        .into_iter()
+        .map(|word| SubtitleCue {
+            start: word.start,
+            end: word.end,
+            text: word.text,
+        })
+        .collect();
+    transcript_tokens(&cues)
+}
+
+/// 把带时间的段落吸附到词边界：段落至少一半的词匹配上时，开始取首个匹配词的开始、
+/// 结束取最后一个匹配词的结束；偏移超过 MAX_SHIFT_SECONDS 的视为误配。返回修正的段落数
+pub fn snap_segments(segments: &mut [ArticleSegment], words: &[TimedToken]) -> usize {
+    let mut text_tokens = Vec::new();
+    let mut owners = Vec::new();
+    let mut token_counts = vec![0usize; segments.len()];
+    for (index, seg) in segments.iter().enumerate() {
+        if seg.start_time.is_none() {
+            continue;
+        }
+        for token in tokenize(&seg.text) {
+            text_tokens.push(token);
+            owners.push(index);
+            token_counts[index] += 1;
+        }
+    }
+
+    let mut spans: Vec<Option<(f64, f64, usize)>> = vec![None; segments.len()];
+    for (token_index, matched) in align_tokens(&text_tokens, words).into_iter().enumerate() {
+        let Some(matched) = matched else {
+            continue;
+        };
+        let word = &words[matched];
+        let span = &mut spans[owners[token_index]];
+        *span = Some(match *span {
+            Some((start, end, count)) => (start.min(word.start), end.max(word.end), count + 1),
+            None => (word.start, word.end, 1),
+        });
+    }
+
+    let mut snapped = 0;
+    for (index, seg) in segments.iter_mut().enumerate() {
+        let (Some((start, end, matched)), Some(original)) = (spans[index], seg.start_time) else {
+            continue;
+        };
+        if matched * 2 < token_counts[index]
+            || end <= start
+            || (start - original).abs() > MAX_SHIFT_SECONDS
+        {
+            continue;
+        }
+        seg.start_time = Some(start);
+        seg.end_time = Some(end);
+        snapped += 1;
+    }
+    snapped
+}
+
+/// 用本地 whisper 命令行生成媒体文件的单词时间戳
+pub async fn word_timestamps(
+    settings: &TranscriptionSettings,
+    media_path: &Path,
+) -> Result<Vec<TimedToken>, String> {
+    let output_dir = media_path
+        .parent()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let stem = media_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let command = whisper_command(settings);
+
+    let output = Command::new(&command)
+        .arg(media_path)
+        .args([
+            "--model",
+            &whisper_model(settings),
+            "--output_format",
+            "json",
+            "--word_timestamps",
+            "True",
+        ])
+        .arg("--output_dir")
+        .arg(output_dir)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| {
+            tr_with(
+                "error.whisper_command",
+                &[("command", command.clone()), ("error", e.to_string())],
+            )
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(tr_with(
+            "error.whisper_alignment_failed",
+            &[("error", stderr.to_string())],
+        ));
+    }
+
+    let json_path = output_dir.join(format!("{}.json", stem));
+    let content = fs::read_to_string(&json_path).map_err(|e| {
+        tr_with(
+            "error.whisper_output_unreadable",
+            &[("error", e.to_string())],
+        )
+    })?;
+    let _ = fs::remove_file(&json_path);
+
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| tr_with("error.whisper_output_invalid", &[("error", e.to_string())]))?;
+    Ok(parse_word_timestamps(&value))
+}
@@ -0,0 +1,71 @@
+// 术语表
+// 翻译前找出原文中出现的术语，把指定译法注入提示词，
+// 保证人名、专有名词和技术术语在整篇文章中译法一致
+
+use crate::storage::load_all_glossary_entries;
+use crate::types::GlossaryEntry;
+use tauri::AppHandle;
+
+/// 找出目标语言匹配且在任一原文中出现的条目（拉丁字母不区分大小写），按术语长度降序
+pub fn matching_entries<'a>(
+    entries: &'a [GlossaryEntry],
+    texts: &[&str],
+    target_language: &str,
+) -> Vec<&'a GlossaryEntry> {
+    let lowered: Vec<String> = texts.iter().map(|t| t.to_lowercase()).collect();
+
+    let mut matched: Vec<&GlossaryEntry> = entries
+        .iter()
+        .filter(|entry| {
+            let term = entry.term.trim().to_lowercase();
+            !term.is_empty()
+                && entry.target_language.eq_ignore_ascii_case(target_language)
+                && lowered.iter().any(|text| text.contains(&term))
+        })
+        .collect();
+
+    // 较长的术语优先，避免 "New York Times" 被 "New York" 抢先
+    matched.sort_by_key(|entry| std::cmp::Reverse(entry.term.chars().count()));
+    matched
+}
+
+/// 生成注入翻译提示词的术语表段落，无匹配条目时返回空字符串
+pub fn glossary_prompt(entries: &[&GlossaryEntry]) -> String {
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    let mut prompt =
+        String::from("Glossary (always use these translations for the following terms):\n");
+    for entry in entries {
+        prompt.push_str(&format!(
+            "- {} => {}",
+            entry.term.trim(),
+            entry.translation.trim()
+        ));
+        if let Some(note) = entry.note.as_deref().filter(|n| !n.trim().is_empty()) {
+            prompt.push_str(&format!(" ({})", note.trim()));
+        }
+        prompt.push('\n');
+    }
+    prompt
+}
+
+/// 术语表指纹：匹配条目及译法变化后，翻译记忆不再复用旧译文
+pub fn glossary_signature(entries: &[&GlossaryEntry]) -> String {
+    let mut pairs: Vec<String> = entries
+        .iter()
+        .map(|e| format!("{}={}", e.term.trim(), e.translation.trim()))
+        .collect();
+    pairs.sort();
+    pairs.join(";")
+}
+
+/// 读取全部术语表条目（读取失败时视为空表，不影响翻译）
+pub fn load_entries(app_handle: &AppHandle) -> Vec<GlossaryEntry> {
+    load_all_glossary_entries(app_handle)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|json| serde_json::from_str::<GlossaryEntry>(json).ok())
+        .collect()
+}
@@ -0,0 +1,271 @@
+// 系统健康检查
+// 设置页的诊断面板一次性查看外部工具、本地服务、插件、AI 配置和磁盘空间的状态，
+// 避免用户在任务进行到一半时才发现环境问题
+
+use crate::ai_service::AIService;
+use crate::error::AppError;
+use crate::plugin_manager::{get_plugin_execution_command, scan_plugins};
+use crate::storage::{get_app_data_dir, load_config};
+use crate::video_server::VIDEO_SERVER_PORT;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// 外部命令检查的超时时间
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+/// 剩余空间低于该值时警告
+const DISK_WARNING_BYTES: u64 = 1024 * 1024 * 1024;
+/// 剩余空间低于该值时报错（视频下载、字幕提取都需要临时文件）
+const DISK_ERROR_BYTES: u64 = 200 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// 检查项 ID: ffmpeg / yt_dlp / resource_server / plugins / ai_config / disk_space
+    pub id: String,
+    pub status: HealthStatus,
+    /// 版本号、错误信息等说明
+    pub detail: String,
+}
+
+impl HealthCheck {
+    fn new(id: &str, status: HealthStatus, detail: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    pub checked_at: String,
+    /// 所有检查项中最差的状态
+    pub overall: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// 从 `df -Pk` 输出中解析可用空间（字节）
+pub fn parse_df_available_bytes(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+/// 根据剩余空间判断状态
+pub fn disk_space_status(available_bytes: u64) -> HealthStatus {
+    if available_bytes < DISK_ERROR_BYTES {
+        HealthStatus::Error
+    } else if available_bytes < DISK_WARNING_BYTES {
+        HealthStatus::Warning
+    } else {
+        HealthStatus::Ok
+    }
+}
+
+/// 检查 sidecar 能否运行，成功时返回输出的第一行作为版本信息
+async fn check_sidecar(app: &AppHandle, id: &str, name: &str, version_arg: &str) -> HealthCheck {
+    let command = match app.shell().sidecar(name) {
+        Ok(command) => command.args([version_arg]),
+        Err(e) => return HealthCheck::new(id, HealthStatus::Error, e.to_string()),
+    };
+
+    match tokio::time::timeout(COMMAND_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version = stdout.lines().next().unwrap_or("").trim().to_string();
+            HealthCheck::new(id, HealthStatus::Ok, version)
+        }
+        Ok(Ok(output)) => HealthCheck::new(
+            id,
+            HealthStatus::Error,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ),
+        Ok(Err(e)) => HealthCheck::new(id, HealthStatus::Error, e.to_string()),
+        Err(_) => HealthCheck::new(id, HealthStatus::Error, "Timed out"),
+    }
+}
+
+/// 资源服务器（视频、书籍、图片）是否在监听端口
+async fn check_resource_server() -> HealthCheck {
+    let address = format!("127.0.0.1:{}", VIDEO_SERVER_PORT);
+    match tokio::time::timeout(
+        Duration::from_secs(2),
+        tokio::net::TcpStream::connect(&address),
+    )
+    .await
+    {
+        Ok(Ok(_)) => HealthCheck::new("resource_server", HealthStatus::Ok, address),
+        Ok(Err(e)) => HealthCheck::new(
+            "resource_server",
+            HealthStatus::Error,
+            format!("{}: {}", address, e),
+        ),
+        Err(_) => HealthCheck::new(
+            "resource_server",
+            HealthStatus::Error,
+            format!("{}: timed out", address),
+        ),
+    }
+}
+
+/// 已安装插件的入口命令能否解析
+fn check_plugins(app: &AppHandle) -> HealthCheck {
+    let plugins = scan_plugins(app);
+    if plugins.is_empty() {
+        return HealthCheck::new("plugins", HealthStatus::Ok, "No plugins installed");
+    }
+
+    let broken: Vec<String> = plugins
+        .iter()
+        .filter_map(|plugin| {
+            get_plugin_execution_command(app, &plugin.metadata.name)
+                .err()
+                .map(|e| format!("{}: {}", plugin.metadata.name, e))
+        })
+        .collect();
+
+    if broken.is_empty() {
+        let names: Vec<String> = plugins
+            .iter()
+            .map(|p| {
+                format!(
+                    "{} {} ({:?})",
+                    p.metadata.name, p.metadata.version, p.active_mode
+                )
+            })
+            .collect();
+        HealthCheck::new("plugins", HealthStatus::Ok, names.join(", "))
+    } else {
+        HealthCheck::new("plugins", HealthStatus::Error, broken.join("; "))
+    }
+}
+
+/// 当前 AI 配置的 API 端点是否可达
+async fn check_ai_config(app: &AppHandle) -> HealthCheck {
+    let config = match load_config(app) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            return HealthCheck::new("ai_config", HealthStatus::Warning, "No AI model configured")
+        }
+        Err(e) => return HealthCheck::new("ai_config", HealthStatus::Error, e),
+    };
+    let Some(active) = config.get_active_config() else {
+        return HealthCheck::new("ai_config", HealthStatus::Warning, "No AI model configured");
+    };
+
+    let service = AIService::with_base_url(
+        active.api_key.clone(),
+        active.api_provider.clone(),
+        active.model.clone(),
+        active.base_url.clone(),
+    );
+    let label = format!("{} / {}", active.api_provider, active.model);
+    match service.check_reachable().await {
+        Ok(status) => HealthCheck::new(
+            "ai_config",
+            HealthStatus::Ok,
+            format!("{} (HTTP {})", label, status),
+        ),
+        Err(e) => HealthCheck::new(
+            "ai_config",
+            HealthStatus::Error,
+            format!("{}: {}", label, e),
+        ),
+    }
+}
+
+/// 查询目录所在磁盘的可用空间（字节）
+async fn available_disk_bytes(path: &Path) -> Result<u64, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            path.display().to_string().replace('\'', "''")
+        );
+        let output = tokio::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .output()
+            .await
+            .map_err(|e| e.to_string())?;
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|_| "Unable to read free disk space".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let output = tokio::process::Command::new("df")
+            .arg("-Pk")
+            .arg(path)
+            .output()
+            .await
+            .map_err(|e| e.to_string())?;
+        parse_df_available_bytes(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| "Unable to read free disk space".to_string())
+    }
+}
+
+async fn check_disk_space(app: &AppHandle) -> HealthCheck {
+    let app_data_dir = match get_app_data_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => return HealthCheck::new("disk_space", HealthStatus::Error, e),
+    };
+
+    match tokio::time::timeout(COMMAND_TIMEOUT, available_disk_bytes(&app_data_dir)).await {
+        Ok(Ok(bytes)) => HealthCheck::new(
+            "disk_space",
+            disk_space_status(bytes),
+            format!("{:.1} GB free", bytes as f64 / 1024.0 / 1024.0 / 1024.0),
+        ),
+        Ok(Err(e)) => HealthCheck::new("disk_space", HealthStatus::Warning, e),
+        Err(_) => HealthCheck::new("disk_space", HealthStatus::Warning, "Timed out"),
+    }
+}
+
+/// 获取系统健康状态（各项检查并发执行）
+#[tauri::command]
+pub async fn get_system_health_cmd(app_handle: AppHandle) -> Result<SystemHealth, AppError> {
+    let (ffmpeg, yt_dlp, resource_server, ai_config, disk_space) = tokio::join!(
+        check_sidecar(&app_handle, "ffmpeg", "ffmpeg", "-version"),
+        check_sidecar(&app_handle, "yt_dlp", "yt-dlp", "--version"),
+        check_resource_server(),
+        check_ai_config(&app_handle),
+        check_disk_space(&app_handle),
+    );
+    let plugins = check_plugins(&app_handle);
+
+    let checks = vec![
+        ffmpeg,
+        yt_dlp,
+        resource_server,
+        plugins,
+        ai_config,
+        disk_space,
+    ];
+    let overall = if checks.iter().any(|c| c.status == HealthStatus::Error) {
+        HealthStatus::Error
+    } else if checks.iter().any(|c| c.status == HealthStatus::Warning) {
+        HealthStatus::Warning
+    } else {
+        HealthStatus::Ok
+    };
+
+    Ok(SystemHealth {
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        overall,
+        checks,
+    })
+}
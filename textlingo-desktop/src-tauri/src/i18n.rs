@@ -0,0 +1,1127 @@
+use std::sync::RwLock;
+
+/// 后端提示信息支持的界面语言（与前端 src/locales 保持一致）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Zh,
+    Ja,
+}
+
+impl Lang {
+    /// 解析 interface_language，如 "zh"、"zh-CN"、"ja-JP"，未知语言回退到英文
+    pub fn from_code(code: &str) -> Self {
+        let code = code.trim().to_lowercase();
+        if code.starts_with("zh") {
+            Lang::Zh
+        } else if code.starts_with("ja") {
+            Lang::Ja
+        } else {
+            Lang::En
+        }
+    }
+}
+
+static CURRENT_LANG: RwLock<Lang> = RwLock::new(Lang::En);
+
+/// 设置当前界面语言（加载或保存配置时调用）
+pub fn set_language(code: &str) {
+    if let Ok(mut lang) = CURRENT_LANG.write() {
+        *lang = Lang::from_code(code);
+    }
+}
+
+pub fn current_language() -> Lang {
+    CURRENT_LANG.read().map(|lang| *lang).unwrap_or(Lang::En)
+}
+
+/// (key, en, zh, ja)
+const CATALOG: &[(&str, &str, &str, &str)] = &[
+    // 文章翻译进度
+    (
+        "translate.chunk",
+        "Translating chunk {current}/{total}",
+        "正在翻译第 {current}/{total} 批",
+        "翻訳中 {current}/{total}",
+    ),
+    (
+        "translate.completed",
+        "Translation completed",
+        "翻译完成",
+        "翻訳が完了しました",
+    ),
+    (
+        "translate.chunk_incomplete",
+        "{missing} segments were not returned by the model",
+        "模型漏译了 {missing} 个段落",
+        "モデルが {missing} 個の段落を翻訳しませんでした",
+    ),
+    (
+        "translate.completed_with_failures",
+        "Translation finished, {failed} batches failed. You can resume later",
+        "翻译结束，{failed} 批翻译失败，可稍后继续翻译",
+        "翻訳が終了しました。{failed} バッチが失敗しました。後で再開できます",
+    ),
+    (
+        "translate.quality_warning",
+        "Spot check scored the translation {score}/10 ({low} of {sampled} sampled sentences look wrong). Consider retranslating with a stronger model",
+        "抽查译文平均 {score}/10 分（抽查 {sampled} 句中 {low} 句有问题），建议换用更好的模型重新翻译",
+        "翻訳の抜き取り検査は平均 {score}/10 点でした（{sampled} 文中 {low} 文に問題あり）。より高性能なモデルでの再翻訳をおすすめします",
+    ),
+    // 段落讲解进度
+    (
+        "explain.batch",
+        "Explained {current}/{total} segments",
+        "已讲解 {current}/{total} 个段落",
+        "{current}/{total} 段落を解説しました",
+    ),
+    // 独立窗口标题
+    ("window.review", "Review - OpenKoto", "复习 - OpenKoto", "復習 - OpenKoto"),
+    ("window.stats", "Statistics - OpenKoto", "统计 - OpenKoto", "統計 - OpenKoto"),
+    (
+        "explain.completed",
+        "Explanations generated",
+        "段落讲解生成完成",
+        "解説の生成が完了しました",
+    ),
+    (
+        "explain.completed_with_failures",
+        "Explanations finished, {failed} segments failed",
+        "讲解生成结束，{failed} 个段落失败",
+        "解説の生成が終了しました。{failed} 段落が失敗しました",
+    ),
+    // 注音生成进度
+    (
+        "reading.batch",
+        "Generating readings {current}/{total}",
+        "正在生成注音 {current}/{total}",
+        "読み仮名を生成中 {current}/{total}",
+    ),
+    (
+        "reading.completed",
+        "Readings generated",
+        "注音生成完成",
+        "読み仮名の生成が完了しました",
+    ),
+    // 生词读音补全进度
+    (
+        "phonetic.batch",
+        "Generating word pronunciations {current}/{total}",
+        "正在生成单词读音 {current}/{total}",
+        "単語の発音表記を生成中 {current}/{total}",
+    ),
+    (
+        "phonetic.completed",
+        "Word pronunciations generated",
+        "单词读音生成完成",
+        "単語の発音表記の生成が完了しました",
+    ),
+    // 长文分析进度
+    (
+        "analysis.chunk",
+        "Analyzing part {current}/{total}",
+        "正在分析第 {current}/{total} 部分",
+        "{current}/{total} 部分を分析中",
+    ),
+    (
+        "analysis.synthesizing",
+        "Combining the results of {total} parts",
+        "正在汇总 {total} 个部分的分析结果",
+        "{total} 部分の分析結果をまとめています",
+    ),
+    (
+        "analysis.completed",
+        "Analysis completed",
+        "分析完成",
+        "分析が完了しました",
+    ),
+    // 字幕提取进度
+    (
+        "subtitle.start",
+        "Starting subtitle extraction...",
+        "开始提取字幕...",
+        "字幕の抽出を開始しています...",
+    ),
+    (
+        "subtitle.kimi_mode",
+        "Using Kimi video understanding mode...",
+        "正在使用 Kimi 视频理解模式...",
+        "Kimi 動画理解モードを使用しています...",
+    ),
+    (
+        "subtitle.chunked",
+        "Long video detected, extracting in chunks...",
+        "视频较长，启用分片提取模式...",
+        "長い動画のため分割して抽出します...",
+    ),
+    (
+        "subtitle.audio",
+        "Extracting audio...",
+        "提取音频中...",
+        "音声を抽出しています...",
+    ),
+    (
+        "subtitle.transcribe",
+        "Transcribing audio...",
+        "转录音频中...",
+        "音声を文字起こししています...",
+    ),
+    (
+        "subtitle.chunk_pair",
+        "Extracting chunks {start}-{end}/{total}",
+        "提取片段 {start}-{end}/{total}",
+        "チャンク {start}-{end}/{total} を抽出中",
+    ),
+    (
+        "subtitle.chunk",
+        "Extracting chunk {index}/{total}",
+        "提取片段 {index}/{total}",
+        "チャンク {index}/{total} を抽出中",
+    ),
+    (
+        "subtitle.chunks_completed",
+        "Completed {done}/{total} chunks",
+        "已完成 {done}/{total} 片段",
+        "{done}/{total} チャンク完了",
+    ),
+    (
+        "subtitle.aligning",
+        "Aligning subtitle times to the audio...",
+        "正在把字幕时间对齐到音频...",
+        "字幕のタイミングを音声に合わせています...",
+    ),
+    (
+        "subtitle.resumed",
+        "Resuming: {done}/{total} chunks were already transcribed",
+        "从上次中断处继续：已转录 {done}/{total} 个片段",
+        "前回の続きから再開します：{done}/{total} チャンクは文字起こし済みです",
+    ),
+    (
+        "subtitle.chunk_failed",
+        "Chunk {index} failed: {error}. {done}/{total} chunks were saved; extract again to resume",
+        "片段 {index} 提取失败：{error}。已保存 {done}/{total} 个片段，重新提取将从中断处继续",
+        "チャンク {index} の抽出に失敗しました：{error}。{done}/{total} チャンクを保存済みです。再度抽出すると続きから再開します",
+    ),
+    (
+        "subtitle.merging",
+        "Merging, sorting and deduplicating...",
+        "合并排序去重中...",
+        "結合・並べ替え・重複除去中...",
+    ),
+    (
+        "subtitle.compress",
+        "Optimizing video size...",
+        "正在优化视频体积...",
+        "動画サイズを最適化しています...",
+    ),
+    (
+        "subtitle.encode",
+        "Encoding video data...",
+        "正在编码视频数据...",
+        "動画データをエンコードしています...",
+    ),
+    (
+        "subtitle.kimi_analyze",
+        "Kimi is analyzing the video to generate subtitles...",
+        "Kimi 正在分析视频生成字幕...",
+        "Kimi が動画を解析して字幕を生成しています...",
+    ),
+    (
+        "subtitle.done",
+        "Subtitle extraction completed!",
+        "字幕提取完成！",
+        "字幕の抽出が完了しました！",
+    ),
+    // 文件夹批量导入进度
+    (
+        "import.folder_file",
+        "Importing {current}/{total}: {file}",
+        "正在导入 {current}/{total}：{file}",
+        "インポート中 {current}/{total}: {file}",
+    ),
+    (
+        "import.folder_completed",
+        "Folder import finished: {imported} imported, {failed} failed",
+        "文件夹导入完成：成功 {imported} 个，失败 {failed} 个",
+        "フォルダのインポートが完了しました：成功 {imported} 件、失敗 {failed} 件",
+    ),
+    (
+        "import.channel_video",
+        "Importing {current}/{total}: {title}",
+        "正在导入 {current}/{total}：{title}",
+        "インポート中 {current}/{total}: {title}",
+    ),
+    (
+        "import.channel_completed",
+        "Channel check finished: {imported} new, {skipped} already imported, {failed} failed",
+        "频道检查完成：新导入 {imported} 个，已导入 {skipped} 个，失败 {failed} 个",
+        "チャンネルの確認が完了しました：新規 {imported} 件、取り込み済み {skipped} 件、失敗 {failed} 件",
+    ),
+    // 导入后暂无正文时的占位文本
+    (
+        "article.subtitles_pending",
+        "[Video imported, subtitles not extracted yet] {title}",
+        "[视频已导入，字幕待识别] {title}",
+        "[動画を取り込みました。字幕は未抽出です] {title}",
+    ),
+    (
+        "book.untitled",
+        "Untitled book",
+        "未命名书籍",
+        "無題の本",
+    ),
+    (
+        "book.imported",
+        "[Book imported] {title}",
+        "[书籍已导入] {title}",
+        "[本を取り込みました] {title}",
+    ),
+    (
+        "book.epub",
+        "[EPUB book] {title}",
+        "[EPUB 书籍] {title}",
+        "[EPUB の本] {title}",
+    ),
+    (
+        "book.pdf",
+        "[PDF book] {title}",
+        "[PDF 书籍] {title}",
+        "[PDF の本] {title}",
+    ),
+    // 插件安装进度
+    (
+        "plugin.downloading",
+        "Downloading plugin...",
+        "正在下载插件...",
+        "プラグインをダウンロードしています...",
+    ),
+    (
+        "plugin.download_progress",
+        "Downloading... {percent}%",
+        "下载中... {percent}%",
+        "ダウンロード中... {percent}%",
+    ),
+    (
+        "plugin.extracting",
+        "Extracting and installing...",
+        "正在解压安装...",
+        "展開してインストールしています...",
+    ),
+    (
+        "plugin.installed",
+        "Installation completed!",
+        "安装完成！",
+        "インストールが完了しました！",
+    ),
+    // 任务限流
+    (
+        "job.rate_limited",
+        "Rate limited, resuming at {time}",
+        "触发服务商限流，将于 {time} 自动继续",
+        "レート制限中のため、{time} に再開します",
+    ),
+    // 请求检查
+    (
+        "warning.context_exceeded",
+        "The request (about {tokens} tokens) may exceed the context length of {model} ({limit} tokens)",
+        "请求约 {tokens} 个 token，可能超出 {model} 的上下文长度（{limit}）",
+        "リクエスト（約 {tokens} トークン）が {model} のコンテキスト長（{limit}）を超える可能性があります",
+    ),
+    // 错误信息
+    (
+        "error.offline_mode",
+        "Offline mode is on. Turn it off to use AI and network features.",
+        "离线模式已开启，关闭后才能使用 AI 和联网功能。",
+        "オフラインモードがオンです。AI とネットワーク機能を使うにはオフにしてください。",
+    ),
+    (
+        "error.ai_not_configured",
+        "AI service not initialized. Please configure a model in settings.",
+        "AI 服务未初始化，请先在设置中配置模型。",
+        "AI サービスが初期化されていません。設定でモデルを構成してください。",
+    ),
+    (
+        "error.config_not_found",
+        "Configuration not found",
+        "未找到该配置",
+        "設定が見つかりません",
+    ),
+    (
+        "error.invalid_time_range",
+        "Invalid time range: {start}s - {end}s",
+        "无效的时间范围：{start} 秒 - {end} 秒",
+        "無効な時間範囲です: {start} 秒 - {end} 秒",
+    ),
+    (
+        "error.translation_nothing_to_resume",
+        "There is no unfinished translation for this article",
+        "该文章没有未完成的翻译",
+        "この記事に未完了の翻訳はありません",
+    ),
+    (
+        "error.reading_language_unknown",
+        "Cannot tell which language to annotate. Set the article's learning language first",
+        "无法判断需要注音的语言，请先设置文章的学习语言",
+        "読みを付ける言語を判別できません。先に記事の学習言語を設定してください",
+    ),
+    (
+        "error.local_reading_unsupported",
+        "Local readings are only available for Chinese, Korean, Cyrillic and Arabic script articles",
+        "本地注音仅适用于中文、韩语以及西里尔字母和阿拉伯字母书写的文章",
+        "ローカル注音は中国語・韓国語・キリル文字・アラビア文字の記事でのみ使用できます",
+    ),
+    (
+        "error.segment_not_text",
+        "Image segments cannot be translated",
+        "图片段落无法翻译",
+        "画像の段落は翻訳できません",
+    ),
+    (
+        "error.result_invalid",
+        "The AI returned invalid data ({reason}); it was not saved and can be retried from the quarantine list",
+        "AI 返回的数据不符合要求（{reason}），未保存，可在隔离列表中重试",
+        "AI が返したデータが不正なため（{reason}）保存されませんでした。隔離リストから再試行できます",
+    ),
+    (
+        "error.quarantine_not_found",
+        "Quarantined result not found",
+        "未找到该隔离结果",
+        "隔離された結果が見つかりません",
+    ),
+    (
+        "error.window_article_required",
+        "Choose an article to open in the reader window",
+        "请选择要在阅读器窗口中打开的文章",
+        "リーダーウィンドウで開く記事を選んでください",
+    ),
+    (
+        "error.segment_not_found",
+        "Segment not found",
+        "未找到该段落",
+        "段落が見つかりません",
+    ),
+    (
+        "error.invalid_analysis_type",
+        "Invalid analysis type",
+        "无效的分析类型",
+        "無効な解析タイプです",
+    ),
+    (
+        "error.http_only",
+        "Only HTTP and HTTPS URLs are supported",
+        "仅支持 HTTP 和 HTTPS 链接",
+        "HTTP と HTTPS の URL のみ対応しています",
+    ),
+    (
+        "error.http_status",
+        "HTTP error: {status}",
+        "HTTP 错误: {status}",
+        "HTTP エラー: {status}",
+    ),
+    (
+        "error.no_meaningful_content",
+        "Could not extract meaningful content. The page might be empty or require JavaScript interaction that is not supported.",
+        "未能提取到有效内容。页面可能为空，或需要暂不支持的 JavaScript 交互。",
+        "有効な本文を抽出できませんでした。ページが空か、未対応の JavaScript 操作が必要な可能性があります。",
+    ),
+    (
+        "error.content_too_short",
+        "Extracted content is too short. Please check the URL and try again.",
+        "提取的内容过短，请检查链接后重试。",
+        "抽出された内容が短すぎます。URL を確認して再試行してください。",
+    ),
+    (
+        "error.study_plan_date_invalid",
+        "The target date must be after today",
+        "目标日期必须晚于今天",
+        "目標日は今日より後の日付にしてください",
+    ),
+    (
+        "error.pack_name_required",
+        "Pack name is required",
+        "单词包名称不能为空",
+        "単語帳の名前は必須です",
+    ),
+    (
+        "error.system_pack_delete",
+        "System pack cannot be deleted",
+        "系统单词包不可删除",
+        "システム単語帳は削除できません",
+    ),
+    (
+        "error.pack_merge_same",
+        "Cannot merge a word pack into itself",
+        "不能将单词包合并到自身",
+        "単語帳を自分自身に統合することはできません",
+    ),
+    (
+        "error.word_meaning_required",
+        "Word and meaning are required",
+        "单词和释义不能为空",
+        "単語と意味は必須です",
+    ),
+    (
+        "error.pack_too_large",
+        "Word pack is too large (max 20000 entries)",
+        "单词包过大（最多 20000 条）",
+        "単語帳が大きすぎます（最大 20000 件）",
+    ),
+    (
+        "error.folder_missing",
+        "Folder does not exist: {path}",
+        "文件夹不存在：{path}",
+        "フォルダが存在しません: {path}",
+    ),
+    (
+        "error.folder_no_media",
+        "No video or audio files found in the folder",
+        "文件夹中没有找到视频或音频文件",
+        "フォルダに動画または音声ファイルが見つかりません",
+    ),
+    (
+        "error.source_file_missing",
+        "Source file does not exist",
+        "源文件不存在",
+        "元のファイルが存在しません",
+    ),
+    (
+        "error.file_missing",
+        "File does not exist: {path}",
+        "文件不存在: {path}",
+        "ファイルが存在しません: {path}",
+    ),
+    (
+        "error.video_missing",
+        "Video file does not exist: {path}",
+        "视频文件不存在: {path}",
+        "動画ファイルが存在しません: {path}",
+    ),
+    (
+        "error.unsupported_format",
+        "Unsupported file format: {ext}",
+        "不支持的文件格式: {ext}",
+        "対応していないファイル形式です: {ext}",
+    ),
+    (
+        "error.subtitle_local_model",
+        "Subtitle extraction does not support Ollama / LM Studio local models yet. Please switch to Gemini or Kimi K2.5.",
+        "字幕提取暂不支持 Ollama / LM Studio 本地模型。请切换到 Gemini 或 Kimi K2.5。",
+        "字幕抽出は Ollama / LM Studio のローカルモデルに未対応です。Gemini または Kimi K2.5 に切り替えてください。",
+    ),
+    (
+        "error.subtitle_model_unsupported",
+        "Subtitle extraction requires a Gemini or Kimi K2.5 cloud model. Please switch models in settings.",
+        "字幕提取需要使用 Gemini 或 Kimi K2.5 云端模型。请在设置中切换模型。",
+        "字幕抽出には Gemini または Kimi K2.5 のクラウドモデルが必要です。設定でモデルを切り替えてください。",
+    ),
+    (
+        "error.transcription_engine_unknown",
+        "Unknown transcription engine: {engine}",
+        "未知的字幕转录引擎: {engine}",
+        "不明な文字起こしエンジンです: {engine}",
+    ),
+    (
+        "error.transcription_plugin_missing",
+        "The plugin transcription engine requires a plugin name in settings.",
+        "插件转录引擎需要在设置中指定插件名称。",
+        "プラグイン文字起こしエンジンには設定でプラグイン名を指定する必要があります。",
+    ),
+    (
+        "error.subtitle_file_empty",
+        "No subtitle lines found in the file",
+        "字幕文件中没有找到字幕内容",
+        "字幕ファイルに字幕が見つかりません",
+    ),
+    (
+        "error.subtitle_extraction_running",
+        "Subtitles are already being extracted for this article",
+        "这篇文章正在提取字幕",
+        "この記事の字幕はすでに抽出中です",
+    ),
+    (
+        "error.subtitle_target_no_media",
+        "Subtitles can only be attached to a video or audio article",
+        "字幕只能关联到视频或音频文章",
+        "字幕は動画または音声の記事にのみ関連付けできます",
+    ),
+    (
+        "error.channel_url_empty",
+        "Please enter a channel URL",
+        "请输入频道地址",
+        "チャンネルの URL を入力してください",
+    ),
+    (
+        "error.channel_list_failed",
+        "Could not list the channel's videos: {error}",
+        "获取频道视频列表失败：{error}",
+        "チャンネルの動画一覧を取得できませんでした：{error}",
+    ),
+    (
+        "error.media_url_empty",
+        "Please enter a video or audio URL",
+        "请输入视频或音频地址",
+        "動画または音声の URL を入力してください",
+    ),
+    (
+        "error.media_url_unsupported",
+        "This site is not supported for media downloads",
+        "不支持从该网站下载媒体",
+        "このサイトからのメディアのダウンロードには対応していません",
+    ),
+    (
+        "error.channel_subscription_not_found",
+        "Channel subscription not found",
+        "没有找到该频道订阅",
+        "チャンネルの購読が見つかりません",
+    ),
+    (
+        "error.nothing_to_undo",
+        "There is nothing to undo",
+        "没有可撤销的操作",
+        "元に戻せる操作はありません",
+    ),
+    (
+        "error.embedded_subtitle_missing",
+        "Subtitle track {index} was not found in the video",
+        "视频中没有找到字幕轨 {index}",
+        "動画に字幕トラック {index} が見つかりません",
+    ),
+    (
+        "error.embedded_subtitle_bitmap",
+        "The {codec} track is an image-based subtitle and cannot be extracted as text",
+        "{codec} 字幕轨是图形字幕，无法提取为文本",
+        "{codec} トラックは画像字幕のため、テキストとして抽出できません",
+    ),
+    (
+        "error.embedded_subtitle_extract_failed",
+        "FFmpeg could not extract the subtitle track: {error}",
+        "FFmpeg 字幕提取失败：{error}",
+        "FFmpeg で字幕トラックを抽出できませんでした：{error}",
+    ),
+    (
+        "error.invalid_file_path",
+        "Invalid file path",
+        "无效的文件路径",
+        "無効なファイルパスです",
+    ),
+    (
+        "error.ffmpeg_sidecar",
+        "Could not start FFmpeg: {error}. Please check the sidecar configuration",
+        "无法创建 FFmpeg sidecar：{error}。请确保 sidecar 配置正确",
+        "FFmpeg を起動できませんでした：{error}。sidecar の設定を確認してください",
+    ),
+    (
+        "error.ffmpeg_failed",
+        "FFmpeg failed to run: {error}. Please make sure FFmpeg is installed",
+        "FFmpeg 执行失败：{error}。请确保已安装 FFmpeg",
+        "FFmpeg の実行に失敗しました：{error}。FFmpeg がインストールされているか確認してください",
+    ),
+    (
+        "error.article_not_timed",
+        "This article has no timed segments",
+        "这篇文章没有带时间轴的段落",
+        "この記事にはタイム付きの段落がありません",
+    ),
+    (
+        "error.timing_invalid",
+        "The offset must be a number and the scale factor must be greater than 0",
+        "偏移量必须是数字，缩放系数必须大于 0",
+        "オフセットは数値、倍率は 0 より大きい値にしてください",
+    ),
+    (
+        "error.no_timed_segments",
+        "This article has no timed segments to export as subtitles",
+        "这篇文章没有带时间轴的段落，无法导出字幕",
+        "この記事には字幕として書き出せるタイム付きの段落がありません",
+    ),
+    (
+        "error.waveform_requires_media",
+        "Only video or audio articles have a waveform",
+        "只有视频或音频文章才有波形",
+        "波形を表示できるのは動画または音声の記事のみです",
+    ),
+    (
+        "error.audio_decode_failed",
+        "FFmpeg could not decode the audio: {error}",
+        "FFmpeg 音频解码失败：{error}",
+        "FFmpeg で音声をデコードできませんでした：{error}",
+    ),
+    (
+        "error.burn_requires_video",
+        "Subtitles can only be burned into a video article",
+        "只能为视频文章烧录字幕",
+        "字幕を焼き込めるのは動画の記事のみです",
+    ),
+    (
+        "error.subtitle_burn_failed",
+        "FFmpeg could not burn the subtitles: {error}",
+        "FFmpeg 字幕烧录失败：{error}",
+        "FFmpeg で字幕を焼き込めませんでした：{error}",
+    ),
+    (
+        "error.merge_needs_two_articles",
+        "Select at least two articles to merge",
+        "请至少选择两篇文章进行合并",
+        "結合するには記事を 2 つ以上選択してください",
+    ),
+    (
+        "error.article_no_segments",
+        "The article has no segments yet",
+        "文章还没有分段",
+        "記事にはまだ段落がありません",
+    ),
+    (
+        "error.alignment_requires_media",
+        "Only video or audio articles can be aligned to the audio",
+        "只有视频或音频文章才能与音频对齐",
+        "音声と位置合わせできるのは動画または音声の記事のみです",
+    ),
+    (
+        "error.alignment_no_words",
+        "The local whisper CLI returned no word timestamps",
+        "本地 whisper 没有返回单词时间戳",
+        "ローカルの whisper から単語のタイムスタンプが返されませんでした",
+    ),
+    (
+        "error.alignment_failed",
+        "The audio does not match the article text, so no segment could be aligned",
+        "音频与文章内容不一致，没有段落能够对齐",
+        "音声が記事の本文と一致しないため、段落を対応付けられませんでした",
+    ),
+    (
+        "error.whisper_command",
+        "Could not run the whisper command '{command}': {error}",
+        "无法执行 whisper 命令 '{command}'：{error}",
+        "whisper コマンド '{command}' を実行できませんでした：{error}",
+    ),
+    (
+        "error.whisper_alignment_failed",
+        "whisper alignment failed: {error}",
+        "whisper 对齐失败：{error}",
+        "whisper による位置合わせに失敗しました：{error}",
+    ),
+    (
+        "error.whisper_output_unreadable",
+        "Could not read the whisper output: {error}",
+        "读取 whisper 输出失败：{error}",
+        "whisper の出力を読み込めませんでした：{error}",
+    ),
+    (
+        "error.whisper_output_invalid",
+        "Could not parse the whisper output: {error}",
+        "解析 whisper 输出失败：{error}",
+        "whisper の出力を解析できませんでした：{error}",
+    ),
+    (
+        "error.no_subtitles",
+        "No subtitles could be extracted from the video",
+        "未能从视频中提取到字幕内容",
+        "動画から字幕を抽出できませんでした",
+    ),
+    (
+        "error.subtitle_list_failed",
+        "Could not list the video's subtitles: {error}",
+        "获取字幕列表失败：{error}",
+        "字幕一覧を取得できませんでした：{error}",
+    ),
+    (
+        "error.video_unavailable",
+        "Video unavailable. It may be private or deleted.",
+        "视频不可用，可能是私有视频或已被删除",
+        "動画を利用できません。非公開または削除された可能性があります。",
+    ),
+    (
+        "error.video_login_required",
+        "This video requires signing in to watch. Set a browser or cookies.txt for video downloads in Settings and try again",
+        "此视频需要登录才能观看，请在设置中为视频下载指定浏览器或 cookies.txt 后重试",
+        "この動画の視聴にはログインが必要です。設定で動画ダウンロード用のブラウザまたは cookies.txt を指定してから再試行してください",
+    ),
+    (
+        "error.ffmpeg_required",
+        "FFmpeg is required to download this video. Please install it and try again.",
+        "需要安装 FFmpeg 才能下载此视频。请安装后重试。",
+        "この動画のダウンロードには FFmpeg が必要です。インストールして再試行してください。",
+    ),
+    (
+        "error.parallel_missing_separator",
+        "Line {line} has no tab or || separator between source and translation",
+        "第 {line} 行缺少原文与译文之间的制表符或 || 分隔符",
+        "{line} 行目に原文と訳文を区切るタブまたは || がありません",
+    ),
+    (
+        "error.parallel_line_mismatch",
+        "Line counts do not match: source has {source} lines, translation has {translation}",
+        "行数不一致：原文 {source} 行，译文 {translation} 行",
+        "行数が一致しません：原文 {source} 行、訳文 {translation} 行",
+    ),
+    (
+        "error.pdf_pairs_missing",
+        "No paragraph pairs found for this PDF. Translate it again with the latest PDF translator plugin",
+        "未找到该 PDF 的段落对照数据，请使用最新版 PDF 翻译插件重新翻译",
+        "この PDF の段落対照データが見つかりません。最新の PDF 翻訳プラグインで再翻訳してください",
+    ),
+    (
+        "error.parallel_empty",
+        "No aligned sentences found",
+        "未找到可对齐的句子",
+        "対応する文が見つかりません",
+    ),
+    (
+        "error.cancelled",
+        "Operation cancelled",
+        "操作已取消",
+        "操作はキャンセルされました",
+    ),
+    (
+        "error.prompt_template_empty",
+        "The prompt template cannot be empty",
+        "提示词模板不能为空",
+        "プロンプトテンプレートを空にすることはできません",
+    ),
+    (
+        "error.prompt_template_placeholder",
+        "Unsupported placeholder in template: {name}",
+        "模板中包含不支持的占位符: {name}",
+        "テンプレートに未対応のプレースホルダーがあります: {name}",
+    ),
+    (
+        "error.glossary_empty",
+        "Both the term and its translation are required",
+        "术语和译法都不能为空",
+        "用語と訳語の両方が必要です",
+    ),
+    (
+        "error.tag_rule_empty",
+        "Both the pattern and the tag are required",
+        "匹配内容和标签都不能为空",
+        "条件とタグの両方が必要です",
+    ),
+    (
+        "error.tag_rule_not_found",
+        "Tag rule not found",
+        "未找到该标签规则",
+        "タグルールが見つかりません",
+    ),
+    (
+        "error.export_template_not_found",
+        "Export template not found",
+        "未找到该导出模板",
+        "エクスポートテンプレートが見つかりません",
+    ),
+    (
+        "error.export_template_empty",
+        "The template name and content are required",
+        "模板名称和内容都不能为空",
+        "テンプレート名と内容の両方が必要です",
+    ),
+    (
+        "error.export_template_invalid",
+        "Template syntax error: {error}",
+        "模板语法错误：{error}",
+        "テンプレートの構文エラー: {error}",
+    ),
+    (
+        "error.export_template_kind",
+        "This template cannot be used for this export",
+        "该模板不能用于此类导出",
+        "このテンプレートはこのエクスポートには使えません",
+    ),
+    (
+        "error.glossary_not_found",
+        "Glossary entry not found",
+        "未找到该术语",
+        "用語が見つかりません",
+    ),
+    (
+        "error.no_explanations_to_export",
+        "This article has no explanations with vocabulary or grammar to export yet",
+        "该文章还没有可导出的词汇或语法讲解",
+        "この記事にはまだエクスポートできる語彙や文法の解説がありません",
+    ),
+    (
+        "error.segment_split_invalid",
+        "Cannot split the segment at this position",
+        "无法在该位置拆分段落",
+        "この位置では段落を分割できません",
+    ),
+    (
+        "error.segment_merge_invalid",
+        "Select at least two adjacent text segments to merge",
+        "请选择至少两个相邻的文字段落进行合并",
+        "結合するには隣接するテキスト段落を 2 つ以上選択してください",
+    ),
+    (
+        "error.segment_position_invalid",
+        "Cannot move or insert a segment at this position",
+        "无法在该位置移动或插入段落",
+        "この位置では段落を移動・挿入できません",
+    ),
+    (
+        "error.segment_length_invalid",
+        "Segment length must be greater than 0",
+        "分段长度必须大于 0",
+        "分割の長さは 0 より大きくしてください",
+    ),
+    (
+        "error.language_undetected",
+        "Could not detect the language of this article",
+        "无法识别该文章的语言",
+        "この記事の言語を判定できません",
+    ),
+    (
+        "error.grammar_not_found",
+        "Saved grammar point not found",
+        "未找到该语法收藏",
+        "保存した文法が見つかりません",
+    ),
+    (
+        "error.revision_not_found",
+        "Revision not found",
+        "未找到该修订版本",
+        "該当する版が見つかりません",
+    ),
+    (
+        "error.recap_not_found",
+        "No recap has been generated for this article yet",
+        "该文章尚未生成学习总结",
+        "この記事の学習まとめはまだ作成されていません",
+    ),
+    (
+        "error.plugin_release_not_found",
+        "No release containing the plugin was found",
+        "未找到包含插件的发布版本",
+        "プラグインを含むリリースが見つかりません",
+    ),
+    (
+        "error.download_http_status",
+        "Download failed: HTTP {status}",
+        "下载失败: HTTP {status}",
+        "ダウンロード失敗: HTTP {status}",
+    ),
+    (
+        "error.media_duration_unknown",
+        "Could not read the media duration from FFmpeg output: {error}",
+        "无法从 FFmpeg 输出中解析媒体时长：{error}",
+        "FFmpeg の出力からメディアの長さを取得できませんでした：{error}",
+    ),
+    (
+        "error.audio_extract_failed",
+        "FFmpeg could not extract the audio: {error}",
+        "FFmpeg 音频提取失败：{error}",
+        "FFmpeg で音声を抽出できませんでした：{error}",
+    ),
+    (
+        "error.audio_output_missing",
+        "FFmpeg did not produce an audio file",
+        "音频文件未生成",
+        "音声ファイルが生成されませんでした",
+    ),
+    (
+        "error.video_compress_failed",
+        "FFmpeg could not compress the video: {error}",
+        "FFmpeg 压缩视频失败：{error}",
+        "FFmpeg で動画を圧縮できませんでした：{error}",
+    ),
+    (
+        "error.video_output_missing",
+        "FFmpeg did not produce the compressed video",
+        "压缩后的视频文件未生成",
+        "圧縮した動画ファイルが生成されませんでした",
+    ),
+    (
+        "error.transcript_invalid",
+        "Could not parse the transcription result: {error}",
+        "解析转录结果失败：{error}",
+        "文字起こしの結果を解析できませんでした：{error}",
+    ),
+    (
+        "error.media_read_failed",
+        "Could not read the media file: {error}",
+        "读取媒体文件失败：{error}",
+        "メディアファイルを読み込めませんでした：{error}",
+    ),
+    (
+        "error.upload_build_failed",
+        "Could not prepare the upload: {error}",
+        "构建上传数据失败：{error}",
+        "アップロードデータを作成できませんでした：{error}",
+    ),
+    (
+        "error.api_request_failed",
+        "API request failed: {error}",
+        "API 请求失败：{error}",
+        "API リクエストに失敗しました：{error}",
+    ),
+    (
+        "error.api_response_invalid",
+        "Could not parse the response: {error}",
+        "解析响应失败：{error}",
+        "レスポンスを解析できませんでした：{error}",
+    ),
+    (
+        "error.kimi_analysis_failed",
+        "Kimi could not analyze the video: {error}",
+        "Kimi 分析失败：{error}",
+        "Kimi による分析に失敗しました：{error}",
+    ),
+    (
+        "error.whisper_transcription_failed",
+        "whisper transcription failed: {error}",
+        "whisper 转录失败：{error}",
+        "whisper による文字起こしに失敗しました：{error}",
+    ),
+    (
+        "error.retries_exhausted",
+        "Still failing after several retries: {error}",
+        "多次重试后仍然失败：{error}",
+        "何度か再試行しましたが失敗しました：{error}",
+    ),
+    (
+        "error.network_request_failed",
+        "Network request failed: {error}",
+        "网络请求失败：{error}",
+        "ネットワークリクエストに失敗しました：{error}",
+    ),
+    (
+        "error.github_api_status",
+        "GitHub API returned an error: {status}",
+        "GitHub API 返回错误：{status}",
+        "GitHub API がエラーを返しました：{status}",
+    ),
+    (
+        "error.plugin_download_missing",
+        "The release has no download link for the plugin",
+        "未找到下载链接",
+        "プラグインのダウンロードリンクが見つかりません",
+    ),
+    (
+        "error.create_dir_failed",
+        "Could not create the folder: {error}",
+        "创建目录失败：{error}",
+        "フォルダを作成できませんでした：{error}",
+    ),
+    (
+        "error.download_failed",
+        "Download failed: {error}",
+        "下载失败：{error}",
+        "ダウンロードに失敗しました：{error}",
+    ),
+    (
+        "error.download_interrupted",
+        "Download interrupted: {error}",
+        "下载中断：{error}",
+        "ダウンロードが中断されました：{error}",
+    ),
+    (
+        "error.plugin_archive_invalid",
+        "Could not open the plugin archive: {error}",
+        "无法打开插件压缩包：{error}",
+        "プラグインのアーカイブを開けませんでした：{error}",
+    ),
+    (
+        "error.write_file_failed",
+        "Could not write the file: {error}",
+        "写入文件失败：{error}",
+        "ファイルを書き込めませんでした：{error}",
+    ),
+    (
+        "error.plugin_install_failed",
+        "Could not set up the plugin executable: {error}",
+        "设置插件可执行文件失败：{error}",
+        "プラグインの実行ファイルを設定できませんでした：{error}",
+    ),
+    (
+        "error.video_download_failed",
+        "Video download failed: {error}",
+        "视频下载失败：{error}",
+        "動画のダウンロードに失敗しました：{error}",
+    ),
+    (
+        "error.video_info_missing",
+        "Could not get the video information",
+        "无法获取视频信息",
+        "動画の情報を取得できませんでした",
+    ),
+    (
+        "error.video_unreadable",
+        "Could not read the video file: {error}",
+        "无法读取视频文件：{error}",
+        "動画ファイルを読み込めませんでした：{error}",
+    ),
+    (
+        "error.video_file_missing",
+        "No downloaded file was found for video {id}",
+        "未找到视频文件：{id}",
+        "動画 {id} のファイルが見つかりません",
+    ),
+    (
+        "error.subtitle_requires_media",
+        "Subtitles can only be extracted from a video or audio article",
+        "该文章不是视频，无法提取字幕",
+        "字幕を抽出できるのは動画または音声の記事のみです",
+    ),
+    (
+        "error.app_data_dir_failed",
+        "Could not locate the app data folder: {error}",
+        "获取应用数据目录失败：{error}",
+        "アプリのデータフォルダを取得できませんでした：{error}",
+    ),
+    (
+        "error.file_format_unknown",
+        "Could not determine the file format",
+        "无法识别文件格式",
+        "ファイル形式を判別できません",
+    ),
+    (
+        "error.copy_file_failed",
+        "Could not copy the file: {error}",
+        "复制文件失败：{error}",
+        "ファイルをコピーできませんでした：{error}",
+    ),
+    (
+        "error.serialize_failed",
+        "Could not save the article: {error}",
+        "序列化文章失败：{error}",
+        "記事を保存できませんでした：{error}",
+    ),
+    (
+        "error.tts_audio_read_failed",
+        "Could not read the synthesized audio: {error}",
+        "读取合成音频失败：{error}",
+        "合成音声を読み込めませんでした：{error}",
+    ),
+    (
+        "error.tts_empty_audio",
+        "The speech API returned no audio",
+        "语音合成接口未返回音频",
+        "音声合成 API から音声が返されませんでした",
+    ),
+];
+
+/// 按当前界面语言获取提示信息，未知 key 原样返回
+pub fn tr(key: &str) -> String {
+    translate(current_language(), key)
+}
+
+/// 获取提示信息并替换 `{name}` 占位符
+pub fn tr_with(key: &str, args: &[(&str, String)]) -> String {
+    let mut message = tr(key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    message
+}
+
+/// 按指定语言获取提示信息
+pub fn translate(lang: Lang, key: &str) -> String {
+    CATALOG
+        .iter()
+        .find(|(k, _, _, _)| *k == key)
+        .map(|(_, en, zh, ja)| match lang {
+            Lang::En => *en,
+            Lang::Zh => *zh,
+            Lang::Ja => *ja,
+        })
+        .unwrap_or(key)
+        .to_string()
+}
@@ -0,0 +1,215 @@
+// 长时间运行任务（整篇翻译、文章分析、字幕提取）的取消支持
+// 前端发起任务时传入 job_id，之后可通过 cancel_job_cmd 取消
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::AppError;
+use crate::i18n::{tr, tr_with};
+
+/// 区分同一 job_id 的先后两次运行
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 正在运行的任务，作为 Tauri 托管状态
+#[derive(Clone, Default)]
+pub struct JobRegistry(Arc<Mutex<HashMap<String, (u64, CancellationToken)>>>);
+
+impl JobRegistry {
+    /// 注册任务；未提供 job_id 时任务仍可运行，只是无法从前端取消
+    pub fn register(&self, job_id: Option<String>) -> JobGuard {
+        let token = CancellationToken::new();
+        let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+        if let Some(id) = &job_id {
+            if let Ok(mut jobs) = self.0.lock() {
+                // 同一 job_id 重复发起时，取消旧任务
+                if let Some((_, previous)) = jobs.insert(id.clone(), (run_id, token.clone())) {
+                    previous.cancel();
+                }
+            }
+        }
+        JobGuard {
+            registry: self.clone(),
+            job_id,
+            run_id,
+            token,
+        }
+    }
+
+    /// 注册任务；同一 job_id 已有任务在运行时返回 None，不影响正在运行的任务
+    pub fn try_register(&self, job_id: String) -> Option<JobGuard> {
+        let token = CancellationToken::new();
+        let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+        let mut jobs = self.0.lock().ok()?;
+        if jobs.contains_key(&job_id) {
+            return None;
+        }
+        jobs.insert(job_id.clone(), (run_id, token.clone()));
+        Some(JobGuard {
+            registry: self.clone(),
+            job_id: Some(job_id),
+            run_id,
+            token,
+        })
+    }
+
+    /// 取消任务，返回任务是否存在
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.0.lock() {
+            Ok(jobs) => match jobs.get(job_id) {
+                Some((_, token)) => {
+                    token.cancel();
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+/// 任务结束（含出错、被取消）时自动从注册表移除
+pub struct JobGuard {
+    registry: JobRegistry,
+    job_id: Option<String>,
+    run_id: u64,
+    token: CancellationToken,
+}
+
+impl JobGuard {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let Some(id) = &self.job_id else {
+            return;
+        };
+        if let Ok(mut jobs) = self.registry.0.lock() {
+            // 只移除本次运行，避免误删同一 job_id 的新任务
+            if jobs
+                .get(id)
+                .is_some_and(|(run_id, _)| *run_id == self.run_id)
+            {
+                jobs.remove(id);
+            }
+        }
+    }
+}
+
+/// 任务被取消时返回的错误信息
+pub fn cancelled_message() -> String {
+    tr("error.cancelled")
+}
+
+/// 任务中内部函数返回的字符串错误：任务已被取消时归为 Cancelled，否则交给 convert 构造
+pub fn job_error(
+    token: &CancellationToken,
+    message: String,
+    convert: impl FnOnce(String) -> AppError,
+) -> AppError {
+    if token.is_cancelled() {
+        AppError::cancelled(message)
+    } else {
+        convert(message)
+    }
+}
+
+/// 运行 future，任务被取消时立即放弃（正在进行的请求随 future 一起被丢弃）
+pub async fn run_cancellable<T>(
+    token: &CancellationToken,
+    future: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    tokio::select! {
+        result = future => result,
+        _ = token.cancelled() => Err(cancelled_message()),
+    }
+}
+
+/// 限流错误信息的标记，只有 rate_limit_error 组装的信息带有该标记
+const RATE_LIMIT_MARKER: &str = "429 Too Many Requests";
+/// 限流错误信息中携带 Retry-After 秒数的标记
+const RETRY_AFTER_MARKER: &str = "[retry-after: ";
+/// 单次限流等待的上限，服务商给出更长时间时按失败处理
+pub const MAX_RATE_LIMIT_WAIT_SECS: u64 = 15 * 60;
+/// 同一批/同一任务最多因限流暂停的次数
+pub const MAX_RATE_LIMIT_WAITS: u32 = 5;
+
+/// 解析 Retry-After 响应头：秒数或 HTTP 日期
+pub fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .num_seconds()
+            .max(0) as u64,
+    )
+}
+
+/// 组装 429 错误信息，有 Retry-After 时附带秒数
+pub fn rate_limit_error(label: &str, retry_after: Option<u64>, body: &str) -> String {
+    match retry_after {
+        Some(secs) => format!(
+            "{} error: {} {}{}s] {}",
+            label, RATE_LIMIT_MARKER, RETRY_AFTER_MARKER, secs, body
+        ),
+        None => format!("{} error: {} {}", label, RATE_LIMIT_MARKER, body),
+    }
+}
+
+/// 是否为 rate_limit_error 组装的限流错误
+pub fn is_rate_limit_error(message: &str) -> bool {
+    message.contains(&format!("error: {}", RATE_LIMIT_MARKER))
+}
+
+/// 从错误信息中取出 Retry-After 秒数
+pub fn retry_after_secs(message: &str) -> Option<u64> {
+    let start = message.find(RETRY_AFTER_MARKER)? + RETRY_AFTER_MARKER.len();
+    let rest = &message[start..];
+    rest[..rest.find("s]")?].parse().ok()
+}
+
+/// 服务商限流并给出 Retry-After 时暂停任务：发送 job://rate-limited 事件（含恢复时间）后等待，
+/// 返回 true 表示已等待完毕可以重试；不是可等待的限流错误、等待过长或任务被取消时返回 false
+pub async fn wait_for_rate_limit(
+    app_handle: &AppHandle,
+    token: &CancellationToken,
+    job: &str,
+    error: &str,
+) -> bool {
+    let Some(secs) = retry_after_secs(error).filter(|&secs| secs <= MAX_RATE_LIMIT_WAIT_SECS)
+    else {
+        return false;
+    };
+    // 至少等一秒，避免 Retry-After: 0 时立即重试
+    let secs = secs.max(1);
+    let resume_at = (chrono::Local::now() + chrono::Duration::seconds(secs as i64))
+        .format("%H:%M")
+        .to_string();
+    println!("[Jobs] {} rate limited, resuming in {}s", job, secs);
+    let _ = app_handle.emit(
+        "job://rate-limited",
+        serde_json::json!({
+            "job": job,
+            "retry_after_secs": secs,
+            "resume_at": resume_at,
+            "message": tr_with("job.rate_limited", &[("time", resume_at.clone())]),
+        }),
+    );
+
+    let _ = run_cancellable(token, async {
+        tokio::time::sleep(Duration::from_secs(secs)).await;
+        Ok(())
+    })
+    .await;
+    !token.is_cancelled()
+}
@@ -0,0 +1,207 @@
+// 原文语言检测（本地启发式）
+// 先按文字系统统计字符数，拉丁字母文本再用常见虚词区分具体语言；
+// 检测结果写入 Article.language，用于选择分句规则和翻译提示
+
+/// 只取开头的若干字母参与判断，长文（如整本 TXT）也能很快完成
+const SAMPLE_LETTERS: usize = 4000;
+
+/// 拉丁字母语言的常见虚词，命中次数最多的语言胜出
+const LATIN_STOPWORDS: [(&str, &[&str]); 6] = [
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "was", "with", "for",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "des", "une", "du", "que", "dans", "pour", "pas",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "ich", "mit", "sie", "auf",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "del", "que", "una", "por", "con", "para", "está",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "di", "che", "è", "della", "una", "non", "per", "sono", "gli", "nel", "anche",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "e", "não", "uma", "do", "da", "em", "que", "com", "para", "está",
+        ],
+    ),
+];
+
+#[derive(Default)]
+struct ScriptCounts {
+    latin: usize,
+    han: usize,
+    kana: usize,
+    hangul: usize,
+    cyrillic: usize,
+    arabic: usize,
+    thai: usize,
+    devanagari: usize,
+    greek: usize,
+    hebrew: usize,
+}
+
+/// 检测文本的语言，返回 ISO 639-1 代码；文本太短或无法判断时返回 None
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let mut counts = ScriptCounts::default();
+    for c in text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .take(SAMPLE_LETTERS)
+    {
+        match c {
+            '\u{3040}'..='\u{30ff}' => counts.kana += 1,
+            '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' => counts.hangul += 1,
+            '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}' => counts.han += 1,
+            '\u{0400}'..='\u{04ff}' => counts.cyrillic += 1,
+            '\u{0600}'..='\u{06ff}' => counts.arabic += 1,
+            '\u{0e00}'..='\u{0e7f}' => counts.thai += 1,
+            '\u{0900}'..='\u{097f}' => counts.devanagari += 1,
+            '\u{0370}'..='\u{03ff}' => counts.greek += 1,
+            '\u{0590}'..='\u{05ff}' => counts.hebrew += 1,
+            c if c.is_ascii_alphabetic() || ('\u{00c0}'..='\u{024f}').contains(&c) => {
+                counts.latin += 1
+            }
+            _ => {}
+        }
+    }
+
+    // 中日文合并统计，再按假名占比区分（日文汉字之间几乎总夹着假名）
+    let cjk = counts.han + counts.kana;
+    let scripts = [
+        ("cjk", cjk),
+        ("ko", counts.hangul),
+        ("cyrillic", counts.cyrillic),
+        ("arabic", counts.arabic),
+        ("th", counts.thai),
+        ("hi", counts.devanagari),
+        ("el", counts.greek),
+        ("he", counts.hebrew),
+        ("latin", counts.latin),
+    ];
+    let (script, count) =
+        scripts.into_iter().fold(
+            ("", 0),
+            |best, item| if item.1 > best.1 { item } else { best },
+        );
+    if count == 0 {
+        return None;
+    }
+
+    match script {
+        "cjk" if counts.kana * 10 >= cjk => Some("ja"),
+        "cjk" => Some("zh"),
+        // 乌克兰语特有字母
+        "cyrillic" if text.contains(['і', 'ї', 'є', 'ґ']) => Some("uk"),
+        "cyrillic" => Some("ru"),
+        // 波斯语特有字母
+        "arabic" if text.contains(['پ', 'چ', 'ژ', 'گ']) => Some("fa"),
+        "arabic" => Some("ar"),
+        "latin" => detect_latin_language(text),
+        other => Some(other),
+    }
+}
+
+fn detect_latin_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .take(SAMPLE_LETTERS / 4)
+        .map(str::to_lowercase)
+        .collect();
+
+    LATIN_STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|w| stopwords.contains(&w.as_str()))
+                .count();
+            (*language, hits)
+        })
+        .fold(
+            None,
+            |best: Option<(&str, usize)>, (language, hits)| match best {
+                Some((_, best_hits)) if best_hits >= hits => best,
+                _ if hits > 0 => Some((language, hits)),
+                _ => best,
+            },
+        )
+        .map(|(language, _)| language)
+}
+
+/// 语言代码对应的英文名称，用于提示词；未收录的代码原样返回
+pub fn language_name(code: &str) -> &str {
+    match code.split(['-', '_']).next().unwrap_or_default() {
+        "en" => "English",
+        "ja" => "Japanese",
+        "zh" => "Chinese",
+        "ko" => "Korean",
+        "fr" => "French",
+        "de" => "German",
+        "es" => "Spanish",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "ru" => "Russian",
+        "uk" => "Ukrainian",
+        "ar" => "Arabic",
+        "fa" => "Persian",
+        "th" => "Thai",
+        "hi" => "Hindi",
+        "el" => "Greek",
+        "he" => "Hebrew",
+        _ => code,
+    }
+}
+
+/// 分句规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceRules {
+    /// 半角句点以外的句末标点
+    pub terminators: &'static [char],
+    /// 半角句点只在后接空白或段尾时断句（中日文里的半角句点多出现在数字、网址和缩写中）
+    pub period_needs_space: bool,
+}
+
+/// 按原文语言选择分句规则，未知语言使用通用规则
+pub fn sentence_rules(language: Option<&str>) -> SentenceRules {
+    let language = language
+        .and_then(|l| l.split(['-', '_']).next())
+        .unwrap_or_default();
+    match language {
+        "zh" | "ja" => SentenceRules {
+            terminators: &['。', '？', '！', '?', '!'],
+            period_needs_space: true,
+        },
+        "ar" | "fa" | "ur" => SentenceRules {
+            terminators: &['؟', '۔', '?', '!'],
+            period_needs_space: false,
+        },
+        "hi" => SentenceRules {
+            terminators: &['।', '॥', '?', '!'],
+            period_needs_space: false,
+        },
+        _ => SentenceRules {
+            terminators: &['。', '？', '！', '?', '!'],
+            period_needs_space: false,
+        },
+    }
+}
@@ -1,12 +1,55 @@
 // Modules
 mod ai_service;
+pub mod anki_export;
+pub mod article_merge;
+pub mod article_upgrade;
+pub mod audio_alignment;
+pub mod card_bundle;
 pub mod commands;
+pub mod corpus_counts;
+pub mod docx_import;
+pub mod embedded_subtitles;
+pub mod error;
+pub mod export_templates;
+pub mod extraction_rules;
+pub mod folder_watcher;
+pub mod forced_alignment;
+pub mod glossary;
+pub mod health;
+pub mod i18n;
+pub mod jobs;
+pub mod language_detect;
+pub mod markdown_import;
+pub mod model_metadata;
+pub mod obsidian_export;
+pub mod offline;
+pub mod parallel_text;
 mod plugin_manager;
+pub mod prompt_templates;
+pub mod readings;
+pub mod result_validation;
+pub mod segmentation;
+pub mod segmentation_plugin;
 mod storage;
+pub mod subtitle_burn;
+pub mod subtitle_checkpoint;
 mod subtitle_extraction;
+pub mod subtitle_files;
+pub mod tag_rules;
+pub mod text_diff;
+pub mod transcription;
+pub mod translation_memory;
+pub mod translation_qa;
+pub mod tts;
 pub mod types;
+pub mod undo;
 mod video_server;
-mod youtube;
+pub mod vocabulary_images;
+pub mod waveform;
+mod web_images;
+pub mod windows;
+pub mod word_density;
+pub mod youtube;
 
 // Re-exports
 use ai_service::AIServiceCache;
@@ -19,17 +62,33 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(AIServiceCache::default())
+        .manage(jobs::JobRegistry::default())
+        .manage(folder_watcher::FolderWatcher::default())
+        .manage(windows::WindowRegistry::default())
+        .manage(undo::UndoJournal::default())
         .invoke_handler(tauri::generate_handler![
             // App initialization
             commands::init_app,
+            // 提示词模板
+            commands::list_prompt_templates_cmd,
+            commands::save_prompt_template_cmd,
+            commands::reset_prompt_template_cmd,
             // Configuration
             commands::get_config,
             commands::save_config_cmd,
+            commands::set_offline_mode_cmd,
+            commands::get_offline_mode_cmd,
+            // 独立窗口
+            commands::open_window_cmd,
+            commands::get_window_route_cmd,
+            commands::set_window_article_cmd,
+            commands::list_windows_cmd,
             commands::set_api_key,
             commands::save_model_config,
             commands::delete_model_config,
             commands::set_active_model_config,
             commands::get_active_model_config,
+            commands::set_watch_folder_cmd,
             // Articles
             commands::create_article,
             commands::resegment_article,
@@ -37,41 +96,120 @@ pub fn run() {
             commands::list_articles_cmd,
             commands::update_article,
             commands::update_article_segment,
+            commands::split_segment_cmd,
+            commands::merge_segments_cmd,
+            commands::insert_segment_cmd,
+            commands::delete_segment_cmd,
+            commands::move_segment_cmd,
             commands::delete_article_cmd,
+            commands::merge_articles_cmd,
+            commands::detect_article_language_cmd,
+            commands::list_article_revisions_cmd,
+            commands::diff_article_revision_cmd,
+            commands::revert_article_revision_cmd,
+            commands::generate_article_recap_cmd,
+            commands::get_article_recap_cmd,
+            commands::list_due_article_recaps_cmd,
+            commands::mark_article_recap_reread_cmd,
+            commands::export_article_recap_cmd,
+            commands::export_article_anki_cmd,
+            commands::export_articles_to_obsidian_cmd,
             commands::fetch_url_content,
             commands::import_web_material_cmd,
+            commands::import_parallel_text_cmd,
+            commands::import_markdown_cmd,
+            commands::import_docx_cmd,
+            commands::import_subtitle_file_cmd,
+            commands::list_embedded_subtitles_cmd,
+            commands::extract_embedded_subtitles_cmd,
+            commands::export_subtitles_cmd,
+            commands::burn_subtitles_cmd,
+            commands::get_waveform_cmd,
+            commands::import_html_file_cmd,
+            commands::list_failed_captures_cmd,
+            commands::delete_failed_capture_cmd,
+            commands::retry_failed_captures_cmd,
+            commands::list_quarantined_results_cmd,
+            commands::delete_quarantined_result_cmd,
+            commands::retry_quarantined_result_cmd,
+            commands::get_extraction_rules_cmd,
+            commands::save_extraction_rules_cmd,
+            commands::test_extraction_rules_cmd,
             // AI operations
             commands::translate_text,
             commands::analyze_text,
+            commands::get_openrouter_models_cmd,
+            commands::estimate_request_cmd,
             commands::chat_completion,
             commands::stream_chat_completion,
             commands::translate_article,
+            commands::resume_translation_cmd,
+            commands::get_translation_progress_cmd,
+            commands::get_translation_qa_cmd,
+            commands::retranslate_segment_cmd,
+            commands::clear_translation_memory_cmd,
             commands::analyze_article,
+            commands::list_article_analyses_cmd,
+            commands::save_article_analysis_cmd,
+            commands::delete_article_analysis_result_cmd,
             commands::segment_translate_explain_cmd,
+            commands::explain_article_cmd,
+            commands::prefetch_explanations_cmd,
+            commands::generate_readings_cmd,
+            commands::cancel_job_cmd,
+            commands::get_last_undo_action_cmd,
+            commands::undo_last_action_cmd,
             // 收藏夹命令
             commands::create_word_pack_cmd,
             commands::update_word_pack_cmd,
             commands::list_word_packs_cmd,
             commands::delete_word_pack_cmd,
+            commands::merge_packs_cmd,
+            commands::split_word_pack_cmd,
             commands::add_favorite_vocabulary_cmd,
+            commands::backfill_vocabulary_phonetics_cmd,
             commands::list_favorite_vocabularies_cmd,
             commands::list_favorite_vocabularies_by_pack_cmd,
             commands::set_vocabulary_pack_ids_cmd,
             commands::get_due_vocabulary_queue_cmd,
+            commands::generate_study_plan_cmd,
             commands::review_vocabulary_cmd,
+            commands::record_vocabulary_encounter_cmd,
+            commands::get_daily_review_summary_cmd,
+            commands::get_retention_stats_cmd,
+            commands::run_srs_maintenance_cmd,
+            commands::get_srs_maintenance_summary_cmd,
+            commands::upgrade_articles_cmd,
+            commands::get_article_upgrade_report_cmd,
             commands::export_word_pack_cmd,
+            commands::export_card_bundle_cmd,
             commands::import_word_pack_cmd,
             commands::delete_favorite_vocabulary_cmd,
+            commands::set_vocabulary_image_cmd,
             commands::add_favorite_grammar_cmd,
             commands::list_favorite_grammars_cmd,
             commands::delete_favorite_grammar_cmd,
+            commands::list_grammar_usages_cmd,
+            commands::get_article_grammar_links_cmd,
             // External
+            commands::list_youtube_subtitles_cmd,
             commands::import_youtube_video_cmd,
+            commands::import_media_url_cmd,
+            commands::subscribe_youtube_channel_cmd,
+            commands::check_youtube_channel_cmd,
+            commands::list_channel_subscriptions_cmd,
+            commands::delete_channel_subscription_cmd,
             commands::import_local_video_cmd,
+            commands::import_local_video_folder_cmd,
             // 书籍导入
             commands::import_book_cmd,
             // 字幕提取
             commands::extract_subtitles_cmd,
+            commands::cancel_subtitle_extraction_cmd,
+            commands::adjust_subtitle_timing_cmd,
+            commands::align_article_audio_cmd,
+            commands::snap_subtitle_timing_cmd,
+            commands::list_transcription_engines_cmd,
             // 文件操作
             commands::write_text_file,
             commands::write_binary_file,
@@ -81,7 +219,9 @@ pub fn run() {
             // PDF翻译
             commands::translate_pdf_document,
             commands::check_pdf_translation_files,
+            commands::import_pdf_translation_cmd,
             commands::export_file_cmd,
+            commands::generate_tts_playlist_cmd,
             // 插件管理
             plugin_manager::list_plugins_cmd,
             plugin_manager::open_plugins_directory,
@@ -91,12 +231,39 @@ pub fn run() {
             plugin_manager::check_plugin_installed_cmd,
             plugin_manager::get_plugin_release_info_cmd,
             plugin_manager::install_plugin_cmd,
+            // 系统健康检查
+            health::get_system_health_cmd,
             // 书签管理
             commands::add_bookmark_cmd,
             commands::list_bookmarks_cmd,
             commands::list_bookmarks_for_book_cmd,
             commands::update_bookmark_cmd,
             commands::delete_bookmark_cmd,
+            // 术语表
+            commands::add_glossary_entry_cmd,
+            commands::list_glossary_entries_cmd,
+            commands::update_glossary_entry_cmd,
+            commands::delete_glossary_entry_cmd,
+            commands::add_tag_rule_cmd,
+            commands::list_tag_rules_cmd,
+            commands::update_tag_rule_cmd,
+            commands::delete_tag_rule_cmd,
+            commands::apply_tag_rules_cmd,
+            commands::set_article_tags_cmd,
+            commands::recount_vocabulary_corpus_cmd,
+            commands::default_export_template_cmd,
+            commands::add_export_template_cmd,
+            commands::list_export_templates_cmd,
+            commands::update_export_template_cmd,
+            commands::delete_export_template_cmd,
+            commands::export_vocabulary_csv_cmd,
+            commands::export_anki_notes_cmd,
+            // 已知词库与生词密度
+            commands::add_known_words_cmd,
+            commands::list_known_words_cmd,
+            commands::delete_known_word_cmd,
+            commands::get_article_difficulty_cmd,
+            commands::get_pre_reading_words_cmd,
         ])
         .setup(|app| {
             // Initialize app on startup
@@ -105,6 +272,15 @@ pub fn run() {
                 // Ensure app directories exist
                 let _ = commands::init_app(app_handle.clone()).await;
 
+                // 文章数据版本较旧时重写为最新结构
+                commands::upgrade_articles_if_needed(&app_handle);
+
+                // 恢复监视文件夹的自动导入
+                folder_watcher::start_from_config(&app_handle);
+
+                // 每日 SRS 维护（过期统计、顽固词识别、复习记录清理）
+                commands::start_srs_maintenance_scheduler(app_handle.clone());
+
                 // 启动资源服务器 (视频 + 书籍)
                 let app_data_dir = app_handle.path().app_data_dir().unwrap();
                 if let Err(e) = video_server::start_resource_server(app_data_dir).await {
@@ -0,0 +1,370 @@
+// Markdown 文件导入：解析标题、列表、引用、代码块和插图，去掉行内格式得到段落文本，
+// 块结构记录在段落的 block 字段上，阅读器据此还原文档排版
+
+use crate::segmentation::{split_content, SegmentPiece};
+use crate::types::{SegmentBlock, SegmentationMode};
+use crate::web_images::{image_placeholder, parse_image_placeholder};
+use regex::Regex;
+use std::path::Path;
+
+/// 解析出的一个块；kind 为 None 的是普通正文段落
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownBlock {
+    pub kind: Option<SegmentBlock>,
+    pub text: String,
+    /// 插图地址（此时 text 为 alt 文本）
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MarkdownDocument {
+    /// frontmatter 的 title，或文档开头的一级标题
+    pub title: Option<String>,
+    pub blocks: Vec<MarkdownBlock>,
+}
+
+/// 正在累积的块：连续的非空行
+struct PendingBlock {
+    kind: Option<SegmentBlock>,
+    lines: Vec<String>,
+}
+
+/// 两侧都是 CJK 字符时直接拼接，否则用空格连接折行
+fn join_lines(lines: &[String]) -> String {
+    let mut out = String::new();
+    for line in lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let cjk_boundary = out.chars().last().is_some_and(|c| c >= '\u{2E80}')
+            && line.chars().next().is_some_and(|c| c >= '\u{2E80}');
+        if !out.is_empty() && !cjk_boundary {
+            out.push(' ');
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// 去掉行内格式：强调、删除线、行内代码、链接、HTML 标签、脚注引用和转义符
+pub fn strip_inline(text: &str) -> String {
+    // 转义的 * 和 _ 先换成私用区字符，避免被当作强调标记
+    let mut text = text.replace("\\*", "\u{E001}").replace("\\_", "\u{E002}");
+    let rules = [
+        (r"`([^`]+)`", "$1"),
+        (r"!\[([^\]]*)\]\([^)]*\)", "$1"),
+        (r"\[\^[^\]]+\]", ""),
+        (r"\[([^\]]+)\]\([^)]*\)", "$1"),
+        (r"\[([^\]]+)\]\[[^\]]*\]", "$1"),
+        (r"<(https?://[^>\s]+)>", "$1"),
+        (r"</?[A-Za-z][^>]*>", ""),
+        (r"\*\*(.+?)\*\*", "$1"),
+        (r"__(.+?)__", "$1"),
+        (r"~~(.+?)~~", "$1"),
+        (r"\*([^*\s](?:[^*]*[^*\s])?)\*", "$1"),
+        // 单下划线只在词边界处视为强调，保留 snake_case 这类标识符
+        (r"(^|[^\w])_([^_\s](?:[^_]*[^_\s])?)_($|[^\w])", "$1$2$3"),
+        (r"\\([\\`{}\[\]()#+\-.!>~|])", "$1"),
+    ];
+    for (pattern, replacement) in rules {
+        text = Regex::new(pattern)
+            .unwrap()
+            .replace_all(&text, replacement)
+            .into_owned();
+    }
+    text.replace('\u{E001}', "*")
+        .replace('\u{E002}', "_")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 分隔线：三个以上相同的 - * _，中间可有空格
+fn is_thematic_break(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].contains(&marks[0]) && marks.iter().all(|&c| c == marks[0])
+}
+
+/// 表格分隔行，如 |---|:---:|
+fn is_table_separator(line: &str) -> bool {
+    line.contains('-') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// 读取 frontmatter，返回其中的 title 和正文开始的行号
+fn front_matter(lines: &[&str]) -> (Option<String>, usize) {
+    if lines.first().map(|l| l.trim()) != Some("---") {
+        return (None, 0);
+    }
+    let Some(end) = lines.iter().skip(1).position(|l| l.trim() == "---") else {
+        return (None, 0);
+    };
+    let title = lines[1..=end].iter().find_map(|line| {
+        let value = line.strip_prefix("title:")?.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        Some(value.to_string()).filter(|v| !v.is_empty())
+    });
+    (title, end + 2)
+}
+
+/// 解析 Markdown 文本
+pub fn parse_markdown(text: &str) -> MarkdownDocument {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let lines: Vec<&str> = text.lines().collect();
+    let (front_title, body_start) = front_matter(&lines);
+
+    let re_heading = Regex::new(r"^(#{1,6})\s+(.*?)(?:\s+#+)?\s*$").unwrap();
+    let re_list = Regex::new(r"^([ \t]*)([-*+]|\d{1,9}[.)])\s+(.*)$").unwrap();
+    let re_task = Regex::new(r"^\[[ xX]\]\s+").unwrap();
+
+    let mut blocks = Vec::new();
+    let mut pending: Option<PendingBlock> = None;
+    let mut code: Option<(String, Vec<String>)> = None;
+
+    fn flush(blocks: &mut Vec<MarkdownBlock>, pending: &mut Option<PendingBlock>) {
+        if let Some(block) = pending.take() {
+            let text = strip_inline(&join_lines(&block.lines));
+            if !text.is_empty() {
+                blocks.push(MarkdownBlock {
+                    kind: block.kind,
+                    text,
+                    image_url: None,
+                });
+            }
+        }
+    }
+
+    for line in &lines[body_start..] {
+        let trimmed = line.trim();
+
+        if let Some((fence, code_lines)) = code.as_mut() {
+            if trimmed.starts_with(fence.as_str()) {
+                let text = code_lines.join("\n").trim_end().to_string();
+                if !text.trim().is_empty() {
+                    blocks.push(MarkdownBlock {
+                        kind: Some(SegmentBlock::Code),
+                        text,
+                        image_url: None,
+                    });
+                }
+                code = None;
+            } else {
+                code_lines.push(line.to_string());
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            flush(&mut blocks, &mut pending);
+            code = Some((trimmed[..3].to_string(), Vec::new()));
+            continue;
+        }
+
+        if trimmed.is_empty() || (trimmed.starts_with("<!--") && trimmed.ends_with("-->")) {
+            flush(&mut blocks, &mut pending);
+            continue;
+        }
+
+        if let Some(caps) = re_heading.captures(trimmed) {
+            flush(&mut blocks, &mut pending);
+            pending = Some(PendingBlock {
+                kind: Some(SegmentBlock::Heading {
+                    level: caps[1].len() as u8,
+                }),
+                lines: vec![caps[2].to_string()],
+            });
+            flush(&mut blocks, &mut pending);
+            continue;
+        }
+
+        // Setext 标题：正文下方一行 === 或 ---
+        let setext_level = if trimmed.chars().all(|c| c == '=') {
+            Some(1)
+        } else if trimmed.chars().all(|c| c == '-') {
+            Some(2)
+        } else {
+            None
+        };
+        if let (Some(level), Some(block)) = (setext_level, pending.as_mut()) {
+            if block.kind.is_none() {
+                block.kind = Some(SegmentBlock::Heading { level });
+                flush(&mut blocks, &mut pending);
+                continue;
+            }
+        }
+
+        if is_thematic_break(trimmed) {
+            flush(&mut blocks, &mut pending);
+            continue;
+        }
+
+        if let Some((alt, url)) = parse_image_placeholder(trimmed) {
+            flush(&mut blocks, &mut pending);
+            blocks.push(MarkdownBlock {
+                kind: None,
+                text: strip_inline(&alt),
+                image_url: Some(url),
+            });
+            continue;
+        }
+
+        if let Some(caps) = re_list.captures(line) {
+            flush(&mut blocks, &mut pending);
+            let indent: usize = caps[1].chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+            let item = re_task.replace(&caps[3], "").into_owned();
+            pending = Some(PendingBlock {
+                kind: Some(SegmentBlock::ListItem {
+                    ordered: caps[2].starts_with(|c: char| c.is_ascii_digit()),
+                    depth: (indent / 2).min(u8::MAX as usize) as u8,
+                }),
+                lines: vec![item],
+            });
+            continue;
+        }
+
+        if let Some(quoted) = trimmed.strip_prefix('>') {
+            let quoted = quoted.trim_start_matches(['>', ' ']).trim();
+            if !matches!(
+                pending,
+                Some(PendingBlock {
+                    kind: Some(SegmentBlock::Quote),
+                    ..
+                })
+            ) {
+                flush(&mut blocks, &mut pending);
+                pending = Some(PendingBlock {
+                    kind: Some(SegmentBlock::Quote),
+                    lines: Vec::new(),
+                });
+            }
+            // 引用内的空行分隔引用中的段落
+            if quoted.is_empty() {
+                flush(&mut blocks, &mut pending);
+            } else if let Some(block) = pending.as_mut() {
+                block.lines.push(quoted.to_string());
+            }
+            continue;
+        }
+
+        // 表格每行作为一段，单元格之间用 | 分隔
+        if trimmed.starts_with('|') {
+            flush(&mut blocks, &mut pending);
+            if !is_table_separator(trimmed) {
+                let cells: Vec<String> = trimmed
+                    .trim_matches('|')
+                    .split('|')
+                    .map(strip_inline)
+                    .filter(|cell| !cell.is_empty())
+                    .collect();
+                if !cells.is_empty() {
+                    blocks.push(MarkdownBlock {
+                        kind: None,
+                        text: cells.join(" | "),
+                        image_url: None,
+                    });
+                }
+            }
+            continue;
+        }
+
+        match pending.as_mut() {
+            Some(block) => block.lines.push(trimmed.to_string()),
+            None => {
+                pending = Some(PendingBlock {
+                    kind: None,
+                    lines: vec![trimmed.to_string()],
+                })
+            }
+        }
+    }
+    flush(&mut blocks, &mut pending);
+    // 未闭合的代码块保留到文末
+    if let Some((_, code_lines)) = code {
+        let text = code_lines.join("\n").trim_end().to_string();
+        if !text.trim().is_empty() {
+            blocks.push(MarkdownBlock {
+                kind: Some(SegmentBlock::Code),
+                text,
+                image_url: None,
+            });
+        }
+    }
+
+    // 文档开头的一级标题作为文章标题，不再重复成段落
+    let mut title = front_title;
+    if let Some(first) = blocks.first() {
+        if first.kind == Some(SegmentBlock::Heading { level: 1 })
+            && title
+                .as_deref()
+                .is_none_or(|t| strip_inline(t) == first.text)
+        {
+            title = Some(blocks.remove(0).text);
+        }
+    }
+
+    MarkdownDocument { title, blocks }
+}
+
+/// 相对路径的插图按 Markdown 文件所在目录解析为 file:// 地址
+pub fn resolve_image_urls(document: &mut MarkdownDocument, base_dir: &Path) {
+    for block in &mut document.blocks {
+        let Some(url) = block.image_url.as_mut() else {
+            continue;
+        };
+        if url.contains("://") || url.starts_with("data:") {
+            continue;
+        }
+        let relative = url.replace("%20", " ");
+        if let Ok(file_url) = url::Url::from_file_path(base_dir.join(relative)) {
+            *url = file_url.to_string();
+        }
+    }
+}
+
+/// 文章正文：去掉格式后的纯文本，块之间空一行，插图保留为占位行
+pub fn plain_content(document: &MarkdownDocument) -> String {
+    document
+        .blocks
+        .iter()
+        .map(|block| match block.image_url.as_deref() {
+            Some(url) => image_placeholder(&block.text, url),
+            None => block.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 切分为段落：每个块另起一段；标题和代码块整块作为一段，其余按句切分，
+/// 同一块切出的句子都带上块类型
+pub fn markdown_pieces(
+    document: &MarkdownDocument,
+    language: Option<&str>,
+) -> Vec<(SegmentPiece, Option<SegmentBlock>)> {
+    let mut pieces = Vec::new();
+    for block in &document.blocks {
+        if block.image_url.is_some()
+            || matches!(
+                block.kind,
+                Some(SegmentBlock::Heading { .. }) | Some(SegmentBlock::Code)
+            )
+        {
+            pieces.push((
+                SegmentPiece {
+                    text: block.text.clone(),
+                    is_new_paragraph: true,
+                    image_url: block.image_url.clone(),
+                },
+                block.kind,
+            ));
+            continue;
+        }
+        for (index, mut piece) in split_content(&block.text, language, &SegmentationMode::Sentence)
+            .into_iter()
+            .enumerate()
+        {
+            piece.is_new_paragraph = index == 0;
+            pieces.push((piece, block.kind));
+        }
+    }
+    pieces
+}
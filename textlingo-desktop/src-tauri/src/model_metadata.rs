@@ -0,0 +1,266 @@
+// OpenRouter 模型元数据：上下文长度和按 token 计价
+// 从 /api/v1/models 拉取后缓存在 app_data/openrouter_models.json，一天内不重复请求；
+// 发送请求前用于估算 token 数、费用，并在超出上下文长度时提醒
+
+use crate::i18n::tr_with;
+use crate::storage::get_app_data_dir;
+use crate::types::{ModelMetadata, RequestEstimate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const OPENROUTER_MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
+const CACHE_FILE_NAME: &str = "openrouter_models.json";
+/// 缓存有效期（小时）
+const CACHE_TTL_HOURS: i64 = 24;
+
+/// 批量翻译/注音每批最多的条目数（字幕等短句可以一次发送更多）
+pub const MAX_BATCH_ITEMS: usize = 100;
+/// 没有模型元数据时按保守的上下文长度和单次输出上限计算
+const DEFAULT_CONTEXT_TOKENS: u64 = 32_000;
+const DEFAULT_COMPLETION_TOKENS: u64 = 4_096;
+/// 指令、术语表等固定部分预留的 token
+const PROMPT_OVERHEAD_TOKENS: u64 = 1_000;
+/// 每条的编号和 JSON 结构开销
+const ITEM_OVERHEAD_TOKENS: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MetadataCache {
+    fetched_at: String,
+    models: Vec<ModelMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ModelsResponse {
+    data: Vec<RawModel>,
+}
+
+#[derive(Deserialize)]
+struct RawModel {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    context_length: Option<u64>,
+    #[serde(default)]
+    pricing: Option<RawPricing>,
+    #[serde(default)]
+    top_provider: Option<RawTopProvider>,
+}
+
+/// OpenRouter 的价格是字符串形式的美元/token，如 "0.0000015"
+#[derive(Deserialize)]
+struct RawPricing {
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    completion: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawTopProvider {
+    #[serde(default)]
+    context_length: Option<u64>,
+    #[serde(default)]
+    max_completion_tokens: Option<u64>,
+}
+
+/// 解析 /api/v1/models 的响应
+pub fn parse_openrouter_models(json: &str) -> Result<Vec<ModelMetadata>, String> {
+    let response: ModelsResponse = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse OpenRouter models: {}", e))?;
+    let price = |value: &Option<String>| value.as_deref().and_then(|v| v.trim().parse().ok());
+
+    Ok(response
+        .data
+        .into_iter()
+        .map(|model| {
+            let top = model.top_provider.as_ref();
+            ModelMetadata {
+                name: model.name.unwrap_or_else(|| model.id.clone()),
+                context_length: model
+                    .context_length
+                    .or_else(|| top.and_then(|t| t.context_length)),
+                max_completion_tokens: top.and_then(|t| t.max_completion_tokens),
+                prompt_price: model.pricing.as_ref().and_then(|p| price(&p.prompt)),
+                completion_price: model.pricing.as_ref().and_then(|p| price(&p.completion)),
+                id: model.id,
+            }
+        })
+        .collect())
+}
+
+/// 粗略估算 token 数：中日韩文字约每字 1 个 token，其他文字约每 4 个字符 1 个 token
+pub fn estimate_tokens(text: &str) -> usize {
+    let (cjk, other) = text.chars().fold((0usize, 0usize), |(cjk, other), c| {
+        if c >= '\u{2e80}' {
+            (cjk + 1, other)
+        } else {
+            (cjk, other + 1)
+        }
+    });
+    cjk + other.div_ceil(4)
+}
+
+/// 按模型元数据估算一次请求：token 数、费用和是否超出上下文长度
+pub fn estimate_request(
+    model: &str,
+    metadata: Option<&ModelMetadata>,
+    text: &str,
+    completion_tokens: usize,
+) -> RequestEstimate {
+    let prompt_tokens = estimate_tokens(text);
+    let context_length = metadata.and_then(|m| m.context_length);
+    let estimated_cost = metadata.and_then(|m| {
+        Some(
+            m.prompt_price? * prompt_tokens as f64
+                + m.completion_price.unwrap_or(0.0) * completion_tokens as f64,
+        )
+    });
+    RequestEstimate {
+        model: model.to_string(),
+        prompt_tokens,
+        completion_tokens,
+        context_length,
+        exceeds_context: context_length
+            .is_some_and(|limit| (prompt_tokens + completion_tokens) as u64 > limit),
+        estimated_cost,
+    }
+}
+
+/// 每批条目的 token 预算：输出（译文约为原文的 1.5 倍）不能超过单次输出上限，
+/// 输入和输出合计不能超过上下文长度
+pub fn batch_token_budget(metadata: Option<&ModelMetadata>) -> usize {
+    let context = metadata
+        .and_then(|m| m.context_length)
+        .unwrap_or(DEFAULT_CONTEXT_TOKENS);
+    let completion = metadata
+        .and_then(|m| m.max_completion_tokens)
+        .unwrap_or(DEFAULT_COMPLETION_TOKENS);
+    (context.saturating_sub(PROMPT_OVERHEAD_TOKENS) * 2 / 5).min(completion) as usize
+}
+
+/// 按估算的 token 数把条目分批：每批不超过 budget 和 max_items，超长的单条独占一批
+pub fn plan_batches<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+    budget: usize,
+    max_items: usize,
+) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    let mut end = 0;
+    for text in texts {
+        let cost = estimate_tokens(text) * 3 / 2 + ITEM_OVERHEAD_TOKENS;
+        if end > start && (used + cost > budget || end - start >= max_items.max(1)) {
+            batches.push(start..end);
+            start = end;
+            used = 0;
+        }
+        used += cost;
+        end += 1;
+    }
+    if end > start {
+        batches.push(start..end);
+    }
+    batches
+}
+
+/// 超出上下文长度时的提示文字
+pub fn context_warning(estimate: &RequestEstimate) -> Option<String> {
+    let limit = estimate
+        .context_length
+        .filter(|_| estimate.exceeds_context)?;
+    Some(tr_with(
+        "warning.context_exceeded",
+        &[
+            ("model", estimate.model.clone()),
+            (
+                "tokens",
+                (estimate.prompt_tokens + estimate.completion_tokens).to_string(),
+            ),
+            ("limit", limit.to_string()),
+        ],
+    ))
+}
+
+fn cache_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_data_dir(app_handle)?.join(CACHE_FILE_NAME))
+}
+
+fn read_cache(app_handle: &AppHandle) -> Option<MetadataCache> {
+    let content = fs::read_to_string(cache_file_path(app_handle).ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn is_fresh(cache: &MetadataCache) -> bool {
+    chrono::DateTime::parse_from_rfc3339(&cache.fetched_at).is_ok_and(|fetched| {
+        chrono::Utc::now().signed_duration_since(fetched) < chrono::Duration::hours(CACHE_TTL_HOURS)
+    })
+}
+
+/// 获取 OpenRouter 模型列表：缓存未过期时直接返回，否则重新拉取；拉取失败时退回旧缓存
+pub async fn openrouter_models(
+    app_handle: &AppHandle,
+    refresh: bool,
+) -> Result<Vec<ModelMetadata>, String> {
+    let cached = read_cache(app_handle);
+    if let Some(cache) = cached.as_ref().filter(|c| !refresh && is_fresh(c)) {
+        return Ok(cache.models.clone());
+    }
+    // 离线时使用已有缓存，不论是否过期
+    if crate::offline::is_offline() {
+        return match cached {
+            Some(cache) => Ok(cache.models),
+            None => crate::offline::check_online().map(|_| Vec::new()),
+        };
+    }
+
+    let fetched = async {
+        let body = reqwest::Client::new()
+            .get(OPENROUTER_MODELS_URL)
+            .timeout(std::time::Duration::from_secs(20))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Request failed: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+        parse_openrouter_models(&body)
+    }
+    .await;
+
+    match fetched {
+        Ok(models) => {
+            let cache = MetadataCache {
+                fetched_at: chrono::Utc::now().to_rfc3339(),
+                models,
+            };
+            let content = serde_json::to_string(&cache)
+                .map_err(|e| format!("Failed to serialize model metadata: {}", e))?;
+            if let Err(e) = fs::write(cache_file_path(app_handle)?, content) {
+                eprintln!("[ModelMetadata] Failed to write cache: {}", e);
+            }
+            Ok(cache.models)
+        }
+        Err(e) => match cached {
+            Some(cache) => {
+                eprintln!("[ModelMetadata] Refresh failed, using cached list: {}", e);
+                Ok(cache.models)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// 只从缓存中查找模型元数据（不发网络请求），用于发送请求前的检查
+pub fn cached_model(app_handle: &AppHandle, model: &str) -> Option<ModelMetadata> {
+    read_cache(app_handle)?
+        .models
+        .into_iter()
+        .find(|m| m.id == model)
+}
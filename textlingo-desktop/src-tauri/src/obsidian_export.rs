@@ -0,0 +1,162 @@
+// 导出为 Obsidian 笔记：每篇文章一个 Markdown 文件，包含摘录、生词（[[双链]]）、语法和来源
+// 笔记 frontmatter 记录文章 id，再次导出时按 id 找到原文件原地更新，文章改名也不会产生重复笔记
+
+use crate::types::{Article, Bookmark, FavoriteGrammar, FavoriteVocabulary};
+use std::path::{Path, PathBuf};
+
+/// frontmatter 中记录文章 id 的键
+pub const NOTE_ID_KEY: &str = "openkoto_id";
+
+/// 生成文件名：去掉文件系统和 Obsidian 链接中不允许的字符
+pub fn note_file_stem(title: &str) -> String {
+    let stem = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | '?' | '%' | '*' | ':' | '|' | '"' | '<' | '>' | '#' | '^' | '[' | ']' => {
+                '-'
+            }
+            c if c.is_control() => ' ',
+            _ => c,
+        })
+        .collect::<String>();
+    let stem = stem.trim().trim_start_matches('.').trim();
+    if stem.is_empty() {
+        "Untitled".to_string()
+    } else {
+        stem.chars().take(120).collect()
+    }
+}
+
+/// 双链目标中不能出现 [ ] | # ^，替换为空格
+fn wikilink(target: &str) -> String {
+    let cleaned: String = target
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | '|' | '#' | '^' => ' ',
+            _ => c,
+        })
+        .collect();
+    format!(
+        "[[{}]]",
+        cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+    )
+}
+
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// 读取笔记 frontmatter 中的文章 id
+fn note_article_id(content: &str) -> Option<String> {
+    let rest = content.strip_prefix("---\n")?;
+    let frontmatter = &rest[..rest.find("\n---")?];
+    frontmatter.lines().find_map(|line| {
+        let value = line.strip_prefix(NOTE_ID_KEY)?.strip_prefix(':')?.trim();
+        Some(serde_json::from_str::<String>(value).unwrap_or_else(|_| value.to_string()))
+    })
+}
+
+/// 确定文章对应的笔记路径：优先沿用已导出过的文件，否则按标题命名，
+/// 与其他文章的笔记重名时在文件名后附加 id 前缀
+pub fn note_path(dir: &Path, article: &Article) -> PathBuf {
+    let existing = std::fs::read_dir(dir).ok().and_then(|entries| {
+        entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .find(|path| {
+                std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| note_article_id(&content))
+                    .is_some_and(|id| id == article.id)
+            })
+    });
+    if let Some(path) = existing {
+        return path;
+    }
+
+    let stem = note_file_stem(&article.title);
+    let path = dir.join(format!("{}.md", stem));
+    if !path.exists() {
+        return path;
+    }
+    let short_id: String = article.id.chars().take(8).collect();
+    dir.join(format!("{} ({}).md", stem, short_id))
+}
+
+/// 渲染文章笔记；highlights 为书籍摘录（书签中的选中文字和笔记）
+pub fn render_article_note(
+    article: &Article,
+    highlights: &[Bookmark],
+    vocabulary: &[FavoriteVocabulary],
+    grammar: &[FavoriteGrammar],
+) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("{}: {}\n", NOTE_ID_KEY, yaml_string(&article.id)));
+    out.push_str(&format!("title: {}\n", yaml_string(&article.title)));
+    if let Some(url) = article.source_url.as_deref().filter(|u| !u.is_empty()) {
+        out.push_str(&format!("source: {}\n", yaml_string(url)));
+    }
+    if let Some(language) = article.language.as_deref() {
+        out.push_str(&format!("language: {}\n", yaml_string(language)));
+    }
+    out.push_str(&format!("created: {}\n", yaml_string(&article.created_at)));
+    out.push_str("tags:\n  - openkoto\n---\n\n");
+
+    out.push_str(&format!("# {}\n", article.title));
+    if let Some(url) = article.source_url.as_deref().filter(|u| !u.is_empty()) {
+        out.push_str(&format!("\nSource: <{}>\n", url));
+    }
+
+    let highlights: Vec<&Bookmark> = highlights
+        .iter()
+        .filter(|b| {
+            b.selected_text
+                .as_deref()
+                .is_some_and(|t| !t.trim().is_empty())
+                || b.note.as_deref().is_some_and(|n| !n.trim().is_empty())
+        })
+        .collect();
+    if !highlights.is_empty() {
+        out.push_str("\n## Highlights\n");
+        for bookmark in highlights {
+            out.push('\n');
+            if let Some(text) = bookmark.selected_text.as_deref().map(str::trim) {
+                for line in text.lines() {
+                    out.push_str(&format!("> {}\n", line));
+                }
+            }
+            if let Some(note) = bookmark.note.as_deref().map(str::trim) {
+                if !note.is_empty() {
+                    out.push_str(&format!("\n{}\n", note));
+                }
+            }
+        }
+    }
+
+    if !vocabulary.is_empty() {
+        out.push_str("\n## Vocabulary\n\n");
+        for item in vocabulary {
+            match item.reading.as_deref().filter(|r| !r.is_empty()) {
+                Some(reading) => out.push_str(&format!(
+                    "- {} ({}) — {}\n",
+                    wikilink(&item.word),
+                    reading,
+                    item.meaning
+                )),
+                None => out.push_str(&format!("- {} — {}\n", wikilink(&item.word), item.meaning)),
+            }
+        }
+    }
+
+    if !grammar.is_empty() {
+        out.push_str("\n## Grammar\n\n");
+        for point in grammar {
+            out.push_str(&format!("- **{}** — {}\n", point.point, point.explanation));
+            if let Some(example) = point.example.as_deref().filter(|e| !e.is_empty()) {
+                out.push_str(&format!("  - {}\n", example));
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,35 @@
+// 离线模式：只在本次运行内有效（不写入配置，重启后恢复联网）
+// 开启后依赖 AI 或网络的命令立即返回 offline 错误，不再等待请求超时；
+// 词典、SRS、阅读和本地语音识别等本地功能不受影响
+
+use crate::error::AppError;
+use crate::i18n::tr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_offline_mode(enabled: bool) {
+    OFFLINE_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE_MODE.load(Ordering::Relaxed)
+}
+
+/// 离线时返回 offline 错误（命令层使用）
+pub fn ensure_online() -> Result<(), AppError> {
+    if is_offline() {
+        Err(AppError::offline(tr("error.offline_mode")))
+    } else {
+        Ok(())
+    }
+}
+
+/// 离线时返回错误信息（内部函数使用，转换为 AppError 时归类为 offline）
+pub fn check_online() -> Result<(), String> {
+    if is_offline() {
+        Err(tr("error.offline_mode"))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+// 对照文本（原文 + 人工译文）解析：逐行对齐，导入后无需 AI 翻译
+
+use crate::i18n::tr_with;
+use serde::Deserialize;
+
+/// 一组对齐的原文与译文
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedPair {
+    pub source: String,
+    pub translation: String,
+    /// 前面有空行，另起一段显示
+    pub is_new_paragraph: bool,
+}
+
+/// 单个文件内的分隔符：制表符或 "||"
+fn split_aligned_line(line: &str) -> Option<(&str, &str)> {
+    line.split_once('\t').or_else(|| line.split_once("||"))
+}
+
+fn strip_bom(text: &str) -> &str {
+    text.strip_prefix('\u{feff}').unwrap_or(text)
+}
+
+/// 解析每行 "原文<TAB>译文" 或 "原文 || 译文" 的对照文本，空行表示分段
+pub fn parse_aligned_lines(text: &str) -> Result<Vec<AlignedPair>, String> {
+    let mut pairs = Vec::new();
+    let mut new_paragraph = true;
+
+    for (index, line) in strip_bom(text).lines().enumerate() {
+        if line.trim().is_empty() {
+            new_paragraph = true;
+            continue;
+        }
+
+        let (source, translation) = split_aligned_line(line).ok_or_else(|| {
+            tr_with(
+                "error.parallel_missing_separator",
+                &[("line", (index + 1).to_string())],
+            )
+        })?;
+
+        pairs.push(AlignedPair {
+            source: source.trim().to_string(),
+            translation: translation.trim().to_string(),
+            is_new_paragraph: new_paragraph,
+        });
+        new_paragraph = false;
+    }
+
+    Ok(pairs)
+}
+
+/// 按非空行对齐两个文件，行数必须一致；分段以原文的空行为准
+pub fn align_texts(source: &str, translation: &str) -> Result<Vec<AlignedPair>, String> {
+    let translations: Vec<&str> = strip_bom(translation)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut pairs = Vec::new();
+    let mut new_paragraph = true;
+    for line in strip_bom(source).lines().map(str::trim) {
+        if line.is_empty() {
+            new_paragraph = true;
+            continue;
+        }
+        pairs.push(AlignedPair {
+            source: line.to_string(),
+            translation: String::new(),
+            is_new_paragraph: new_paragraph,
+        });
+        new_paragraph = false;
+    }
+
+    if pairs.len() != translations.len() {
+        return Err(tr_with(
+            "error.parallel_line_mismatch",
+            &[
+                ("source", pairs.len().to_string()),
+                ("translation", translations.len().to_string()),
+            ],
+        ));
+    }
+
+    for (pair, translation) in pairs.iter_mut().zip(translations) {
+        pair.translation = translation.to_string();
+    }
+    Ok(pairs)
+}
+
+/// PDF 翻译插件输出的段落对照文件（{stem}-pairs.json）
+#[derive(Debug, Clone, Deserialize)]
+pub struct PdfTranslationPairs {
+    #[serde(default)]
+    pub lang_in: String,
+    #[serde(default)]
+    pub lang_out: String,
+    pub pairs: Vec<PdfParagraphPair>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PdfParagraphPair {
+    #[serde(default)]
+    pub page: u32,
+    pub source: String,
+    #[serde(default)]
+    pub translation: String,
+}
+
+/// 解析 PDF 段落对照，每个 PDF 段落作为单独一段；
+/// 跳过页码、编号等不含文字的段落
+pub fn parse_pdf_pairs(json: &str) -> Result<(PdfTranslationPairs, Vec<AlignedPair>), String> {
+    let parsed: PdfTranslationPairs = serde_json::from_str(strip_bom(json))
+        .map_err(|e| format!("Failed to parse PDF translation pairs: {}", e))?;
+    let pairs = parsed
+        .pairs
+        .iter()
+        .map(|pair| (pair.source.trim(), pair.translation.trim()))
+        .filter(|(source, _)| source.chars().any(char::is_alphabetic))
+        .map(|(source, translation)| AlignedPair {
+            source: source.to_string(),
+            translation: translation.to_string(),
+            is_new_paragraph: true,
+        })
+        .collect();
+    Ok((parsed, pairs))
+}
+
+/// 由对齐结果还原原文正文，保留分段空行
+pub fn source_content(pairs: &[AlignedPair]) -> String {
+    let mut content = String::new();
+    for (index, pair) in pairs.iter().enumerate() {
+        if index > 0 {
+            content.push_str(if pair.is_new_paragraph { "\n\n" } else { "\n" });
+        }
+        content.push_str(&pair.source);
+    }
+    content
+}
@@ -1,3 +1,5 @@
+use crate::error::{AppError, ErrorKind};
+use crate::i18n::{tr, tr_with};
 use futures_util::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -48,7 +50,7 @@ pub struct PluginConfig {
 
 /// 扫描 plugins 目录获取所有插件
 /// 扫描 plugins 目录获取所有插件
-fn scan_plugins(app_handle: &AppHandle) -> Vec<PluginInfo> {
+pub(crate) fn scan_plugins(app_handle: &AppHandle) -> Vec<PluginInfo> {
     let mut all_instances = Vec::new();
 
     let app_data_dir = match app_handle.path().app_data_dir() {
@@ -224,12 +226,12 @@ fn save_plugin_config(app_handle: &AppHandle, config: &PluginConfig) -> Result<(
 // ================= Commands =================
 
 #[tauri::command]
-pub async fn list_plugins_cmd(app_handle: AppHandle) -> Result<Vec<PluginInfo>, String> {
+pub async fn list_plugins_cmd(app_handle: AppHandle) -> Result<Vec<PluginInfo>, AppError> {
     Ok(scan_plugins(&app_handle))
 }
 
 #[tauri::command]
-pub async fn open_plugins_directory(app_handle: AppHandle) -> Result<(), String> {
+pub async fn open_plugins_directory(app_handle: AppHandle) -> Result<(), AppError> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -261,13 +263,13 @@ pub async fn set_plugin_mode_cmd(
     app_handle: AppHandle,
     plugin_name: String,
     mode: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut config = load_plugin_config(&app_handle)?;
 
     let mode_enum = match mode.as_str() {
         "dev" => PluginMode::Dev,
         "prod" => PluginMode::Prod,
-        _ => return Err("Invalid mode".to_string()),
+        _ => return Err(AppError::invalid_input("Invalid mode")),
     };
 
     config.modes.insert(plugin_name.clone(), mode_enum);
@@ -279,7 +281,7 @@ pub async fn set_plugin_mode_cmd(
 #[tauri::command]
 pub async fn get_plugin_modes_cmd(
     app_handle: AppHandle,
-) -> Result<HashMap<String, PluginMode>, String> {
+) -> Result<HashMap<String, PluginMode>, AppError> {
     let config = load_plugin_config(&app_handle)?;
     Ok(config.modes)
 }
@@ -373,7 +375,7 @@ fn get_builtin_plugin_json() -> &'static str {
 pub async fn check_plugin_installed_cmd(
     app_handle: AppHandle,
     plugin_name: String,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -420,7 +422,8 @@ fn get_platform_zip_names() -> Vec<&'static str> {
 #[tauri::command]
 pub async fn get_plugin_release_info_cmd(
     release_repo: String,
-) -> Result<PluginReleaseInfo, String> {
+) -> Result<PluginReleaseInfo, AppError> {
+    crate::offline::ensure_online()?;
     let client = Client::builder()
         .user_agent("OpenKoto-Desktop")
         .build()
@@ -432,20 +435,24 @@ pub async fn get_plugin_release_info_cmd(
         release_repo
     );
 
-    let response = client
-        .get(&api_url)
-        .send()
-        .await
-        .map_err(|e| format!("网络请求失败: {}", e))?;
+    let response = client.get(&api_url).send().await.map_err(|e| {
+        AppError::network(tr_with(
+            "error.network_request_failed",
+            &[("error", e.to_string())],
+        ))
+    })?;
 
     if !response.status().is_success() {
-        return Err(format!("GitHub API 返回错误: {}", response.status()));
+        return Err(AppError::network(tr_with(
+            "error.github_api_status",
+            &[("status", response.status().to_string())],
+        )));
     }
 
     let releases: Vec<serde_json::Value> = response
         .json()
         .await
-        .map_err(|e| format!("解析响应失败: {}", e))?;
+        .map_err(|e| tr_with("error.api_response_invalid", &[("error", e.to_string())]))?;
 
     let zip_names = get_platform_zip_names();
 
@@ -469,7 +476,7 @@ pub async fn get_plugin_release_info_cmd(
 
                         let download_url = asset["browser_download_url"]
                             .as_str()
-                            .ok_or("未找到下载链接")?
+                            .ok_or_else(|| tr("error.plugin_download_missing"))?
                             .to_string();
 
                         let file_size = asset["size"].as_u64().unwrap_or(0);
@@ -488,7 +495,7 @@ pub async fn get_plugin_release_info_cmd(
         }
     }
 
-    Err("未找到包含插件的发布版本".to_string())
+    Err(AppError::not_found(tr("error.plugin_release_not_found")))
 }
 
 /// 下载并安装插件（从 plugins.zip 解压）
@@ -497,7 +504,8 @@ pub async fn install_plugin_cmd(
     app_handle: AppHandle,
     download_url: String,
     plugin_name: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    crate::offline::ensure_online()?;
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -505,7 +513,12 @@ pub async fn install_plugin_cmd(
     let plugins_dir = app_data_dir.join("plugins");
 
     // 确保 plugins 目录存在
-    std::fs::create_dir_all(&plugins_dir).map_err(|e| format!("创建插件目录失败: {}", e))?;
+    std::fs::create_dir_all(&plugins_dir).map_err(|e| {
+        AppError::new(
+            ErrorKind::Io,
+            tr_with("error.create_dir_failed", &[("error", e.to_string())]),
+        )
+    })?;
 
     // 发送开始下载事件
     let _ = app_handle.emit(
@@ -513,7 +526,7 @@ pub async fn install_plugin_cmd(
         InstallProgress {
             stage: "downloading".to_string(),
             progress: 0.0,
-            message: "正在下载插件...".to_string(),
+            message: tr("plugin.downloading"),
         },
     );
 
@@ -523,14 +536,18 @@ pub async fn install_plugin_cmd(
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
-    let response = client
-        .get(&download_url)
-        .send()
-        .await
-        .map_err(|e| format!("下载失败: {}", e))?;
+    let response = client.get(&download_url).send().await.map_err(|e| {
+        AppError::network(tr_with(
+            "error.download_failed",
+            &[("error", e.to_string())],
+        ))
+    })?;
 
     if !response.status().is_success() {
-        return Err(format!("下载失败: HTTP {}", response.status()));
+        return Err(AppError::network(tr_with(
+            "error.download_http_status",
+            &[("status", response.status().to_string())],
+        )));
     }
 
     let total_size = response.content_length().unwrap_or(0);
@@ -539,7 +556,12 @@ pub async fn install_plugin_cmd(
     let mut stream = response.bytes_stream();
 
     while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.map_err(|e| format!("下载中断: {}", e))?;
+        let chunk = chunk_result.map_err(|e| {
+            AppError::network(tr_with(
+                "error.download_interrupted",
+                &[("error", e.to_string())],
+            ))
+        })?;
         downloaded_bytes.extend_from_slice(&chunk);
         downloaded += chunk.len() as u64;
 
@@ -554,7 +576,10 @@ pub async fn install_plugin_cmd(
             InstallProgress {
                 stage: "downloading".to_string(),
                 progress,
-                message: format!("下载中... {:.1}%", progress * 100.0),
+                message: tr_with(
+                    "plugin.download_progress",
+                    &[("percent", format!("{:.1}", progress * 100.0))],
+                ),
             },
         );
     }
@@ -565,19 +590,19 @@ pub async fn install_plugin_cmd(
         InstallProgress {
             stage: "installing".to_string(),
             progress: 0.9,
-            message: "正在解压安装...".to_string(),
+            message: tr("plugin.extracting"),
         },
     );
 
     // 解压 zip 到 plugins 目录
     let cursor = std::io::Cursor::new(downloaded_bytes);
-    let mut archive =
-        zip::ZipArchive::new(cursor).map_err(|e| format!("无法打开 zip 文件: {}", e))?;
+    let mut archive = zip::ZipArchive::new(cursor)
+        .map_err(|e| tr_with("error.plugin_archive_invalid", &[("error", e.to_string())]))?;
 
     for i in 0..archive.len() {
         let mut file = archive
             .by_index(i)
-            .map_err(|e| format!("读取 zip 条目失败: {}", e))?;
+            .map_err(|e| tr_with("error.plugin_archive_invalid", &[("error", e.to_string())]))?;
 
         let outpath = match file.enclosed_name() {
             Some(path) => plugins_dir.join(path),
@@ -585,14 +610,33 @@ pub async fn install_plugin_cmd(
         };
 
         if file.is_dir() {
-            std::fs::create_dir_all(&outpath).map_err(|e| format!("创建目录失败: {}", e))?;
+            std::fs::create_dir_all(&outpath).map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    tr_with("error.create_dir_failed", &[("error", e.to_string())]),
+                )
+            })?;
         } else {
             if let Some(parent) = outpath.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| format!("创建父目录失败: {}", e))?;
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    AppError::new(
+                        ErrorKind::Io,
+                        tr_with("error.create_dir_failed", &[("error", e.to_string())]),
+                    )
+                })?;
             }
-            let mut outfile =
-                std::fs::File::create(&outpath).map_err(|e| format!("创建文件失败: {}", e))?;
-            std::io::copy(&mut file, &mut outfile).map_err(|e| format!("写入文件失败: {}", e))?;
+            let mut outfile = std::fs::File::create(&outpath).map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    tr_with("error.write_file_failed", &[("error", e.to_string())]),
+                )
+            })?;
+            std::io::copy(&mut file, &mut outfile).map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    tr_with("error.write_file_failed", &[("error", e.to_string())]),
+                )
+            })?;
 
             // 设置可执行权限 (Unix)
             #[cfg(unix)]
@@ -611,9 +655,18 @@ pub async fn install_plugin_cmd(
 
     // 如果 zip 没有包含 plugin.json，写入内置版本
     if !plugin_json_path.exists() {
-        std::fs::create_dir_all(&plugin_dir).map_err(|e| format!("创建插件目录失败: {}", e))?;
-        std::fs::write(&plugin_json_path, get_builtin_plugin_json())
-            .map_err(|e| format!("写入 plugin.json 失败: {}", e))?;
+        std::fs::create_dir_all(&plugin_dir).map_err(|e| {
+            AppError::new(
+                ErrorKind::Io,
+                tr_with("error.create_dir_failed", &[("error", e.to_string())]),
+            )
+        })?;
+        std::fs::write(&plugin_json_path, get_builtin_plugin_json()).map_err(|e| {
+            AppError::new(
+                ErrorKind::Io,
+                tr_with("error.write_file_failed", &[("error", e.to_string())]),
+            )
+        })?;
     }
 
     // 处理平台特定的可执行文件命名
@@ -638,8 +691,12 @@ pub async fn install_plugin_cmd(
                         "[PluginManager] Renaming platform binary: {} -> {}",
                         name, generic_exe_name
                     );
-                    std::fs::rename(&src, &generic_exe_path)
-                        .map_err(|e| format!("重命名可执行文件失败: {}", e))?;
+                    std::fs::rename(&src, &generic_exe_path).map_err(|e| {
+                        AppError::new(
+                            ErrorKind::Io,
+                            tr_with("error.plugin_install_failed", &[("error", e.to_string())]),
+                        )
+                    })?;
                     break;
                 }
             }
@@ -651,11 +708,20 @@ pub async fn install_plugin_cmd(
     if generic_exe_path.exists() {
         use std::os::unix::fs::PermissionsExt;
         let mut perms = std::fs::metadata(&generic_exe_path)
-            .map_err(|e| format!("获取文件权限失败: {}", e))?
+            .map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    tr_with("error.plugin_install_failed", &[("error", e.to_string())]),
+                )
+            })?
             .permissions();
         perms.set_mode(0o755);
-        std::fs::set_permissions(&generic_exe_path, perms)
-            .map_err(|e| format!("设置可执行权限失败: {}", e))?;
+        std::fs::set_permissions(&generic_exe_path, perms).map_err(|e| {
+            AppError::new(
+                ErrorKind::Io,
+                tr_with("error.plugin_install_failed", &[("error", e.to_string())]),
+            )
+        })?;
     }
 
     // 发送完成事件
@@ -664,7 +730,7 @@ pub async fn install_plugin_cmd(
         InstallProgress {
             stage: "completed".to_string(),
             progress: 1.0,
-            message: "安装完成！".to_string(),
+            message: tr("plugin.installed"),
         },
     );
 
@@ -0,0 +1,342 @@
+// 提示词模板
+// 翻译、讲解和分析的提示词可由用户自定义（例如直译/意译风格、领域说明），
+// 自定义内容保存在 app_data/prompt_templates.json，未自定义的使用内置模板
+
+use crate::i18n::{tr, tr_with};
+use crate::storage::get_app_data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const TEMPLATES_FILE_NAME: &str = "prompt_templates.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptKind {
+    /// 单句翻译的 system prompt
+    Translate,
+    /// 整篇批量翻译的指令（待翻译列表和 JSON 返回格式由程序追加）
+    BatchTranslate,
+    /// 快速模式的批量翻译指令
+    BatchTranslateFast,
+    /// 精翻模式第二轮审校的指令
+    TranslationReview,
+    /// 整篇翻译完成后抽查译文质量的指令（抽样列表和返回格式由程序追加）
+    TranslationQa,
+    /// 整篇批量生成注音（振假名/拼音/罗马音）的指令
+    BatchReading,
+    /// 分段 AI 讲解（需要返回固定 JSON 结构）
+    SegmentExplanation,
+    AnalysisSummary,
+    AnalysisKeyPoints,
+    AnalysisVocabulary,
+    AnalysisGrammar,
+    AnalysisFull,
+    /// 长文分块分析的汇总步骤：把各块的分析结果合成全文结果
+    AnalysisSynthesis,
+    /// 语义分段：把编号片段组合成学习单元（编号列表和返回格式由程序追加）
+    SemanticSegmentation,
+    /// 为未填写标题的文章生成标题（正文由程序追加）
+    ArticleTitle,
+}
+
+impl PromptKind {
+    pub const ALL: [PromptKind; 15] = [
+        PromptKind::Translate,
+        PromptKind::BatchTranslate,
+        PromptKind::BatchTranslateFast,
+        PromptKind::TranslationReview,
+        PromptKind::TranslationQa,
+        PromptKind::BatchReading,
+        PromptKind::SegmentExplanation,
+        PromptKind::AnalysisSummary,
+        PromptKind::AnalysisKeyPoints,
+        PromptKind::AnalysisVocabulary,
+        PromptKind::AnalysisGrammar,
+        PromptKind::AnalysisFull,
+        PromptKind::AnalysisSynthesis,
+        PromptKind::SemanticSegmentation,
+        PromptKind::ArticleTitle,
+    ];
+
+    /// 模板中可用的占位符
+    pub fn placeholders(self) -> &'static [&'static str] {
+        match self {
+            PromptKind::Translate
+            | PromptKind::BatchTranslate
+            | PromptKind::BatchTranslateFast
+            | PromptKind::TranslationReview
+            | PromptKind::TranslationQa => &["target_language"],
+            PromptKind::BatchReading => &["reading_style"],
+            PromptKind::SegmentExplanation => &["native_language", "text"],
+            PromptKind::AnalysisSynthesis => &["task"],
+            _ => &[],
+        }
+    }
+
+    pub fn default_template(self) -> &'static str {
+        match self {
+            PromptKind::Translate => {
+                "You are a professional translator. Translate the following text to {target_language}. \
+                Preserve the original meaning and tone. Only return the translated text without any explanations."
+            }
+            PromptKind::BatchTranslate => {
+                "将以下编号的文本翻译成{target_language}。严格按照JSON数组格式返回，每项包含id和translation字段。"
+            }
+            PromptKind::BatchTranslateFast => {
+                "译成{target_language}，按JSON数组返回id和translation。"
+            }
+            PromptKind::TranslationReview => {
+                "你是资深译审。下面每条文本包含原文和{target_language}初译。请对照原文审校，\
+                修正误译、漏译，并把生硬、不自然的句子改写得地道流畅；初译没有问题时原样保留。\
+                严格按照JSON数组格式返回每一条的最终译文，每项包含id和translation字段。"
+            }
+            PromptKind::TranslationQa => {
+                "你是翻译质检员。下面每条文本包含原文和{target_language}译文。请对照原文评估译文，\
+                按 1-10 分打分：10 分为准确通顺，5 分以下表示有明显误译、漏译或语句不通。\
+                严格按照JSON数组格式返回，每项包含id、score（整数）和issue（有问题时用一句话说明，没有问题时为空字符串）字段。"
+            }
+            PromptKind::BatchReading => {
+                "为以下编号的文本标注读音。标注方式：{reading_style}。\
+                不要翻译或改写原文。严格按照JSON数组格式返回，每项包含id和reading字段。"
+            }
+            PromptKind::SegmentExplanation => DEFAULT_SEGMENT_EXPLANATION,
+            PromptKind::AnalysisSummary => {
+                "Provide a concise summary of the following text in 3-5 sentences."
+            }
+            PromptKind::AnalysisKeyPoints => {
+                "Extract and list the key points from the following text. Use bullet points."
+            }
+            PromptKind::AnalysisVocabulary => {
+                "Identify and explain important vocabulary words, phrases, and idioms from the following text. \
+                Include definitions and example sentences."
+            }
+            PromptKind::AnalysisGrammar => {
+                "Analyze the grammatical structures and patterns used in the following text. \
+                Highlight any interesting or complex constructions."
+            }
+            PromptKind::AnalysisFull => {
+                "Provide a comprehensive analysis of the following text including: \
+                1) Summary, 2) Key points, 3) Vocabulary highlights, 4) Grammar notes."
+            }
+            PromptKind::AnalysisSynthesis => {
+                "The following are analysis results for consecutive parts of one long text. \
+                The original instruction for each part was: {task}\n\
+                Combine them into a single result for the whole text: merge duplicates, \
+                keep the structure the instruction asks for, and do not mention the parts."
+            }
+            PromptKind::SemanticSegmentation => {
+                "下面是一篇文本按顺序切成的编号片段（可能缺少标点，如语音转写稿）。\
+                请把相邻片段组合成适合语言学习的学习单元：每个单元是意思完整的一句话或分句，\
+                对话按说话轮次分开，列表的每一项单独成单元，单元不要过长。\
+                只返回每个学习单元第一个片段的编号组成的JSON数组。"
+            }
+            PromptKind::ArticleTitle => {
+                "为下面的文章起一个简短的标题，使用与正文相同的语言，不超过15个词。\
+                只返回标题本身，不要加引号、标点或任何说明。"
+            }
+        }
+    }
+}
+
+const DEFAULT_SEGMENT_EXPLANATION: &str = r#"You are a professional language learning assistant. The user's native language is {native_language}. Please analyze the following text segment comprehensively and return the result strictly in the following JSON format. Do NOT add any extra explanations or markdown formatting outside the JSON block.
+
+User's Native Language: {native_language}
+
+Text to Analyze:
+---
+{text}
+---
+
+Please strictly adhere to this JSON structure (all keys must be in English):
+{
+  "translation": "Translate the text into natural, fluent {native_language}",
+  "explanation": "Explain the text in {native_language}, covering context, tone, and cultural background. Use Markdown formatting.",
+  "vocabulary": [
+    {
+      "word": "The word or phrase from the text",
+      "reading": "Pronunciation/Reading (e.g., Hiragana for Japanese, IPA for English)",
+      "meaning": "Core meaning in the context, explained in {native_language}",
+      "usage": "Usage notes and collocations in {native_language}",
+      "example": "Example sentence containing the word, with {native_language} translation"
+    }
+  ],
+  "grammar_points": [
+    {
+      "point": "Name of the grammar point",
+      "explanation": "Detailed explanation in {native_language}",
+      "example": "Example sentence using the grammar point, with {native_language} translation"
+    }
+  ],
+  "cultural_context": "Cultural background info in {native_language} (if applicable, else null)",
+  "difficulty_level": "beginner | intermediate | advanced",
+  "learning_tips": "Learning advice for this segment in {native_language}"
+}
+
+Ensure all explanations, meanings, and descriptive text are written in {native_language}."#;
+
+/// 模板信息（设置页使用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplateInfo {
+    pub kind: PromptKind,
+    pub template: String,
+    pub default_template: String,
+    pub placeholders: Vec<String>,
+    pub customized: bool,
+}
+
+/// 用户自定义的模板（启动时从文件加载，保存/重置时更新）
+static CUSTOM_TEMPLATES: Mutex<Option<HashMap<PromptKind, String>>> = Mutex::new(None);
+
+/// 各语言的注音方式说明（填入 BatchReading 模板的 {reading_style}）
+pub fn reading_style(language: &str) -> &'static str {
+    match language.split(['-', '_']).next().unwrap_or_default() {
+        "ja" => "在每个汉字词后用括号标注平假名读音，如 漢字(かんじ)，假名部分保持原样",
+        "zh" => "在每个汉字词后用括号标注带声调的拼音，如 汉字(hànzì)",
+        "ko" => "给出整句的罗马字转写（Revised Romanization）",
+        "ru" | "uk" | "el" | "ar" | "th" => "给出整句的拉丁字母转写",
+        _ => "给出整句的国际音标（IPA）",
+    }
+}
+
+/// 生词拉丁字母读音的标注方式（同样填入 {reading_style}），reading 字段只含读音、不含原文
+pub fn phonetic_style(language: &str) -> &'static str {
+    match language.split(['-', '_']).next().unwrap_or_default() {
+        "ja" => "只给出单词的平文式罗马字（Hepburn），长音用长音符，如 東京 -> tōkyō，不要包含原文",
+        "ko" => {
+            "只给出单词的国语罗马字（Revised Romanization），如 한국어 -> hangugeo，不要包含原文"
+        }
+        "zh" => "只给出单词带声调的拼音，如 汉字 -> hànzì，不要包含原文",
+        _ => "只给出单词的国际音标（IPA），用斜杠包围，如 water -> /ˈwɔːtər/，不要包含原文",
+    }
+}
+
+/// 根据文字推断需要注音的语言：含假名为日语，含谚文为韩语，只有汉字为中文，
+/// 西里尔字母按俄语、阿拉伯字母按阿拉伯语处理
+pub fn detect_reading_language(text: &str) -> Option<&'static str> {
+    let has = |range: std::ops::RangeInclusive<char>| text.chars().any(|c| range.contains(&c));
+    if has('\u{3040}'..='\u{30ff}') {
+        Some("ja")
+    } else if has('\u{ac00}'..='\u{d7af}') {
+        Some("ko")
+    } else if has('\u{4e00}'..='\u{9fff}') {
+        Some("zh")
+    } else if has('\u{0400}'..='\u{04ff}') {
+        Some("ru")
+    } else if has('\u{0600}'..='\u{06ff}') {
+        Some("ar")
+    } else {
+        None
+    }
+}
+
+/// 替换 `{name}` 占位符；未列出的花括号内容（如 JSON 示例）保持原样
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// 校验模板：不能为空，且只能使用该类型支持的占位符
+pub fn validate(kind: PromptKind, template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err(tr("error.prompt_template_empty"));
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        let is_identifier =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+        if is_identifier && !kind.placeholders().contains(&name) {
+            return Err(tr_with(
+                "error.prompt_template_placeholder",
+                &[("name", name.to_string())],
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 获取模板（自定义优先）
+pub fn template(kind: PromptKind) -> String {
+    CUSTOM_TEMPLATES
+        .lock()
+        .ok()
+        .and_then(|cache| cache.as_ref()?.get(&kind).cloned())
+        .unwrap_or_else(|| kind.default_template().to_string())
+}
+
+fn templates_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_data_dir(app_handle)?.join(TEMPLATES_FILE_NAME))
+}
+
+/// 从文件加载自定义模板到缓存（文件损坏时忽略，使用内置模板）
+pub fn load_templates(app_handle: &AppHandle) -> Result<(), String> {
+    let path = templates_file_path(app_handle)?;
+    let custom: HashMap<PromptKind, String> = if path.exists() {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read prompt templates: {}", e))?;
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("[PromptTemplates] Invalid templates file: {}", e);
+            HashMap::new()
+        })
+    } else {
+        HashMap::new()
+    };
+
+    let mut cache = CUSTOM_TEMPLATES.lock().map_err(|e| e.to_string())?;
+    *cache = Some(custom);
+    Ok(())
+}
+
+fn write_templates(
+    app_handle: &AppHandle,
+    custom: &HashMap<PromptKind, String>,
+) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(custom)
+        .map_err(|e| format!("Failed to serialize prompt templates: {}", e))?;
+    fs::write(templates_file_path(app_handle)?, content)
+        .map_err(|e| format!("Failed to write prompt templates: {}", e))
+}
+
+/// 保存（template 为 None 时重置为内置模板）并更新缓存
+pub fn set_template(
+    app_handle: &AppHandle,
+    kind: PromptKind,
+    template: Option<String>,
+) -> Result<(), String> {
+    if let Some(t) = &template {
+        validate(kind, t)?;
+    }
+
+    let mut cache = CUSTOM_TEMPLATES.lock().map_err(|e| e.to_string())?;
+    let mut custom = cache.clone().unwrap_or_default();
+    match template {
+        Some(t) => custom.insert(kind, t),
+        None => custom.remove(&kind),
+    };
+    write_templates(app_handle, &custom)?;
+    *cache = Some(custom);
+    Ok(())
+}
+
+pub fn template_info(kind: PromptKind) -> PromptTemplateInfo {
+    let template = template(kind);
+    PromptTemplateInfo {
+        kind,
+        customized: template != kind.default_template(),
+        default_template: kind.default_template().to_string(),
+        placeholders: kind.placeholders().iter().map(|p| p.to_string()).collect(),
+        template,
+    }
+}
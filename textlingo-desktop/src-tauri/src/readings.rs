@@ -0,0 +1,494 @@
+// 本地注音：无需调用 AI 的注音方式
+// 中文用拼音词典生成带声调的拼音，韩语按国语罗马字（Revised Romanization）转写，
+// 西里尔字母和阿拉伯字母按固定对照表转写为拉丁字母；日语假名可转写为罗马字（用于生词读音）
+
+use pinyin::ToPinyin;
+
+fn primary_subtag(language: &str) -> &str {
+    language.split(['-', '_']).next().unwrap_or_default()
+}
+
+/// 该语言是否支持本地注音
+pub fn supports_local(language: &str) -> bool {
+    matches!(
+        primary_subtag(language),
+        "zh" | "ko"
+            | "ru"
+            | "uk"
+            | "be"
+            | "bg"
+            | "sr"
+            | "mk"
+            | "kk"
+            | "ky"
+            | "mn"
+            | "tg"
+            | "ar"
+            | "fa"
+            | "ur"
+    )
+}
+
+/// 按语言生成本地注音，不支持的语言返回 None
+pub fn local_reading(language: &str, text: &str) -> Option<String> {
+    if !supports_local(language) {
+        return None;
+    }
+    Some(match primary_subtag(language) {
+        "zh" => annotate_pinyin(text),
+        "ko" => romanize_korean(text),
+        "ar" | "fa" | "ur" => transliterate_arabic(text),
+        _ => transliterate_cyrillic(text),
+    })
+}
+
+/// 给每段连续的汉字加注带声调的拼音，格式与 AI 注音一致：你好(nǐ hǎo)，其他字符原样保留
+pub fn annotate_pinyin(text: &str) -> String {
+    let mut result = String::new();
+    let mut run = String::new();
+    let mut syllables: Vec<&str> = Vec::new();
+
+    for c in text.chars() {
+        match c.to_pinyin() {
+            Some(pinyin) => {
+                run.push(c);
+                syllables.push(pinyin.with_tone());
+            }
+            None => {
+                flush_run(&mut result, &mut run, &mut syllables);
+                result.push(c);
+            }
+        }
+    }
+    flush_run(&mut result, &mut run, &mut syllables);
+    result
+}
+
+fn flush_run(result: &mut String, run: &mut String, syllables: &mut Vec<&str>) {
+    if run.is_empty() {
+        return;
+    }
+    result.push_str(run);
+    result.push('(');
+    result.push_str(&syllables.join(" "));
+    result.push(')');
+    run.clear();
+    syllables.clear();
+}
+
+const HANGUL_BASE: u32 = 0xAC00;
+const HANGUL_LAST: u32 = 0xD7A3;
+
+const KO_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+const KO_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+/// 音节末尾（收音）的读法
+const KO_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+/// 后接元音开头（ㅇ）的音节时，单收音连读为下一音节的声母
+const KO_FINALS_LIAISON: [Option<&str>; 28] = [
+    None,
+    Some("g"),
+    Some("kk"),
+    None,
+    Some("n"),
+    None,
+    None,
+    Some("d"),
+    Some("r"),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some("m"),
+    Some("b"),
+    None,
+    Some("s"),
+    Some("ss"),
+    None,
+    Some("j"),
+    Some("ch"),
+    Some("k"),
+    Some("t"),
+    Some("p"),
+    Some(""),
+];
+
+/// 谚文音节的 (声母, 韵母, 收音) 序号
+type HangulParts = (usize, usize, usize);
+
+/// 拆分谚文音节
+fn decompose_hangul(c: char) -> Option<HangulParts> {
+    let code = c as u32;
+    if !(HANGUL_BASE..=HANGUL_LAST).contains(&code) {
+        return None;
+    }
+    let index = (code - HANGUL_BASE) as usize;
+    Some((index / 588, (index % 588) / 28, index % 28))
+}
+
+/// 韩语转写为国语罗马字，处理收音连读（한국어 -> hangugeo），非谚文字符原样保留
+pub fn romanize_korean(text: &str) -> String {
+    let syllables: Vec<(char, Option<HangulParts>)> =
+        text.chars().map(|c| (c, decompose_hangul(c))).collect();
+    let mut result = String::new();
+
+    for (i, (c, parts)) in syllables.iter().enumerate() {
+        let Some((initial, vowel, final_)) = *parts else {
+            result.push(*c);
+            continue;
+        };
+        // 上一音节的收音已连读为本音节声母时，本音节不再输出 ㅇ 声母
+        let initial_carried = i > 0
+            && initial == 11
+            && syllables[i - 1]
+                .1
+                .is_some_and(|(_, _, prev_final)| KO_FINALS_LIAISON[prev_final].is_some());
+        if !initial_carried {
+            result.push_str(KO_INITIALS[initial]);
+        }
+        result.push_str(KO_VOWELS[vowel]);
+
+        let next_is_vowel = syllables
+            .get(i + 1)
+            .and_then(|(_, next)| *next)
+            .is_some_and(|(next_initial, _, _)| next_initial == 11);
+        match KO_FINALS_LIAISON[final_] {
+            Some(liaison) if next_is_vowel => result.push_str(liaison),
+            _ => result.push_str(KO_FINALS[final_]),
+        }
+    }
+    result
+}
+
+fn cyrillic_letter(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ґ' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => "yo",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'і' => "i",
+        'ї' => "yi",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ў' => "w",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' => "",
+        'ы' => "y",
+        'ь' => "",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        'ђ' => "dj",
+        'ј' => "j",
+        'љ' => "lj",
+        'њ' => "nj",
+        'ћ' => "c",
+        'џ' => "dz",
+        'ә' => "a",
+        'ғ' => "gh",
+        'қ' => "q",
+        'ң' => "ng",
+        'ө' => "o",
+        'ұ' => "u",
+        'ү' => "u",
+        'һ' => "h",
+        _ => return None,
+    })
+}
+
+/// 西里尔字母转写为拉丁字母，保留大写首字母，其他字符原样保留
+pub fn transliterate_cyrillic(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        match cyrillic_letter(lower) {
+            Some(latin) if lower != c => {
+                let mut chars = latin.chars();
+                if let Some(first) = chars.next() {
+                    result.extend(first.to_uppercase());
+                    result.push_str(chars.as_str());
+                }
+            }
+            Some(latin) => result.push_str(latin),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+fn arabic_letter(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ا' | 'ى' | 'ة' => "a",
+        'أ' => "a",
+        'إ' => "i",
+        'آ' => "aa",
+        'ء' | 'ئ' | 'ؤ' | 'ع' => "'",
+        'ب' => "b",
+        'پ' => "p",
+        'ت' | 'ط' => "t",
+        'ث' => "th",
+        'ج' => "j",
+        'چ' => "ch",
+        'ح' | 'ه' | 'ھ' => "h",
+        'خ' => "kh",
+        'د' | 'ض' => "d",
+        'ذ' => "dh",
+        'ر' => "r",
+        'ز' | 'ظ' => "z",
+        'ژ' => "zh",
+        'س' | 'ص' => "s",
+        'ش' => "sh",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ك' | 'ک' => "k",
+        'گ' => "g",
+        'ل' => "l",
+        'م' => "m",
+        'ن' => "n",
+        'و' => "w",
+        'ي' | 'ی' => "y",
+        // 元音符号（多数文本不标注，此时只得到辅音骨架）
+        '\u{064E}' => "a",
+        '\u{0650}' => "i",
+        '\u{064F}' => "u",
+        '\u{064B}' => "an",
+        '\u{064D}' => "in",
+        '\u{064C}' => "un",
+        '\u{0652}' | '\u{0651}' | '\u{0640}' => "",
+        '،' => ",",
+        '؟' => "?",
+        '؛' => ";",
+        _ => return None,
+    })
+}
+
+/// 阿拉伯字母（含波斯语、乌尔都语常用字母）转写为拉丁字母，其他字符原样保留
+pub fn transliterate_arabic(text: &str) -> String {
+    text.chars()
+        .map(|c| match arabic_letter(c) {
+            Some(latin) => latin.to_string(),
+            None => c.to_string(),
+        })
+        .collect()
+}
+
+/// 单个平假名（片假名先转为平假名）的平文式罗马字，拗音、促音、长音由 kana_to_romaji 组合处理
+fn kana_syllable(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+/// 片假名转为对应的平假名，其他字符不变
+fn to_hiragana(c: char) -> char {
+    match c {
+        '\u{30a1}'..='\u{30f6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// 假名转写为平文式罗马字（ひらがな -> hiragana，コーヒー -> kōhī）。
+/// 拗音合并（きゃ -> kya），促音双写下一个辅音，长音符号ー给前一个元音加长音符，
+/// ん 在元音和 y 前写作 n'。含汉字等非假名字符时无法转写，返回 None
+pub fn kana_to_romaji(text: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().map(to_hiragana).collect();
+    let mut syllables: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            'っ' => syllables.push("っ".to_string()),
+            'ー' => syllables.push("ー".to_string()),
+            '・' | '　' | ' ' => syllables.push(" ".to_string()),
+            '、' | '。' => {}
+            _ => {
+                let base = kana_syllable(c)?;
+                let small = chars.get(i + 1).copied().filter(|next| {
+                    matches!(next, 'ゃ' | 'ゅ' | 'ょ' | 'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ')
+                });
+                match small {
+                    // 小写假名与前一个假名合为一个音节：辅音部分 + 小写假名的元音
+                    // う、い 后接小写元音为外来音 wi、ye 等
+                    Some(small)
+                        if base.len() > 1
+                            || matches!((base, small), ("u", 'ぃ' | 'ぇ' | 'ぉ') | ("i", 'ぇ')) =>
+                    {
+                        let stem = match base {
+                            "u" => "w",
+                            "i" => "y",
+                            _ => &base[..base.len() - 1],
+                        };
+                        let tail = kana_syllable(small)?;
+                        let syllable = match (stem, tail.strip_prefix('y')) {
+                            ("sh" | "ch" | "j", Some(vowel)) => format!("{}{}", stem, vowel),
+                            _ => format!("{}{}", stem, tail),
+                        };
+                        syllables.push(syllable);
+                        i += 1;
+                    }
+                    _ => syllables.push(base.to_string()),
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let mut romaji = String::new();
+    for (index, syllable) in syllables.iter().enumerate() {
+        let next = syllables.get(index + 1).map(String::as_str).unwrap_or("");
+        match syllable.as_str() {
+            "っ" => {
+                if next.starts_with("ch") {
+                    romaji.push('t');
+                } else if let Some(consonant) = next
+                    .chars()
+                    .next()
+                    .filter(|c| c.is_ascii_alphabetic() && !"aiueon".contains(*c))
+                {
+                    romaji.push(consonant);
+                }
+            }
+            "ー" => {
+                if let Some(last) = romaji.pop() {
+                    romaji.push(match last {
+                        'a' => 'ā',
+                        'i' => 'ī',
+                        'u' => 'ū',
+                        'e' => 'ē',
+                        'o' => 'ō',
+                        other => other,
+                    });
+                }
+            }
+            "n" => {
+                romaji.push('n');
+                if next.starts_with(['a', 'i', 'u', 'e', 'o', 'y']) {
+                    romaji.push('\'');
+                }
+            }
+            other => romaji.push_str(other),
+        }
+    }
+    let romaji = romaji.trim().to_string();
+    (!romaji.is_empty()).then_some(romaji)
+}
+
+/// 生词的本地读音：日语用读音（没有时用单词本身）转写为罗马字，韩语转写为国语罗马字。
+/// 其他语言或含汉字而没有假名读音时返回 None，需要由 AI 生成
+pub fn local_phonetic(language: &str, word: &str, reading: Option<&str>) -> Option<String> {
+    match primary_subtag(language) {
+        "ja" => reading
+            .map(str::trim)
+            .filter(|reading| !reading.is_empty())
+            .and_then(kana_to_romaji)
+            .or_else(|| kana_to_romaji(word.trim())),
+        "ko" => {
+            let romanized = romanize_korean(word.trim());
+            (romanized.is_ascii() && !romanized.trim().is_empty()).then_some(romanized)
+        }
+        _ => None,
+    }
+}
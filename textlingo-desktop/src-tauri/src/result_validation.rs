@@ -0,0 +1,90 @@
+// AI 生成的段落数据在写入文章前的校验：模型偶尔返回空字段、超长的重复输出或把 JSON 原样当译文，
+// 存进文章后会导致阅读界面渲染异常。不合格的结果不写入文章，另存到隔离区供查看和重试
+
+use crate::types::SegmentExplanation;
+
+/// 单个字段的最大字符数，超出通常是模型陷入重复输出
+pub const MAX_FIELD_CHARS: usize = 10_000;
+
+/// 译文长度相对原文的上限倍数（短句另有 MAX_FIELD_CHARS 以内的余量）
+const MAX_TRANSLATION_RATIO: usize = 8;
+
+/// 除换行、制表符外的控制字符会破坏渲染
+fn has_control_chars(text: &str) -> bool {
+    text.chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+}
+
+fn check_field(name: &str, value: &str, required: bool) -> Result<(), String> {
+    if required && value.trim().is_empty() {
+        return Err(format!("{} is empty", name));
+    }
+    if value.chars().count() > MAX_FIELD_CHARS {
+        return Err(format!("{} exceeds {} characters", name, MAX_FIELD_CHARS));
+    }
+    if has_control_chars(value) {
+        return Err(format!("{} contains control characters", name));
+    }
+    Ok(())
+}
+
+fn check_optional(name: &str, value: Option<&str>) -> Result<(), String> {
+    value.map_or(Ok(()), |value| check_field(name, value, false))
+}
+
+/// 校验段落讲解：译文和讲解必填，词汇和语法点不能缺少主体内容
+pub fn validate_explanation(explanation: &SegmentExplanation) -> Result<(), String> {
+    check_field("translation", &explanation.translation, true)?;
+    check_field("explanation", &explanation.explanation, true)?;
+    check_optional("reading_text", explanation.reading_text.as_deref())?;
+    check_optional("cultural_context", explanation.cultural_context.as_deref())?;
+    check_optional("difficulty_level", explanation.difficulty_level.as_deref())?;
+    check_optional("learning_tips", explanation.learning_tips.as_deref())?;
+    for (i, item) in explanation.vocabulary.iter().enumerate() {
+        check_field(&format!("vocabulary[{}].word", i), &item.word, true)?;
+        check_field(&format!("vocabulary[{}].meaning", i), &item.meaning, true)?;
+        check_field(&format!("vocabulary[{}].usage", i), &item.usage, false)?;
+        check_optional(
+            &format!("vocabulary[{}].example", i),
+            item.example.as_deref(),
+        )?;
+        check_optional(
+            &format!("vocabulary[{}].reading", i),
+            item.reading.as_deref(),
+        )?;
+    }
+    for (i, point) in explanation.grammar_points.iter().enumerate() {
+        check_field(&format!("grammar_points[{}].point", i), &point.point, true)?;
+        check_field(
+            &format!("grammar_points[{}].explanation", i),
+            &point.explanation,
+            false,
+        )?;
+        check_optional(
+            &format!("grammar_points[{}].example", i),
+            point.example.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// 校验段落译文：不能为空、不能远长于原文，也不能是模型原样返回的 JSON
+pub fn validate_translation(source: &str, translation: &str) -> Result<(), String> {
+    check_field("translation", translation, true)?;
+    let limit = (source.chars().count() * MAX_TRANSLATION_RATIO).max(200);
+    if translation.chars().count() > limit {
+        return Err(format!(
+            "translation is more than {} times longer than the source",
+            MAX_TRANSLATION_RATIO
+        ));
+    }
+    let trimmed = translation.trim();
+    let source_is_json = source.trim_start().starts_with(['{', '[']);
+    if !source_is_json
+        && trimmed.starts_with(['{', '['])
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return Err("translation is a raw JSON payload".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,801 @@
+// 文章分段：按分段方式把正文切成段落列表
+// 句子模式之外还支持按空行段落、按行（歌词、诗歌、对话稿）、按固定长度和不切分
+
+use crate::language_detect::{sentence_rules, SentenceRules};
+use crate::types::{ArticleSegment, MoveDirection, SegmentationMode};
+use crate::web_images::parse_image_placeholder;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 切分出的一段（尚未分配 id 和顺序）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentPiece {
+    pub text: String,
+    /// 是否另起一行显示
+    pub is_new_paragraph: bool,
+    /// 网页导入保留的插图（此时 text 为图片说明）
+    pub image_url: Option<String>,
+}
+
+enum Block<'a> {
+    Image {
+        alt: String,
+        url: String,
+    },
+    /// 连续的非空行
+    Text(Vec<&'a str>),
+}
+
+/// 按行拆成块：插图独占一块；空行分隔文本块，merge_text 为 true 时插图之间的文本合为一块
+fn content_blocks(content: &str, merge_text: bool) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut lines = Vec::new();
+    for line in content.split('\n').map(str::trim) {
+        if line.is_empty() {
+            if !merge_text && !lines.is_empty() {
+                blocks.push(Block::Text(std::mem::take(&mut lines)));
+            }
+            continue;
+        }
+        if let Some((alt, url)) = parse_image_placeholder(line) {
+            if !lines.is_empty() {
+                blocks.push(Block::Text(std::mem::take(&mut lines)));
+            }
+            blocks.push(Block::Image { alt, url });
+            continue;
+        }
+        lines.push(line);
+    }
+    if !lines.is_empty() {
+        blocks.push(Block::Text(lines));
+    }
+    blocks
+}
+
+/// 按分段方式切分正文，分句规则随原文语言而定
+pub fn split_content(
+    content: &str,
+    language: Option<&str>,
+    mode: &SegmentationMode,
+) -> Vec<SegmentPiece> {
+    let rules = sentence_rules(language);
+    let mut pieces = Vec::new();
+
+    for block in content_blocks(content, *mode == SegmentationMode::NoSplit) {
+        let lines = match block {
+            Block::Image { alt, url } => {
+                pieces.push(SegmentPiece {
+                    text: alt,
+                    is_new_paragraph: true,
+                    image_url: Some(url),
+                });
+                continue;
+            }
+            Block::Text(lines) => lines,
+        };
+
+        match mode {
+            // 每行视为一个段落，段落的第一个句子换行显示，后续句子紧跟前一个显示
+            SegmentationMode::Sentence | SegmentationMode::Semantic => {
+                for line in lines {
+                    push_paragraph(&mut pieces, split_into_sentences(line, &rules));
+                }
+            }
+            SegmentationMode::FixedLength { max_chars } => {
+                for line in lines {
+                    let sentences = split_into_sentences(line, &rules);
+                    push_paragraph(&mut pieces, pack_sentences(sentences, *max_chars));
+                }
+            }
+            SegmentationMode::Line => {
+                for line in lines {
+                    push_paragraph(&mut pieces, vec![line.to_string()]);
+                }
+            }
+            SegmentationMode::Paragraph | SegmentationMode::NoSplit => {
+                push_paragraph(&mut pieces, vec![lines.join("\n")]);
+            }
+        }
+    }
+
+    pieces
+}
+
+fn push_paragraph(pieces: &mut Vec<SegmentPiece>, texts: Vec<String>) {
+    for (index, text) in texts.into_iter().enumerate() {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        pieces.push(SegmentPiece {
+            text: text.to_string(),
+            is_new_paragraph: index == 0,
+            image_url: None,
+        });
+    }
+}
+
+/// 把相邻句子合并成不超过 max_chars 个字符的段，超长的句子强制截断
+fn pack_sentences(sentences: Vec<String>, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for part in sentences.iter().flat_map(|s| hard_split(s, max_chars)) {
+        let part_len = part.chars().count();
+        let separator = needs_space(&current, &part);
+        if !current.is_empty() && current_len + usize::from(separator) + part_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        } else if separator {
+            current.push(' ');
+            current_len += 1;
+        }
+        current.push_str(&part);
+        current_len += part_len;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// 按字符数截断，尽量在空白处断开
+fn hard_split(text: &str, max_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut rest = &chars[..];
+    let mut parts = Vec::new();
+    while rest.len() > max_chars {
+        let cut = rest[..max_chars]
+            .iter()
+            .rposition(|c| c.is_whitespace())
+            .filter(|&pos| pos > 0)
+            .unwrap_or(max_chars);
+        parts.push(rest[..cut].iter().collect::<String>().trim().to_string());
+        rest = &rest[cut..];
+        while rest.first().is_some_and(|c| c.is_whitespace()) {
+            rest = &rest[1..];
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(rest.iter().collect());
+    }
+    parts
+}
+
+/// 语义分段时交给模型组合的最小单元长度
+pub const SEMANTIC_UNIT_CHARS: usize = 40;
+
+/// 语义分段第一步：按句切分后把长句在空白处切成不超过 SEMANTIC_UNIT_CHARS 个字符的小单元
+pub fn semantic_units(content: &str, language: Option<&str>) -> Vec<SegmentPiece> {
+    let mut units = Vec::new();
+    for piece in split_content(content, language, &SegmentationMode::Sentence) {
+        if piece.image_url.is_some() {
+            units.push(piece);
+            continue;
+        }
+        for (index, text) in hard_split(&piece.text, SEMANTIC_UNIT_CHARS)
+            .into_iter()
+            .enumerate()
+        {
+            units.push(SegmentPiece {
+                text,
+                is_new_paragraph: index == 0 && piece.is_new_paragraph,
+                image_url: None,
+            });
+        }
+    }
+    units
+}
+
+/// 按模型返回的起始编号组合单元：编号在 starts 中的单元开始新段落，插图始终单独成段，
+/// 无效编号忽略；段落沿用第一个单元的换行标记
+pub fn group_semantic_units(units: Vec<SegmentPiece>, starts: &[usize]) -> Vec<SegmentPiece> {
+    let starts: HashSet<usize> = starts.iter().copied().collect();
+    let mut grouped: Vec<SegmentPiece> = Vec::new();
+    for (index, unit) in units.into_iter().enumerate() {
+        match grouped.last_mut() {
+            Some(last)
+                if unit.image_url.is_none()
+                    && last.image_url.is_none()
+                    && !starts.contains(&index) =>
+            {
+                if needs_space(&last.text, &unit.text) {
+                    last.text.push(' ');
+                }
+                last.text.push_str(&unit.text);
+            }
+            _ => grouped.push(unit),
+        }
+    }
+    grouped
+}
+
+/// 长句拆分的最小段落长度，避免设置过小时切出零碎片段
+pub const MIN_SEGMENT_CHARS: usize = 20;
+
+/// 可以在其后断开的分句标点
+const CLAUSE_PUNCTUATION: &[char] = &[',', ';', ':', '，', '、', '；', '：'];
+
+/// 可以在其前断开的连词；拉丁文字的连词需前后都是空白
+const CLAUSE_CONJUNCTIONS: &[&str] = &[
+    "and",
+    "but",
+    "or",
+    "so",
+    "because",
+    "which",
+    "while",
+    "whereas",
+    "although",
+    "unless",
+    "et",
+    "mais",
+    "ou",
+    "und",
+    "aber",
+    "oder",
+    "pero",
+    "ma",
+    "但是",
+    "而且",
+    "因此",
+    "所以",
+    "并且",
+    "しかし",
+    "そして",
+    "けれども",
+];
+
+/// 把超过 max_chars 个字符的段落在逗号、连词处拆开，拆出的后续片段紧跟前一段显示
+pub fn limit_piece_length(pieces: Vec<SegmentPiece>, max_chars: usize) -> Vec<SegmentPiece> {
+    let max_chars = max_chars.max(MIN_SEGMENT_CHARS);
+    let mut limited = Vec::with_capacity(pieces.len());
+    for piece in pieces {
+        if piece.image_url.is_some() || piece.text.chars().count() <= max_chars {
+            limited.push(piece);
+            continue;
+        }
+        for (index, text) in split_long_sentence(&piece.text, max_chars)
+            .into_iter()
+            .enumerate()
+        {
+            limited.push(SegmentPiece {
+                text,
+                is_new_paragraph: index == 0 && piece.is_new_paragraph,
+                image_url: None,
+            });
+        }
+    }
+    limited
+}
+
+/// 拆分长句：优先在分句标点后断开，其次在连词前，都没有时在空白处或按长度截断
+pub fn split_long_sentence(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let chars: Vec<char> = text.chars().collect();
+    let mut rest = &chars[..];
+    let mut parts = Vec::new();
+    while rest.len() > max_chars {
+        let cut = clause_break(rest, max_chars)
+            .or_else(|| {
+                rest[..max_chars]
+                    .iter()
+                    .rposition(|c| c.is_whitespace())
+                    .filter(|&pos| pos > 0)
+            })
+            .unwrap_or(max_chars);
+        let part = rest[..cut].iter().collect::<String>().trim().to_string();
+        if !part.is_empty() {
+            parts.push(part);
+        }
+        rest = &rest[cut..];
+        while rest.first().is_some_and(|c| c.is_whitespace()) {
+            rest = &rest[1..];
+        }
+    }
+    let last = rest.iter().collect::<String>().trim().to_string();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// 在前 max_chars 个字符内找最靠后的断点（返回断开位置），太靠前的断点会切出零碎片段，不予考虑
+fn clause_break(chars: &[char], max_chars: usize) -> Option<usize> {
+    let end = max_chars.min(chars.len().saturating_sub(1));
+    let start = (max_chars / 4).max(1);
+    if start > end {
+        return None;
+    }
+    let after_punctuation = |pos: usize| {
+        let prev = chars[pos - 1];
+        // 半角标点后需有空白，避免拆开 1,000 或 10:30
+        CLAUSE_PUNCTUATION.contains(&prev) && (!prev.is_ascii() || chars[pos].is_whitespace())
+    };
+    (start..=end)
+        .rev()
+        .find(|&pos| after_punctuation(pos))
+        .or_else(|| {
+            (start..=end)
+                .rev()
+                .find(|&pos| starts_conjunction(chars, pos))
+        })
+}
+
+fn starts_conjunction(chars: &[char], pos: usize) -> bool {
+    CLAUSE_CONJUNCTIONS.iter().any(|word| {
+        let word: Vec<char> = word.chars().collect();
+        let Some(candidate) = chars.get(pos..pos + word.len()) else {
+            return false;
+        };
+        if !candidate
+            .iter()
+            .zip(&word)
+            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+        {
+            return false;
+        }
+        if word[0].is_ascii() {
+            chars[pos - 1].is_whitespace()
+                && chars
+                    .get(pos + word.len())
+                    .is_some_and(|c| c.is_whitespace())
+        } else {
+            true
+        }
+    })
+}
+
+/// 合并时两侧都不是中日韩文字才需要加空格
+fn needs_space(current: &str, next: &str) -> bool {
+    let is_cjk = |c: char| c >= '\u{2e80}';
+    match (current.chars().last(), next.chars().next()) {
+        (Some(last), Some(first)) => !is_cjk(last) && !is_cjk(first),
+        _ => false,
+    }
+}
+
+/// 重新分段后把旧段落的译文、注音、讲解、时间轴、文档结构和说话人转到文本相同的新段落上，返回转移的段落数
+/// 文本按空白归一化后比较；相同文本出现多次时按先后顺序一一对应，匹配的新段落沿用旧 id
+pub fn carry_over_segments(old: &[ArticleSegment], new: &mut [ArticleSegment]) -> usize {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut by_text: HashMap<String, VecDeque<&ArticleSegment>> = HashMap::new();
+    for segment in old {
+        by_text
+            .entry(normalize(&segment.text))
+            .or_default()
+            .push_back(segment);
+    }
+
+    let mut carried = 0;
+    for segment in new.iter_mut() {
+        let Some(previous) = by_text
+            .get_mut(&normalize(&segment.text))
+            .and_then(VecDeque::pop_front)
+        else {
+            continue;
+        };
+        if previous.image_url != segment.image_url {
+            continue;
+        }
+        segment.id = previous.id.clone();
+        segment.reading_text = previous.reading_text.clone();
+        segment.translation = previous.translation.clone();
+        segment.translations = previous.translations.clone();
+        segment.explanation = previous.explanation.clone();
+        segment.start_time = previous.start_time;
+        segment.end_time = previous.end_time;
+        segment.block = segment.block.or(previous.block);
+        if segment.speaker.is_none() {
+            segment.speaker = previous.speaker.clone();
+        }
+        carried += 1;
+    }
+    carried
+}
+
+/// 在第 char_offset 个字符处把段落拆成两段；拆分点两侧去掉空白后都不能为空，否则返回 None
+/// 前一段沿用原 id；原有译文、注音和讲解对应整段，拆分后清空；
+/// 字幕段落按字符比例在原时间范围内插值出分界时间
+pub fn split_segment_at(
+    segment: &ArticleSegment,
+    char_offset: usize,
+) -> Option<(ArticleSegment, ArticleSegment)> {
+    let chars: Vec<char> = segment.text.chars().collect();
+    if segment.image_url.is_some() || char_offset == 0 || char_offset >= chars.len() {
+        return None;
+    }
+    let head = chars[..char_offset]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    let tail = chars[char_offset..]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    if head.is_empty() || tail.is_empty() {
+        return None;
+    }
+
+    let split_time = match (segment.start_time, segment.end_time) {
+        (Some(start), Some(end)) => {
+            Some(start + (end - start) * char_offset as f64 / chars.len() as f64)
+        }
+        _ => None,
+    };
+
+    let mut first = segment.clone();
+    first.text = head;
+    first.reading_text = None;
+    first.translation = None;
+    first.translations.clear();
+    first.explanation = None;
+    first.processing_status = None;
+    if split_time.is_some() {
+        first.end_time = split_time;
+    }
+
+    let second = ArticleSegment {
+        id: uuid::Uuid::new_v4().to_string(),
+        text: tail,
+        start_time: split_time.or(segment.start_time),
+        end_time: segment.end_time,
+        is_new_paragraph: false,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ..first.clone()
+    };
+
+    Some((first, second))
+}
+
+/// 把 ids 指定的相邻段落合并为一段（沿用第一段的 id），并重新编号 order
+/// 至少两段、必须在列表中连续且都不是插图，否则返回 false 且不做修改；
+/// 时间范围取各段的并集，原有译文、注音和讲解清空
+pub fn merge_adjacent_segments(segments: &mut Vec<ArticleSegment>, ids: &[String]) -> bool {
+    let mut indices: Vec<usize> = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| ids.contains(&s.id))
+        .map(|(i, _)| i)
+        .collect();
+    indices.sort_unstable();
+    let (Some(&first), Some(&last)) = (indices.first(), indices.last()) else {
+        return false;
+    };
+    if indices.len() < 2
+        || indices.len() != ids.len()
+        || last - first + 1 != indices.len()
+        || segments[first..=last].iter().any(|s| s.image_url.is_some())
+    {
+        return false;
+    }
+
+    let merged_parts: Vec<ArticleSegment> = segments.drain(first + 1..=last).collect();
+    let target = &mut segments[first];
+    for part in &merged_parts {
+        if needs_space(&target.text, &part.text) {
+            target.text.push(' ');
+        }
+        target.text.push_str(&part.text);
+    }
+    let times = || std::iter::once(&*target).chain(&merged_parts);
+    let start = times().filter_map(|s| s.start_time).reduce(f64::min);
+    let end = times().filter_map(|s| s.end_time).reduce(f64::max);
+    target.start_time = start;
+    target.end_time = end;
+    target.reading_text = None;
+    target.translation = None;
+    target.translations.clear();
+    target.explanation = None;
+    target.processing_status = None;
+
+    renumber_segments(segments);
+    true
+}
+
+/// 按列表位置重新编号 order
+pub fn renumber_segments(segments: &mut [ArticleSegment]) {
+    for (order, segment) in segments.iter_mut().enumerate() {
+        segment.order = order as i32;
+    }
+}
+
+/// 删除第 index 段并重新编号；被删段落如果是段首，由下一段接替段首标记
+pub fn remove_segment(segments: &mut Vec<ArticleSegment>, index: usize) -> Option<ArticleSegment> {
+    if index >= segments.len() {
+        return None;
+    }
+    let removed = segments.remove(index);
+    if removed.is_new_paragraph {
+        if let Some(next) = segments.get_mut(index) {
+            next.is_new_paragraph = true;
+        }
+    }
+    renumber_segments(segments);
+    Some(removed)
+}
+
+/// 撤销 remove_segment：把段落放回第 index 位（超出时追加到末尾），
+/// 恢复下一段原有的段首标记并重新编号
+pub fn restore_segment(
+    segments: &mut Vec<ArticleSegment>,
+    index: usize,
+    segment: ArticleSegment,
+    next_is_new_paragraph: Option<bool>,
+) {
+    let index = index.min(segments.len());
+    segments.insert(index, segment);
+    if let (Some(flag), Some(next)) = (next_is_new_paragraph, segments.get_mut(index + 1)) {
+        next.is_new_paragraph = flag;
+    }
+    renumber_segments(segments);
+}
+
+/// 把第 index 段与上一段或下一段交换位置并重新编号；已在首尾时返回 false
+/// 段首标记跟随位置而不是段落，移动不改变分段结构
+pub fn move_segment(
+    segments: &mut [ArticleSegment],
+    index: usize,
+    direction: MoveDirection,
+) -> bool {
+    let other = match direction {
+        MoveDirection::Up => index.checked_sub(1),
+        MoveDirection::Down => Some(index + 1),
+    };
+    let Some(other) = other.filter(|&other| other < segments.len() && index < segments.len())
+    else {
+        return false;
+    };
+    segments.swap(index, other);
+    let (a, b) = (index.min(other), index.max(other));
+    let paragraph = segments[a].is_new_paragraph;
+    segments[a].is_new_paragraph = segments[b].is_new_paragraph;
+    segments[b].is_new_paragraph = paragraph;
+    renumber_segments(segments);
+    true
+}
+
+/// 阅读到某段时需要预取讲解的段落：按顺序取其后 count 个文字段落中还没有讲解的，
+/// 图片和空段不计数；找不到当前段落时返回空
+pub fn segments_to_prefetch<'a>(
+    segments: &'a [ArticleSegment],
+    current_id: &str,
+    count: usize,
+) -> Vec<&'a ArticleSegment> {
+    let mut ordered: Vec<&ArticleSegment> = segments.iter().collect();
+    ordered.sort_by_key(|segment| segment.order);
+    let Some(position) = ordered.iter().position(|segment| segment.id == current_id) else {
+        return Vec::new();
+    };
+    ordered[position + 1..]
+        .iter()
+        .filter(|segment| segment.image_url.is_none() && !segment.text.trim().is_empty())
+        .take(count)
+        .filter(|segment| segment.explanation.is_none())
+        .copied()
+        .collect()
+}
+
+/// 自动生成的标题最多保留的字符数
+pub const MAX_TITLE_CHARS: usize = 50;
+
+/// 取正文第一句（跳过插图）作为标题，去掉句末标点，过长时截断并加省略号
+pub fn title_from_content(content: &str, language: Option<&str>) -> Option<String> {
+    let first = split_content(content, language, &SegmentationMode::Sentence)
+        .into_iter()
+        .find(|piece| piece.image_url.is_none())?;
+    let sentence = first
+        .text
+        .trim()
+        .trim_end_matches(['。', '.', '．', '，', ',', ':', '：']);
+    let sentence = sentence.trim();
+    if sentence.is_empty() {
+        return None;
+    }
+    if sentence.chars().count() <= MAX_TITLE_CHARS {
+        return Some(sentence.to_string());
+    }
+    let truncated: String = sentence.chars().take(MAX_TITLE_CHARS).collect();
+    Some(format!("{}…", truncated.trim_end()))
+}
+
+/// 把正文按行切成不超过 max_chars 个字符的块（用于长文分块分析），超长的行强制截断
+pub fn chunk_text(content: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    let lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    for part in lines.flat_map(|line| hard_split(line, max_chars)) {
+        let part_len = part.chars().count();
+        if !current.is_empty() && current_len + 1 + part_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        } else if !current.is_empty() {
+            current.push('\n');
+            current_len += 1;
+        }
+        current.push_str(&part);
+        current_len += part_len;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// 句末标点之后一并归入该句的收尾引号和括号
+const CLOSERS: &[char] = &[
+    '"', '\'', '\u{201d}', '\u{2019}', ')', '）', '」', '』', '】', '》', '〉', ']', '］',
+];
+
+/// 成对的中日文引号和括号：括号内的句末标点不断句，整段引语保持在同一句中
+const QUOTE_PAIRS: &[(char, char)] = &[
+    ('「', '」'),
+    ('『', '』'),
+    ('\u{201c}', '\u{201d}'),
+    ('（', '）'),
+    ('【', '】'),
+    ('《', '》'),
+    ('〈', '〉'),
+];
+
+/// 引号后接引用助词时句子尚未结束，如 「行こう。」と言った / 「うん。」って
+const QUOTATIVE_PARTICLES: &[char] = &['と', 'っ'];
+
+/// 将段落拆分成句子，保留句末标点
+/// 半角句点之外的句末标点由 rules 决定（默认为 。？！?!）；
+/// 连续的句末标点（？！、。。。、...）和其后的收尾引号、括号归入同一句
+fn split_into_sentences(text: &str, rules: &SentenceRules) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    // 引号不成对（如跨行的引语）时退回不识别引号的切分，避免整段合成一句
+    let mut sentences = split_sentences_with(&chars, rules, true)
+        .unwrap_or_else(|| split_sentences_with(&chars, rules, false).unwrap_or_default());
+
+    // 如果整个段落没有分割成功（没有找到分隔符），返回整段
+    if sentences.is_empty() && !text.trim().is_empty() {
+        sentences.push(text.trim().to_string());
+    }
+
+    sentences
+}
+
+/// track_quotes 为 true 时引号内不断句；段落结束时仍有未闭合的引号则返回 None
+fn split_sentences_with(
+    chars: &[char],
+    rules: &SentenceRules,
+    track_quotes: bool,
+) -> Option<Vec<String>> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        current.push(c);
+
+        let mut closes_quote = false;
+        if track_quotes {
+            if QUOTE_PAIRS.iter().any(|(open, _)| *open == c) {
+                depth += 1;
+            } else if depth > 0 && QUOTE_PAIRS.iter().any(|(_, close)| *close == c) {
+                depth -= 1;
+                // 。」 这样以句末标点收尾的引语在闭合处断句
+                closes_quote = depth == 0 && i > 0 && is_terminator_char(chars[i - 1], rules);
+            }
+        }
+
+        // 检查是否是句子结束符
+        let is_sentence_end = depth == 0 && (closes_quote || is_terminator_at(chars, i, rules));
+
+        if is_sentence_end {
+            // 连续的句末标点和省略号作为整体，如 ？！、……。、...
+            let run_start = i;
+            while chars
+                .get(i + 1)
+                .is_some_and(|&next| is_terminator_char(next, rules) || next == '…')
+            {
+                i += 1;
+                current.push(chars[i]);
+            }
+            let is_ellipsis = chars[run_start..=i]
+                .iter()
+                .filter(|&&ch| ch == '.' || ch == '…')
+                .count()
+                >= 2;
+
+            // 处理引号闭合情况：如 ... said." 或 。」，把收尾引号和括号也加进来
+            while chars.get(i + 1).is_some_and(|next| CLOSERS.contains(next)) {
+                i += 1;
+                current.push(chars[i]);
+            }
+
+            let next_visible = chars[i + 1..].iter().find(|ch| !ch.is_whitespace());
+            let continues = chars
+                .get(i + 1)
+                .is_some_and(|next| QUOTATIVE_PARTICLES.contains(next))
+                // 句中的省略号：Wait... what happened?
+                || (is_ellipsis && next_visible.is_some_and(|ch| ch.is_lowercase()));
+
+            if !continues {
+                let trimmed = current.trim().to_string();
+                if !trimmed.is_empty() {
+                    sentences.push(trimmed);
+                }
+                current = String::new();
+            }
+        }
+
+        i += 1;
+    }
+
+    if depth > 0 {
+        return None;
+    }
+
+    // 处理剩余内容（没有句号结尾的情况）
+    let trimmed = current.trim().to_string();
+    if !trimmed.is_empty() {
+        sentences.push(trimmed);
+    }
+
+    Some(sentences)
+}
+
+fn is_terminator_char(c: char, rules: &SentenceRules) -> bool {
+    c == '.' || rules.terminators.contains(&c)
+}
+
+/// 位置 pos 的字符是否结束句子（半角句点需排除缩写和中日文中的数字、网址）
+fn is_terminator_at(chars: &[char], pos: usize, rules: &SentenceRules) -> bool {
+    if chars[pos] == '.' {
+        !is_abbreviation(chars, pos)
+            && (!rules.period_needs_space
+                || chars.get(pos + 1).is_none_or(|next| next.is_whitespace()))
+    } else {
+        rules.terminators.contains(&chars[pos])
+    }
+}
+
+/// 检查句点是否是缩写的一部分（如 Mr. Mrs. Dr. U.S. 等）
+/// 简单的启发式规则
+fn is_abbreviation(chars: &[char], pos: usize) -> bool {
+    // 如果句点后面紧跟字母，可能是缩写 (如 U.S.A)
+    if pos + 1 < chars.len() && chars[pos + 1].is_alphabetic() {
+        return true;
+    }
+
+    // 检查句点前是否是常见缩写
+    // 向前查找单词
+    let mut word = String::new();
+    let mut j = pos as i32 - 1;
+    while j >= 0 && chars[j as usize].is_alphabetic() {
+        word.insert(0, chars[j as usize]);
+        j -= 1;
+    }
+
+    let word_lower = word.to_lowercase();
+    let abbreviations = [
+        "mr", "mrs", "ms", "dr", "jr", "sr", "vs", "etc", "inc", "ltd", "no", "st", "ave", "rd",
+    ];
+
+    if abbreviations.contains(&word_lower.as_str()) {
+        return true;
+    }
+
+    // 单字母后跟句点通常是缩写（如 A. B. C.）
+    if word.len() == 1 && word.chars().next().unwrap().is_uppercase() {
+        return true;
+    }
+
+    false
+}
@@ -0,0 +1,100 @@
+// 插件分段：MeCab、jieba、韩语形态分析等社区分词器以插件形式提供，无需编译进应用
+// 在设置中按语言指定插件后，按句分段的文章交给插件切分：原文通过 stdin 传入，
+// 语言代码作为最后一个参数，stdout 返回段落 JSON。插件失败时退回内置规则
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde_json::Value;
+use tauri::AppHandle;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::plugin_manager::get_plugin_execution_command;
+use crate::segmentation::SegmentPiece;
+
+/// 插件分段的超时时间
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 解析插件输出：段落数组（字符串，或 {"text", "is_new_paragraph"} 对象），
+/// 也接受 {"segments": [...]}。第一段总是另起一行，空段落跳过
+pub fn parse_plugin_segments(output: &str) -> Result<Vec<SegmentPiece>, String> {
+    let value: Value = serde_json::from_str(output.trim())
+        .map_err(|e| format!("Invalid plugin segmentation output: {}", e))?;
+    let items = value
+        .as_array()
+        .or_else(|| value["segments"].as_array())
+        .ok_or("Plugin segmentation output is not an array")?;
+
+    let mut pieces: Vec<SegmentPiece> = Vec::new();
+    for item in items {
+        let (text, is_new_paragraph) = match item {
+            Value::String(text) => (text.as_str(), false),
+            _ => (
+                item["text"].as_str().unwrap_or_default(),
+                item["is_new_paragraph"]
+                    .as_bool()
+                    .or_else(|| item["new_paragraph"].as_bool())
+                    .unwrap_or(false),
+            ),
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        pieces.push(SegmentPiece {
+            text: text.to_string(),
+            is_new_paragraph: is_new_paragraph || pieces.is_empty(),
+            image_url: None,
+        });
+    }
+
+    if pieces.is_empty() {
+        return Err("Plugin returned no segments".to_string());
+    }
+    Ok(pieces)
+}
+
+/// 调用插件切分文本
+pub async fn split_with_plugin(
+    app: &AppHandle,
+    plugin_name: &str,
+    language: &str,
+    content: &str,
+) -> Result<Vec<SegmentPiece>, String> {
+    let (cmd, args, plugin_dir) = get_plugin_execution_command(app, plugin_name)?;
+    println!(
+        "[Plugin] Segmenting with {}: {} {:?}",
+        plugin_name, cmd, args
+    );
+
+    let mut child = Command::new(&cmd)
+        .args(&args)
+        .arg(language)
+        .current_dir(&plugin_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to execute plugin command '{}': {}", cmd, e))?;
+
+    let mut stdin = child.stdin.take().ok_or("Failed to open plugin stdin")?;
+    stdin
+        .write_all(content.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write to plugin: {}", e))?;
+    // 关闭 stdin，插件读到 EOF 后开始输出
+    drop(stdin);
+
+    let output = tokio::time::timeout(PLUGIN_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("Plugin '{}' timed out", plugin_name))?
+        .map_err(|e| format!("Failed to read plugin output: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Plugin segmentation failed: {}", stderr));
+    }
+    parse_plugin_segments(&String::from_utf8_lossy(&output.stdout))
+}
@@ -1,11 +1,50 @@
 use crate::types::AppConfig;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde_json;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 const CONFIG_FILE: &str = "config.json";
-const ARTICLES_DIR: &str = "articles";
+const DATABASE_FILE: &str = "openkoto.db";
+const LEGACY_BACKUP_DIR: &str = "backups/json-storage";
+
+// SQLite 集合名称（每个集合对应旧版的一个 JSON 目录）
+const ARTICLES_COLLECTION: &str = "articles";
+const FAVORITES_VOCAB_COLLECTION: &str = "favorite_vocabulary";
+const FAVORITES_GRAMMAR_COLLECTION: &str = "favorite_grammar";
+const FAVORITES_PACKS_COLLECTION: &str = "word_packs";
+const BOOKMARKS_COLLECTION: &str = "bookmarks";
+const FAILED_CAPTURES_COLLECTION: &str = "failed_captures";
+const ARTICLE_RECAPS_COLLECTION: &str = "article_recaps";
+const REVIEW_LOG_COLLECTION: &str = "review_log";
+const TRANSLATION_MEMORY_COLLECTION: &str = "translation_memory";
+const GLOSSARY_COLLECTION: &str = "glossary";
+const TRANSLATION_PROGRESS_COLLECTION: &str = "translation_progress";
+const TRANSLATION_QA_COLLECTION: &str = "translation_qa";
+const KNOWN_WORDS_COLLECTION: &str = "known_words";
+const ARTICLE_REVISIONS_COLLECTION: &str = "article_revisions";
+const VOCABULARY_ENCOUNTERS_COLLECTION: &str = "vocabulary_encounters";
+const TAG_RULES_COLLECTION: &str = "tag_rules";
+const EXPORT_TEMPLATES_COLLECTION: &str = "export_templates";
+const QUARANTINE_COLLECTION: &str = "quarantined_results";
+const CHANNEL_SUBSCRIPTIONS_COLLECTION: &str = "channel_subscriptions";
+
+/// 旧版按文件存储的目录 -> SQLite 集合
+const LEGACY_JSON_DIRS: [(&str, &str); 5] = [
+    ("articles", ARTICLES_COLLECTION),
+    ("favorites/vocabulary", FAVORITES_VOCAB_COLLECTION),
+    ("favorites/grammar", FAVORITES_GRAMMAR_COLLECTION),
+    ("favorites/packs", FAVORITES_PACKS_COLLECTION),
+    ("bookmarks", BOOKMARKS_COLLECTION),
+];
+
+const JSON_MIGRATION_KEY: &str = "json_migration_completed_at";
+
+/// 进程内共享的数据库连接（首次访问时打开并完成迁移）
+static DATABASE: Mutex<Option<Connection>> = Mutex::new(None);
 
 pub fn get_app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
     app_handle
@@ -16,15 +55,17 @@ pub fn get_app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
 
 pub fn ensure_app_dirs(app_handle: &AppHandle) -> Result<(), String> {
     let data_dir = get_app_data_dir(app_handle)?;
-    let articles_dir = data_dir.join(ARTICLES_DIR);
 
-    fs::create_dir_all(&articles_dir)
-        .map_err(|e| format!("Failed to create articles directory: {}", e))?;
+    fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
 
-    Ok(())
+    // 打开数据库会自动建表并迁移旧版 JSON 文件
+    with_connection(app_handle, |_| Ok(()))
 }
 
 pub fn save_config(app_handle: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    crate::i18n::set_language(&config.interface_language);
+
     let data_dir = get_app_data_dir(app_handle)?;
     let config_path = data_dir.join(CONFIG_FILE);
 
@@ -54,84 +95,315 @@ pub fn load_config(app_handle: &AppHandle) -> Result<Option<AppConfig>, String>
             return Err(format!("FATAL_CONFIG_CORRUPTION: {}", e));
         }
     };
+    crate::i18n::set_language(&config.interface_language);
 
     Ok(Some(config))
 }
 
-pub fn save_article(app_handle: &AppHandle, article_id: &str, content: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let article_path = data_dir.join(ARTICLES_DIR).join(article_id);
+// ============================================================================
+// SQLite Document Store - 所有 JSON 文档统一存放在 openkoto.db 中
+// ============================================================================
 
-    fs::write(article_path, content).map_err(|e| format!("Failed to save article: {}", e))?;
+/// 在共享连接上执行操作，必要时先打开数据库
+fn with_connection<T>(
+    app_handle: &AppHandle,
+    op: impl FnOnce(&Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = DATABASE
+        .lock()
+        .map_err(|_| "Database lock poisoned".to_string())?;
+
+    if let Some(conn) = guard.as_ref() {
+        return op(conn);
+    }
 
-    Ok(())
+    let data_dir = get_app_data_dir(app_handle)?;
+    let conn = open_database(&data_dir)?;
+    let result = op(&conn);
+    *guard = Some(conn);
+    result
 }
 
-pub fn load_article(app_handle: &AppHandle, article_id: &str) -> Result<String, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let article_path = data_dir.join(ARTICLES_DIR).join(article_id);
+/// 打开数据库、建表，并在首次运行时导入旧版 JSON 文件
+fn open_database(data_dir: &Path) -> Result<Connection, String> {
+    fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    let conn = Connection::open(data_dir.join(DATABASE_FILE))
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    conn.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         CREATE TABLE IF NOT EXISTS documents (
+             collection TEXT NOT NULL,
+             id TEXT NOT NULL,
+             content TEXT NOT NULL,
+             updated_at TEXT NOT NULL,
+             PRIMARY KEY (collection, id)
+         );
+         CREATE TABLE IF NOT EXISTS storage_meta (
+             key TEXT PRIMARY KEY,
+             value TEXT NOT NULL
+         );",
+    )
+    .map_err(|e| format!("Failed to initialize database schema: {}", e))?;
+
+    migrate_legacy_json_files(&conn, data_dir)?;
+
+    Ok(conn)
+}
 
-    if !article_path.exists() {
-        return Err("Article not found".to_string());
+/// 将旧版每条记录一个 JSON 文件的数据导入 SQLite
+/// 导入完成后原目录移动到 backups/json-storage 下保留备份
+fn migrate_legacy_json_files(conn: &Connection, data_dir: &Path) -> Result<(), String> {
+    let migrated: Option<String> = conn
+        .query_row(
+            "SELECT value FROM storage_meta WHERE key = ?1",
+            params![JSON_MIGRATION_KEY],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read storage metadata: {}", e))?;
+
+    if migrated.is_some() {
+        return Ok(());
     }
 
-    fs::read_to_string(article_path).map_err(|e| format!("Failed to read article: {}", e))
-}
+    let now = chrono::Utc::now().to_rfc3339();
+    let backup_root = data_dir.join(LEGACY_BACKUP_DIR);
 
-pub fn list_articles(app_handle: &AppHandle) -> Result<Vec<String>, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let articles_dir = data_dir.join(ARTICLES_DIR);
+    for (legacy_dir, collection) in LEGACY_JSON_DIRS {
+        let dir = data_dir.join(legacy_dir);
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let entries = fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read legacy directory {}: {}", legacy_dir, e))?;
 
-    if !articles_dir.exists() {
-        return Ok(Vec::new());
+        let mut imported = 0usize;
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let id = match entry.file_name().into_string() {
+                Ok(id) => id,
+                Err(_) => continue,
+            };
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!(
+                        "[Storage] Skipping unreadable legacy file {:?}: {}",
+                        path, e
+                    );
+                    continue;
+                }
+            };
+
+            conn.execute(
+                "INSERT OR IGNORE INTO documents (collection, id, content, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![collection, id, content, now],
+            )
+            .map_err(|e| format!("Failed to migrate {}: {}", legacy_dir, e))?;
+            imported += 1;
+        }
+
+        println!(
+            "[Storage] Migrated {} legacy JSON files from {}",
+            imported, legacy_dir
+        );
+
+        let backup_dir = backup_root.join(legacy_dir);
+        if let Some(parent) = backup_dir.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Err(e) = fs::rename(&dir, &backup_dir) {
+            eprintln!(
+                "[Storage] Failed to move legacy directory {} to backup: {}",
+                legacy_dir, e
+            );
+        }
     }
 
-    let entries = fs::read_dir(articles_dir)
-        .map_err(|e| format!("Failed to read articles directory: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO storage_meta (key, value) VALUES (?1, ?2)",
+        params![JSON_MIGRATION_KEY, now],
+    )
+    .map_err(|e| format!("Failed to record migration: {}", e))?;
 
-    let article_ids: Vec<String> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| entry.file_name().into_string().ok())
-        .collect();
+    Ok(())
+}
 
-    Ok(article_ids)
+/// 保存（插入或覆盖）一条文档
+pub fn save_document(
+    app_handle: &AppHandle,
+    collection: &str,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "INSERT INTO documents (collection, id, content, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (collection, id)
+             DO UPDATE SET content = excluded.content, updated_at = excluded.updated_at",
+            params![collection, id, content, chrono::Utc::now().to_rfc3339()],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save {}: {}", collection, e))
+    })
 }
 
-pub fn delete_article(app_handle: &AppHandle, article_id: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let article_path = data_dir.join(ARTICLES_DIR).join(article_id);
+/// 读取一条文档，不存在时返回 None
+pub fn load_document(
+    app_handle: &AppHandle,
+    collection: &str,
+    id: &str,
+) -> Result<Option<String>, String> {
+    with_connection(app_handle, |conn| {
+        conn.query_row(
+            "SELECT content FROM documents WHERE collection = ?1 AND id = ?2",
+            params![collection, id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read {}: {}", collection, e))
+    })
+}
 
-    if article_path.exists() {
-        fs::remove_file(article_path).map_err(|e| format!("Failed to delete article: {}", e))?;
-    }
+/// 列出集合中的所有文档 ID
+pub fn list_document_ids(app_handle: &AppHandle, collection: &str) -> Result<Vec<String>, String> {
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id FROM documents WHERE collection = ?1")
+            .map_err(|e| format!("Failed to list {}: {}", collection, e))?;
+        let ids = stmt
+            .query_map(params![collection], |row| row.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+            .map_err(|e| format!("Failed to list {}: {}", collection, e))?;
+        Ok(ids)
+    })
+}
 
-    Ok(())
+/// 一次查询读取集合中的全部文档内容（列表命令使用，避免逐条读取）
+pub fn load_all_documents(app_handle: &AppHandle, collection: &str) -> Result<Vec<String>, String> {
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn
+            .prepare("SELECT content FROM documents WHERE collection = ?1")
+            .map_err(|e| format!("Failed to load {}: {}", collection, e))?;
+        let contents = stmt
+            .query_map(params![collection], |row| row.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+            .map_err(|e| format!("Failed to load {}: {}", collection, e))?;
+        Ok(contents)
+    })
+}
+
+/// 读取集合中的全部 (ID, 文档内容)，用于按 ID 处理无法解析的文档
+pub fn load_all_documents_with_ids(
+    app_handle: &AppHandle,
+    collection: &str,
+) -> Result<Vec<(String, String)>, String> {
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, content FROM documents WHERE collection = ?1")
+            .map_err(|e| format!("Failed to load {}: {}", collection, e))?;
+        let documents = stmt
+            .query_map(params![collection], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<(String, String)>, _>>())
+            .map_err(|e| format!("Failed to load {}: {}", collection, e))?;
+        Ok(documents)
+    })
+}
+
+/// 读取 storage_meta 中的键值
+pub fn load_meta(app_handle: &AppHandle, key: &str) -> Result<Option<String>, String> {
+    with_connection(app_handle, |conn| {
+        conn.query_row(
+            "SELECT value FROM storage_meta WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read storage metadata: {}", e))
+    })
+}
+
+/// 写入 storage_meta 中的键值
+pub fn save_meta(app_handle: &AppHandle, key: &str, value: &str) -> Result<(), String> {
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO storage_meta (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save storage metadata: {}", e))
+    })
+}
+
+/// 删除一条文档（不存在时忽略）
+pub fn delete_document(app_handle: &AppHandle, collection: &str, id: &str) -> Result<(), String> {
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "DELETE FROM documents WHERE collection = ?1 AND id = ?2",
+            params![collection, id],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to delete {}: {}", collection, e))
+    })
+}
+
+/// 删除集合中的全部文档，返回删除条数
+pub fn delete_collection(app_handle: &AppHandle, collection: &str) -> Result<usize, String> {
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "DELETE FROM documents WHERE collection = ?1",
+            params![collection],
+        )
+        .map_err(|e| format!("Failed to clear {}: {}", collection, e))
+    })
 }
 
 // ============================================================================
-// Favorites Storage - 独立于文章存储，删除文章不会影响收藏
+// Articles Storage
 // ============================================================================
 
-const FAVORITES_VOCAB_DIR: &str = "favorites/vocabulary";
-const FAVORITES_GRAMMAR_DIR: &str = "favorites/grammar";
-const FAVORITES_PACKS_DIR: &str = "favorites/packs";
+pub fn save_article(app_handle: &AppHandle, article_id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, ARTICLES_COLLECTION, article_id, content)
+}
 
-/// 确保收藏夹目录存在
-pub fn ensure_favorites_dirs(app_handle: &AppHandle) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let vocab_dir = data_dir.join(FAVORITES_VOCAB_DIR);
-    let grammar_dir = data_dir.join(FAVORITES_GRAMMAR_DIR);
-    let packs_dir = data_dir.join(FAVORITES_PACKS_DIR);
+pub fn load_article(app_handle: &AppHandle, article_id: &str) -> Result<String, String> {
+    load_document(app_handle, ARTICLES_COLLECTION, article_id)?
+        .ok_or_else(|| "Article not found".to_string())
+}
 
-    fs::create_dir_all(&vocab_dir)
-        .map_err(|e| format!("Failed to create vocabulary favorites directory: {}", e))?;
-    fs::create_dir_all(&grammar_dir)
-        .map_err(|e| format!("Failed to create grammar favorites directory: {}", e))?;
-    fs::create_dir_all(&packs_dir)
-        .map_err(|e| format!("Failed to create word packs directory: {}", e))?;
+/// 读取所有文章 JSON
+pub fn load_all_articles(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, ARTICLES_COLLECTION)
+}
 
-    Ok(())
+/// 读取所有文章的 (ID, JSON)
+pub fn load_all_article_entries(app_handle: &AppHandle) -> Result<Vec<(String, String)>, String> {
+    load_all_documents_with_ids(app_handle, ARTICLES_COLLECTION)
+}
+
+pub fn delete_article(app_handle: &AppHandle, article_id: &str) -> Result<(), String> {
+    delete_document(app_handle, ARTICLES_COLLECTION, article_id)
+}
+
+// ============================================================================
+// Favorites Storage - 独立于文章存储，删除文章不会影响收藏
+// ============================================================================
+
+/// 确保收藏夹存储可用（数据库已打开并完成迁移）
+pub fn ensure_favorites_dirs(app_handle: &AppHandle) -> Result<(), String> {
+    with_connection(app_handle, |_| Ok(()))
 }
 
 /// 保存单词收藏
@@ -140,265 +412,462 @@ pub fn save_favorite_vocabulary(
     id: &str,
     content: &str,
 ) -> Result<(), String> {
-    ensure_favorites_dirs(app_handle)?;
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_VOCAB_DIR).join(id);
-
-    fs::write(path, content).map_err(|e| format!("Failed to save vocabulary favorite: {}", e))?;
-
-    Ok(())
+    save_document(app_handle, FAVORITES_VOCAB_COLLECTION, id, content)
 }
 
 /// 加载单词收藏
 pub fn load_favorite_vocabulary(app_handle: &AppHandle, id: &str) -> Result<String, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_VOCAB_DIR).join(id);
+    load_document(app_handle, FAVORITES_VOCAB_COLLECTION, id)?
+        .ok_or_else(|| "Vocabulary favorite not found".to_string())
+}
 
-    if !path.exists() {
-        return Err("Vocabulary favorite not found".to_string());
-    }
+/// 读取所有单词收藏 JSON
+pub fn load_all_favorite_vocabularies(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, FAVORITES_VOCAB_COLLECTION)
+}
 
-    fs::read_to_string(path).map_err(|e| format!("Failed to read vocabulary favorite: {}", e))
+/// 删除单词收藏
+pub fn delete_favorite_vocabulary(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, FAVORITES_VOCAB_COLLECTION, id)
 }
 
-/// 列出所有单词收藏ID
-pub fn list_favorite_vocabularies(app_handle: &AppHandle) -> Result<Vec<String>, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let dir = data_dir.join(FAVORITES_VOCAB_DIR);
+/// 保存语法收藏
+pub fn save_favorite_grammar(
+    app_handle: &AppHandle,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, FAVORITES_GRAMMAR_COLLECTION, id, content)
+}
 
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
+/// 读取所有语法收藏 JSON
+pub fn load_all_favorite_grammars(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, FAVORITES_GRAMMAR_COLLECTION)
+}
 
-    let entries = fs::read_dir(dir)
-        .map_err(|e| format!("Failed to read vocabulary favorites directory: {}", e))?;
+/// 删除语法收藏
+pub fn delete_favorite_grammar(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, FAVORITES_GRAMMAR_COLLECTION, id)
+}
 
-    let ids: Vec<String> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| entry.file_name().into_string().ok())
-        .collect();
+/// 保存单词包
+pub fn save_word_pack(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, FAVORITES_PACKS_COLLECTION, id, content)
+}
 
-    Ok(ids)
+/// 加载单词包
+pub fn load_word_pack(app_handle: &AppHandle, id: &str) -> Result<String, String> {
+    load_document(app_handle, FAVORITES_PACKS_COLLECTION, id)?
+        .ok_or_else(|| "Word pack not found".to_string())
 }
 
-/// 删除单词收藏
-pub fn delete_favorite_vocabulary(app_handle: &AppHandle, id: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_VOCAB_DIR).join(id);
+/// 列出所有单词包ID
+pub fn list_word_packs(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    list_document_ids(app_handle, FAVORITES_PACKS_COLLECTION)
+}
 
-    if path.exists() {
-        fs::remove_file(path)
-            .map_err(|e| format!("Failed to delete vocabulary favorite: {}", e))?;
-    }
+/// 读取所有单词包 JSON
+pub fn load_all_word_packs_json(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, FAVORITES_PACKS_COLLECTION)
+}
 
-    Ok(())
+/// 删除单词包
+pub fn delete_word_pack(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, FAVORITES_PACKS_COLLECTION, id)
 }
 
-/// 保存语法收藏
-pub fn save_favorite_grammar(
+// ============================================================================
+// Bookmarks Storage - 书签存储
+// ============================================================================
+
+/// 保存书签
+pub fn save_bookmark(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, BOOKMARKS_COLLECTION, id, content)
+}
+
+/// 加载书签
+pub fn load_bookmark(app_handle: &AppHandle, id: &str) -> Result<String, String> {
+    load_document(app_handle, BOOKMARKS_COLLECTION, id)?
+        .ok_or_else(|| "Bookmark not found".to_string())
+}
+
+/// 读取所有书签 JSON
+pub fn load_all_bookmarks(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, BOOKMARKS_COLLECTION)
+}
+
+/// 删除书签
+pub fn delete_bookmark(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, BOOKMARKS_COLLECTION, id)
+}
+
+/// 列出指定书籍的所有书签
+pub fn list_bookmarks_for_book(
+    app_handle: &AppHandle,
+    book_path: &str,
+) -> Result<Vec<String>, String> {
+    // 简单检查 JSON 中是否包含 book_path
+    // 更准确的方法是反序列化，但这里为了性能直接在 SQLite 中做字符串匹配
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id FROM documents WHERE collection = ?1 AND instr(content, ?2) > 0")
+            .map_err(|e| format!("Failed to list bookmarks: {}", e))?;
+        let ids = stmt
+            .query_map(params![BOOKMARKS_COLLECTION, book_path], |row| row.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+            .map_err(|e| format!("Failed to list bookmarks: {}", e))?;
+        Ok(ids)
+    })
+}
+
+// ============================================================================
+// Failed Captures Storage - 网页抓取失败重试队列
+// ============================================================================
+
+/// 保存抓取失败记录
+pub fn save_failed_capture(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, FAILED_CAPTURES_COLLECTION, id, content)
+}
+
+/// 读取所有抓取失败记录 JSON
+pub fn load_all_failed_captures(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, FAILED_CAPTURES_COLLECTION)
+}
+
+/// 删除抓取失败记录
+pub fn delete_failed_capture(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, FAILED_CAPTURES_COLLECTION, id)
+}
+
+// ============================================================================
+// Quarantine Storage - 未通过校验的 AI 结果
+// ============================================================================
+
+/// 保存隔离的结果
+pub fn save_quarantined_result(
     app_handle: &AppHandle,
     id: &str,
     content: &str,
 ) -> Result<(), String> {
-    ensure_favorites_dirs(app_handle)?;
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_GRAMMAR_DIR).join(id);
+    save_document(app_handle, QUARANTINE_COLLECTION, id, content)
+}
 
-    fs::write(path, content).map_err(|e| format!("Failed to save grammar favorite: {}", e))?;
+/// 读取隔离的结果，不存在时返回 None
+pub fn load_quarantined_result(app_handle: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    load_document(app_handle, QUARANTINE_COLLECTION, id)
+}
 
-    Ok(())
+/// 读取全部隔离的结果 JSON
+pub fn load_all_quarantined_results(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, QUARANTINE_COLLECTION)
 }
 
-/// 加载语法收藏
-pub fn load_favorite_grammar(app_handle: &AppHandle, id: &str) -> Result<String, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_GRAMMAR_DIR).join(id);
+/// 删除隔离的结果
+pub fn delete_quarantined_result(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, QUARANTINE_COLLECTION, id)
+}
 
-    if !path.exists() {
-        return Err("Grammar favorite not found".to_string());
-    }
+// ============================================================================
+// Article Recap Storage - 文章学习总结卡片（以文章 ID 为键）
+// ============================================================================
 
-    fs::read_to_string(path).map_err(|e| format!("Failed to read grammar favorite: {}", e))
+/// 保存文章总结卡片
+pub fn save_article_recap(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, ARTICLE_RECAPS_COLLECTION, article_id, content)
 }
 
-/// 列出所有语法收藏ID
-pub fn list_favorite_grammars(app_handle: &AppHandle) -> Result<Vec<String>, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let dir = data_dir.join(FAVORITES_GRAMMAR_DIR);
+/// 读取文章总结卡片，未生成时返回 None
+pub fn load_article_recap(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Option<String>, String> {
+    load_document(app_handle, ARTICLE_RECAPS_COLLECTION, article_id)
+}
 
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
+/// 读取所有总结卡片 JSON
+pub fn load_all_article_recaps(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, ARTICLE_RECAPS_COLLECTION)
+}
 
-    let entries = fs::read_dir(dir)
-        .map_err(|e| format!("Failed to read grammar favorites directory: {}", e))?;
+/// 删除文章总结卡片
+pub fn delete_article_recap(app_handle: &AppHandle, article_id: &str) -> Result<(), String> {
+    delete_document(app_handle, ARTICLE_RECAPS_COLLECTION, article_id)
+}
 
-    let ids: Vec<String> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| entry.file_name().into_string().ok())
-        .collect();
+// ============================================================================
+// Review Log Storage - 单词复习记录
+// ============================================================================
 
-    Ok(ids)
+/// 保存一条复习记录
+pub fn save_review_log_entry(
+    app_handle: &AppHandle,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, REVIEW_LOG_COLLECTION, id, content)
 }
 
-/// 删除语法收藏
-pub fn delete_favorite_grammar(app_handle: &AppHandle, id: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_GRAMMAR_DIR).join(id);
+/// 读取所有复习记录 JSON
+pub fn load_all_review_log_entries(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, REVIEW_LOG_COLLECTION)
+}
 
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("Failed to delete grammar favorite: {}", e))?;
-    }
+/// 删除复习记录
+pub fn delete_review_log_entry(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, REVIEW_LOG_COLLECTION, id)
+}
 
-    Ok(())
+// ============================================================================
+// Translation Memory Storage - 翻译记忆（以原文/目标语言/模型的哈希为键）
+// ============================================================================
+
+/// 读取翻译记忆，不存在时返回 None
+pub fn load_translation_memory(
+    app_handle: &AppHandle,
+    key: &str,
+) -> Result<Option<String>, String> {
+    load_document(app_handle, TRANSLATION_MEMORY_COLLECTION, key)
 }
 
-/// 保存单词包
-pub fn save_word_pack(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
-    ensure_favorites_dirs(app_handle)?;
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_PACKS_DIR).join(id);
-    fs::write(path, content).map_err(|e| format!("Failed to save word pack: {}", e))?;
-    Ok(())
+/// 保存翻译记忆
+pub fn save_translation_memory(
+    app_handle: &AppHandle,
+    key: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, TRANSLATION_MEMORY_COLLECTION, key, content)
 }
 
-/// 加载单词包
-pub fn load_word_pack(app_handle: &AppHandle, id: &str) -> Result<String, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_PACKS_DIR).join(id);
+/// 清空翻译记忆，返回删除条数
+pub fn clear_translation_memory(app_handle: &AppHandle) -> Result<usize, String> {
+    delete_collection(app_handle, TRANSLATION_MEMORY_COLLECTION)
+}
 
-    if !path.exists() {
-        return Err("Word pack not found".to_string());
-    }
+// ============================================================================
+// Glossary Storage - 术语表
+// ============================================================================
 
-    fs::read_to_string(path).map_err(|e| format!("Failed to read word pack: {}", e))
+/// 保存术语表条目
+pub fn save_glossary_entry(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, GLOSSARY_COLLECTION, id, content)
 }
 
-/// 列出所有单词包ID
-pub fn list_word_packs(app_handle: &AppHandle) -> Result<Vec<String>, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let dir = data_dir.join(FAVORITES_PACKS_DIR);
+/// 读取术语表条目，不存在时返回 None
+pub fn load_glossary_entry(app_handle: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    load_document(app_handle, GLOSSARY_COLLECTION, id)
+}
 
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
+/// 读取全部术语表条目 JSON
+pub fn load_all_glossary_entries(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, GLOSSARY_COLLECTION)
+}
 
-    let entries =
-        fs::read_dir(dir).map_err(|e| format!("Failed to read word packs directory: {}", e))?;
+/// 删除术语表条目
+pub fn delete_glossary_entry(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, GLOSSARY_COLLECTION, id)
+}
 
-    let ids: Vec<String> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| entry.file_name().into_string().ok())
-        .collect();
+// ============================================================================
+// Translation Progress Storage - 整篇翻译的分批进度（以文章 ID 为键）
+// ============================================================================
 
-    Ok(ids)
+/// 保存文章的翻译进度
+pub fn save_translation_progress(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(
+        app_handle,
+        TRANSLATION_PROGRESS_COLLECTION,
+        article_id,
+        content,
+    )
 }
 
-/// 删除单词包
-pub fn delete_word_pack(app_handle: &AppHandle, id: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(FAVORITES_PACKS_DIR).join(id);
+/// 读取文章的翻译进度，没有未完成的翻译时返回 None
+pub fn load_translation_progress(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Option<String>, String> {
+    load_document(app_handle, TRANSLATION_PROGRESS_COLLECTION, article_id)
+}
 
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("Failed to delete word pack: {}", e))?;
-    }
+/// 删除文章的翻译进度
+pub fn delete_translation_progress(app_handle: &AppHandle, article_id: &str) -> Result<(), String> {
+    delete_document(app_handle, TRANSLATION_PROGRESS_COLLECTION, article_id)
+}
 
-    Ok(())
+// ============================================================================
+// Translation QA Storage - 整篇翻译后的译文抽查结果（以文章 ID 为键）
+// ============================================================================
+
+/// 保存文章最近一次的译文抽查结果
+pub fn save_translation_qa(
+    app_handle: &AppHandle,
+    article_id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, TRANSLATION_QA_COLLECTION, article_id, content)
+}
+
+/// 读取文章的译文抽查结果，没有时返回 None
+pub fn load_translation_qa(
+    app_handle: &AppHandle,
+    article_id: &str,
+) -> Result<Option<String>, String> {
+    load_document(app_handle, TRANSLATION_QA_COLLECTION, article_id)
+}
+
+/// 删除文章的译文抽查结果
+pub fn delete_translation_qa(app_handle: &AppHandle, article_id: &str) -> Result<(), String> {
+    delete_document(app_handle, TRANSLATION_QA_COLLECTION, article_id)
 }
 
 // ============================================================================
-// Bookmarks Storage - 书签存储
+// Known Words Storage - 已知词库（以小写单词为键）
 // ============================================================================
 
-const BOOKMARKS_DIR: &str = "bookmarks";
+/// 保存已知词
+pub fn save_known_word(app_handle: &AppHandle, word: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, KNOWN_WORDS_COLLECTION, word, content)
+}
 
-/// 确保书签目录存在
-pub fn ensure_bookmarks_dir(app_handle: &AppHandle) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let bookmarks_dir = data_dir.join(BOOKMARKS_DIR);
+/// 读取全部已知词 JSON
+pub fn load_all_known_words(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, KNOWN_WORDS_COLLECTION)
+}
 
-    fs::create_dir_all(&bookmarks_dir)
-        .map_err(|e| format!("Failed to create bookmarks directory: {}", e))?;
+/// 删除已知词
+pub fn delete_known_word(app_handle: &AppHandle, word: &str) -> Result<(), String> {
+    delete_document(app_handle, KNOWN_WORDS_COLLECTION, word)
+}
 
-    Ok(())
+// ============================================================================
+// Vocabulary Encounter Storage - 生词在文章中的作答记录
+// ============================================================================
+
+/// 保存一条作答记录
+pub fn save_vocabulary_encounter(
+    app_handle: &AppHandle,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, VOCABULARY_ENCOUNTERS_COLLECTION, id, content)
 }
 
-/// 保存书签
-pub fn save_bookmark(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
-    ensure_bookmarks_dir(app_handle)?;
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(BOOKMARKS_DIR).join(id);
+/// 读取全部作答记录 JSON
+pub fn load_all_vocabulary_encounters(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, VOCABULARY_ENCOUNTERS_COLLECTION)
+}
 
-    fs::write(path, content).map_err(|e| format!("Failed to save bookmark: {}", e))?;
+// ============================================================================
+// Tag Rule Storage - 自动标签规则
+// ============================================================================
 
-    Ok(())
+/// 保存标签规则
+pub fn save_tag_rule(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, TAG_RULES_COLLECTION, id, content)
 }
 
-/// 加载书签
-pub fn load_bookmark(app_handle: &AppHandle, id: &str) -> Result<String, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(BOOKMARKS_DIR).join(id);
+/// 读取标签规则，不存在时返回 None
+pub fn load_tag_rule(app_handle: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    load_document(app_handle, TAG_RULES_COLLECTION, id)
+}
 
-    if !path.exists() {
-        return Err("Bookmark not found".to_string());
-    }
+/// 读取全部标签规则 JSON
+pub fn load_all_tag_rules(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, TAG_RULES_COLLECTION)
+}
 
-    fs::read_to_string(path).map_err(|e| format!("Failed to read bookmark: {}", e))
+/// 删除标签规则
+pub fn delete_tag_rule(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, TAG_RULES_COLLECTION, id)
 }
 
-/// 列出所有书签ID
-pub fn list_bookmarks(app_handle: &AppHandle) -> Result<Vec<String>, String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let dir = data_dir.join(BOOKMARKS_DIR);
+// ============================================================================
+// Export Template Storage - 导出模板
+// ============================================================================
 
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
+/// 保存导出模板
+pub fn save_export_template(app_handle: &AppHandle, id: &str, content: &str) -> Result<(), String> {
+    save_document(app_handle, EXPORT_TEMPLATES_COLLECTION, id, content)
+}
 
-    let entries =
-        fs::read_dir(dir).map_err(|e| format!("Failed to read bookmarks directory: {}", e))?;
+/// 读取导出模板，不存在时返回 None
+pub fn load_export_template(app_handle: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    load_document(app_handle, EXPORT_TEMPLATES_COLLECTION, id)
+}
 
-    let ids: Vec<String> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| entry.file_name().into_string().ok())
-        .collect();
+/// 读取全部导出模板 JSON
+pub fn load_all_export_templates(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, EXPORT_TEMPLATES_COLLECTION)
+}
 
-    Ok(ids)
+/// 删除导出模板
+pub fn delete_export_template(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, EXPORT_TEMPLATES_COLLECTION, id)
 }
 
-/// 删除书签
-pub fn delete_bookmark(app_handle: &AppHandle, id: &str) -> Result<(), String> {
-    let data_dir = get_app_data_dir(app_handle)?;
-    let path = data_dir.join(BOOKMARKS_DIR).join(id);
+// ============================================================================
+// Article Revision Storage - 文章修订历史（内容和分段快照）
+// ============================================================================
 
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("Failed to delete bookmark: {}", e))?;
-    }
+/// 保存文章修订
+pub fn save_article_revision(
+    app_handle: &AppHandle,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, ARTICLE_REVISIONS_COLLECTION, id, content)
+}
 
-    Ok(())
+/// 读取文章修订，不存在时返回 None
+pub fn load_article_revision(app_handle: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    load_document(app_handle, ARTICLE_REVISIONS_COLLECTION, id)
 }
 
-/// 列出指定书籍的所有书签
-pub fn list_bookmarks_for_book(
+/// 读取全部文章修订 JSON
+pub fn load_all_article_revisions(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, ARTICLE_REVISIONS_COLLECTION)
+}
+
+/// 删除文章修订
+pub fn delete_article_revision(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, ARTICLE_REVISIONS_COLLECTION, id)
+}
+
+// ============================================================================
+// Channel Subscription Storage - 订阅的 YouTube 频道
+// ============================================================================
+
+/// 保存频道订阅
+pub fn save_channel_subscription(
     app_handle: &AppHandle,
-    book_path: &str,
-) -> Result<Vec<String>, String> {
-    let all_ids = list_bookmarks(app_handle)?;
-    let mut matching_ids = Vec::new();
-
-    for id in all_ids {
-        if let Ok(json) = load_bookmark(app_handle, &id) {
-            // 简单检查 JSON 中是否包含 book_path
-            // 更准确的方法是反序列化，但这里为了性能使用字符串匹配
-            if json.contains(book_path) {
-                matching_ids.push(id);
-            }
-        }
-    }
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    save_document(app_handle, CHANNEL_SUBSCRIPTIONS_COLLECTION, id, content)
+}
+
+/// 读取频道订阅，不存在时返回 None
+pub fn load_channel_subscription(
+    app_handle: &AppHandle,
+    id: &str,
+) -> Result<Option<String>, String> {
+    load_document(app_handle, CHANNEL_SUBSCRIPTIONS_COLLECTION, id)
+}
+
+/// 读取全部频道订阅 JSON
+pub fn load_all_channel_subscriptions(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    load_all_documents(app_handle, CHANNEL_SUBSCRIPTIONS_COLLECTION)
+}
 
-    Ok(matching_ids)
+/// 删除频道订阅
+pub fn delete_channel_subscription(app_handle: &AppHandle, id: &str) -> Result<(), String> {
+    delete_document(app_handle, CHANNEL_SUBSCRIPTIONS_COLLECTION, id)
 }
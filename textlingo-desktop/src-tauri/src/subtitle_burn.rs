@@ -0,0 +1,198 @@
+// 硬字幕导出：把段落和译文生成 ASS 字幕，再用 FFmpeg 烧录进视频副本
+// 导出的视频不依赖应用即可在手机等设备上播放；进度通过 subtitle-burn-progress://{event_id} 事件发送
+
+use crate::i18n::{tr, tr_with};
+use crate::jobs::cancelled_message;
+use crate::windows::emit_article_event;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// 默认输出路径：原视频旁的 {文件名}.subtitled.mp4
+pub fn burned_video_path(video_path: &Path) -> PathBuf {
+    let stem = video_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    video_path.with_file_name(format!("{}.subtitled.mp4", stem))
+}
+
+/// 解析 FFmpeg -progress 输出中的已编码时长（秒）：out_time_us / out_time_ms 的单位都是微秒
+pub fn parse_progress_time(line: &str) -> Option<f64> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .map(|us| us.max(0) as f64 / 1_000_000.0),
+        _ => None,
+    }
+}
+
+fn emit_progress(app: &AppHandle, event_id: &str, phase: &str, progress: f64) {
+    emit_article_event(
+        app,
+        event_id,
+        &format!("subtitle-burn-progress://{}", event_id),
+        serde_json::json!({ "phase": phase, "progress": progress.clamp(0.0, 1.0) }),
+    );
+}
+
+/// 把 ASS 字幕烧录进视频，输出为 H.264 MP4，音轨重新编码为 AAC 以兼容 MP4 容器
+///
+/// 字幕文件写到输出目录并以相对路径传给 subtitles 滤镜，避免 Windows 盘符中的冒号被滤镜语法误解析
+pub async fn burn_subtitles(
+    app: &AppHandle,
+    video_path: &Path,
+    ass_content: &str,
+    output_path: &Path,
+    event_id: &str,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let output_path_str = output_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let work_dir = output_path
+        .parent()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    std::fs::create_dir_all(work_dir).map_err(|e| format!("Failed to create dir: {}", e))?;
+
+    let ass_name = format!("burn-{}.ass", Uuid::new_v4());
+    let ass_path = work_dir.join(&ass_name);
+    std::fs::write(&ass_path, ass_content)
+        .map_err(|e| format!("Failed to write subtitle file: {}", e))?;
+
+    // 时长只用于计算进度，获取失败时仍继续烧录
+    let duration = crate::subtitle_extraction::get_video_duration(app, video_path)
+        .await
+        .ok()
+        .filter(|d| *d > 0.0);
+    emit_progress(app, event_id, "start", 0.0);
+
+    let result = run_ffmpeg(
+        app,
+        video_path_str,
+        &ass_name,
+        output_path_str,
+        work_dir,
+        duration,
+        event_id,
+        cancel,
+    )
+    .await;
+
+    let _ = std::fs::remove_file(&ass_path);
+    match &result {
+        Ok(()) => emit_progress(app, event_id, "done", 1.0),
+        Err(_) => {
+            // 失败或取消时不留下不完整的视频
+            let _ = std::fs::remove_file(output_path);
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_ffmpeg(
+    app: &AppHandle,
+    video_path: &str,
+    ass_name: &str,
+    output_path: &str,
+    work_dir: &Path,
+    duration: Option<f64>,
+    event_id: &str,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    // -progress pipe:1 每隔约半秒在 stdout 输出 key=value 形式的进度
+    let (mut rx, child) = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
+        .current_dir(work_dir)
+        .args([
+            "-i",
+            video_path,
+            "-vf",
+            &format!("subtitles={}", ass_name),
+            "-c:v",
+            "libx264",
+            "-crf",
+            "20",
+            "-preset",
+            "veryfast",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "160k",
+            "-movflags",
+            "+faststart",
+            "-progress",
+            "pipe:1",
+            "-nostats",
+            "-y",
+            output_path,
+        ])
+        .spawn()
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
+
+    let mut child = Some(child);
+    let mut stderr_tail = String::new();
+    loop {
+        let event = tokio::select! {
+            event = rx.recv() => event,
+            _ = cancel.cancelled() => {
+                if let Some(child) = child.take() {
+                    let _ = child.kill();
+                }
+                return Err(cancelled_message());
+            }
+        };
+        match event {
+            Some(CommandEvent::Stdout(bytes)) => {
+                let text = String::from_utf8_lossy(&bytes);
+                if let (Some(seconds), Some(duration)) =
+                    (text.lines().find_map(parse_progress_time), duration)
+                {
+                    emit_progress(app, event_id, "encoding", seconds / duration);
+                }
+            }
+            Some(CommandEvent::Stderr(bytes)) => {
+                stderr_tail.push_str(&String::from_utf8_lossy(&bytes));
+                stderr_tail.push('\n');
+                if stderr_tail.len() > 4000 {
+                    let cut = stderr_tail.len() - 2000;
+                    let cut = (cut..stderr_tail.len())
+                        .find(|i| stderr_tail.is_char_boundary(*i))
+                        .unwrap_or(cut);
+                    stderr_tail.drain(..cut);
+                }
+            }
+            Some(CommandEvent::Error(e)) => {
+                return Err(tr_with("error.ffmpeg_failed", &[("error", e)]))
+            }
+            Some(CommandEvent::Terminated(payload)) => {
+                return if payload.code == Some(0) {
+                    Ok(())
+                } else {
+                    Err(burn_failed(&stderr_tail))
+                };
+            }
+            Some(_) => {}
+            None => return Err(burn_failed(&stderr_tail)),
+        }
+    }
+}
+
+fn burn_failed(stderr_tail: &str) -> String {
+    tr_with(
+        "error.subtitle_burn_failed",
+        &[("error", stderr_tail.trim().to_string())],
+    )
+}
@@ -0,0 +1,106 @@
+// 分片字幕提取的断点：每个片段转录完成后把结果写入断点文件，某个片段失败时已完成的片段不丢失，
+// 再次提取同一视频时跳过已完成的片段；视频、转录引擎或分片方案变化时断点作废
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::TranscriptionSegment;
+
+/// 断点文件所在目录（位于应用数据目录下）
+pub const CHECKPOINT_DIR: &str = "subtitle_checkpoints";
+
+/// 一次分片提取的断点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkCheckpoint {
+    pub video_path: String,
+    /// 视频文件大小，文件被替换时断点作废
+    pub video_size: u64,
+    pub engine: String,
+    pub chunk_seconds: f64,
+    pub window_end: f64,
+    pub chunk_starts: Vec<f64>,
+    /// 已完成片段的下标 → 转录结果（时间已平移到原视频）
+    #[serde(default)]
+    pub chunks: BTreeMap<usize, Vec<TranscriptionSegment>>,
+}
+
+impl ChunkCheckpoint {
+    pub fn new(
+        video_path: &Path,
+        video_size: u64,
+        engine: &str,
+        chunk_seconds: f64,
+        window_end: f64,
+        chunk_starts: Vec<f64>,
+    ) -> Self {
+        Self {
+            video_path: video_path.to_string_lossy().to_string(),
+            video_size,
+            engine: engine.to_string(),
+            chunk_seconds,
+            window_end,
+            chunk_starts,
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// 是否为同一视频、同一引擎、同一分片方案的断点
+    pub fn same_plan(&self, other: &ChunkCheckpoint) -> bool {
+        self.video_path == other.video_path
+            && self.video_size == other.video_size
+            && self.engine == other.engine
+            && self.chunk_seconds == other.chunk_seconds
+            && self.window_end == other.window_end
+            && self.chunk_starts == other.chunk_starts
+    }
+
+    /// 还没有完成的片段下标
+    pub fn pending_chunks(&self) -> Vec<usize> {
+        (0..self.chunk_starts.len())
+            .filter(|index| !self.chunks.contains_key(index))
+            .collect()
+    }
+
+    /// 读取与本次分片方案一致的断点，没有或已作废时返回 fresh
+    pub fn resume_or(path: &Path, fresh: ChunkCheckpoint) -> ChunkCheckpoint {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<ChunkCheckpoint>(&json).ok())
+            .filter(|saved| saved.same_plan(&fresh))
+            .unwrap_or(fresh)
+    }
+
+    /// 先写临时文件再改名，写到一半中断时不会留下损坏的断点
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create checkpoint dir: {}", e))?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize checkpoint: {}", e))?;
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, json)
+            .and_then(|_| fs::rename(&partial, path))
+            .map_err(|e| format!("Failed to save checkpoint: {}", e))
+    }
+}
+
+/// 视频的断点文件路径
+pub fn checkpoint_path(app_data_dir: &Path, video_id: &str) -> PathBuf {
+    app_data_dir
+        .join(CHECKPOINT_DIR)
+        .join(format!("{}.json", video_id))
+}
+
+/// 删除断点（提取完成或文章删除时）
+pub fn remove_checkpoint(app_data_dir: &Path, video_id: &str) {
+    let path = checkpoint_path(app_data_dir, video_id);
+    if path.exists() {
+        if let Err(e) = fs::remove_file(&path) {
+            println!("[SubtitleExtraction] 删除断点失败: {}", e);
+        }
+    }
+}
@@ -1,34 +1,109 @@
 // 字幕提取模块
-// 使用 Gemini 多模态 API 从视频中提取字幕
+// 负责音视频处理、分片与合并，具体转录交给 transcription 模块中的引擎
 //
 // 工作流程:
-// 1. 使用 FFmpeg 从视频中提取音频 (MP3 格式)
-// 2. 将音频文件编码为 base64
-// 3. 发送至 Gemini API 进行转录
-// 4. 解析转录结果为 ArticleSegment
-
-use crate::ai_service::AIService;
-use crate::types::{
-    ArticleSegment, ChatContent, ChatMessage, ChatRequest, ContentPart, TranscriptionResult,
-    TranscriptionSegment, VideoUrl,
+// 1. 使用 FFmpeg 从视频中提取音频 (MP3 格式)，视频类引擎则压缩整段视频
+// 2. 长音频按引擎的分片长度切片，交给引擎转录
+// 3. 合并、排序、去重后解析为 ArticleSegment
+
+use crate::i18n::{tr, tr_with};
+use crate::jobs::cancelled_message;
+use crate::storage::{get_app_data_dir, load_config};
+use crate::subtitle_checkpoint::{checkpoint_path, ChunkCheckpoint};
+use crate::transcription::{
+    chunk_options, parse_speaker, ChunkOptions, TranscriptionEngine, TranscriptionInput,
 };
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use crate::types::{ArticleSegment, TranscriptionResult, TranscriptionSegment};
+use crate::windows::emit_article_event;
 use chrono::Utc;
-use reqwest::Client;
-use serde_json::{json, Value};
+use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
-use tauri::Emitter;
 use tauri_plugin_shell::ShellExt;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-// API 端点
-const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
-const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
-const API_302AI_URL: &str = "https://api.302.ai/v1/chat/completions";
-const MOONSHOT_API_URL: &str = "https://api.moonshot.cn/v1/chat/completions";
-const GOOGLE_GEMINI_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+/// 只提取视频中的一段（秒），生成的时间轴仍相对于原视频
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeRange {
+    pub start: f64,
+    /// 未指定结束时间时为无穷大，提取时按视频时长裁剪
+    pub end: f64,
+}
+
+impl TimeRange {
+    /// 校验前端传入的起止时间
+    pub fn new(start: Option<f64>, end: Option<f64>) -> Result<TimeRange, String> {
+        let start = start.unwrap_or(0.0);
+        let end = end.unwrap_or(f64::INFINITY);
+        if !start.is_finite() || start < 0.0 || end.is_nan() || end <= start {
+            return Err(tr_with(
+                "error.invalid_time_range",
+                &[("start", start.to_string()), ("end", end.to_string())],
+            ));
+        }
+        Ok(TimeRange { start, end })
+    }
+
+    /// 按视频时长裁剪结束时间，起点超出视频时长时报错
+    fn clamp_to(self, duration: f64) -> Result<TimeRange, String> {
+        if self.start >= duration {
+            return Err(tr_with(
+                "error.invalid_time_range",
+                &[
+                    ("start", self.start.to_string()),
+                    ("end", duration.to_string()),
+                ],
+            ));
+        }
+        Ok(TimeRange {
+            start: self.start,
+            end: self.end.min(duration),
+        })
+    }
+
+    fn len(&self) -> f64 {
+        self.end - self.start
+    }
+
+    fn contains(&self, time: f64) -> bool {
+        time >= self.start && time < self.end
+    }
+}
+
+/// 把区间提取的新字幕合并进原有字幕：替换区间内的旧字幕，保留区间外的，按时间重新排序编号
+pub(crate) fn merge_range_segments(
+    existing: Vec<ArticleSegment>,
+    extracted: Vec<ArticleSegment>,
+    range: TimeRange,
+) -> Vec<ArticleSegment> {
+    let mut merged: Vec<ArticleSegment> = existing
+        .into_iter()
+        .filter(|seg| seg.start_time.is_some_and(|start| !range.contains(start)))
+        .chain(extracted)
+        .collect();
+    merged.sort_by(|a, b| {
+        a.start_time
+            .unwrap_or(0.0)
+            .partial_cmp(&b.start_time.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for (i, seg) in merged.iter_mut().enumerate() {
+        seg.order = i as i32;
+    }
+    merged
+}
+
+/// 发送提取进度（event_id 为文章 ID，只发给显示该文章的窗口）
+pub(crate) fn emit_progress(app: &AppHandle, event_id: &str, payload: Value) {
+    emit_article_event(
+        app,
+        event_id,
+        &format!("subtitle-extraction-progress://{}", event_id),
+        payload,
+    );
+}
 
 /// 从视频中提取字幕的主函数
 ///
@@ -36,9 +111,10 @@ const GOOGLE_GEMINI_URL: &str = "https://generativelanguage.googleapis.com/v1bet
 /// - `app`: Tauri 应用句柄
 /// - `video_path`: 视频文件路径
 /// - `video_id`: 视频 ID (用于生成 segment ID)
-/// - `provider`: API 提供商 ("openrouter", "302ai", "google")
-/// - `api_key`: API 密钥
-/// - `model`: 模型名称
+/// - `engine`: 转录引擎 (见 transcription::create_engine)
+/// - `event_id`: 进度事件 ID
+/// - `cancel`: 取消令牌
+/// - `range`: 只提取的时间范围（None 为整段视频）
 ///
 /// # 返回
 /// - 成功: Vec<ArticleSegment> 字幕段落列表
@@ -47,20 +123,39 @@ pub async fn extract_subtitles(
     app: AppHandle,
     video_path: &Path,
     video_id: &str,
-    provider: &str,
-    api_key: &str,
-    model: &str,
-    base_url: Option<&str>,
+    engine: &dyn TranscriptionEngine,
     event_id: &str,
+    cancel: &CancellationToken,
+    range: Option<TimeRange>,
 ) -> Result<Vec<ArticleSegment>, String> {
-    println!("[SubtitleExtraction] 开始提取字幕: {:?}", video_path);
+    println!(
+        "[SubtitleExtraction] 开始提取字幕: {:?}, 引擎: {}, 范围: {:?}",
+        video_path,
+        engine.id(),
+        range
+    );
 
     // 发送开始事件
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "start", "message": "开始提取字幕..." }),
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "start", "message": tr("subtitle.start") }),
     );
 
+    if let Some(message) = engine.mode_message() {
+        emit_progress(
+            &app,
+            event_id,
+            serde_json::json!({ "phase": "processing", "message": message }),
+        );
+    }
+
+    // 视频理解类引擎：上传整段压缩视频
+    if engine.input() == TranscriptionInput::Video {
+        return extract_subtitles_from_video(app, video_path, video_id, engine, event_id, range)
+            .await;
+    }
+
     // 1. 获取视频时长
     let duration = get_video_duration(&app, video_path).await?;
     println!(
@@ -69,65 +164,155 @@ pub async fn extract_subtitles(
         duration / 60.0
     );
 
-    // 分片提取阈值：10分钟
-    const CHUNK_THRESHOLD_SECONDS: f64 = 10.0 * 60.0;
-
-    // Kimi K2.5 视频理解模式
-    if provider == "moonshot" && model.contains("k2.5") {
-        println!("[SubtitleExtraction] 检测到 Kimi K2.5 模型，启用视频理解模式");
-        let _ = app.emit(&format!("subtitle-extraction-progress://{}", event_id), 
-            serde_json::json!({ "phase": "processing", "message": "正在使用 Kimi 视频理解模式..." }));
+    let window = match range {
+        Some(range) => range.clamp_to(duration)?,
+        None => TimeRange {
+            start: 0.0,
+            end: duration,
+        },
+    };
 
-        return extract_subtitles_with_kimi(app, video_path, video_id, api_key, model, event_id)
+    // 分片长度、重叠和并发数可在转录设置中调整
+    let settings = load_config(&app)
+        .ok()
+        .flatten()
+        .map(|config| config.transcription)
+        .unwrap_or_default();
+    if let Some(options) = chunk_options(engine.chunk_seconds(), &settings) {
+        if window.len() > options.chunk_seconds {
+            println!("[SubtitleExtraction] 视频超过引擎单次处理时长，启用分片提取模式");
+            emit_progress(
+                &app,
+                event_id,
+                serde_json::json!({ "phase": "chunked", "message": tr("subtitle.chunked") }),
+            );
+            return extract_subtitles_chunked(
+                app, video_path, video_id, engine, options, window, event_id, cancel,
+            )
             .await;
+        }
     }
 
-    if duration > CHUNK_THRESHOLD_SECONDS {
-        println!("[SubtitleExtraction] 视频超过5分钟，启用分片提取模式");
-        let _ = app.emit(
-            &format!("subtitle-extraction-progress://{}", event_id),
-            serde_json::json!({ "phase": "chunked", "message": "视频较长，启用分片提取模式..." }),
+    // 短视频直接提取
+    println!("[SubtitleExtraction] 使用标准提取模式");
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "audio", "message": tr("subtitle.audio") }),
+    );
+
+    // 指定了时间范围：只提取该区间的音频，时间轴按区间起点平移回原视频
+    if range.is_some() {
+        emit_progress(
+            &app,
+            event_id,
+            serde_json::json!({ "phase": "transcribe", "message": engine.transcribe_message() }),
         );
-        return extract_subtitles_chunked(
-            app, video_path, video_id, provider, api_key, model, base_url, duration, event_id,
+        let chunk = extract_and_transcribe_segment(
+            &app,
+            engine,
+            video_path,
+            window.start,
+            window.len(),
+            "range".to_string(),
         )
-        .await;
-    }
+        .await?;
+        let result = TranscriptionResult {
+            segments: chunk.segments,
+            full_text: String::new(),
+        };
+        let segments = transcription_to_segments(&result, video_id);
 
-    // 原有逻辑：短视频直接提取
-    println!("[SubtitleExtraction] 视频较短，使用标准提取模式");
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "audio", "message": "提取音频中..." }),
-    );
+        emit_progress(
+            &app,
+            event_id,
+            serde_json::json!({ "phase": "done", "message": tr("subtitle.done"), "count": segments.len() }),
+        );
+
+        return Ok(segments);
+    }
 
     // 2. 从视频中提取完整音频
     let audio_path = extract_audio_from_video(&app, video_path).await?;
     println!("[SubtitleExtraction] 音频提取完成: {:?}", audio_path);
 
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "transcribe", "message": "转录音频中..." }),
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "transcribe", "message": engine.transcribe_message() }),
     );
 
-    // 3. 调用 Gemini API 进行转录
-    let transcription =
-        transcribe_audio_with_gemini(&audio_path, provider, api_key, model, base_url).await?;
+    // 3. 调用引擎进行转录
+    let transcription = engine.transcribe(&audio_path).await;
+
+    // 4. 清理临时音频文件
+    if let Err(e) = fs::remove_file(&audio_path) {
+        println!("[SubtitleExtraction] 清理临时音频文件失败: {}", e);
+    }
+
+    let transcription = transcription?;
     println!(
         "[SubtitleExtraction] 转录完成，共 {} 个片段",
         transcription.segments.len()
     );
 
-    // 4. 转换为 ArticleSegment
+    // 5. 转换为 ArticleSegment
     let segments = transcription_to_segments(&transcription, video_id);
 
-    // 5. 清理临时音频文件
-    if let Err(e) = fs::remove_file(&audio_path) {
-        println!("[SubtitleExtraction] 清理临时音频文件失败: {}", e);
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "done", "message": tr("subtitle.done"), "count": segments.len() }),
+    );
+
+    Ok(segments)
+}
+
+/// 视频理解模式：压缩视频后整段交给引擎
+async fn extract_subtitles_from_video(
+    app: AppHandle,
+    video_path: &Path,
+    video_id: &str,
+    engine: &dyn TranscriptionEngine,
+    event_id: &str,
+    range: Option<TimeRange>,
+) -> Result<Vec<ArticleSegment>, String> {
+    // 1. 压缩视频 (至 480p, CRF 28 以减小体积，便于 Base64 编码)
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "compress", "message": tr("subtitle.compress") }),
+    );
+
+    let compressed_path = compress_video_for_upload(&app, video_path, range).await?;
+    println!("[SubtitleExtraction] 视频压缩完成: {:?}", compressed_path);
+
+    // 2. 发送转录请求
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "analyze", "message": engine.transcribe_message() }),
+    );
+
+    let transcription = engine.transcribe(&compressed_path).await;
+
+    // 清理本地压缩文件
+    if let Err(e) = fs::remove_file(&compressed_path) {
+        println!("[SubtitleExtraction] 警告: 清理临时视频文件失败: {}", e);
     }
 
-    let _ = app.emit(&format!("subtitle-extraction-progress://{}", event_id), 
-        serde_json::json!({ "phase": "done", "message": "字幕提取完成！", "count": segments.len() }));
+    // 3. 转换为 ArticleSegment（区间提取时时间轴平移回原视频）
+    let mut transcription = transcription?;
+    if let Some(range) = range {
+        transcription.segments = offset_segments(transcription.segments, range.start);
+    }
+    let segments = transcription_to_segments(&transcription, video_id);
+
+    emit_progress(
+        &app,
+        event_id,
+        serde_json::json!({ "phase": "done", "message": tr("subtitle.done"), "count": segments.len() }),
+    );
 
     Ok(segments)
 }
@@ -135,8 +320,10 @@ pub async fn extract_subtitles(
 /// 获取视频时长（秒）
 ///
 /// 使用 FFmpeg 获取视频的精确时长（通过解析 stderr 输出）
-async fn get_video_duration(app: &AppHandle, video_path: &Path) -> Result<f64, String> {
-    let video_path_str = video_path.to_str().ok_or("无效的视频文件路径")?;
+pub(crate) async fn get_video_duration(app: &AppHandle, video_path: &Path) -> Result<f64, String> {
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
     let shell = app.shell();
 
     // 使用 FFmpeg 获取时长
@@ -144,11 +331,11 @@ async fn get_video_duration(app: &AppHandle, video_path: &Path) -> Result<f64, S
     // FFmpeg 会在 stderr 中输出类似 "Duration: 00:25:30.50" 的信息
     let output = shell
         .sidecar("ffmpeg")
-        .map_err(|e| format!("无法创建 FFmpeg sidecar: {}。请确保 sidecar 配置正确。", e))?
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
         .args(["-i", video_path_str, "-f", "null", "-"])
         .output()
         .await
-        .map_err(|e| format!("FFmpeg 执行失败: {}。请确保已安装 FFmpeg。", e))?;
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
 
     // FFmpeg 即使成功也会返回非0状态码（因为我们没有真正输出）
     // 所以我们直接解析 stderr
@@ -168,9 +355,9 @@ async fn get_video_duration(app: &AppHandle, video_path: &Path) -> Result<f64, S
         }
     }
 
-    Err(format!(
-        "无法从 FFmpeg 输出中解析视频时长。stderr: {}",
-        stderr.chars().take(500).collect::<String>()
+    Err(tr_with(
+        "error.media_duration_unknown",
+        &[("error", stderr.chars().take(500).collect::<String>())],
     ))
 }
 
@@ -178,18 +365,21 @@ async fn get_video_duration(app: &AppHandle, video_path: &Path) -> Result<f64, S
 fn parse_ffmpeg_duration(time_str: &str) -> Result<f64, String> {
     let parts: Vec<&str> = time_str.split(':').collect();
     if parts.len() != 3 {
-        return Err(format!("无效的时长格式: {}", time_str));
+        return Err(tr_with(
+            "error.media_duration_unknown",
+            &[("error", time_str.to_string())],
+        ));
     }
 
-    let hours: f64 = parts[0]
-        .parse()
-        .map_err(|_| format!("无法解析小时: {}", parts[0]))?;
-    let minutes: f64 = parts[1]
-        .parse()
-        .map_err(|_| format!("无法解析分钟: {}", parts[1]))?;
-    let seconds: f64 = parts[2]
-        .parse()
-        .map_err(|_| format!("无法解析秒: {}", parts[2]))?;
+    let invalid = || {
+        tr_with(
+            "error.media_duration_unknown",
+            &[("error", time_str.to_string())],
+        )
+    };
+    let hours: f64 = parts[0].parse().map_err(|_| invalid())?;
+    let minutes: f64 = parts[1].parse().map_err(|_| invalid())?;
+    let seconds: f64 = parts[2].parse().map_err(|_| invalid())?;
 
     Ok(hours * 3600.0 + minutes * 60.0 + seconds)
 }
@@ -224,16 +414,22 @@ async fn extract_audio_segment(
     duration: f64,
     suffix: &str,
 ) -> Result<PathBuf, String> {
-    let video_dir = video_path.parent().ok_or("无法获取视频目录")?;
+    let video_dir = video_path
+        .parent()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     let video_stem = video_path
         .file_stem()
         .and_then(|s| s.to_str())
-        .ok_or("无法获取视频文件名")?;
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     let audio_path = video_dir.join(format!("{}_audio_{}.mp3", video_stem, suffix));
-    let audio_path_str = audio_path.to_str().ok_or("无效的音频文件路径")?;
-    let video_path_str = video_path.to_str().ok_or("无效的视频文件路径")?;
+    let audio_path_str = audio_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     // 清理旧文件
     if audio_path.exists() {
@@ -251,7 +447,7 @@ async fn extract_audio_segment(
     // -ab 192k: 192kbps比特率兼顾质量和API文件大小限制
     let output = shell
         .sidecar("ffmpeg")
-        .map_err(|e| format!("无法创建 FFmpeg sidecar: {}。请确保 sidecar 配置正确。", e))?
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
         .args([
             "-ss",
             &format!("{:.2}", start_time),
@@ -273,15 +469,18 @@ async fn extract_audio_segment(
         ])
         .output()
         .await
-        .map_err(|e| format!("FFmpeg 执行失败: {}。请确保已安装 FFmpeg。", e))?;
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg 音频片段提取失败: {}", stderr));
+        return Err(tr_with(
+            "error.audio_extract_failed",
+            &[("error", stderr.to_string())],
+        ));
     }
 
     if !audio_path.exists() {
-        return Err("音频片段文件未生成".to_string());
+        return Err(tr("error.audio_output_missing"));
     }
 
     Ok(audio_path)
@@ -291,15 +490,12 @@ async fn extract_audio_segment(
 ///
 /// 此函数提取指定时间段的音频，发送至 API 转录，并调整时间轴
 async fn extract_and_transcribe_segment(
-    app: AppHandle,
-    video_path: PathBuf,
+    app: &AppHandle,
+    engine: &dyn TranscriptionEngine,
+    video_path: &Path,
     start_time: f64,
     duration: f64,
     suffix: String,
-    provider: String,
-    api_key: String,
-    model: String,
-    base_url: Option<String>,
 ) -> Result<ChunkTranscriptionResult, String> {
     println!(
         "[SubtitleExtraction] 提取片段: start={:.1}s, duration={:.1}s, suffix={}",
@@ -307,38 +503,19 @@ async fn extract_and_transcribe_segment(
     );
 
     // 1. 提取音频片段
-    let audio_path =
-        extract_audio_segment(&app, &video_path, start_time, duration, &suffix).await?;
+    let audio_path = extract_audio_segment(app, video_path, start_time, duration, &suffix).await?;
 
     // 2. 转录音频
-    let transcription = transcribe_audio_with_gemini(
-        &audio_path,
-        &provider,
-        &api_key,
-        &model,
-        base_url.as_deref(),
-    )
-    .await?;
+    let transcription = engine.transcribe(&audio_path).await;
 
     // 3. 清理临时音频文件
     if let Err(e) = fs::remove_file(&audio_path) {
         println!("[SubtitleExtraction] 清理临时音频片段失败: {}", e);
     }
+    let transcription = transcription?;
 
     // 4. 调整时间轴（加上偏移量）
-    let segments: Vec<TranscriptionSegment> = transcription
-        .segments
-        .into_iter()
-        .map(|mut seg| {
-            if let Some(st) = seg.start_time {
-                seg.start_time = Some(st + start_time);
-            }
-            if let Some(et) = seg.end_time {
-                seg.end_time = Some(et + start_time);
-            }
-            seg
-        })
-        .collect();
+    let segments = offset_segments(transcription.segments, start_time);
 
     // 5. 获取边界时间
     let first_segment_start = segments.first().and_then(|s| s.start_time);
@@ -360,144 +537,199 @@ async fn extract_and_transcribe_segment(
     })
 }
 
+/// 把片段内的相对时间平移为原视频时间
+fn offset_segments(segments: Vec<TranscriptionSegment>, offset: f64) -> Vec<TranscriptionSegment> {
+    segments
+        .into_iter()
+        .map(|mut seg| {
+            if let Some(st) = seg.start_time {
+                seg.start_time = Some(st + offset);
+            }
+            if let Some(et) = seg.end_time {
+                seg.end_time = Some(et + offset);
+            }
+            seg
+        })
+        .collect()
+}
+
 /// 分片提取长视频字幕（顺序线性分片策略）
 ///
 /// # 算法说明
-/// 1. 将音频按固定步长（10分钟）顺序切片，相邻片段有30秒重叠
-/// 2. 每两个相邻片段并发提取，逐步向前推进
+/// 1. 将音频（或指定区间）按分片长度（默认10分钟）顺序切片，相邻片段有重叠（默认30秒）
+/// 2. 每次并发提取若干个相邻片段（默认2个），逐步向前推进
 /// 3. 合并所有片段后，通过模糊匹配去重消除overlap区域的重复字幕
 async fn extract_subtitles_chunked(
     app: AppHandle,
     video_path: &Path,
     video_id: &str,
-    provider: &str,
-    api_key: &str,
-    model: &str,
-    base_url: Option<&str>,
-    total_duration: f64,
+    engine: &dyn TranscriptionEngine,
+    options: ChunkOptions,
+    window: TimeRange,
     event_id: &str,
+    cancel: &CancellationToken,
 ) -> Result<Vec<ArticleSegment>, String> {
-    const CHUNK_DURATION: f64 = 10.0 * 60.0; // 每片10分钟
-    const OVERLAP: f64 = 30.0; // 30秒重叠
-    let step = CHUNK_DURATION - OVERLAP; // 实际步进 = 9分30秒
+    let chunk_duration = options.chunk_seconds;
+    let step = options.step(); // 默认步进 = 9分30秒
 
     // 计算所有片段的起始时间
     let mut chunk_starts: Vec<f64> = Vec::new();
-    let mut pos = 0.0;
-    while pos < total_duration {
+    let mut pos = window.start;
+    while pos < window.end {
         chunk_starts.push(pos);
         pos += step;
     }
     let total_chunks = chunk_starts.len() as i32;
-    let mut completed_chunks = 0;
 
     println!(
         "[SubtitleExtraction] 顺序分片: 共 {} 个片段, 每片 {:.0}s, 重叠 {:.0}s, 步进 {:.0}s",
-        total_chunks, CHUNK_DURATION, OVERLAP, step
+        total_chunks, chunk_duration, options.overlap, step
     );
 
-    let mut all_segments: Vec<TranscriptionSegment> = Vec::new();
-
-    // 两两并发提取
-    let mut i = 0;
-    while i < chunk_starts.len() {
-        // 计算本轮要提取的片段（最多2个并发）
-        let start1 = chunk_starts[i];
-        let dur1 = (total_duration - start1).min(CHUNK_DURATION);
-
-        if i + 1 < chunk_starts.len() {
-            // 并发提取两个片段
-            let start2 = chunk_starts[i + 1];
-            let dur2 = (total_duration - start2).min(CHUNK_DURATION);
-
-            let _ = app.emit(
-                &format!("subtitle-extraction-progress://{}", event_id),
-                serde_json::json!({
-                    "phase": "chunk",
-                    "message": format!("提取片段 {}-{}/{}", i+1, i+2, total_chunks),
-                    "current": completed_chunks,
-                    "total": total_chunks
-                }),
-            );
+    // 读取断点：同一视频上次提取中途失败时，跳过已完成的片段
+    let checkpoint_file = checkpoint_path(&get_app_data_dir(&app)?, video_id);
+    let video_size = fs::metadata(video_path).map(|m| m.len()).unwrap_or(0);
+    let mut checkpoint = ChunkCheckpoint::resume_or(
+        &checkpoint_file,
+        ChunkCheckpoint::new(
+            video_path,
+            video_size,
+            engine.id(),
+            chunk_duration,
+            window.end,
+            chunk_starts.clone(),
+        ),
+    );
+    let pending = checkpoint.pending_chunks();
+    let mut completed_chunks = total_chunks - pending.len() as i32;
+    if completed_chunks > 0 {
+        println!(
+            "[SubtitleExtraction] 从断点继续: 已完成 {}/{} 个片段",
+            completed_chunks, total_chunks
+        );
+        emit_progress(
+            &app,
+            event_id,
+            serde_json::json!({
+                "phase": "resumed",
+                "message": tr_with("subtitle.resumed", &[
+                    ("done", completed_chunks.to_string()),
+                    ("total", total_chunks.to_string()),
+                ]),
+                "current": completed_chunks,
+                "total": total_chunks
+            }),
+        );
+    }
 
-            let (r1, r2) = tokio::join!(
-                extract_and_transcribe_segment(
-                    app.clone(),
-                    video_path.to_path_buf(),
-                    start1,
-                    dur1,
-                    format!("chunk_{}", i),
-                    provider.to_string(),
-                    api_key.to_string(),
-                    model.to_string(),
-                    base_url.map(str::to_string),
-                ),
-                extract_and_transcribe_segment(
-                    app.clone(),
-                    video_path.to_path_buf(),
-                    start2,
-                    dur2,
-                    format!("chunk_{}", i + 1),
-                    provider.to_string(),
-                    api_key.to_string(),
-                    model.to_string(),
-                    base_url.map(str::to_string),
-                )
-            );
+    // 按设置的并发数分组提取
+    for batch in pending.chunks(options.concurrency) {
+        // 每轮开始前检查是否已取消，避免继续提取、上传后续片段
+        if cancel.is_cancelled() {
+            println!("[SubtitleExtraction] 已取消，停止分片提取");
+            return Err(cancelled_message());
+        }
 
-            all_segments.extend(r1?.segments);
-            all_segments.extend(r2?.segments);
-            completed_chunks += 2;
-            i += 2;
-        } else {
-            // 奇数片段，单独提取
-            let _ = app.emit(
-                &format!("subtitle-extraction-progress://{}", event_id),
-                serde_json::json!({
-                    "phase": "chunk",
-                    "message": format!("提取片段 {}/{}", i+1, total_chunks),
-                    "current": completed_chunks,
-                    "total": total_chunks
-                }),
-            );
+        let message = match batch {
+            [first, .., last] => tr_with(
+                "subtitle.chunk_pair",
+                &[
+                    ("start", (first + 1).to_string()),
+                    ("end", (last + 1).to_string()),
+                    ("total", total_chunks.to_string()),
+                ],
+            ),
+            _ => tr_with(
+                "subtitle.chunk",
+                &[
+                    ("index", (batch[0] + 1).to_string()),
+                    ("total", total_chunks.to_string()),
+                ],
+            ),
+        };
+        emit_progress(
+            &app,
+            event_id,
+            serde_json::json!({
+                "phase": "chunk",
+                "message": message,
+                "current": completed_chunks,
+                "total": total_chunks
+            }),
+        );
 
-            let r = extract_and_transcribe_segment(
-                app.clone(),
-                video_path.to_path_buf(),
-                start1,
-                dur1,
-                format!("chunk_{}", i),
-                provider.to_string(),
-                api_key.to_string(),
-                model.to_string(),
-                base_url.map(str::to_string),
+        let results = futures::future::join_all(batch.iter().map(|&index| {
+            let start = chunk_starts[index];
+            extract_and_transcribe_segment(
+                &app,
+                engine,
+                video_path,
+                start,
+                (window.end - start).min(chunk_duration),
+                format!("chunk_{}", index),
             )
-            .await?;
+        }))
+        .await;
 
-            all_segments.extend(r.segments);
-            completed_chunks += 1;
-            i += 1;
+        // 成功的片段先写入断点，再报告失败的片段
+        let mut failure = None;
+        for (&index, result) in batch.iter().zip(results) {
+            match result {
+                Ok(chunk) => {
+                    checkpoint.chunks.insert(index, chunk.segments);
+                    completed_chunks += 1;
+                }
+                Err(e) => {
+                    failure.get_or_insert((index, e));
+                }
+            }
+        }
+        if let Err(e) = checkpoint.save(&checkpoint_file) {
+            println!("[SubtitleExtraction] 保存断点失败: {}", e);
+        }
+        if let Some((index, error)) = failure {
+            return Err(tr_with(
+                "subtitle.chunk_failed",
+                &[
+                    ("index", (index + 1).to_string()),
+                    ("done", completed_chunks.to_string()),
+                    ("total", total_chunks.to_string()),
+                    ("error", error),
+                ],
+            ));
         }
 
-        let _ = app.emit(&format!("subtitle-extraction-progress://{}", event_id),
+        emit_progress(
+            &app,
+            event_id,
             serde_json::json!({
                 "phase": "chunk",
-                "message": format!("已完成 {}/{} 片段", completed_chunks.min(total_chunks), total_chunks),
-                "current": completed_chunks.min(total_chunks),
+                "message": tr_with("subtitle.chunks_completed", &[
+                    ("done", completed_chunks.to_string()),
+                    ("total", total_chunks.to_string()),
+                ]),
+                "current": completed_chunks,
                 "total": total_chunks
-            }));
+            }),
+        );
     }
 
+    let mut all_segments: Vec<TranscriptionSegment> = std::mem::take(&mut checkpoint.chunks)
+        .into_values()
+        .flatten()
+        .collect();
+
     // === 合并、排序、去重 ===
     println!(
         "[SubtitleExtraction] === 合并排序去重: {} 个原始字幕 ===",
         all_segments.len()
     );
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
+    emit_progress(
+        &app,
+        event_id,
         serde_json::json!({
             "phase": "merge",
-            "message": "合并排序去重中..."
+            "message": tr("subtitle.merging")
         }),
     );
 
@@ -521,15 +753,21 @@ async fn extract_subtitles_chunked(
         deduped_segments.len()
     );
 
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
+    emit_progress(
+        &app,
+        event_id,
         serde_json::json!({
             "phase": "done",
-            "message": "字幕提取完成！",
+            "message": tr("subtitle.done"),
             "count": deduped_segments.len()
         }),
     );
 
+    // 全部片段已合并，断点不再需要
+    if let Err(e) = fs::remove_file(&checkpoint_file) {
+        println!("[SubtitleExtraction] 删除断点失败: {}", e);
+    }
+
     // 转换为 ArticleSegment
     let result = TranscriptionResult {
         segments: deduped_segments,
@@ -645,16 +883,22 @@ fn deduplicate_segments(segments: Vec<TranscriptionSegment>) -> Vec<Transcriptio
 /// 输出格式: MP3 (Gemini 支持的格式)
 /// 输出位置: 与视频同目录，文件名为 {video_name}_audio.mp3
 async fn extract_audio_from_video(app: &AppHandle, video_path: &Path) -> Result<PathBuf, String> {
-    let video_dir = video_path.parent().ok_or("无法获取视频目录")?;
+    let video_dir = video_path
+        .parent()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     let video_stem = video_path
         .file_stem()
         .and_then(|s| s.to_str())
-        .ok_or("无法获取视频文件名")?;
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     let audio_path = video_dir.join(format!("{}_audio.mp3", video_stem));
-    let audio_path_str = audio_path.to_str().ok_or("无效的音频文件路径")?;
-    let video_path_str = video_path.to_str().ok_or("无效的视频文件路径")?;
+    let audio_path_str = audio_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let video_path_str = video_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
 
     // 检查是否已存在音频文件（之前提取过但未清理）
     if audio_path.exists() {
@@ -676,7 +920,7 @@ async fn extract_audio_from_video(app: &AppHandle, video_path: &Path) -> Result<
 
     let output = shell
         .sidecar("ffmpeg")
-        .map_err(|e| format!("无法创建 FFmpeg sidecar: {}。请确保 sidecar 配置正确。", e))?
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
         .args([
             "-i",
             video_path_str,
@@ -694,151 +938,38 @@ async fn extract_audio_from_video(app: &AppHandle, video_path: &Path) -> Result<
         ])
         .output()
         .await
-        .map_err(|e| format!("FFmpeg 执行失败: {}。请确保已安装 FFmpeg。", e))?;
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg 音频提取失败: {}", stderr));
+        return Err(tr_with(
+            "error.audio_extract_failed",
+            &[("error", stderr.to_string())],
+        ));
     }
 
     // 验证音频文件已创建
     if !audio_path.exists() {
-        return Err("音频文件未生成".to_string());
+        return Err(tr("error.audio_output_missing"));
     }
 
     Ok(audio_path)
 }
 
-/// 使用 Kimi K2.5 模型提取字幕 (视频理解 - 使用 Base64 内嵌视频)
-async fn extract_subtitles_with_kimi(
-    app: AppHandle,
-    video_path: &Path,
-    video_id: &str,
-    api_key: &str,
-    model: &str,
-    event_id: &str,
-) -> Result<Vec<ArticleSegment>, String> {
-    // 1. 压缩视频 (至 480p, CRF 28 以减小体积，便于 Base64 编码)
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "compress", "message": "正在优化视频体积..." }),
-    );
-
-    let compressed_path = compress_video_for_upload(&app, video_path).await?;
-    println!("[SubtitleExtraction] 视频压缩完成: {:?}", compressed_path);
-
-    // 2. 读取压缩后的视频并 Base64 编码
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "encode", "message": "正在编码视频数据..." }),
-    );
-
-    let video_bytes = fs::read(&compressed_path).map_err(|e| format!("读取压缩视频失败: {}", e))?;
-
-    let video_size_mb = video_bytes.len() as f64 / 1024.0 / 1024.0;
-    println!(
-        "[SubtitleExtraction] 压缩后视频大小: {:.2} MB",
-        video_size_mb
-    );
-
-    // 获取视频扩展名用于 MIME 类型
-    let ext = compressed_path
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("mp4")
-        .to_lowercase();
-
-    // 构建 data URL: data:video/{ext};base64,{base64_data}
-    let video_base64 = BASE64.encode(&video_bytes);
-    let video_data_url = format!("data:video/{};base64,{}", ext, video_base64);
-
-    // 清理本地压缩文件
-    if let Err(e) = fs::remove_file(&compressed_path) {
-        println!("[SubtitleExtraction] 警告: 清理临时视频文件失败: {}", e);
-    }
-
-    // 3. 发送转录请求
-    let _ = app.emit(
-        &format!("subtitle-extraction-progress://{}", event_id),
-        serde_json::json!({ "phase": "analyze", "message": "Kimi 正在分析视频生成字幕..." }),
-    );
-
-    let prompt = r#"请分析视频中的语音内容，并生成带时间轴的字幕。
-严格按照以下 JSON 格式返回结果：
-{
-  "segments": [
-    {
-      "start": "MM:SS",
-      "end": "MM:SS",
-      "content": "字幕内容"
-    }
-  ],
-  "full_text": "全文内容"
-}
-要求：
-1. 精确对应语音时间。
-2. 按句子或短语断句。
-3. 保持原语言，不要翻译。
-4. 忽略背景音和无意义语气词。
-"#;
-
-    let ai_service = AIService::new(
-        api_key.to_string(),
-        "moonshot".to_string(),
-        model.to_string(),
-    );
-
-    let chat_request = ChatRequest {
-        model: model.to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: ChatContent::Parts(vec![
-                ContentPart {
-                    part_type: "video_url".to_string(),
-                    text: None,
-                    image_url: None,
-                    file_data: None,
-                    video_url: Some(VideoUrl {
-                        url: video_data_url, // 使用 Base64 data URL
-                    }),
-                },
-                ContentPart {
-                    part_type: "text".to_string(),
-                    text: Some(prompt.to_string()),
-                    image_url: None,
-                    file_data: None,
-                    video_url: None,
-                },
-            ]),
-        }],
-        temperature: Some(1.0), // Kimi 要求 temperature=1
-    };
-
-    let response = match ai_service.chat(chat_request).await {
-        Ok(res) => res,
-        Err(e) => return Err(format!("Kimi 分析失败: {}", e)),
-    };
-
-    // 4. 解析结果
-    let transcription = parse_transcription_response(&response.content)?;
-
-    // 5. 转换为 ArticleSegment
-    let segments = transcription_to_segments(&transcription, video_id);
-
-    let _ = app.emit(&format!("subtitle-extraction-progress://{}", event_id), 
-        serde_json::json!({ "phase": "done", "message": "字幕提取完成！", "count": segments.len() }));
-
-    Ok(segments)
-}
-
-/// 压缩视频以便上传
+/// 压缩视频以便上传（指定时间范围时只截取该区间）
 /// 目标: 480p, CRF 28, Preset veryfast
-async fn compress_video_for_upload(app: &AppHandle, video_path: &Path) -> Result<PathBuf, String> {
-    let video_dir = video_path.parent().ok_or("无效的视频目录")?;
+async fn compress_video_for_upload(
+    app: &AppHandle,
+    video_path: &Path,
+    range: Option<TimeRange>,
+) -> Result<PathBuf, String> {
+    let video_dir = video_path
+        .parent()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
     let video_stem = video_path
         .file_stem()
         .and_then(|s| s.to_str())
-        .ok_or("无效的文件名")?;
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
     let output_path = video_dir.join(format!("{}_compressed.mp4", video_stem));
 
     if output_path.exists() {
@@ -847,9 +978,19 @@ async fn compress_video_for_upload(app: &AppHandle, video_path: &Path) -> Result
 
     let shell = app.shell();
 
+    // -ss 放在 -i 前面快速定位；未指定结束时间时截取到视频结尾
+    let mut args: Vec<String> = Vec::new();
+    if let Some(range) = range {
+        args.extend(["-ss".to_string(), format!("{:.2}", range.start)]);
+        if range.end.is_finite() {
+            args.extend(["-t".to_string(), format!("{:.2}", range.len())]);
+        }
+    }
+
     let output = shell
         .sidecar("ffmpeg")
-        .map_err(|e| format!("无法创建 FFmpeg sidecar: {}", e))?
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
+        .args(args)
         .args([
             "-i",
             video_path.to_str().unwrap(),
@@ -872,236 +1013,25 @@ async fn compress_video_for_upload(app: &AppHandle, video_path: &Path) -> Result
         ])
         .output()
         .await
-        .map_err(|e| format!("FFmpeg 压缩失败: {}", e))?;
+        .map_err(|e| tr_with("error.video_compress_failed", &[("error", e.to_string())]))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg 压缩错误: {}", stderr));
+        return Err(tr_with(
+            "error.video_compress_failed",
+            &[("error", stderr.to_string())],
+        ));
     }
 
     if !output_path.exists() {
-        return Err("压缩后的视频文件未生成".to_string());
+        return Err(tr("error.video_output_missing"));
     }
 
     Ok(output_path)
 }
 
-/// 使用 Gemini API 转录音频
-///
-/// 支持的 API 提供商:
-/// - openrouter: OpenRouter API (使用 input_audio 格式)
-/// - 302ai: 302.AI API (兼容 OpenAI 格式)
-/// - google: Google Gemini 直接 API
-async fn transcribe_audio_with_gemini(
-    audio_path: &Path,
-    provider: &str,
-    api_key: &str,
-    model: &str,
-    base_url: Option<&str>,
-) -> Result<TranscriptionResult, String> {
-    const MAX_RETRIES: u32 = 3;
-    let mut retry_count = 0;
-
-    loop {
-        // 读取并编码音频文件 (每次重试都重新读取可能没必要，但为了安全起见暂时不改这里)
-        let audio_bytes = fs::read(audio_path).map_err(|e| format!("读取音频文件失败: {}", e))?;
-
-        let audio_base64 = BASE64.encode(&audio_bytes);
-        let audio_size_mb = audio_bytes.len() as f64 / 1024.0 / 1024.0;
-        println!("[SubtitleExtraction] 音频文件大小: {:.2} MB", audio_size_mb);
-
-        // 注意：20MB 限制现在由分片提取算法处理，此处不再需要检查
-
-        // 转录提示词 - 强调时间戳精度和按句子断句
-        let transcription_prompt = r#"Transcribe this audio into text with precise timestamps. Return strictly in the following JSON format.
-
-Requirements:
-1. **Sentence-level segmentation**: Each segment contains exactly ONE complete sentence. Do NOT merge multiple sentences.
-2. Split at sentence-ending punctuation (periods, question marks, exclamation marks) or natural speech pauses.
-3. Each sentence should be roughly 5-30 characters/words. Never exceed 50.
-4. **Timestamp accuracy is critical**: start and end times MUST precisely match when the speech actually begins and ends in the audio. Listen carefully to the exact timing.
-5. Format: MM:SS (e.g., "01:23" for 1 minute 23 seconds). Both start and end are required.
-6. Keep the original language. Do NOT translate.
-7. Timestamps must be monotonically increasing — each segment's start must be >= the previous segment's end.
-
-Return format:
-{
-  "segments": [
-    {
-      "start": "00:00",
-      "end": "00:03",
-      "content": "First sentence of the audio.",
-      "speaker": null
-    },
-    {
-      "start": "00:03",
-      "end": "00:06",
-      "content": "Second sentence of the audio.",
-      "speaker": null
-    }
-  ],
-  "full_text": "Full transcription text..."
-}
-
-IMPORTANT: Each segment = one sentence. Timestamps must be precise to the second.
-"#;
-
-        let client = Client::new();
-
-        // 根据提供商选择不同的 API 格式
-        let response = match provider {
-            "google" | "google-ai-studio" => {
-                // Google Gemini 直接 API
-                let url = format!(
-                    "{}/{}:generateContent?key={}",
-                    GOOGLE_GEMINI_URL,
-                    model.strip_prefix("models/").unwrap_or(model),
-                    api_key
-                );
-
-                let request_body = json!({
-                    "contents": [{
-                        "parts": [
-                            {
-                                "inline_data": {
-                                    "mime_type": "audio/mp3",
-                                    "data": audio_base64
-                                }
-                            },
-                            {
-                                "text": transcription_prompt
-                            }
-                        ]
-                    }],
-                    "generationConfig": {
-                        "response_mime_type": "application/json"
-                    }
-                });
-
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .json(&request_body)
-                    .send()
-                    .await
-                    .map_err(|e| format!("API 请求失败: {}", e))?
-            }
-            _ => {
-                // OpenAI 兼容格式：优先使用用户配置的 base_url，避免错误回退到固定网关
-                let api_url = if let Some(custom_base_url) =
-                    base_url.and_then(|url| (!url.trim().is_empty()).then_some(url))
-                {
-                    let trimmed = custom_base_url.trim_end_matches('/');
-                    if trimmed.ends_with("/chat/completions") {
-                        trimmed.to_string()
-                    } else {
-                        format!("{}/chat/completions", trimmed)
-                    }
-                } else {
-                    match provider {
-                        "openrouter" => OPENROUTER_API_URL.to_string(),
-                        "302ai" => API_302AI_URL.to_string(),
-                        "moonshot" => MOONSHOT_API_URL.to_string(),
-                        "openai" => OPENAI_API_URL.to_string(),
-                        "openai-compatible" => {
-                            return Err("openai-compatible provider requires base_url in settings"
-                                .to_string());
-                        }
-                        _ => {
-                            return Err(format!(
-                                "Unsupported provider '{}' for subtitle transcription without base_url",
-                                provider
-                            ));
-                        }
-                    }
-                };
-
-                // 使用 OpenAI 兼容的 input_audio 格式
-                let request_body = json!({
-                    "model": model,
-                    "messages": [{
-                        "role": "user",
-                        "content": [
-                            {
-                                "type": "input_audio",
-                                "input_audio": {
-                                    "data": audio_base64,
-                                    "format": "mp3"
-                                }
-                            },
-                            {
-                                "type": "text",
-                                "text": transcription_prompt
-                            }
-                        ]
-                    }],
-                    "temperature": 0.1
-                });
-
-                client
-                    .post(&api_url)
-                    .header("Authorization", format!("Bearer {}", api_key))
-                    .header("Content-Type", "application/json")
-                    .json(&request_body)
-                    .send()
-                    .await
-                    .map_err(|e| format!("API 请求失败: {}", e))?
-            }
-        };
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(format!("API 错误: {}", error_text));
-        }
-
-        let response_json: Value = response
-            .json()
-            .await
-            .map_err(|e| format!("解析响应失败: {}", e))?;
-
-        // 提取响应内容
-        let content = if provider == "google" || provider == "google-ai-studio" {
-            // Google API 响应格式
-            response_json["candidates"][0]["content"]["parts"][0]["text"]
-                .as_str()
-                .unwrap_or("")
-                .to_string()
-        } else {
-            // OpenAI 兼容格式
-            response_json["choices"][0]["message"]["content"]
-                .as_str()
-                .unwrap_or("")
-                .to_string()
-        };
-
-        // 解析转录结果
-        match parse_transcription_response(&content) {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                println!("[SubtitleExtraction] JSON 解析失败: {}", e);
-                println!("[SubtitleExtraction] 尝试解析的原始内容: {}", content);
-
-                retry_count += 1;
-                if retry_count >= MAX_RETRIES {
-                    // 最后一次尝试失败，如果是解析错误且内容不为空，可能是格式问题
-                    // 但如果内容为空，已经在 parse_transcription_response 中处理了
-                    return Err(format!("多次重试后仍然失败: {}", e));
-                }
-
-                println!(
-                    "[SubtitleExtraction] 将进行第 {} 次重试...",
-                    retry_count + 1
-                );
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                continue;
-            }
-        }
-    } // end loop
-}
-
-/// 解析转录 API 响应
 /// 解析转录 API 响应
-fn parse_transcription_response(content: &str) -> Result<TranscriptionResult, String> {
+pub(crate) fn parse_transcription_response(content: &str) -> Result<TranscriptionResult, String> {
     // 0. 处理空内容
     if content.trim().is_empty() {
         println!("[SubtitleExtraction] 警告: API 返回内容为空，视为空音频处理");
@@ -1140,16 +1070,21 @@ fn parse_transcription_response(content: &str) -> Result<TranscriptionResult, St
 
     // 解析 JSON
     let parsed: Value = serde_json::from_str(&json_str).map_err(|e| {
-        format!(
-            "JSON 解析失败: {}. \n提取的JSON: {}\n原始响应: {}",
-            e, json_str, content
+        tr_with(
+            "error.transcript_invalid",
+            &[("error", format!("{}\n{}", e, content))],
         )
     })?;
 
     // 提取 segments
     let segments = parsed["segments"]
         .as_array()
-        .ok_or("响应中没有 segments 字段")?
+        .ok_or_else(|| {
+            tr_with(
+                "error.transcript_invalid",
+                &[("error", "segments".to_string())],
+            )
+        })?
         .iter()
         .filter_map(|seg| {
             // 支持 "start"/"end" 或旧格式 "timestamp"
@@ -1160,7 +1095,7 @@ fn parse_transcription_response(content: &str) -> Result<TranscriptionResult, St
             let end_time = parse_time_str(end_str);
 
             Some(TranscriptionSegment {
-                speaker: seg["speaker"].as_str().map(|s| s.to_string()),
+                speaker: parse_speaker(&seg["speaker"]),
                 // timestamp removed as per user request
                 content: seg["content"].as_str()?.to_string(),
                 start_time: Some(start_time),
@@ -1246,6 +1181,11 @@ fn transcription_to_segments(
             end_time: seg.end_time,
             created_at: Utc::now().to_rfc3339(),
             is_new_paragraph: true,
+            image_url: None,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: seg.speaker.clone(),
         })
         .collect()
 }
@@ -1284,6 +1224,54 @@ mod tests {
         assert_eq!(result.segments[0].end_time, Some(5.0));
     }
 
+    fn segment_at(start: f64, text: &str) -> ArticleSegment {
+        ArticleSegment {
+            id: text.to_string(),
+            article_id: "v1".to_string(),
+            order: 0,
+            text: text.to_string(),
+            reading_text: None,
+            translation: None,
+            explanation: None,
+            start_time: Some(start),
+            end_time: Some(start + 2.0),
+            created_at: String::new(),
+            is_new_paragraph: true,
+            image_url: None,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_time_range_validation() {
+        assert!(TimeRange::new(Some(60.0), Some(120.0)).is_ok());
+        assert!(TimeRange::new(Some(120.0), Some(60.0)).is_err());
+        assert!(TimeRange::new(Some(-1.0), None).is_err());
+
+        let open_ended = TimeRange::new(Some(600.0), None).unwrap();
+        assert_eq!(open_ended.clamp_to(1800.0).unwrap().end, 1800.0);
+        assert!(open_ended.clamp_to(300.0).is_err());
+    }
+
+    #[test]
+    fn test_merge_range_segments_replaces_only_inside_range() {
+        let existing = vec![
+            segment_at(10.0, "before"),
+            segment_at(70.0, "old inside"),
+            segment_at(200.0, "after"),
+        ];
+        let extracted = vec![segment_at(65.0, "new a"), segment_at(90.0, "new b")];
+        let range = TimeRange::new(Some(60.0), Some(120.0)).unwrap();
+
+        let merged = merge_range_segments(existing, extracted, range);
+        let texts: Vec<&str> = merged.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["before", "new a", "new b", "after"]);
+        assert_eq!(merged[3].order, 3);
+    }
+
     #[test]
     fn test_parse_time_str() {
         assert_eq!(parse_time_str("00:00"), 0.0);
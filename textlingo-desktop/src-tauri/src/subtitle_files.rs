@@ -0,0 +1,349 @@
+// 字幕文件解析：SRT、WebVTT 和 ASS/SSA
+// 解析结果为带时间轴的字幕条目，导入为文章时每条字幕成为一个段落；也可把带时间的段落导出为 SRT/VTT
+
+use crate::types::ArticleSegment;
+use chrono::Utc;
+use regex::Regex;
+use uuid::Uuid;
+
+/// 一条字幕
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// 解析时间戳：支持 HH:MM:SS,mmm（SRT）、HH:MM:SS.mmm / MM:SS.mmm（VTT）和 H:MM:SS.cc（ASS）
+pub fn parse_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    let seconds: f64 = parts.last()?.parse().ok()?;
+    let minutes: f64 = parts[parts.len() - 2].parse().ok()?;
+    let hours: f64 = if parts.len() == 3 {
+        parts[0].parse().ok()?
+    } else {
+        0.0
+    };
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// 去掉字幕中的格式标签（<i>、<c.yellow>、<v 说话人>、{\an8}）并合并多行
+fn clean_text(lines: &[&str]) -> String {
+    let re_tags = Regex::new(r"<[^>]*>|\{\\[^}]*\}").unwrap();
+    let text = lines.join(" ");
+    let text = re_tags.replace_all(&text, "");
+    html_escape::decode_html_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 解析 "开始 --> 结束" 时间行，VTT 结束时间后可能带有位置等设置
+fn parse_time_line(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start)?, parse_timestamp(end)?))
+}
+
+/// 按空行分块，每块中时间行之后的行为字幕文本（SRT 和 VTT 共用）
+fn parse_blocks(content: &str) -> Vec<SubtitleCue> {
+    let content = content
+        .strip_prefix('\u{feff}')
+        .unwrap_or(content)
+        .replace("\r\n", "\n");
+    let mut cues = Vec::new();
+    for block in Regex::new(r"\n[ \t]*\n").unwrap().split(&content) {
+        let lines: Vec<&str> = block.lines().map(str::trim).collect();
+        let Some(time_index) = lines.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+        // VTT 的注释和样式块不是字幕
+        if lines
+            .first()
+            .is_some_and(|l| l.starts_with("NOTE") || l.starts_with("STYLE"))
+        {
+            continue;
+        }
+        let Some((start, end)) = parse_time_line(lines[time_index]) else {
+            continue;
+        };
+        let text = clean_text(&lines[time_index + 1..]);
+        if !text.is_empty() {
+            cues.push(SubtitleCue { start, end, text });
+        }
+    }
+    cues
+}
+
+/// 解析 SRT 字幕
+pub fn parse_srt(content: &str) -> Vec<SubtitleCue> {
+    parse_blocks(content)
+}
+
+/// 解析 WebVTT 字幕
+pub fn parse_vtt(content: &str) -> Vec<SubtitleCue> {
+    parse_blocks(content)
+}
+
+/// 解析 ASS/SSA 字幕：按 [Events] 中 Format 行的字段顺序读取 Dialogue 行
+pub fn parse_ass(content: &str) -> Vec<SubtitleCue> {
+    let default_format = [
+        "layer", "start", "end", "style", "name", "marginl", "marginr", "marginv", "effect", "text",
+    ];
+    let re_override = Regex::new(r"\{[^}]*\}").unwrap();
+    let mut format: Vec<String> = default_format.iter().map(|f| f.to_string()).collect();
+    let mut in_events = false;
+    let mut cues = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+        if let Some(fields) = line.strip_prefix("Format:") {
+            format = fields.split(',').map(|f| f.trim().to_lowercase()).collect();
+            continue;
+        }
+        let Some(values) = line.strip_prefix("Dialogue:") else {
+            continue;
+        };
+        // 文本字段在最后且可能包含逗号
+        let values: Vec<&str> = values.splitn(format.len(), ',').collect();
+        let field = |name: &str| {
+            format
+                .iter()
+                .position(|f| f == name)
+                .and_then(|i| values.get(i))
+                .map(|v| v.trim())
+        };
+        let (Some(start), Some(end), Some(text)) = (
+            field("start").and_then(parse_timestamp),
+            field("end").and_then(parse_timestamp),
+            field("text"),
+        ) else {
+            continue;
+        };
+        let text = re_override
+            .replace_all(text, "")
+            .replace("\\N", " ")
+            .replace("\\n", " ")
+            .replace("\\h", " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            cues.push(SubtitleCue { start, end, text });
+        }
+    }
+    // ASS 的 Dialogue 行不一定按时间排列
+    cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+    cues
+}
+
+/// 按扩展名解析字幕文件，不支持的格式返回 None
+pub fn parse_subtitles(content: &str, extension: &str) -> Option<Vec<SubtitleCue>> {
+    match extension.to_lowercase().as_str() {
+        "srt" => Some(parse_srt(content)),
+        "vtt" => Some(parse_vtt(content)),
+        "ass" | "ssa" => Some(parse_ass(content)),
+        _ => None,
+    }
+}
+
+/// 字幕条目转换为段落，每条字幕单独成段
+pub fn cues_to_segments(article_id: &str, cues: &[SubtitleCue]) -> Vec<ArticleSegment> {
+    cues.iter()
+        .enumerate()
+        .map(|(order, cue)| ArticleSegment {
+            id: Uuid::new_v4().to_string(),
+            article_id: article_id.to_string(),
+            order: order as i32,
+            text: cue.text.clone(),
+            reading_text: None,
+            translation: None,
+            explanation: None,
+            start_time: Some(cue.start),
+            end_time: Some(cue.end),
+            created_at: Utc::now().to_rfc3339(),
+            is_new_paragraph: true,
+            image_url: None,
+            translations: Default::default(),
+            processing_status: None,
+            block: None,
+            speaker: None,
+        })
+        .collect()
+}
+
+/// 文章正文：每条字幕一行
+pub fn cues_content(cues: &[SubtitleCue]) -> String {
+    cues.iter()
+        .map(|cue| cue.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 格式化时间戳：SRT 用 HH:MM:SS,mmm，VTT 用 HH:MM:SS.mmm
+pub fn format_timestamp(seconds: f64, vtt: bool) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        if vtt { '.' } else { ',' },
+        millis % 1000
+    )
+}
+
+/// 带开始和结束时间的段落转换为字幕条目；include_translation 为 true 时译文作为第二行
+/// 有说话人的段落在原文和译文前都加上 "说话人: " 前缀
+pub fn segments_to_cues(
+    segments: &[ArticleSegment],
+    include_translation: bool,
+) -> Vec<SubtitleCue> {
+    // 字幕内不能有空行，否则会被播放器当作下一条
+    let one_line = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut segments: Vec<&ArticleSegment> = segments.iter().collect();
+    segments.sort_by_key(|s| s.order);
+    segments
+        .into_iter()
+        .filter_map(|segment| {
+            let (start, end) = (segment.start_time?, segment.end_time?);
+            let text = one_line(&segment.text);
+            if text.is_empty() {
+                return None;
+            }
+            let mut text = segment.with_speaker_prefix(&text);
+            if include_translation {
+                let translation = one_line(segment.translation.as_deref().unwrap_or_default());
+                if !translation.is_empty() {
+                    text = format!("{}\n{}", text, segment.with_speaker_prefix(&translation));
+                }
+            }
+            Some(SubtitleCue {
+                start,
+                end: end.max(start),
+                text,
+            })
+        })
+        .collect()
+}
+
+/// 调整段落时间轴：新时间 = 原时间 × scale + offset（秒），小于 0 时截为 0，返回调整的段落数
+/// 整体提前或延后用 offset；字幕越往后偏差越大（帧率不一致）时用 scale 拉伸
+pub fn adjust_timing(segments: &mut [ArticleSegment], offset: f64, scale: f64) -> usize {
+    let adjust = |time: f64| (time * scale + offset).max(0.0);
+    let mut adjusted = 0;
+    for segment in segments {
+        if segment.start_time.is_none() && segment.end_time.is_none() {
+            continue;
+        }
+        segment.start_time = segment.start_time.map(adjust);
+        segment.end_time = segment.end_time.map(adjust);
+        adjusted += 1;
+    }
+    adjusted
+}
+
+/// 生成 SRT 文件内容
+pub fn render_srt(cues: &[SubtitleCue]) -> String {
+    cues.iter()
+        .enumerate()
+        .map(|(i, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                i + 1,
+                format_timestamp(cue.start, false),
+                format_timestamp(cue.end, false),
+                cue.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 生成 WebVTT 文件内容
+pub fn render_vtt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::from("WEBVTT\n");
+    for cue in cues {
+        out.push_str(&format!(
+            "\n{} --> {}\n{}\n",
+            format_timestamp(cue.start, true),
+            format_timestamp(cue.end, true),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// 按格式名（srt / vtt）生成字幕文件内容，不支持的格式返回 None
+pub fn render_subtitles(cues: &[SubtitleCue], format: &str) -> Option<String> {
+    match format
+        .trim()
+        .trim_start_matches('.')
+        .to_lowercase()
+        .as_str()
+    {
+        "srt" => Some(render_srt(cues)),
+        "vtt" => Some(render_vtt(cues)),
+        _ => None,
+    }
+}
+
+/// ASS 时间戳：H:MM:SS.cc（百分之一秒）
+pub fn format_ass_timestamp(seconds: f64) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centis / 360_000,
+        centis / 6000 % 60,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+/// 字幕文本中的花括号会被当作样式标签，替换为普通括号
+fn ass_text(text: &str) -> String {
+    text.replace('{', "(").replace('}', ")")
+}
+
+/// 生成双语 ASS 字幕：第一行原文用 Original 样式，其余行（译文）用较小的 Translation 样式
+pub fn render_ass(cues: &[SubtitleCue]) -> String {
+    let mut out = String::from(
+        "[Script Info]
+ScriptType: v4.00+
+PlayResX: 1920
+PlayResY: 1080
+WrapStyle: 0
+ScaledBorderAndShadow: yes
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Original,Arial,64,&H00FFFFFF,&H00FFFFFF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,3,1,2,60,60,50,1
+Style: Translation,Arial,48,&H0080FFFF,&H0080FFFF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,2.5,1,2,60,60,50,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+",
+    );
+    for cue in cues {
+        let mut lines = cue.text.lines().map(ass_text);
+        let mut text = lines.next().unwrap_or_default();
+        for line in lines {
+            text.push_str(&format!("\\N{{\\rTranslation}}{}", line));
+        }
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},Original,,0,0,0,,{}\n",
+            format_ass_timestamp(cue.start),
+            format_ass_timestamp(cue.end),
+            text
+        ));
+    }
+    out
+}
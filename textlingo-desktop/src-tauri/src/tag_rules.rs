@@ -0,0 +1,58 @@
+// 自动标签规则：导入素材时按来源域名、语言、YouTube 频道或关键词自动加标签，
+// 新素材直接归入对应合集；规则修改后也可对已有文章重新执行
+
+use crate::types::{Article, TagRule, TagRuleField};
+
+/// 网址的域名（小写，去掉 www.）
+pub fn source_domain(url: &str) -> Option<String> {
+    let host = url::Url::parse(url.trim()).ok()?.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+/// 规则是否匹配文章；模式不区分大小写
+pub fn rule_matches(rule: &TagRule, article: &Article) -> bool {
+    let pattern = rule.pattern.trim().to_lowercase();
+    if !rule.enabled || pattern.is_empty() {
+        return false;
+    }
+    match rule.field {
+        TagRuleField::Domain => {
+            let pattern = pattern.strip_prefix("www.").unwrap_or(&pattern);
+            article
+                .source_url
+                .as_deref()
+                .and_then(source_domain)
+                .is_some_and(|domain| {
+                    domain == pattern || domain.ends_with(&format!(".{}", pattern))
+                })
+        }
+        TagRuleField::Language => article.language.as_deref().is_some_and(|language| {
+            let language = language.to_lowercase().replace('_', "-");
+            language == pattern || language.starts_with(&format!("{}-", pattern))
+        }),
+        TagRuleField::Channel => article
+            .channel
+            .as_deref()
+            .is_some_and(|channel| channel.trim().to_lowercase() == pattern),
+        TagRuleField::Keyword => {
+            article.title.to_lowercase().contains(&pattern)
+                || article.content.to_lowercase().contains(&pattern)
+        }
+    }
+}
+
+/// 为文章加上所有匹配规则的标签（已有的标签不重复添加），返回是否有新增
+pub fn apply_rules(rules: &[TagRule], article: &mut Article) -> bool {
+    let mut changed = false;
+    for rule in rules {
+        let tag = rule.tag.trim();
+        if tag.is_empty() || article.tags.iter().any(|t| t == tag) {
+            continue;
+        }
+        if rule_matches(rule, article) {
+            article.tags.push(tag.to_string());
+            changed = true;
+        }
+    }
+    changed
+}
@@ -0,0 +1,86 @@
+// 按行比较文本，用于文章修订历史的差异显示
+
+use crate::types::{DiffKind, DiffLine};
+
+/// 参与逐行比较的最大规模（行数乘积），超出时中间部分整体显示为删除 + 新增
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// 逐行比较 old 和 new：先去掉相同的首尾行，再对中间部分求最长公共子序列
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    let mut result: Vec<DiffLine> = old_lines[..prefix]
+        .iter()
+        .map(|line| line_of(DiffKind::Unchanged, line))
+        .collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        result.extend(old_mid.iter().map(|line| line_of(DiffKind::Removed, line)));
+        result.extend(new_mid.iter().map(|line| line_of(DiffKind::Added, line)));
+    } else {
+        result.extend(lcs_diff(old_mid, new_mid));
+    }
+
+    result.extend(
+        old_lines[old_lines.len() - suffix..]
+            .iter()
+            .map(|line| line_of(DiffKind::Unchanged, line)),
+    );
+    result
+}
+
+fn line_of(kind: DiffKind, text: &str) -> DiffLine {
+    DiffLine {
+        kind,
+        text: text.to_string(),
+    }
+}
+
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    // lengths[i][j]: old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            result.push(line_of(DiffKind::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            result.push(line_of(DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            result.push(line_of(DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|line| line_of(DiffKind::Removed, line)));
+    result.extend(new[j..].iter().map(|line| line_of(DiffKind::Added, line)));
+    result
+}
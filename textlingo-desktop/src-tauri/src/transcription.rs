@@ -0,0 +1,1026 @@
+// 字幕转录引擎
+// subtitle_extraction 负责音视频处理、分片与合并，具体的转录交给 TranscriptionEngine:
+// - gemini: Gemini 多模态音频转录（Google 直连或 OpenAI 兼容网关）
+// - openai_audio: OpenAI /audio/transcriptions (whisper-1 等)
+// - kimi_video: Kimi K2.5 视频理解（上传整段压缩视频，不分片）
+// - whisper_local: 本地 whisper 命令行
+// - plugin: 由插件执行转录
+// 新增引擎只需实现 TranscriptionEngine 并在 create_engine 中注册
+
+use crate::ai_service::AIService;
+use crate::i18n::{tr, tr_with};
+use crate::plugin_manager::get_plugin_execution_command;
+use crate::subtitle_extraction::parse_transcription_response;
+use crate::types::{
+    ChatContent, ChatMessage, ChatRequest, ContentPart, ModelConfig, TranscriptionResult,
+    TranscriptionSegment, TranscriptionSettings, VideoUrl,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::process::Command;
+
+// API 端点
+const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const OPENAI_AUDIO_TRANSCRIPTIONS_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const API_302AI_URL: &str = "https://api.302.ai/v1/chat/completions";
+const MOONSHOT_API_URL: &str = "https://api.moonshot.cn/v1/chat/completions";
+const GOOGLE_GEMINI_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// 云端引擎单次请求的音频长度（秒），更长的视频会按此分片
+pub const DEFAULT_CHUNK_SECONDS: f64 = 10.0 * 60.0;
+
+/// 相邻分片的默认重叠（秒）
+pub const DEFAULT_CHUNK_OVERLAP: f64 = 30.0;
+
+/// 默认同时转录的分片数
+pub const DEFAULT_CHUNK_CONCURRENCY: usize = 2;
+
+/// 设置中分片长度的范围（秒）：太短请求过多，太长超出上传大小限制
+const CHUNK_SECONDS_RANGE: (f64, f64) = (60.0, 30.0 * 60.0);
+
+/// 同时转录分片数的上限
+const MAX_CHUNK_CONCURRENCY: usize = 8;
+
+/// 长音频分片方案
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChunkOptions {
+    pub chunk_seconds: f64,
+    pub overlap: f64,
+    pub concurrency: usize,
+}
+
+impl ChunkOptions {
+    /// 相邻分片起点的间隔
+    pub fn step(&self) -> f64 {
+        self.chunk_seconds - self.overlap
+    }
+}
+
+/// 按设置调整引擎的分片方案；引擎整段处理（返回 None）时设置不生效
+/// 超出范围的取值被限制在范围内，重叠最多为分片长度的四分之一
+pub fn chunk_options(
+    engine_chunk_seconds: Option<f64>,
+    settings: &TranscriptionSettings,
+) -> Option<ChunkOptions> {
+    let default_seconds = engine_chunk_seconds?;
+    let chunk_seconds = settings
+        .chunk_seconds
+        .filter(|s| s.is_finite())
+        .map_or(default_seconds, |s| {
+            s.clamp(CHUNK_SECONDS_RANGE.0, CHUNK_SECONDS_RANGE.1)
+        });
+    let overlap = settings
+        .chunk_overlap_seconds
+        .filter(|s| s.is_finite())
+        .unwrap_or(DEFAULT_CHUNK_OVERLAP)
+        .clamp(0.0, chunk_seconds / 4.0);
+    let concurrency = settings
+        .chunk_concurrency
+        .unwrap_or(DEFAULT_CHUNK_CONCURRENCY)
+        .clamp(1, MAX_CHUNK_CONCURRENCY);
+    Some(ChunkOptions {
+        chunk_seconds,
+        overlap,
+        concurrency,
+    })
+}
+
+/// 可选的转录引擎 ID（"auto" 根据字幕转录模型自动选择）
+pub const TRANSCRIPTION_ENGINES: &[&str] = &[
+    "auto",
+    "gemini",
+    "openai_audio",
+    "kimi_video",
+    "whisper_local",
+    "plugin",
+];
+
+/// 引擎需要的媒体输入
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptionInput {
+    /// 从视频提取的 MP3 音频（可分片）
+    Audio,
+    /// 压缩后的整段视频
+    Video,
+}
+
+/// 转录引擎
+pub trait TranscriptionEngine: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    fn input(&self) -> TranscriptionInput {
+        TranscriptionInput::Audio
+    }
+
+    /// 单次可处理的最长音频（秒），超过时分片；None 表示整段交给引擎
+    fn chunk_seconds(&self) -> Option<f64> {
+        Some(DEFAULT_CHUNK_SECONDS)
+    }
+
+    /// 开始提取时额外显示的模式提示
+    fn mode_message(&self) -> Option<String> {
+        None
+    }
+
+    /// 转录阶段的进度提示
+    fn transcribe_message(&self) -> String {
+        tr("subtitle.transcribe")
+    }
+
+    /// 转录音频/视频文件，时间戳相对于文件开头（秒）
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>>;
+}
+
+fn is_local_provider(provider: &str) -> bool {
+    provider == "ollama" || provider == "lmstudio"
+}
+
+fn is_kimi_video_model(model: &ModelConfig) -> bool {
+    model.api_provider == "moonshot" && model.model.contains("k2.5")
+}
+
+/// OpenAI 的 whisper / transcribe 模型，以及兼容网关上的 whisper 模型（如 whisper-large-v3）
+fn is_openai_audio_model(model: &ModelConfig) -> bool {
+    let name = model.model.to_lowercase();
+    name.contains("whisper") || (model.api_provider == "openai" && name.contains("transcribe"))
+}
+
+/// 根据设置和字幕转录模型确定引擎 ID
+pub fn resolve_engine_id(
+    settings: &TranscriptionSettings,
+    model: Option<&ModelConfig>,
+) -> Result<&'static str, String> {
+    let requested = settings.engine.trim();
+    if requested.is_empty() || requested == "auto" {
+        let model = model.ok_or_else(|| tr("error.subtitle_model_unsupported"))?;
+        if is_local_provider(&model.api_provider) {
+            return Err(tr("error.subtitle_local_model"));
+        }
+        if is_kimi_video_model(model) {
+            return Ok("kimi_video");
+        }
+        if is_openai_audio_model(model) {
+            return Ok("openai_audio");
+        }
+
+        // 允许的 Gemini 或 Kimi 模型
+        let provider = model.api_provider.as_str();
+        let is_supported = model.model.contains("gemini")
+            || provider == "google"
+            || provider == "google-ai-studio"
+            || model.model.contains("kimi");
+        return if is_supported {
+            Ok("gemini")
+        } else {
+            Err(tr("error.subtitle_model_unsupported"))
+        };
+    }
+
+    TRANSCRIPTION_ENGINES
+        .iter()
+        .copied()
+        .find(|id| *id != "auto" && *id == requested)
+        .ok_or_else(|| {
+            tr_with(
+                "error.transcription_engine_unknown",
+                &[("engine", requested.to_string())],
+            )
+        })
+}
+
+/// 创建引擎；云端引擎使用字幕转录模型的 API 配置
+pub fn create_engine(
+    app: &AppHandle,
+    engine_id: &str,
+    settings: &TranscriptionSettings,
+    model: Option<&ModelConfig>,
+) -> Result<Box<dyn TranscriptionEngine>, String> {
+    let require_model = || {
+        model
+            .filter(|m| !is_local_provider(&m.api_provider))
+            .ok_or_else(|| tr("error.subtitle_local_model"))
+    };
+
+    // 除本地 whisper 外的引擎都需要联网
+    if engine_id != "whisper_local" {
+        crate::offline::check_online()?;
+    }
+
+    let engine: Box<dyn TranscriptionEngine> = match engine_id {
+        "gemini" => {
+            let model = require_model()?;
+            Box::new(GeminiAudioEngine {
+                provider: model.api_provider.clone(),
+                api_key: model.api_key.clone(),
+                model: model.model.clone(),
+                base_url: model.base_url.clone(),
+            })
+        }
+        "openai_audio" => {
+            let model = require_model()?;
+            Box::new(OpenAiAudioEngine {
+                api_key: model.api_key.clone(),
+                model: model.model.clone(),
+                base_url: model.base_url.clone(),
+            })
+        }
+        "kimi_video" => {
+            let model = require_model()?;
+            Box::new(KimiVideoEngine {
+                api_key: model.api_key.clone(),
+                model: model.model.clone(),
+            })
+        }
+        "whisper_local" => Box::new(WhisperLocalEngine {
+            command: whisper_command(settings),
+            model: whisper_model(settings),
+        }),
+        "plugin" => Box::new(PluginEngine {
+            app: app.clone(),
+            plugin_name: settings
+                .plugin_name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .ok_or_else(|| tr("error.transcription_plugin_missing"))?,
+        }),
+        other => {
+            return Err(tr_with(
+                "error.transcription_engine_unknown",
+                &[("engine", other.to_string())],
+            ))
+        }
+    };
+
+    Ok(engine)
+}
+
+/// 本地 whisper 命令行的可执行文件，未设置时为 PATH 中的 whisper
+pub fn whisper_command(settings: &TranscriptionSettings) -> String {
+    settings
+        .whisper_command
+        .clone()
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| "whisper".to_string())
+}
+
+/// 本地 whisper 使用的模型，未设置时为 small
+pub fn whisper_model(settings: &TranscriptionSettings) -> String {
+    settings
+        .whisper_model
+        .clone()
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| "small".to_string())
+}
+
+/// 单词级时间戳
+pub(crate) struct TimedWord {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// 没有分段时，单词间停顿超过该时长（秒）就另起一条字幕
+const WORD_PAUSE_SECONDS: f64 = 0.8;
+/// 由单词组合的字幕最长时长（秒）
+const MAX_WORD_GROUP_SECONDS: f64 = 6.0;
+
+/// 读取说话人标签：支持字符串（"A"、"SPEAKER_00"、"Speaker 1"）和数字编号，空值返回 None
+pub fn parse_speaker(value: &Value) -> Option<String> {
+    let label = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => format!("Speaker {}", n),
+        _ => return None,
+    };
+    (!label.is_empty()).then_some(label)
+}
+
+pub(crate) fn parse_words(value: &Value) -> Vec<TimedWord> {
+    value
+        .as_array()
+        .map(|words| {
+            words
+                .iter()
+                .filter_map(|w| {
+                    let text = w["word"].as_str().or_else(|| w["text"].as_str())?.trim();
+                    let (start, end) = (w["start"].as_f64()?, w["end"].as_f64()?);
+                    (!text.is_empty() && end >= start).then(|| TimedWord {
+                        start,
+                        end,
+                        text: text.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 用单词时间戳收紧分段起止时间（分段时间通常包含前后的静音）：取中点落在分段内的首尾单词
+fn snap_to_words(segments: &mut [TranscriptionSegment], words: &[TimedWord]) {
+    for segment in segments {
+        let (Some(start), Some(end)) = (segment.start_time, segment.end_time) else {
+            continue;
+        };
+        let mut inside = words.iter().filter(|w| {
+            let middle = (w.start + w.end) / 2.0;
+            middle >= start && middle <= end
+        });
+        if let Some(first) = inside.next() {
+            let last = inside.next_back().unwrap_or(first);
+            segment.start_time = Some(first.start);
+            segment.end_time = Some(last.end);
+        }
+    }
+}
+
+/// 中日文单词之间不加空格
+fn join_words(words: &[TimedWord]) -> String {
+    let is_cjk = |c: char| matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{9fff}');
+    let mut text = String::new();
+    for word in words {
+        let cjk_pair = text.ends_with(is_cjk) && word.text.starts_with(is_cjk);
+        if !(text.is_empty() || cjk_pair) {
+            text.push(' ');
+        }
+        text.push_str(&word.text);
+    }
+    text
+}
+
+/// 只有单词时间戳时按停顿和时长把单词组合成字幕
+fn group_words(words: &[TimedWord]) -> Vec<TranscriptionSegment> {
+    let mut groups: Vec<&[TimedWord]> = Vec::new();
+    let mut group_start = 0;
+    for i in 1..=words.len() {
+        let split = i == words.len()
+            || words[i].start - words[i - 1].end >= WORD_PAUSE_SECONDS
+            || words[i].end - words[group_start].start > MAX_WORD_GROUP_SECONDS;
+        if split {
+            groups.push(&words[group_start..i]);
+            group_start = i;
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .map(|group| TranscriptionSegment {
+            speaker: None,
+            content: join_words(group),
+            start_time: Some(group[0].start),
+            end_time: Some(group[group.len() - 1].end),
+        })
+        .collect()
+}
+
+/// 解析以秒为单位的分段结果（OpenAI verbose_json、whisper CLI 和插件输出共用）
+///
+/// 格式: `{"segments": [{"start": 0.0, "end": 2.5, "text": "...", "speaker": "A"}], "text": "..."}`
+/// speaker 为可选的说话人标签（OpenAI diarized_json、WhisperX 等会输出）
+/// 带单词时间戳时（OpenAI 在顶层 words，whisper CLI 在各分段的 words）用它修正分段时间；
+/// 只有单词没有分段时按停顿组合成字幕
+pub fn parse_timed_segments(value: &Value) -> TranscriptionResult {
+    let mut words = parse_words(&value["words"]);
+    if let Some(segments) = value["segments"].as_array() {
+        words.extend(segments.iter().flat_map(|seg| parse_words(&seg["words"])));
+    }
+
+    let mut segments: Vec<TranscriptionSegment> = value["segments"]
+        .as_array()
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|seg| {
+                    let text = seg["text"].as_str()?.trim();
+                    if text.is_empty() {
+                        return None;
+                    }
+                    Some(TranscriptionSegment {
+                        speaker: parse_speaker(&seg["speaker"]),
+                        content: text.to_string(),
+                        start_time: seg["start"].as_f64(),
+                        end_time: seg["end"].as_f64(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let full_text = value["text"].as_str().unwrap_or("").trim().to_string();
+
+    if segments.is_empty() {
+        segments = group_words(&words);
+    } else {
+        snap_to_words(&mut segments, &words);
+    }
+
+    // 没有分段信息时（如 json 格式响应），整段作为一条字幕
+    if segments.is_empty() && !full_text.is_empty() {
+        segments.push(TranscriptionSegment {
+            speaker: None,
+            content: full_text.clone(),
+            start_time: Some(0.0),
+            end_time: Some(value["duration"].as_f64().unwrap_or(0.0)),
+        });
+    }
+
+    TranscriptionResult {
+        segments,
+        full_text,
+    }
+}
+
+// ============================================================================
+// Gemini 音频转录
+// ============================================================================
+
+struct GeminiAudioEngine {
+    provider: String,
+    api_key: String,
+    model: String,
+    base_url: Option<String>,
+}
+
+impl TranscriptionEngine for GeminiAudioEngine {
+    fn id(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>> {
+        Box::pin(transcribe_audio_with_gemini(
+            media_path,
+            &self.provider,
+            &self.api_key,
+            &self.model,
+            self.base_url.as_deref(),
+        ))
+    }
+}
+
+// ============================================================================
+// OpenAI 音频转录 (/audio/transcriptions)
+// ============================================================================
+
+struct OpenAiAudioEngine {
+    api_key: String,
+    model: String,
+    base_url: Option<String>,
+}
+
+impl OpenAiAudioEngine {
+    fn endpoint(&self) -> String {
+        match self
+            .base_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+        {
+            Some(base) => {
+                let base = base.trim_end_matches('/');
+                let base = base.strip_suffix("/chat/completions").unwrap_or(base);
+                if base.ends_with("/audio/transcriptions") {
+                    base.to_string()
+                } else {
+                    format!("{}/audio/transcriptions", base)
+                }
+            }
+            None => OPENAI_AUDIO_TRANSCRIPTIONS_URL.to_string(),
+        }
+    }
+}
+
+impl TranscriptionEngine for OpenAiAudioEngine {
+    fn id(&self) -> &'static str {
+        "openai_audio"
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>> {
+        Box::pin(async move {
+            let audio_bytes = fs::read(media_path)
+                .map_err(|e| tr_with("error.media_read_failed", &[("error", e.to_string())]))?;
+            let file_part = reqwest::multipart::Part::bytes(audio_bytes)
+                .file_name("audio.mp3")
+                .mime_str("audio/mpeg")
+                .map_err(|e| tr_with("error.upload_build_failed", &[("error", e.to_string())]))?;
+
+            // whisper-1 支持 verbose_json 分段时间戳，gpt-4o-*-transcribe 只支持 json，
+            // 说话人分离模型（gpt-4o-transcribe-diarize）用 diarized_json 返回带说话人的分段
+            let diarize = self.model.contains("diarize");
+            let response_format = if diarize {
+                "diarized_json"
+            } else if self.model.contains("whisper") {
+                "verbose_json"
+            } else {
+                "json"
+            };
+            let form = reqwest::multipart::Form::new()
+                .text("model", self.model.clone())
+                .text("response_format", response_format);
+            let form = if diarize {
+                // 分离模型要求指定分块策略，且不支持单词时间戳
+                form.text("chunking_strategy", "auto")
+            } else {
+                // 同时请求单词时间戳，用于修正分段起止时间
+                form.text("timestamp_granularities[]", "segment")
+                    .text("timestamp_granularities[]", "word")
+            };
+            let form = form.part("file", file_part);
+
+            let response = Client::new()
+                .post(self.endpoint())
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .multipart(form)
+                .send()
+                .await
+                .map_err(|e| tr_with("error.api_request_failed", &[("error", e.to_string())]))?;
+
+            if !response.status().is_success() {
+                return Err(crate::ai_service::response_error("API", response).await);
+            }
+
+            let response_json: Value = response
+                .json()
+                .await
+                .map_err(|e| tr_with("error.api_response_invalid", &[("error", e.to_string())]))?;
+            Ok(parse_timed_segments(&response_json))
+        })
+    }
+}
+
+// ============================================================================
+// Kimi K2.5 视频理解
+// ============================================================================
+
+struct KimiVideoEngine {
+    api_key: String,
+    model: String,
+}
+
+impl TranscriptionEngine for KimiVideoEngine {
+    fn id(&self) -> &'static str {
+        "kimi_video"
+    }
+
+    fn input(&self) -> TranscriptionInput {
+        TranscriptionInput::Video
+    }
+
+    fn chunk_seconds(&self) -> Option<f64> {
+        None
+    }
+
+    fn mode_message(&self) -> Option<String> {
+        Some(tr("subtitle.kimi_mode"))
+    }
+
+    fn transcribe_message(&self) -> String {
+        tr("subtitle.kimi_analyze")
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>> {
+        Box::pin(async move {
+            let video_bytes = fs::read(media_path)
+                .map_err(|e| tr_with("error.media_read_failed", &[("error", e.to_string())]))?;
+
+            let video_size_mb = video_bytes.len() as f64 / 1024.0 / 1024.0;
+            println!(
+                "[SubtitleExtraction] 压缩后视频大小: {:.2} MB",
+                video_size_mb
+            );
+
+            // 获取视频扩展名用于 MIME 类型
+            let ext = media_path
+                .extension()
+                .and_then(|s| s.to_str())
+                .unwrap_or("mp4")
+                .to_lowercase();
+
+            // 构建 data URL: data:video/{ext};base64,{base64_data}
+            let video_data_url =
+                format!("data:video/{};base64,{}", ext, BASE64.encode(&video_bytes));
+
+            let prompt = r#"请分析视频中的语音内容，并生成带时间轴的字幕。
+严格按照以下 JSON 格式返回结果：
+{
+  "segments": [
+    {
+      "start": "MM:SS",
+      "end": "MM:SS",
+      "content": "字幕内容",
+      "speaker": "说话人"
+    }
+  ],
+  "full_text": "全文内容"
+}
+要求：
+1. 精确对应语音时间。
+2. 按句子或短语断句。
+3. 保持原语言，不要翻译。
+4. 忽略背景音和无意义语气词。
+5. 有多人说话时（访谈、播客、对话），speaker 标注说话人：能从内容得知姓名时用姓名，否则用 "Speaker 1"、"Speaker 2"，同一人前后保持一致；只有一人说话时 speaker 为 null。
+"#;
+
+            let ai_service = AIService::new(
+                self.api_key.clone(),
+                "moonshot".to_string(),
+                self.model.clone(),
+            );
+
+            let chat_request = ChatRequest {
+                model: self.model.clone(),
+                messages: vec![ChatMessage {
+                    role: "user".to_string(),
+                    content: ChatContent::Parts(vec![
+                        ContentPart {
+                            part_type: "video_url".to_string(),
+                            text: None,
+                            image_url: None,
+                            file_data: None,
+                            video_url: Some(VideoUrl {
+                                url: video_data_url, // 使用 Base64 data URL
+                            }),
+                        },
+                        ContentPart {
+                            part_type: "text".to_string(),
+                            text: Some(prompt.to_string()),
+                            image_url: None,
+                            file_data: None,
+                            video_url: None,
+                        },
+                    ]),
+                }],
+                temperature: Some(1.0), // Kimi 要求 temperature=1
+            };
+
+            let response = ai_service
+                .chat(chat_request)
+                .await
+                .map_err(|e| tr_with("error.kimi_analysis_failed", &[("error", e.to_string())]))?;
+
+            parse_transcription_response(&response.content)
+        })
+    }
+}
+
+// ============================================================================
+// 本地 whisper 命令行
+// ============================================================================
+
+struct WhisperLocalEngine {
+    command: String,
+    model: String,
+}
+
+impl TranscriptionEngine for WhisperLocalEngine {
+    fn id(&self) -> &'static str {
+        "whisper_local"
+    }
+
+    // 本地转录没有上传大小限制，整段处理
+    fn chunk_seconds(&self) -> Option<f64> {
+        None
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>> {
+        Box::pin(async move {
+            let output_dir = media_path
+                .parent()
+                .ok_or_else(|| tr("error.invalid_file_path"))?;
+            let stem = media_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| tr("error.invalid_file_path"))?;
+
+            let output = Command::new(&self.command)
+                .arg(media_path)
+                .args(["--model", &self.model, "--output_format", "json"])
+                .arg("--output_dir")
+                .arg(output_dir)
+                .kill_on_drop(true)
+                .output()
+                .await
+                .map_err(|e| {
+                    tr_with(
+                        "error.whisper_command",
+                        &[("command", self.command.clone()), ("error", e.to_string())],
+                    )
+                })?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(tr_with(
+                    "error.whisper_transcription_failed",
+                    &[("error", stderr.to_string())],
+                ));
+            }
+
+            let json_path = output_dir.join(format!("{}.json", stem));
+            let content = fs::read_to_string(&json_path).map_err(|e| {
+                tr_with(
+                    "error.whisper_output_unreadable",
+                    &[("error", e.to_string())],
+                )
+            })?;
+            let _ = fs::remove_file(&json_path);
+
+            let value: Value = serde_json::from_str(&content).map_err(|e| {
+                tr_with("error.whisper_output_invalid", &[("error", e.to_string())])
+            })?;
+            Ok(parse_timed_segments(&value))
+        })
+    }
+}
+
+// ============================================================================
+// 插件转录
+// ============================================================================
+
+/// 插件以音频路径作为最后一个参数调用，stdout 输出 parse_timed_segments 格式的 JSON
+struct PluginEngine {
+    app: AppHandle,
+    plugin_name: String,
+}
+
+impl TranscriptionEngine for PluginEngine {
+    fn id(&self) -> &'static str {
+        "plugin"
+    }
+
+    fn chunk_seconds(&self) -> Option<f64> {
+        None
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        media_path: &'a Path,
+    ) -> BoxFuture<'a, Result<TranscriptionResult, String>> {
+        Box::pin(async move {
+            let (cmd, args, plugin_dir) =
+                get_plugin_execution_command(&self.app, &self.plugin_name)?;
+
+            println!(
+                "[Plugin] Transcribing with {}: {} {:?}",
+                self.plugin_name, cmd, args
+            );
+
+            let output = Command::new(&cmd)
+                .args(&args)
+                .arg(media_path)
+                .current_dir(&plugin_dir)
+                .kill_on_drop(true)
+                .output()
+                .await
+                .map_err(|e| format!("Failed to execute plugin command '{}': {}", cmd, e))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("Plugin transcription failed: {}", stderr));
+            }
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let value: Value = serde_json::from_str(stdout.trim())
+                .map_err(|e| format!("Invalid plugin transcription output: {}", e))?;
+            Ok(parse_timed_segments(&value))
+        })
+    }
+}
+
+// ============================================================================
+// Gemini 音频转录请求
+// ============================================================================
+
+/// 使用 Gemini API 转录音频
+///
+/// 支持的 API 提供商:
+/// - openrouter: OpenRouter API (使用 input_audio 格式)
+/// - 302ai: 302.AI API (兼容 OpenAI 格式)
+/// - google: Google Gemini 直接 API
+async fn transcribe_audio_with_gemini(
+    audio_path: &Path,
+    provider: &str,
+    api_key: &str,
+    model: &str,
+    base_url: Option<&str>,
+) -> Result<TranscriptionResult, String> {
+    const MAX_RETRIES: u32 = 3;
+    let mut retry_count = 0;
+
+    loop {
+        // 读取并编码音频文件 (每次重试都重新读取可能没必要，但为了安全起见暂时不改这里)
+        let audio_bytes = fs::read(audio_path)
+            .map_err(|e| tr_with("error.media_read_failed", &[("error", e.to_string())]))?;
+
+        let audio_base64 = BASE64.encode(&audio_bytes);
+        let audio_size_mb = audio_bytes.len() as f64 / 1024.0 / 1024.0;
+        println!("[SubtitleExtraction] 音频文件大小: {:.2} MB", audio_size_mb);
+
+        // 注意：20MB 限制现在由分片提取算法处理，此处不再需要检查
+
+        // 转录提示词 - 强调时间戳精度和按句子断句
+        let transcription_prompt = r#"Transcribe this audio into text with precise timestamps. Return strictly in the following JSON format.
+
+Requirements:
+1. **Sentence-level segmentation**: Each segment contains exactly ONE complete sentence. Do NOT merge multiple sentences.
+2. Split at sentence-ending punctuation (periods, question marks, exclamation marks) or natural speech pauses.
+3. Each sentence should be roughly 5-30 characters/words. Never exceed 50.
+4. **Timestamp accuracy is critical**: start and end times MUST precisely match when the speech actually begins and ends in the audio. Listen carefully to the exact timing.
+5. Format: MM:SS (e.g., "01:23" for 1 minute 23 seconds). Both start and end are required.
+6. Keep the original language. Do NOT translate.
+7. Timestamps must be monotonically increasing — each segment's start must be >= the previous segment's end.
+8. **Speakers**: when more than one person speaks (interviews, podcasts, dialogues), set "speaker" to a label for who is speaking — their name if it is stated in the audio, otherwise "Speaker 1", "Speaker 2", ... Keep each person's label consistent throughout. Never split one sentence across speakers. If only one person speaks, use null.
+
+Return format:
+{
+  "segments": [
+    {
+      "start": "00:00",
+      "end": "00:03",
+      "content": "First sentence of the audio.",
+      "speaker": "Speaker 1"
+    },
+    {
+      "start": "00:03",
+      "end": "00:06",
+      "content": "Second sentence of the audio.",
+      "speaker": "Speaker 2"
+    }
+  ],
+  "full_text": "Full transcription text..."
+}
+
+IMPORTANT: Each segment = one sentence. Timestamps must be precise to the second.
+"#;
+
+        let client = Client::new();
+
+        // 根据提供商选择不同的 API 格式
+        let response = match provider {
+            "google" | "google-ai-studio" => {
+                // Google Gemini 直接 API
+                let url = format!(
+                    "{}/{}:generateContent?key={}",
+                    GOOGLE_GEMINI_URL,
+                    model.strip_prefix("models/").unwrap_or(model),
+                    api_key
+                );
+
+                let request_body = json!({
+                    "contents": [{
+                        "parts": [
+                            {
+                                "inline_data": {
+                                    "mime_type": "audio/mp3",
+                                    "data": audio_base64
+                                }
+                            },
+                            {
+                                "text": transcription_prompt
+                            }
+                        ]
+                    }],
+                    "generationConfig": {
+                        "response_mime_type": "application/json"
+                    }
+                });
+
+                client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&request_body)
+                    .send()
+                    .await
+                    .map_err(|e| tr_with("error.api_request_failed", &[("error", e.to_string())]))?
+            }
+            _ => {
+                // OpenAI 兼容格式：优先使用用户配置的 base_url，避免错误回退到固定网关
+                let api_url = if let Some(custom_base_url) =
+                    base_url.and_then(|url| (!url.trim().is_empty()).then_some(url))
+                {
+                    let trimmed = custom_base_url.trim_end_matches('/');
+                    if trimmed.ends_with("/chat/completions") {
+                        trimmed.to_string()
+                    } else {
+                        format!("{}/chat/completions", trimmed)
+                    }
+                } else {
+                    match provider {
+                        "openrouter" => OPENROUTER_API_URL.to_string(),
+                        "302ai" => API_302AI_URL.to_string(),
+                        "moonshot" => MOONSHOT_API_URL.to_string(),
+                        "openai" => OPENAI_API_URL.to_string(),
+                        "openai-compatible" => {
+                            return Err("openai-compatible provider requires base_url in settings"
+                                .to_string());
+                        }
+                        _ => {
+                            return Err(format!(
+                                "Unsupported provider '{}' for subtitle transcription without base_url",
+                                provider
+                            ));
+                        }
+                    }
+                };
+
+                // 使用 OpenAI 兼容的 input_audio 格式
+                let request_body = json!({
+                    "model": model,
+                    "messages": [{
+                        "role": "user",
+                        "content": [
+                            {
+                                "type": "input_audio",
+                                "input_audio": {
+                                    "data": audio_base64,
+                                    "format": "mp3"
+                                }
+                            },
+                            {
+                                "type": "text",
+                                "text": transcription_prompt
+                            }
+                        ]
+                    }],
+                    "temperature": 0.1
+                });
+
+                client
+                    .post(&api_url)
+                    .header("Authorization", format!("Bearer {}", api_key))
+                    .header("Content-Type", "application/json")
+                    .json(&request_body)
+                    .send()
+                    .await
+                    .map_err(|e| tr_with("error.api_request_failed", &[("error", e.to_string())]))?
+            }
+        };
+
+        if !response.status().is_success() {
+            return Err(crate::ai_service::response_error("API", response).await);
+        }
+
+        let response_json: Value = response
+            .json()
+            .await
+            .map_err(|e| tr_with("error.api_response_invalid", &[("error", e.to_string())]))?;
+
+        // 提取响应内容
+        let content = if provider == "google" || provider == "google-ai-studio" {
+            // Google API 响应格式
+            response_json["candidates"][0]["content"]["parts"][0]["text"]
+                .as_str()
+                .unwrap_or("")
+                .to_string()
+        } else {
+            // OpenAI 兼容格式
+            response_json["choices"][0]["message"]["content"]
+                .as_str()
+                .unwrap_or("")
+                .to_string()
+        };
+
+        // 解析转录结果
+        match parse_transcription_response(&content) {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                println!("[SubtitleExtraction] JSON 解析失败: {}", e);
+                println!("[SubtitleExtraction] 尝试解析的原始内容: {}", content);
+
+                retry_count += 1;
+                if retry_count >= MAX_RETRIES {
+                    // 最后一次尝试失败，如果是解析错误且内容不为空，可能是格式问题
+                    // 但如果内容为空，已经在 parse_transcription_response 中处理了
+                    return Err(tr_with(
+                        "error.retries_exhausted",
+                        &[("error", e.to_string())],
+                    ));
+                }
+
+                println!(
+                    "[SubtitleExtraction] 将进行第 {} 次重试...",
+                    retry_count + 1
+                );
+                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                continue;
+            }
+        }
+    } // end loop
+}
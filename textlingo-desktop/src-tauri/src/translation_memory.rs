@@ -0,0 +1,76 @@
+// 翻译记忆
+// 以 (原文, 目标语言, 模型) 为键保存整篇翻译的结果，重新导入或重新分段的文章
+// 中相同的句子直接复用，不再调用 API
+
+use crate::storage::{load_translation_memory, save_translation_memory};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationMemoryEntry {
+    pub source_text: String,
+    pub target_language: String,
+    pub model: String,
+    pub translation: String,
+    pub created_at: String,
+}
+
+/// 计算翻译记忆的键（FNV-1a 64 位哈希，跨版本稳定）
+///
+/// 原文首尾空白不参与计算；哈希冲突由读取时比对原文排除
+pub fn memory_key(text: &str, target_language: &str, model: &str) -> String {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET;
+    for part in [text.trim(), target_language, model] {
+        // 0x1f 作为分隔符，避免不同拆分产生相同输入
+        for byte in part.bytes().chain(std::iter::once(0x1f)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// 查找已保存的翻译
+pub fn lookup(
+    app_handle: &AppHandle,
+    text: &str,
+    target_language: &str,
+    model: &str,
+) -> Option<String> {
+    let key = memory_key(text, target_language, model);
+    let json = load_translation_memory(app_handle, &key).ok()??;
+    let entry: TranslationMemoryEntry = serde_json::from_str(&json).ok()?;
+
+    (entry.source_text == text.trim()
+        && entry.target_language == target_language
+        && entry.model == model)
+        .then_some(entry.translation)
+}
+
+/// 保存一条翻译（失败只记录日志，不影响翻译流程）
+pub fn remember(
+    app_handle: &AppHandle,
+    text: &str,
+    target_language: &str,
+    model: &str,
+    translation: &str,
+) {
+    let entry = TranslationMemoryEntry {
+        source_text: text.trim().to_string(),
+        target_language: target_language.to_string(),
+        model: model.to_string(),
+        translation: translation.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let result = serde_json::to_string(&entry)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            save_translation_memory(app_handle, &memory_key(text, target_language, model), &json)
+        });
+    if let Err(e) = result {
+        eprintln!("[TranslationMemory] Failed to save entry: {}", e);
+    }
+}
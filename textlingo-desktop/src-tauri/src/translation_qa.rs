@@ -0,0 +1,67 @@
+// 译文质量抽查：整篇翻译完成后随机抽取几句，让模型对照原文打分，
+// 平均分过低或低分句过多时给出警告，便于在学习之前发现廉价模型的译文不可用
+
+use crate::types::{TranslationQaIssue, TranslationQaReport};
+
+/// 低于等于该分数的句子列为问题句
+pub const LOW_SCORE: u8 = 5;
+
+/// 平均分低于该值时警告
+pub const WARNING_AVERAGE: f64 = 6.5;
+
+/// 从 len 个候选中不重复地随机抽取 size 个下标（按原顺序返回），seed 相同时结果相同
+pub fn sample_indices(len: usize, size: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    let size = size.min(len);
+    // xorshift64，种子为 0 时无法产生随机数
+    let mut state = seed.max(1);
+    for i in 0..size {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = i + (state % (len - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    let mut sampled = indices[..size].to_vec();
+    sampled.sort_unstable();
+    sampled
+}
+
+/// 汇总模型给出的 (段落 ID, 分数, 问题)；没有任何评分时返回 None
+/// 平均分低于 WARNING_AVERAGE 或三分之一以上的句子为低分时警告
+pub fn build_report(
+    article_id: &str,
+    target_language: &str,
+    model: &str,
+    grades: &[(String, u8, String)],
+    checked_at: &str,
+) -> Option<TranslationQaReport> {
+    if grades.is_empty() {
+        return None;
+    }
+    let average_score = grades
+        .iter()
+        .map(|(_, score, _)| *score as f64)
+        .sum::<f64>()
+        / grades.len() as f64;
+    let issues: Vec<TranslationQaIssue> = grades
+        .iter()
+        .filter(|(_, score, _)| *score <= LOW_SCORE)
+        .map(|(segment_id, score, issue)| TranslationQaIssue {
+            segment_id: segment_id.clone(),
+            score: *score,
+            issue: issue.clone(),
+        })
+        .collect();
+    let warning = average_score < WARNING_AVERAGE || issues.len() * 3 >= grades.len();
+    Some(TranslationQaReport {
+        article_id: article_id.to_string(),
+        target_language: target_language.to_string(),
+        model: model.to_string(),
+        sampled: grades.len(),
+        average_score: (average_score * 10.0).round() / 10.0,
+        issues,
+        warning,
+        checked_at: checked_at.to_string(),
+    })
+}
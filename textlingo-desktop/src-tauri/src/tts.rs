@@ -0,0 +1,119 @@
+// 朗读模式：逐句合成语音（OpenAI 兼容 /audio/speech），缓存到本地并通过资源服务器提供
+// 音频文件按 (文本, 模型, 音色, 语速) 的哈希命名，相同句子在不同文章间共用同一个文件
+
+use reqwest::Client;
+use serde_json::json;
+use std::path::Path;
+
+use crate::i18n::{tr, tr_with};
+use crate::types::{ArticleSegment, SegmentBlock, TtsPlaylist};
+use crate::video_server::VIDEO_SERVER_PORT;
+
+/// 音频目录: app_data_dir/tts_audio，播放清单为 {article_id}.json
+pub const TTS_AUDIO_DIR: &str = "tts_audio";
+
+const OPENAI_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+
+/// 单句文本上限（/audio/speech 限制 4096 字符）
+pub const MAX_SPEECH_CHARS: usize = 4096;
+
+/// FNV-1a 64 位哈希：结果与 Rust 版本无关，升级后缓存仍可命中
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// 缓存音频的文件名
+pub fn audio_file_name(text: &str, model: &str, voice: &str, speed: f32) -> String {
+    let key = format!(
+        "{}\u{1f}{}\u{1f}{:.2}\u{1f}{}",
+        model,
+        voice,
+        speed,
+        text.trim()
+    );
+    format!("{:016x}.mp3", fnv1a(key.as_bytes()))
+}
+
+/// 需要朗读的段落：跳过插图、代码块和空段落
+pub fn is_speakable(segment: &ArticleSegment) -> bool {
+    segment.image_url.is_none()
+        && segment.block != Some(SegmentBlock::Code)
+        && segment.text.chars().any(char::is_alphanumeric)
+}
+
+/// 资源服务器上的音频或清单地址
+pub fn audio_url(filename: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/tts/{}",
+        VIDEO_SERVER_PORT,
+        urlencoding::encode(filename)
+    )
+}
+
+/// 用户配置了 base_url 时使用 {base}/audio/speech，否则使用 OpenAI 官方地址
+pub fn speech_endpoint(base_url: Option<&str>) -> String {
+    match base_url.map(str::trim).filter(|url| !url.is_empty()) {
+        Some(base) => {
+            let base = base.trim_end_matches('/');
+            let base = base.strip_suffix("/chat/completions").unwrap_or(base);
+            if base.ends_with("/audio/speech") {
+                base.to_string()
+            } else {
+                format!("{}/audio/speech", base)
+            }
+        }
+        None => OPENAI_SPEECH_URL.to_string(),
+    }
+}
+
+/// 合成一句语音，返回 MP3 数据
+pub async fn synthesize(
+    api_key: &str,
+    base_url: Option<&str>,
+    model: &str,
+    voice: &str,
+    speed: f32,
+    text: &str,
+) -> Result<Vec<u8>, String> {
+    let input: String = text.trim().chars().take(MAX_SPEECH_CHARS).collect();
+    let response = Client::new()
+        .post(speech_endpoint(base_url))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&json!({
+            "model": model,
+            "voice": voice,
+            "input": input,
+            "speed": speed,
+            "response_format": "mp3",
+        }))
+        .send()
+        .await
+        .map_err(|e| tr_with("error.api_request_failed", &[("error", e.to_string())]))?;
+
+    if !response.status().is_success() {
+        return Err(crate::ai_service::response_error("API", response).await);
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| tr_with("error.tts_audio_read_failed", &[("error", e.to_string())]))?;
+    if bytes.is_empty() {
+        return Err(tr("error.tts_empty_audio"));
+    }
+    Ok(bytes.to_vec())
+}
+
+/// 写入播放清单，返回清单文件名
+pub fn save_playlist(app_data_dir: &Path, playlist: &TtsPlaylist) -> Result<String, String> {
+    let dir = app_data_dir.join(TTS_AUDIO_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create TTS dir: {}", e))?;
+    let filename = format!("{}.json", playlist.article_id);
+    let json = serde_json::to_string_pretty(playlist)
+        .map_err(|e| format!("Failed to serialize playlist: {}", e))?;
+    std::fs::write(dir.join(&filename), json)
+        .map_err(|e| format!("Failed to write playlist: {}", e))?;
+    Ok(filename)
+}
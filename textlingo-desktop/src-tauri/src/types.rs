@@ -31,6 +31,32 @@ impl ModelConfig {
     }
 }
 
+/// 模型元数据（目前来自 OpenRouter 的模型列表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub id: String,
+    pub name: String,
+    /// 上下文长度（token）
+    pub context_length: Option<u64>,
+    pub max_completion_tokens: Option<u64>,
+    /// 输入价格（美元/token）
+    pub prompt_price: Option<f64>,
+    /// 输出价格（美元/token）
+    pub completion_price: Option<f64>,
+}
+
+/// 发送请求前的估算结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestEstimate {
+    pub model: String,
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub context_length: Option<u64>,
+    pub exceeds_context: bool,
+    /// 估算费用（美元），没有价格信息时为 None
+    pub estimated_cost: Option<f64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     /// Whether onboarding has been completed at least once
@@ -59,6 +85,56 @@ pub struct AppConfig {
     /// Daily limit for review cards in SRS
     #[serde(default = "default_srs_daily_review_limit")]
     pub srs_daily_review_limit: i32,
+    /// Chrome/Chromium/Edge executable used to render JavaScript-heavy pages
+    /// when static extraction finds no content (None disables the fallback)
+    #[serde(default)]
+    pub headless_browser_path: Option<String>,
+    /// Model config overrides per AI task (unset tasks use the active model)
+    #[serde(default)]
+    pub task_models: TaskModelRouting,
+    /// Subtitle transcription engine selection and local engine settings
+    #[serde(default)]
+    pub transcription: TranscriptionSettings,
+    /// Language currently being studied (e.g. "ja", "fr"); new articles,
+    /// vocabulary and word packs are tagged with it
+    #[serde(default)]
+    pub active_learning_language: Option<String>,
+    /// Folder watched for new files to import automatically (e.g. a synced folder)
+    #[serde(default)]
+    pub watch_folder: Option<String>,
+    /// 按句分段时单个段落的最大字符数，超出的长句在逗号、连词处拆开；None 表示不限制
+    #[serde(default)]
+    pub max_segment_chars: Option<usize>,
+    /// Text-to-speech settings for reading-aloud playlists
+    #[serde(default)]
+    pub tts: TtsSettings,
+    /// Distinct source articles a word must be answered correctly in before it
+    /// counts as known (None only requires the SRS interval)
+    #[serde(default)]
+    pub known_min_sources: Option<usize>,
+    /// How to title pasted articles created without a title
+    #[serde(default)]
+    pub title_generation: TitleGeneration,
+    /// Pre-request explanations for the next few segments while reading, so
+    /// tapping "explain" answers from the article instead of waiting on the model
+    #[serde(default)]
+    pub explanation_prefetch: bool,
+    /// Segments spot-checked by the model after a bulk translation finishes,
+    /// to warn when the translation model produces poor output (0 disables)
+    #[serde(default = "default_translation_qa_sample")]
+    pub translation_qa_sample: usize,
+    /// Plugins used as sentence segmenters, keyed by language code
+    /// (e.g. "ja" -> a MeCab plugin); other languages use the built-in rules
+    #[serde(default)]
+    pub segmentation_plugins: std::collections::BTreeMap<String, String>,
+    /// Browser whose cookies yt-dlp uses for restricted videos, in yt-dlp's
+    /// --cookies-from-browser form (e.g. "firefox", "chrome:Profile 1")
+    #[serde(default)]
+    pub ytdlp_cookies_from_browser: Option<String>,
+    /// Netscape-format cookies.txt passed to yt-dlp; takes precedence over
+    /// ytdlp_cookies_from_browser
+    #[serde(default)]
+    pub ytdlp_cookies_file: Option<String>,
 }
 
 impl Default for AppConfig {
@@ -73,6 +149,20 @@ impl Default for AppConfig {
             auth_token: None,
             srs_daily_new_limit: default_srs_daily_new_limit(),
             srs_daily_review_limit: default_srs_daily_review_limit(),
+            headless_browser_path: None,
+            task_models: TaskModelRouting::default(),
+            transcription: TranscriptionSettings::default(),
+            active_learning_language: None,
+            watch_folder: None,
+            max_segment_chars: None,
+            tts: TtsSettings::default(),
+            known_min_sources: None,
+            title_generation: TitleGeneration::default(),
+            explanation_prefetch: false,
+            translation_qa_sample: default_translation_qa_sample(),
+            segmentation_plugins: Default::default(),
+            ytdlp_cookies_from_browser: None,
+            ytdlp_cookies_file: None,
         }
     }
 }
@@ -87,10 +177,351 @@ impl AppConfig {
         }
     }
 
+    /// Segmentation plugin configured for a language; "zh-CN" falls back to
+    /// an entry for "zh"
+    pub fn segmentation_plugin(&self, language: Option<&str>) -> Option<&str> {
+        let language = language?.trim().to_lowercase();
+        let primary = language.split(['-', '_']).next().unwrap_or_default();
+        let find = |code: &str| {
+            self.segmentation_plugins
+                .iter()
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case(code))
+                .map(|(_, plugin)| plugin.trim())
+                .filter(|plugin| !plugin.is_empty())
+        };
+        find(&language).or_else(|| find(primary))
+    }
+
     /// Get a model config by ID
     pub fn get_config(&self, id: &str) -> Option<&ModelConfig> {
         self.model_configs.iter().find(|c| c.id == id)
     }
+
+    /// Get the model config routed to a task, falling back to the active config
+    /// (also when the routed config has been deleted)
+    pub fn get_config_for_task(&self, task: AiTask) -> Option<&ModelConfig> {
+        self.task_models
+            .model_id(task)
+            .and_then(|id| self.get_config(id))
+            .or_else(|| self.get_active_config())
+    }
+}
+
+/// AI tasks that can be assigned their own model config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AiTask {
+    /// Whole-article batch translation
+    BatchTranslation,
+    /// Per-segment translation + explanation
+    SegmentExplanation,
+    /// Subtitle extraction from audio/video
+    SubtitleTranscription,
+    /// Chat and streaming chat
+    Chat,
+    /// Review pass of thorough article translation
+    TranslationReview,
+}
+
+impl AiTask {
+    pub const ALL: [AiTask; 5] = [
+        AiTask::BatchTranslation,
+        AiTask::SegmentExplanation,
+        AiTask::SubtitleTranscription,
+        AiTask::Chat,
+        AiTask::TranslationReview,
+    ];
+}
+
+/// Model config ID per task; None means "use the active model"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskModelRouting {
+    #[serde(default)]
+    pub batch_translation: Option<String>,
+    #[serde(default)]
+    pub segment_explanation: Option<String>,
+    #[serde(default)]
+    pub subtitle_transcription: Option<String>,
+    #[serde(default)]
+    pub chat: Option<String>,
+    #[serde(default)]
+    pub translation_review: Option<String>,
+}
+
+impl TaskModelRouting {
+    pub fn model_id(&self, task: AiTask) -> Option<&str> {
+        let id = match task {
+            AiTask::BatchTranslation => &self.batch_translation,
+            AiTask::SegmentExplanation => &self.segment_explanation,
+            AiTask::SubtitleTranscription => &self.subtitle_transcription,
+            AiTask::Chat => &self.chat,
+            AiTask::TranslationReview => &self.translation_review,
+        };
+        id.as_deref().filter(|id| !id.is_empty())
+    }
+}
+
+/// Subtitle transcription engine settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionSettings {
+    /// Engine ID ("auto" picks one from the subtitle transcription model)
+    #[serde(default = "default_transcription_engine")]
+    pub engine: String,
+    /// Executable of the local whisper CLI (defaults to "whisper" on PATH)
+    #[serde(default)]
+    pub whisper_command: Option<String>,
+    /// Model name passed to the local whisper CLI (defaults to "small")
+    #[serde(default)]
+    pub whisper_model: Option<String>,
+    /// Plugin used by the "plugin" engine
+    #[serde(default)]
+    pub plugin_name: Option<String>,
+    /// Seconds of audio per request when long media is split (engine default when None)
+    #[serde(default)]
+    pub chunk_seconds: Option<f64>,
+    /// Seconds shared by neighbouring chunks so words at the cut are not lost
+    #[serde(default)]
+    pub chunk_overlap_seconds: Option<f64>,
+    /// Chunks transcribed at the same time (lower it for strict rate limits)
+    #[serde(default)]
+    pub chunk_concurrency: Option<usize>,
+    /// Snap extracted subtitle times to word boundaries found by the local
+    /// whisper CLI (word timestamps) after transcription
+    #[serde(default)]
+    pub forced_alignment: bool,
+}
+
+impl Default for TranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            engine: default_transcription_engine(),
+            whisper_command: None,
+            whisper_model: None,
+            plugin_name: None,
+            chunk_seconds: None,
+            chunk_overlap_seconds: None,
+            chunk_concurrency: None,
+            forced_alignment: false,
+        }
+    }
+}
+
+/// Text-to-speech settings (OpenAI-compatible /audio/speech)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TtsSettings {
+    /// Model config whose API key and base URL are used (None uses the active model config)
+    #[serde(default)]
+    pub model_config_id: Option<String>,
+    /// Speech model name
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    /// Default voice
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    /// Default playback speed (0.25 - 4.0)
+    #[serde(default = "default_tts_speed")]
+    pub speed: f32,
+}
+
+impl Default for TtsSettings {
+    fn default() -> Self {
+        Self {
+            model_config_id: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            speed: default_tts_speed(),
+        }
+    }
+}
+
+fn default_tts_model() -> String {
+    "tts-1".to_string()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".to_string()
+}
+
+fn default_tts_speed() -> f32 {
+    1.0
+}
+
+/// 朗读播放清单中的一句
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TtsPlaylistItem {
+    pub segment_id: String,
+    pub order: i32,
+    pub text: String,
+    /// 资源服务器上的音频地址
+    pub audio_url: String,
+    pub is_new_paragraph: bool,
+}
+
+/// YouTube 字幕来源：作者上传的字幕或自动生成的字幕
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleTrackKind {
+    Manual,
+    Auto,
+}
+
+/// YouTube 视频可下载的字幕轨（yt-dlp 列出）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YoutubeSubtitleTrack {
+    /// 语言代码，如 "en"、"ja"、"zh-Hans"
+    pub language: String,
+    /// 显示名称，如 "English (auto-generated)"
+    pub name: Option<String>,
+    pub kind: SubtitleTrackKind,
+}
+
+/// 导入 YouTube 视频时下载哪些字幕；未指定时沿用默认的英文、中文自动字幕
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YoutubeSubtitleOptions {
+    /// 按优先顺序排列的语言代码，第一个下载到的用作文章段落
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// 下载作者上传的字幕
+    #[serde(default)]
+    pub manual: bool,
+    /// 下载自动生成的字幕（同一语言有上传字幕时 yt-dlp 优先使用上传字幕）
+    #[serde(default = "default_true")]
+    pub auto: bool,
+}
+
+impl Default for YoutubeSubtitleOptions {
+    fn default() -> Self {
+        Self {
+            languages: vec![
+                "en".to_string(),
+                "zh-Hans".to_string(),
+                "zh-Hant".to_string(),
+            ],
+            manual: false,
+            auto: true,
+        }
+    }
+}
+
+/// YouTube 下载清晰度，磁盘空间或带宽有限时可选较小的下载
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum YoutubeQuality {
+    #[serde(rename = "360p")]
+    P360,
+    /// 默认：预合并的 720p MP4，无需 FFmpeg
+    #[default]
+    #[serde(rename = "720p")]
+    P720,
+    /// 1080p 只有分离的视频和音频流，需要 FFmpeg 合并
+    #[serde(rename = "1080p")]
+    P1080,
+    /// 只下载音频（m4a），适合播客和讲座，体积约为视频的十分之一，转录也更快
+    #[serde(rename = "audio_only")]
+    AudioOnly,
+    /// 只下载音频并转为 mp3，需要 FFmpeg
+    #[serde(rename = "audio_mp3")]
+    AudioMp3,
+}
+
+impl YoutubeQuality {
+    pub fn is_audio_only(self) -> bool {
+        matches!(self, YoutubeQuality::AudioOnly | YoutubeQuality::AudioMp3)
+    }
+}
+
+/// 频道中的一个视频（yt-dlp 平铺列表，最新的在前）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelVideo {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// 订阅的 YouTube 频道，检查时导入最新 limit 个视频中尚未导入的
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelSubscription {
+    pub id: String,
+    pub url: String,
+    /// 频道名称（首次检查后填写）
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 每次检查的最新视频数
+    pub limit: usize,
+    #[serde(default)]
+    pub subtitles: YoutubeSubtitleOptions,
+    #[serde(default)]
+    pub quality: YoutubeQuality,
+    /// 已导入过的视频 ID，文章被删除后也不会重新导入
+    #[serde(default)]
+    pub seen_video_ids: Vec<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub last_checked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedChannelVideo {
+    pub video_id: String,
+    pub title: String,
+    pub error: String,
+}
+
+/// 检查频道新视频的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelImportResult {
+    pub subscription: ChannelSubscription,
+    /// 本次新导入的文章
+    pub imported: Vec<Article>,
+    /// 已导入过而跳过的视频数
+    pub skipped: usize,
+    pub failed: Vec<FailedChannelVideo>,
+}
+
+/// 本地视频内嵌的字幕轨（ffmpeg 探测得到）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedSubtitleTrack {
+    /// ffmpeg 的流序号（-map 0:{stream_index}）
+    pub stream_index: u32,
+    /// 轨道语言（ISO 639-2，如 "eng"、"jpn"），未标注时为 None
+    pub language: Option<String>,
+    /// 编码：mov_text、subrip、ass、hdmv_pgs_subtitle 等
+    pub codec: String,
+    pub title: Option<String>,
+    pub default: bool,
+    pub forced: bool,
+    /// 文本字幕可直接提取，图形字幕（PGS、VobSub）需要 OCR，无法提取
+    pub text_based: bool,
+}
+
+/// 媒体文件的波形峰值，播放器进度条据此绘制波形
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveformPeaks {
+    /// 每秒的峰值个数，第 i 个峰值对应 i / peaks_per_second 秒起的一小段
+    pub peaks_per_second: u32,
+    /// 音轨时长（秒）
+    pub duration: f64,
+    /// 各段的最大振幅，范围 0-1
+    pub peaks: Vec<f32>,
+}
+
+/// 文章的朗读播放清单：按段落顺序排列，阅读器播放时按 segment_id 高亮当前句
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TtsPlaylist {
+    pub article_id: String,
+    pub model: String,
+    pub voice: String,
+    pub speed: f32,
+    pub items: Vec<TtsPlaylistItem>,
+    /// 合成失败而未列入清单的段落数
+    pub failed: usize,
+    /// 资源服务器上的清单地址
+    #[serde(default)]
+    pub manifest_url: Option<String>,
+    pub generated_at: String,
+}
+
+fn default_transcription_engine() -> String {
+    "auto".to_string()
 }
 
 fn default_interface_language() -> String {
@@ -121,12 +552,57 @@ fn default_zero() -> i32 {
     0
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_translation_qa_sample() -> usize {
+    6
+}
+
+/// 文章修订：修改内容或重新分段前保存的快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleRevision {
+    pub id: String,
+    pub article_id: String,
+    pub title: String,
+    pub content: String,
+    pub segments: Vec<ArticleSegment>,
+    pub created_at: String,
+}
+
+/// 修订列表项（不含内容和分段）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleRevisionInfo {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    /// 内容字符数
+    pub content_length: usize,
+    pub segment_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+/// 按行比较的一行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Article {
     pub id: String,
     pub title: String,
     pub content: String,
-    /// 素材来源类型: "web" | "article" | "youtube" | "local_video" | "audio" | "book"
+    /// 素材来源类型: "web" | "article" | "markdown" | "docx" | "subtitle" | "youtube" | "local_video" | "audio" | "book"
     #[serde(default)]
     pub source_type: Option<String>,
     pub source_url: Option<String>,
@@ -137,12 +613,127 @@ pub struct Article {
     /// 书籍类型: "epub" | "txt" | "pdf"
     #[serde(default)]
     pub book_type: Option<String>,
+    /// 学习语言（素材本身的语言，如 "ja"、"fr"），旧数据为 None
+    #[serde(default)]
+    pub language: Option<String>,
     pub created_at: String,
     pub translated: bool,
+    /// 分段 translation 字段对应的语言（首次整篇翻译时记录）
+    #[serde(default)]
+    pub translation_language: Option<String>,
+    /// 创建或重新分段时指定的分段方式，None 表示默认的按句分段
+    #[serde(default)]
+    pub segmentation: Option<SegmentationMode>,
+    /// 整篇分析结果，键为分析类型（summary / key_points / vocabulary / grammar / full）
+    #[serde(default)]
+    pub analyses: std::collections::BTreeMap<String, ArticleAnalysis>,
+    /// YouTube 频道名（导入时记录，用于自动标签规则）
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// 标签（合集），可由自动标签规则在导入时添加
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 下载导入的视频/音频的元数据（缩略图、时长等），其他文章为 None
+    #[serde(default)]
+    pub media_info: Option<MediaInfo>,
     #[serde(default)]
     pub segments: Vec<ArticleSegment>,
 }
 
+/// yt-dlp 提供的媒体元数据，供资料库显示缩略图、作者和时长
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MediaInfo {
+    /// 缩略图文件（app_data_dir/thumbnails 下）
+    #[serde(default)]
+    pub thumbnail_path: Option<String>,
+    /// 资源服务器上的缩略图地址
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    #[serde(default)]
+    pub uploader: Option<String>,
+    /// 时长（秒）
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// 上传日期，YYYY-MM-DD
+    #[serde(default)]
+    pub upload_date: Option<String>,
+    /// 视频的原始语言代码（网站提供时）
+    #[serde(default)]
+    pub original_language: Option<String>,
+}
+
+/// 保存在文章上的整篇分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleAnalysis {
+    /// summary / key_points / vocabulary / grammar / full
+    pub analysis_type: String,
+    pub result: String,
+    /// 长文分块分析时的块数，未分块为 1；由前端直接保存的结果为 0
+    #[serde(default)]
+    pub chunk_count: usize,
+    pub model: String,
+    pub created_at: String,
+}
+
+/// 文章分段方式（前端传 {"mode": "fixed_length", "max_chars": 120} 这样的对象）
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SegmentationMode {
+    /// 按句子切分，每行视为一个段落
+    #[default]
+    Sentence,
+    /// 以空行分隔的段落为一段
+    Paragraph,
+    /// 每行一段（歌词、诗歌、对话稿）
+    Line,
+    /// 把相邻句子合并成不超过 max_chars 个字符的段
+    FixedLength { max_chars: usize },
+    /// 不切分，整篇为一段（插图仍单独成段）
+    NoSplit,
+    /// 由模型按意思边界（对话轮次、列表项等）组合成学习单元，适合没有标点的转写稿；
+    /// 无法调用模型的场合按句切分
+    Semantic,
+}
+
+/// 新建文章未填写标题时的标题生成方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleGeneration {
+    /// 取正文第一句
+    #[default]
+    FirstSentence,
+    /// 由模型概括，调用失败时退回第一句
+    Ai,
+}
+
+/// 可撤销的破坏性操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UndoActionKind {
+    DeleteVocabulary,
+    DeleteWordPack,
+    DeleteBookmark,
+    DeleteSegment,
+}
+
+/// 撤销日志中的一条记录，供前端显示"已删除 xxx，撤销"提示
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoActionInfo {
+    pub id: String,
+    pub kind: UndoActionKind,
+    /// 被删除对象的名称：单词、单词包名、书签标题或段落开头
+    pub label: String,
+    pub created_at: String,
+}
+
+/// 手动调整段落顺序时的移动方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleSegment {
     pub id: String,
@@ -162,6 +753,129 @@ pub struct ArticleSegment {
     /// 是否是新段落开始（true则另起一行显示，false则紧跟上一段显示）
     #[serde(default)]
     pub is_new_paragraph: bool,
+    /// 图片段落的图片地址（网页导入保留的插图），此时 text 为图片的 alt 文本
+    #[serde(default)]
+    pub image_url: Option<String>,
+    /// 其他目标语言的译文（语言代码 -> 译文），主译文仍在 translation 字段
+    #[serde(default)]
+    pub translations: std::collections::BTreeMap<String, String>,
+    /// 最近一次翻译或讲解流程中该段的处理状态，供阅读界面显示进度和失败原因
+    #[serde(default)]
+    pub processing_status: Option<SegmentProcessing>,
+    /// 段落在原文档中的结构（Markdown 导入时记录），阅读器据此渲染标题、列表、引用和代码
+    #[serde(default)]
+    pub block: Option<SegmentBlock>,
+    /// 说话人标签（转录时识别出的 "Speaker 1" 或姓名），访谈、播客等多人音频才有
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+/// Markdown 文档中的块类型；普通正文段落不记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SegmentBlock {
+    /// 标题，level 为 1-6，标出章节的开始
+    Heading {
+        level: u8,
+    },
+    /// 列表项，depth 为缩进层级（从 0 开始）
+    ListItem {
+        ordered: bool,
+        depth: u8,
+    },
+    Quote,
+    /// 代码块，整块作为一段不分句
+    Code,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingTask {
+    Translation,
+    Explanation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStatus {
+    Pending,
+    Processing,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentProcessing {
+    pub task: ProcessingTask,
+    pub status: ProcessingStatus,
+    /// 失败原因（仅 Failed）
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ArticleSegment {
+    /// 更新处理状态
+    pub fn set_processing(
+        &mut self,
+        task: ProcessingTask,
+        status: ProcessingStatus,
+        error: Option<String>,
+    ) {
+        self.processing_status = Some(SegmentProcessing {
+            task,
+            status,
+            error,
+        });
+    }
+
+    /// 获取指定语言的译文；language 为文章主译文语言时返回 translation 字段
+    pub fn translation_for(&self, language: &str, primary_language: Option<&str>) -> Option<&str> {
+        if primary_language.is_none_or(|primary| primary.eq_ignore_ascii_case(language)) {
+            self.translation.as_deref()
+        } else {
+            self.translations.get(language).map(String::as_str)
+        }
+    }
+
+    /// 有说话人时在文本前加上 "说话人: "，文本已带该前缀时原样返回
+    pub fn with_speaker_prefix(&self, text: &str) -> String {
+        match self.speaker.as_deref().map(str::trim) {
+            Some(speaker) if !speaker.is_empty() && self.strip_speaker_prefix(text) == text => {
+                format!("{}: {}", speaker, text)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    /// 去掉文本开头的说话人前缀（半角或全角冒号）；译文不带前缀保存，导出时再统一加上
+    pub fn strip_speaker_prefix<'a>(&self, text: &'a str) -> &'a str {
+        let Some(speaker) = self
+            .speaker
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        else {
+            return text;
+        };
+        text.trim_start()
+            .strip_prefix(speaker)
+            .and_then(|rest| rest.strip_prefix(':').or_else(|| rest.strip_prefix('：')))
+            .map_or(text, str::trim_start)
+    }
+
+    /// 写入指定语言的译文，规则同 translation_for
+    pub fn set_translation(
+        &mut self,
+        language: &str,
+        primary_language: Option<&str>,
+        translation: String,
+    ) {
+        if primary_language.is_none_or(|primary| primary.eq_ignore_ascii_case(language)) {
+            self.translation = Some(translation);
+        } else {
+            self.translations.insert(language.to_string(), translation);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -205,12 +919,24 @@ pub struct FavoriteVocabulary {
     pub explanation: Option<String>,
     pub example: Option<String>,
     pub reading: Option<String>,
+    /// 拉丁字母读音（日语为罗马字，韩语为罗马字转写，其他语言为 IPA），供还不认识文字的初学者朗读
+    #[serde(default)]
+    pub phonetic: Option<String>,
     /// 来源文章ID（可选，文章删除后收藏仍保留）
     pub source_article_id: Option<String>,
     /// 来源文章标题（快照，便于显示）
     pub source_article_title: Option<String>,
     #[serde(default)]
     pub pack_ids: Vec<String>,
+    /// 词条标签（如 "N5"、"CET4"），来自导入的单词包，用于按标签/等级拆分包
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 生词配图的文件名（位于 vocabulary_images 目录，资源服务器 /vocabulary-image/ 路径提供）
+    #[serde(default)]
+    pub image_path: Option<String>,
+    /// 学习语言（如 "ja"、"fr"），旧数据为 None
+    #[serde(default)]
+    pub language: Option<String>,
     #[serde(default = "default_srs_state")]
     pub srs_state: String,
     #[serde(default = "default_srs_ease_factor")]
@@ -225,9 +951,149 @@ pub struct FavoriteVocabulary {
     pub last_reviewed_at: Option<String>,
     #[serde(default = "default_zero")]
     pub review_count: i32,
+    /// 评为 "unknown"（忘记）的次数，用于识别反复遗忘的顽固词
+    #[serde(default = "default_zero")]
+    pub lapses: i32,
+    /// 在已导入文章中出现的总次数（导入和删除文章时增量更新）
+    #[serde(default)]
+    pub corpus_count: u32,
+    /// 出现过该词的文章篇数
+    #[serde(default)]
+    pub corpus_articles: u32,
     pub created_at: String,
 }
 
+/// 单词复习记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewLogEntry {
+    pub id: String,
+    pub vocabulary_id: String,
+    /// "unknown" | "uncertain" | "known"
+    pub grade: String,
+    /// 复习的本地日期 (YYYY-MM-DD)
+    pub review_date: String,
+    /// 本次复习后的间隔天数
+    pub interval_days: i32,
+    pub reviewed_at: String,
+}
+
+/// 生词在某篇文章中被作答的记录（阅读时的小测或在文章上下文中复习）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyEncounter {
+    pub id: String,
+    pub vocabulary_id: String,
+    pub article_id: String,
+    /// 是否答对（复习评为 "known" 或 "uncertain"）
+    pub correct: bool,
+    pub encountered_at: String,
+}
+
+/// 每日复习小结
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReviewSummary {
+    /// 本地日期 (YYYY-MM-DD)
+    pub date: String,
+    /// 当日复习次数（同一单词复习多次分别计数）
+    pub review_count: usize,
+    pub known_count: usize,
+    pub uncertain_count: usize,
+    pub unknown_count: usize,
+    /// 正确率 0.0-1.0，"known" 和 "uncertain" 计为正确；无复习时为 0
+    pub accuracy: f64,
+    /// 当日首次复习的单词
+    pub new_words: Vec<FavoriteVocabulary>,
+    /// 明日到期的 learning/review 单词数（含仍未复习的过期单词）
+    pub due_tomorrow_count: usize,
+}
+
+/// 某个间隔档位的记忆保持率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionBucket {
+    /// 档位名称，如 "2-3d"、"91d+"
+    pub label: String,
+    pub min_days: i32,
+    /// 档位上限（含），最后一档为 None
+    pub max_days: Option<i32>,
+    pub review_count: usize,
+    /// 记住的次数（"known" 和 "uncertain"）
+    pub passed_count: usize,
+    /// 保持率 0.0-1.0；该档位没有复习时为 0
+    pub retention: f64,
+}
+
+/// 记忆保持率统计（类似 Anki 的 true retention），只统计非首次复习
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionStats {
+    /// 统计的月份 (YYYY-MM)，None 表示全部
+    pub month: Option<String>,
+    /// 统计的单词包，None 表示全部
+    pub pack_id: Option<String>,
+    pub review_count: usize,
+    pub passed_count: usize,
+    pub retention: f64,
+    /// 按复习前间隔天数分档的保持率
+    pub buckets: Vec<RetentionBucket>,
+}
+
+/// 无法升级的文章（原 JSON 保留在数据库和备份目录中）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleUpgradeFailure {
+    pub id: String,
+    pub error: String,
+}
+
+/// 文章数据升级的执行结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArticleUpgradeReport {
+    pub ran_at: String,
+    /// 升级到的文章数据版本
+    pub schema_version: u32,
+    pub total: usize,
+    /// 重写为最新结构的文章数
+    pub upgraded: usize,
+    /// 已是最新结构、未改动的文章数
+    pub unchanged: usize,
+    pub failed: Vec<ArticleUpgradeFailure>,
+    /// 被改写或无法解析的文章原文备份目录，没有需要备份的文章时为 None
+    pub backup_dir: Option<String>,
+}
+
+/// SRS 维护任务的执行结果（同时通过 srs-maintenance://completed 事件发送）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SrsMaintenanceSummary {
+    pub ran_at: String,
+    /// 已过期未复习的 learning/review 单词数
+    pub overdue_count: usize,
+    /// 按单词包统计的过期数量（一个单词可属于多个包）
+    pub overdue_by_pack: std::collections::BTreeMap<String, usize>,
+    /// 最早的过期日期
+    pub oldest_overdue_date: Option<String>,
+    /// 遗忘次数达到阈值的顽固词 ID
+    pub leech_ids: Vec<String>,
+    /// 本次清理掉的复习记录条数
+    pub review_log_removed: usize,
+}
+
+/// 单词包学习计划：在目标日期前学完剩余新词所需的每日新词数及预计复习量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudyPlan {
+    pub pack_id: String,
+    pub target_date: String,
+    /// 距目标日期的天数（不含目标日当天）
+    pub days_remaining: i64,
+    /// 尚未学习的新词数
+    pub new_count: usize,
+    /// 建议的每日新词数
+    pub daily_new: usize,
+    /// 按建议节奏模拟的每日复习量（最多预测 STUDY_PLAN_FORECAST_DAYS 天）
+    pub review_forecast: Vec<usize>,
+    pub peak_reviews: usize,
+    /// 复习高峰是否超过每日复习上限（超出部分会积压到之后几天）
+    pub exceeds_review_limit: bool,
+    /// 是否已把 daily_new 写入单词包的每日新词上限
+    pub applied: bool,
+}
+
 /// 单词包 - 用于组织和分享单词集合
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordPack {
@@ -239,6 +1105,7 @@ pub struct WordPack {
     pub cover_url: Option<String>,
     #[serde(default)]
     pub author: Option<String>,
+    /// 学习语言，按学习语言分库时以此过滤
     #[serde(default)]
     pub language_from: Option<String>,
     #[serde(default)]
@@ -251,6 +1118,40 @@ pub struct WordPack {
     pub updated_at: String,
     #[serde(default)]
     pub is_system: bool,
+    /// 本包每日新词上限（由学习计划设置），为空时使用全局 srs_daily_new_limit
+    #[serde(default)]
+    pub daily_new_limit: Option<i32>,
+}
+
+/// 拆分单词包的依据：按词条的第一个标签，或按 SRS 掌握程度（srs_state）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackSplitKey {
+    Tag,
+    Level,
+}
+
+/// 文章学习总结卡片（"学到了什么"），学完文章后生成，用于间隔重读
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleRecap {
+    pub article_id: String,
+    /// 文章标题（快照）
+    pub article_title: String,
+    /// AI 生成的文章摘要
+    pub summary: String,
+    /// 该文章收藏的单词
+    #[serde(default)]
+    pub vocabulary: Vec<VocabularyItem>,
+    /// 该文章收藏的语法点
+    #[serde(default)]
+    pub grammar_points: Vec<GrammarPoint>,
+    pub created_at: String,
+    /// 已完成的重读次数，决定下次重读间隔
+    #[serde(default = "default_zero")]
+    pub reread_count: i32,
+    /// 下次重读日期 (YYYY-MM-DD)
+    #[serde(default = "default_srs_due_date")]
+    pub next_reread_date: String,
 }
 
 /// 收藏的语法点
@@ -264,9 +1165,25 @@ pub struct FavoriteGrammar {
     pub source_article_id: Option<String>,
     /// 来源文章标题（快照，便于显示）
     pub source_article_title: Option<String>,
+    /// 收藏时所在的段落（从段落讲解中收藏时记录）
+    #[serde(default)]
+    pub source_segment_id: Option<String>,
     pub created_at: String,
 }
 
+/// 语法收藏在文章中的一处用例
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrammarUsage {
+    pub article_id: String,
+    pub article_title: String,
+    pub segment_id: String,
+    pub order: i32,
+    pub text: String,
+    pub translation: Option<String>,
+    /// 是否为收藏时所在的段落
+    pub is_source: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationRequest {
     pub text: String,
@@ -274,6 +1191,114 @@ pub struct TranslationRequest {
     pub context: Option<String>,
 }
 
+/// Whole-article translation quality mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationQuality {
+    /// Short single-pass prompt, cheapest
+    Fast,
+    /// Single pass with the regular batch prompt
+    #[default]
+    Standard,
+    /// Regular pass followed by a review pass that refines awkward translations
+    Thorough,
+}
+
+impl TranslationQuality {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TranslationQuality::Fast => "fast",
+            TranslationQuality::Standard => "standard",
+            TranslationQuality::Thorough => "thorough",
+        }
+    }
+}
+
+/// 注音方式：AI 批量生成，或本地生成（中文拼音、韩语罗马字、西里尔/阿拉伯字母转写）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadingEngine {
+    Ai,
+    #[serde(alias = "pinyin")]
+    Local,
+}
+
+impl ReadingEngine {
+    /// 未指定时支持本地注音的语言使用本地注音，其他语言（如日语）使用 AI
+    pub fn default_for(language: &str) -> Self {
+        if crate::readings::supports_local(language) {
+            ReadingEngine::Local
+        } else {
+            ReadingEngine::Ai
+        }
+    }
+}
+
+/// 只翻译部分段落：按 order 区间（含两端）和/或显式的段落 ID 列表选择
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentSelection {
+    #[serde(default)]
+    pub from_order: Option<i32>,
+    #[serde(default)]
+    pub to_order: Option<i32>,
+    #[serde(default)]
+    pub segment_ids: Option<Vec<String>>,
+}
+
+impl SegmentSelection {
+    pub fn includes(&self, segment: &ArticleSegment) -> bool {
+        self.from_order.is_none_or(|from| segment.order >= from)
+            && self.to_order.is_none_or(|to| segment.order <= to)
+            && self
+                .segment_ids
+                .as_ref()
+                .is_none_or(|ids| ids.iter().any(|id| id == &segment.id))
+    }
+}
+
+/// 整篇翻译的分批进度，存在未完成批次时保留，用于断点续译
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationProgress {
+    pub article_id: String,
+    pub target_language: String,
+    #[serde(default)]
+    pub quality: TranslationQuality,
+    /// 只翻译部分段落时的选择范围，续译时沿用
+    #[serde(default)]
+    pub selection: Option<SegmentSelection>,
+    pub chunks: Vec<TranslationChunkStatus>,
+    pub updated_at: String,
+}
+
+impl TranslationProgress {
+    /// 重试后仍失败的批次数
+    pub fn failed_count(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.status == ChunkStatus::Failed)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationChunkStatus {
+    pub segment_ids: Vec<String>,
+    pub status: ChunkStatus,
+    /// 已尝试次数（含首次）
+    pub attempts: u32,
+    /// 最近一次失败的原因
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStatus {
+    Pending,
+    Done,
+    Failed,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationResponse {
     pub translated_text: String,
@@ -408,3 +1433,268 @@ pub struct Bookmark {
     #[serde(default)]
     pub color: Option<String>,
 }
+
+/// 抓取失败的网页 - 加入重试队列，稍后自动导入
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedCapture {
+    pub id: String,
+    pub url: String,
+    /// 最近一次失败的错误信息
+    pub error: String,
+    /// 首次失败时间
+    pub failed_at: String,
+    /// 最近一次重试时间
+    #[serde(default)]
+    pub last_attempt_at: Option<String>,
+    /// 已重试次数
+    #[serde(default)]
+    pub retry_count: i32,
+}
+
+/// 被隔离的 AI 结果类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuarantineKind {
+    Explanation,
+    Translation,
+}
+
+/// 未通过校验、没有写入文章的 AI 结果，供查看后重新生成
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedResult {
+    pub id: String,
+    pub article_id: String,
+    pub segment_id: String,
+    pub kind: QuarantineKind,
+    /// 译文的目标语言、讲解的母语
+    pub target_language: String,
+    /// 模型返回的原始内容（讲解为 JSON）
+    pub payload: String,
+    /// 未通过校验的原因
+    pub reason: String,
+    pub created_at: String,
+}
+
+/// 译文抽查中得分偏低的一句
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationQaIssue {
+    pub segment_id: String,
+    /// 1-10 分
+    pub score: u8,
+    /// 模型给出的问题说明
+    pub issue: String,
+}
+
+/// 整篇翻译完成后的译文质量抽查结果，每篇文章保留最近一次
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationQaReport {
+    pub article_id: String,
+    pub target_language: String,
+    /// 被抽查的翻译所用的模型（含质量模式）
+    pub model: String,
+    /// 实际拿到评分的段落数
+    pub sampled: usize,
+    /// 平均分（1-10）
+    pub average_score: f64,
+    /// 得分偏低的段落
+    pub issues: Vec<TranslationQaIssue>,
+    /// 平均分过低或低分段落过多，建议换用更好的模型重新翻译
+    pub warning: bool,
+    pub checked_at: String,
+}
+
+/// 已知词 - 统计文章生词密度时不计为生词
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownWord {
+    pub word: String,
+    /// 学习语言，统计时按文章语言筛选（未标记的词始终计入）
+    #[serde(default)]
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+/// 单个段落的生词统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentDifficulty {
+    pub segment_id: String,
+    pub order: i32,
+    pub token_count: usize,
+    pub unknown_count: usize,
+    /// 生词密度 0.0-1.0
+    pub density: f64,
+    /// 难度分值 0-100（生词密度的百分比）
+    pub score: u32,
+}
+
+/// 文章逐段难度，前端据此绘制滚动条旁的热力图
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleDifficulty {
+    pub article_id: String,
+    /// 参与统计的已知词数量（含已掌握的收藏词）
+    pub known_word_count: usize,
+    /// 全文生词密度
+    pub overall_density: f64,
+    pub segments: Vec<SegmentDifficulty>,
+}
+
+/// 读前生词表中的一个词
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreReadingWord {
+    /// 生词（小写）
+    pub word: String,
+    /// 在文章中出现的次数
+    pub count: usize,
+    /// 首次出现的段落
+    pub segment_id: String,
+    /// 首次出现的句子，供预习时参考用法
+    pub context: String,
+    /// AI 给出的简短释义，未请求时为 None
+    #[serde(default)]
+    pub gloss: Option<String>,
+}
+
+/// 读前生词表：文章中既不在已知词库也未收藏的词，按出现次数排序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreReadingList {
+    pub article_id: String,
+    /// 参与过滤的已知词和收藏词数量
+    pub known_word_count: usize,
+    /// 文章中不同生词的总数（截取前）
+    pub unknown_word_count: usize,
+    pub words: Vec<PreReadingWord>,
+}
+
+/// 术语表条目 - 翻译时强制使用的译法
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: String,
+    /// 原文术语（人名、专有名词、技术术语等）
+    pub term: String,
+    /// 指定译法
+    pub translation: String,
+    /// 原文语言（仅用于分组显示，匹配以原文中是否出现术语为准）
+    #[serde(default)]
+    pub source_language: Option<String>,
+    /// 目标语言，与翻译请求的 target_language 匹配（不区分大小写）
+    pub target_language: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 自动标签规则匹配的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagRuleField {
+    /// 来源网址的域名（含子域名）
+    Domain,
+    /// 素材语言（"ja" 同时匹配 "ja-JP"）
+    Language,
+    /// YouTube 频道名
+    Channel,
+    /// 标题或正文中包含的关键词
+    Keyword,
+}
+
+/// 自动标签规则：导入时素材的某个字段匹配 pattern 就加上 tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagRule {
+    pub id: String,
+    pub field: TagRuleField,
+    pub pattern: String,
+    pub tag: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+/// 导出模板的用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportTemplateKind {
+    /// 文章导出为 Markdown 笔记（Obsidian 导出）
+    ArticleMarkdown,
+    /// 单词导出为 CSV，每个字段一列
+    VocabularyCsv,
+    /// 单词导出为 Anki 文本导入文件，每个字段对应一个笔记字段
+    AnkiNote,
+}
+
+/// CSV 列或 Anki 笔记字段：列名和生成该列内容的模板
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateField {
+    pub name: String,
+    pub template: String,
+}
+
+/// 用户编辑的导出模板（语法见 export_templates 模块）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    pub id: String,
+    pub name: String,
+    pub kind: ExportTemplateKind,
+    /// ArticleMarkdown 的正文模板
+    #[serde(default)]
+    pub body: String,
+    /// VocabularyCsv 的列 / AnkiNote 的字段
+    #[serde(default)]
+    pub fields: Vec<TemplateField>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 文件夹批量导入本地视频的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportVideoFolderResult {
+    /// 按文件名顺序成功导入的文章
+    pub imported: Vec<Article>,
+    /// 导入失败的文件
+    pub failed: Vec<FailedVideoImport>,
+    /// 已排队的字幕提取任务 ID（可通过 cancel_job_cmd 取消）
+    pub subtitle_job_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedVideoImport {
+    pub file_path: String,
+    pub error: String,
+}
+
+/// 批量重试抓取失败网页的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryCapturesResult {
+    /// 重试成功并已导入的文章
+    pub imported: Vec<Article>,
+    /// 仍然失败、保留在队列中的条目
+    pub still_failing: Vec<FailedCapture>,
+}
+
+/// 可在独立窗口打开的界面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowKind {
+    Reader,
+    Review,
+    Stats,
+}
+
+impl WindowKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WindowKind::Reader => "reader",
+            WindowKind::Review => "review",
+            WindowKind::Stats => "stats",
+        }
+    }
+}
+
+/// 独立窗口的路由：窗口启动时据此显示对应界面和文章
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowRoute {
+    pub label: String,
+    pub kind: WindowKind,
+    /// 阅读器窗口显示的文章
+    #[serde(default)]
+    pub article_id: Option<String>,
+}
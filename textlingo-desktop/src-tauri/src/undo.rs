@@ -0,0 +1,122 @@
+// 撤销日志：删除单词、单词包、书签、段落前把被删除的数据记入短期日志，
+// 之后可通过 undo_last_action_cmd 恢复最近一次操作。日志只保存在内存中，
+// 超过 UNDO_TTL_SECONDS 或超出 MAX_UNDO_ENTRIES 的记录被丢弃，重启后清空
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::types::{ArticleSegment, UndoActionInfo, UndoActionKind};
+
+/// 记录的保留时间（秒）
+pub const UNDO_TTL_SECONDS: i64 = 600;
+
+/// 最多保留的记录数
+pub const MAX_UNDO_ENTRIES: usize = 20;
+
+/// 恢复所需的数据；单词、单词包、书签保存删除前的 JSON
+#[derive(Debug, Clone)]
+pub enum UndoAction {
+    DeleteVocabulary {
+        vocabulary: String,
+        /// 单词配图 (文件名, 图片数据)
+        images: Vec<(String, Vec<u8>)>,
+    },
+    DeleteWordPack {
+        pack: String,
+        /// 删除前属于该包的单词及其原有的 pack_ids
+        members: Vec<(String, Vec<String>)>,
+    },
+    DeleteBookmark {
+        bookmark: String,
+    },
+    DeleteSegment {
+        article_id: String,
+        index: usize,
+        segment: Box<ArticleSegment>,
+        /// 删除前下一段的段首标记（删除段首段落时下一段会被标为段首）
+        next_is_new_paragraph: Option<bool>,
+    },
+}
+
+impl UndoAction {
+    pub fn kind(&self) -> UndoActionKind {
+        match self {
+            UndoAction::DeleteVocabulary { .. } => UndoActionKind::DeleteVocabulary,
+            UndoAction::DeleteWordPack { .. } => UndoActionKind::DeleteWordPack,
+            UndoAction::DeleteBookmark { .. } => UndoActionKind::DeleteBookmark,
+            UndoAction::DeleteSegment { .. } => UndoActionKind::DeleteSegment,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub info: UndoActionInfo,
+    pub action: UndoAction,
+    recorded_at: DateTime<Utc>,
+}
+
+/// 撤销日志，作为 Tauri 托管状态
+#[derive(Clone, Default)]
+pub struct UndoJournal(Arc<Mutex<Vec<UndoEntry>>>);
+
+impl UndoJournal {
+    /// 记录一次删除，返回记录信息
+    pub fn record(&self, label: &str, action: UndoAction, now: DateTime<Utc>) -> UndoActionInfo {
+        let info = UndoActionInfo {
+            id: Uuid::new_v4().to_string(),
+            kind: action.kind(),
+            label: label.chars().take(80).collect(),
+            created_at: now.to_rfc3339(),
+        };
+        if let Ok(mut entries) = self.0.lock() {
+            prune(&mut entries, now);
+            entries.push(UndoEntry {
+                info: info.clone(),
+                action,
+                recorded_at: now,
+            });
+            let overflow = entries.len().saturating_sub(MAX_UNDO_ENTRIES);
+            entries.drain(..overflow);
+        }
+        info
+    }
+
+    /// 最近一次仍可撤销的操作
+    pub fn latest(&self, now: DateTime<Utc>) -> Option<UndoActionInfo> {
+        let mut entries = self.0.lock().ok()?;
+        prune(&mut entries, now);
+        entries.last().map(|entry| entry.info.clone())
+    }
+
+    /// 查看最近一次仍可撤销的操作（不移出日志，恢复成功后再调用 remove）
+    pub fn peek(&self, now: DateTime<Utc>) -> Option<UndoEntry> {
+        let mut entries = self.0.lock().ok()?;
+        prune(&mut entries, now);
+        entries.last().cloned()
+    }
+
+    /// 移出指定记录，返回是否存在
+    pub fn remove(&self, id: &str) -> bool {
+        let Ok(mut entries) = self.0.lock() else {
+            return false;
+        };
+        let before = entries.len();
+        entries.retain(|entry| entry.info.id != id);
+        entries.len() != before
+    }
+
+    /// 取出最近一次仍可撤销的操作
+    pub fn pop(&self, now: DateTime<Utc>) -> Option<UndoEntry> {
+        let mut entries = self.0.lock().ok()?;
+        prune(&mut entries, now);
+        entries.pop()
+    }
+}
+
+fn prune(entries: &mut Vec<UndoEntry>, now: DateTime<Utc>) {
+    let cutoff = now - Duration::seconds(UNDO_TTL_SECONDS);
+    entries.retain(|entry| entry.recorded_at > cutoff);
+}
@@ -16,7 +16,7 @@ use warp::Filter;
 pub const VIDEO_SERVER_PORT: u16 = 19420;
 
 /// 启动资源服务器（在后台运行）
-/// 提供视频和书籍文件的本地访问
+/// 提供视频、书籍、网页导入图片、生词图片、朗读音频和视频缩略图的本地访问
 pub async fn start_resource_server(app_data_dir: PathBuf) -> Result<(), String> {
     let app_data_dir = Arc::new(app_data_dir);
 
@@ -32,6 +32,30 @@ pub async fn start_resource_server(app_data_dir: PathBuf) -> Result<(), String>
         warp::any().map(move || Arc::new(dir.clone()))
     };
 
+    // 网页导入图片目录: app_data_dir/web_images
+    let images_dir_filter = {
+        let dir = app_data_dir.join(crate::web_images::WEB_IMAGES_DIR);
+        warp::any().map(move || Arc::new(dir.clone()))
+    };
+
+    // 生词图片目录: app_data_dir/vocabulary_images
+    let vocabulary_images_dir_filter = {
+        let dir = app_data_dir.join(crate::vocabulary_images::VOCABULARY_IMAGES_DIR);
+        warp::any().map(move || Arc::new(dir.clone()))
+    };
+
+    // 朗读音频目录: app_data_dir/tts_audio
+    let tts_dir_filter = {
+        let dir = app_data_dir.join(crate::tts::TTS_AUDIO_DIR);
+        warp::any().map(move || Arc::new(dir.clone()))
+    };
+
+    // 视频缩略图目录: app_data_dir/thumbnails
+    let thumbnails_dir_filter = {
+        let dir = app_data_dir.join(crate::youtube::THUMBNAILS_DIR);
+        warp::any().map(move || Arc::new(dir.clone()))
+    };
+
     // GET /video/{filename}
     let video_route = warp::path("video")
         .and(warp::path::param::<String>())
@@ -46,13 +70,47 @@ pub async fn start_resource_server(app_data_dir: PathBuf) -> Result<(), String>
         .and(books_dir_filter)
         .and_then(serve_file);
 
+    // GET /image/{filename}
+    let image_route = warp::path("image")
+        .and(warp::path::param::<String>())
+        .and(warp::header::optional::<String>("range"))
+        .and(images_dir_filter)
+        .and_then(serve_file);
+
+    // GET /vocabulary-image/{filename}
+    let vocabulary_image_route = warp::path("vocabulary-image")
+        .and(warp::path::param::<String>())
+        .and(warp::header::optional::<String>("range"))
+        .and(vocabulary_images_dir_filter)
+        .and_then(serve_file);
+
+    // GET /tts/{filename}
+    let tts_route = warp::path("tts")
+        .and(warp::path::param::<String>())
+        .and(warp::header::optional::<String>("range"))
+        .and(tts_dir_filter)
+        .and_then(serve_file);
+
+    // GET /thumbnail/{filename}
+    let thumbnail_route = warp::path("thumbnail")
+        .and(warp::path::param::<String>())
+        .and(warp::header::optional::<String>("range"))
+        .and(thumbnails_dir_filter)
+        .and_then(serve_file);
+
     // CORS 支持（允许来自 Tauri webview 的请求）
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(vec!["GET", "HEAD", "OPTIONS"])
         .allow_headers(vec!["range", "content-type"]);
 
-    let routes = video_route.or(book_route).with(cors);
+    let routes = video_route
+        .or(book_route)
+        .or(image_route)
+        .or(vocabulary_image_route)
+        .or(tts_route)
+        .or(thumbnail_route)
+        .with(cors);
 
     // 在后台启动服务器
     tokio::spawn(async move {
@@ -135,10 +193,24 @@ async fn serve_file(
         "audio/x-ms-wma"
     } else if decoded_filename.ends_with(".epub") {
         "application/epub+zip"
+    } else if decoded_filename.ends_with(".json") {
+        "application/json; charset=utf-8"
     } else if decoded_filename.ends_with(".txt") {
         "text/plain; charset=utf-8"
     } else if decoded_filename.ends_with(".pdf") {
         "application/pdf"
+    } else if decoded_filename.ends_with(".png") {
+        "image/png"
+    } else if decoded_filename.ends_with(".jpg") || decoded_filename.ends_with(".jpeg") {
+        "image/jpeg"
+    } else if decoded_filename.ends_with(".gif") {
+        "image/gif"
+    } else if decoded_filename.ends_with(".webp") {
+        "image/webp"
+    } else if decoded_filename.ends_with(".svg") {
+        "image/svg+xml"
+    } else if decoded_filename.ends_with(".avif") {
+        "image/avif"
     } else {
         "application/octet-stream"
     };
@@ -0,0 +1,145 @@
+// 生词配图：截图或粘贴的图片保存到收藏目录，并通过资源服务器提供
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::path::Path;
+
+use crate::video_server::VIDEO_SERVER_PORT;
+
+/// 图片目录: app_data_dir/vocabulary_images，文件名为 {vocabulary_id}_{时间戳}.{扩展名}
+pub const VOCABULARY_IMAGES_DIR: &str = "vocabulary_images";
+
+/// 单张图片最大体积
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+/// 根据 MIME 类型或文件扩展名得到保存用的扩展名，不支持的格式返回 None
+pub fn image_extension(kind: &str) -> Option<&'static str> {
+    match kind.trim().to_lowercase().as_str() {
+        "image/png" | "png" => Some("png"),
+        "image/jpeg" | "image/jpg" | "jpeg" | "jpg" => Some("jpg"),
+        "image/gif" | "gif" => Some("gif"),
+        "image/webp" | "webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+/// 解析粘贴得到的 data URL（data:image/png;base64,...），返回 (扩展名, 图片数据)
+pub fn decode_data_url(data_url: &str) -> Result<(&'static str, Vec<u8>), String> {
+    let rest = data_url
+        .trim()
+        .strip_prefix("data:")
+        .ok_or_else(|| "Not a data URL".to_string())?;
+    let (mime, data) = rest
+        .split_once(";base64,")
+        .ok_or_else(|| "Data URL is not base64 encoded".to_string())?;
+    let extension =
+        image_extension(mime).ok_or_else(|| format!("Unsupported image type: {}", mime))?;
+    let bytes = BASE64
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid base64 image data: {}", e))?;
+    Ok((extension, bytes))
+}
+
+/// 读取本地图片文件（截图保存的文件）
+pub fn read_image_file(path: &Path) -> Result<(&'static str, Vec<u8>), String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(image_extension)
+        .ok_or_else(|| format!("Unsupported image file: {}", path.display()))?;
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    Ok((extension, bytes))
+}
+
+/// 保存生词图片并删除该单词之前的图片，返回文件名
+pub fn save_image(
+    app_data_dir: &Path,
+    vocabulary_id: &str,
+    extension: &str,
+    bytes: &[u8],
+) -> Result<String, String> {
+    if bytes.is_empty() {
+        return Err("Image is empty".to_string());
+    }
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("Image too large: {} bytes", bytes.len()));
+    }
+
+    let images_dir = app_data_dir.join(VOCABULARY_IMAGES_DIR);
+    std::fs::create_dir_all(&images_dir)
+        .map_err(|e| format!("Failed to create images dir: {}", e))?;
+    delete_images(app_data_dir, vocabulary_id);
+
+    let filename = format!(
+        "{}_{}.{}",
+        vocabulary_id,
+        chrono::Utc::now().timestamp_millis(),
+        extension
+    );
+    std::fs::write(images_dir.join(&filename), bytes)
+        .map_err(|e| format!("Failed to write image: {}", e))?;
+    Ok(filename)
+}
+
+/// 读取单词对应的本地图片 (文件名, 图片数据)，删除前留存以便撤销
+pub fn read_images(app_data_dir: &Path, vocabulary_id: &str) -> Vec<(String, Vec<u8>)> {
+    let Ok(entries) = std::fs::read_dir(app_data_dir.join(VOCABULARY_IMAGES_DIR)) else {
+        return Vec::new();
+    };
+
+    let prefix = format!("{}_", vocabulary_id);
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(&prefix) {
+                return None;
+            }
+            std::fs::read(entry.path()).ok().map(|data| (name, data))
+        })
+        .collect()
+}
+
+/// 写回 read_images 留存的图片
+pub fn restore_images(app_data_dir: &Path, images: &[(String, Vec<u8>)]) -> Result<(), String> {
+    if images.is_empty() {
+        return Ok(());
+    }
+    let images_dir = app_data_dir.join(VOCABULARY_IMAGES_DIR);
+    std::fs::create_dir_all(&images_dir)
+        .map_err(|e| format!("Failed to create images directory: {}", e))?;
+    for (name, data) in images {
+        std::fs::write(images_dir.join(name), data)
+            .map_err(|e| format!("Failed to restore image: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 删除单词对应的本地图片
+pub fn delete_images(app_data_dir: &Path, vocabulary_id: &str) {
+    let images_dir = app_data_dir.join(VOCABULARY_IMAGES_DIR);
+    let Ok(entries) = std::fs::read_dir(&images_dir) else {
+        return;
+    };
+
+    let prefix = format!("{}_", vocabulary_id);
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                eprintln!(
+                    "[VocabularyImages] Failed to delete {:?}: {}",
+                    entry.path(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// 资源服务器上的生词图片地址
+pub fn image_url(filename: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/vocabulary-image/{}",
+        VIDEO_SERVER_PORT,
+        urlencoding::encode(filename)
+    )
+}
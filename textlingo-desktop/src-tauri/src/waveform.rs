@@ -0,0 +1,228 @@
+// 播放器波形：用 FFmpeg 把音轨解码为低采样率的单声道 PCM，按固定时间间隔取峰值
+// 结果按媒体文件（路径、大小、修改时间）缓存为 JSON，同一文件再次打开时直接读取
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::i18n::{tr, tr_with};
+use crate::tts::fnv1a;
+use crate::types::WaveformPeaks;
+
+/// 缓存目录: app_data_dir/waveforms
+pub const WAVEFORM_DIR: &str = "waveforms";
+
+/// 未指定精度时每秒的峰值个数
+pub const DEFAULT_PEAKS_PER_SECOND: u32 = 50;
+
+/// 每个峰值覆盖的采样数，解码采样率 = 每秒峰值数 × 该值
+const SAMPLES_PER_PEAK: usize = 100;
+
+/// 限制在每秒 10-200 个峰值，过高时长视频的数据量过大
+pub fn peaks_per_second(requested: Option<u32>) -> u32 {
+    requested.unwrap_or(DEFAULT_PEAKS_PER_SECOND).clamp(10, 200)
+}
+
+/// 逐块读入 16 位小端 PCM，每 samples_per_peak 个采样取一个最大振幅
+pub struct PeakAccumulator {
+    samples_per_peak: usize,
+    /// 上一块末尾未凑成一个采样的字节
+    pending_byte: Option<u8>,
+    current_max: u16,
+    current_count: usize,
+    total_samples: u64,
+    peaks: Vec<f32>,
+}
+
+impl PeakAccumulator {
+    pub fn new(samples_per_peak: usize) -> Self {
+        Self {
+            samples_per_peak: samples_per_peak.max(1),
+            pending_byte: None,
+            current_max: 0,
+            current_count: 0,
+            total_samples: 0,
+            peaks: Vec::new(),
+        }
+    }
+
+    fn push_sample(&mut self, sample: i16) {
+        self.current_max = self.current_max.max(sample.unsigned_abs());
+        self.current_count += 1;
+        self.total_samples += 1;
+        if self.current_count == self.samples_per_peak {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        // 保留三位小数，缓存文件和传给前端的数据都更小
+        let peak = (self.current_max as f32 / 32768.0).min(1.0);
+        self.peaks.push((peak * 1000.0).round() / 1000.0);
+        self.current_max = 0;
+        self.current_count = 0;
+    }
+
+    pub fn push(&mut self, mut bytes: &[u8]) {
+        if let Some(low) = self.pending_byte.take() {
+            let Some((&high, rest)) = bytes.split_first() else {
+                self.pending_byte = Some(low);
+                return;
+            };
+            self.push_sample(i16::from_le_bytes([low, high]));
+            bytes = rest;
+        }
+        let mut samples = bytes.chunks_exact(2);
+        for pair in samples.by_ref() {
+            self.push_sample(i16::from_le_bytes([pair[0], pair[1]]));
+        }
+        self.pending_byte = samples.remainder().first().copied();
+    }
+
+    /// 已读入的采样数
+    pub fn total_samples(&self) -> u64 {
+        self.total_samples
+    }
+
+    /// 结束时不足一个区间的采样也记为一个峰值
+    pub fn finish(mut self) -> Vec<f32> {
+        if self.current_count > 0 {
+            self.flush();
+        }
+        self.peaks
+    }
+}
+
+/// 缓存文件名：媒体文件被替换（大小或修改时间变化）或精度不同时重新计算
+pub fn cache_file_name(
+    media_path: &Path,
+    size: u64,
+    modified: u64,
+    peaks_per_second: u32,
+) -> String {
+    let key = format!(
+        "{}\u{1f}{}\u{1f}{}\u{1f}{}",
+        media_path.to_string_lossy(),
+        size,
+        modified,
+        peaks_per_second
+    );
+    format!("{:016x}.json", fnv1a(key.as_bytes()))
+}
+
+fn cache_path(
+    app_data_dir: &Path,
+    media_path: &Path,
+    peaks_per_second: u32,
+) -> Result<PathBuf, String> {
+    let metadata =
+        fs::metadata(media_path).map_err(|e| format!("Failed to read media file: {}", e))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    Ok(app_data_dir.join(WAVEFORM_DIR).join(cache_file_name(
+        media_path,
+        metadata.len(),
+        modified,
+        peaks_per_second,
+    )))
+}
+
+/// 读取缓存的波形，没有缓存时用 FFmpeg 解码计算并写入缓存
+pub async fn load_or_compute(
+    app: &AppHandle,
+    app_data_dir: &Path,
+    media_path: &Path,
+    peaks_per_second: u32,
+) -> Result<WaveformPeaks, String> {
+    let cache = cache_path(app_data_dir, media_path, peaks_per_second)?;
+    if let Some(cached) = fs::read_to_string(&cache)
+        .ok()
+        .and_then(|json| serde_json::from_str::<WaveformPeaks>(&json).ok())
+    {
+        return Ok(cached);
+    }
+
+    let cache_dir = app_data_dir.join(WAVEFORM_DIR);
+    fs::create_dir_all(&cache_dir).map_err(|e| format!("Failed to create waveform dir: {}", e))?;
+    let pcm_path = cache.with_extension("pcm");
+    let media_str = media_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+    let pcm_str = pcm_path
+        .to_str()
+        .ok_or_else(|| tr("error.invalid_file_path"))?;
+
+    // -vn: 不处理视频流；-ac 1 -ar N: 单声道、低采样率；-f s16le: 无文件头的 16 位小端 PCM
+    let sample_rate = peaks_per_second as usize * SAMPLES_PER_PEAK;
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| tr_with("error.ffmpeg_sidecar", &[("error", e.to_string())]))?
+        .args([
+            "-i",
+            media_str,
+            "-vn",
+            "-ac",
+            "1",
+            "-ar",
+            &sample_rate.to_string(),
+            "-f",
+            "s16le",
+            "-y",
+            pcm_str,
+        ])
+        .output()
+        .await
+        .map_err(|e| tr_with("error.ffmpeg_failed", &[("error", e.to_string())]))?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&pcm_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(tr_with(
+            "error.audio_decode_failed",
+            &[("error", stderr.to_string())],
+        ));
+    }
+
+    // 长视频的 PCM 可达上百 MB，分块读取
+    let (peaks, total_samples) = {
+        let mut file =
+            fs::File::open(&pcm_path).map_err(|e| format!("Failed to open PCM data: {}", e))?;
+        let mut accumulator = PeakAccumulator::new(SAMPLES_PER_PEAK);
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = file
+                .read(&mut buffer)
+                .map_err(|e| format!("Failed to read PCM data: {}", e))?;
+            if read == 0 {
+                break;
+            }
+            accumulator.push(&buffer[..read]);
+        }
+        let total_samples = accumulator.total_samples();
+        (accumulator.finish(), total_samples)
+    };
+    let _ = fs::remove_file(&pcm_path);
+
+    let waveform = WaveformPeaks {
+        peaks_per_second,
+        duration: total_samples as f64 / sample_rate as f64,
+        peaks,
+    };
+    match serde_json::to_string(&waveform) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&cache, json) {
+                eprintln!("[Waveform] Failed to write cache {:?}: {}", cache, e);
+            }
+        }
+        Err(e) => eprintln!("[Waveform] Failed to serialize waveform: {}", e),
+    }
+    Ok(waveform)
+}
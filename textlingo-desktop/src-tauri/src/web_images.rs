@@ -0,0 +1,217 @@
+// 网页导入的插图：提取时保留为占位行，导入时可下载到本地并通过资源服务器提供
+
+use regex::Regex;
+use reqwest::Client;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::video_server::VIDEO_SERVER_PORT;
+
+/// 图片目录: app_data_dir/web_images，文件名为 {article_id}_{序号}.{扩展名}
+pub const WEB_IMAGES_DIR: &str = "web_images";
+
+/// 单张图片最大体积，超过则保留远程地址
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+/// 生成独占一行的图片占位符，格式与 Markdown 图片一致: ![alt](url)
+pub fn image_placeholder(alt: &str, url: &str) -> String {
+    let alt = alt
+        .replace(['[', ']'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("![{}]({})", alt, url.replace(' ', "%20"))
+}
+
+/// 解析图片占位行，返回 (alt, url)
+pub fn parse_image_placeholder(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let rest = line.strip_prefix("![")?;
+    let (alt, rest) = rest.split_once("](")?;
+    let url = rest.strip_suffix(')')?;
+    if url.is_empty() || url.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((alt.to_string(), url.to_string()))
+}
+
+/// 将 HTML 中的 <img> 替换为图片占位行（相对地址按页面 URL 解析，懒加载的 data-src 优先）
+pub fn replace_img_tags(html: &str, base_url: &url::Url) -> String {
+    let re_img = Regex::new(r"(?i)<img\b[^>]*>").unwrap();
+    let re_src = Regex::new(r#"(?i)\s(data-src|src)\s*=\s*["']([^"']+)["']"#).unwrap();
+    let re_alt = Regex::new(r#"(?i)\salt\s*=\s*["']([^"']*)["']"#).unwrap();
+
+    re_img
+        .replace_all(html, |caps: &regex::Captures| {
+            let tag = &caps[0];
+            let src = re_src
+                .captures_iter(tag)
+                .map(|c| (c[1].to_lowercase(), c[2].to_string()))
+                .min_by_key(|(attr, _)| if attr == "data-src" { 0 } else { 1 })
+                .map(|(_, src)| html_escape::decode_html_entities(&src).to_string());
+
+            let resolved = src
+                .filter(|src| !src.starts_with("data:"))
+                .and_then(|src| base_url.join(&src).ok())
+                .filter(|url| url.scheme() == "http" || url.scheme() == "https");
+
+            match resolved {
+                Some(url) => {
+                    let alt = re_alt
+                        .captures(tag)
+                        .map(|c| html_escape::decode_html_entities(&c[1]).to_string())
+                        .unwrap_or_default();
+                    // 用块级标签包裹，保证占位符独占一行
+                    format!("<p>{}</p>", image_placeholder(&alt, url.as_str()))
+                }
+                None => String::new(),
+            }
+        })
+        .to_string()
+}
+
+/// 资源服务器上的图片地址
+pub fn local_image_url(filename: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/image/{}",
+        VIDEO_SERVER_PORT,
+        urlencoding::encode(filename)
+    )
+}
+
+/// 下载正文中的远程图片到 web_images 目录，并把占位符改为本地地址
+/// 单张图片下载失败时保留原地址，不影响导入
+pub async fn download_images(app_data_dir: &Path, article_id: &str, content: &str) -> String {
+    // 离线时保留远程地址，联网后阅读时再加载
+    if crate::offline::is_offline() {
+        return content.to_string();
+    }
+    let images_dir = app_data_dir.join(WEB_IMAGES_DIR);
+    if let Err(e) = std::fs::create_dir_all(&images_dir) {
+        eprintln!("[WebImages] Failed to create images dir: {}", e);
+        return content.to_string();
+    }
+
+    let client = match Client::builder().timeout(Duration::from_secs(30)).build() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("[WebImages] Failed to create HTTP client: {}", e);
+            return content.to_string();
+        }
+    };
+
+    let mut index = 0;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let Some((alt, url)) = parse_image_placeholder(line) else {
+            lines.push(line.to_string());
+            continue;
+        };
+
+        index += 1;
+        match download_image(&client, &url, &images_dir, article_id, index).await {
+            Ok(filename) => lines.push(image_placeholder(&alt, &local_image_url(&filename))),
+            Err(e) => {
+                eprintln!("[WebImages] Failed to download {}: {}", url, e);
+                lines.push(line.to_string());
+            }
+        }
+    }
+
+    println!("[WebImages] Processed {} images for {}", index, article_id);
+    lines.join("\n")
+}
+
+async fn download_image(
+    client: &Client,
+    url: &str,
+    images_dir: &Path,
+    article_id: &str,
+    index: usize,
+) -> Result<String, String> {
+    let response = client
+        .get(url)
+        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch image: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    let extension = match content_type.split(';').next().unwrap_or("").trim() {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/avif" => "avif",
+        other => return Err(format!("Not an image: {}", other)),
+    };
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("Image too large: {} bytes", bytes.len()));
+    }
+
+    let filename = format!("{}_{}.{}", article_id, index, extension);
+    std::fs::write(images_dir.join(&filename), &bytes)
+        .map_err(|e| format!("Failed to write image: {}", e))?;
+    Ok(filename)
+}
+
+/// 把一篇文章的本地图片复制给另一篇文章（合并文章时使用），新文件名为 {to_id}_{part}-{原序号}，
+/// 返回 (旧地址, 新地址)，供改写正文和段落中的图片地址
+pub fn copy_article_images(
+    app_data_dir: &Path,
+    from_id: &str,
+    to_id: &str,
+    part: usize,
+) -> Vec<(String, String)> {
+    let images_dir = app_data_dir.join(WEB_IMAGES_DIR);
+    let Ok(entries) = std::fs::read_dir(&images_dir) else {
+        return Vec::new();
+    };
+
+    let prefix = format!("{}_", from_id);
+    let mut copied = Vec::new();
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        let Some(rest) = filename.strip_prefix(&prefix) else {
+            continue;
+        };
+        let new_filename = format!("{}_{}-{}", to_id, part, rest);
+        match std::fs::copy(entry.path(), images_dir.join(&new_filename)) {
+            Ok(_) => copied.push((local_image_url(&filename), local_image_url(&new_filename))),
+            Err(e) => eprintln!("[WebImages] Failed to copy {:?}: {}", entry.path(), e),
+        }
+    }
+    copied
+}
+
+/// 删除文章对应的本地图片
+pub fn delete_article_images(app_data_dir: &Path, article_id: &str) {
+    let images_dir = app_data_dir.join(WEB_IMAGES_DIR);
+    let Ok(entries) = std::fs::read_dir(&images_dir) else {
+        return;
+    };
+
+    let prefix = format!("{}_", article_id);
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                eprintln!("[WebImages] Failed to delete {:?}: {}", entry.path(), e);
+            }
+        }
+    }
+}
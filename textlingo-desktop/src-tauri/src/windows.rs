@@ -0,0 +1,121 @@
+// 多窗口：阅读器、复习、统计可以在独立窗口打开（如视频在一个窗口、复习在另一个窗口）
+// 窗口的路由（界面和文章）由后端登记，新窗口启动时通过 get_window_route_cmd 取回；
+// 与文章相关的进度事件只发给主窗口和正在显示该文章的窗口。
+// 前端用 getCurrentWebviewWindow().listen 监听时按窗口过滤，全局 listen 仍会收到全部事件
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
+
+use crate::types::{WindowKind, WindowRoute};
+
+/// 主窗口的 label（tauri.conf.json 中未指定时的默认值）
+pub const MAIN_WINDOW: &str = "main";
+
+/// 窗口 label：阅读器每篇文章一个窗口，复习和统计各只有一个
+/// label 只允许字母、数字和 -_/:，文章 ID 中的其他字符替换为 _
+pub fn window_label(kind: WindowKind, article_id: Option<&str>) -> String {
+    match (kind, article_id) {
+        (WindowKind::Reader, Some(id)) => {
+            let id: String = id
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{}-{}", kind.as_str(), id)
+        }
+        _ => kind.as_str().to_string(),
+    }
+}
+
+/// 窗口加载的页面，查询参数供前端在取回路由前先显示对应界面
+pub fn window_url(route: &WindowRoute) -> String {
+    let mut url = format!("index.html?window={}", route.kind.as_str());
+    if let Some(id) = &route.article_id {
+        url.push_str("&article=");
+        url.push_str(&urlencoding::encode(id));
+    }
+    url
+}
+
+/// 已打开的独立窗口，作为 Tauri 托管状态
+#[derive(Clone, Default)]
+pub struct WindowRegistry(Arc<Mutex<HashMap<String, WindowRoute>>>);
+
+impl WindowRegistry {
+    pub fn insert(&self, route: WindowRoute) {
+        if let Ok(mut routes) = self.0.lock() {
+            routes.insert(route.label.clone(), route);
+        }
+    }
+
+    /// 窗口关闭时移除
+    pub fn remove(&self, label: &str) {
+        if let Ok(mut routes) = self.0.lock() {
+            routes.remove(label);
+        }
+    }
+
+    pub fn route(&self, label: &str) -> Option<WindowRoute> {
+        self.0.lock().ok()?.get(label).cloned()
+    }
+
+    /// 阅读器窗口切换文章时更新路由，返回窗口是否已登记
+    pub fn set_article(&self, label: &str, article_id: Option<String>) -> bool {
+        match self.0.lock() {
+            Ok(mut routes) => match routes.get_mut(label) {
+                Some(route) => {
+                    route.article_id = article_id;
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<WindowRoute> {
+        let mut routes: Vec<WindowRoute> = self
+            .0
+            .lock()
+            .map(|routes| routes.values().cloned().collect())
+            .unwrap_or_default();
+        routes.sort_by(|a, b| a.label.cmp(&b.label));
+        routes
+    }
+
+    /// 窗口是否应收到该文章的事件：主窗口和未登记的窗口都收到，
+    /// 独立窗口只收到自己所显示文章的事件
+    pub fn receives(&self, label: &str, article_id: &str) -> bool {
+        label == MAIN_WINDOW
+            || self
+                .route(label)
+                .is_none_or(|route| route.article_id.as_deref() == Some(article_id))
+    }
+}
+
+/// 发送与某篇文章相关的事件（翻译、讲解、字幕提取等进度），只发给应收到该文章事件的窗口
+pub fn emit_article_event<S: Serialize + Clone>(
+    app: &AppHandle,
+    article_id: &str,
+    event: &str,
+    payload: S,
+) {
+    let Some(registry) = app.try_state::<WindowRegistry>() else {
+        let _ = app.emit(event, payload);
+        return;
+    };
+    let _ = app.emit_filter(event, payload, |target| match target {
+        EventTarget::Window { label }
+        | EventTarget::Webview { label }
+        | EventTarget::WebviewWindow { label } => registry.receives(label, article_id),
+        _ => true,
+    });
+}
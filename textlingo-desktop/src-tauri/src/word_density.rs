@@ -0,0 +1,183 @@
+// 生词密度：按已知词库统计每段的生词数量，用于生成文章难度热力图
+// 有空格分词的语言按单词切分；中日文没有空格，按已知词做最长匹配，
+// 未匹配的汉字逐字计为生词，未匹配的假名视为语法成分不计入
+// 同样的切分也用于列出读前需要预习的生词
+
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{ArticleSegment, PreReadingWord};
+
+/// 段落中的词数和其中的生词数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCounts {
+    pub tokens: usize,
+    pub unknown: usize,
+}
+
+impl WordCounts {
+    /// 生词密度 0.0-1.0，没有可统计的词时为 0
+    pub fn density(self) -> f64 {
+        if self.tokens == 0 {
+            0.0
+        } else {
+            self.unknown as f64 / self.tokens as f64
+        }
+    }
+}
+
+/// 已知词库（小写），记录最长词的字数用于中日文最长匹配
+pub struct KnownWordSet {
+    words: HashSet<String>,
+    max_chars: usize,
+}
+
+impl KnownWordSet {
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words: HashSet<String> = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let max_chars = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+        Self { words, max_chars }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}')
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30ff}')
+}
+
+/// 切出文本中的词，对每个词调用 on_token(字符, 开始位置, 结束位置, 是否已知)；
+/// 未匹配的汉字逐字回调，未匹配的假名和纯数字跳过
+fn scan_words(
+    text: &str,
+    known: &KnownWordSet,
+    mut on_token: impl FnMut(&[char], usize, usize, bool),
+) {
+    let chars: Vec<char> = text.to_lowercase().chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if is_han(c) || is_kana(c) {
+            // 从最长的候选开始匹配已知词
+            let longest = known.max_chars.min(chars.len() - i);
+            let matched = (1..=longest).rev().find(|&len| {
+                let candidate: String = chars[i..i + len].iter().collect();
+                known.contains(&candidate)
+            });
+            match matched {
+                Some(len) => {
+                    on_token(&chars, i, i + len, true);
+                    i += len;
+                }
+                None => {
+                    if is_han(c) {
+                        on_token(&chars, i, i + 1, false);
+                    }
+                    i += 1;
+                }
+            }
+        } else if c.is_alphanumeric() {
+            let start = i;
+            while i < chars.len()
+                && !is_han(chars[i])
+                && !is_kana(chars[i])
+                && (chars[i].is_alphanumeric()
+                    || (matches!(chars[i], '\'' | '’' | '-')
+                        && chars.get(i + 1).is_some_and(|n| n.is_alphanumeric())))
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if word.chars().all(|c| c.is_numeric()) {
+                continue;
+            }
+            on_token(&chars, start, i, known.contains(&word));
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// 统计一段文本的词数和生词数
+pub fn count_words(text: &str, known: &KnownWordSet) -> WordCounts {
+    let mut counts = WordCounts::default();
+    scan_words(text, known, |_, _, _, is_known| {
+        counts.tokens += 1;
+        if !is_known {
+            counts.unknown += 1;
+        }
+    });
+    counts
+}
+
+/// 按出现顺序列出文本中的生词（小写）；中日文相邻的未知汉字合并为一个词，
+/// 如未收录的「経済」整体列出而不是拆成两个字
+pub fn unknown_words(text: &str, known: &KnownWordSet) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut han_run_end = None;
+    scan_words(text, known, |chars, start, end, is_known| {
+        if is_known {
+            return;
+        }
+        let word: String = chars[start..end].iter().collect();
+        let han = is_han(chars[start]);
+        match words.last_mut() {
+            Some(last) if han && han_run_end == Some(start) => last.push_str(&word),
+            _ => words.push(word),
+        }
+        han_run_end = han.then_some(end);
+    });
+    words
+}
+
+/// 汇总各段的生词，按出现次数从多到少排序，次数相同时按首次出现的先后；
+/// 单个字母等过短的非汉字词不列出
+pub fn pre_reading_words(segments: &[ArticleSegment], known: &KnownWordSet) -> Vec<PreReadingWord> {
+    let mut words: Vec<PreReadingWord> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for seg in segments.iter().filter(|s| s.image_url.is_none()) {
+        for word in unknown_words(&seg.text, known) {
+            if word.chars().count() < 2 && !word.chars().any(is_han) {
+                continue;
+            }
+            match positions.get(&word) {
+                Some(&index) => words[index].count += 1,
+                None => {
+                    positions.insert(word.clone(), words.len());
+                    words.push(PreReadingWord {
+                        word,
+                        count: 1,
+                        segment_id: seg.id.clone(),
+                        context: seg.text.trim().to_string(),
+                        gloss: None,
+                    });
+                }
+            }
+        }
+    }
+    // 稳定排序保留首次出现的先后
+    words.sort_by_key(|w| std::cmp::Reverse(w.count));
+    words
+}
@@ -1,26 +1,539 @@
-use crate::types::{Article, ArticleSegment};
+use crate::i18n::{tr, tr_with};
+use crate::storage::load_config;
+use crate::subtitle_files;
+use crate::types::{
+    AppConfig, Article, ArticleSegment, ChannelVideo, MediaInfo, SubtitleTrackKind, YoutubeQuality,
+    YoutubeSubtitleOptions, YoutubeSubtitleTrack,
+};
+use crate::video_server::VIDEO_SERVER_PORT;
 use chrono::Utc;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
 use uuid::Uuid;
 
 const VIDEOS_DIR: &str = "videos";
 
+/// 缩略图目录: app_data_dir/thumbnails，文件名为文章 ID
+pub const THUMBNAILS_DIR: &str = "thumbnails";
+
+/// yt-dlp 下载的缩略图可能的扩展名
+const THUMBNAIL_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "png", "webp"];
+
+/// 下载后可能得到的视频文件扩展名
+const VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "webm", "mkv", "avi", "mov"];
+
+/// 只下载音频时得到的文件扩展名
+const AUDIO_EXTENSIONS: [&str; 2] = ["m4a", "mp3"];
+
+/// 其他网站可能得到视频或音频（如 SoundCloud 只有音频）
+const MEDIA_EXTENSIONS: [&str; 7] = ["mp4", "webm", "mkv", "avi", "mov", "m4a", "mp3"];
+
+/// 下载进度行的前缀（配合 --progress-template 输出机器可读的进度）
+const PROGRESS_PREFIX: &str = "openkoto-progress";
+
+/// yt-dlp 的进度模板：已下载字节、总字节、估计总字节、速度（字节/秒）、剩余秒数，缺失的值为 NA
+const PROGRESS_TEMPLATE: &str = "download:openkoto-progress %(progress.downloaded_bytes)s %(progress.total_bytes)s %(progress.total_bytes_estimate)s %(progress.speed)s %(progress.eta)s";
+
+/// 需要登录或年龄验证的视频用配置的 cookies 下载：cookies.txt 优先，其次读取浏览器的 cookies
+pub fn cookie_args(config: &AppConfig) -> Vec<String> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    if let Some(file) = non_empty(&config.ytdlp_cookies_file) {
+        vec!["--cookies".to_string(), file]
+    } else if let Some(browser) = non_empty(&config.ytdlp_cookies_from_browser) {
+        vec!["--cookies-from-browser".to_string(), browser]
+    } else {
+        Vec::new()
+    }
+}
+
+fn configured_cookie_args(app: &AppHandle) -> Vec<String> {
+    load_config(app)
+        .ok()
+        .flatten()
+        .map(|config| cookie_args(&config))
+        .unwrap_or_default()
+}
+
+/// 视频下载进度，通过 youtube-download-progress://{event_id} 事件发送
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DownloadProgress {
+    /// 0-100，总大小未知时为 None
+    pub percent: Option<f64>,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    /// 字节/秒
+    pub speed: Option<f64>,
+    /// 预计剩余秒数
+    pub eta: Option<u64>,
+}
+
+/// 解析 PROGRESS_TEMPLATE 输出的一行，其他输出返回 None
+pub fn parse_download_progress(line: &str) -> Option<DownloadProgress> {
+    let mut fields = line
+        .trim()
+        .strip_prefix(PROGRESS_PREFIX)?
+        .split_whitespace();
+    let mut next = || {
+        fields
+            .next()
+            .filter(|value| *value != "NA" && *value != "None")
+    };
+    let downloaded_bytes = next()?.parse::<f64>().ok()? as u64;
+    let total = next().and_then(|v| v.parse::<f64>().ok());
+    let estimate = next().and_then(|v| v.parse::<f64>().ok());
+    let speed = next().and_then(|v| v.parse::<f64>().ok());
+    let eta = next()
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|eta| eta as u64);
+
+    let total_bytes = total.or(estimate).filter(|t| *t > 0.0).map(|t| t as u64);
+    Some(DownloadProgress {
+        percent: total_bytes
+            .map(|total| (downloaded_bytes as f64 / total as f64 * 100.0).clamp(0.0, 100.0)),
+        downloaded_bytes,
+        total_bytes,
+        speed,
+        eta,
+    })
+}
+
+/// 运行 yt-dlp，把下载进度转为 youtube-download-progress://{event_id} 事件
+/// 返回 (stdout, stderr, 是否成功退出)
+async fn run_with_progress(
+    app: &AppHandle,
+    args: Vec<String>,
+    event_id: &str,
+) -> Result<(String, String, bool), String> {
+    let (mut rx, _child) = app
+        .shell()
+        .sidecar("yt-dlp")
+        .map_err(|e| format!("Failed to create sidecar command: {}", e))?
+        .args(&args)
+        .spawn()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    let event_name = format!("youtube-download-progress://{}", event_id);
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    // 只在进度变化至少 0.5% 时发送，避免每个数据块都发事件
+    let mut last_percent: Option<f64> = None;
+    let mut handle_line = |line: &str| -> bool {
+        let Some(progress) = parse_download_progress(line) else {
+            if line.starts_with("[Merger]") || line.starts_with("[VideoRemuxer]") {
+                let _ = app.emit(&event_name, serde_json::json!({ "phase": "processing" }));
+            }
+            return false;
+        };
+        let changed = match (progress.percent, last_percent) {
+            (Some(percent), Some(last)) => (percent - last).abs() >= 0.5 || percent >= 100.0,
+            _ => true,
+        };
+        if changed {
+            last_percent = progress.percent;
+            let mut payload = serde_json::to_value(&progress).unwrap_or_default();
+            payload["phase"] = "downloading".into();
+            let _ = app.emit(&event_name, payload);
+        }
+        true
+    };
+
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    if !handle_line(line) {
+                        stdout.push_str(line);
+                        stdout.push('\n');
+                    }
+                }
+            }
+            CommandEvent::Stderr(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    if !handle_line(line) {
+                        stderr.push_str(line);
+                        stderr.push('\n');
+                    }
+                }
+            }
+            CommandEvent::Error(e) => return Err(format!("Failed to execute yt-dlp: {}", e)),
+            CommandEvent::Terminated(payload) => {
+                return Ok((stdout, stderr, payload.code == Some(0)));
+            }
+            _ => {}
+        }
+    }
+    Ok((stdout, stderr, false))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct YtDlpOutput {
     id: String,
     title: String,
     #[serde(default)]
     ext: String,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    uploader: Option<String>,
 }
 
-/// Import a YouTube video: download, extract subs, create Article
-/// 字幕下载是可选的，如果失败会继续导入视频（后续可用 TTS 识别）
-pub async fn import_youtube_video(app: AppHandle, url: String) -> Result<Article, String> {
+/// 从 yt-dlp 的视频 JSON 中读取作者、时长、上传日期和原始语言（缩略图下载后另行填写）
+pub fn parse_media_info(info: &Value) -> MediaInfo {
+    let text = |key: &str| {
+        info[key]
+            .as_str()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    // upload_date 为 YYYYMMDD
+    let upload_date = text("upload_date").map(|date| {
+        if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) {
+            format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..])
+        } else {
+            date
+        }
+    });
+    MediaInfo {
+        thumbnail_path: None,
+        thumbnail_url: None,
+        uploader: text("uploader").or_else(|| text("channel")),
+        duration: info["duration"].as_f64().filter(|d| *d > 0.0),
+        upload_date,
+        original_language: text("language"),
+    }
+}
+
+/// 资源服务器上的缩略图地址
+pub fn thumbnail_url(filename: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/thumbnail/{}",
+        VIDEO_SERVER_PORT,
+        urlencoding::encode(filename)
+    )
+}
+
+/// 把 yt-dlp 写在视频旁边的缩略图移到缩略图目录，返回新路径；网站没有缩略图时返回 None
+fn move_thumbnail(videos_dir: &Path, app_data_dir: &Path, file_stem: &str) -> Option<PathBuf> {
+    let (source, ext) = THUMBNAIL_EXTENSIONS.iter().find_map(|ext| {
+        let path = videos_dir.join(format!("{}.{}", file_stem, ext));
+        path.exists().then_some((path, ext))
+    })?;
+    let thumbnails_dir = app_data_dir.join(THUMBNAILS_DIR);
+    fs::create_dir_all(&thumbnails_dir).ok()?;
+    let target = thumbnails_dir.join(format!("{}.{}", file_stem, ext));
+    match fs::rename(&source, &target) {
+        Ok(()) => Some(target),
+        Err(e) => {
+            eprintln!("[YouTube] 移动缩略图失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 删除文章的缩略图
+pub fn remove_thumbnail(app_data_dir: &Path, article_id: &str) {
+    let thumbnails_dir = app_data_dir.join(THUMBNAILS_DIR);
+    for ext in THUMBNAIL_EXTENSIONS {
+        let _ = fs::remove_file(thumbnails_dir.join(format!("{}.{}", article_id, ext)));
+    }
+}
+
+/// 解析 yt-dlp -J 输出中的字幕轨：subtitles 为上传字幕，automatic_captions 为自动字幕
+/// 上传字幕排在前面，同类按语言代码排序；只提供 live_chat 等非字幕格式的条目跳过
+pub fn parse_subtitle_tracks(info: &Value) -> Vec<YoutubeSubtitleTrack> {
+    let mut tracks = Vec::new();
+    for (field, kind) in [
+        ("subtitles", SubtitleTrackKind::Manual),
+        ("automatic_captions", SubtitleTrackKind::Auto),
+    ] {
+        let Some(languages) = info[field].as_object() else {
+            continue;
+        };
+        let mut entries: Vec<YoutubeSubtitleTrack> = languages
+            .iter()
+            .filter(|(language, _)| language.as_str() != "live_chat")
+            .filter_map(|(language, formats)| {
+                let formats = formats.as_array().filter(|f| !f.is_empty())?;
+                Some(YoutubeSubtitleTrack {
+                    language: language.clone(),
+                    name: formats
+                        .iter()
+                        .find_map(|f| f["name"].as_str())
+                        .map(str::to_string),
+                    kind,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.language.cmp(&b.language));
+        tracks.extend(entries);
+    }
+    tracks
+}
+
+/// 字幕相关的 yt-dlp 参数；既不要上传字幕也不要自动字幕时不下载字幕
+pub fn subtitle_args(options: &YoutubeSubtitleOptions) -> Vec<String> {
+    let languages: Vec<&str> = options
+        .languages
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if languages.is_empty() || !(options.manual || options.auto) {
+        return Vec::new();
+    }
+    let mut args = Vec::new();
+    if options.manual {
+        args.push("--write-subs".to_string());
+    }
+    if options.auto {
+        args.push("--write-auto-subs".to_string());
+    }
+    args.push("--sub-lang".to_string());
+    args.push(languages.join(","));
+    args.push("--convert-subs".to_string());
+    args.push("srt".to_string());
+    args
+}
+
+/// 列出视频可下载的字幕轨（不下载视频）
+pub async fn list_subtitle_tracks(
+    app: &AppHandle,
+    url: &str,
+) -> Result<Vec<YoutubeSubtitleTrack>, String> {
+    let output = app
+        .shell()
+        .sidecar("yt-dlp")
+        .map_err(|e| format!("Failed to create sidecar command: {}", e))?
+        .args(configured_cookie_args(app))
+        .args([
+            "--no-warnings",
+            "--skip-download",
+            "--no-playlist",
+            "-J",
+            url,
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("Video unavailable") {
+            return Err(tr("error.video_unavailable"));
+        } else if stderr.contains("Sign in") {
+            return Err(tr("error.video_login_required"));
+        }
+        return Err(tr_with(
+            "error.subtitle_list_failed",
+            &[("error", stderr.to_string())],
+        ));
+    }
+
+    let info: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+    Ok(parse_subtitle_tracks(&info))
+}
+
+/// 频道主页地址补上 /videos，只列出上传的视频（不含直播、Shorts 等其他标签页）
+pub fn channel_videos_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let is_channel = ["/@", "/channel/", "/c/", "/user/"]
+        .iter()
+        .any(|marker| url.contains(marker));
+    let has_tab = ["/videos", "/streams", "/shorts", "/playlists", "/featured"]
+        .iter()
+        .any(|tab| url.ends_with(tab));
+    if is_channel && !has_tab {
+        format!("{}/videos", url)
+    } else {
+        url.to_string()
+    }
+}
+
+/// 解析 yt-dlp --flat-playlist -J 的输出，返回 (频道名称, 视频列表)
+/// 嵌套的播放列表（频道标签页）跳过
+pub fn parse_channel_videos(info: &Value) -> (Option<String>, Vec<ChannelVideo>) {
+    let title = ["channel", "uploader", "title"]
+        .iter()
+        .find_map(|field| info[*field].as_str())
+        .map(|title| title.trim_end_matches(" - Videos").to_string());
+    let videos = info["entries"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|entry| entry["_type"].as_str() != Some("playlist"))
+                .filter_map(|entry| {
+                    let id = entry["id"].as_str()?.to_string();
+                    let url = entry["url"]
+                        .as_str()
+                        .filter(|url| url.starts_with("http"))
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", id));
+                    Some(ChannelVideo {
+                        title: entry["title"].as_str().unwrap_or(&id).to_string(),
+                        id,
+                        url,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (title, videos)
+}
+
+/// 列出频道最新的 limit 个视频（不下载）
+pub async fn list_channel_videos(
+    app: &AppHandle,
+    url: &str,
+    limit: usize,
+) -> Result<(Option<String>, Vec<ChannelVideo>), String> {
+    let channel_url = channel_videos_url(url);
+    let output = app
+        .shell()
+        .sidecar("yt-dlp")
+        .map_err(|e| format!("Failed to create sidecar command: {}", e))?
+        .args(configured_cookie_args(app))
+        .args([
+            "--no-warnings",
+            "--flat-playlist",
+            "--playlist-end",
+            &limit.to_string(),
+            "-J",
+            &channel_url,
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(tr_with(
+            "error.channel_list_failed",
+            &[("error", stderr.to_string())],
+        ));
+    }
+
+    let info: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+    let (title, mut videos) = parse_channel_videos(&info);
+    videos.truncate(limit);
+    Ok((title, videos))
+}
+
+/// 按清晰度生成 yt-dlp 的格式参数，视频都要求 H.264 的 MP4 以便在 Mac/Win 播放
+pub fn quality_args(quality: YoutubeQuality) -> Vec<&'static str> {
+    match quality {
+        // 18: YouTube 标准 360p MP4 (H.264+AAC) - 预合并，无需 FFmpeg
+        YoutubeQuality::P360 => vec![
+            "-f",
+            "18/best[ext=mp4][vcodec^=avc1][height<=360]/worst[ext=mp4]",
+            "--remux-video",
+            "mp4",
+        ],
+        // 格式优化 - 确保下载真正的 MP4 容器：
+        // 1. 22: YouTube 标准 720p MP4 (H.264+AAC) - 预合并，无需 FFmpeg
+        // 2. 18: YouTube 标准 360p MP4 (H.264+AAC) - 预合并，无需 FFmpeg
+        // 3. 回退到任意 mp4 格式
+        // 注意：不再使用 best[ext=mp4] 因为它可能匹配到 MPEG-TS
+        YoutubeQuality::P720 => vec![
+            "-f",
+            "22/18/best[ext=mp4][vcodec^=avc1]/best[ext=mp4]",
+            "--remux-video",
+            "mp4", // 如果格式不对，重新封装为 MP4
+        ],
+        // 1080p 没有预合并格式，下载 H.264 视频流和 m4a 音频流后合并；取不到时回退到 720p
+        YoutubeQuality::P1080 => vec![
+            "-f",
+            "bestvideo[height<=1080][ext=mp4][vcodec^=avc1]+bestaudio[ext=m4a]/22/18/best[ext=mp4]",
+            "--merge-output-format",
+            "mp4",
+        ],
+        // 140: YouTube 标准 128k AAC 音频
+        YoutubeQuality::AudioOnly => vec!["-f", "140/bestaudio[ext=m4a]/bestaudio[ext=mp4]"],
+        YoutubeQuality::AudioMp3 => vec![
+            "-f",
+            "bestaudio/best",
+            "--extract-audio",
+            "--audio-format",
+            "mp3",
+        ],
+    }
+}
+
+/// 其他网站的格式参数：没有 YouTube 的固定格式编号，按高度选择，优先 MP4
+pub fn generic_quality_args(quality: YoutubeQuality) -> Vec<&'static str> {
+    let format = match quality {
+        YoutubeQuality::P360 => {
+            "best[ext=mp4][height<=360]/bestvideo[ext=mp4][height<=360]+bestaudio[ext=m4a]/best[height<=360]/worst"
+        }
+        YoutubeQuality::P720 => {
+            "best[ext=mp4][height<=720]/bestvideo[ext=mp4][height<=720]+bestaudio[ext=m4a]/best[height<=720]/best"
+        }
+        YoutubeQuality::P1080 => {
+            "best[ext=mp4][height<=1080]/bestvideo[ext=mp4][height<=1080]+bestaudio[ext=m4a]/best[height<=1080]/best"
+        }
+        // SoundCloud 等音频网站常只有 mp3 或 opus，转为 m4a（需要 FFmpeg）；已是 m4a 时不重新转换
+        YoutubeQuality::AudioOnly => {
+            return vec![
+                "-f",
+                "bestaudio[ext=m4a]/bestaudio/best",
+                "--extract-audio",
+                "--audio-format",
+                "m4a",
+            ]
+        }
+        YoutubeQuality::AudioMp3 => return quality_args(quality),
+    };
+    vec![
+        "-f",
+        format,
+        "--merge-output-format",
+        "mp4",
+        "--remux-video",
+        "mp4",
+    ]
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// 一次 yt-dlp 下载的结果
+struct DownloadedMedia {
+    metadata: YtDlpOutput,
+    media_info: MediaInfo,
+    media_path: PathBuf,
+    /// 字幕段落，没有字幕时为空（后续可用 TTS 识别）
+    segments: Vec<ArticleSegment>,
+}
+
+/// 用 yt-dlp 下载媒体和字幕到 videos 目录，文件名为 file_stem，未指定时用视频 ID；
+/// 下载后按 extensions 查找媒体文件。
+/// 字幕下载是可选的，网站没有字幕或下载失败时仍然导入媒体
+async fn download_media(
+    app: &AppHandle,
+    url: &str,
+    subtitles: &YoutubeSubtitleOptions,
+    format_args: Vec<&str>,
+    file_stem: Option<&str>,
+    extensions: &[&str],
+    event_id: &str,
+) -> Result<DownloadedMedia, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -34,49 +547,44 @@ pub async fn import_youtube_video(app: AppHandle, url: String) -> Result<Article
 
     // 1. Run yt-dlp to download video and subs
     // Output template: videos_dir/%(id)s.%(ext)s
-    let output_template = videos_dir.join("%(id)s.%(ext)s");
+    let output_template = videos_dir.join(format!("{}.%(ext)s", file_stem.unwrap_or("%(id)s")));
     let output_template_str = output_template.to_str().ok_or("Invalid output path")?;
 
-    let shell = app.shell();
+    let subtitle_args = subtitle_args(subtitles);
 
     // 使用 --ignore-errors 让字幕下载失败时继续
     // 使用 --no-warnings 减少警告输出
-    // 格式选择器说明:
-    // - best[ext=mp4]: 优先选择已合并的 MP4（无需 FFmpeg）
-    // - bestvideo+bestaudio: 如果没有合并格式，下载最佳并尝试合并
-    // - best: 最后的回退选项
-    let output = shell
-        .sidecar("yt-dlp")
-        .map_err(|e| format!("Failed to create sidecar command: {}", e))?
-        .args([
-            "--no-warnings",   // 忽略警告（如 JS runtime 警告）
-            "--ignore-errors", // 忽略非致命错误（如字幕下载失败）
-            "--write-auto-sub",
-            "--sub-lang",
-            "en,zh-Hans,zh-Hant", // 首选语言
-            "--convert-subs",
-            "srt",
-            // 格式优化 - 确保下载真正的 MP4 容器：
-            // 1. 22: YouTube 标准 720p MP4 (H.264+AAC) - 预合并，无需 FFmpeg
-            // 2. 18: YouTube 标准 360p MP4 (H.264+AAC) - 预合并，无需 FFmpeg
-            // 3. 回退到任意 mp4 格式
-            // 注意：不再使用 best[ext=mp4] 因为它可能匹配到 MPEG-TS
-            "-f",
-            "22/18/best[ext=mp4][vcodec^=avc1]/best[ext=mp4]",
-            "--remux-video",
-            "mp4", // 如果格式不对，重新封装为 MP4
+    // 格式选择器见 quality_args
+    let mut args: Vec<String> = [
+        "--no-warnings",   // 忽略警告（如 JS runtime 警告）
+        "--ignore-errors", // 忽略非致命错误（如字幕下载失败）
+        "--no-playlist",   // 链接指向播放列表中的视频时只下载该视频
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+    args.extend(configured_cookie_args(app));
+    args.extend(subtitle_args);
+    args.extend(format_args.iter().map(|arg| arg.to_string()));
+    args.extend(
+        [
             "-o",
             output_template_str,
-            "--print-json", // 获取元数据
+            "--print-json",      // 获取元数据
+            "--write-thumbnail", // 缩略图供资料库显示
             "--no-simulate",
-            &url,
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+            // --print-json 隐含安静模式，需要显式打开进度输出，每次更新单独一行
+            "--progress",
+            "--newline",
+            "--progress-template",
+            PROGRESS_TEMPLATE,
+            url,
+        ]
+        .iter()
+        .map(|arg| arg.to_string()),
+    );
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (stdout, stderr, success) = run_with_progress(app, args, event_id).await?;
 
     // 检查是否有 JSON 输出（视频下载成功的标志）
     let json_line = stdout.lines().filter(|l| l.starts_with('{')).last();
@@ -86,60 +594,90 @@ pub async fn import_youtube_video(app: AppHandle, url: String) -> Result<Article
         Some(line) => line,
         None => {
             // 检查 stderr 中是否有更具体的错误信息
-            if stderr.contains("Video unavailable") {
-                return Err("视频不可用，可能是私有视频或已被删除".to_string());
+            if stderr.contains("Unsupported URL") {
+                return Err(tr("error.media_url_unsupported"));
+            } else if stderr.contains("Video unavailable") {
+                return Err(tr("error.video_unavailable"));
             } else if stderr.contains("Sign in") {
-                return Err("此视频需要登录才能观看".to_string());
+                return Err(tr("error.video_login_required"));
             } else if stderr.contains("ffmpeg") || stderr.contains("FFmpeg") {
-                return Err("需要安装 FFmpeg 才能下载此视频。请安装后重试。".to_string());
-            } else if !output.status.success() {
-                return Err(format!("视频下载失败: {}", stderr));
+                return Err(tr("error.ffmpeg_required"));
+            } else if !success {
+                return Err(tr_with(
+                    "error.video_download_failed",
+                    &[("error", stderr.to_string())],
+                ));
             } else {
-                return Err("无法获取视频信息".to_string());
+                return Err(tr("error.video_info_missing"));
             }
         }
     };
 
-    let metadata: YtDlpOutput =
+    let info: Value =
         serde_json::from_str(json_line).map_err(|e| format!("Failed to parse metadata: {}", e))?;
-
-    let video_id = metadata.id;
-    let video_title = metadata.title;
+    let mut media_info = parse_media_info(&info);
+    let metadata: YtDlpOutput =
+        serde_json::from_value(info).map_err(|e| format!("Failed to parse metadata: {}", e))?;
+    let file_stem = file_stem.unwrap_or(&metadata.id);
+
+    if let Some(thumbnail) = move_thumbnail(&videos_dir, &app_data_dir, file_stem) {
+        let filename = thumbnail
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        media_info.thumbnail_url = Some(thumbnail_url(&filename));
+        media_info.thumbnail_path = Some(thumbnail.to_string_lossy().into_owned());
+    }
 
     // 查找实际下载的视频文件（可能是 .mp4, .webm 等）
-    let video_path = find_video_file(&videos_dir, &video_id)?;
+    let media_path = find_video_file(&videos_dir, file_stem, extensions)?;
 
     // 验证视频格式是否能在 Mac/Win 平台播放
-    verify_video_format(&video_path)?;
+    if !is_audio_file(&media_path) {
+        verify_video_format(&media_path)?;
+    }
 
     // 2. 查找字幕文件（可选，失败不报错）
     // yt-dlp pattern: {id}.{lang}.srt
-    let segments = match find_srt_file(&videos_dir, &video_id) {
-        Ok(srt_path) => {
-            // 字幕文件存在，解析它
-            match parse_srt(&srt_path) {
-                Ok(mut segs) => {
-                    for segment in &mut segs {
-                        segment.article_id = video_id.clone();
-                    }
-                    segs
-                }
-                Err(_) => {
-                    // 字幕解析失败，返回空列表
-                    Vec::new()
-                }
-            }
-        }
-        Err(_) => {
-            // 没有找到字幕文件，返回空列表（后续可用 TTS 识别）
-            Vec::new()
-        }
+    let segments = match find_srt_file(&videos_dir, file_stem, &subtitles.languages) {
+        // 字幕解析失败时返回空列表
+        Ok(srt_path) => parse_srt(&srt_path).unwrap_or_default(),
+        // 没有找到字幕文件，返回空列表（后续可用 TTS 识别）
+        Err(_) => Vec::new(),
     };
 
+    Ok(DownloadedMedia {
+        metadata,
+        media_info,
+        media_path,
+        segments,
+    })
+}
+
+/// 由下载结果构建文章，没有字幕时内容为占位文本
+fn media_article(
+    id: String,
+    url: String,
+    source_type: &str,
+    downloaded: DownloadedMedia,
+) -> Article {
+    let DownloadedMedia {
+        metadata,
+        media_info,
+        media_path,
+        mut segments,
+    } = downloaded;
+    for segment in &mut segments {
+        segment.article_id = id.clone();
+    }
+
     // 3. 构建内容文本
     let content = if segments.is_empty() {
         // 没有字幕时，使用占位文本
-        format!("[视频已导入，字幕待识别] {}", video_title)
+        tr_with(
+            "article.subtitles_pending",
+            &[("title", metadata.title.clone())],
+        )
     } else {
         segments
             .iter()
@@ -149,21 +687,85 @@ pub async fn import_youtube_video(app: AppHandle, url: String) -> Result<Article
     };
 
     // 4. Create Article
-    let article = Article {
-        id: video_id.clone(),
-        title: video_title,
+    Article {
+        id,
+        title: metadata.title,
         content,
-        source_type: Some("youtube".to_string()),
+        source_type: Some(source_type.to_string()),
         source_url: Some(url),
-        media_path: Some(video_path.to_string_lossy().into_owned()),
+        media_path: Some(media_path.to_string_lossy().into_owned()),
         book_path: None,
         book_type: None,
+        language: None,
         created_at: Utc::now().to_rfc3339(),
         translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: metadata.channel.or(metadata.uploader),
+        tags: Vec::new(),
+        media_info: Some(media_info),
         segments,
+    }
+}
+
+/// Import a YouTube video: download, extract subs, create Article
+/// 字幕下载是可选的，如果失败会继续导入视频（后续可用 TTS 识别）
+pub async fn import_youtube_video(
+    app: AppHandle,
+    url: String,
+    subtitles: YoutubeSubtitleOptions,
+    quality: YoutubeQuality,
+    event_id: &str,
+) -> Result<Article, String> {
+    // 只下载音频时只找音频文件，避免取到以前以视频导入时留下的文件
+    let extensions: &[&str] = if quality.is_audio_only() {
+        &AUDIO_EXTENSIONS
+    } else {
+        &VIDEO_EXTENSIONS
     };
+    let downloaded = download_media(
+        &app,
+        &url,
+        &subtitles,
+        quality_args(quality),
+        None,
+        extensions,
+        event_id,
+    )
+    .await?;
+    // 文章 ID 即视频 ID，频道订阅据此判断视频是否已导入
+    let id = downloaded.metadata.id.clone();
+    Ok(media_article(id, url, "youtube", downloaded))
+}
 
-    Ok(article)
+/// 导入任意 yt-dlp 支持的网站（Vimeo、SoundCloud、新闻网站等）的视频或音频
+/// 不同网站的视频 ID 可能重复，文章和文件使用新生成的 ID
+pub async fn import_media_url(
+    app: AppHandle,
+    url: String,
+    subtitles: YoutubeSubtitleOptions,
+    quality: YoutubeQuality,
+    event_id: &str,
+) -> Result<Article, String> {
+    let id = Uuid::new_v4().to_string();
+    let downloaded = download_media(
+        &app,
+        &url,
+        &subtitles,
+        generic_quality_args(quality),
+        Some(&id),
+        &MEDIA_EXTENSIONS,
+        event_id,
+    )
+    .await?;
+    // 只有音频的网站（如 SoundCloud）即使未选择只下载音频，得到的也是音频文件
+    let source_type = if is_audio_file(&downloaded.media_path) {
+        "audio"
+    } else {
+        "local_video"
+    };
+    Ok(media_article(id, url, source_type, downloaded))
 }
 
 /// 验证视频格式是否能在 Mac/Win 平台播放
@@ -171,12 +773,13 @@ pub async fn import_youtube_video(app: AppHandle, url: String) -> Result<Article
 fn verify_video_format(path: &Path) -> Result<(), String> {
     use std::io::Read;
 
-    let mut file = fs::File::open(path).map_err(|e| format!("无法打开视频文件验证: {}", e))?;
+    let mut file = fs::File::open(path)
+        .map_err(|e| tr_with("error.video_unreadable", &[("error", e.to_string())]))?;
 
     // 读取文件前 12 字节
     let mut header = [0u8; 12];
     file.read_exact(&mut header)
-        .map_err(|e| format!("无法读取视频文件头: {}", e))?;
+        .map_err(|e| tr_with("error.video_unreadable", &[("error", e.to_string())]))?;
 
     // MP4 文件格式检查：
     // MP4 文件以 ftyp atom 开头，格式为：
@@ -219,8 +822,7 @@ fn verify_video_format(path: &Path) -> Result<(), String> {
 }
 
 /// 查找实际下载的视频文件（可能是 .mp4, .webm, .mkv 等格式）
-fn find_video_file(dir: &Path, video_id: &str) -> Result<PathBuf, String> {
-    let video_extensions = ["mp4", "webm", "mkv", "m4a", "avi", "mov"];
+fn find_video_file(dir: &Path, video_id: &str, extensions: &[&str]) -> Result<PathBuf, String> {
     let entries = fs::read_dir(dir).map_err(|e| e.to_string())?;
 
     // 优先查找完整的视频文件（不包含格式代码如 .f398.mp4）
@@ -233,7 +835,7 @@ fn find_video_file(dir: &Path, video_id: &str) -> Result<PathBuf, String> {
             // 文件名必须以 video_id 开头
             if fname.starts_with(video_id) {
                 // 检查是否是视频文件
-                for ext in &video_extensions {
+                for ext in extensions {
                     if fname.ends_with(&format!(".{}", ext)) {
                         all_matches.push(path.clone());
                         break;
@@ -257,11 +859,19 @@ fn find_video_file(dir: &Path, video_id: &str) -> Result<PathBuf, String> {
     all_matches
         .into_iter()
         .next()
-        .ok_or_else(|| format!("未找到视频文件: {}", video_id))
+        .ok_or_else(|| tr_with("error.video_file_missing", &[("id", video_id.to_string())]))
 }
 
-fn find_srt_file(dir: &Path, video_id: &str) -> Result<PathBuf, String> {
+fn find_srt_file(dir: &Path, video_id: &str, languages: &[String]) -> Result<PathBuf, String> {
     // Check for common patterns: id.en.srt, id.zh-Hans.srt, etc.
+    // 下载了多种语言时按首选顺序选用
+    for language in languages {
+        let path = dir.join(format!("{}.{}.srt", video_id, language.trim()));
+        if path.exists() {
+            return Ok(path);
+        }
+    }
+
     let entries = fs::read_dir(dir).map_err(|e| e.to_string())?;
 
     for entry in entries {
@@ -279,79 +889,9 @@ fn find_srt_file(dir: &Path, video_id: &str) -> Result<PathBuf, String> {
 
 fn parse_srt(path: &Path) -> Result<Vec<ArticleSegment>, String> {
     let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let mut segments = Vec::new();
-
-    // Simple SRT parser
-    // Block format:
-    // 1
-    // 00:00:00,000 --> 00:00:02,000
-    // Text line 1
-    // Text line 2
-
-    let blocks: Vec<&str> = content.split("\n\n").collect();
-    let time_regex =
-        Regex::new(r"(\d{2}:\d{2}:\d{2},\d{3}) --> (\d{2}:\d{2}:\d{2},\d{3})").unwrap();
-
-    for block in blocks {
-        let lines: Vec<&str> = block.lines().collect();
-        if lines.len() >= 3 {
-            // Line 0: Index
-            // Line 1: Timestamp
-            // Line 2+: Text
-            if let Some(caps) = time_regex.captures(lines[1]) {
-                let start_str = &caps[1];
-                let end_str = &caps[2];
-
-                let start_time = parse_srt_timestamp(start_str);
-                let end_time = parse_srt_timestamp(end_str);
-
-                let text = lines[2..].join(" ");
-
-                // Clean text (remove HTML tags if any)
-                let text = text
-                    .replace("<i>", "")
-                    .replace("</i>", "")
-                    .trim()
-                    .to_string();
-
-                if !text.is_empty() {
-                    segments.push(ArticleSegment {
-                        id: Uuid::new_v4().to_string(),
-                        article_id: String::new(), // Will be set by caller
-                        order: segments.len() as i32,
-                        text,
-                        reading_text: None,
-                        translation: None,
-                        explanation: None,
-                        start_time,
-                        end_time,
-                        created_at: Utc::now().to_rfc3339(),
-                        is_new_paragraph: true, // SRT blocks usually separate sentences/phrases
-                    });
-                }
-            }
-        }
-    }
-
-    Ok(segments)
-}
-
-fn parse_srt_timestamp(ts: &str) -> Option<f64> {
-    // format: 00:00:00,000
-    let parts: Vec<&str> = ts.split(',').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let time_parts: Vec<&str> = parts[0].split(':').collect();
-    if time_parts.len() != 3 {
-        return None;
-    }
-
-    let h: f64 = time_parts[0].parse().ok()?;
-    let m: f64 = time_parts[1].parse().ok()?;
-    let s: f64 = time_parts[2].parse().ok()?;
-    let ms: f64 = parts[1].parse().ok()?;
-
-    Some(h * 3600.0 + m * 60.0 + s + ms / 1000.0)
+    // article_id 由调用方设置
+    Ok(subtitle_files::cues_to_segments(
+        "",
+        &subtitle_files::parse_srt(&content),
+    ))
 }
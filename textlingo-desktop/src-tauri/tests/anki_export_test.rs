@@ -0,0 +1,127 @@
+use openkoto_desktop_lib::anki_export::{
+    article_cards, default_deck_name, render_anki_tsv, AnkiCardKind,
+};
+use openkoto_desktop_lib::types::Article;
+use serde_json::{json, Value};
+
+fn segment(order: i32, text: &str, explanation: Option<Value>) -> Value {
+    json!({
+        "id": format!("s{}", order),
+        "article_id": "a1",
+        "order": order,
+        "text": text,
+        "translation": format!("译文 {}", order),
+        "explanation": explanation,
+        "created_at": "2026-05-01T00:00:00Z",
+    })
+}
+
+fn explanation(words: &[(&str, &str)], points: &[(&str, Option<&str>)]) -> Value {
+    json!({
+        "translation": "",
+        "explanation": "",
+        "vocabulary": words
+            .iter()
+            .map(|(word, meaning)| json!({
+                "word": word,
+                "meaning": meaning,
+                "usage": "",
+                "reading": "よみ",
+            }))
+            .collect::<Vec<_>>(),
+        "grammar_points": points
+            .iter()
+            .map(|(point, example)| json!({
+                "point": point,
+                "explanation": "表示原因",
+                "example": example,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn article(segments: Vec<Value>) -> Article {
+    serde_json::from_value(json!({
+        "id": "a1",
+        "title": "雨の日",
+        "content": "",
+        "created_at": "2026-05-01T00:00:00Z",
+        "translated": true,
+        "segments": segments,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn builds_cards_from_every_segment_explanation() {
+    let article = article(vec![
+        segment(
+            1,
+            "雨なので、傘を持っていく。",
+            Some(explanation(
+                &[("傘", "雨伞")],
+                &[("〜ので", Some("寒いので窓を閉める。"))],
+            )),
+        ),
+        segment(0, "今日は雨だ。", Some(explanation(&[("雨", "雨")], &[]))),
+        segment(2, "没有讲解的分段。", None),
+        // 重复出现的单词只生成一张卡
+        segment(3, "雨が止んだ。", Some(explanation(&[("雨", "雨")], &[]))),
+    ]);
+
+    let cards = article_cards(&article);
+    let kinds: Vec<AnkiCardKind> = cards.iter().map(|c| c.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            AnkiCardKind::Vocabulary,
+            AnkiCardKind::Vocabulary,
+            AnkiCardKind::Grammar
+        ]
+    );
+    // 按分段顺序
+    assert_eq!(cards[0].front, "雨");
+    assert_eq!(cards[1].front, "傘");
+
+    // 背面含例句（单词加粗）、译文和出处
+    let back = &cards[1].back;
+    assert!(back.contains("雨なので、<b>傘</b>を持っていく。"));
+    assert!(back.contains("译文 1"));
+    assert!(back.contains("よみ"));
+    assert!(back.contains("<div class=\"source\">雨の日</div>"));
+
+    // 语法卡保留讲解自带的例句和所在分段
+    assert!(cards[2].back.contains("寒いので窓を閉める。"));
+    assert!(cards[2].back.contains("雨なので、傘を持っていく。"));
+}
+
+#[test]
+fn renders_an_anki_import_file() {
+    let mut seg = segment(
+        0,
+        "a <b>\tline\nbreak",
+        Some(explanation(&[("line", "行")], &[])),
+    );
+    seg["translation"] = Value::Null;
+    let article = article(vec![seg]);
+    assert_eq!(default_deck_name(&article), "OpenKoto::雨の日");
+
+    let tsv = render_anki_tsv(&article_cards(&article), "Deck\tName");
+    let lines: Vec<&str> = tsv.lines().collect();
+    assert_eq!(
+        &lines[..4],
+        [
+            "#separator:tab",
+            "#html:true",
+            "#deck:Deck Name",
+            "#tags column:3"
+        ]
+    );
+    assert_eq!(lines.len(), 5);
+    // 每行正好三列，字段内的 HTML 被转义，换行转为 <br>
+    let columns: Vec<&str> = lines[4].split('\t').collect();
+    assert_eq!(columns.len(), 3);
+    assert!(columns[1].contains("a &lt;b&gt; <b>line</b><br>break"));
+    assert_eq!(columns[2], "openkoto vocabulary");
+    assert!(render_anki_tsv(&[], "Deck").ends_with("#tags column:3\n"));
+}
@@ -0,0 +1,69 @@
+use openkoto_desktop_lib::error::{AppError, ErrorKind};
+use openkoto_desktop_lib::jobs::rate_limit_error;
+use serde_json::json;
+
+#[test]
+fn string_errors_are_internal_whatever_the_text() {
+    // 类别在出错处构造，不根据信息文本猜测
+    for message in [
+        "AI service not initialized",
+        "Failed to send request: connection refused",
+        "Article not found",
+        "FFmpeg 执行失败: exit 1",
+        "Task cancelled",
+        "任务已取消",
+        "Invalid URL format",
+        "API error: 429 Too Many Requests",
+    ] {
+        let error = AppError::from(message.to_string());
+        assert_eq!(error.kind, ErrorKind::Internal, "{}", message);
+        assert!(!error.retryable);
+    }
+}
+
+#[test]
+fn ai_provider_errors_recognize_rate_limits() {
+    let limited = AppError::ai_provider(rate_limit_error("API", Some(30), "slow down"));
+    assert_eq!(limited.kind, ErrorKind::RateLimited);
+    assert!(limited.retryable);
+
+    // ID、数量中碰巧出现的 429 不算限流
+    let other = AppError::ai_provider("API error: 500 model 429b overloaded");
+    assert_eq!(other.kind, ErrorKind::AiProvider);
+    assert!(other.retryable);
+}
+
+#[test]
+fn retryable_flag_follows_kind() {
+    assert!(AppError::network("offline").retryable);
+    assert!(AppError::ai_provider("API error: 503 Service Unavailable").retryable);
+    assert!(!AppError::not_found("Segment not found").retryable);
+    assert!(!AppError::invalid_input("Invalid mode").retryable);
+}
+
+#[test]
+fn app_error_serializes_for_frontend() {
+    let error = AppError::not_found("Article not found").with_details(json!({ "id": "a1" }));
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(value["kind"], "not_found");
+    assert_eq!(value["message"], "Article not found");
+    assert_eq!(value["retryable"], false);
+    assert_eq!(value["details"]["id"], "a1");
+
+    let plain = serde_json::to_value(AppError::network("offline")).unwrap();
+    assert!(plain.get("details").is_none());
+}
+
+#[test]
+fn offline_mode_fails_fast_with_offline_error() {
+    use openkoto_desktop_lib::offline;
+
+    offline::set_offline_mode(true);
+    let error = offline::ensure_online().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Offline);
+    assert!(!error.retryable);
+
+    offline::set_offline_mode(false);
+    assert!(offline::ensure_online().is_ok());
+}
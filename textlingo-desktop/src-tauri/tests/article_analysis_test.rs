@@ -0,0 +1,47 @@
+use openkoto_desktop_lib::types::{Article, ArticleAnalysis};
+
+#[test]
+fn articles_saved_before_analyses_still_load() {
+    let json = r#"{
+        "id": "a1",
+        "title": "Old article",
+        "content": "Hello.",
+        "source_url": null,
+        "media_path": null,
+        "created_at": "2024-01-01T00:00:00Z",
+        "translated": false
+    }"#;
+    let article: Article = serde_json::from_str(json).unwrap();
+    assert!(article.analyses.is_empty());
+    assert!(article.segmentation.is_none());
+}
+
+#[test]
+fn analyses_round_trip_by_type() {
+    let json = r#"{
+        "id": "a1",
+        "title": "Article",
+        "content": "Hello.",
+        "source_url": null,
+        "media_path": null,
+        "created_at": "2024-01-01T00:00:00Z",
+        "translated": false,
+        "analyses": {
+            "summary": {
+                "analysis_type": "summary",
+                "result": "A short greeting.",
+                "model": "gpt-4o-mini",
+                "created_at": "2024-01-02T00:00:00Z"
+            }
+        }
+    }"#;
+    let article: Article = serde_json::from_str(json).unwrap();
+    let summary: &ArticleAnalysis = &article.analyses["summary"];
+    assert_eq!(summary.result, "A short greeting.");
+    // 前端直接保存的结果没有分块数
+    assert_eq!(summary.chunk_count, 0);
+
+    let reparsed: Article =
+        serde_json::from_str(&serde_json::to_string(&article).unwrap()).unwrap();
+    assert_eq!(reparsed.analyses["summary"].model, "gpt-4o-mini");
+}
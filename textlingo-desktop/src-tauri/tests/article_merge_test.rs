@@ -0,0 +1,87 @@
+use openkoto_desktop_lib::article_merge::merge_articles;
+use openkoto_desktop_lib::types::Article;
+use serde_json::json;
+use std::collections::HashSet;
+
+fn article(id: &str, source_url: &str, tags: &[&str], texts: &[(i32, &str)]) -> Article {
+    let segments: Vec<_> = texts
+        .iter()
+        .map(|(order, text)| {
+            json!({
+                "id": format!("{}-{}", id, order),
+                "article_id": id,
+                "order": order,
+                "text": text,
+                "reading_text": null,
+                "translation": format!("译:{}", text),
+                "explanation": null,
+                "created_at": "2026-01-01T00:00:00Z",
+                "is_new_paragraph": false
+            })
+        })
+        .collect();
+    serde_json::from_value(json!({
+        "id": id,
+        "title": format!("Part {}", id),
+        "content": texts.iter().map(|(_, t)| *t).collect::<Vec<_>>().join("\n") + "\n",
+        "source_type": "web",
+        "source_url": source_url,
+        "media_path": null,
+        "language": "ja",
+        "created_at": "2026-01-01T00:00:00Z",
+        "translated": true,
+        "tags": tags,
+        "segments": segments
+    }))
+    .unwrap()
+}
+
+#[test]
+fn merges_content_and_segments_in_the_given_order() {
+    let first = article(
+        "a",
+        "https://blog/1",
+        &["serial"],
+        &[(1, "二文目。"), (0, "一文目。")],
+    );
+    let second = article("b", "https://blog/2", &["serial", "n4"], &[(0, "三文目。")]);
+
+    let merged = merge_articles(&[first, second], "m", "  ", "2026-02-01T00:00:00Z");
+
+    assert_eq!(merged.id, "m");
+    // 标题为空时沿用第一篇
+    assert_eq!(merged.title, "Part a");
+    assert_eq!(merged.content, "二文目。\n一文目。\n\n三文目。");
+    let texts: Vec<&str> = merged.segments.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(texts, ["一文目。", "二文目。", "三文目。"]);
+    // 段落换用新 ID，不与原文章的段落重复
+    let ids: HashSet<&str> = merged.segments.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(!ids.contains("a-0") && !ids.contains("a-1") && !ids.contains("b-0"));
+    assert!(merged.segments.iter().all(|s| s.article_id == "m"));
+    let orders: Vec<i32> = merged.segments.iter().map(|s| s.order).collect();
+    assert_eq!(orders, [0, 1, 2]);
+    assert!(merged.segments[2].is_new_paragraph);
+    assert_eq!(
+        merged.segments[2].translation.as_deref(),
+        Some("译:三文目。")
+    );
+
+    // 只有各篇一致的来源信息才保留
+    assert_eq!(merged.source_type.as_deref(), Some("web"));
+    assert_eq!(merged.source_url, None);
+    assert_eq!(merged.language.as_deref(), Some("ja"));
+    assert!(merged.translated);
+    assert_eq!(merged.tags, ["serial", "n4"]);
+}
+
+#[test]
+fn explicit_title_is_trimmed() {
+    let parts = [
+        article("a", "https://blog", &[], &[(0, "a")]),
+        article("b", "https://blog", &[], &[(0, "b")]),
+    ];
+    let merged = merge_articles(&parts, "m", " Full story ", "2026-02-01T00:00:00Z");
+    assert_eq!(merged.title, "Full story");
+    assert_eq!(merged.source_url.as_deref(), Some("https://blog"));
+}
@@ -0,0 +1,64 @@
+use openkoto_desktop_lib::article_upgrade::{
+    backup_file_name, upgrade_article_json, ArticleUpgrade,
+};
+use openkoto_desktop_lib::types::Article;
+use serde_json::json;
+
+fn legacy_article() -> serde_json::Value {
+    // 旧版文章：缺少后来新增的字段，段落顺序和归属不一致
+    json!({
+        "id": "a1",
+        "title": "Old",
+        "content": "One. Two.",
+        "source_url": null,
+        "created_at": "2024-01-01T00:00:00Z",
+        "translated": false,
+        "segments": [
+            {"id": "s2", "article_id": "old", "order": 5, "text": "Two.",
+             "reading_text": null, "translation": null, "explanation": null,
+             "created_at": "2024-01-01T00:00:00Z"},
+            {"id": "s1", "article_id": "a1", "order": 2, "text": "One.",
+             "reading_text": null, "translation": null, "explanation": null,
+             "created_at": "2024-01-01T00:00:00Z"}
+        ]
+    })
+}
+
+#[test]
+fn rewrites_legacy_articles_to_the_latest_shape() {
+    let ArticleUpgrade::Upgraded(upgraded) =
+        upgrade_article_json(&legacy_article().to_string()).unwrap()
+    else {
+        panic!("legacy article should be upgraded");
+    };
+    let value: serde_json::Value = serde_json::from_str(&upgraded).unwrap();
+    // 默认字段显式写出
+    assert_eq!(value["tags"], json!([]));
+    assert!(value.get("channel").is_some());
+
+    let article: Article = serde_json::from_value(value).unwrap();
+    let segments: Vec<(&str, i32, &str)> = article
+        .segments
+        .iter()
+        .map(|s| (s.id.as_str(), s.order, s.article_id.as_str()))
+        .collect();
+    assert_eq!(segments, [("s1", 0, "a1"), ("s2", 1, "a1")]);
+
+    // 再次升级时已是最新结构
+    assert_eq!(
+        upgrade_article_json(&upgraded).unwrap(),
+        ArticleUpgrade::Unchanged
+    );
+}
+
+#[test]
+fn reports_unparseable_articles() {
+    assert!(upgrade_article_json("{not json").is_err());
+    assert!(upgrade_article_json(r#"{"id": "a1"}"#).is_err());
+}
+
+#[test]
+fn backup_names_are_safe_file_names() {
+    assert_eq!(backup_file_name("3f2a-9c_x"), "3f2a-9c_x.json");
+    assert_eq!(backup_file_name("../a b"), "___a_b.json");
+}
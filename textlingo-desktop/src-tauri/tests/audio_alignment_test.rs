@@ -0,0 +1,75 @@
+use openkoto_desktop_lib::audio_alignment::{
+    align_segments, align_tokens, tokenize, transcript_tokens,
+};
+use openkoto_desktop_lib::subtitle_files::SubtitleCue;
+
+fn cue(start: f64, end: f64, text: &str) -> SubtitleCue {
+    SubtitleCue {
+        start,
+        end,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn tokenizes_words_and_cjk_characters() {
+    assert_eq!(
+        tokenize("Don't STOP, \"now\"!"),
+        vec!["dont", "stop", "now"]
+    );
+    assert_eq!(tokenize("猫が好き。ok"), vec!["猫", "が", "好", "き", "ok"]);
+}
+
+#[test]
+fn interpolates_word_times_within_cue() {
+    let tokens = transcript_tokens(&[cue(10.0, 14.0, "ab cd")]);
+    assert_eq!(tokens.len(), 2);
+    assert_eq!((tokens[0].start, tokens[0].end), (10.0, 12.0));
+    assert_eq!((tokens[1].start, tokens[1].end), (12.0, 14.0));
+}
+
+#[test]
+fn aligns_segments_despite_transcription_errors() {
+    let segments = [
+        "Chapter One",
+        "It was a bright cold day in April.",
+        "The clocks were striking thirteen.",
+        "[Illustration]",
+        "Winston hurried home.",
+    ];
+    // 转录稿没有读章节标题，且把 thirteen 听成了 thirty
+    let cues = [
+        cue(0.0, 3.0, "it was a bright cold day in april"),
+        cue(3.0, 6.0, "the clocks were striking thirty"),
+        cue(7.0, 9.0, "winston hurried home"),
+    ];
+    let spans = align_segments(&segments, &cues);
+    assert_eq!(spans[0], None);
+    assert_eq!(spans[1], Some((0.0, 3.0)));
+    let (start, end) = spans[2].unwrap();
+    assert_eq!(start, 3.0);
+    assert!(end < 6.0 && end > 5.0);
+    // 插图说明夹在前后两段之间
+    assert_eq!(spans[3], Some((end, 7.0)));
+    assert_eq!(spans[4], Some((7.0, 9.0)));
+}
+
+#[test]
+fn long_texts_align_in_windows() {
+    let words: Vec<String> = (0..2000).map(|i| format!("w{}", i)).collect();
+    let mut transcript_words = words.clone();
+    transcript_words.insert(0, "intro".to_string());
+    transcript_words.remove(1501);
+    let cues: Vec<SubtitleCue> = transcript_words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| cue(i as f64, i as f64 + 1.0, w))
+        .collect();
+    let transcript = transcript_tokens(&cues);
+    let matches = align_tokens(&words, &transcript);
+    assert_eq!(matches[0], Some(1));
+    assert_eq!(matches[1499], Some(1500));
+    assert_eq!(matches[1500], None);
+    assert_eq!(matches[1999], Some(1999));
+    assert_eq!(matches.iter().filter(|m| m.is_some()).count(), 1999);
+}
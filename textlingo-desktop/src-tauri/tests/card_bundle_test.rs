@@ -0,0 +1,95 @@
+use chrono::TimeZone;
+use openkoto_desktop_lib::card_bundle::{
+    bundle_dir_name, render_index_html, write_bundle, BundleCard, BundlePack, CardBundle,
+    CARD_BUNDLE_SCHEMA,
+};
+use openkoto_desktop_lib::types::FavoriteVocabulary;
+
+fn vocab() -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: "v1".to_string(),
+        word: "猫".to_string(),
+        meaning: "cat".to_string(),
+        usage: "  ".to_string(),
+        explanation: None,
+        example: Some("猫がいる。</script>".to_string()),
+        reading: Some("ねこ".to_string()),
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: vec!["p1".to_string()],
+        tags: vec!["N5".to_string()],
+        image_path: Some("v1_1.png".to_string()),
+        language: Some("ja".to_string()),
+        srs_state: "new".to_string(),
+        ease_factor: 2.5,
+        repetitions: 0,
+        interval_days: 0,
+        due_date: "2026-05-01".to_string(),
+        last_reviewed_at: None,
+        review_count: 0,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-02-16T00:00:00Z".to_string(),
+    }
+}
+
+fn bundle() -> CardBundle {
+    let mut card = BundleCard::from_vocabulary(&vocab());
+    card.audio = Some("audio/abc.mp3".to_string());
+    CardBundle {
+        schema_version: CARD_BUNDLE_SCHEMA.to_string(),
+        pack: BundlePack {
+            name: "JLPT <N5>".to_string(),
+            description: None,
+            language_from: Some("ja".to_string()),
+            language_to: Some("en".to_string()),
+        },
+        exported_at: "2026-05-01T10:00:00+00:00".to_string(),
+        cards: vec![card],
+    }
+}
+
+#[test]
+fn card_copies_study_fields_without_media() {
+    let card = BundleCard::from_vocabulary(&vocab());
+    assert_eq!(card.word, "猫");
+    assert_eq!(card.reading.as_deref(), Some("ねこ"));
+    assert_eq!(card.usage, None);
+    assert_eq!(card.tags, vec!["N5".to_string()]);
+    assert_eq!(card.image, None);
+    assert_eq!(card.audio, None);
+}
+
+#[test]
+fn dir_name_is_safe_and_timestamped() {
+    let at = chrono::Local
+        .with_ymd_and_hms(2026, 5, 1, 9, 30, 5)
+        .unwrap();
+    assert_eq!(
+        bundle_dir_name("N5: Basics/1", &at),
+        "N5- Basics-1-20260501-093005"
+    );
+    assert_eq!(bundle_dir_name(" .. ", &at), "word-pack-20260501-093005");
+    assert_eq!(bundle_dir_name("", &at), "word-pack-20260501-093005");
+}
+
+#[test]
+fn index_embeds_bundle_safely() {
+    let html = render_index_html(&bundle()).unwrap();
+    assert!(html.contains("<title>JLPT &lt;N5&gt;</title>"));
+    assert!(html.contains("猫がいる。<\\/script>"));
+    assert_eq!(html.matches("</script>").count(), 2);
+}
+
+#[test]
+fn writes_json_and_page() {
+    let dir = std::env::temp_dir().join(format!("card-bundle-{}", uuid::Uuid::new_v4()));
+    write_bundle(&dir, &bundle()).unwrap();
+    let json = std::fs::read_to_string(dir.join("cards.json")).unwrap();
+    let parsed: CardBundle = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, bundle());
+    assert!(dir.join("index.html").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
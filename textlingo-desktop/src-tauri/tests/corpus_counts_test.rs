@@ -0,0 +1,82 @@
+use openkoto_desktop_lib::corpus_counts::{apply_article, Corpus, CorpusText};
+use openkoto_desktop_lib::types::{Article, FavoriteVocabulary};
+
+fn article(id: &str, content: &str, language: Option<&str>) -> Article {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "title": id,
+        "content": content,
+        "source_url": null,
+        "media_path": null,
+        "language": language,
+        "created_at": "2026-01-01T00:00:00Z",
+        "translated": false
+    }))
+    .unwrap()
+}
+
+fn vocab(word: &str, language: Option<&str>) -> FavoriteVocabulary {
+    serde_json::from_value(serde_json::json!({
+        "id": word,
+        "word": word,
+        "meaning": "meaning",
+        "usage": "",
+        "example": null,
+        "reading": null,
+        "source_article_id": null,
+        "source_article_title": null,
+        "language": language,
+        "created_at": "2026-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn counts_whole_words_phrases_and_cjk() {
+    let text = CorpusText::new(
+        "The cat sat. A category of cats? CAT! Give up, don't give up. 猫が好き、猫です。",
+    );
+    assert_eq!(text.count("cat"), 2);
+    assert_eq!(text.count(" Cat "), 2);
+    assert_eq!(text.count("give up"), 2);
+    assert_eq!(text.count("don't"), 1);
+    assert_eq!(text.count("猫"), 2);
+    assert_eq!(text.count("dog"), 0);
+    assert_eq!(text.count(""), 0);
+}
+
+#[test]
+fn import_and_delete_update_counts_incrementally() {
+    let mut favorites = vec![
+        vocab("chat", Some("fr")),
+        vocab("chien", Some("fr")),
+        vocab("chat", Some("en")),
+    ];
+    let first = article("a1", "Le chat dort. Un chat noir.", Some("fr"));
+    let second = article("a2", "Le chat et le chien.", Some("fr"));
+
+    assert_eq!(apply_article(&mut favorites, &first, false), [0]);
+    assert_eq!(apply_article(&mut favorites, &second, false), [0, 1]);
+    assert_eq!(
+        (favorites[0].corpus_count, favorites[0].corpus_articles),
+        (3, 2)
+    );
+    assert_eq!(
+        (favorites[1].corpus_count, favorites[1].corpus_articles),
+        (1, 1)
+    );
+    // 学习语言不同的同形词不计入
+    assert_eq!(favorites[2].corpus_count, 0);
+
+    apply_article(&mut favorites, &first, true);
+    assert_eq!(
+        (favorites[0].corpus_count, favorites[0].corpus_articles),
+        (1, 1)
+    );
+
+    // 全量重新统计与增量结果一致
+    let corpus = Corpus::new(&[second]);
+    let mut recounted = vocab("chat", None);
+    corpus.count_into(&mut recounted);
+    assert_eq!((recounted.corpus_count, recounted.corpus_articles), (1, 1));
+}
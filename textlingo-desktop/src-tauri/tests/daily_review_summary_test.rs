@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+use openkoto_desktop_lib::commands::build_daily_review_summary;
+use openkoto_desktop_lib::types::{FavoriteVocabulary, ReviewLogEntry};
+
+fn make_vocab(id: &str, state: &str, due: &str) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: format!("word-{}", id),
+        meaning: "meaning".to_string(),
+        usage: "usage".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: vec!["default".to_string()],
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
+        srs_state: state.to_string(),
+        ease_factor: 2.5,
+        repetitions: 1,
+        interval_days: 1,
+        due_date: due.to_string(),
+        last_reviewed_at: None,
+        review_count: 1,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-03-01T00:00:00Z".to_string(),
+    }
+}
+
+fn log(vocabulary_id: &str, grade: &str, date: &str) -> ReviewLogEntry {
+    ReviewLogEntry {
+        id: format!("{}-{}-{}", vocabulary_id, grade, date),
+        vocabulary_id: vocabulary_id.to_string(),
+        grade: grade.to_string(),
+        review_date: date.to_string(),
+        interval_days: 1,
+        reviewed_at: format!("{}T10:00:00Z", date),
+    }
+}
+
+#[test]
+fn summarizes_grades_new_words_and_tomorrow_due() {
+    let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let favorites = vec![
+        make_vocab("a", "review", "2026-03-11"),
+        make_vocab("b", "learning", "2026-03-11"),
+        make_vocab("c", "review", "2026-03-20"),
+        make_vocab("d", "review", "2026-03-05"),
+        make_vocab("e", "new", "2026-03-10"),
+    ];
+    let review_log = vec![
+        log("a", "known", "2026-03-10"),
+        log("b", "unknown", "2026-03-10"),
+        log("b", "uncertain", "2026-03-10"),
+        log("c", "known", "2026-03-10"),
+        log("c", "known", "2026-03-02"),
+        log("d", "known", "2026-03-01"),
+    ];
+
+    let summary = build_daily_review_summary(&review_log, &favorites, date);
+    assert_eq!(summary.date, "2026-03-10");
+    assert_eq!(summary.review_count, 4);
+    assert_eq!(summary.known_count, 2);
+    assert_eq!(summary.uncertain_count, 1);
+    assert_eq!(summary.unknown_count, 1);
+    assert!((summary.accuracy - 0.75).abs() < 1e-9);
+
+    let new_ids: Vec<&str> = summary.new_words.iter().map(|w| w.id.as_str()).collect();
+    assert_eq!(new_ids, vec!["a", "b"]);
+    // a、b 明日到期，d 已过期仍未复习
+    assert_eq!(summary.due_tomorrow_count, 3);
+}
+
+#[test]
+fn empty_day_has_zero_accuracy() {
+    let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let summary = build_daily_review_summary(&[], &[], date);
+    assert_eq!(summary.review_count, 0);
+    assert_eq!(summary.accuracy, 0.0);
+    assert!(summary.new_words.is_empty());
+}
@@ -0,0 +1,106 @@
+use openkoto_desktop_lib::docx_import::{parse_docx, parse_style_names};
+use openkoto_desktop_lib::types::SegmentBlock;
+use std::io::Write;
+
+fn docx(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn paragraph(style: Option<&str>, runs: &[&str]) -> String {
+    let style = style
+        .map(|s| format!(r#"<w:pPr><w:pStyle w:val="{}"/></w:pPr>"#, s))
+        .unwrap_or_default();
+    let runs: String = runs
+        .iter()
+        .map(|t| format!(r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r>"#, t))
+        .collect();
+    format!("<w:p>{}{}</w:p>", style, runs)
+}
+
+const STYLES: &str = r#"<?xml version="1.0"?>
+<w:styles>
+  <w:style w:type="paragraph" w:styleId="1"><w:name w:val="heading 1"/></w:style>
+  <w:style w:type="paragraph" w:styleId="2"><w:name w:val="heading 2"/></w:style>
+  <w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/></w:style>
+</w:styles>"#;
+
+#[test]
+fn maps_styles_to_blocks() {
+    let list_item = r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="3"/></w:numPr></w:pPr><w:r><w:t>Nested item</w:t></w:r></w:p>"#;
+    let body = [
+        paragraph(Some("1"), &["Lesson One"]),
+        paragraph(None, &["Tom &amp; Jerry ", "went out.", ""]),
+        paragraph(None, &[]),
+        paragraph(Some("2"), &["Vocabulary"]),
+        list_item.to_string(),
+        paragraph(Some("Quote"), &["A quoted line."]),
+    ]
+    .concat();
+    let document = format!(
+        r#"<?xml version="1.0"?><w:document><w:body>{}<w:sectPr/></w:body></w:document>"#,
+        body
+    );
+    let bytes = docx(&[
+        ("word/document.xml", &document),
+        ("word/styles.xml", STYLES),
+    ]);
+
+    let doc = parse_docx(&bytes).unwrap();
+    assert_eq!(doc.title.as_deref(), Some("Lesson One"));
+    let blocks: Vec<(Option<SegmentBlock>, &str)> = doc
+        .blocks
+        .iter()
+        .map(|b| (b.kind, b.text.as_str()))
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![
+            (None, "Tom & Jerry went out."),
+            (Some(SegmentBlock::Heading { level: 2 }), "Vocabulary"),
+            (
+                Some(SegmentBlock::ListItem {
+                    ordered: false,
+                    depth: 1
+                }),
+                "Nested item"
+            ),
+            (Some(SegmentBlock::Quote), "A quoted line."),
+        ]
+    );
+}
+
+#[test]
+fn core_title_takes_priority() {
+    let document = format!(
+        "<w:document><w:body>{}{}</w:body></w:document>",
+        paragraph(Some("Heading1"), &["Chapter 1"]),
+        paragraph(None, &["Body text here."])
+    );
+    let core = "<cp:coreProperties><dc:title>My Reader</dc:title></cp:coreProperties>";
+    let bytes = docx(&[
+        ("word/document.xml", &document),
+        ("docProps/core.xml", core),
+    ]);
+
+    let doc = parse_docx(&bytes).unwrap();
+    assert_eq!(doc.title.as_deref(), Some("My Reader"));
+    assert_eq!(doc.blocks.len(), 2);
+    assert_eq!(doc.blocks[0].kind, Some(SegmentBlock::Heading { level: 1 }));
+}
+
+#[test]
+fn rejects_non_docx_data() {
+    assert!(parse_docx(b"not a zip").is_err());
+    assert!(parse_docx(&docx(&[("other.xml", "<x/>")])).is_err());
+    assert_eq!(
+        parse_style_names(STYLES).get("1").map(String::as_str),
+        Some("heading 1")
+    );
+}
@@ -0,0 +1,49 @@
+use openkoto_desktop_lib::embedded_subtitles::parse_subtitle_streams;
+
+const PROBE: &str = "Input #0, matroska,webm, from 'episode.mkv':
+  Metadata:
+    title           : Episode 1
+  Duration: 00:23:40.02, start: 0.000000, bitrate: 2500 kb/s
+  Stream #0:0(jpn): Video: h264 (High), yuv420p, 1920x1080, 23.98 fps (default)
+    Metadata:
+      title           : Main video
+  Stream #0:1(jpn): Audio: aac (LC), 48000 Hz, stereo, fltp (default)
+  Stream #0:2(eng): Subtitle: ass (default)
+    Metadata:
+      title           : English Signs & Songs
+  Stream #0:3(jpn): Subtitle: subrip (forced)
+  Stream #0:4[0x1200](und): Subtitle: hdmv_pgs_subtitle ([144][0][0][0] / 0x0090), 1920x1080
+  Stream #0:5: Subtitle: mov_text (tx3g / 0x67337874), 0 kb/s
+At least one output file must be specified";
+
+#[test]
+fn parses_subtitle_streams_with_metadata() {
+    let tracks = parse_subtitle_streams(PROBE);
+    let indices: Vec<u32> = tracks.iter().map(|t| t.stream_index).collect();
+    assert_eq!(indices, [2, 3, 4, 5]);
+
+    assert_eq!(tracks[0].language.as_deref(), Some("eng"));
+    assert_eq!(tracks[0].codec, "ass");
+    assert_eq!(tracks[0].title.as_deref(), Some("English Signs & Songs"));
+    assert!(tracks[0].default && !tracks[0].forced && tracks[0].text_based);
+
+    assert_eq!(tracks[1].codec, "subrip");
+    assert!(tracks[1].forced);
+    // 视频流的 title 不会归到字幕轨上
+    assert_eq!(tracks[1].title, None);
+
+    // 图形字幕只列出，不能提取；und 视为未标注语言
+    assert_eq!(tracks[2].codec, "hdmv_pgs_subtitle");
+    assert_eq!(tracks[2].language, None);
+    assert!(!tracks[2].text_based);
+
+    assert_eq!(tracks[3].codec, "mov_text");
+    assert_eq!(tracks[3].language, None);
+    assert!(tracks[3].text_based);
+}
+
+#[test]
+fn no_subtitle_streams() {
+    let probe = "  Stream #0:0: Video: h264\n  Stream #0:1(eng): Audio: aac\n";
+    assert!(parse_subtitle_streams(probe).is_empty());
+}
@@ -0,0 +1,141 @@
+use openkoto_desktop_lib::export_templates::{
+    article_context, default_template, render, render_anki_notes, render_csv, Template,
+};
+use openkoto_desktop_lib::types::{Article, ExportTemplateKind, FavoriteVocabulary, TemplateField};
+use serde_json::json;
+
+fn vocab(word: &str, reading: Option<&str>, tags: &[&str]) -> FavoriteVocabulary {
+    serde_json::from_value(json!({
+        "id": word,
+        "word": word,
+        "meaning": "meaning, with comma",
+        "usage": "",
+        "example": null,
+        "reading": reading,
+        "source_article_id": "a1",
+        "source_article_title": null,
+        "tags": tags,
+        "created_at": "2026-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn field(name: &str, template: &str) -> TemplateField {
+    TemplateField {
+        name: name.to_string(),
+        template: template.to_string(),
+    }
+}
+
+#[test]
+fn renders_variables_conditions_and_loops() {
+    let context = json!({
+        "title": "Title",
+        "source": { "url": "https://example.com" },
+        "empty": "",
+        "items": [{ "word": "a" }, { "word": "b" }],
+        "tags": ["x", "y"]
+    });
+    assert_eq!(
+        render("{{title}} <{{source.url}}> {{missing}}", &context).unwrap(),
+        "Title <https://example.com> "
+    );
+    assert_eq!(
+        render(
+            "{{#if empty}}yes{{else}}no{{/if}} {{#unless title}}none{{/unless}}",
+            &context
+        )
+        .unwrap(),
+        "no "
+    );
+    // 循环中可访问当前项、下标和外层字段
+    assert_eq!(
+        render(
+            "{{#each items}}{{@index}}:{{word}}@{{title}} {{/each}}",
+            &context
+        )
+        .unwrap(),
+        "0:a@Title 1:b@Title "
+    );
+    assert_eq!(
+        render("{{tags}}|{{json title}}|{{upper title}}", &context).unwrap(),
+        "x, y|\"Title\"|TITLE"
+    );
+    assert_eq!(
+        render("{{#each nothing}}x{{else}}empty{{/each}}", &context).unwrap(),
+        "empty"
+    );
+}
+
+#[test]
+fn standalone_block_lines_leave_no_blank_lines() {
+    let template = "List:\n{{#each items}}\n  - {{word}}\n{{/each}}\nEnd\n";
+    let context = json!({ "items": [{ "word": "a" }, { "word": "b" }] });
+    assert_eq!(
+        render(template, &context).unwrap(),
+        "List:\n  - a\n  - b\nEnd\n"
+    );
+}
+
+#[test]
+fn reports_syntax_errors() {
+    assert!(Template::parse("{{#if a}}unclosed").is_err());
+    assert!(Template::parse("{{/each}}").is_err());
+    assert!(Template::parse("{{title").is_err());
+    assert!(Template::parse("{{#with a}}{{/with}}").is_err());
+    assert!(Template::parse("{{shout title}}").is_err());
+    assert!(Template::parse("{{! comment }}ok").is_ok());
+}
+
+#[test]
+fn exports_csv_and_anki_with_default_fields() {
+    let items = vec![
+        serde_json::to_value(vocab("猫", Some("ねこ"), &["N5", "animal"])).unwrap(),
+        serde_json::to_value(vocab("say \"hi\"", None, &[])).unwrap(),
+    ];
+
+    let (_, csv_fields) = default_template(ExportTemplateKind::VocabularyCsv);
+    let csv = render_csv(&csv_fields, &items).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "word,reading,meaning,usage,example,tags");
+    assert_eq!(lines[1], "猫,ねこ,\"meaning, with comma\",,,\"N5, animal\"");
+    assert!(lines[2].starts_with("\"say \"\"hi\"\"\",,"));
+
+    let (_, anki_fields) = default_template(ExportTemplateKind::AnkiNote);
+    let anki = render_anki_notes(&anki_fields, &items).unwrap();
+    assert!(anki
+        .starts_with("#separator:tab\n#html:true\n#columns:Front\tBack\tTags\n#tags column:3\n"));
+    assert!(anki.contains("猫<br>ねこ\tmeaning, with comma\tN5 animal\n"));
+
+    let custom = vec![field(
+        "Word",
+        "{{word}}\n{{#if reading}}({{reading}}){{/if}}",
+    )];
+    let anki = render_anki_notes(&custom, &items[..1]).unwrap();
+    assert!(anki.ends_with("猫<br>(ねこ)\n"));
+    assert!(render_csv(&[field("bad", "{{#if}}")], &items).is_err());
+}
+
+#[test]
+fn default_markdown_template_renders_article_note() {
+    let article: Article = serde_json::from_value(json!({
+        "id": "a1",
+        "title": "My \"Post\"",
+        "content": "Hello.",
+        "source_url": null,
+        "media_path": null,
+        "created_at": "2026-01-01T00:00:00Z",
+        "translated": false
+    }))
+    .unwrap();
+    let vocabulary = vec![vocab("猫", Some("ねこ"), &[])];
+    let (body, _) = default_template(ExportTemplateKind::ArticleMarkdown);
+    let note = render(&body, &article_context(&article, &[], &vocabulary, &[])).unwrap();
+
+    assert!(note.starts_with("---\nopenkoto_id: \"a1\"\ntitle: \"My \\\"Post\\\"\"\ncreated:"));
+    assert!(!note.contains("source:"));
+    assert!(
+        note.contains("# My \"Post\"\n\n## Vocabulary\n\n- [[猫]] (ねこ) — meaning, with comma\n")
+    );
+    assert!(!note.contains("## Highlights"));
+}
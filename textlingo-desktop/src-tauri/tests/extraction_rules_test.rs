@@ -0,0 +1,122 @@
+use openkoto_desktop_lib::extraction_rules::{
+    select_content_html, select_title, ExtractionRules, SiteRule,
+};
+use scraper::Html;
+
+fn rule(domains: &[&str]) -> SiteRule {
+    SiteRule {
+        name: String::new(),
+        domains: domains.iter().map(|d| d.to_string()).collect(),
+        content_selectors: vec!["#body".to_string()],
+        title_selector: None,
+        cleanup_patterns: Vec::new(),
+    }
+}
+
+#[test]
+fn site_rules_match_domain_and_subdomains() {
+    let rules = ExtractionRules {
+        sites: vec![rule(&["example.com"]), rule(&["*.blog.jp"])],
+        fallback_selectors: Vec::new(),
+    };
+
+    assert!(rules.find_site_rule("https://example.com/a").is_some());
+    assert!(rules.find_site_rule("https://www.Example.com/a").is_some());
+    assert!(rules.find_site_rule("https://foo.blog.jp/post/1").is_some());
+    assert!(rules.find_site_rule("https://notexample.com/a").is_none());
+    assert!(rules.find_site_rule("not a url").is_none());
+}
+
+#[test]
+fn default_rules_cover_uta_net() {
+    let rules = ExtractionRules::default();
+    let uta_net = rules
+        .find_site_rule("https://www.uta-net.com/song/12345/")
+        .expect("uta-net rule");
+    assert_eq!(uta_net.content_selectors, vec!["#kashi_area".to_string()]);
+    assert!(rules.validate().is_ok());
+}
+
+#[test]
+fn missing_fallback_selectors_use_defaults() {
+    let rules: ExtractionRules =
+        serde_json::from_str(r#"{ "sites": [{ "domains": ["example.com"] }] }"#).unwrap();
+    assert_eq!(
+        rules.fallback_selectors,
+        ExtractionRules::default().fallback_selectors
+    );
+    assert!(rules.sites[0].content_selectors.is_empty());
+}
+
+#[test]
+fn validate_rejects_bad_selectors_and_patterns() {
+    let mut bad_selector = rule(&["example.com"]);
+    bad_selector.content_selectors = vec!["div[".to_string()];
+    let mut bad_pattern = rule(&["example.com"]);
+    bad_pattern.cleanup_patterns = vec!["(unclosed".to_string()];
+
+    for site in [bad_selector, bad_pattern, rule(&[" "])] {
+        let rules = ExtractionRules {
+            sites: vec![site],
+            fallback_selectors: Vec::new(),
+        };
+        assert!(rules.validate().is_err());
+    }
+}
+
+#[test]
+fn cleanup_patterns_remove_matches() {
+    let mut site = rule(&["example.com"]);
+    site.cleanup_patterns = vec![r"(?m)^作詞：.*$".to_string(), r"\(c\) \d{4}".to_string()];
+
+    let cleaned = site.apply_cleanup("作詞：someone\n歌詞の本文\n(c) 2024");
+    assert_eq!(cleaned, "歌詞の本文");
+}
+
+#[test]
+fn selectors_pick_first_substantial_element() {
+    let long_text = "歌詞".repeat(60);
+    let html = format!(
+        r#"<html><body><h1 class="song"> Song <b>Title</b> </h1><div id="short">hi</div><div id="body">{}</div></body></html>"#,
+        long_text
+    );
+    let document = Html::parse_document(&html);
+
+    let selected =
+        select_content_html(&document, &["#short".to_string(), "#body".to_string()]).unwrap();
+    assert!(selected.contains(&long_text));
+    assert!(select_content_html(&document, &["#missing".to_string()]).is_none());
+    assert_eq!(
+        select_title(&document, "h1.song"),
+        Some("Song Title".to_string())
+    );
+}
+
+#[test]
+fn saved_page_url_prefers_browser_comment() {
+    use openkoto_desktop_lib::commands::saved_page_url;
+
+    let html = r#"<!DOCTYPE html>
+<!-- saved from url=(0031)https://example.com/news/story -->
+<html><head><link rel="canonical" href="https://example.com/canonical"></head></html>"#;
+    assert_eq!(
+        saved_page_url(html).as_deref(),
+        Some("https://example.com/news/story")
+    );
+
+    let single_file = "<html><!--\n Page saved with SingleFile \n url: https://blog.example.org/post \n saved date: today\n--><head></head></html>";
+    assert_eq!(
+        saved_page_url(single_file).as_deref(),
+        Some("https://blog.example.org/post")
+    );
+
+    let meta =
+        r#"<html><head><meta property="og:url" content="https://site.example/a"></head></html>"#;
+    assert_eq!(
+        saved_page_url(meta).as_deref(),
+        Some("https://site.example/a")
+    );
+
+    let local = r#"<html><head><link rel="canonical" href="file:///tmp/page.html"></head></html>"#;
+    assert_eq!(saved_page_url(local), None);
+}
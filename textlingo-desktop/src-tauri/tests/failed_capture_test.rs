@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+
+use openkoto_desktop_lib::commands::{settle_capture_retry, CaptureRetry};
+use openkoto_desktop_lib::types::FailedCapture;
+
+fn capture(id: &str) -> FailedCapture {
+    FailedCapture {
+        id: id.to_string(),
+        url: format!("https://example.com/{}", id),
+        error: "timeout".to_string(),
+        failed_at: "2026-01-01T00:00:00Z".to_string(),
+        last_attempt_at: None,
+        retry_count: 0,
+    }
+}
+
+#[test]
+fn mixed_batch_keeps_going_past_failures() {
+    let removed = RefCell::new(Vec::new());
+    let persisted = RefCell::new(Vec::new());
+    let outcomes: Vec<(FailedCapture, Result<&str, String>)> = vec![
+        (capture("a"), Ok("article-a")),
+        (capture("b"), Err("HTTP 503".to_string())),
+        (capture("c"), Ok("article-c")),
+    ];
+
+    let mut imported = Vec::new();
+    let mut still_failing = Vec::new();
+    for (capture, outcome) in outcomes {
+        match settle_capture_retry(
+            capture,
+            outcome,
+            "2026-01-02T00:00:00Z",
+            |id| {
+                removed.borrow_mut().push(id.to_string());
+                Ok(())
+            },
+            |capture| {
+                persisted.borrow_mut().push(capture.id.clone());
+                Ok(())
+            },
+        ) {
+            CaptureRetry::Imported(article) => imported.push(article),
+            CaptureRetry::StillFailing(capture) => still_failing.push(capture),
+        }
+    }
+
+    assert_eq!(imported, vec!["article-a", "article-c"]);
+    assert_eq!(*removed.borrow(), vec!["a", "c"]);
+    assert_eq!(*persisted.borrow(), vec!["b"]);
+    assert_eq!(still_failing.len(), 1);
+    assert_eq!(still_failing[0].error, "HTTP 503");
+    assert_eq!(still_failing[0].retry_count, 1);
+    assert_eq!(
+        still_failing[0].last_attempt_at.as_deref(),
+        Some("2026-01-02T00:00:00Z")
+    );
+}
+
+#[test]
+fn queue_write_errors_do_not_abort_the_batch() {
+    let imported = settle_capture_retry(
+        capture("a"),
+        Ok("article-a"),
+        "2026-01-02T00:00:00Z",
+        |_| Err("disk full".to_string()),
+        |_| Ok(()),
+    );
+    assert!(matches!(imported, CaptureRetry::Imported("article-a")));
+
+    let failing = settle_capture_retry(
+        capture("b"),
+        Err::<&str, _>("HTTP 404".to_string()),
+        "2026-01-02T00:00:00Z",
+        |_| Ok(()),
+        |_| Err("disk full".to_string()),
+    );
+    let CaptureRetry::StillFailing(failing) = failing else {
+        panic!("expected the capture to stay queued");
+    };
+    assert_eq!(failing.id, "b");
+    assert_eq!(failing.error, "HTTP 404");
+}
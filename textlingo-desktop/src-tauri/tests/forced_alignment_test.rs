@@ -0,0 +1,90 @@
+use openkoto_desktop_lib::forced_alignment::{parse_word_timestamps, snap_segments};
+use openkoto_desktop_lib::types::ArticleSegment;
+use serde_json::json;
+
+fn segment(order: i32, text: &str, start: f64, end: f64) -> ArticleSegment {
+    serde_json::from_value(json!({
+        "id": format!("s{}", order),
+        "article_id": "a",
+        "order": order,
+        "text": text,
+        "reading_text": null,
+        "translation": null,
+        "explanation": null,
+        "start_time": start,
+        "end_time": end,
+        "created_at": "2026-01-01T00:00:00Z",
+        "is_new_paragraph": false
+    }))
+    .unwrap()
+}
+
+fn whisper_output() -> serde_json::Value {
+    json!({
+        "text": "Good morning everyone. Let's begin today's lesson.",
+        "segments": [
+            { "start": 0.0, "end": 4.0, "text": "Good morning everyone.", "words": [
+                { "word": " Good", "start": 1.12, "end": 1.40 },
+                { "word": " morning", "start": 1.40, "end": 1.86 },
+                { "word": " everyone.", "start": 1.86, "end": 2.54 }
+            ]},
+            { "start": 4.0, "end": 9.0, "text": "Let's begin today's lesson.", "words": [
+                { "word": " Let's", "start": 5.31, "end": 5.60 },
+                { "word": " begin", "start": 5.60, "end": 5.95 },
+                { "word": " today's", "start": 5.95, "end": 6.40 },
+                { "word": " lesson.", "start": 6.40, "end": 7.02 }
+            ]}
+        ]
+    })
+}
+
+#[test]
+fn parses_whisper_word_timestamps() {
+    let words = parse_word_timestamps(&whisper_output());
+    let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        ["good", "morning", "everyone", "lets", "begin", "todays", "lesson"]
+    );
+    assert_eq!((words[0].start, words[0].end), (1.12, 1.40));
+
+    // 中日文的词按字拆开并分配时间
+    let words = parse_word_timestamps(&json!({
+        "words": [{ "word": "日本", "start": 0.0, "end": 1.0 }]
+    }));
+    assert_eq!(words.len(), 2);
+    assert_eq!((words[1].start, words[1].end), (0.5, 1.0));
+}
+
+#[test]
+fn snaps_second_accurate_times_to_word_boundaries() {
+    let words = parse_word_timestamps(&whisper_output());
+    let mut segments = vec![
+        segment(0, "Good morning everyone.", 1.0, 3.0),
+        segment(1, "Let's begin today's lesson.", 5.0, 7.0),
+        // 音频中没有的段落保持原时间
+        segment(2, "Homework is on page ten.", 8.0, 10.0),
+    ];
+
+    assert_eq!(snap_segments(&mut segments, &words), 2);
+    assert_eq!(
+        (segments[0].start_time, segments[0].end_time),
+        (Some(1.12), Some(2.54))
+    );
+    assert_eq!(
+        (segments[1].start_time, segments[1].end_time),
+        (Some(5.31), Some(7.02))
+    );
+    assert_eq!(
+        (segments[2].start_time, segments[2].end_time),
+        (Some(8.0), Some(10.0))
+    );
+}
+
+#[test]
+fn ignores_matches_that_drift_too_far() {
+    let words = parse_word_timestamps(&whisper_output());
+    let mut segments = vec![segment(0, "Let's begin today's lesson.", 60.0, 63.0)];
+    assert_eq!(snap_segments(&mut segments, &words), 0);
+    assert_eq!(segments[0].start_time, Some(60.0));
+}
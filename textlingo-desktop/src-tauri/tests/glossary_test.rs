@@ -0,0 +1,54 @@
+use openkoto_desktop_lib::glossary::{glossary_prompt, glossary_signature, matching_entries};
+use openkoto_desktop_lib::types::GlossaryEntry;
+
+fn entry(term: &str, translation: &str, target: &str) -> GlossaryEntry {
+    GlossaryEntry {
+        id: term.to_string(),
+        term: term.to_string(),
+        translation: translation.to_string(),
+        source_language: None,
+        target_language: target.to_string(),
+        note: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        updated_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn matches_terms_present_in_text_for_target_language() {
+    let entries = vec![
+        entry("New York", "纽约", "zh-CN"),
+        entry("New York Times", "纽约时报", "zh-CN"),
+        entry("kubernetes", "Kubernetes", "zh-CN"),
+        entry("New York", "ニューヨーク", "ja"),
+        entry("碇シンジ", "碇真嗣", "zh-CN"),
+    ];
+    let texts = [
+        "I read the New York Times.",
+        "Deploy on Kubernetes. 碇シンジ",
+    ];
+
+    let matched = matching_entries(&entries, &texts, "ZH-cn");
+    let terms: Vec<&str> = matched.iter().map(|e| e.term.as_str()).collect();
+    assert_eq!(
+        terms,
+        vec!["New York Times", "kubernetes", "New York", "碇シンジ"]
+    );
+}
+
+#[test]
+fn prompt_lists_terms_and_is_empty_without_matches() {
+    assert!(glossary_prompt(&[]).is_empty());
+
+    let mut with_note = entry("Alice", "爱丽丝", "zh-CN");
+    with_note.note = Some("主角".to_string());
+    let plain = entry("Bob", "鲍勃", "zh-CN");
+    let prompt = glossary_prompt(&[&with_note, &plain]);
+    assert!(prompt.contains("- Alice => 爱丽丝 (主角)"));
+    assert!(prompt.contains("- Bob => 鲍勃\n"));
+
+    assert_eq!(
+        glossary_signature(&[&plain, &with_note]),
+        glossary_signature(&[&with_note, &plain])
+    );
+}
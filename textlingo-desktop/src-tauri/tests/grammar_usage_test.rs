@@ -0,0 +1,114 @@
+use openkoto_desktop_lib::commands::{
+    article_grammar_links, find_grammar_usages, segment_uses_grammar,
+};
+use openkoto_desktop_lib::types::{Article, ArticleSegment, FavoriteGrammar};
+
+fn segment(id: &str, order: i32, points: &[&str]) -> ArticleSegment {
+    let grammar_points: Vec<_> = points
+        .iter()
+        .map(|p| serde_json::json!({ "point": p, "explanation": "", "example": null }))
+        .collect();
+    let explanation = (!points.is_empty()).then(|| {
+        serde_json::json!({
+            "translation": "",
+            "explanation": "",
+            "reading_text": null,
+            "grammar_points": grammar_points,
+            "cultural_context": null,
+            "difficulty_level": null,
+            "learning_tips": null
+        })
+    });
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "article_id": "a1",
+        "order": order,
+        "text": format!("text {}", id),
+        "reading_text": null,
+        "translation": null,
+        "explanation": explanation,
+        "created_at": "2026-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn article(id: &str, segments: Vec<ArticleSegment>) -> Article {
+    let mut article: Article = serde_json::from_value(serde_json::json!({
+        "id": id,
+        "title": format!("Article {}", id),
+        "content": "",
+        "source_url": null,
+        "media_path": null,
+        "language": "ja",
+        "created_at": "2026-01-01T00:00:00Z",
+        "translated": false,
+        "segments": []
+    }))
+    .unwrap();
+    article.segments = segments;
+    article
+}
+
+fn grammar(id: &str, point: &str, source_segment_id: Option<&str>) -> FavoriteGrammar {
+    FavoriteGrammar {
+        id: id.to_string(),
+        point: point.to_string(),
+        explanation: String::new(),
+        example: None,
+        source_article_id: Some("a1".to_string()),
+        source_article_title: None,
+        source_segment_id: source_segment_id.map(str::to_string),
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn matches_source_segment_and_same_point() {
+    let g = grammar("g1", "〜ばかり", Some("s3"));
+    assert!(segment_uses_grammar(&segment("s1", 0, &["ばかり"]), &g));
+    assert!(segment_uses_grammar(&segment("s3", 2, &[]), &g));
+    assert!(!segment_uses_grammar(&segment("s2", 1, &["ながら"]), &g));
+}
+
+#[test]
+fn usages_list_source_first() {
+    let g = grammar("g1", "〜ばかり", Some("s3"));
+    let articles = vec![
+        article("a2", vec![segment("t1", 0, &["ばかり"])]),
+        article(
+            "a1",
+            vec![segment("s1", 0, &[]), segment("s3", 1, &["ばかり"])],
+        ),
+    ];
+    let usages = find_grammar_usages(&g, &articles);
+    let ids: Vec<(&str, bool)> = usages
+        .iter()
+        .map(|u| (u.segment_id.as_str(), u.is_source))
+        .collect();
+    assert_eq!(ids, vec![("s3", true), ("t1", false)]);
+    assert_eq!(usages[1].article_title, "Article a2");
+}
+
+#[test]
+fn article_links_map_segments_to_grammar_ids() {
+    let a = article(
+        "a1",
+        vec![
+            segment("s1", 0, &["ながら", "ばかり"]),
+            segment("s2", 1, &[]),
+        ],
+    );
+    let links = article_grammar_links(
+        &a,
+        &[
+            grammar("g1", "〜ばかり", None),
+            grammar("g2", "ながら", None),
+            grammar("g3", "ものの", None),
+        ],
+    );
+    assert_eq!(links.len(), 1);
+    assert_eq!(
+        links.get("s1"),
+        Some(&vec!["g1".to_string(), "g2".to_string()])
+    );
+}
@@ -0,0 +1,17 @@
+use openkoto_desktop_lib::health::{disk_space_status, parse_df_available_bytes, HealthStatus};
+
+#[test]
+fn parses_available_space_from_df() {
+    let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                  /dev/disk3s5    482797652 301234567 170000000      64% /System/Volumes/Data\n";
+    assert_eq!(parse_df_available_bytes(output), Some(170_000_000 * 1024));
+    assert_eq!(parse_df_available_bytes("Filesystem\n"), None);
+}
+
+#[test]
+fn low_disk_space_is_flagged() {
+    const MB: u64 = 1024 * 1024;
+    assert_eq!(disk_space_status(100 * MB), HealthStatus::Error);
+    assert_eq!(disk_space_status(500 * MB), HealthStatus::Warning);
+    assert_eq!(disk_space_status(5 * 1024 * MB), HealthStatus::Ok);
+}
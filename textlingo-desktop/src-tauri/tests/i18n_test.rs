@@ -0,0 +1,25 @@
+use openkoto_desktop_lib::i18n::{set_language, tr_with, translate, Lang};
+
+#[test]
+fn interface_language_codes_map_to_catalogs() {
+    assert_eq!(Lang::from_code("zh-CN"), Lang::Zh);
+    assert_eq!(Lang::from_code("ja"), Lang::Ja);
+    assert_eq!(Lang::from_code("en-US"), Lang::En);
+    assert_eq!(Lang::from_code("fr"), Lang::En);
+}
+
+#[test]
+fn translate_falls_back_to_key_for_unknown_messages() {
+    assert_eq!(translate(Lang::Zh, "subtitle.done"), "字幕提取完成！");
+    assert_eq!(translate(Lang::En, "no.such.key"), "no.such.key");
+}
+
+#[test]
+fn placeholders_are_filled_in_current_language() {
+    set_language("ja");
+    let message = tr_with(
+        "subtitle.chunk",
+        &[("index", "2".to_string()), ("total", "5".to_string())],
+    );
+    assert_eq!(message, "チャンク 2/5 を抽出中");
+}
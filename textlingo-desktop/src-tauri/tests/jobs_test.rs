@@ -0,0 +1,29 @@
+use openkoto_desktop_lib::jobs::JobRegistry;
+
+#[test]
+fn try_register_rejects_a_running_job_without_cancelling_it() {
+    let jobs = JobRegistry::default();
+    let first = jobs.try_register("subtitles:a".to_string()).unwrap();
+
+    assert!(jobs.try_register("subtitles:a".to_string()).is_none());
+    assert!(!first.token().is_cancelled());
+    assert!(jobs.try_register("subtitles:b".to_string()).is_some());
+}
+
+#[test]
+fn try_register_succeeds_again_after_the_run_finishes() {
+    let jobs = JobRegistry::default();
+    drop(jobs.try_register("subtitles:a".to_string()).unwrap());
+
+    assert!(jobs.try_register("subtitles:a".to_string()).is_some());
+}
+
+#[test]
+fn cancel_reaches_a_job_registered_with_try_register() {
+    let jobs = JobRegistry::default();
+    let job = jobs.try_register("subtitles:a".to_string()).unwrap();
+
+    assert!(jobs.cancel("subtitles:a"));
+    assert!(job.token().is_cancelled());
+    assert!(!jobs.cancel("subtitles:b"));
+}
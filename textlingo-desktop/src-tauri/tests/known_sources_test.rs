@@ -0,0 +1,75 @@
+use openkoto_desktop_lib::commands::{
+    correct_source_counts, is_vocabulary_mastered, MASTERED_INTERVAL_DAYS,
+};
+use openkoto_desktop_lib::types::{FavoriteVocabulary, VocabularyEncounter};
+
+fn make_vocab(id: &str, interval_days: i32) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: format!("word-{}", id),
+        meaning: "meaning".to_string(),
+        usage: "usage".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: Vec::new(),
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
+        srs_state: "review".to_string(),
+        ease_factor: 2.5,
+        repetitions: 5,
+        interval_days,
+        due_date: "2026-05-01".to_string(),
+        last_reviewed_at: None,
+        review_count: 5,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-02-16T00:00:00Z".to_string(),
+    }
+}
+
+fn encounter(vocabulary_id: &str, article_id: &str, correct: bool) -> VocabularyEncounter {
+    VocabularyEncounter {
+        id: format!("{}-{}-{}", vocabulary_id, article_id, correct),
+        vocabulary_id: vocabulary_id.to_string(),
+        article_id: article_id.to_string(),
+        correct,
+        encountered_at: "2026-03-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn counts_distinct_articles_with_correct_answers() {
+    let counts = correct_source_counts(&[
+        encounter("v1", "a1", true),
+        encounter("v1", "a1", true),
+        encounter("v1", "a2", true),
+        encounter("v1", "a3", false),
+        encounter("v2", "a1", false),
+    ]);
+    assert_eq!(counts.get("v1"), Some(&2));
+    assert_eq!(counts.get("v2"), None);
+}
+
+#[test]
+fn mastery_requires_enough_sources_when_configured() {
+    let counts = correct_source_counts(&[encounter("v1", "a1", true), encounter("v1", "a2", true)]);
+    let mature = make_vocab("v1", MASTERED_INTERVAL_DAYS);
+
+    assert!(is_vocabulary_mastered(&mature, &counts, None));
+    assert!(is_vocabulary_mastered(&mature, &counts, Some(2)));
+    assert!(!is_vocabulary_mastered(&mature, &counts, Some(3)));
+
+    // 来源足够但复习间隔未达标
+    let young = make_vocab("v1", MASTERED_INTERVAL_DAYS - 1);
+    assert!(!is_vocabulary_mastered(&young, &counts, Some(1)));
+
+    // 从未在文章中答对过
+    let other = make_vocab("v9", 60);
+    assert!(!is_vocabulary_mastered(&other, &counts, Some(1)));
+}
@@ -0,0 +1,50 @@
+use openkoto_desktop_lib::language_detect::{detect_language, language_name, sentence_rules};
+
+#[test]
+fn detects_language_by_script() {
+    assert_eq!(detect_language("今日は東京へ行きました。"), Some("ja"));
+    assert_eq!(detect_language("我们今天去北京学习中文。"), Some("zh"));
+    assert_eq!(detect_language("한국어를 공부합니다."), Some("ko"));
+    assert_eq!(detect_language("Я люблю читать книги."), Some("ru"));
+    assert_eq!(detect_language("Я їду до Києва."), Some("uk"));
+    assert_eq!(detect_language("أنا أحب القراءة."), Some("ar"));
+    assert_eq!(detect_language("12345 ..."), None);
+}
+
+#[test]
+fn detects_latin_languages_by_common_words() {
+    assert_eq!(
+        detect_language("The cat is sleeping on the sofa and it is happy."),
+        Some("en")
+    );
+    assert_eq!(
+        detect_language("Le chat dort sur le canapé et il est content."),
+        Some("fr")
+    );
+    assert_eq!(
+        detect_language("Die Katze schläft auf dem Sofa und ist nicht müde."),
+        Some("de")
+    );
+    assert_eq!(
+        detect_language("El gato duerme en el sofá y los niños juegan."),
+        Some("es")
+    );
+}
+
+#[test]
+fn mostly_english_text_with_a_few_kanji_stays_english() {
+    assert_eq!(
+        detect_language("The word 漢字 means Chinese characters and is used in Japan."),
+        Some("en")
+    );
+}
+
+#[test]
+fn sentence_rules_follow_language() {
+    assert!(sentence_rules(Some("ja")).period_needs_space);
+    assert!(!sentence_rules(Some("en")).period_needs_space);
+    assert!(sentence_rules(Some("ar")).terminators.contains(&'؟'));
+    assert!(sentence_rules(None).terminators.contains(&'。'));
+    assert_eq!(language_name("pt-BR"), "Portuguese");
+    assert_eq!(language_name("sw"), "sw");
+}
@@ -0,0 +1,29 @@
+use openkoto_desktop_lib::commands::matches_learning_language;
+use openkoto_desktop_lib::types::{AppConfig, Article};
+
+#[test]
+fn filter_matches_language_case_insensitively() {
+    assert!(matches_learning_language(Some("ja"), Some("ja")));
+    assert!(matches_learning_language(Some("FR"), Some("fr")));
+    assert!(!matches_learning_language(Some("ja"), Some("fr")));
+}
+
+#[test]
+fn untagged_items_and_missing_filter_always_match() {
+    assert!(matches_learning_language(None, Some("ja")));
+    assert!(matches_learning_language(Some("ja"), None));
+    assert!(matches_learning_language(None, None));
+}
+
+#[test]
+fn legacy_data_deserializes_without_language() {
+    let article: Article = serde_json::from_str(
+        r#"{"id":"a1","title":"t","content":"c","source_url":null,"media_path":null,
+            "created_at":"2024-01-01T00:00:00Z","translated":false}"#,
+    )
+    .unwrap();
+    assert_eq!(article.language, None);
+
+    let config: AppConfig = serde_json::from_str(r#"{"target_language":"zh-CN"}"#).unwrap();
+    assert_eq!(config.active_learning_language, None);
+}
@@ -0,0 +1,138 @@
+use openkoto_desktop_lib::markdown_import::{
+    markdown_pieces, parse_markdown, plain_content, resolve_image_urls, strip_inline,
+};
+use openkoto_desktop_lib::types::SegmentBlock;
+
+const DOC: &str = "---
+title: \"Notes\"
+tags: [a]
+---
+# Notes
+
+Intro with **bold** text and a [link](https://example.com).
+It wraps onto a second line.
+
+## Section *one*
+
+- first item
+  continues here
+  - nested `code`
+1. ordered
+
+> Quoted line.
+> Still quoted.
+
+```rust
+fn main() {}
+```
+
+![A figure](img/fig%201.png)
+
+Setext heading
+--------------
+";
+
+#[test]
+fn strips_inline_formatting() {
+    assert_eq!(
+        strip_inline("**Bold**, *em*, ~~gone~~ and `x` [text](http://a.b)"),
+        "Bold, em, gone and x text"
+    );
+    assert_eq!(strip_inline("keep snake_case_name"), "keep snake_case_name");
+    assert_eq!(strip_inline(r"literal \*star\*"), "literal *star*");
+    assert_eq!(strip_inline("note[^1] <b>tag</b>"), "note tag");
+}
+
+#[test]
+fn parses_blocks_and_title() {
+    let doc = parse_markdown(DOC);
+    assert_eq!(doc.title.as_deref(), Some("Notes"));
+
+    let kinds: Vec<(Option<SegmentBlock>, &str)> = doc
+        .blocks
+        .iter()
+        .map(|b| (b.kind, b.text.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                None,
+                "Intro with bold text and a link. It wraps onto a second line."
+            ),
+            (Some(SegmentBlock::Heading { level: 2 }), "Section one"),
+            (
+                Some(SegmentBlock::ListItem {
+                    ordered: false,
+                    depth: 0
+                }),
+                "first item continues here"
+            ),
+            (
+                Some(SegmentBlock::ListItem {
+                    ordered: false,
+                    depth: 1
+                }),
+                "nested code"
+            ),
+            (
+                Some(SegmentBlock::ListItem {
+                    ordered: true,
+                    depth: 0
+                }),
+                "ordered"
+            ),
+            (Some(SegmentBlock::Quote), "Quoted line. Still quoted."),
+            (Some(SegmentBlock::Code), "fn main() {}"),
+            (None, "A figure"),
+            (Some(SegmentBlock::Heading { level: 2 }), "Setext heading"),
+        ]
+    );
+    assert_eq!(doc.blocks[7].image_url.as_deref(), Some("img/fig%201.png"));
+}
+
+#[test]
+fn first_heading_becomes_title_without_front_matter() {
+    let doc = parse_markdown("# 标题\n\n第一行\n第二行。");
+    assert_eq!(doc.title.as_deref(), Some("标题"));
+    assert_eq!(doc.blocks.len(), 1);
+    assert_eq!(doc.blocks[0].text, "第一行第二行。");
+}
+
+#[test]
+fn pieces_keep_block_structure() {
+    let doc = parse_markdown("## Intro\n\nOne sentence here. Another one follows.\n\n> Quote.");
+    let pieces = markdown_pieces(&doc, Some("en"));
+    let summary: Vec<(&str, bool, Option<SegmentBlock>)> = pieces
+        .iter()
+        .map(|(p, b)| (p.text.as_str(), p.is_new_paragraph, *b))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Intro", true, Some(SegmentBlock::Heading { level: 2 })),
+            ("One sentence here.", true, None),
+            ("Another one follows.", false, None),
+            ("Quote.", true, Some(SegmentBlock::Quote)),
+        ]
+    );
+    assert_eq!(
+        plain_content(&doc),
+        "Intro\n\nOne sentence here. Another one follows.\n\nQuote."
+    );
+}
+
+#[test]
+fn relative_images_resolve_against_file_dir() {
+    let mut doc = parse_markdown("![fig](images/a%20b.png)\n\n![remote](https://x.y/z.png)");
+    let dir = std::env::temp_dir();
+    resolve_image_urls(&mut doc, &dir);
+    let expected = url::Url::from_file_path(dir.join("images/a b.png"))
+        .unwrap()
+        .to_string();
+    assert_eq!(doc.blocks[0].image_url.as_deref(), Some(expected.as_str()));
+    assert_eq!(
+        doc.blocks[1].image_url.as_deref(),
+        Some("https://x.y/z.png")
+    );
+}
@@ -0,0 +1,92 @@
+use openkoto_desktop_lib::model_metadata::{
+    context_warning, estimate_request, estimate_tokens, parse_openrouter_models,
+};
+
+const MODELS_JSON: &str = r#"{
+    "data": [
+        {
+            "id": "openai/gpt-4o-mini",
+            "name": "OpenAI: GPT-4o-mini",
+            "context_length": 128000,
+            "pricing": {"prompt": "0.00000015", "completion": "0.0000006"},
+            "top_provider": {"context_length": 128000, "max_completion_tokens": 16384}
+        },
+        {
+            "id": "tiny/model",
+            "pricing": {"prompt": "0", "completion": "0"},
+            "top_provider": {"context_length": 100}
+        }
+    ]
+}"#;
+
+#[test]
+fn parses_openrouter_model_list() {
+    let models = parse_openrouter_models(MODELS_JSON).unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].context_length, Some(128000));
+    assert_eq!(models[0].max_completion_tokens, Some(16384));
+    assert_eq!(models[0].prompt_price, Some(0.00000015));
+    // 缺少名称和顶层上下文长度时回退到 id 和 top_provider
+    assert_eq!(models[1].name, "tiny/model");
+    assert_eq!(models[1].context_length, Some(100));
+}
+
+#[test]
+fn estimates_tokens_for_mixed_scripts() {
+    assert_eq!(estimate_tokens("abcdefgh"), 2);
+    assert_eq!(estimate_tokens("日本語"), 3);
+    assert_eq!(estimate_tokens(""), 0);
+}
+
+#[test]
+fn estimates_cost_and_flags_context_overflow() {
+    let models = parse_openrouter_models(MODELS_JSON).unwrap();
+
+    let estimate = estimate_request("openai/gpt-4o-mini", Some(&models[0]), "abcd", 10);
+    assert_eq!(estimate.prompt_tokens, 1);
+    let cost = estimate.estimated_cost.unwrap();
+    assert!((cost - (0.00000015 + 0.0000060)).abs() < 1e-12);
+    assert!(!estimate.exceeds_context);
+    assert!(context_warning(&estimate).is_none());
+
+    let long_text = "字".repeat(150);
+    let estimate = estimate_request("tiny/model", Some(&models[1]), &long_text, 0);
+    assert!(estimate.exceeds_context);
+    assert!(context_warning(&estimate).is_some());
+
+    let estimate = estimate_request("local", None, "abcd", 0);
+    assert_eq!(estimate.context_length, None);
+    assert_eq!(estimate.estimated_cost, None);
+}
+
+#[test]
+fn batches_adapt_to_segment_length_and_model() {
+    use openkoto_desktop_lib::model_metadata::{batch_token_budget, plan_batches, MAX_BATCH_ITEMS};
+
+    let budget = batch_token_budget(None);
+    // 字幕短句：一批可超过原来固定的 30 条，但不超过上限
+    let short: Vec<String> = (0..250).map(|i| format!("Line {}", i)).collect();
+    let batches = plan_batches(short.iter().map(String::as_str), budget, MAX_BATCH_ITEMS);
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0], 0..100);
+    assert_eq!(batches[2], 200..250);
+
+    // 长句：每批更少
+    let long: Vec<String> = (0..30).map(|_| "word ".repeat(400)).collect();
+    let batches = plan_batches(long.iter().map(String::as_str), budget, MAX_BATCH_ITEMS);
+    assert!(batches.len() > 1);
+    assert!(batches.iter().all(|b| b.len() < 30));
+    assert_eq!(batches.last().unwrap().end, 30);
+
+    // 超出预算的单条独占一批
+    let huge = "字".repeat(10_000);
+    assert_eq!(
+        plan_batches([huge.as_str(), "a"], budget, 10),
+        vec![0..1, 1..2]
+    );
+
+    // 上下文很小的模型预算更小
+    let models = parse_openrouter_models(MODELS_JSON).unwrap();
+    assert!(batch_token_budget(Some(&models[1])) < budget);
+    assert!(plan_batches(std::iter::empty(), budget, 10).is_empty());
+}
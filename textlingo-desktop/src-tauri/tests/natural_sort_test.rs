@@ -0,0 +1,47 @@
+use std::cmp::Ordering;
+use std::path::Path;
+
+use openkoto_desktop_lib::commands::{is_local_media_file, natural_cmp};
+
+#[test]
+fn numbers_compare_by_value() {
+    let mut names = vec![
+        "Lesson 10.mp4",
+        "Lesson 2.mp4",
+        "Lesson 1.mp4",
+        "lesson 3.mp4",
+    ];
+    names.sort_by(|a, b| natural_cmp(a, b));
+    assert_eq!(
+        names,
+        vec![
+            "Lesson 1.mp4",
+            "Lesson 2.mp4",
+            "lesson 3.mp4",
+            "Lesson 10.mp4"
+        ]
+    );
+}
+
+#[test]
+fn chinese_episode_names_sort_naturally() {
+    let mut names = vec!["第12课.mkv", "第2课.mkv", "第1课.mkv"];
+    names.sort_by(|a, b| natural_cmp(a, b));
+    assert_eq!(names, vec!["第1课.mkv", "第2课.mkv", "第12课.mkv"]);
+}
+
+#[test]
+fn leading_zeros_do_not_change_order() {
+    assert_eq!(natural_cmp("ep02", "ep10"), Ordering::Less);
+    assert_eq!(natural_cmp("ep002", "ep2"), Ordering::Greater);
+    assert_eq!(natural_cmp("ep2", "ep2"), Ordering::Equal);
+    assert_eq!(natural_cmp("ep", "ep1"), Ordering::Less);
+}
+
+#[test]
+fn only_media_files_are_imported() {
+    assert!(is_local_media_file(Path::new("/tmp/a/Lesson 1.MP4")));
+    assert!(is_local_media_file(Path::new("/tmp/a/track.flac")));
+    assert!(!is_local_media_file(Path::new("/tmp/a/notes.txt")));
+    assert!(!is_local_media_file(Path::new("/tmp/a/README")));
+}
@@ -0,0 +1,81 @@
+use openkoto_desktop_lib::obsidian_export::{note_file_stem, note_path, render_article_note};
+use openkoto_desktop_lib::types::{Article, FavoriteGrammar, FavoriteVocabulary};
+
+fn article(id: &str, title: &str) -> Article {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "title": title,
+        "content": "Hello.",
+        "source_url": "https://example.com/post",
+        "media_path": null,
+        "created_at": "2026-01-01T00:00:00Z",
+        "translated": false
+    }))
+    .unwrap()
+}
+
+fn vocab(word: &str) -> FavoriteVocabulary {
+    serde_json::from_value(serde_json::json!({
+        "id": word,
+        "word": word,
+        "meaning": "meaning",
+        "usage": "",
+        "example": null,
+        "reading": "よみ",
+        "source_article_id": "a1",
+        "source_article_title": null,
+        "created_at": "2026-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn note_contains_frontmatter_wikilinks_and_grammar() {
+    let grammar = FavoriteGrammar {
+        id: "g1".to_string(),
+        point: "〜ばかり".to_string(),
+        explanation: "just did".to_string(),
+        example: Some("食べたばかり".to_string()),
+        source_article_id: Some("a1".to_string()),
+        source_article_title: None,
+        source_segment_id: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    };
+    let note = render_article_note(
+        &article("a1", "News: today"),
+        &[],
+        &[vocab("勉強"), vocab("a|b")],
+        &[grammar],
+    );
+
+    assert!(note.starts_with("---\nopenkoto_id: \"a1\"\n"));
+    assert!(note.contains("source: \"https://example.com/post\""));
+    assert!(note.contains("- [[勉強]] (よみ) — meaning"));
+    assert!(note.contains("- [[a b]] (よみ) — meaning"));
+    assert!(note.contains("- **〜ばかり** — just did\n  - 食べたばかり"));
+    assert!(!note.contains("## Highlights"));
+}
+
+#[test]
+fn re_export_reuses_the_existing_note() {
+    assert_eq!(note_file_stem("a/b: [c]"), "a-b- -c-");
+    assert_eq!(note_file_stem("  "), "Untitled");
+
+    let dir = std::env::temp_dir().join(format!("obsidian-export-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let first = article("a1", "Title");
+    let path = note_path(&dir, &first);
+    assert_eq!(path, dir.join("Title.md"));
+    std::fs::write(&path, render_article_note(&first, &[], &[], &[])).unwrap();
+
+    // 改名后仍写回原文件
+    let renamed = article("a1", "New title");
+    assert_eq!(note_path(&dir, &renamed), path);
+
+    // 其他文章同名时不覆盖
+    let other = article("b2345678-xyz", "Title");
+    assert_eq!(note_path(&dir, &other), dir.join("Title (b2345678).md"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,72 @@
+use openkoto_desktop_lib::parallel_text::{
+    align_texts, parse_aligned_lines, parse_pdf_pairs, source_content,
+};
+
+#[test]
+fn single_file_splits_on_tab_or_double_bar() {
+    let text =
+        "\u{feff}こんにちは。\tHello.\n元気？ || How are you?\n\n新しい段落。\tNew paragraph.\n";
+    let pairs = parse_aligned_lines(text).unwrap();
+
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[0].source, "こんにちは。");
+    assert_eq!(pairs[0].translation, "Hello.");
+    assert_eq!(pairs[1].source, "元気？");
+    assert_eq!(pairs[1].translation, "How are you?");
+    assert!(pairs[0].is_new_paragraph);
+    assert!(!pairs[1].is_new_paragraph);
+    assert!(pairs[2].is_new_paragraph);
+    assert_eq!(
+        source_content(&pairs),
+        "こんにちは。\n元気？\n\n新しい段落。"
+    );
+}
+
+#[test]
+fn single_file_reports_line_without_separator() {
+    let error = parse_aligned_lines("a\tb\nno separator here").unwrap_err();
+    assert!(error.contains('2'));
+}
+
+#[test]
+fn two_files_align_by_non_empty_lines() {
+    let source = "第一句。\n第二句。\n\n第三句。";
+    let translation = "First.\n\nSecond.\nThird.\n";
+    let pairs = align_texts(source, translation).unwrap();
+
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[1].translation, "Second.");
+    assert!(pairs[2].is_new_paragraph);
+    assert!(!pairs[1].is_new_paragraph);
+}
+
+#[test]
+fn two_files_with_different_line_counts_fail() {
+    assert!(align_texts("a\nb\nc", "A\nB").is_err());
+}
+
+#[test]
+fn pdf_pairs_skip_page_numbers_and_keep_languages() {
+    let json = r#"{
+        "lang_in": "en",
+        "lang_out": "zh",
+        "pairs": [
+            {"page": 0, "source": "Attention Is All You Need", "translation": "注意力就是你所需要的"},
+            {"page": 0, "source": " 12 ", "translation": "12"},
+            {"page": 1, "source": "We propose a new architecture.", "translation": ""}
+        ]
+    }"#;
+    let (parsed, pairs) = parse_pdf_pairs(json).unwrap();
+
+    assert_eq!(parsed.lang_in, "en");
+    assert_eq!(parsed.lang_out, "zh");
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[0].translation, "注意力就是你所需要的");
+    assert_eq!(pairs[1].source, "We propose a new architecture.");
+    assert!(pairs.iter().all(|p| p.is_new_paragraph));
+}
+
+#[test]
+fn pdf_pairs_reject_invalid_json() {
+    assert!(parse_pdf_pairs("not json").is_err());
+}
@@ -0,0 +1,57 @@
+use openkoto_desktop_lib::prompt_templates::{
+    detect_reading_language, reading_style, render, validate, PromptKind,
+};
+
+#[test]
+fn default_templates_are_valid() {
+    for kind in PromptKind::ALL {
+        assert!(
+            validate(kind, kind.default_template()).is_ok(),
+            "{:?}",
+            kind
+        );
+    }
+}
+
+#[test]
+fn render_replaces_known_placeholders_and_keeps_json_braces() {
+    let rendered = render(
+        PromptKind::SegmentExplanation.default_template(),
+        &[("native_language", "中文"), ("text", "こんにちは")],
+    );
+    assert!(rendered.contains("The user's native language is 中文."));
+    assert!(rendered.contains("---\nこんにちは\n---"));
+    assert!(rendered.contains("{\n  \"translation\""));
+    assert!(!rendered.contains("{native_language}"));
+}
+
+#[test]
+fn validate_rejects_empty_and_unknown_placeholders() {
+    assert!(validate(PromptKind::Translate, "   ").is_err());
+    assert!(validate(
+        PromptKind::Translate,
+        "Translate literally into {target_language}."
+    )
+    .is_ok());
+    assert!(validate(
+        PromptKind::Translate,
+        "Translate {text} into {target_language}."
+    )
+    .is_err());
+    assert!(validate(
+        PromptKind::AnalysisSummary,
+        "Summarize as JSON: {\"summary\": \"...\"}"
+    )
+    .is_ok());
+}
+
+#[test]
+fn reading_style_follows_language_and_detection() {
+    assert!(reading_style("ja").contains("かんじ"));
+    assert!(reading_style("zh-CN").contains("拼音"));
+    assert!(reading_style("en").contains("IPA"));
+    assert_eq!(detect_reading_language("今日は晴れです"), Some("ja"));
+    assert_eq!(detect_reading_language("今天天气很好"), Some("zh"));
+    assert_eq!(detect_reading_language("안녕하세요"), Some("ko"));
+    assert_eq!(detect_reading_language("Hello"), None);
+}
@@ -0,0 +1,32 @@
+use chrono::TimeZone;
+use openkoto_desktop_lib::error::{AppError, ErrorKind};
+use openkoto_desktop_lib::jobs::{parse_retry_after, rate_limit_error, retry_after_secs};
+
+#[test]
+fn retry_after_header_accepts_seconds_and_http_dates() {
+    let now = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    assert_eq!(parse_retry_after("30", now), Some(30));
+    assert_eq!(
+        parse_retry_after("Sun, 01 Mar 2026 12:02:00 GMT", now),
+        Some(120)
+    );
+    assert_eq!(
+        parse_retry_after("Sun, 01 Mar 2026 11:00:00 GMT", now),
+        Some(0)
+    );
+    assert_eq!(parse_retry_after("soon", now), None);
+}
+
+#[test]
+fn rate_limit_errors_carry_the_delay() {
+    let message = rate_limit_error("API", Some(45), r#"{"error": "slow down"}"#);
+    assert_eq!(retry_after_secs(&message), Some(45));
+    assert_eq!(AppError::ai_provider(message).kind, ErrorKind::RateLimited);
+
+    let without_header = rate_limit_error("Google API", None, "quota exceeded");
+    assert_eq!(retry_after_secs(&without_header), None);
+    assert_eq!(
+        AppError::ai_provider(without_header).kind,
+        ErrorKind::RateLimited
+    );
+}
@@ -0,0 +1,77 @@
+use openkoto_desktop_lib::readings::{
+    annotate_pinyin, kana_to_romaji, local_phonetic, local_reading, romanize_korean,
+    transliterate_arabic, transliterate_cyrillic,
+};
+use openkoto_desktop_lib::types::ReadingEngine;
+
+#[test]
+fn annotates_each_run_of_han_characters() {
+    assert_eq!(
+        annotate_pinyin("你好，世界！"),
+        "你好(nǐ hǎo)，世界(shì jiè)！"
+    );
+    assert_eq!(annotate_pinyin("学习 AI"), "学习(xué xí) AI");
+    assert_eq!(annotate_pinyin("abc"), "abc");
+}
+
+#[test]
+fn chinese_articles_default_to_local_pinyin() {
+    assert_eq!(ReadingEngine::default_for("zh"), ReadingEngine::Local);
+    assert_eq!(ReadingEngine::default_for("zh-TW"), ReadingEngine::Local);
+    assert_eq!(ReadingEngine::default_for("ko"), ReadingEngine::Local);
+    assert_eq!(ReadingEngine::default_for("ru"), ReadingEngine::Local);
+    assert_eq!(ReadingEngine::default_for("ja"), ReadingEngine::Ai);
+    assert_eq!(
+        serde_json::from_str::<ReadingEngine>("\"pinyin\"").unwrap(),
+        ReadingEngine::Local
+    );
+}
+
+#[test]
+fn romanizes_korean_with_liaison() {
+    assert_eq!(romanize_korean("안녕하세요"), "annyeonghaseyo");
+    assert_eq!(romanize_korean("한국어 공부"), "hangugeo gongbu");
+    assert_eq!(romanize_korean("ABC 읽다"), "ABC ikda");
+}
+
+#[test]
+fn transliterates_cyrillic_and_arabic() {
+    assert_eq!(transliterate_cyrillic("Привет, мир!"), "Privet, mir!");
+    assert_eq!(transliterate_cyrillic("Щука і їжак"), "Shchuka i yizhak");
+    assert_eq!(transliterate_arabic("كِتَاب"), "kitaab");
+    assert_eq!(local_reading("uk", "Дім").as_deref(), Some("Dim"));
+    assert_eq!(local_reading("ja", "東京"), None);
+}
+
+#[test]
+fn converts_kana_to_hepburn_romaji() {
+    assert_eq!(kana_to_romaji("ねこ").as_deref(), Some("neko"));
+    assert_eq!(kana_to_romaji("しゃしん").as_deref(), Some("shashin"));
+    assert_eq!(kana_to_romaji("きっぷ").as_deref(), Some("kippu"));
+    assert_eq!(kana_to_romaji("まっちゃ").as_deref(), Some("matcha"));
+    assert_eq!(kana_to_romaji("ほんや").as_deref(), Some("hon'ya"));
+    assert_eq!(kana_to_romaji("コーヒー").as_deref(), Some("kōhī"));
+    assert_eq!(kana_to_romaji("パーティー").as_deref(), Some("pātī"));
+    assert_eq!(kana_to_romaji("ファイル").as_deref(), Some("fairu"));
+    // 含汉字时无法本地转写
+    assert_eq!(kana_to_romaji("猫"), None);
+}
+
+#[test]
+fn vocabulary_phonetics_fall_back_from_reading_to_word() {
+    assert_eq!(
+        local_phonetic("ja", "東京", Some("とうきょう")).as_deref(),
+        Some("toukyou")
+    );
+    assert_eq!(
+        local_phonetic("ja", "ありがとう", None).as_deref(),
+        Some("arigatou")
+    );
+    assert_eq!(local_phonetic("ja", "東京", None), None);
+    assert_eq!(
+        local_phonetic("ko", "한국어", None).as_deref(),
+        Some("hangugeo")
+    );
+    // 其他语言的 IPA 由 AI 生成
+    assert_eq!(local_phonetic("fr", "chat", None), None);
+}
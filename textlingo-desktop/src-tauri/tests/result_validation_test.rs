@@ -0,0 +1,52 @@
+use openkoto_desktop_lib::result_validation::{
+    validate_explanation, validate_translation, MAX_FIELD_CHARS,
+};
+use openkoto_desktop_lib::types::SegmentExplanation;
+use serde_json::json;
+
+fn explanation(value: serde_json::Value) -> SegmentExplanation {
+    let mut base = json!({
+        "translation": "我喜欢猫。",
+        "explanation": "简单句。",
+        "reading_text": null,
+        "vocabulary": [{ "word": "猫", "meaning": "cat", "usage": "", "example": null, "reading": "ねこ" }],
+        "grammar_points": [{ "point": "が好き", "explanation": "表示喜欢", "example": null }],
+        "cultural_context": null,
+        "difficulty_level": "N5",
+        "learning_tips": null
+    });
+    for (key, field) in value.as_object().unwrap() {
+        base[key] = field.clone();
+    }
+    serde_json::from_value(base).unwrap()
+}
+
+#[test]
+fn explanations_need_content_in_required_fields() {
+    assert!(validate_explanation(&explanation(json!({}))).is_ok());
+
+    let err = validate_explanation(&explanation(json!({ "translation": "  " }))).unwrap_err();
+    assert_eq!(err, "translation is empty");
+
+    let err = validate_explanation(&explanation(json!({
+        "vocabulary": [{ "word": "", "meaning": "cat", "usage": "" }]
+    })))
+    .unwrap_err();
+    assert_eq!(err, "vocabulary[0].word is empty");
+
+    let long = "あ".repeat(MAX_FIELD_CHARS + 1);
+    assert!(validate_explanation(&explanation(json!({ "learning_tips": long }))).is_err());
+    assert!(validate_explanation(&explanation(json!({ "explanation": "a\u{0}b" }))).is_err());
+}
+
+#[test]
+fn translations_reject_empty_runaway_and_json_output() {
+    assert!(validate_translation("猫が好きです。", "I like cats.\nReally.").is_ok());
+    assert!(validate_translation("猫が好きです。", "").is_err());
+    assert!(validate_translation("Hi.", &"ha".repeat(150)).is_err());
+    assert!(validate_translation("Hi.", r#"{"translation": "你好。"}"#).is_err());
+    // 原文本身是 JSON 时不误判
+    assert!(validate_translation(r#"{"a": 1}"#, r#"{"a": 1}"#).is_ok());
+    // 方括号开头但不是 JSON 的译文正常保存
+    assert!(validate_translation("[Music] Hello.", "[音乐] 你好。").is_ok());
+}
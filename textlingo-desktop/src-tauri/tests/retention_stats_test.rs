@@ -0,0 +1,86 @@
+use openkoto_desktop_lib::commands::build_retention_stats;
+use openkoto_desktop_lib::types::{FavoriteVocabulary, ReviewLogEntry};
+
+fn make_vocab(id: &str, pack: &str) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: format!("word-{}", id),
+        meaning: "meaning".to_string(),
+        usage: "usage".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: vec![pack.to_string()],
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
+        srs_state: "review".to_string(),
+        ease_factor: 2.5,
+        repetitions: 1,
+        interval_days: 1,
+        due_date: "2026-03-01".to_string(),
+        last_reviewed_at: None,
+        review_count: 1,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+fn log(vocabulary_id: &str, grade: &str, date: &str, interval_days: i32) -> ReviewLogEntry {
+    ReviewLogEntry {
+        id: format!("{}-{}", vocabulary_id, date),
+        vocabulary_id: vocabulary_id.to_string(),
+        grade: grade.to_string(),
+        review_date: date.to_string(),
+        interval_days,
+        reviewed_at: format!("{}T10:00:00Z", date),
+    }
+}
+
+fn sample() -> (Vec<ReviewLogEntry>, Vec<FavoriteVocabulary>) {
+    let favorites = vec![make_vocab("a", "p1"), make_vocab("b", "p2")];
+    let review_log = vec![
+        // a: 首次复习不计入，之后间隔 1 天记住，间隔 6 天忘记
+        log("a", "known", "2026-02-01", 1),
+        log("a", "known", "2026-02-02", 6),
+        log("a", "unknown", "2026-03-10", 1),
+        // b: 间隔 3 天时一次 uncertain（计为记住）
+        log("b", "known", "2026-02-27", 3),
+        log("b", "uncertain", "2026-03-02", 8),
+    ];
+    (review_log, favorites)
+}
+
+#[test]
+fn buckets_reviews_by_previous_interval() {
+    let (review_log, favorites) = sample();
+    let stats = build_retention_stats(&review_log, &favorites, None, None);
+
+    assert_eq!(stats.review_count, 3);
+    assert_eq!(stats.passed_count, 2);
+    let bucket = |label: &str| stats.buckets.iter().find(|b| b.label == label).unwrap();
+    assert_eq!(bucket("1d").review_count, 1);
+    assert_eq!(bucket("1d").retention, 1.0);
+    assert_eq!(bucket("2-3d").passed_count, 1);
+    assert_eq!(bucket("4-7d").review_count, 1);
+    assert_eq!(bucket("4-7d").retention, 0.0);
+    assert_eq!(bucket("91d+").max_days, None);
+}
+
+#[test]
+fn filters_by_month_and_pack() {
+    let (review_log, favorites) = sample();
+
+    let march = build_retention_stats(&review_log, &favorites, Some("2026-03"), None);
+    assert_eq!(march.review_count, 2);
+    assert_eq!(march.retention, 0.5);
+
+    let p2 = build_retention_stats(&review_log, &favorites, None, Some("p2"));
+    assert_eq!(p2.review_count, 1);
+    assert_eq!(p2.retention, 1.0);
+}
@@ -0,0 +1,60 @@
+use openkoto_desktop_lib::types::{Article, ArticleSegment};
+
+fn segment() -> ArticleSegment {
+    serde_json::from_str(
+        r#"{
+            "id": "s1",
+            "article_id": "a1",
+            "order": 0,
+            "text": "こんにちは",
+            "reading_text": null,
+            "translation": "你好",
+            "explanation": null,
+            "created_at": "2026-01-01T00:00:00Z"
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn legacy_segment_uses_translation_as_primary() {
+    let seg = segment();
+    assert!(seg.translations.is_empty());
+    assert_eq!(seg.translation_for("zh-CN", None), Some("你好"));
+    assert_eq!(seg.translation_for("zh-CN", Some("zh-CN")), Some("你好"));
+    assert_eq!(seg.translation_for("en", Some("zh-CN")), None);
+}
+
+#[test]
+fn second_language_is_stored_separately() {
+    let mut seg = segment();
+    seg.set_translation("en", Some("zh-CN"), "Hello".to_string());
+    seg.set_translation("ZH-cn", Some("zh-CN"), "您好".to_string());
+
+    assert_eq!(seg.translation.as_deref(), Some("您好"));
+    assert_eq!(seg.translation_for("en", Some("zh-CN")), Some("Hello"));
+
+    let json = serde_json::to_string(&seg).unwrap();
+    let restored: ArticleSegment = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        restored.translations.get("en").map(String::as_str),
+        Some("Hello")
+    );
+}
+
+#[test]
+fn article_without_translation_language_still_loads() {
+    let article: Article = serde_json::from_str(
+        r#"{
+            "id": "a1",
+            "title": "t",
+            "content": "c",
+            "source_url": null,
+            "media_path": null,
+            "created_at": "2026-01-01T00:00:00Z",
+            "translated": true
+        }"#,
+    )
+    .unwrap();
+    assert!(article.translation_language.is_none());
+}
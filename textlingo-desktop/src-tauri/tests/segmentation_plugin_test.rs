@@ -0,0 +1,55 @@
+use openkoto_desktop_lib::segmentation_plugin::parse_plugin_segments;
+use openkoto_desktop_lib::types::AppConfig;
+
+#[test]
+fn parses_string_and_object_segments() {
+    let pieces = parse_plugin_segments(r#"["今日は晴れ。", " ", "散歩に行く。"]"#).unwrap();
+    let texts: Vec<&str> = pieces.iter().map(|p| p.text.as_str()).collect();
+    assert_eq!(texts, ["今日は晴れ。", "散歩に行く。"]);
+    // 第一段总是另起一行
+    assert!(pieces[0].is_new_paragraph);
+    assert!(!pieces[1].is_new_paragraph);
+
+    let pieces = parse_plugin_segments(
+        r#"{"segments": [{"text": "첫 문장."}, {"text": "둘째 문단.", "new_paragraph": true}]}"#,
+    )
+    .unwrap();
+    assert_eq!(pieces.len(), 2);
+    assert!(pieces[1].is_new_paragraph);
+    assert!(pieces.iter().all(|p| p.image_url.is_none()));
+}
+
+#[test]
+fn rejects_invalid_or_empty_output() {
+    assert!(parse_plugin_segments("not json").is_err());
+    assert!(parse_plugin_segments(r#"{"ok": true}"#).is_err());
+    assert!(parse_plugin_segments("[]").is_err());
+}
+
+#[test]
+fn plugins_are_chosen_per_language() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.segmentation_plugin(Some("ja")), None);
+
+    config
+        .segmentation_plugins
+        .insert("ja".to_string(), "mecab-segmenter".to_string());
+    config
+        .segmentation_plugins
+        .insert("zh".to_string(), "jieba-segmenter".to_string());
+    config
+        .segmentation_plugins
+        .insert("ko".to_string(), " ".to_string());
+
+    assert_eq!(
+        config.segmentation_plugin(Some("JA")),
+        Some("mecab-segmenter")
+    );
+    // 带地区的语言代码退回主语言
+    assert_eq!(
+        config.segmentation_plugin(Some("zh-CN")),
+        Some("jieba-segmenter")
+    );
+    assert_eq!(config.segmentation_plugin(Some("ko")), None);
+    assert_eq!(config.segmentation_plugin(None), None);
+}
@@ -0,0 +1,442 @@
+use openkoto_desktop_lib::segmentation::{
+    carry_over_segments, chunk_text, group_semantic_units, limit_piece_length,
+    merge_adjacent_segments, move_segment, remove_segment, segments_to_prefetch, semantic_units,
+    split_content, split_long_sentence, split_segment_at, title_from_content, MAX_TITLE_CHARS,
+};
+use openkoto_desktop_lib::types::{ArticleSegment, MoveDirection, SegmentationMode};
+
+fn texts(content: &str, language: Option<&str>, mode: SegmentationMode) -> Vec<String> {
+    split_content(content, language, &mode)
+        .into_iter()
+        .map(|piece| piece.text)
+        .collect()
+}
+
+const LYRICS: &str = "Twinkle, twinkle, little star.\nHow I wonder what you are!\n\nUp above the world so high.\nLike a diamond in the sky.";
+
+#[test]
+fn sentence_mode_keeps_existing_behaviour() {
+    let pieces = split_content(
+        "Hello there. How are you?\n我很好。谢谢！",
+        None,
+        &SegmentationMode::Sentence,
+    );
+    let texts: Vec<&str> = pieces.iter().map(|p| p.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec!["Hello there.", "How are you?", "我很好。", "谢谢！"]
+    );
+    let new_paragraph: Vec<bool> = pieces.iter().map(|p| p.is_new_paragraph).collect();
+    assert_eq!(new_paragraph, vec![true, false, true, false]);
+}
+
+#[test]
+fn line_and_paragraph_modes() {
+    assert_eq!(texts(LYRICS, Some("en"), SegmentationMode::Line).len(), 4);
+    assert_eq!(
+        texts(LYRICS, Some("en"), SegmentationMode::Paragraph),
+        vec![
+            "Twinkle, twinkle, little star.\nHow I wonder what you are!",
+            "Up above the world so high.\nLike a diamond in the sky.",
+        ]
+    );
+    assert_eq!(
+        texts(LYRICS, Some("en"), SegmentationMode::NoSplit).len(),
+        1
+    );
+}
+
+#[test]
+fn images_stay_separate_even_without_splitting() {
+    let pieces = split_content(
+        "First part.\n![Cover](https://example.com/a.png)\nSecond part.",
+        None,
+        &SegmentationMode::NoSplit,
+    );
+    assert_eq!(pieces.len(), 3);
+    assert_eq!(
+        pieces[1].image_url.as_deref(),
+        Some("https://example.com/a.png")
+    );
+}
+
+#[test]
+fn fixed_length_packs_sentences_and_cuts_long_ones() {
+    assert_eq!(
+        texts(
+            "One. Two. Three. Four.",
+            Some("en"),
+            SegmentationMode::FixedLength { max_chars: 10 },
+        ),
+        vec!["One. Two.", "Three.", "Four."]
+    );
+    assert_eq!(
+        texts(
+            "一二三四五六七八九十。",
+            Some("zh"),
+            SegmentationMode::FixedLength { max_chars: 4 },
+        ),
+        vec!["一二三四", "五六七八", "九十。"]
+    );
+}
+
+#[test]
+fn segmentation_mode_uses_tagged_json() {
+    let mode: SegmentationMode =
+        serde_json::from_str(r#"{"mode": "fixed_length", "max_chars": 80}"#).unwrap();
+    assert_eq!(mode, SegmentationMode::FixedLength { max_chars: 80 });
+    let mode: SegmentationMode = serde_json::from_str(r#"{"mode": "no_split"}"#).unwrap();
+    assert_eq!(mode, SegmentationMode::NoSplit);
+}
+
+#[test]
+fn chunk_text_splits_long_content_on_line_boundaries() {
+    let content = "aaaa\nbbbb\n\ncccc\ndddddddddd";
+    assert_eq!(
+        chunk_text(content, 9),
+        vec!["aaaa\nbbbb", "cccc", "ddddddddd", "d"]
+    );
+    assert_eq!(chunk_text("short", 100), vec!["short"]);
+}
+
+fn sentences(content: &str, language: &str) -> Vec<String> {
+    texts(content, Some(language), SegmentationMode::Sentence)
+}
+
+#[test]
+fn japanese_quotes_stay_in_one_sentence() {
+    assert_eq!(
+        sentences("「今日は晴れだ。」と彼は言った。明日は雨だ。", "ja"),
+        vec!["「今日は晴れだ。」と彼は言った。", "明日は雨だ。"]
+    );
+    assert_eq!(
+        sentences("「そうですか。では行きます。」「気をつけて！」", "ja"),
+        vec!["「そうですか。では行きます。」", "「気をつけて！」"]
+    );
+    assert_eq!(
+        sentences("『本当？』って聞いた。", "ja"),
+        vec!["『本当？』って聞いた。"]
+    );
+}
+
+#[test]
+fn terminator_runs_and_ellipses_are_kept_together() {
+    assert_eq!(
+        sentences("本当に？！信じられない……。次へ。", "ja"),
+        vec!["本当に？！", "信じられない……。", "次へ。"]
+    );
+    assert_eq!(
+        sentences("Wait... what happened? Nothing. Really?!", "en"),
+        vec!["Wait... what happened?", "Nothing.", "Really?!"]
+    );
+}
+
+#[test]
+fn chinese_quotes_and_unbalanced_brackets() {
+    assert_eq!(
+        sentences("他说：“我明天来。你等我。”然后走了。", "zh"),
+        vec!["他说：“我明天来。你等我。”", "然后走了。"]
+    );
+    // 引号没有闭合时按普通规则切分
+    assert_eq!(
+        sentences("「閉じない引用。次の文。", "ja"),
+        vec!["「閉じない引用。", "次の文。"]
+    );
+}
+
+fn segment(id: &str, text: &str, translation: Option<&str>) -> ArticleSegment {
+    ArticleSegment {
+        id: id.to_string(),
+        article_id: "a1".to_string(),
+        order: 0,
+        text: text.to_string(),
+        reading_text: None,
+        translation: translation.map(str::to_string),
+        explanation: None,
+        start_time: None,
+        end_time: None,
+        created_at: String::new(),
+        is_new_paragraph: false,
+        image_url: None,
+        translations: Default::default(),
+        processing_status: None,
+        block: None,
+        speaker: None,
+    }
+}
+
+#[test]
+fn resegmenting_keeps_translations_of_unchanged_text() {
+    let mut old = vec![
+        segment("s1", "Hello there.", Some("你好。")),
+        segment("s2", "How are you?", Some("你好吗？")),
+        segment("s3", "Again.", Some("再一次。")),
+        segment("s4", "Again.", Some("又一次。")),
+    ];
+    old[0].speaker = Some("Speaker 1".to_string());
+    let mut new = vec![
+        segment("n1", "Hello  there.", None),
+        segment("n2", "How are you today?", None),
+        segment("n3", "Again.", None),
+        segment("n4", "Again.", None),
+    ];
+
+    assert_eq!(carry_over_segments(&old, &mut new), 3);
+    assert_eq!(new[0].id, "s1");
+    assert_eq!(new[0].translation.as_deref(), Some("你好。"));
+    assert_eq!(new[0].speaker.as_deref(), Some("Speaker 1"));
+    // 文本有改动的段落不沿用旧译文
+    assert_eq!(new[1].id, "n2");
+    assert_eq!(new[1].translation, None);
+    // 重复文本按顺序对应
+    assert_eq!(new[2].translation.as_deref(), Some("再一次。"));
+    assert_eq!(new[3].translation.as_deref(), Some("又一次。"));
+}
+
+#[test]
+fn splitting_a_segment_interpolates_subtitle_times() {
+    let mut merged = segment("s1", "Hello there. How are you?", Some("你好。你好吗？"));
+    merged.start_time = Some(10.0);
+    merged.end_time = Some(15.0);
+    merged.is_new_paragraph = true;
+
+    let (first, second) = split_segment_at(&merged, 12).unwrap();
+    assert_eq!(first.id, "s1");
+    assert_eq!(first.text, "Hello there.");
+    assert_eq!(second.text, "How are you?");
+    assert_ne!(second.id, "s1");
+    assert!(first.translation.is_none() && second.translation.is_none());
+    assert!(first.is_new_paragraph && !second.is_new_paragraph);
+    // 25 个字符中的第 12 个：10 + 5 * 12 / 25
+    assert_eq!(first.end_time, Some(12.4));
+    assert_eq!(second.start_time, Some(12.4));
+    assert_eq!(second.end_time, Some(15.0));
+
+    assert!(split_segment_at(&merged, 0).is_none());
+    assert!(split_segment_at(&merged, 25).is_none());
+    // 拆分点一侧只有空白
+    assert!(split_segment_at(&segment("s2", "Hi ", None), 2).is_none());
+}
+
+#[test]
+fn merging_adjacent_segments_joins_text_and_times() {
+    let mut segments = vec![
+        segment("s1", "Hello.", Some("你好。")),
+        segment("s2", "everyone.", Some("大家。")),
+        segment("s3", "Next line.", None),
+    ];
+    segments[0].start_time = Some(1.0);
+    segments[0].end_time = Some(1.8);
+    segments[1].start_time = Some(1.9);
+    segments[1].end_time = Some(2.5);
+    for (i, s) in segments.iter_mut().enumerate() {
+        s.order = i as i32;
+    }
+
+    let ids = vec!["s2".to_string(), "s1".to_string()];
+    assert!(merge_adjacent_segments(&mut segments, &ids));
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].id, "s1");
+    assert_eq!(segments[0].text, "Hello. everyone.");
+    assert_eq!(segments[0].start_time, Some(1.0));
+    assert_eq!(segments[0].end_time, Some(2.5));
+    assert!(segments[0].translation.is_none());
+    assert_eq!(segments[1].order, 1);
+}
+
+#[test]
+fn merge_rejects_gaps_and_single_segments() {
+    let mut segments = vec![
+        segment("s1", "一。", None),
+        segment("s2", "二。", None),
+        segment("s3", "三。", None),
+    ];
+    assert!(!merge_adjacent_segments(
+        &mut segments,
+        &["s1".to_string(), "s3".to_string()]
+    ));
+    assert!(!merge_adjacent_segments(&mut segments, &["s1".to_string()]));
+    assert_eq!(segments.len(), 3);
+
+    assert!(merge_adjacent_segments(
+        &mut segments,
+        &["s1".to_string(), "s2".to_string(), "s3".to_string()]
+    ));
+    // 中日文合并时不加空格
+    assert_eq!(segments[0].text, "一。二。三。");
+}
+
+#[test]
+fn moving_and_removing_segments_renumbers_order() {
+    let mut segments = vec![
+        segment("s1", "One.", None),
+        segment("s2", "Two.", None),
+        segment("s3", "Three.", None),
+    ];
+    segments[0].is_new_paragraph = true;
+
+    assert!(move_segment(&mut segments, 1, MoveDirection::Up));
+    let ids: Vec<&str> = segments.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["s2", "s1", "s3"]);
+    assert!(segments[0].is_new_paragraph);
+    assert!(!segments[1].is_new_paragraph);
+    assert_eq!(segments[2].order, 2);
+    assert!(!move_segment(&mut segments, 0, MoveDirection::Up));
+    assert!(!move_segment(&mut segments, 2, MoveDirection::Down));
+
+    let removed = remove_segment(&mut segments, 0).unwrap();
+    assert_eq!(removed.id, "s2");
+    assert_eq!(segments[0].id, "s1");
+    assert!(segments[0].is_new_paragraph);
+    assert_eq!(segments[1].order, 1);
+    assert!(remove_segment(&mut segments, 5).is_none());
+}
+
+#[test]
+fn prefetch_picks_next_unexplained_text_segments() {
+    let mut segments: Vec<ArticleSegment> = ["s1", "s2", "img", "s3", "s4", "s5"]
+        .iter()
+        .enumerate()
+        .map(|(order, id)| {
+            let mut s = segment(id, &format!("{}.", id), None);
+            s.order = order as i32;
+            s
+        })
+        .collect();
+    segments[2].image_url = Some("cover.png".to_string());
+    segments[3].explanation = serde_json::from_value(serde_json::json!({
+        "translation": "t",
+        "explanation": "e",
+        "reading_text": null,
+        "cultural_context": null,
+        "difficulty_level": null,
+        "learning_tips": null
+    }))
+    .unwrap();
+    // 存储顺序与 order 不一致时按 order 取
+    segments.swap(0, 5);
+
+    let ids = |current: &str| -> Vec<String> {
+        segments_to_prefetch(&segments, current, 3)
+            .iter()
+            .map(|s| s.id.clone())
+            .collect()
+    };
+    // 图片不计数；已有讲解的段落占一个名额但不再请求
+    assert_eq!(ids("s1"), ["s2", "s4"]);
+    assert_eq!(ids("s4"), ["s5"]);
+    assert!(ids("s5").is_empty());
+    assert!(ids("missing").is_empty());
+}
+
+#[test]
+fn long_sentences_split_at_commas_then_conjunctions() {
+    let text = "The lessee shall maintain the premises in good repair, including all fixtures and fittings, and shall notify the lessor of any defects without delay.";
+    let parts = split_long_sentence(text, 60);
+    assert_eq!(
+        parts,
+        vec![
+            "The lessee shall maintain the premises in good repair,",
+            "including all fixtures and fittings,",
+            "and shall notify the lessor of any defects without delay.",
+        ]
+    );
+    assert!(parts.iter().all(|p| p.chars().count() <= 60));
+
+    // 数字中的逗号不作为断点，没有标点时在连词前断开
+    let parts = split_long_sentence(
+        "Revenue reached 1,000,000 dollars last year while costs kept rising steadily",
+        50,
+    );
+    assert_eq!(parts[1], "while costs kept rising steadily");
+
+    let parts = split_long_sentence(
+        "本合同自双方签字之日起生效，有效期为三年，期满后如双方无异议则自动续期一年",
+        20,
+    );
+    assert_eq!(
+        parts,
+        [
+            "本合同自双方签字之日起生效，",
+            "有效期为三年，",
+            "期满后如双方无异议则自动续期一年"
+        ]
+    );
+}
+
+#[test]
+fn limiting_piece_length_keeps_paragraph_start_on_first_part() {
+    let pieces = split_content(
+        "Short one. This sentence is rather long, so it will be split into two parts here.",
+        Some("en"),
+        &SegmentationMode::Sentence,
+    );
+    let limited = limit_piece_length(pieces, 45);
+    let texts: Vec<&str> = limited.iter().map(|p| p.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            "Short one.",
+            "This sentence is rather long,",
+            "so it will be split into two parts here.",
+        ]
+    );
+    assert!(limited[0].is_new_paragraph);
+    assert!(!limited[1].is_new_paragraph);
+    assert!(!limited[2].is_new_paragraph);
+}
+
+#[test]
+fn semantic_units_are_grouped_by_model_breaks() {
+    let content = "so yeah I went to the store yesterday and they were out of milk which was annoying\n![cover](https://example.com/a.png)\nanyway";
+    let units = semantic_units(content, Some("en"));
+    let texts: Vec<&str> = units.iter().map(|u| u.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            "so yeah I went to the store yesterday",
+            "and they were out of milk which was",
+            "annoying",
+            "cover",
+            "anyway",
+        ]
+    );
+
+    // 编号 2 开始新段落；越界编号忽略；插图不与前后合并
+    let grouped = group_semantic_units(units, &[0, 2, 99]);
+    let texts: Vec<&str> = grouped.iter().map(|u| u.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            "so yeah I went to the store yesterday and they were out of milk which was",
+            "annoying",
+            "cover",
+            "anyway",
+        ]
+    );
+    assert!(grouped[0].is_new_paragraph);
+    assert!(!grouped[1].is_new_paragraph);
+    assert!(grouped[2].image_url.is_some());
+}
+
+#[test]
+fn title_from_first_sentence() {
+    assert_eq!(
+        title_from_content("The weather changed. Then it rained.", Some("en")).as_deref(),
+        Some("The weather changed")
+    );
+    assert_eq!(
+        title_from_content("今日は晴れです。明日は雨です。", Some("ja")).as_deref(),
+        Some("今日は晴れです")
+    );
+    assert_eq!(
+        title_from_content("Is it done? Maybe.", Some("en")).as_deref(),
+        Some("Is it done?")
+    );
+    assert_eq!(title_from_content("  \n ", Some("en")), None);
+
+    let long = "word ".repeat(40);
+    let title = title_from_content(&long, Some("en")).unwrap();
+    assert!(title.ends_with('…'));
+    assert!(title.chars().count() <= MAX_TITLE_CHARS + 1);
+}
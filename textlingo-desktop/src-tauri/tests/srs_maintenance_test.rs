@@ -0,0 +1,77 @@
+use chrono::NaiveDate;
+use openkoto_desktop_lib::commands::{summarize_srs_backlog, LEECH_LAPSE_THRESHOLD};
+use openkoto_desktop_lib::types::FavoriteVocabulary;
+
+fn make_vocab(id: &str, state: &str, due: &str, packs: &[&str], lapses: i32) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: id.to_string(),
+        meaning: "m".to_string(),
+        usage: "u".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: packs.iter().map(|p| p.to_string()).collect(),
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
+        srs_state: state.to_string(),
+        due_date: due.to_string(),
+        interval_days: 1,
+        ease_factor: 2.5,
+        repetitions: 1,
+        last_reviewed_at: None,
+        review_count: 1,
+        lapses,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn counts_overdue_cards_per_pack_and_oldest_date() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let favorites = vec![
+        make_vocab("a", "review", "2026-03-01", &["p1"], 0),
+        make_vocab("b", "learning", "2026-03-09", &["p1", "p2"], 0),
+        make_vocab("c", "review", "2026-03-10", &["p1"], 0),
+        make_vocab("d", "new", "2026-01-01", &["p2"], 0),
+    ];
+
+    let summary = summarize_srs_backlog(&favorites, today);
+    assert_eq!(summary.overdue_count, 2);
+    assert_eq!(summary.overdue_by_pack.get("p1"), Some(&2));
+    assert_eq!(summary.overdue_by_pack.get("p2"), Some(&1));
+    assert_eq!(summary.oldest_overdue_date.as_deref(), Some("2026-03-01"));
+    assert!(summary.leech_ids.is_empty());
+}
+
+#[test]
+fn flags_leeches_at_threshold() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let favorites = vec![
+        make_vocab(
+            "leech",
+            "learning",
+            "2026-03-11",
+            &["p1"],
+            LEECH_LAPSE_THRESHOLD,
+        ),
+        make_vocab(
+            "ok",
+            "review",
+            "2026-03-11",
+            &["p1"],
+            LEECH_LAPSE_THRESHOLD - 1,
+        ),
+    ];
+
+    let summary = summarize_srs_backlog(&favorites, today);
+    assert_eq!(summary.leech_ids, vec!["leech".to_string()]);
+    assert_eq!(summary.overdue_count, 0);
+    assert!(summary.oldest_overdue_date.is_none());
+}
@@ -16,9 +16,13 @@ fn make_vocab(
         explanation: None,
         example: None,
         reading: None,
+        phonetic: None,
         source_article_id: None,
         source_article_title: None,
         pack_ids: pack_ids.into_iter().map(|s| s.to_string()).collect(),
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
         srs_state: state.to_string(),
         ease_factor: 2.5,
         repetitions: 0,
@@ -26,6 +30,9 @@ fn make_vocab(
         due_date: due_date.to_string(),
         last_reviewed_at: last_reviewed_at.map(|s| s.to_string()),
         review_count: 0,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
         created_at: "2026-02-16T00:00:00Z".to_string(),
     }
 }
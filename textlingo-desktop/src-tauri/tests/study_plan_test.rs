@@ -0,0 +1,83 @@
+use chrono::NaiveDate;
+use openkoto_desktop_lib::commands::{forecast_daily_reviews, plan_study_pace};
+use openkoto_desktop_lib::types::FavoriteVocabulary;
+
+fn make_vocab(id: &str, state: &str, due_date: &str, interval_days: i32) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: format!("word-{}", id),
+        meaning: "meaning".to_string(),
+        usage: "usage".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: vec!["p1".to_string()],
+        tags: Vec::new(),
+        image_path: None,
+        language: None,
+        srs_state: state.to_string(),
+        ease_factor: 2.5,
+        repetitions: if state == "new" { 0 } else { 2 },
+        interval_days,
+        due_date: due_date.to_string(),
+        last_reviewed_at: None,
+        review_count: 0,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-02-16T00:00:00Z".to_string(),
+    }
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn pace_spreads_new_words_over_remaining_days() {
+    let favorites: Vec<_> = (0..800)
+        .map(|i| make_vocab(&i.to_string(), "new", "2026-03-01", 0))
+        .collect();
+    let plan = plan_study_pace(
+        "p1",
+        &favorites,
+        date("2026-03-01"),
+        date("2026-04-30"),
+        100,
+    )
+    .unwrap();
+
+    assert_eq!(plan.days_remaining, 60);
+    assert_eq!(plan.new_count, 800);
+    assert_eq!(plan.daily_new, 14);
+    assert_eq!(plan.review_forecast.len(), 60);
+    // 第一天只学新词，之后每天复习前一天的新词以及更早到期的词
+    assert_eq!(plan.review_forecast[0], 0);
+    assert_eq!(plan.review_forecast[1], 14);
+    assert!(plan.peak_reviews >= 14);
+    assert!(!plan.applied);
+
+    assert!(plan_study_pace(
+        "p1",
+        &favorites,
+        date("2026-03-01"),
+        date("2026-03-01"),
+        100
+    )
+    .is_none());
+}
+
+#[test]
+fn forecast_counts_overdue_and_scheduled_reviews() {
+    let favorites = vec![
+        make_vocab("a", "review", "2026-02-20", 6),
+        make_vocab("b", "review", "2026-03-03", 6),
+        make_vocab("c", "learning", "2026-03-01", 1),
+    ];
+    let forecast = forecast_daily_reviews(&favorites, date("2026-03-01"), 4, 0);
+    // a（过期）和 c 计入第一天，b 在第三天到期，c 复习后间隔 3 天再次到期
+    assert_eq!(forecast, vec![2, 0, 1, 1]);
+}
@@ -0,0 +1,45 @@
+use openkoto_desktop_lib::subtitle_burn::{burned_video_path, parse_progress_time};
+use openkoto_desktop_lib::subtitle_files::{
+    format_ass_timestamp, parse_ass, render_ass, SubtitleCue,
+};
+use std::path::Path;
+
+#[test]
+fn renders_bilingual_ass() {
+    let cues = vec![SubtitleCue {
+        start: 61.234,
+        end: 3723.5,
+        text: "Say {hello}.\n你好。".to_string(),
+    }];
+    let ass = render_ass(&cues);
+    assert!(ass.contains("Style: Original,"));
+    assert!(ass.contains("Style: Translation,"));
+    assert!(ass.contains(
+        "Dialogue: 0,0:01:01.23,1:02:03.50,Original,,0,0,0,,Say (hello).\\N{\\rTranslation}你好。\n"
+    ));
+
+    let parsed = parse_ass(&ass);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].text, "Say (hello). 你好。");
+    assert_eq!(format_ass_timestamp(-3.0), "0:00:00.00");
+}
+
+#[test]
+fn parses_ffmpeg_progress_lines() {
+    assert_eq!(parse_progress_time("out_time_us=2500000"), Some(2.5));
+    assert_eq!(parse_progress_time("out_time_ms=1000000\n"), Some(1.0));
+    assert_eq!(
+        parse_progress_time("out_time_us=-9223372036854775807"),
+        Some(0.0)
+    );
+    assert_eq!(parse_progress_time("out_time=00:00:02.500000"), None);
+    assert_eq!(parse_progress_time("progress=continue"), None);
+}
+
+#[test]
+fn default_output_sits_next_to_video() {
+    assert_eq!(
+        burned_video_path(Path::new("/videos/lesson 1.mkv")),
+        Path::new("/videos/lesson 1.subtitled.mp4")
+    );
+}
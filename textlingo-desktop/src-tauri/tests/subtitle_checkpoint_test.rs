@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use openkoto_desktop_lib::subtitle_checkpoint::{
+    checkpoint_path, remove_checkpoint, ChunkCheckpoint,
+};
+use openkoto_desktop_lib::types::TranscriptionSegment;
+
+fn plan(engine: &str) -> ChunkCheckpoint {
+    ChunkCheckpoint::new(
+        Path::new("/videos/lecture.mp4"),
+        1024,
+        engine,
+        600.0,
+        1500.0,
+        vec![0.0, 570.0, 1140.0],
+    )
+}
+
+fn cue(content: &str, start: f64) -> TranscriptionSegment {
+    TranscriptionSegment {
+        speaker: None,
+        content: content.to_string(),
+        start_time: Some(start),
+        end_time: Some(start + 2.0),
+    }
+}
+
+#[test]
+fn saved_chunks_are_skipped_when_resuming_the_same_plan() {
+    let dir = std::env::temp_dir().join(format!("subtitle-checkpoint-{}", std::process::id()));
+    let path = checkpoint_path(&dir, "video1");
+
+    // 没有断点时从头开始
+    let fresh = ChunkCheckpoint::resume_or(&path, plan("gemini"));
+    assert_eq!(fresh.pending_chunks(), [0, 1, 2]);
+
+    let mut checkpoint = fresh;
+    checkpoint.chunks.insert(1, vec![cue("middle", 600.0)]);
+    checkpoint.save(&path).unwrap();
+
+    let resumed = ChunkCheckpoint::resume_or(&path, plan("gemini"));
+    assert_eq!(resumed.pending_chunks(), [0, 2]);
+    assert_eq!(resumed.chunks[&1][0].content, "middle");
+
+    // 换了转录引擎时断点作废
+    let other = ChunkCheckpoint::resume_or(&path, plan("openai"));
+    assert_eq!(other.pending_chunks(), [0, 1, 2]);
+
+    remove_checkpoint(&dir, "video1");
+    assert!(!path.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
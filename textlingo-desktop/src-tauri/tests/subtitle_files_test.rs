@@ -0,0 +1,159 @@
+use openkoto_desktop_lib::subtitle_files::{
+    adjust_timing, cues_to_segments, format_timestamp, parse_ass, parse_srt, parse_subtitles,
+    parse_timestamp, parse_vtt, render_subtitles, segments_to_cues, SubtitleCue,
+};
+
+#[test]
+fn parses_timestamps_in_all_formats() {
+    assert_eq!(parse_timestamp("00:01:02,500"), Some(62.5));
+    assert_eq!(parse_timestamp("01:00:00.000"), Some(3600.0));
+    assert_eq!(parse_timestamp("01:02.250"), Some(62.25));
+    assert_eq!(parse_timestamp("0:00:03.50"), Some(3.5));
+    assert_eq!(parse_timestamp("abc"), None);
+}
+
+#[test]
+fn parses_srt_with_crlf_and_tags() {
+    let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>Hello</i>\r\nthere\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\n{\\an8}Second\r\n\r\n";
+    let cues = parse_srt(srt);
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].text, "Hello there");
+    assert_eq!((cues[0].start, cues[0].end), (1.0, 2.5));
+    assert_eq!(cues[1].text, "Second");
+}
+
+#[test]
+fn parses_vtt_skipping_header_and_notes() {
+    let vtt = "WEBVTT\nKind: captions\n\nNOTE a comment --> not a cue\n\nintro\n00:01.000 --> 00:02.000 align:start position:10%\n<v Anna>Bonjour &amp; <c.yellow>salut</c>\n\n00:00:03.000 --> 00:00:04.000\nEncore\n";
+    let cues = parse_vtt(vtt);
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].text, "Bonjour & salut");
+    assert_eq!((cues[0].start, cues[0].end), (1.0, 2.0));
+    assert_eq!(cues[1].start, 3.0);
+}
+
+#[test]
+fn parses_ass_events_in_time_order() {
+    let ass = "[Script Info]\nTitle: Test\n\n[V4+ Styles]\nFormat: Name, Fontname\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,{\\i1}Later, with comma{\\i0}\nComment: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,ignored\nDialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,First\\Nline\n";
+    let cues = parse_ass(ass);
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].text, "First line");
+    assert_eq!(cues[0].end, 2.5);
+    assert_eq!(cues[1].text, "Later, with comma");
+}
+
+#[test]
+fn builds_timed_segments() {
+    let cues = parse_subtitles("1\n00:00:01,000 --> 00:00:02,000\nHi\n", "SRT").unwrap();
+    let segments = cues_to_segments("a1", &cues);
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].article_id, "a1");
+    assert_eq!(segments[0].start_time, Some(1.0));
+    assert!(segments[0].is_new_paragraph);
+    assert!(parse_subtitles("", "txt").is_none());
+}
+
+#[test]
+fn formats_timestamps() {
+    assert_eq!(format_timestamp(62.5, false), "00:01:02,500");
+    assert_eq!(format_timestamp(3661.0105, true), "01:01:01.011");
+    assert_eq!(format_timestamp(-1.0, true), "00:00:00.000");
+}
+
+#[test]
+fn exports_timed_segments_with_translations() {
+    let cues = vec![
+        SubtitleCue {
+            start: 1.0,
+            end: 2.5,
+            text: "Hello there.".to_string(),
+        },
+        SubtitleCue {
+            start: 3.0,
+            end: 4.0,
+            text: "Bye.".to_string(),
+        },
+    ];
+    let mut segments = cues_to_segments("a1", &cues);
+    segments[0].translation = Some("你好。".to_string());
+    let mut untimed = segments[1].clone();
+    untimed.start_time = None;
+    untimed.order = 2;
+    segments.push(untimed);
+
+    let exported = segments_to_cues(&segments, true);
+    assert_eq!(exported.len(), 2);
+    assert_eq!(exported[0].text, "Hello there.\n你好。");
+    assert_eq!(exported[1].text, "Bye.");
+
+    let srt = render_subtitles(&exported, "srt").unwrap();
+    assert!(srt.starts_with("1\n00:00:01,000 --> 00:00:02,500\nHello there.\n你好。\n\n2\n"));
+    let vtt = render_subtitles(&exported, ".VTT").unwrap();
+    assert!(vtt.starts_with("WEBVTT\n\n00:00:01.000 --> 00:00:02.500\n"));
+    assert!(render_subtitles(&exported, "ass").is_none());
+
+    // 导出的文件能被重新导入
+    let reparsed = parse_srt(&srt);
+    assert_eq!(reparsed.len(), 2);
+    assert_eq!(reparsed[0].text, "Hello there. 你好。");
+    assert_eq!(parse_vtt(&vtt)[1].start, 3.0);
+
+    let without = segments_to_cues(&segments, false);
+    assert_eq!(without[0].text, "Hello there.");
+}
+
+#[test]
+fn exports_speaker_prefix_on_both_lines() {
+    let cues = vec![SubtitleCue {
+        start: 0.0,
+        end: 2.0,
+        text: "How are you?".to_string(),
+    }];
+    let mut segments = cues_to_segments("a1", &cues);
+    segments[0].speaker = Some("Anna".to_string());
+    segments[0].translation = Some("你好吗？".to_string());
+
+    let exported = segments_to_cues(&segments, true);
+    assert_eq!(exported[0].text, "Anna: How are you?\nAnna: 你好吗？");
+
+    // 模型照抄的前缀被去掉，已带前缀的文本不重复添加
+    assert_eq!(
+        segments[0].strip_speaker_prefix("Anna： 你好吗？"),
+        "你好吗？"
+    );
+    assert_eq!(
+        segments[0].strip_speaker_prefix("Annabel: hi"),
+        "Annabel: hi"
+    );
+    assert_eq!(segments[0].with_speaker_prefix("Anna: hi"), "Anna: hi");
+    segments[0].speaker = None;
+    assert_eq!(segments[0].with_speaker_prefix("hi"), "hi");
+}
+
+#[test]
+fn shifts_and_stretches_segment_timing() {
+    let cues = vec![
+        SubtitleCue {
+            start: 0.5,
+            end: 2.0,
+            text: "One".to_string(),
+        },
+        SubtitleCue {
+            start: 10.0,
+            end: 12.0,
+            text: "Two".to_string(),
+        },
+    ];
+    let mut segments = cues_to_segments("a1", &cues);
+    segments.push(segments[0].clone());
+    segments[2].start_time = None;
+    segments[2].end_time = None;
+
+    assert_eq!(adjust_timing(&mut segments, -1.0, 1.1), 2);
+    // 提前后小于 0 的时间截为 0
+    assert_eq!(segments[0].start_time, Some(0.0));
+    assert!((segments[0].end_time.unwrap() - 1.2).abs() < 1e-9);
+    assert_eq!(segments[1].start_time, Some(10.0));
+    assert!((segments[1].end_time.unwrap() - 12.2).abs() < 1e-9);
+    assert_eq!(segments[2].start_time, None);
+}
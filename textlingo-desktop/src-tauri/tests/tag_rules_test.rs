@@ -0,0 +1,106 @@
+use openkoto_desktop_lib::tag_rules::{apply_rules, rule_matches, source_domain};
+use openkoto_desktop_lib::types::{Article, TagRule, TagRuleField};
+
+fn article(url: Option<&str>, language: Option<&str>, channel: Option<&str>) -> Article {
+    Article {
+        id: "a1".to_string(),
+        title: "Cooking with Miso".to_string(),
+        content: "Today we make soup.".to_string(),
+        source_type: Some("web".to_string()),
+        source_url: url.map(str::to_string),
+        media_path: None,
+        book_path: None,
+        book_type: None,
+        language: language.map(str::to_string),
+        created_at: "2026-05-01T00:00:00Z".to_string(),
+        translated: false,
+        translation_language: None,
+        segmentation: None,
+        analyses: Default::default(),
+        channel: channel.map(str::to_string),
+        tags: Vec::new(),
+        media_info: None,
+        segments: Vec::new(),
+    }
+}
+
+fn rule(field: TagRuleField, pattern: &str, tag: &str) -> TagRule {
+    TagRule {
+        id: tag.to_string(),
+        field,
+        pattern: pattern.to_string(),
+        tag: tag.to_string(),
+        enabled: true,
+        created_at: "2026-05-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn extracts_source_domain() {
+    assert_eq!(
+        source_domain("https://www.NHK.or.jp/news/a.html").as_deref(),
+        Some("nhk.or.jp")
+    );
+    assert_eq!(source_domain("not a url"), None);
+}
+
+#[test]
+fn matches_each_field() {
+    let web = article(Some("https://news.nhk.or.jp/x"), Some("ja-JP"), None);
+    assert!(rule_matches(
+        &rule(TagRuleField::Domain, "nhk.or.jp", "NHK"),
+        &web
+    ));
+    assert!(rule_matches(
+        &rule(TagRuleField::Domain, "www.nhk.or.jp", "NHK"),
+        &web
+    ));
+    assert!(!rule_matches(
+        &rule(TagRuleField::Domain, "hk.or.jp", "HK"),
+        &web
+    ));
+    assert!(rule_matches(
+        &rule(TagRuleField::Language, "JA", "日本語"),
+        &web
+    ));
+    assert!(!rule_matches(&rule(TagRuleField::Language, "j", "x"), &web));
+    assert!(rule_matches(
+        &rule(TagRuleField::Keyword, "miso", "Food"),
+        &web
+    ));
+    assert!(rule_matches(
+        &rule(TagRuleField::Keyword, "SOUP", "Food"),
+        &web
+    ));
+
+    let video = article(None, None, Some("Easy Japanese"));
+    assert!(rule_matches(
+        &rule(TagRuleField::Channel, "easy japanese", "Easy"),
+        &video
+    ));
+    assert!(!rule_matches(
+        &rule(TagRuleField::Channel, "easy", "Easy"),
+        &video
+    ));
+    assert!(!rule_matches(
+        &rule(TagRuleField::Domain, "nhk.or.jp", "NHK"),
+        &video
+    ));
+}
+
+#[test]
+fn applies_only_missing_tags_from_enabled_rules() {
+    let mut a = article(Some("https://nhk.or.jp"), Some("ja"), None);
+    a.tags.push("NHK".to_string());
+    let mut disabled = rule(TagRuleField::Keyword, "soup", "Soup");
+    disabled.enabled = false;
+    let rules = vec![
+        rule(TagRuleField::Domain, "nhk.or.jp", "NHK"),
+        rule(TagRuleField::Language, "ja", "Japanese"),
+        rule(TagRuleField::Keyword, "cooking", "Japanese"),
+        disabled,
+    ];
+    assert!(apply_rules(&rules, &mut a));
+    assert_eq!(a.tags, vec!["NHK", "Japanese"]);
+    assert!(!apply_rules(&rules, &mut a));
+}
@@ -0,0 +1,65 @@
+use openkoto_desktop_lib::types::{AiTask, AppConfig, ModelConfig, TranslationQuality};
+
+fn model(id: &str) -> ModelConfig {
+    let mut config = ModelConfig::new(
+        id.to_string(),
+        "key".to_string(),
+        "openai".to_string(),
+        format!("{}-model", id),
+    );
+    config.id = id.to_string();
+    config
+}
+
+#[test]
+fn tasks_use_routed_model_or_fall_back_to_active() {
+    let mut config = AppConfig {
+        model_configs: vec![model("strong"), model("cheap")],
+        active_model_id: Some("strong".to_string()),
+        ..AppConfig::default()
+    };
+    config.task_models.batch_translation = Some("cheap".to_string());
+    config.task_models.chat = Some("deleted".to_string());
+
+    let id_for = |task| config.get_config_for_task(task).map(|c| c.id.as_str());
+    assert_eq!(id_for(AiTask::BatchTranslation), Some("cheap"));
+    assert_eq!(id_for(AiTask::SegmentExplanation), Some("strong"));
+    assert_eq!(id_for(AiTask::Chat), Some("strong"));
+}
+
+#[test]
+fn routing_defaults_when_missing_from_saved_config() {
+    let config: AppConfig = serde_json::from_str(r#"{ "target_language": "zh-CN" }"#).unwrap();
+    assert!(config.task_models.model_id(AiTask::Chat).is_none());
+
+    let routed: AppConfig = serde_json::from_str(
+        r#"{ "target_language": "zh-CN", "task_models": { "subtitle_transcription": "m1" } }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        routed.task_models.model_id(AiTask::SubtitleTranscription),
+        Some("m1")
+    );
+}
+
+#[test]
+fn translation_review_pass_can_use_its_own_model() {
+    let mut config = AppConfig {
+        model_configs: vec![model("strong"), model("cheap")],
+        active_model_id: Some("cheap".to_string()),
+        ..AppConfig::default()
+    };
+    config.task_models.translation_review = Some("strong".to_string());
+
+    let id_for = |task| config.get_config_for_task(task).map(|c| c.id.as_str());
+    assert_eq!(id_for(AiTask::BatchTranslation), Some("cheap"));
+    assert_eq!(id_for(AiTask::TranslationReview), Some("strong"));
+}
+
+#[test]
+fn translation_quality_defaults_to_standard() {
+    assert_eq!(TranslationQuality::default(), TranslationQuality::Standard);
+    let quality: TranslationQuality = serde_json::from_str(r#""thorough""#).unwrap();
+    assert_eq!(quality, TranslationQuality::Thorough);
+    assert_eq!(TranslationQuality::Fast.as_str(), "fast");
+}
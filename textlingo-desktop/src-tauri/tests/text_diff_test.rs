@@ -0,0 +1,31 @@
+use openkoto_desktop_lib::text_diff::diff_lines;
+use openkoto_desktop_lib::types::DiffKind;
+
+fn render(old: &str, new: &str) -> Vec<String> {
+    diff_lines(old, new)
+        .into_iter()
+        .map(|line| {
+            let mark = match line.kind {
+                DiffKind::Unchanged => ' ',
+                DiffKind::Removed => '-',
+                DiffKind::Added => '+',
+            };
+            format!("{}{}", mark, line.text)
+        })
+        .collect()
+}
+
+#[test]
+fn marks_changed_lines_between_common_context() {
+    assert_eq!(
+        render("a\nb\nc\nd", "a\nB\nc\nd\ne"),
+        vec![" a", "-b", "+B", " c", " d", "+e"]
+    );
+}
+
+#[test]
+fn identical_and_empty_texts() {
+    assert_eq!(render("same\ntext", "same\ntext"), vec![" same", " text"]);
+    assert_eq!(render("", "new"), vec!["+new"]);
+    assert_eq!(render("old", ""), vec!["-old"]);
+}
@@ -0,0 +1,166 @@
+use openkoto_desktop_lib::transcription::{
+    chunk_options, parse_speaker, parse_timed_segments, resolve_engine_id, ChunkOptions,
+    DEFAULT_CHUNK_SECONDS,
+};
+use openkoto_desktop_lib::types::{ModelConfig, TranscriptionSettings};
+use serde_json::json;
+
+fn model(provider: &str, model: &str) -> ModelConfig {
+    ModelConfig::new(
+        "test".to_string(),
+        "key".to_string(),
+        provider.to_string(),
+        model.to_string(),
+    )
+}
+
+#[test]
+fn auto_engine_follows_transcription_model() {
+    let settings = TranscriptionSettings::default();
+    let resolve = |provider, name| resolve_engine_id(&settings, Some(&model(provider, name)));
+
+    assert_eq!(resolve("google", "gemini-2.5-flash"), Ok("gemini"));
+    assert_eq!(resolve("openrouter", "google/gemini-2.5-pro"), Ok("gemini"));
+    assert_eq!(resolve("moonshot", "kimi-k2.5"), Ok("kimi_video"));
+    assert_eq!(resolve("openai", "whisper-1"), Ok("openai_audio"));
+    assert_eq!(resolve("openai", "gpt-4o-transcribe"), Ok("openai_audio"));
+    assert_eq!(
+        resolve("openai-compatible", "whisper-large-v3"),
+        Ok("openai_audio")
+    );
+    assert!(resolve("openai", "gpt-4o").is_err());
+    assert!(resolve("ollama", "gemma3").is_err());
+    assert!(resolve_engine_id(&settings, None).is_err());
+}
+
+#[test]
+fn explicit_engine_does_not_need_model() {
+    let settings = TranscriptionSettings {
+        engine: "whisper_local".to_string(),
+        ..TranscriptionSettings::default()
+    };
+    assert_eq!(resolve_engine_id(&settings, None), Ok("whisper_local"));
+
+    let unknown = TranscriptionSettings {
+        engine: "nope".to_string(),
+        ..TranscriptionSettings::default()
+    };
+    assert!(resolve_engine_id(&unknown, None).is_err());
+}
+
+#[test]
+fn chunk_settings_override_engine_defaults_within_limits() {
+    let defaults = TranscriptionSettings::default();
+    assert_eq!(chunk_options(None, &defaults), None);
+    assert_eq!(
+        chunk_options(Some(DEFAULT_CHUNK_SECONDS), &defaults),
+        Some(ChunkOptions {
+            chunk_seconds: 600.0,
+            overlap: 30.0,
+            concurrency: 2,
+        })
+    );
+
+    let tuned = TranscriptionSettings {
+        chunk_seconds: Some(300.0),
+        chunk_overlap_seconds: Some(10.0),
+        chunk_concurrency: Some(1),
+        ..TranscriptionSettings::default()
+    };
+    let options = chunk_options(Some(DEFAULT_CHUNK_SECONDS), &tuned).unwrap();
+    assert_eq!((options.chunk_seconds, options.step()), (300.0, 290.0));
+    assert_eq!(options.concurrency, 1);
+
+    // 超出范围的值被限制，重叠不超过分片的四分之一
+    let extreme = TranscriptionSettings {
+        chunk_seconds: Some(5.0),
+        chunk_overlap_seconds: Some(120.0),
+        chunk_concurrency: Some(0),
+        ..TranscriptionSettings::default()
+    };
+    let options = chunk_options(Some(DEFAULT_CHUNK_SECONDS), &extreme).unwrap();
+    assert_eq!(
+        (options.chunk_seconds, options.overlap, options.concurrency),
+        (60.0, 15.0, 1)
+    );
+}
+
+#[test]
+fn timed_segments_parse_and_fall_back_to_full_text() {
+    let verbose = json!({
+        "text": "Hello there. Bye.",
+        "segments": [
+            { "start": 0.0, "end": 1.5, "text": " Hello there." },
+            { "start": 1.5, "end": 2.0, "text": "  " },
+            { "start": 2.0, "end": 3.2, "text": "Bye." }
+        ]
+    });
+    let result = parse_timed_segments(&verbose);
+    assert_eq!(result.segments.len(), 2);
+    assert_eq!(result.segments[0].content, "Hello there.");
+    assert_eq!(result.segments[1].start_time, Some(2.0));
+    assert_eq!(result.full_text, "Hello there. Bye.");
+
+    let plain = parse_timed_segments(&json!({ "text": "Only text", "duration": 4.0 }));
+    assert_eq!(plain.segments.len(), 1);
+    assert_eq!(plain.segments[0].end_time, Some(4.0));
+}
+
+#[test]
+fn word_timestamps_tighten_segments() {
+    let verbose = json!({
+        "text": "Hello there. Bye.",
+        "segments": [
+            { "start": 0.0, "end": 2.0, "text": "Hello there." },
+            { "start": 2.0, "end": 4.0, "text": "Bye." }
+        ],
+        "words": [
+            { "word": "Hello", "start": 0.4, "end": 0.8 },
+            { "word": "there", "start": 0.9, "end": 1.3 },
+            { "word": "Bye", "start": 2.6, "end": 2.9 }
+        ]
+    });
+    let result = parse_timed_segments(&verbose);
+    assert_eq!(result.segments[0].start_time, Some(0.4));
+    assert_eq!(result.segments[0].end_time, Some(1.3));
+    assert_eq!(result.segments[1].start_time, Some(2.6));
+    assert_eq!(result.segments[1].end_time, Some(2.9));
+}
+
+#[test]
+fn words_without_segments_group_at_pauses() {
+    let result = parse_timed_segments(&json!({
+        "text": "one two three 四五",
+        "words": [
+            { "word": "one", "start": 0.0, "end": 0.3 },
+            { "word": "two", "start": 0.4, "end": 0.7 },
+            { "word": "three", "start": 2.0, "end": 2.4 },
+            { "word": "四", "start": 2.5, "end": 2.6 },
+            { "word": "五", "start": 2.6, "end": 2.8 }
+        ]
+    }));
+    let texts: Vec<&str> = result.segments.iter().map(|s| s.content.as_str()).collect();
+    assert_eq!(texts, ["one two", "three 四五"]);
+    assert_eq!(result.segments[1].start_time, Some(2.0));
+    assert_eq!(result.segments[1].end_time, Some(2.8));
+}
+
+#[test]
+fn diarized_segments_keep_speaker_labels() {
+    let result = parse_timed_segments(&json!({
+        "text": "Hi. Hello.",
+        "segments": [
+            { "start": 0.0, "end": 1.0, "text": "Hi.", "speaker": "A" },
+            { "start": 1.0, "end": 2.0, "text": "Hello.", "speaker": " " },
+            { "start": 2.0, "end": 3.0, "text": "Bye." }
+        ]
+    }));
+    let speakers: Vec<Option<&str>> = result
+        .segments
+        .iter()
+        .map(|s| s.speaker.as_deref())
+        .collect();
+    assert_eq!(speakers, [Some("A"), None, None]);
+    assert_eq!(parse_speaker(&json!(2)).as_deref(), Some("Speaker 2"));
+    assert_eq!(parse_speaker(&json!(null)), None);
+}
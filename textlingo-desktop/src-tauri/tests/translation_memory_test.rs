@@ -0,0 +1,18 @@
+use openkoto_desktop_lib::translation_memory::memory_key;
+
+#[test]
+fn key_ignores_surrounding_whitespace_only() {
+    let key = memory_key("こんにちは。", "zh-CN", "gpt-4o-mini");
+    assert_eq!(key.len(), 16);
+    assert_eq!(key, memory_key("  こんにちは。\n", "zh-CN", "gpt-4o-mini"));
+    assert_ne!(key, memory_key("こんにちは", "zh-CN", "gpt-4o-mini"));
+}
+
+#[test]
+fn key_depends_on_language_and_model() {
+    let base = memory_key("Hello", "zh-CN", "model-a");
+    assert_ne!(base, memory_key("Hello", "ja", "model-a"));
+    assert_ne!(base, memory_key("Hello", "zh-CN", "model-b"));
+    // 分隔符避免拼接歧义
+    assert_ne!(memory_key("ab", "c", "m"), memory_key("a", "bc", "m"));
+}
@@ -0,0 +1,110 @@
+use openkoto_desktop_lib::types::{
+    ArticleSegment, ChunkStatus, ProcessingStatus, ProcessingTask, SegmentSelection,
+    TranslationChunkStatus, TranslationProgress, TranslationQuality,
+};
+
+fn chunk(status: ChunkStatus, attempts: u32) -> TranslationChunkStatus {
+    TranslationChunkStatus {
+        segment_ids: vec!["s1".to_string(), "s2".to_string()],
+        status,
+        attempts,
+        error: None,
+    }
+}
+
+#[test]
+fn counts_only_failed_chunks() {
+    let progress = TranslationProgress {
+        article_id: "a1".to_string(),
+        target_language: "zh-CN".to_string(),
+        quality: TranslationQuality::Thorough,
+        selection: None,
+        chunks: vec![
+            chunk(ChunkStatus::Done, 1),
+            chunk(ChunkStatus::Failed, 3),
+            chunk(ChunkStatus::Pending, 0),
+        ],
+        updated_at: "2026-01-01T00:00:00Z".to_string(),
+    };
+    assert_eq!(progress.failed_count(), 1);
+}
+
+#[test]
+fn saved_progress_round_trips_and_defaults_quality() {
+    let json = r#"{
+        "article_id": "a1",
+        "target_language": "ja",
+        "chunks": [{"segment_ids": ["s1"], "status": "failed", "attempts": 3, "error": "timeout"}],
+        "updated_at": "2026-01-01T00:00:00Z"
+    }"#;
+    let progress: TranslationProgress = serde_json::from_str(json).unwrap();
+    assert_eq!(progress.quality, TranslationQuality::Standard);
+    assert_eq!(progress.chunks[0].status, ChunkStatus::Failed);
+    assert_eq!(progress.chunks[0].error.as_deref(), Some("timeout"));
+
+    let saved = serde_json::to_value(&progress).unwrap();
+    assert_eq!(saved["chunks"][0]["status"], "failed");
+    assert_eq!(saved["quality"], "standard");
+}
+
+fn segment(id: &str, order: i32) -> ArticleSegment {
+    ArticleSegment {
+        id: id.to_string(),
+        article_id: "a1".to_string(),
+        order,
+        text: "text".to_string(),
+        reading_text: None,
+        translation: None,
+        explanation: None,
+        start_time: None,
+        end_time: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        is_new_paragraph: false,
+        image_url: None,
+        translations: Default::default(),
+        processing_status: None,
+        block: None,
+        speaker: None,
+    }
+}
+
+#[test]
+fn selection_filters_by_order_range_and_ids() {
+    let range = SegmentSelection {
+        from_order: Some(2),
+        to_order: Some(4),
+        segment_ids: None,
+    };
+    assert!(!range.includes(&segment("s1", 1)));
+    assert!(range.includes(&segment("s2", 2)));
+    assert!(range.includes(&segment("s4", 4)));
+    assert!(!range.includes(&segment("s5", 5)));
+
+    let ids = SegmentSelection {
+        segment_ids: Some(vec!["s3".to_string()]),
+        ..Default::default()
+    };
+    assert!(ids.includes(&segment("s3", 3)));
+    assert!(!ids.includes(&segment("s2", 2)));
+    assert!(SegmentSelection::default().includes(&segment("s9", 9)));
+}
+
+#[test]
+fn segment_processing_status_serializes_and_defaults() {
+    let mut seg = segment("s1", 1);
+    seg.set_processing(
+        ProcessingTask::Translation,
+        ProcessingStatus::Failed,
+        Some("timeout".to_string()),
+    );
+    let saved = serde_json::to_value(&seg).unwrap();
+    assert_eq!(saved["processing_status"]["task"], "translation");
+    assert_eq!(saved["processing_status"]["status"], "failed");
+    assert_eq!(saved["processing_status"]["error"], "timeout");
+
+    // 旧数据没有该字段
+    let mut legacy = saved;
+    legacy.as_object_mut().unwrap().remove("processing_status");
+    let seg: ArticleSegment = serde_json::from_value(legacy).unwrap();
+    assert!(seg.processing_status.is_none());
+}
@@ -0,0 +1,46 @@
+use openkoto_desktop_lib::translation_qa::{build_report, sample_indices};
+
+fn grades(scores: &[u8]) -> Vec<(String, u8, String)> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| (format!("s{}", i), *score, format!("issue {}", i)))
+        .collect()
+}
+
+#[test]
+fn samples_distinct_indices_in_order() {
+    let sample = sample_indices(50, 6, 42);
+    assert_eq!(sample.len(), 6);
+    assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(sample.iter().all(|&i| i < 50));
+    // 同一种子结果相同，候选不足时全部抽取
+    assert_eq!(sample, sample_indices(50, 6, 42));
+    assert_eq!(sample_indices(3, 6, 0), vec![0, 1, 2]);
+}
+
+#[test]
+fn warns_on_low_average_or_many_bad_sentences() {
+    let good = build_report(
+        "a",
+        "zh",
+        "cheap@fast",
+        &grades(&[9, 8, 10, 9, 4, 9]),
+        "now",
+    )
+    .unwrap();
+    assert_eq!(good.sampled, 6);
+    assert_eq!(good.average_score, 8.2);
+    assert_eq!(good.issues.len(), 1);
+    assert_eq!(good.issues[0].segment_id, "s4");
+    assert!(!good.warning);
+
+    // 平均分尚可，但三分之一的句子得分过低
+    let patchy = build_report("a", "zh", "m", &grades(&[10, 10, 3, 10, 2, 10]), "now").unwrap();
+    assert!(patchy.warning);
+
+    let poor = build_report("a", "zh", "m", &grades(&[6, 6, 6]), "now").unwrap();
+    assert!(poor.warning);
+
+    assert!(build_report("a", "zh", "m", &[], "now").is_none());
+}
@@ -0,0 +1,72 @@
+use openkoto_desktop_lib::tts::{audio_file_name, audio_url, is_speakable, speech_endpoint};
+use openkoto_desktop_lib::types::{ArticleSegment, SegmentBlock, TtsSettings};
+
+fn segment(text: &str) -> ArticleSegment {
+    serde_json::from_value(serde_json::json!({
+        "id": "s1",
+        "article_id": "a1",
+        "order": 0,
+        "text": text,
+        "reading_text": null,
+        "translation": null,
+        "explanation": null,
+        "created_at": "2026-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn audio_file_name_is_stable_per_text_and_voice() {
+    let name = audio_file_name("Hello there.", "tts-1", "alloy", 1.0);
+    assert_eq!(
+        name,
+        audio_file_name("  Hello there. ", "tts-1", "alloy", 1.0)
+    );
+    assert!(name.ends_with(".mp3"));
+    assert_eq!(name.len(), 16 + 4);
+
+    assert_ne!(name, audio_file_name("Hello there.", "tts-1", "nova", 1.0));
+    assert_ne!(
+        name,
+        audio_file_name("Hello there.", "tts-1", "alloy", 1.25)
+    );
+    assert_ne!(name, audio_file_name("Hello here.", "tts-1", "alloy", 1.0));
+}
+
+#[test]
+fn speech_endpoint_follows_base_url() {
+    assert_eq!(
+        speech_endpoint(None),
+        "https://api.openai.com/v1/audio/speech"
+    );
+    assert_eq!(
+        speech_endpoint(Some("http://localhost:8880/v1/")),
+        "http://localhost:8880/v1/audio/speech"
+    );
+    assert_eq!(
+        speech_endpoint(Some("https://gw.example/v1/chat/completions")),
+        "https://gw.example/v1/audio/speech"
+    );
+    assert!(audio_url("abc.mp3").ends_with("/tts/abc.mp3"));
+}
+
+#[test]
+fn skips_images_code_and_punctuation() {
+    assert!(is_speakable(&segment("こんにちは。")));
+    assert!(!is_speakable(&segment("……")));
+
+    let mut image = segment("A figure");
+    image.image_url = Some("https://x.y/z.png".to_string());
+    assert!(!is_speakable(&image));
+
+    let mut code = segment("fn main() {}");
+    code.block = Some(SegmentBlock::Code);
+    assert!(!is_speakable(&code));
+}
+
+#[test]
+fn tts_settings_default_when_missing() {
+    let settings: TtsSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings, TtsSettings::default());
+    assert_eq!(settings.voice, "alloy");
+}
@@ -0,0 +1,97 @@
+use chrono::{Duration, TimeZone, Utc};
+use openkoto_desktop_lib::segmentation::{remove_segment, restore_segment};
+use openkoto_desktop_lib::types::{ArticleSegment, UndoActionKind};
+use openkoto_desktop_lib::undo::{UndoAction, UndoJournal, MAX_UNDO_ENTRIES, UNDO_TTL_SECONDS};
+
+fn bookmark(id: &str) -> UndoAction {
+    UndoAction::DeleteBookmark {
+        bookmark: format!("{{\"id\":\"{}\"}}", id),
+    }
+}
+
+fn segment(order: i32, text: &str, new_paragraph: bool) -> ArticleSegment {
+    serde_json::from_value(serde_json::json!({
+        "id": format!("s{}", order),
+        "article_id": "a1",
+        "order": order,
+        "text": text,
+        "reading_text": null,
+        "translation": null,
+        "explanation": null,
+        "is_new_paragraph": new_paragraph,
+        "created_at": "2026-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn pops_latest_and_drops_expired_entries() {
+    let journal = UndoJournal::default();
+    let start = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+    journal.record("first", bookmark("b1"), start);
+    let second = journal.record("second", bookmark("b2"), start + Duration::seconds(60));
+    assert_eq!(second.kind, UndoActionKind::DeleteBookmark);
+
+    let now = start + Duration::seconds(120);
+    assert_eq!(journal.latest(now).unwrap().label, "second");
+    assert_eq!(journal.pop(now).unwrap().info, second);
+    assert_eq!(journal.latest(now).unwrap().label, "first");
+
+    // 超过保留时间后不可再撤销
+    let later = start + Duration::seconds(UNDO_TTL_SECONDS + 1);
+    assert!(journal.pop(later).is_none());
+}
+
+#[test]
+fn peek_leaves_the_entry_until_it_is_removed() {
+    let journal = UndoJournal::default();
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+    journal.record("first", bookmark("b1"), now);
+    let second = journal.record("second", bookmark("b2"), now);
+
+    // 恢复失败时不调用 remove，记录仍可再次撤销
+    assert_eq!(journal.peek(now).unwrap().info, second);
+    assert_eq!(journal.peek(now).unwrap().info, second);
+
+    assert!(journal.remove(&second.id));
+    assert!(!journal.remove(&second.id));
+    assert_eq!(journal.latest(now).unwrap().label, "first");
+}
+
+#[test]
+fn keeps_only_the_most_recent_entries() {
+    let journal = UndoJournal::default();
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+    for i in 0..MAX_UNDO_ENTRIES + 5 {
+        journal.record(&format!("b{}", i), bookmark("b"), now);
+    }
+    let mut popped = 0;
+    while journal.pop(now).is_some() {
+        popped += 1;
+    }
+    assert_eq!(popped, MAX_UNDO_ENTRIES);
+}
+
+#[test]
+fn restore_segment_reverses_remove() {
+    let mut segments = vec![
+        segment(0, "一。", true),
+        segment(1, "二。", true),
+        segment(2, "三。", false),
+    ];
+    let next_flag = segments.get(2).map(|s| s.is_new_paragraph);
+    let removed = remove_segment(&mut segments, 1).unwrap();
+    // 删除段首段落后下一段成为段首
+    assert!(segments[1].is_new_paragraph);
+
+    restore_segment(&mut segments, 1, removed, next_flag);
+    let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(texts, ["一。", "二。", "三。"]);
+    assert!(!segments[2].is_new_paragraph);
+    assert_eq!(segments[2].order, 2);
+
+    // 位置超出时追加到末尾
+    let removed = remove_segment(&mut segments, 0).unwrap();
+    restore_segment(&mut segments, 10, removed, None);
+    assert_eq!(segments.last().unwrap().text, "一。");
+}
@@ -0,0 +1,31 @@
+use openkoto_desktop_lib::vocabulary_images::{decode_data_url, image_extension, save_image};
+
+#[test]
+fn decodes_pasted_png_data_url() {
+    let (extension, bytes) = decode_data_url("data:image/png;base64,iVBORw0KGgo=").unwrap();
+    assert_eq!(extension, "png");
+    assert_eq!(&bytes[1..4], b"PNG");
+}
+
+#[test]
+fn rejects_unsupported_or_malformed_data_urls() {
+    assert!(decode_data_url("data:image/tiff;base64,AAAA").is_err());
+    assert!(decode_data_url("data:image/png,plain").is_err());
+    assert!(decode_data_url("https://example.com/a.png").is_err());
+    assert_eq!(image_extension("JPEG"), Some("jpg"));
+    assert_eq!(image_extension("bmp"), None);
+}
+
+#[test]
+fn saving_replaces_previous_image_of_the_word() {
+    let dir = std::env::temp_dir().join(format!("vocab-images-{}", std::process::id()));
+    let first = save_image(&dir, "v1", "png", b"first").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let second = save_image(&dir, "v1", "jpg", b"second").unwrap();
+
+    let images = dir.join("vocabulary_images");
+    assert!(!images.join(&first).exists());
+    assert_eq!(std::fs::read(images.join(&second)).unwrap(), b"second");
+    assert!(save_image(&dir, "v2", "png", b"").is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
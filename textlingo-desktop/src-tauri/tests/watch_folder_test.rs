@@ -0,0 +1,24 @@
+use openkoto_desktop_lib::folder_watcher::{watch_import_kind, WatchImportKind};
+use std::path::Path;
+
+#[test]
+fn picks_import_pipeline_by_extension() {
+    let kind = |name: &str| watch_import_kind(Path::new(name));
+    assert_eq!(kind("/sync/notes.md"), Some(WatchImportKind::Markdown));
+    assert_eq!(
+        kind("/sync/notes.markdown"),
+        Some(WatchImportKind::Markdown)
+    );
+    assert_eq!(kind("/sync/story.TXT"), Some(WatchImportKind::Text));
+    assert_eq!(kind("/sync/novel.epub"), Some(WatchImportKind::Book));
+    assert_eq!(kind("/sync/paper.pdf"), Some(WatchImportKind::Book));
+    assert_eq!(kind("/sync/lesson.mp4"), Some(WatchImportKind::Media));
+    assert_eq!(kind("/sync/photo.png"), None);
+}
+
+#[test]
+fn ignores_hidden_and_temporary_files() {
+    assert_eq!(watch_import_kind(Path::new("/sync/.notes.md")), None);
+    assert_eq!(watch_import_kind(Path::new("/sync/~$draft.txt")), None);
+    assert_eq!(watch_import_kind(Path::new("/sync/README")), None);
+}
@@ -0,0 +1,31 @@
+use openkoto_desktop_lib::waveform::{cache_file_name, peaks_per_second, PeakAccumulator};
+use std::path::Path;
+
+fn pcm(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+#[test]
+fn takes_the_loudest_sample_of_each_window() {
+    let mut accumulator = PeakAccumulator::new(3);
+    let bytes = pcm(&[100, -16384, 0, 32767, 5, i16::MIN, 8192]);
+    // 分块边界落在采样中间时也能正确拼接
+    accumulator.push(&bytes[..5]);
+    accumulator.push(&bytes[5..]);
+    assert_eq!(accumulator.total_samples(), 7);
+    assert_eq!(accumulator.finish(), vec![0.5, 1.0, 0.25]);
+}
+
+#[test]
+fn precision_is_clamped_and_cache_names_follow_the_file() {
+    assert_eq!(peaks_per_second(None), 50);
+    assert_eq!(peaks_per_second(Some(1)), 10);
+    assert_eq!(peaks_per_second(Some(1000)), 200);
+
+    let path = Path::new("/videos/lesson.mp4");
+    let name = cache_file_name(path, 1024, 1_700_000_000, 50);
+    assert!(name.ends_with(".json"));
+    assert_eq!(name, cache_file_name(path, 1024, 1_700_000_000, 50));
+    assert_ne!(name, cache_file_name(path, 2048, 1_700_000_000, 50));
+    assert_ne!(name, cache_file_name(path, 1024, 1_700_000_000, 100));
+}
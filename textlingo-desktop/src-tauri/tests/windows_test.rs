@@ -0,0 +1,64 @@
+use openkoto_desktop_lib::types::{WindowKind, WindowRoute};
+use openkoto_desktop_lib::windows::{window_label, window_url, WindowRegistry, MAIN_WINDOW};
+
+fn reader(article_id: &str) -> WindowRoute {
+    WindowRoute {
+        label: window_label(WindowKind::Reader, Some(article_id)),
+        kind: WindowKind::Reader,
+        article_id: Some(article_id.to_string()),
+    }
+}
+
+#[test]
+fn labels_and_urls_identify_the_window() {
+    assert_eq!(
+        window_label(WindowKind::Reader, Some("3f2a-9c")),
+        "reader-3f2a-9c"
+    );
+    // label 不允许的字符被替换
+    assert_eq!(
+        window_label(WindowKind::Reader, Some("a b/c")),
+        "reader-a_b_c"
+    );
+    assert_eq!(window_label(WindowKind::Review, Some("ignored")), "review");
+
+    assert_eq!(
+        window_url(&reader("a b")),
+        "index.html?window=reader&article=a%20b"
+    );
+    let stats = WindowRoute {
+        label: "stats".to_string(),
+        kind: WindowKind::Stats,
+        article_id: None,
+    };
+    assert_eq!(window_url(&stats), "index.html?window=stats");
+}
+
+#[test]
+fn article_events_reach_main_and_matching_windows() {
+    let registry = WindowRegistry::default();
+    let first = reader("a1");
+    registry.insert(first.clone());
+    registry.insert(WindowRoute {
+        label: "review".to_string(),
+        kind: WindowKind::Review,
+        article_id: None,
+    });
+
+    assert!(registry.receives(MAIN_WINDOW, "a1"));
+    assert!(registry.receives(&first.label, "a1"));
+    assert!(!registry.receives(&first.label, "a2"));
+    assert!(!registry.receives("review", "a1"));
+    // 未登记的窗口仍收到全部事件
+    assert!(registry.receives("unknown", "a2"));
+
+    // 阅读器窗口切换文章后只收到新文章的事件
+    assert!(registry.set_article(&first.label, Some("a2".to_string())));
+    assert!(registry.receives(&first.label, "a2"));
+    assert!(!registry.receives(&first.label, "a1"));
+    assert!(!registry.set_article("missing", None));
+
+    registry.remove("review");
+    let labels: Vec<String> = registry.list().into_iter().map(|r| r.label).collect();
+    assert_eq!(labels, [first.label]);
+}
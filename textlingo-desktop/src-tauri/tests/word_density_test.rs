@@ -0,0 +1,89 @@
+use openkoto_desktop_lib::types::ArticleSegment;
+use openkoto_desktop_lib::word_density::{
+    count_words, pre_reading_words, unknown_words, KnownWordSet, WordCounts,
+};
+
+fn segment(id: &str, text: &str) -> ArticleSegment {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "article_id": "a1",
+        "order": 0,
+        "text": text,
+        "reading_text": null,
+        "translation": null,
+        "explanation": null,
+        "created_at": "2026-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn counts_unknown_words_in_spaced_text() {
+    let known = KnownWordSet::new(["the", "cat", "is", "don't"]);
+    let counts = count_words("The cat is sleeping, don't wake it in 2024!", &known);
+    assert_eq!(
+        counts,
+        WordCounts {
+            tokens: 8,
+            unknown: 4
+        }
+    );
+    assert_eq!(counts.density(), 0.5);
+}
+
+#[test]
+fn matches_longest_known_words_in_cjk_text() {
+    let known = KnownWordSet::new(["勉強", "日本"]);
+    // 勉強 和 日本 为已知词，語 为生词，假名不计入
+    let counts = count_words("日本語を勉強します", &known);
+    assert_eq!(
+        counts,
+        WordCounts {
+            tokens: 3,
+            unknown: 1
+        }
+    );
+}
+
+#[test]
+fn empty_text_has_zero_density() {
+    let known = KnownWordSet::new(Vec::<String>::new());
+    assert!(known.is_empty());
+    assert_eq!(count_words("……", &known).density(), 0.0);
+}
+
+#[test]
+fn unknown_words_group_adjacent_kanji() {
+    let known = KnownWordSet::new(["日本", "の"]);
+    assert_eq!(unknown_words("日本の経済を学ぶ", &known), ["経済", "学"]);
+    let known = KnownWordSet::new(["the"]);
+    assert_eq!(
+        unknown_words("The Economy grew", &known),
+        ["economy", "grew"]
+    );
+}
+
+#[test]
+fn pre_reading_words_rank_by_frequency() {
+    let known = KnownWordSet::new(["the", "is", "a"]);
+    let segments = [
+        segment("s1", "The ledger is a record. "),
+        segment("s2", "A ledger grows, x marks it."),
+    ];
+    let words = pre_reading_words(&segments, &known);
+    let listed: Vec<(&str, usize)> = words.iter().map(|w| (w.word.as_str(), w.count)).collect();
+    // 次数相同按首次出现顺序；单个字母不列出
+    assert_eq!(
+        listed,
+        [
+            ("ledger", 2),
+            ("record", 1),
+            ("grows", 1),
+            ("marks", 1),
+            ("it", 1)
+        ]
+    );
+    assert_eq!(words[0].segment_id, "s1");
+    assert_eq!(words[0].context, "The ledger is a record.");
+    assert!(words[0].gloss.is_none());
+}
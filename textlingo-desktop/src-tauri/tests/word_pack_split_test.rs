@@ -0,0 +1,61 @@
+use openkoto_desktop_lib::commands::group_pack_vocabularies;
+use openkoto_desktop_lib::types::{FavoriteVocabulary, PackSplitKey};
+
+fn make_vocab(id: &str, state: &str, packs: &[&str], tags: &[&str]) -> FavoriteVocabulary {
+    FavoriteVocabulary {
+        id: id.to_string(),
+        word: id.to_string(),
+        meaning: "m".to_string(),
+        usage: "u".to_string(),
+        explanation: None,
+        example: None,
+        reading: None,
+        phonetic: None,
+        source_article_id: None,
+        source_article_title: None,
+        pack_ids: packs.iter().map(|p| p.to_string()).collect(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        image_path: None,
+        language: None,
+        srs_state: state.to_string(),
+        due_date: "2026-01-01".to_string(),
+        interval_days: 0,
+        ease_factor: 2.5,
+        repetitions: 0,
+        last_reviewed_at: None,
+        review_count: 0,
+        lapses: 0,
+        corpus_count: 0,
+        corpus_articles: 0,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn groups_by_first_tag_and_leaves_untagged_words() {
+    let favorites = vec![
+        make_vocab("a", "new", &["p1"], &["N5", "verb"]),
+        make_vocab("b", "new", &["p1"], &["N4"]),
+        make_vocab("c", "new", &["p1"], &[]),
+        make_vocab("d", "new", &["p2"], &["N5"]),
+        make_vocab("e", "new", &["p1", "p2"], &[" ", "N5"]),
+    ];
+
+    let groups = group_pack_vocabularies(&favorites, "p1", PackSplitKey::Tag);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["N5"], vec!["a".to_string(), "e".to_string()]);
+    assert_eq!(groups["N4"], vec!["b".to_string()]);
+}
+
+#[test]
+fn groups_by_srs_level() {
+    let favorites = vec![
+        make_vocab("a", "new", &["p1"], &[]),
+        make_vocab("b", "review", &["p1"], &[]),
+        make_vocab("c", "new", &["p1"], &[]),
+    ];
+
+    let groups = group_pack_vocabularies(&favorites, "p1", PackSplitKey::Level);
+    assert_eq!(groups["new"], vec!["a".to_string(), "c".to_string()]);
+    assert_eq!(groups["review"], vec!["b".to_string()]);
+}
@@ -15,6 +15,7 @@ fn word_pack_roundtrip_serialization() {
         created_at: "2026-02-16T00:00:00Z".to_string(),
         updated_at: "2026-02-16T00:00:00Z".to_string(),
         is_system: false,
+        daily_new_limit: None,
     };
 
     let json = serde_json::to_string(&pack).unwrap();
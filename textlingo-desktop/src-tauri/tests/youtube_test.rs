@@ -0,0 +1,242 @@
+use openkoto_desktop_lib::types::{
+    AppConfig, SubtitleTrackKind, YoutubeQuality, YoutubeSubtitleOptions,
+};
+use openkoto_desktop_lib::youtube::{
+    channel_videos_url, cookie_args, generic_quality_args, parse_channel_videos,
+    parse_download_progress, parse_media_info, parse_subtitle_tracks, quality_args, subtitle_args,
+    thumbnail_url,
+};
+use serde_json::json;
+
+#[test]
+fn lists_manual_tracks_before_auto_captions() {
+    let info = json!({
+        "id": "abc",
+        "subtitles": {
+            "ja": [{"ext": "vtt", "name": "Japanese"}],
+            "en": [{"ext": "vtt", "name": "English"}],
+            "live_chat": [{"ext": "json"}],
+        },
+        "automatic_captions": {
+            "en": [{"ext": "vtt", "name": "English (auto-generated)"}],
+            "fr": [],
+        },
+    });
+    let tracks = parse_subtitle_tracks(&info);
+    let listed: Vec<(&str, SubtitleTrackKind)> = tracks
+        .iter()
+        .map(|t| (t.language.as_str(), t.kind))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("en", SubtitleTrackKind::Manual),
+            ("ja", SubtitleTrackKind::Manual),
+            ("en", SubtitleTrackKind::Auto),
+        ]
+    );
+    assert_eq!(tracks[2].name.as_deref(), Some("English (auto-generated)"));
+
+    assert!(parse_subtitle_tracks(&json!({"id": "abc"})).is_empty());
+}
+
+#[test]
+fn builds_subtitle_arguments_from_options() {
+    // 默认沿用原来的英文、中文自动字幕
+    assert_eq!(
+        subtitle_args(&YoutubeSubtitleOptions::default()),
+        [
+            "--write-auto-subs",
+            "--sub-lang",
+            "en,zh-Hans,zh-Hant",
+            "--convert-subs",
+            "srt"
+        ]
+    );
+
+    let options = YoutubeSubtitleOptions {
+        languages: vec!["ja".to_string(), " ".to_string()],
+        manual: true,
+        auto: false,
+    };
+    assert_eq!(
+        subtitle_args(&options),
+        ["--write-subs", "--sub-lang", "ja", "--convert-subs", "srt"]
+    );
+
+    let none = YoutubeSubtitleOptions {
+        languages: vec!["ja".to_string()],
+        manual: false,
+        auto: false,
+    };
+    assert!(subtitle_args(&none).is_empty());
+}
+
+#[test]
+fn channel_urls_point_at_the_videos_tab() {
+    assert_eq!(
+        channel_videos_url("https://www.youtube.com/@NHKWORLDJAPAN/"),
+        "https://www.youtube.com/@NHKWORLDJAPAN/videos"
+    );
+    assert_eq!(
+        channel_videos_url("https://www.youtube.com/channel/UC123/streams"),
+        "https://www.youtube.com/channel/UC123/streams"
+    );
+    // 播放列表等非频道地址保持不变
+    assert_eq!(
+        channel_videos_url("https://www.youtube.com/playlist?list=PL1"),
+        "https://www.youtube.com/playlist?list=PL1"
+    );
+}
+
+#[test]
+fn parses_flat_channel_listing() {
+    let info = json!({
+        "title": "NHK - Videos",
+        "channel": "NHK",
+        "entries": [
+            {"_type": "url", "id": "vid00000001", "title": "Latest",
+             "url": "https://www.youtube.com/watch?v=vid00000001"},
+            {"_type": "playlist", "id": "UC123", "title": "Shorts"},
+            {"_type": "url", "id": "vid00000002"},
+        ],
+    });
+    let (title, videos) = parse_channel_videos(&info);
+    assert_eq!(title.as_deref(), Some("NHK"));
+    let ids: Vec<&str> = videos.iter().map(|v| v.id.as_str()).collect();
+    assert_eq!(ids, ["vid00000001", "vid00000002"]);
+    // 缺少地址和标题时由 ID 补齐
+    assert_eq!(videos[1].url, "https://www.youtube.com/watch?v=vid00000002");
+    assert_eq!(videos[1].title, "vid00000002");
+
+    let (title, videos) = parse_channel_videos(&json!({"title": "Talks - Videos"}));
+    assert_eq!(title.as_deref(), Some("Talks"));
+    assert!(videos.is_empty());
+}
+
+#[test]
+fn parses_download_progress_lines() {
+    let progress =
+        parse_download_progress("openkoto-progress 5242880 20971520 NA 1048576.5 15").unwrap();
+    assert_eq!(progress.downloaded_bytes, 5242880);
+    assert_eq!(progress.total_bytes, Some(20971520));
+    assert_eq!(progress.percent, Some(25.0));
+    assert_eq!(progress.speed, Some(1048576.5));
+    assert_eq!(progress.eta, Some(15));
+
+    // 总大小未知时用估计值，速度和剩余时间可能缺失
+    let estimated = parse_download_progress("openkoto-progress 1000 NA 4000.0 NA NA").unwrap();
+    assert_eq!(estimated.total_bytes, Some(4000));
+    assert_eq!(estimated.percent, Some(25.0));
+    assert_eq!(estimated.speed, None);
+    assert_eq!(estimated.eta, None);
+
+    let unknown = parse_download_progress("openkoto-progress 1000 NA NA NA NA").unwrap();
+    assert_eq!(unknown.percent, None);
+
+    assert!(parse_download_progress("[download] Destination: videos/abc.mp4").is_none());
+    assert!(parse_download_progress("{\"id\": \"abc\"}").is_none());
+}
+
+#[test]
+fn quality_selects_the_yt_dlp_format() {
+    let format = |quality| quality_args(quality)[1];
+    assert!(format(YoutubeQuality::P360).starts_with("18/"));
+    assert!(format(YoutubeQuality::P720).starts_with("22/18/"));
+    assert!(format(YoutubeQuality::P1080).contains("height<=1080"));
+    assert!(format(YoutubeQuality::AudioOnly).starts_with("140/"));
+    // 只下载音频时不重新封装为视频
+    assert!(!quality_args(YoutubeQuality::AudioOnly).contains(&"--remux-video"));
+    let mp3 = quality_args(YoutubeQuality::AudioMp3);
+    assert!(mp3.contains(&"--extract-audio"));
+    assert_eq!(mp3.last(), Some(&"mp3"));
+    assert!(YoutubeQuality::AudioMp3.is_audio_only());
+    assert!(!YoutubeQuality::P1080.is_audio_only());
+
+    assert_eq!(YoutubeQuality::default(), YoutubeQuality::P720);
+    let parsed: Vec<YoutubeQuality> =
+        serde_json::from_str(r#"["360p", "1080p", "audio_only"]"#).unwrap();
+    assert_eq!(
+        parsed,
+        [
+            YoutubeQuality::P360,
+            YoutubeQuality::P1080,
+            YoutubeQuality::AudioOnly
+        ]
+    );
+}
+
+#[test]
+fn cookie_args_prefer_the_cookies_file() {
+    let mut config = AppConfig::default();
+    assert!(cookie_args(&config).is_empty());
+
+    config.ytdlp_cookies_from_browser = Some(" chrome:Profile 1 ".to_string());
+    assert_eq!(
+        cookie_args(&config),
+        ["--cookies-from-browser", "chrome:Profile 1"]
+    );
+
+    config.ytdlp_cookies_file = Some("/home/me/cookies.txt".to_string());
+    assert_eq!(cookie_args(&config), ["--cookies", "/home/me/cookies.txt"]);
+
+    // 空字符串视为未设置
+    config.ytdlp_cookies_file = Some("  ".to_string());
+    assert_eq!(cookie_args(&config)[0], "--cookies-from-browser");
+}
+
+#[test]
+fn generic_sites_select_formats_by_height() {
+    // 其他网站没有 YouTube 的固定格式编号
+    for quality in [
+        YoutubeQuality::P360,
+        YoutubeQuality::P720,
+        YoutubeQuality::P1080,
+        YoutubeQuality::AudioOnly,
+    ] {
+        let format = generic_quality_args(quality)[1];
+        assert!(
+            !format.starts_with("22/") && !format.starts_with("18/") && !format.starts_with("140/")
+        );
+    }
+    assert!(generic_quality_args(YoutubeQuality::P720)[1].contains("height<=720"));
+    assert!(generic_quality_args(YoutubeQuality::P360)[1].ends_with("/worst"));
+
+    let audio = generic_quality_args(YoutubeQuality::AudioOnly);
+    assert!(audio.contains(&"--extract-audio"));
+    assert_eq!(audio.last(), Some(&"m4a"));
+}
+
+#[test]
+fn parses_media_metadata() {
+    let info = json!({
+        "id": "abc",
+        "title": "NHK News",
+        "uploader": "NHK",
+        "channel": "NHK WORLD",
+        "duration": 754.0,
+        "upload_date": "20240115",
+        "language": "ja",
+        "thumbnail": "https://i.ytimg.com/vi/abc/maxresdefault.webp"
+    });
+    let media = parse_media_info(&info);
+    assert_eq!(media.uploader.as_deref(), Some("NHK"));
+    assert_eq!(media.duration, Some(754.0));
+    assert_eq!(media.upload_date.as_deref(), Some("2024-01-15"));
+    assert_eq!(media.original_language.as_deref(), Some("ja"));
+    // 缩略图下载后另行填写
+    assert_eq!(media.thumbnail_path, None);
+
+    // 其他网站可能缺少这些字段
+    let sparse = parse_media_info(
+        &json!({"id": "1", "title": "t", "channel": "Vimeo Staff", "duration": 0}),
+    );
+    assert_eq!(sparse.uploader.as_deref(), Some("Vimeo Staff"));
+    assert_eq!(sparse.duration, None);
+    assert_eq!(sparse.upload_date, None);
+
+    assert_eq!(
+        thumbnail_url("a b.webp"),
+        "http://127.0.0.1:19420/thumbnail/a%20b.webp"
+    );
+}
@@ -15,6 +15,7 @@ import {
     QuickAction
 } from "../../lib/api";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { listen } from "@tauri-apps/api/event";
 
 // Simple Card component since we don't have one in UI
@@ -417,25 +418,25 @@ export function ArticleChatAssistant({
                         });
                     }
 
-                } catch (error: any) {
+                } catch (error) {
                     console.error('Local chat failed:', error);
                     setMessages(prev => {
                         const newMessages = [...prev];
                         const lastMessage = newMessages[newMessages.length - 1];
                         if (lastMessage.role === 'assistant') {
-                            lastMessage.content += `\n[Error: ${error.message || error}]`;
+                            lastMessage.content += `\n[Error: ${errorMessage(error)}]`;
                             lastMessage.isStreaming = false;
                         }
                         return newMessages;
                     });
                 }
             }
-        } catch (error: any) {
+        } catch (error) {
             setMessages(prev => {
                 const newMessages = [...prev];
                 const lastMessage = newMessages[newMessages.length - 1];
                 if (lastMessage.role === 'assistant') {
-                    lastMessage.content += `\n[Error: ${error.message}]`;
+                    lastMessage.content += `\n[Error: ${errorMessage(error)}]`;
                     lastMessage.isStreaming = false;
                 }
                 return newMessages;
@@ -510,7 +511,7 @@ export function ArticleChatAssistant({
                     }
                     return newMessages;
                 });
-            } catch (error: any) {
+            } catch (error) {
                 console.error("Translation failed:", error);
                 setMessages(prev => {
                     const newMessages = [...prev];
@@ -518,7 +519,7 @@ export function ArticleChatAssistant({
                     if (idx !== -1) {
                         newMessages[idx] = {
                             ...newMessages[idx],
-                            content: `Translation failed: ${error.message || error}`,
+                            content: `Translation failed: ${errorMessage(error)}`,
                             isStreaming: false
                         };
                     }
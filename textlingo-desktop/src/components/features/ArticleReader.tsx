@@ -1,5 +1,6 @@
 import React, { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage, isCancelled, isRetryable } from "../../lib/errors";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
 import { save } from "@tauri-apps/plugin-dialog";
 import { Button } from "../ui/button";
@@ -224,7 +225,7 @@ export function ArticleReader({
       setIsEditing(false);
       onUpdate?.();
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
     }
   };
 
@@ -250,7 +251,9 @@ export function ArticleReader({
       // 通知父组件刷新 articles 数组，确保离开页面后再回来数据不丢失
       onUpdate?.();
     } catch (err) {
-      setError(err as string);
+      if (!isCancelled(err)) {
+        setError(isRetryable(err) ? `${errorMessage(err)} ${t("common.retryHint")}` : errorMessage(err));
+      }
     } finally {
       setIsTranslating(false);
     }
@@ -267,7 +270,9 @@ export function ArticleReader({
       });
       setAnalysisResult(result);
     } catch (err) {
-      setError(err as string);
+      if (!isCancelled(err)) {
+        setError(isRetryable(err) ? `${errorMessage(err)} ${t("common.retryHint")}` : errorMessage(err));
+      }
     } finally {
       setIsAnalyzing(false);
     }
@@ -336,7 +341,9 @@ export function ArticleReader({
       await refreshArticle();
     } catch (err) {
       console.error("[ArticleReader] Subtitle extraction failed:", err);
-      setError(t("subtitleExtraction.error") + ": " + String(err));
+      if (!isCancelled(err)) {
+        setError(t("subtitleExtraction.error") + ": " + errorMessage(err));
+      }
     } finally {
       setIsExtractingSubtitles(false);
     }
@@ -355,7 +362,7 @@ export function ArticleReader({
       await invoke("resegment_article", { articleId: article.id });
       onUpdate?.();
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsResegmenting(false);
     }
@@ -537,9 +544,9 @@ export function ArticleReader({
         throw new Error("No explanation returned from AI service");
       }
 
-    } catch (e: any) {
+    } catch (e) {
       console.error("[ArticleReader] Failed to generate explanation", e);
-      setError(typeof e === 'string' ? e : (e.message || "Failed to generate explanation. Check AI Model config."));
+      setError(errorMessage(e) || "Failed to generate explanation. Check AI Model config.");
     } finally {
       setIsGeneratingExplanation(false);
     }
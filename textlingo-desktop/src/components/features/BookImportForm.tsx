@@ -5,6 +5,7 @@
 
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { open } from "@tauri-apps/plugin-dialog";
 import { useTranslation } from "react-i18next";
 import { Button } from "../ui/button";
@@ -79,7 +80,7 @@ export function BookImportForm({ onSave, onCancel }: BookImportFormProps) {
             onSave?.(article);
         } catch (e) {
             console.error("导入书籍失败:", e);
-            setError(String(e));
+            setError(errorMessage(e));
         } finally {
             setIsImporting(false);
         }
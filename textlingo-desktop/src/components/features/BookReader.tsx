@@ -6,6 +6,7 @@
 import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { save } from "@tauri-apps/plugin-dialog";
 import { Tabs, TabsList, TabsTrigger, TabsContent } from "../ui/tabs";
 import { Button } from "../ui/button";
@@ -254,7 +255,7 @@ export function BookReader({ article, onBack }: BookReaderProps) {
             }
         } catch (error) {
             console.error("[PDF Translate] Error:", error);
-            const errorStr = String(error);
+            const errorStr = errorMessage(error);
             // 插件未找到 / 执行失败 → 引导用户安装
             if (errorStr.includes("Plugin") || errorStr.includes("not found") || errorStr.includes("Executable")) {
                 setShowPluginInstallDialog(true);
@@ -1,5 +1,6 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { open } from "@tauri-apps/plugin-dialog";
 import { useTranslation } from "react-i18next";
 import { Button } from "../ui/button";
@@ -53,7 +54,7 @@ export function LocalVideoImportForm({ onSave, onCancel }: LocalVideoImportFormP
             onSave?.(article);
         } catch (err) {
             console.error("Local import failed:", err);
-            setError(String(err));
+            setError(errorMessage(err));
         } finally {
             setIsImporting(false);
         }
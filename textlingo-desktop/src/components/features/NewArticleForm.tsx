@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { useTranslation } from "react-i18next";
 import { Button } from "../ui/button";
 import { Input } from "../ui/input";
@@ -66,7 +67,7 @@ export function NewArticleForm({ onSave, onCancel, initialArticle }: NewArticleF
             }
             onSave?.(article);
         } catch (err) {
-            setError(err as string);
+            setError(errorMessage(err));
         } finally {
             setIsSaving(false);
         }
@@ -132,7 +133,7 @@ export function NewArticleForm({ onSave, onCancel, initialArticle }: NewArticleF
                 setError(t("newArticle.errors.noContentExtracted"));
             }
         } catch (err) {
-            setError(err as string);
+            setError(errorMessage(err));
         } finally {
             setIsFetching(false);
         }
@@ -160,7 +161,7 @@ export function NewArticleForm({ onSave, onCancel, initialArticle }: NewArticleF
                         setError(t("newArticle.errors.noContentExtracted"));
                     }
                 } catch (err) {
-                    setError(err as string);
+                    setError(errorMessage(err));
                 } finally {
                     setIsFetching(false);
                 }
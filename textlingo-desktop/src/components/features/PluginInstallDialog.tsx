@@ -1,5 +1,6 @@
 import { useEffect, useState, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage } from '../../lib/errors';
 import { listen } from '@tauri-apps/api/event';
 import { useTranslation } from 'react-i18next';
 import { Dialog, DialogFooter } from '../ui/dialog';
@@ -107,7 +108,7 @@ export function PluginInstallDialog({ isOpen, onClose, onInstallComplete }: Plug
             setReleaseInfo(info);
             setState('ready');
         } catch (err) {
-            setError(String(err));
+            setError(errorMessage(err));
             setState('error');
         }
     };
@@ -125,7 +126,7 @@ export function PluginInstallDialog({ isOpen, onClose, onInstallComplete }: Plug
                 pluginName
             });
         } catch (err) {
-            setError(String(err));
+            setError(errorMessage(err));
             setState('error');
         }
     };
@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorKind, errorMessage } from "../../lib/errors";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import { useTranslation } from "react-i18next";
 import { Dialog, DialogContent, DialogFooter } from "../ui/dialog";
@@ -165,8 +166,8 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
         }
       }
     } catch (err) {
-      const errorMsg = err as string;
-      if (errorMsg.includes("FATAL_CONFIG_CORRUPTION")) {
+      const errorMsg = errorMessage(err);
+      if (errorKind(err) === "config" && errorMsg.includes("FATAL_CONFIG_CORRUPTION")) {
         setIsCorrupted(true);
         setError(t("settings.errors.configCorrupted"));
       } else {
@@ -272,7 +273,7 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
       onSave?.();
       cancelEdit();
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsSaving(false);
     }
@@ -293,7 +294,7 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
         model_configs: config.model_configs.filter(c => c.id !== configId),
       });
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsSaving(false);
     }
@@ -306,7 +307,7 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
       const active = await invoke<ModelConfig>("set_active_model_config", { configId });
       setConfig({ ...config, active_model_id: active.id });
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsSaving(false);
     }
@@ -498,7 +499,7 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
 
     } catch (err) {
       if (!isAuto) {
-        setSyncError(`${t("settings.syncErrors.syncFailed")}: ${err}`);
+        setSyncError(`${t("settings.syncErrors.syncFailed")}: ${errorMessage(err)}`);
       }
       console.error("Model sync failed:", err);
     } finally {
@@ -986,7 +987,7 @@ export function SettingsDialog({ isOpen, onClose, onSave }: SettingsDialogProps)
             await invoke("save_config_cmd", { config });
             onSave?.();
           } catch (err) {
-            setError(err as string);
+            setError(errorMessage(err));
           } finally {
             setIsSaving(false);
           }
@@ -1,5 +1,6 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { useTranslation } from "react-i18next";
 import { Button } from "../ui/button";
 import { Input } from "../ui/input";
@@ -81,7 +82,7 @@ export function WebImportForm({ onSave, onCancel }: WebImportFormProps) {
         setError(t("webImport.errors.contentTooShort"));
       }
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setIsFetching(false);
     }
@@ -104,7 +105,7 @@ export function WebImportForm({ onSave, onCancel }: WebImportFormProps) {
       });
       onSave?.(article);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setIsImporting(false);
     }
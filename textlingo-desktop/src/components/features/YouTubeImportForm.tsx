@@ -1,5 +1,6 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../../lib/errors";
 import { useTranslation } from "react-i18next";
 import { Button } from "../ui/button";
 import { Input } from "../ui/input";
@@ -36,7 +37,7 @@ export function YouTubeImportForm({ onSave, onCancel }: YouTubeImportFormProps)
             onSave?.(article);
         } catch (err) {
             console.error("YouTube import failed:", err);
-            setError(String(err)); // Show exact error from backend
+            setError(errorMessage(err)); // Show exact error from backend
         } finally {
             setIsImporting(false);
         }
@@ -0,0 +1,54 @@
+// Error type returned by every Tauri command (mirrors src-tauri/src/error.rs)
+
+export type ErrorKind =
+  | "missing_api_key"
+  | "network"
+  | "rate_limited"
+  | "ai_provider"
+  | "not_found"
+  | "invalid_input"
+  | "config"
+  | "storage"
+  | "io"
+  | "external_tool"
+  | "cancelled"
+  | "offline"
+  | "internal";
+
+export interface AppError {
+  kind: ErrorKind;
+  message: string;
+  retryable: boolean;
+  details?: unknown;
+}
+
+export function isAppError(err: unknown): err is AppError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    typeof (err as AppError).kind === "string" &&
+    typeof (err as AppError).message === "string"
+  );
+}
+
+/** Human-readable message for a rejected invoke, a thrown Error or a plain string */
+export function errorMessage(err: unknown): string {
+  if (isAppError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  if (typeof err === "string") return err;
+  return String(err);
+}
+
+export function errorKind(err: unknown): ErrorKind | null {
+  return isAppError(err) ? err.kind : null;
+}
+
+/** The user cancelled the job, so there is nothing to report */
+export function isCancelled(err: unknown): boolean {
+  return errorKind(err) === "cancelled";
+}
+
+/** Transient failure (network, rate limit, provider hiccup) that may succeed on retry */
+export function isRetryable(err: unknown): boolean {
+  return isAppError(err) && err.retryable;
+}
@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { AppConfig } from "../tauri";
+import { errorMessage } from "../errors";
 
 type LoadType = "init" | "refresh";
 
@@ -18,7 +19,7 @@ export function useConfig() {
       return appConfig;
     } catch (err) {
       console.error(`[useConfig] Failed to ${type === "init" ? "load" : "refresh"} config:`, err);
-      setError(err as string);
+      setError(errorMessage(err));
       throw err;
     } finally {
       setLoading(false);